@@ -0,0 +1,190 @@
+//! Achievement unlocks behind a platform-agnostic sink.
+//!
+//! Game code only talks to [`AchievementSink`]. [`LocalAchievements`] keeps
+//! unlocks in a JSON file; storefront backends (Steam, itch) can implement
+//! the same trait later.
+
+use common::{GameError, GameResult, Storage};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// An achievement the game can award.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Achievement {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+const fn achievement(
+    id: &'static str,
+    name: &'static str,
+    description: &'static str,
+) -> Achievement {
+    Achievement {
+        id,
+        name,
+        description,
+    }
+}
+
+/// Every achievement in the order shown on the achievements screen.
+pub const ACHIEVEMENTS: [Achievement; 14] = [
+    achievement("first_catch", "First Catch", "Land your first fish."),
+    achievement("every_species", "Complete Angler", "Catch every species."),
+    achievement("legendary", "Stuff of Legend", "Land a legendary fish."),
+    achievement(
+        "legend_hunter",
+        "Legend Hunter",
+        "Land every legendary fish.",
+    ),
+    achievement(
+        "storm_survivor",
+        "Eye of the Storm",
+        "Ride out a storm with 1 HP.",
+    ),
+    achievement(
+        "codex_coast_25",
+        "Tidepool Novice",
+        "Coast codex 25% complete.",
+    ),
+    achievement(
+        "codex_coast_50",
+        "Coast Collector",
+        "Coast codex 50% complete.",
+    ),
+    achievement("codex_coast_100", "Shore Warden", "Coast codex complete."),
+    achievement(
+        "codex_offshore_25",
+        "Reef Novice",
+        "Offshore codex 25% complete.",
+    ),
+    achievement(
+        "codex_offshore_50",
+        "Reef Collector",
+        "Offshore codex 50% complete.",
+    ),
+    achievement(
+        "codex_offshore_100",
+        "Reef Ranger",
+        "Offshore codex complete.",
+    ),
+    achievement(
+        "codex_deep_sea_25",
+        "Abyss Novice",
+        "Deep Sea codex 25% complete.",
+    ),
+    achievement(
+        "codex_deep_sea_50",
+        "Abyss Collector",
+        "Deep Sea codex 50% complete.",
+    ),
+    achievement(
+        "codex_deep_sea_100",
+        "Abyss Sage",
+        "Deep Sea codex complete.",
+    ),
+];
+
+/// The achievement with `id`, if it is in [`ACHIEVEMENTS`].
+pub fn find(id: &str) -> Option<&'static Achievement> {
+    ACHIEVEMENTS.iter().find(|a| a.id == id)
+}
+
+/// Receives achievement unlocks from the game.
+pub trait AchievementSink {
+    /// Marks `id` as unlocked. Returns `true` if it was not unlocked before.
+    fn unlock(&mut self, id: &str) -> GameResult<bool>;
+
+    /// Whether `id` has been unlocked.
+    fn is_unlocked(&self, id: &str) -> bool;
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct UnlockFile {
+    unlocked: BTreeSet<String>,
+}
+
+/// Sink persisting unlocked ids to a JSON file through [`Storage`].
+pub struct LocalAchievements {
+    storage: Box<dyn Storage>,
+    key: String,
+    file: UnlockFile,
+}
+
+impl LocalAchievements {
+    /// Loads the unlocks stored under `key`, starting empty if missing.
+    pub fn load(storage: Box<dyn Storage>, key: &str) -> GameResult<Self> {
+        let file = match storage.read_string(key)? {
+            Some(data) => serde_json::from_str(&data)
+                .map_err(|e| GameError::Parse(format!("achievements: {}", e)))?,
+            None => UnlockFile::default(),
+        };
+        Ok(Self {
+            storage,
+            key: key.to_string(),
+            file,
+        })
+    }
+
+    /// Unlocked ids in sorted order.
+    pub fn unlocked(&self) -> impl Iterator<Item = &str> {
+        self.file.unlocked.iter().map(String::as_str)
+    }
+
+    fn save(&self) -> GameResult<()> {
+        let out = serde_json::to_string_pretty(&self.file)
+            .map_err(|e| GameError::Parse(format!("achievements: {}", e)))?;
+        self.storage.write(&self.key, out.as_bytes())
+    }
+}
+
+impl AchievementSink for LocalAchievements {
+    fn unlock(&mut self, id: &str) -> GameResult<bool> {
+        if !self.file.unlocked.insert(id.to_string()) {
+            return Ok(false);
+        }
+        self.save()?;
+        Ok(true)
+    }
+
+    fn is_unlocked(&self, id: &str) -> bool {
+        self.file.unlocked.contains(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::MemoryStorage;
+    use std::rc::Rc;
+
+    #[test]
+    fn unlock_persists_once() {
+        let storage: Rc<dyn Storage> = Rc::new(MemoryStorage::default());
+        let mut local = LocalAchievements::load(Box::new(storage.clone()), "ach.json").unwrap();
+        assert!(local.unlock("first_catch").unwrap());
+        assert!(!local.unlock("first_catch").unwrap());
+        assert!(local.is_unlocked("first_catch"));
+
+        let reloaded = LocalAchievements::load(Box::new(storage), "ach.json").unwrap();
+        assert_eq!(reloaded.unlocked().collect::<Vec<_>>(), vec!["first_catch"]);
+    }
+
+    #[test]
+    fn achievement_ids_are_unique() {
+        let ids: BTreeSet<_> = ACHIEVEMENTS.iter().map(|a| a.id).collect();
+        assert_eq!(ids.len(), ACHIEVEMENTS.len());
+        assert_eq!(find("first_catch").unwrap().name, "First Catch");
+        assert!(find("missing").is_none());
+    }
+
+    #[test]
+    fn corrupt_file_is_a_parse_error() {
+        let storage = MemoryStorage::default();
+        storage.write("ach.json", b"not json").unwrap();
+        let result = LocalAchievements::load(Box::new(storage), "ach.json");
+        assert!(matches!(result, Err(GameError::Parse(_))));
+    }
+}
@@ -1,6 +1,21 @@
-//! Simple audio playback utilities.
+//! Sound effects and music. Each [`Sound`] plays a clip from
+//! `assets/sounds/`, named after the sound (`catch.ogg` or `catch.wav`),
+//! at the effects volume scaled by the sound's own volume from
+//! `sounds.json` there. Under them loops the track for the current
+//! [`MusicState`] from `assets/sounds/music/`, at its own volume.
+//!
+//! With the `rodio` feature the clips go out to the default audio device,
+//! which mixes sounds that overlap. Without it, or without a device, the
+//! game plays silently.
 
-use common::GameResult;
+pub mod music;
+mod output;
+
+use common::{EventListener, GameError, GameEvent, GameResult};
+pub use music::{Music, MusicState};
+use output::Output;
+use std::collections::HashMap;
+use std::path::Path;
 
 /// Supported sound effect kinds.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -11,34 +26,211 @@ pub enum Sound {
     Storm,
 }
 
-/// Basic audio manager storing volume level.
+impl Sound {
+    pub const ALL: [Sound; 4] = [Sound::Hit, Sound::LineSnap, Sound::Catch, Sound::Storm];
+
+    /// Name of the sound's clip file and its key in `sounds.json`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Sound::Hit => "hit",
+            Sound::LineSnap => "line_snap",
+            Sound::Catch => "catch",
+            Sound::Storm => "storm",
+        }
+    }
+}
+
+/// Clip file extensions, in the order they are looked for.
+const CLIP_EXTENSIONS: [&str; 2] = ["ogg", "wav"];
+/// File in the sounds directory giving each sound its volume (0-10).
+const VOLUMES_FILE: &str = "sounds.json";
+/// Directory of the music tracks, inside the sounds directory.
+const MUSIC_DIR: &str = "music";
+
+/// The first clip for `name` in `dir`, if there is one.
+fn read_clip(dir: &Path, name: &str) -> GameResult<Option<Vec<u8>>> {
+    let file = CLIP_EXTENSIONS
+        .iter()
+        .map(|ext| dir.join(format!("{}.{}", name, ext)))
+        .find(|file| file.exists());
+    Ok(match file {
+        Some(file) => Some(std::fs::read(file)?),
+        None => None,
+    })
+}
+
+/// Audio manager holding the effect and music volume levels.
 #[derive(Debug)]
 pub struct AudioManager {
-    volume: u8,
+    sfx_volume: u8,
+    /// Volume of each sound, 0-10; sounds not listed play at 10.
+    levels: Vec<(Sound, u8)>,
+    music_volume: u8,
+    /// Silences effects and music alike, keeping their volumes.
+    muted: bool,
+    music: Music,
+    output: Output,
 }
 
 impl AudioManager {
-    /// Creates a new manager with the given volume (0-10).
+    /// Creates a new manager with the given effects volume (0-10) and no
+    /// clips. Music starts at the same volume.
     pub fn new(volume: u8) -> Self {
         Self {
-            volume: volume.min(10),
+            sfx_volume: volume.min(10),
+            levels: Vec::new(),
+            music_volume: volume.min(10),
+            muted: false,
+            music: Music::default(),
+            output: Output::silent(),
+        }
+    }
+
+    /// Creates a manager playing the clips in `dir` at the volumes in its
+    /// `sounds.json`, and the tracks in `dir/music`. Sounds and states
+    /// without a clip stay silent. Clips that fail to decode or an unknown
+    /// sound in `sounds.json` are errors.
+    pub fn load(volume: u8, dir: impl AsRef<Path>) -> GameResult<Self> {
+        let dir = dir.as_ref();
+        let mut clips = Vec::new();
+        for sound in Sound::ALL {
+            if let Some(clip) = read_clip(dir, sound.name())? {
+                clips.push((sound, clip));
+            }
+        }
+        let mut tracks = Vec::new();
+        for state in MusicState::ALL {
+            if let Some(clip) = read_clip(&dir.join(MUSIC_DIR), state.name())? {
+                tracks.push((state, clip));
+            }
+        }
+        let mut manager = Self {
+            output: Output::open(clips, tracks)?,
+            ..Self::new(volume)
+        };
+        let volumes = dir.join(VOLUMES_FILE);
+        if volumes.exists() {
+            let text = std::fs::read_to_string(volumes)?;
+            let levels: HashMap<String, u8> =
+                serde_json::from_str(&text).map_err(|e| GameError::Parse(e.to_string()))?;
+            for (name, level) in levels {
+                let sound = Sound::ALL
+                    .into_iter()
+                    .find(|s| s.name() == name)
+                    .ok_or_else(|| GameError::Parse(format!("unknown sound: {}", name)))?;
+                manager.set_sound_volume(sound, level);
+            }
         }
+        Ok(manager)
+    }
+
+    /// Sets the sound effects volume (0-10).
+    pub fn set_sfx_volume(&mut self, volume: u8) {
+        self.sfx_volume = volume.min(10);
+    }
+
+    /// Returns the sound effects volume.
+    pub fn sfx_volume(&self) -> u8 {
+        self.sfx_volume
+    }
+
+    /// Mutes or unmutes all sound.
+    pub fn set_muted(&mut self, muted: bool) {
+        self.muted = muted;
+    }
+
+    pub fn is_muted(&self) -> bool {
+        self.muted
+    }
+
+    /// Sets the volume of one sound (0-10), on top of the effects volume.
+    pub fn set_sound_volume(&mut self, sound: Sound, volume: u8) {
+        self.levels.retain(|(s, _)| *s != sound);
+        self.levels.push((sound, volume.min(10)));
+    }
+
+    /// Returns the volume of one sound.
+    pub fn sound_volume(&self, sound: Sound) -> u8 {
+        self.levels
+            .iter()
+            .find(|(s, _)| *s == sound)
+            .map_or(10, |&(_, level)| level)
+    }
+
+    /// Whether a clip was loaded for `sound`.
+    pub fn has_clip(&self, sound: Sound) -> bool {
+        self.output.has_clip(sound)
     }
 
-    /// Sets the playback volume (0-10).
-    pub fn set_volume(&mut self, volume: u8) {
-        self.volume = volume.min(10);
+    /// Whether a track was loaded for `state`.
+    pub fn has_track(&self, state: MusicState) -> bool {
+        self.output.has_track(state)
     }
 
-    /// Returns current volume.
-    pub fn volume(&self) -> u8 {
-        self.volume
+    /// Sets the music volume (0-10), apart from the effects.
+    pub fn set_music_volume(&mut self, volume: u8) {
+        self.music_volume = volume.min(10);
     }
 
-    /// Plays the requested sound effect.
+    /// Returns the music volume.
+    pub fn music_volume(&self) -> u8 {
+        self.music_volume
+    }
+
+    /// The state whose track is playing or fading in.
+    pub fn music(&self) -> Option<MusicState> {
+        self.music.state()
+    }
+
+    /// Crossfades to the track of `state` over the next
+    /// [`music::CROSSFADE_MS`]. Returns false if it is already playing.
+    pub fn set_music(&mut self, state: MusicState) -> bool {
+        self.music.set_state(state)
+    }
+
+    /// Moves the crossfade on by `ms` and sets each track's volume.
+    pub fn advance_music(&mut self, ms: f32) {
+        self.music.advance(ms);
+        let volume = if self.muted {
+            0.0
+        } else {
+            self.music_volume as f32 / 10.0
+        };
+        let gains: Vec<(MusicState, f32)> = MusicState::ALL
+            .iter()
+            .map(|&state| (state, self.music.level(state) * volume))
+            .collect();
+        self.output.set_track_gains(&gains);
+    }
+
+    /// Amplitude `sound` plays at, from 0 to 1.
+    pub fn gain(&self, sound: Sound) -> f32 {
+        if self.muted {
+            return 0.0;
+        }
+        (self.sfx_volume as f32 / 10.0) * (self.sound_volume(sound) as f32 / 10.0)
+    }
+
+    /// Plays the requested sound effect over any already playing.
     pub fn play(&self, sound: Sound) -> GameResult<()> {
-        println!("Play sound {:?} at volume {}", sound, self.volume);
-        Ok(())
+        let gain = self.gain(sound);
+        if gain == 0.0 {
+            return Ok(());
+        }
+        self.output.play(sound, gain)
+    }
+}
+
+impl EventListener for AudioManager {
+    fn on_event(&mut self, event: &GameEvent) {
+        let sound = match event {
+            GameEvent::FishHooked => Sound::Hit,
+            GameEvent::FishCaught { .. } => Sound::Catch,
+            GameEvent::LineSnapped { .. } => Sound::LineSnap,
+            GameEvent::WeatherChanged { stormy: true, .. } => Sound::Storm,
+            _ => return,
+        };
+        let _ = self.play(sound);
     }
 }
 
@@ -53,14 +245,26 @@ mod tests {
     #[test]
     fn volume_clamped() {
         let m = AudioManager::new(15);
-        assert_eq!(m.volume(), 10);
+        assert_eq!(m.sfx_volume(), 10);
     }
 
     #[test]
     fn set_volume_clamps() {
         let mut m = AudioManager::new(5);
-        m.set_volume(20);
-        assert_eq!(m.volume(), 10);
+        m.set_sfx_volume(20);
+        assert_eq!(m.sfx_volume(), 10);
+    }
+
+    #[test]
+    fn muting_silences_without_losing_volumes() {
+        let mut m = AudioManager::new(5);
+        m.set_muted(true);
+        assert!(m.is_muted());
+        assert_eq!(m.gain(Sound::Catch), 0.0);
+        assert!(m.play(Sound::Catch).is_ok());
+        m.set_muted(false);
+        assert_eq!(m.sfx_volume(), 5);
+        assert_eq!(m.gain(Sound::Catch), 0.5);
     }
 
     #[test]
@@ -68,4 +272,45 @@ mod tests {
         let m = AudioManager::new(3);
         assert!(m.play(Sound::Hit).is_ok());
     }
+
+    #[test]
+    fn sounds_load_with_their_volumes() {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/../../assets/sounds");
+        let mut m = AudioManager::load(5, dir).expect("sounds");
+        assert!(Sound::ALL.iter().all(|&s| m.has_clip(s)));
+        assert_eq!(m.sound_volume(Sound::Storm), 6);
+        assert_eq!(m.gain(Sound::LineSnap), 0.5);
+        assert!((m.gain(Sound::Storm) - 0.3).abs() < 1e-6);
+        m.set_sound_volume(Sound::Storm, 0);
+        assert_eq!(m.gain(Sound::Storm), 0.0);
+        assert!(m.play(Sound::Catch).is_ok());
+        assert!(m.play(Sound::Storm).is_ok());
+    }
+
+    #[test]
+    fn music_loads_and_has_its_own_volume() {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/../../assets/sounds");
+        let mut m = AudioManager::load(5, dir).expect("sounds");
+        assert!(MusicState::ALL.iter().all(|&s| m.has_track(s)));
+        m.set_music_volume(12);
+        assert_eq!(m.music_volume(), 10);
+        m.set_sfx_volume(0);
+        assert_eq!(m.music_volume(), 10);
+        assert!(m.set_music(MusicState::Storm));
+        m.advance_music(music::CROSSFADE_MS / 2.0);
+        assert_eq!(m.music(), Some(MusicState::Storm));
+    }
+
+    #[test]
+    fn unknown_sounds_are_rejected() {
+        let dir = std::env::temp_dir().join("lurhook_audio_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(VOLUMES_FILE), r#"{ "splash": 3 }"#).unwrap();
+        let res = AudioManager::load(5, &dir);
+        assert!(matches!(res, Err(GameError::Parse(_))));
+        std::fs::write(dir.join(VOLUMES_FILE), "{}").unwrap();
+        let m = AudioManager::load(5, &dir).expect("no clips");
+        assert!(!m.has_clip(Sound::Hit));
+        assert_eq!(m.sound_volume(Sound::Hit), 10);
+    }
 }
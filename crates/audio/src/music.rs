@@ -0,0 +1,117 @@
+//! Background music: a looping track for each mood of play, crossfading
+//! from one to the next as the game's state changes.
+
+/// Milliseconds a crossfade from one track to the next takes.
+pub const CROSSFADE_MS: f32 = 2000.0;
+
+/// Mood of play, each with its own looping track.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MusicState {
+    /// Exploring in fair weather.
+    Calm,
+    Storm,
+    /// A fish on the line.
+    Fight,
+    /// A legendary fish on the line.
+    Legendary,
+}
+
+impl MusicState {
+    pub const ALL: [MusicState; 4] = [
+        MusicState::Calm,
+        MusicState::Storm,
+        MusicState::Fight,
+        MusicState::Legendary,
+    ];
+
+    /// Name of the state's track file under `music/`.
+    pub fn name(self) -> &'static str {
+        match self {
+            MusicState::Calm => "calm",
+            MusicState::Storm => "storm",
+            MusicState::Fight => "fight",
+            MusicState::Legendary => "legendary",
+        }
+    }
+}
+
+/// Which track plays and how far each is faded in.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Music {
+    state: Option<MusicState>,
+    /// Fade level of each track that is not silent, from 0 to 1.
+    levels: Vec<(MusicState, f32)>,
+}
+
+impl Music {
+    /// The state whose track is playing or fading in.
+    pub fn state(&self) -> Option<MusicState> {
+        self.state
+    }
+
+    /// Starts crossfading to the track of `state`. Returns false when it
+    /// is already the one playing.
+    pub fn set_state(&mut self, state: MusicState) -> bool {
+        if self.state == Some(state) {
+            return false;
+        }
+        self.state = Some(state);
+        if !self.levels.iter().any(|(s, _)| *s == state) {
+            self.levels.push((state, 0.0));
+        }
+        true
+    }
+
+    /// How far the track of `state` is faded in.
+    pub fn level(&self, state: MusicState) -> f32 {
+        self.levels
+            .iter()
+            .find(|(s, _)| *s == state)
+            .map_or(0.0, |&(_, level)| level)
+    }
+
+    /// Moves the crossfade on by `ms`: the current track fades in and the
+    /// others out, dropping once silent.
+    pub fn advance(&mut self, ms: f32) {
+        let step = ms / CROSSFADE_MS;
+        for (state, level) in self.levels.iter_mut() {
+            *level = if Some(*state) == self.state {
+                (*level + step).min(1.0)
+            } else {
+                (*level - step).max(0.0)
+            };
+        }
+        let current = self.state;
+        self.levels
+            .retain(|&(state, level)| level > 0.0 || Some(state) == current);
+    }
+
+    /// Fade level of every track that is not silent.
+    pub fn levels(&self) -> &[(MusicState, f32)] {
+        &self.levels
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_crossfade() {
+        let mut music = Music::default();
+        assert!(music.set_state(MusicState::Calm));
+        assert!(!music.set_state(MusicState::Calm));
+        music.advance(CROSSFADE_MS);
+        assert_eq!(music.level(MusicState::Calm), 1.0);
+
+        music.set_state(MusicState::Fight);
+        music.advance(CROSSFADE_MS / 4.0);
+        assert_eq!(music.level(MusicState::Calm), 0.75);
+        assert_eq!(music.level(MusicState::Fight), 0.25);
+        music.set_state(MusicState::Calm);
+        music.advance(CROSSFADE_MS / 4.0);
+        assert_eq!(music.level(MusicState::Calm), 1.0, "fades back in");
+        assert_eq!(music.level(MusicState::Fight), 0.0);
+        assert_eq!(music.levels(), &[(MusicState::Calm, 1.0)]);
+    }
+}
@@ -0,0 +1,182 @@
+//! Where sounds go: out to the default audio device through rodio, or
+//! nowhere when the `rodio` feature is off.
+
+use crate::{MusicState, Sound};
+use common::GameResult;
+
+#[cfg(feature = "rodio")]
+pub(crate) use device::Output;
+#[cfg(not(feature = "rodio"))]
+pub(crate) use silent::Output;
+
+#[cfg(feature = "rodio")]
+mod device {
+    use super::*;
+    use common::GameError;
+    use rodio::source::Buffered;
+    use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink, Source};
+    use std::fmt;
+    use std::io::Cursor;
+
+    type Clip = Buffered<Decoder<Cursor<Vec<u8>>>>;
+
+    fn decode(name: &str, bytes: Vec<u8>) -> GameResult<Clip> {
+        let clip = Decoder::new(Cursor::new(bytes))
+            .map_err(|e| GameError::Parse(format!("{}: {}", name, e)))?;
+        Ok(clip.buffered())
+    }
+
+    /// Decoded clips and the device they play on. Without a device (no
+    /// sound card, a headless test run) every sound is silent.
+    pub(crate) struct Output {
+        clips: Vec<(Sound, Clip)>,
+        /// A paused, silent sink looping each track, ready to fade in;
+        /// `None` without a device.
+        tracks: Vec<(MusicState, Option<Sink>)>,
+        /// Dropping the stream would stop playback.
+        device: Option<(OutputStream, OutputStreamHandle)>,
+    }
+
+    impl Output {
+        pub(crate) fn silent() -> Self {
+            Self {
+                clips: Vec::new(),
+                tracks: Vec::new(),
+                device: None,
+            }
+        }
+
+        /// Decodes `clips` and `tracks` and opens the default device.
+        pub(crate) fn open(
+            clips: Vec<(Sound, Vec<u8>)>,
+            tracks: Vec<(MusicState, Vec<u8>)>,
+        ) -> GameResult<Self> {
+            let clips = clips
+                .into_iter()
+                .map(|(sound, bytes)| Ok((sound, decode(sound.name(), bytes)?)))
+                .collect::<GameResult<_>>()?;
+            let device = OutputStream::try_default().ok();
+            let mut looping = Vec::new();
+            for (state, bytes) in tracks {
+                let track = decode(state.name(), bytes)?;
+                let sink = match &device {
+                    Some((_, handle)) => {
+                        let sink =
+                            Sink::try_new(handle).map_err(|_| GameError::InvalidOperation)?;
+                        sink.set_volume(0.0);
+                        sink.pause();
+                        sink.append(track.repeat_infinite());
+                        Some(sink)
+                    }
+                    None => None,
+                };
+                looping.push((state, sink));
+            }
+            Ok(Self {
+                clips,
+                tracks: looping,
+                device,
+            })
+        }
+
+        pub(crate) fn has_track(&self, state: MusicState) -> bool {
+            self.tracks.iter().any(|(s, _)| *s == state)
+        }
+
+        /// Sets each track's volume, pausing the silent ones.
+        pub(crate) fn set_track_gains(&self, gains: &[(MusicState, f32)]) {
+            for (state, sink) in &self.tracks {
+                let Some(sink) = sink else {
+                    continue;
+                };
+                let gain = gains
+                    .iter()
+                    .find(|(s, _)| s == state)
+                    .map_or(0.0, |&(_, gain)| gain);
+                sink.set_volume(gain);
+                if gain > 0.0 {
+                    sink.play();
+                } else {
+                    sink.pause();
+                }
+            }
+        }
+
+        pub(crate) fn has_clip(&self, sound: Sound) -> bool {
+            self.clips.iter().any(|(s, _)| *s == sound)
+        }
+
+        /// Starts `sound` at `gain`. The device's mixer adds it to any
+        /// sounds still playing.
+        pub(crate) fn play(&self, sound: Sound, gain: f32) -> GameResult<()> {
+            let Some((_, handle)) = &self.device else {
+                return Ok(());
+            };
+            let Some((_, clip)) = self.clips.iter().find(|(s, _)| *s == sound) else {
+                return Ok(());
+            };
+            handle
+                .play_raw(clip.clone().amplify(gain).convert_samples())
+                .map_err(|_| GameError::InvalidOperation)
+        }
+    }
+
+    impl fmt::Debug for Output {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let clips: Vec<Sound> = self.clips.iter().map(|(s, _)| *s).collect();
+            let tracks: Vec<MusicState> = self.tracks.iter().map(|(s, _)| *s).collect();
+            f.debug_struct("Output")
+                .field("clips", &clips)
+                .field("tracks", &tracks)
+                .field("device", &self.device.is_some())
+                .finish()
+        }
+    }
+}
+
+#[cfg(not(feature = "rodio"))]
+mod silent {
+    use super::*;
+
+    /// Keeps track of which sounds and states have clips and plays none
+    /// of them. Nothing is written to stdout either, so the terminal
+    /// backend's screen stays intact.
+    #[derive(Debug)]
+    pub(crate) struct Output {
+        clips: Vec<Sound>,
+        tracks: Vec<MusicState>,
+    }
+
+    impl Output {
+        pub(crate) fn silent() -> Self {
+            Self {
+                clips: Vec::new(),
+                tracks: Vec::new(),
+            }
+        }
+
+        pub(crate) fn open(
+            clips: Vec<(Sound, Vec<u8>)>,
+            tracks: Vec<(MusicState, Vec<u8>)>,
+        ) -> GameResult<Self> {
+            Ok(Self {
+                clips: clips.into_iter().map(|(sound, _)| sound).collect(),
+                tracks: tracks.into_iter().map(|(state, _)| state).collect(),
+            })
+        }
+
+        pub(crate) fn has_clip(&self, sound: Sound) -> bool {
+            self.clips.contains(&sound)
+        }
+
+        pub(crate) fn has_track(&self, state: MusicState) -> bool {
+            self.tracks.contains(&state)
+        }
+
+        pub(crate) fn set_track_gains(&self, _gains: &[(MusicState, f32)]) {}
+
+        pub(crate) fn play(&self, _sound: Sound, _gain: f32) -> GameResult<()> {
+            Ok(())
+        }
+    }
+}
@@ -1,95 +1,744 @@
 //! Codex system for recording captured fish.
+//!
+//! Catches and escapes are recorded in memory; the codex file is only
+//! written when [`Codex::flush`] finds changes, at the game's save points.
 
-use std::collections::HashMap;
-use common::{GameResult};
+use common::{EventListener, GameError, GameEvent, GameResult, Storage};
+use data::FishType;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 
-/// Mapping from fish id to capture count.
-#[derive(Default, Debug, Clone, PartialEq, Eq)]
+/// Ordering applied to codex listings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortKey {
+    /// Alphabetical by species name.
+    Name,
+    /// Rarest species first.
+    Rarity,
+    /// Most captured species first.
+    Captures,
+}
+
+impl SortKey {
+    /// Returns the next sort key, wrapping around.
+    pub fn next(self) -> Self {
+        match self {
+            SortKey::Name => SortKey::Rarity,
+            SortKey::Rarity => SortKey::Captures,
+            SortKey::Captures => SortKey::Name,
+        }
+    }
+}
+
+/// A species paired with its capture count.
+#[derive(Clone, Debug)]
+pub struct CodexEntry<'a> {
+    pub fish: &'a FishType,
+    pub count: u32,
+}
+
+/// Returns the `page`-th slice of `items` with `per_page` entries each.
+pub fn page<T>(items: &[T], page: usize, per_page: usize) -> &[T] {
+    let start = page.saturating_mul(per_page).min(items.len());
+    let end = start.saturating_add(per_page).min(items.len());
+    &items[start..end]
+}
+
+/// Number of pages needed to show `len` entries (at least one).
+pub fn page_count(len: usize, per_page: usize) -> usize {
+    if per_page == 0 {
+        return 1;
+    }
+    len.div_ceil(per_page).max(1)
+}
+
+/// Lifetime statistics for one species.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CodexRecord {
+    /// Number of fish landed.
+    pub count: u32,
+    /// Game turn of the first catch.
+    pub first_turn: Option<u32>,
+    /// Unix time (seconds) of the first catch, when the platform provides a clock.
+    pub first_timestamp: Option<u64>,
+    /// Heaviest fish landed.
+    pub largest_weight: f32,
+    /// Combined weight of all fish landed.
+    pub total_weight: f32,
+    /// Fights lost to this species.
+    pub escapes: u32,
+    /// Catches per location name.
+    pub locations: BTreeMap<String, u32>,
+}
+
+impl CodexRecord {
+    /// Location with the most catches; ties resolve alphabetically.
+    pub fn favorite_location(&self) -> Option<&str> {
+        self.locations
+            .iter()
+            .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+            .map(|(name, _)| name.as_str())
+    }
+}
+
+/// Details of a landed fish passed to [`Codex::record_capture`].
+#[derive(Clone, Copy, Debug)]
+pub struct Capture<'a> {
+    pub id: &'a str,
+    pub turn: u32,
+    pub timestamp: Option<u64>,
+    pub weight: f32,
+    pub location: &'a str,
+}
+
+/// Current on-disk codex layout.
+#[derive(Serialize, Deserialize)]
+struct CodexFile {
+    version: u32,
+    species: HashMap<String, CodexRecord>,
+}
+
+const FORMAT_VERSION: u32 = 2;
+
+/// Mapping from fish id to lifetime records, kept under a storage key.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct Codex {
-    records: HashMap<String, u32>,
+    records: HashMap<String, CodexRecord>,
+    /// Storage key of the codex file.
+    #[serde(skip)]
+    key: String,
+    /// Whether records changed since the file was last written.
+    #[serde(skip)]
+    dirty: bool,
+}
+
+/// Codices are equal when their records are.
+impl PartialEq for Codex {
+    fn eq(&self, other: &Self) -> bool {
+        self.records == other.records
+    }
 }
 
 impl Codex {
-    /// Loads codex data from a simple JSON map file.
-    pub fn load(path: &str) -> GameResult<Self> {
-        let data = match std::fs::read_to_string(path) {
-            Ok(s) => s,
-            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
-            Err(e) => return Err(e.into()),
-        };
-        let mut records = HashMap::new();
-        for line in data.trim().trim_start_matches('{').trim_end_matches('}').split(',') {
-            let line = line.trim();
-            if line.is_empty() { continue; }
-            let mut parts = line.splitn(2, ':');
-            let id = parts.next().unwrap().trim().trim_matches('"');
-            let count = parts.next().unwrap().trim();
-            let count: u32 = count.parse().unwrap_or(0);
-            records.insert(id.to_string(), count);
+    /// An empty codex kept under `key`.
+    pub fn new(key: &str) -> Self {
+        Self {
+            key: key.to_string(),
+            ..Self::default()
         }
-        Ok(Self { records })
     }
 
-    /// Saves codex data back to disk.
-    pub fn save(&self, path: &str) -> GameResult<()> {
-        let mut out = String::from("{\n");
-        for (i, (id, count)) in self.records.iter().enumerate() {
-            out.push_str(&format!("  \"{}\": {}", id, count));
-            if i + 1 != self.records.len() { out.push_str(",\n"); } else { out.push('\n'); }
+    /// Loads codex data stored as JSON under `key`, and keeps it there.
+    ///
+    /// Files in the original `{"FISH_ID": count}` format are migrated on load.
+    pub fn load(storage: &dyn Storage, key: &str) -> GameResult<Self> {
+        let data = match storage.read_string(key)? {
+            Some(s) => s,
+            None => return Ok(Self::new(key)),
+        };
+        let value: serde_json::Value =
+            serde_json::from_str(&data).map_err(|e| GameError::Parse(format!("codex: {}", e)))?;
+        let records = if value.get("species").is_some() {
+            serde_json::from_value::<CodexFile>(value)
+                .map_err(|e| GameError::Parse(format!("codex: {}", e)))?
+                .species
+        } else {
+            serde_json::from_value::<HashMap<String, u32>>(value)
+                .map_err(|e| GameError::Parse(format!("legacy codex: {}", e)))?
+                .into_iter()
+                .map(|(id, count)| {
+                    (
+                        id,
+                        CodexRecord {
+                            count,
+                            ..CodexRecord::default()
+                        },
+                    )
+                })
+                .collect()
+        };
+        Ok(Self {
+            records,
+            ..Self::new(key)
+        })
+    }
+
+    /// Storage key of the codex file.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Whether there are records the codex file does not have yet.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Writes the codex file now.
+    pub fn save(&mut self, storage: &dyn Storage) -> GameResult<()> {
+        let file = CodexFile {
+            version: FORMAT_VERSION,
+            species: self.records.clone(),
+        };
+        let out = serde_json::to_string_pretty(&file)
+            .map_err(|e| GameError::Parse(format!("codex: {}", e)))?;
+        storage.write(&self.key, out.as_bytes())?;
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Writes the codex file if records changed since it was last written.
+    pub fn flush(&mut self, storage: &dyn Storage) -> GameResult<()> {
+        if self.dirty {
+            self.save(storage)?;
         }
-        out.push('}');
-        std::fs::write(path, out)?;
         Ok(())
     }
 
-    /// Increments capture count for a fish id and saves immediately.
-    pub fn record_capture(&mut self, path: &str, id: &str) -> GameResult<()> {
-        *self.records.entry(id.to_string()).or_insert(0) += 1;
-        self.save(path)
+    /// Takes the records of `other` in place of these, to be written on
+    /// the next flush.
+    pub fn replace_records(&mut self, other: Codex) {
+        self.records = other.records;
+        self.dirty = true;
+    }
+
+    /// Records a landed fish.
+    pub fn record_capture(&mut self, capture: &Capture) {
+        self.dirty = true;
+        let record = self.records.entry(capture.id.to_string()).or_default();
+        record.count += 1;
+        if record.first_turn.is_none() {
+            record.first_turn = Some(capture.turn);
+            record.first_timestamp = capture.timestamp;
+        }
+        record.largest_weight = record.largest_weight.max(capture.weight);
+        record.total_weight += capture.weight;
+        *record
+            .locations
+            .entry(capture.location.to_string())
+            .or_insert(0) += 1;
+    }
+
+    /// Records a fish of species `id` escaping.
+    pub fn record_escape(&mut self, id: &str) {
+        self.dirty = true;
+        self.records.entry(id.to_string()).or_default().escapes += 1;
+    }
+
+    /// Returns the full record for a fish id, if any.
+    pub fn record(&self, id: &str) -> Option<&CodexRecord> {
+        self.records.get(id)
     }
 
     /// Returns the capture count for a fish id.
     pub fn count(&self, id: &str) -> u32 {
-        *self.records.get(id).unwrap_or(&0)
+        self.records.get(id).map_or(0, |r| r.count)
+    }
+
+    /// Lists `species` with capture counts in the requested order.
+    pub fn entries<'a>(&self, species: &'a [FishType], sort: SortKey) -> Vec<CodexEntry<'a>> {
+        let mut entries: Vec<CodexEntry<'a>> = species
+            .iter()
+            .map(|fish| CodexEntry {
+                fish,
+                count: self.count(&fish.id),
+            })
+            .collect();
+        match sort {
+            SortKey::Name => entries.sort_by(|a, b| a.fish.name.cmp(&b.fish.name)),
+            SortKey::Rarity => entries.sort_by(|a, b| a.fish.rarity.total_cmp(&b.fish.rarity)),
+            SortKey::Captures => entries.sort_by(|a, b| {
+                b.count
+                    .cmp(&a.count)
+                    .then_with(|| a.fish.name.cmp(&b.fish.name))
+            }),
+        }
+        entries
+    }
+
+    /// Percentage (0-100) of `species` captured at least once.
+    pub fn completion<'a>(&self, species: impl IntoIterator<Item = &'a FishType>) -> f32 {
+        let mut total = 0;
+        let mut found = 0;
+        for fish in species {
+            total += 1;
+            if self.count(&fish.id) > 0 {
+                found += 1;
+            }
+        }
+        if total == 0 {
+            0.0
+        } else {
+            found as f32 * 100.0 / total as f32
+        }
     }
 
     /// Returns the total capture count across all fish.
     pub fn total_captures(&self) -> u32 {
-        self.records.values().copied().sum()
+        self.records.values().map(|r| r.count).sum()
     }
 
     #[cfg(test)]
     pub fn set_count(&mut self, id: &str, count: u32) {
-        self.records.insert(id.to_string(), count);
+        self.records.entry(id.to_string()).or_default().count = count;
+    }
+}
+
+/// Listener recording catches and escapes into a codex.
+pub struct CodexRecorder<'a> {
+    pub codex: &'a mut Codex,
+}
+
+impl EventListener for CodexRecorder<'_> {
+    fn on_event(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::FishCaught {
+                id,
+                weight,
+                location,
+                turn,
+                timestamp,
+                ..
+            } => self.codex.record_capture(&Capture {
+                id,
+                turn: *turn,
+                timestamp: *timestamp,
+                weight: *weight,
+                location,
+            }),
+            GameEvent::LineSnapped { id: Some(id), .. }
+            | GameEvent::FishEscaped { id: Some(id) }
+            | GameEvent::FishStolen { id: Some(id) } => self.codex.record_escape(id),
+            _ => {}
+        }
+    }
+}
+
+/// File format for [`Codex::export`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    /// Conventional file extension without the dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// One species line of a [`CodexReport`].
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ReportRow<'a> {
+    pub id: &'a str,
+    pub name: &'a str,
+    pub rarity: f32,
+    pub count: u32,
+    pub first_turn: Option<u32>,
+    pub first_timestamp: Option<u64>,
+    pub largest_weight: f32,
+    pub total_weight: f32,
+    pub escapes: u32,
+    pub favorite_location: Option<&'a str>,
+    /// Flavor text, revealed once the species has been caught.
+    pub description: Option<&'a str>,
+    pub habitat_note: Option<&'a str>,
+}
+
+/// Totals across every species.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct LifetimeStats {
+    pub species_caught: usize,
+    pub species_total: usize,
+    pub completion: f32,
+    pub total_captures: u32,
+    pub total_escapes: u32,
+    pub total_weight: f32,
+    pub largest_weight: f32,
+}
+
+/// Codex contents plus lifetime stats, ready for serialization.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct CodexReport<'a> {
+    pub stats: LifetimeStats,
+    pub species: Vec<ReportRow<'a>>,
+}
+
+impl CodexReport<'_> {
+    /// Serializes the report as pretty-printed JSON.
+    pub fn to_json(&self) -> GameResult<String> {
+        serde_json::to_string_pretty(self).map_err(|e| GameError::Parse(format!("report: {}", e)))
+    }
+
+    /// Serializes one CSV row per species followed by a `TOTAL` row.
+    pub fn to_csv(&self) -> GameResult<String> {
+        let err = |e: csv::Error| GameError::Parse(format!("report: {}", e));
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        for row in &self.species {
+            writer.serialize(row).map_err(err)?;
+        }
+        writer
+            .serialize(ReportRow {
+                id: "TOTAL",
+                name: "All species",
+                rarity: 0.0,
+                count: self.stats.total_captures,
+                first_turn: None,
+                first_timestamp: None,
+                largest_weight: self.stats.largest_weight,
+                total_weight: self.stats.total_weight,
+                escapes: self.stats.total_escapes,
+                favorite_location: None,
+                description: None,
+                habitat_note: None,
+            })
+            .map_err(err)?;
+        let bytes = writer
+            .into_inner()
+            .map_err(|e| GameError::Parse(format!("report: {}", e)))?;
+        String::from_utf8(bytes).map_err(|e| GameError::Parse(format!("report: {}", e)))
+    }
+}
+
+impl Codex {
+    /// Builds a report covering `species` in the codex listing order.
+    pub fn report<'a>(&'a self, species: &'a [FishType]) -> CodexReport<'a> {
+        let rows: Vec<ReportRow<'a>> = self
+            .entries(species, SortKey::Name)
+            .into_iter()
+            .map(|entry| {
+                let record = self.record(&entry.fish.id);
+                let caught = entry.count > 0;
+                ReportRow {
+                    id: &entry.fish.id,
+                    name: &entry.fish.name,
+                    rarity: entry.fish.rarity,
+                    count: entry.count,
+                    first_turn: record.and_then(|r| r.first_turn),
+                    first_timestamp: record.and_then(|r| r.first_timestamp),
+                    largest_weight: record.map_or(0.0, |r| r.largest_weight),
+                    total_weight: record.map_or(0.0, |r| r.total_weight),
+                    escapes: record.map_or(0, |r| r.escapes),
+                    favorite_location: record.and_then(|r| r.favorite_location()),
+                    description: caught.then_some(entry.fish.description.as_str()),
+                    habitat_note: caught.then_some(entry.fish.habitat_note.as_str()),
+                }
+            })
+            .collect();
+        let stats = LifetimeStats {
+            species_caught: rows.iter().filter(|r| r.count > 0).count(),
+            species_total: rows.len(),
+            completion: self.completion(species),
+            total_captures: rows.iter().map(|r| r.count).sum(),
+            total_escapes: rows.iter().map(|r| r.escapes).sum(),
+            total_weight: rows.iter().map(|r| r.total_weight).sum(),
+            largest_weight: rows.iter().map(|r| r.largest_weight).fold(0.0, f32::max),
+        };
+        CodexReport {
+            stats,
+            species: rows,
+        }
+    }
+
+    /// Writes a report for `species` under `key` in the given format.
+    pub fn export(
+        &self,
+        storage: &dyn Storage,
+        key: &str,
+        species: &[FishType],
+        format: ExportFormat,
+    ) -> GameResult<()> {
+        let report = self.report(species);
+        let out = match format {
+            ExportFormat::Csv => report.to_csv()?,
+            ExportFormat::Json => report.to_json()?,
+        };
+        storage.write(key, out.as_bytes())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::{FileStorage, MemoryStorage};
     use std::fs;
 
     #[test]
     fn load_nonexistent_returns_empty() {
-        let c = Codex::load("/tmp/nope.json").unwrap();
+        let c = Codex::load(&FileStorage::default(), "/tmp/nope.json").unwrap();
         assert!(c.records.is_empty());
     }
 
     #[test]
     fn record_and_load() {
         let path = "/tmp/codex_test.json";
-        let mut c = Codex::default();
-        c.record_capture(path, "A").unwrap();
-        let loaded = Codex::load(path).unwrap();
+        let storage = FileStorage::default();
+        let mut c = Codex::new(path);
+        c.record_capture(&capture("A", 1, 2.0, "Coast"));
+        c.flush(&storage).unwrap();
+        let loaded = Codex::load(&storage, path).unwrap();
         fs::remove_file(path).unwrap();
         assert_eq!(loaded.count("A"), 1);
     }
 
+    fn capture<'a>(id: &'a str, turn: u32, weight: f32, location: &'a str) -> Capture<'a> {
+        Capture {
+            id,
+            turn,
+            timestamp: Some(1_700_000_000 + turn as u64),
+            weight,
+            location,
+        }
+    }
+
+    #[test]
+    fn record_and_load_in_memory() {
+        let storage = MemoryStorage::default();
+        let mut c = Codex::new("codex.json");
+        c.record_capture(&capture("A", 5, 1.5, "Coast"));
+        c.record_capture(&capture("A", 9, 3.0, "Offshore"));
+        c.record_escape("A");
+        c.flush(&storage).unwrap();
+        let loaded = Codex::load(&storage, "codex.json").unwrap();
+        assert_eq!(loaded, c);
+        let rec = loaded.record("A").unwrap();
+        assert_eq!(rec.count, 2);
+        assert_eq!(rec.first_turn, Some(5));
+        assert_eq!(rec.first_timestamp, Some(1_700_000_005));
+        assert_eq!(rec.largest_weight, 3.0);
+        assert_eq!(rec.total_weight, 4.5);
+        assert_eq!(rec.escapes, 1);
+    }
+
+    #[test]
+    fn favorite_location_prefers_most_catches() {
+        let mut rec = CodexRecord::default();
+        assert_eq!(rec.favorite_location(), None);
+        rec.locations.insert("Offshore".into(), 2);
+        rec.locations.insert("Coast".into(), 2);
+        assert_eq!(rec.favorite_location(), Some("Coast"));
+        rec.locations.insert("DeepSea".into(), 3);
+        assert_eq!(rec.favorite_location(), Some("DeepSea"));
+    }
+
+    #[test]
+    fn escape_without_catch_keeps_count_zero() {
+        let storage = MemoryStorage::default();
+        let mut c = Codex::new("codex.json");
+        c.record_escape("A");
+        c.flush(&storage).unwrap();
+        assert_eq!(c.count("A"), 0);
+        assert_eq!(c.record("A").unwrap().escapes, 1);
+    }
+
+    #[test]
+    fn recorder_logs_catches_and_escapes() {
+        let storage = MemoryStorage::default();
+        let mut c = Codex::new("codex.json");
+        let mut recorder = CodexRecorder { codex: &mut c };
+        recorder.on_event(&GameEvent::FishCaught {
+            id: "A".into(),
+            name: "A".into(),
+            weight: 2.0,
+            description: String::new(),
+            habitat_note: String::new(),
+            location: "Coast".into(),
+            turn: 4,
+            timestamp: None,
+        });
+        recorder.on_event(&GameEvent::FishEscaped {
+            id: Some("A".into()),
+        });
+        recorder.on_event(&GameEvent::LineSnapped {
+            id: None,
+            lure_lost: false,
+        });
+        let rec = c.record("A").unwrap();
+        assert_eq!((rec.count, rec.escapes), (1, 1));
+        assert_eq!(rec.first_turn, Some(4));
+        c.flush(&storage).unwrap();
+        assert_eq!(Codex::load(&storage, "codex.json").unwrap(), c);
+    }
+
+    #[test]
+    fn records_wait_for_a_flush() {
+        let storage = MemoryStorage::default();
+        let mut c = Codex::load(&storage, "codex.json").unwrap();
+        assert_eq!(c.key(), "codex.json");
+        c.flush(&storage).unwrap();
+        assert_eq!(storage.read("codex.json").unwrap(), None, "nothing new");
+        c.record_capture(&capture("A", 1, 2.0, "Coast"));
+        c.record_escape("B");
+        assert!(c.is_dirty());
+        assert_eq!(storage.read("codex.json").unwrap(), None);
+        c.flush(&storage).unwrap();
+        assert!(!c.is_dirty());
+        let loaded = Codex::load(&storage, "codex.json").unwrap();
+        assert_eq!(
+            (loaded.count("A"), loaded.record("B").unwrap().escapes),
+            (1, 1)
+        );
+
+        let mut other = Codex::new("elsewhere.json");
+        other.replace_records(loaded);
+        other.flush(&storage).unwrap();
+        assert_eq!(Codex::load(&storage, "elsewhere.json").unwrap(), c);
+    }
+
+    #[test]
+    fn legacy_count_map_is_migrated() {
+        let storage = MemoryStorage::default();
+        storage
+            .write("codex.json", b"{\n  \"A\": 3,\n  \"B\": 1\n}")
+            .unwrap();
+        let mut c = Codex::load(&storage, "codex.json").unwrap();
+        assert_eq!(c.count("A"), 3);
+        assert_eq!(c.record("B").unwrap().first_turn, None);
+        c.save(&storage).unwrap();
+        let saved = storage.read_string("codex.json").unwrap().unwrap();
+        assert!(saved.contains("\"version\": 2"));
+        assert_eq!(Codex::load(&storage, "codex.json").unwrap(), c);
+    }
+
+    #[test]
+    fn malformed_codex_is_parse_error() {
+        let storage = MemoryStorage::default();
+        storage.write("codex.json", b"{ not json").unwrap();
+        assert!(matches!(
+            Codex::load(&storage, "codex.json"),
+            Err(GameError::Parse(_))
+        ));
+    }
+
+    fn species(id: &str, name: &str, rarity: f32) -> FishType {
+        FishType {
+            id: id.into(),
+            name: name.into(),
+            rarity,
+            strength: 1,
+            min_depth: 0,
+            max_depth: 10,
+            fight_style: data::FightStyle::Aggressive,
+            legendary: false,
+            description: format!("About {}", name),
+            habitat_note: "Shallows".into(),
+            diet: Vec::new(),
+            min_weight: 1.0,
+            max_weight: 1.0,
+            biomes: Vec::new(),
+            boss: None,
+            seasons: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn entries_sorted_by_key() {
+        let list = vec![
+            species("B", "Bream", 0.5),
+            species("A", "Anchovy", 0.9),
+            species("C", "Cod", 0.1),
+        ];
+        let mut c = Codex::default();
+        c.set_count("B", 4);
+        c.set_count("C", 1);
+        let names = |sort| {
+            c.entries(&list, sort)
+                .iter()
+                .map(|e| e.fish.id.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(SortKey::Name), vec!["A", "B", "C"]);
+        assert_eq!(names(SortKey::Rarity), vec!["C", "B", "A"]);
+        assert_eq!(names(SortKey::Captures), vec!["B", "C", "A"]);
+    }
+
+    #[test]
+    fn completion_percentage() {
+        let list = vec![species("A", "A", 1.0), species("B", "B", 1.0)];
+        let mut c = Codex::default();
+        assert_eq!(c.completion(&list), 0.0);
+        c.set_count("A", 2);
+        assert!((c.completion(&list) - 50.0).abs() < f32::EPSILON);
+        assert_eq!(c.completion(&[]), 0.0);
+    }
+
+    #[test]
+    fn paging_splits_and_clamps() {
+        let items = [1, 2, 3, 4, 5];
+        assert_eq!(page(&items, 0, 2), &[1, 2]);
+        assert_eq!(page(&items, 2, 2), &[5]);
+        assert!(page(&items, 5, 2).is_empty());
+        assert_eq!(page_count(5, 2), 3);
+        assert_eq!(page_count(0, 2), 1);
+    }
+
+    #[test]
+    fn sort_key_cycles() {
+        assert_eq!(SortKey::Name.next().next().next(), SortKey::Name);
+    }
+
+    fn sample_codex() -> (Codex, Vec<FishType>) {
+        let list = vec![
+            species("B", "Bream, Sea", 0.5),
+            species("A", "Anchovy", 0.9),
+        ];
+        let mut c = Codex::default();
+        c.record_capture(&capture("B", 3, 2.5, "Coast"));
+        c.record_escape("A");
+        (c, list)
+    }
+
+    #[test]
+    fn report_collects_rows_and_totals() {
+        let (c, list) = sample_codex();
+        let report = c.report(&list);
+        assert_eq!(report.species[0].id, "A");
+        assert_eq!(report.species[1].favorite_location, Some("Coast"));
+        assert_eq!(report.species[0].description, None);
+        assert_eq!(report.species[1].description, Some("About Bream, Sea"));
+        assert_eq!(report.species[1].habitat_note, Some("Shallows"));
+        assert_eq!(report.stats.species_caught, 1);
+        assert_eq!(report.stats.species_total, 2);
+        assert_eq!(report.stats.total_captures, 1);
+        assert_eq!(report.stats.total_escapes, 1);
+        assert_eq!(report.stats.largest_weight, 2.5);
+        assert!((report.stats.completion - 50.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn csv_export_quotes_and_totals() {
+        let (c, list) = sample_codex();
+        let csv = c.report(&list).to_csv().unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert!(lines[0].starts_with("id,name,rarity,count,first_turn"));
+        assert!(lines[2].starts_with("B,\"Bream, Sea\",0.5,1,3,"));
+        assert!(lines[3].starts_with("TOTAL,All species,"));
+        assert_eq!(lines.len(), 4);
+    }
+
+    #[test]
+    fn json_export_round_trips_through_serde() {
+        let (c, list) = sample_codex();
+        let storage = MemoryStorage::default();
+        c.export(&storage, "report.json", &list, ExportFormat::Json)
+            .unwrap();
+        let text = storage.read_string("report.json").unwrap().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(value["stats"]["total_captures"], 1);
+        assert_eq!(value["species"][1]["name"], "Bream, Sea");
+        assert_eq!(ExportFormat::Csv.extension(), "csv");
+    }
+
     #[test]
     fn total_captures_sums_values() {
         let mut c = Codex::default();
-        c.records.insert("A".into(), 2);
-        c.records.insert("B".into(), 3);
+        c.set_count("A", 2);
+        c.set_count("B", 3);
         assert_eq!(c.total_captures(), 5);
     }
 }
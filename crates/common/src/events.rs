@@ -0,0 +1,117 @@
+//! Game events queued by the simulation and handed to listeners.
+//!
+//! Game logic only pushes [`GameEvent`]s onto an [`EventQueue`]. Once per
+//! frame the queue is drained and every event is passed to each
+//! [`EventListener`] (message log, audio, codex, ...), so new reactions can
+//! be added without touching the code that raised the event.
+
+use std::collections::VecDeque;
+
+/// Something that happened in the game that other systems may react to.
+#[derive(Clone, Debug, PartialEq)]
+pub enum GameEvent {
+    /// A fish took the bait; the hook still has to be set.
+    FishBite,
+    /// The hook was set and the fight began.
+    FishHooked,
+    /// Nothing bit after a cast.
+    NoBite,
+    /// A fish was landed.
+    FishCaught {
+        id: String,
+        name: String,
+        /// Weight in kilograms.
+        weight: f32,
+        description: String,
+        habitat_note: String,
+        /// Name of the area it was caught in.
+        location: String,
+        turn: u32,
+        /// Unix time of the catch, where a clock is available.
+        timestamp: Option<u64>,
+    },
+    /// The line broke during a fight; `id` is the species that got away.
+    /// A worn line parts above the lure and takes it along.
+    LineSnapped { id: Option<String>, lure_lost: bool },
+    /// The hooked fish slipped free; `id` is the species that got away.
+    FishEscaped { id: Option<String> },
+    /// A shark took the fish off the line mid-fight; `id` is its species.
+    FishStolen { id: Option<String> },
+    /// The line was worn down to nothing.
+    LineRuined,
+    /// The weather changed.
+    WeatherChanged { announcement: String, stormy: bool },
+    /// A storm or gale gave way to calmer weather.
+    StormPassed,
+    /// Hunger ran out and the player starts losing health.
+    HungerCritical,
+    /// Thirst ran out and the player starts losing health.
+    ThirstCritical,
+    /// A fish from the catch was eaten.
+    FishEaten,
+    /// A dish was cooked at a campfire and eaten.
+    DishCooked,
+}
+
+/// Receives events drained from an [`EventQueue`].
+pub trait EventListener {
+    fn on_event(&mut self, event: &GameEvent);
+}
+
+/// First-in, first-out queue of pending events.
+#[derive(Debug, Default)]
+pub struct EventQueue {
+    events: VecDeque<GameEvent>,
+}
+
+impl EventQueue {
+    /// Queues `event` behind the ones already pending.
+    pub fn push(&mut self, event: GameEvent) {
+        self.events.push_back(event);
+    }
+
+    /// Whether no events are pending.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// The pending events in the order they were pushed, left queued.
+    pub fn iter(&self) -> impl Iterator<Item = &GameEvent> + '_ {
+        self.events.iter()
+    }
+
+    /// Removes and returns the pending events in the order they were pushed.
+    pub fn drain(&mut self) -> impl Iterator<Item = GameEvent> + '_ {
+        self.events.drain(..)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Recorder(Vec<GameEvent>);
+
+    impl EventListener for Recorder {
+        fn on_event(&mut self, event: &GameEvent) {
+            self.0.push(event.clone());
+        }
+    }
+
+    #[test]
+    fn events_drain_in_order() {
+        let mut queue = EventQueue::default();
+        queue.push(GameEvent::FishHooked);
+        queue.push(GameEvent::LineRuined);
+        let mut recorder = Recorder::default();
+        for event in queue.drain() {
+            recorder.on_event(&event);
+        }
+        assert_eq!(
+            recorder.0,
+            vec![GameEvent::FishHooked, GameEvent::LineRuined]
+        );
+        assert!(queue.is_empty());
+    }
+}
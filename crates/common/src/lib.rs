@@ -1,7 +1,15 @@
 //! Common types shared across Lurhook crates.
 
+pub mod events;
+pub mod storage;
+
+pub use events::{EventListener, EventQueue, GameEvent};
+pub use storage::{default_storage, FileStorage, MemoryStorage, Storage};
+
+use std::ops::{Add, AddAssign, Mul, Sub};
+
 /// Simple 2D coordinate.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 pub struct Point {
     pub x: i32,
     pub y: i32,
@@ -12,6 +20,214 @@ impl Point {
     pub fn new(x: i32, y: i32) -> Self {
         Self { x, y }
     }
+
+    /// Taxicab distance to `other`.
+    pub fn manhattan(self, other: Point) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// Chessboard distance to `other` (diagonal steps count as one).
+    pub fn chebyshev(self, other: Point) -> i32 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    /// Component-wise sign, turning any offset into a single step.
+    pub fn signum(self) -> Point {
+        Point::new(self.x.signum(), self.y.signum())
+    }
+
+    /// The eight surrounding points, clockwise from north.
+    pub fn neighbors(self) -> impl Iterator<Item = Point> {
+        Direction::ALL.into_iter().map(move |d| self + d.delta())
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Point {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Point) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Point {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Mul<i32> for Point {
+    type Output = Point;
+
+    fn mul(self, rhs: i32) -> Point {
+        Point::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+/// One of the eight compass directions. North is negative `y`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    /// Every direction, clockwise from north.
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    /// Unit offset of one step in this direction.
+    pub fn delta(self) -> Point {
+        match self {
+            Direction::North => Point::new(0, -1),
+            Direction::NorthEast => Point::new(1, -1),
+            Direction::East => Point::new(1, 0),
+            Direction::SouthEast => Point::new(1, 1),
+            Direction::South => Point::new(0, 1),
+            Direction::SouthWest => Point::new(-1, 1),
+            Direction::West => Point::new(-1, 0),
+            Direction::NorthWest => Point::new(-1, -1),
+        }
+    }
+
+    /// Direction pointing along `delta`, or `None` for a zero offset.
+    pub fn from_delta(delta: Point) -> Option<Direction> {
+        let step = delta.signum();
+        Direction::ALL.into_iter().find(|d| d.delta() == step)
+    }
+}
+
+/// Axis-aligned rectangle of cells; `x + width` and `y + height` are exclusive.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl Rect {
+    /// Creates a new [`Rect`].
+    pub const fn new(x: i32, y: i32, width: i32, height: i32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Top-left corner.
+    pub fn origin(self) -> Point {
+        Point::new(self.x, self.y)
+    }
+
+    /// First column past the right edge.
+    pub fn right(self) -> i32 {
+        self.x + self.width
+    }
+
+    /// First row past the bottom edge.
+    pub fn bottom(self) -> i32 {
+        self.y + self.height
+    }
+
+    /// Returns `true` when the rectangle covers no cells.
+    pub fn is_empty(self) -> bool {
+        self.width <= 0 || self.height <= 0
+    }
+
+    /// Whether `pt` lies inside the rectangle.
+    pub fn contains(self, pt: Point) -> bool {
+        pt.x >= self.x && pt.x < self.right() && pt.y >= self.y && pt.y < self.bottom()
+    }
+
+    /// Overlapping area of both rectangles, if any.
+    pub fn intersect(self, other: Rect) -> Option<Rect> {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let rect = Rect::new(
+            x,
+            y,
+            self.right().min(other.right()) - x,
+            self.bottom().min(other.bottom()) - y,
+        );
+        (!rect.is_empty()).then_some(rect)
+    }
+
+    /// Moves `pt` onto the nearest cell inside the rectangle.
+    pub fn clamp(self, pt: Point) -> Point {
+        Point::new(
+            pt.x.clamp(self.x, self.right() - 1),
+            pt.y.clamp(self.y, self.bottom() - 1),
+        )
+    }
+
+    /// Converts `pt` to coordinates relative to the top-left corner.
+    pub fn to_local(self, pt: Point) -> Point {
+        pt - self.origin()
+    }
+
+    /// Every cell in row-major order.
+    pub fn points(self) -> impl Iterator<Item = Point> {
+        (self.y..self.bottom())
+            .flat_map(move |y| (self.x..self.right()).map(move |x| Point::new(x, y)))
+    }
+
+    /// The rectangle shrunk by `margin` cells on every side.
+    pub fn inset(self, margin: i32) -> Rect {
+        Rect::new(
+            self.x + margin,
+            self.y + margin,
+            (self.width - 2 * margin).max(0),
+            (self.height - 2 * margin).max(0),
+        )
+    }
+
+    /// Splits off the top `rows` rows, returning them and the rest.
+    pub fn split_top(self, rows: i32) -> (Rect, Rect) {
+        let rows = rows.clamp(0, self.height.max(0));
+        (
+            Rect::new(self.x, self.y, self.width, rows),
+            Rect::new(self.x, self.y + rows, self.width, self.height - rows),
+        )
+    }
+
+    /// Splits off the left `cols` columns, returning them and the rest.
+    pub fn split_left(self, cols: i32) -> (Rect, Rect) {
+        let cols = cols.clamp(0, self.width.max(0));
+        (
+            Rect::new(self.x, self.y, cols, self.height),
+            Rect::new(self.x + cols, self.y, self.width - cols, self.height),
+        )
+    }
+
+    /// Row `i` of the rectangle, counted from the top.
+    pub fn row(self, i: i32) -> Rect {
+        Rect::new(self.x, self.y + i, self.width, 1)
+    }
 }
 
 /// Unified error type for game logic.
@@ -23,6 +239,8 @@ pub enum GameError {
     Io(#[from] std::io::Error),
     #[error("parse error: {0}")]
     Parse(String),
+    #[error("corrupted data: {0}")]
+    Corrupted(String),
 }
 
 pub type GameResult<T> = Result<T, GameError>;
@@ -38,12 +256,112 @@ mod tests {
         assert_eq!(p.y, 3);
     }
 
+    #[test]
+    fn point_arithmetic() {
+        let p = Point::new(2, 3);
+        assert_eq!(p + Point::new(1, -1), Point::new(3, 2));
+        assert_eq!(p - Point::new(1, 1), Point::new(1, 2));
+        assert_eq!(p * 2, Point::new(4, 6));
+        let mut q = p;
+        q += Point::new(-2, 0);
+        assert_eq!(q, Point::new(0, 3));
+    }
+
+    #[test]
+    fn point_distances() {
+        let a = Point::new(1, 1);
+        let b = Point::new(4, -1);
+        assert_eq!(a.manhattan(b), 5);
+        assert_eq!(a.chebyshev(b), 3);
+        assert_eq!((b - a).signum(), Point::new(1, -1));
+    }
+
+    #[test]
+    fn neighbors_surround_point() {
+        let p = Point::new(5, 5);
+        let n: Vec<Point> = p.neighbors().collect();
+        assert_eq!(n.len(), 8);
+        assert!(n.iter().all(|q| q.chebyshev(p) == 1));
+        assert_eq!(n[0], Point::new(5, 4));
+    }
+
+    #[test]
+    fn direction_delta_round_trip() {
+        for dir in Direction::ALL {
+            assert_eq!(Direction::from_delta(dir.delta()), Some(dir));
+        }
+        assert_eq!(
+            Direction::from_delta(Point::new(3, 0)),
+            Some(Direction::East)
+        );
+        assert_eq!(Direction::from_delta(Point::default()), None);
+    }
+
+    #[test]
+    fn rect_contains_and_clamps() {
+        let r = Rect::new(2, 3, 4, 2);
+        assert!(r.contains(Point::new(2, 3)));
+        assert!(r.contains(Point::new(5, 4)));
+        assert!(!r.contains(Point::new(6, 4)));
+        assert!(!r.contains(Point::new(2, 5)));
+        assert_eq!(r.clamp(Point::new(-1, 10)), Point::new(2, 4));
+        assert_eq!(r.to_local(Point::new(4, 4)), Point::new(2, 1));
+    }
+
+    #[test]
+    fn rect_intersection() {
+        let a = Rect::new(0, 0, 5, 5);
+        assert_eq!(
+            a.intersect(Rect::new(3, 2, 5, 5)),
+            Some(Rect::new(3, 2, 2, 3))
+        );
+        assert_eq!(a.intersect(Rect::new(5, 0, 2, 2)), None);
+    }
+
+    #[test]
+    fn rect_splits_for_layout() {
+        let r = Rect::new(2, 1, 10, 6);
+        assert_eq!(r.inset(1), Rect::new(3, 2, 8, 4));
+        assert_eq!(r.inset(4).height, 0);
+        assert_eq!(
+            r.split_top(2),
+            (Rect::new(2, 1, 10, 2), Rect::new(2, 3, 10, 4))
+        );
+        assert_eq!(r.split_top(9).1.height, 0);
+        assert_eq!(
+            r.split_left(3),
+            (Rect::new(2, 1, 3, 6), Rect::new(5, 1, 7, 6))
+        );
+        assert_eq!(r.row(2), Rect::new(2, 3, 10, 1));
+    }
+
+    #[test]
+    fn rect_points_in_row_major_order() {
+        let pts: Vec<Point> = Rect::new(1, 1, 2, 2).points().collect();
+        assert_eq!(
+            pts,
+            vec![
+                Point::new(1, 1),
+                Point::new(2, 1),
+                Point::new(1, 2),
+                Point::new(2, 2)
+            ]
+        );
+        assert_eq!(Rect::new(0, 0, 0, 3).points().count(), 0);
+    }
+
     #[test]
     fn game_error_display_parse() {
         let err = GameError::Parse("oops".into());
         assert_eq!(format!("{}", err), "parse error: oops");
     }
 
+    #[test]
+    fn game_error_display_corrupted() {
+        let err = GameError::Corrupted("checksum mismatch".into());
+        assert_eq!(format!("{}", err), "corrupted data: checksum mismatch");
+    }
+
     #[test]
     fn io_error_conversion() {
         let io_err = std::io::Error::from(std::io::ErrorKind::Other);
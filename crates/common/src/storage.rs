@@ -0,0 +1,348 @@
+//! Pluggable key/value persistence used for saves, codex, config and profile data.
+
+use crate::{GameError, GameResult};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Byte-oriented key/value store. Keys are file-name-like strings such as
+/// `"codex.json"`.
+pub trait Storage {
+    /// Returns the stored bytes, or `None` when `key` does not exist.
+    fn read(&self, key: &str) -> GameResult<Option<Vec<u8>>>;
+    /// Stores `data` under `key`, replacing any previous value at once: a
+    /// crash part way leaves the previous value, never half of each.
+    fn write(&self, key: &str, data: &[u8]) -> GameResult<()>;
+    /// Deletes `key`; missing keys are not an error.
+    fn remove(&self, key: &str) -> GameResult<()>;
+
+    /// Reads `key` as UTF-8 text.
+    fn read_string(&self, key: &str) -> GameResult<Option<String>> {
+        match self.read(key)? {
+            Some(bytes) => String::from_utf8(bytes)
+                .map(Some)
+                .map_err(|_| GameError::Parse(format!("{} is not UTF-8", key))),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Stores each key as a file below a root directory.
+#[derive(Clone, Debug)]
+pub struct FileStorage {
+    root: PathBuf,
+}
+
+impl FileStorage {
+    /// Creates a storage rooted at `root`. Absolute keys ignore the root.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl Default for FileStorage {
+    /// Stores files relative to the working directory.
+    fn default() -> Self {
+        Self::new(".")
+    }
+}
+
+impl Storage for FileStorage {
+    fn read(&self, key: &str) -> GameResult<Option<Vec<u8>>> {
+        match std::fs::read(self.root.join(key)) {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn write(&self, key: &str, data: &[u8]) -> GameResult<()> {
+        // Write beside the file, then rename it over the old one
+        let path = self.root.join(key);
+        let mut temp = path.clone().into_os_string();
+        temp.push(".tmp");
+        let temp = PathBuf::from(temp);
+        let mut file = std::fs::File::create(&temp)?;
+        file.write_all(data)?;
+        file.sync_all()?;
+        std::fs::rename(&temp, &path)?;
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> GameResult<()> {
+        match std::fs::remove_file(self.root.join(key)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Lets several owners share one backend, e.g. the game and its achievements.
+impl<S: Storage + ?Sized> Storage for std::rc::Rc<S> {
+    fn read(&self, key: &str) -> GameResult<Option<Vec<u8>>> {
+        (**self).read(key)
+    }
+
+    fn write(&self, key: &str, data: &[u8]) -> GameResult<()> {
+        (**self).write(key, data)
+    }
+
+    fn remove(&self, key: &str) -> GameResult<()> {
+        (**self).remove(key)
+    }
+}
+
+/// Volatile storage for tests and headless runs.
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    entries: RefCell<BTreeMap<String, Vec<u8>>>,
+}
+
+impl MemoryStorage {
+    /// Returns the stored keys in sorted order.
+    pub fn keys(&self) -> Vec<String> {
+        self.entries.borrow().keys().cloned().collect()
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn read(&self, key: &str) -> GameResult<Option<Vec<u8>>> {
+        Ok(self.entries.borrow().get(key).cloned())
+    }
+
+    fn write(&self, key: &str, data: &[u8]) -> GameResult<()> {
+        self.entries
+            .borrow_mut()
+            .insert(key.to_string(), data.to_vec());
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> GameResult<()> {
+        self.entries.borrow_mut().remove(key);
+        Ok(())
+    }
+}
+
+/// Browser `localStorage` backend. Values are hex encoded because
+/// `localStorage` only holds strings.
+#[cfg(target_arch = "wasm32")]
+#[derive(Clone, Debug, Default)]
+pub struct LocalStorage {
+    prefix: String,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl LocalStorage {
+    /// Creates a backend whose keys are namespaced with `prefix`.
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+        }
+    }
+
+    fn store(&self) -> GameResult<web_sys::Storage> {
+        web_sys::window()
+            .and_then(|w| w.local_storage().ok().flatten())
+            .ok_or(GameError::InvalidOperation)
+    }
+
+    fn full_key(&self, key: &str) -> String {
+        format!("{}{}", self.prefix, key)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Storage for LocalStorage {
+    fn read(&self, key: &str) -> GameResult<Option<Vec<u8>>> {
+        let value = self
+            .store()?
+            .get_item(&self.full_key(key))
+            .map_err(|_| GameError::InvalidOperation)?;
+        value.map(|v| hex_decode(&v)).transpose()
+    }
+
+    fn write(&self, key: &str, data: &[u8]) -> GameResult<()> {
+        self.store()?
+            .set_item(&self.full_key(key), &hex_encode(data))
+            .map_err(|_| GameError::InvalidOperation)
+    }
+
+    fn remove(&self, key: &str) -> GameResult<()> {
+        self.store()?
+            .remove_item(&self.full_key(key))
+            .map_err(|_| GameError::InvalidOperation)
+    }
+}
+
+/// Encodes bytes as lowercase hex.
+pub fn hex_encode(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Decodes a string produced by [`hex_encode`].
+pub fn hex_decode(s: &str) -> GameResult<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return Err(GameError::Parse("odd hex length".into()));
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| {
+            s.get(i..i + 2)
+                .filter(|pair| pair.bytes().all(|b| b.is_ascii_hexdigit()))
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                .ok_or_else(|| GameError::Parse("invalid hex".into()))
+        })
+        .collect()
+}
+
+/// Extension point for cloud saves: mirrors writes to a remote service.
+pub trait RemoteSync {
+    /// Uploads the latest value for `key`.
+    fn push(&self, key: &str, data: &[u8]) -> GameResult<()>;
+    /// Downloads the remote value for `key`, if any.
+    fn pull(&self, key: &str) -> GameResult<Option<Vec<u8>>>;
+}
+
+/// Local storage that mirrors writes to a [`RemoteSync`] and falls back to
+/// the remote copy when a key is missing locally.
+pub struct SyncedStorage<S, R> {
+    local: S,
+    remote: R,
+}
+
+impl<S: Storage, R: RemoteSync> SyncedStorage<S, R> {
+    /// Wraps `local` with remote mirroring.
+    pub fn new(local: S, remote: R) -> Self {
+        Self { local, remote }
+    }
+}
+
+impl<S: Storage, R: RemoteSync> Storage for SyncedStorage<S, R> {
+    fn read(&self, key: &str) -> GameResult<Option<Vec<u8>>> {
+        if let Some(data) = self.local.read(key)? {
+            return Ok(Some(data));
+        }
+        let pulled = self.remote.pull(key)?;
+        if let Some(data) = &pulled {
+            self.local.write(key, data)?;
+        }
+        Ok(pulled)
+    }
+
+    fn write(&self, key: &str, data: &[u8]) -> GameResult<()> {
+        self.local.write(key, data)?;
+        self.remote.push(key, data)
+    }
+
+    fn remove(&self, key: &str) -> GameResult<()> {
+        self.local.remove(key)
+    }
+}
+
+/// Storage used by the game on the current platform.
+pub fn default_storage() -> Box<dyn Storage> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        Box::new(LocalStorage::new("lurhook/"))
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        Box::new(FileStorage::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_round_trip_and_remove() {
+        let s = MemoryStorage::default();
+        assert_eq!(s.read("a").unwrap(), None);
+        s.write("a", b"hello").unwrap();
+        assert_eq!(s.read_string("a").unwrap().as_deref(), Some("hello"));
+        assert_eq!(s.keys(), vec!["a".to_string()]);
+        s.remove("a").unwrap();
+        s.remove("a").unwrap();
+        assert_eq!(s.read("a").unwrap(), None);
+    }
+
+    #[test]
+    fn file_storage_uses_root() {
+        let root = std::env::temp_dir().join("lurhook_storage_test");
+        std::fs::create_dir_all(&root).unwrap();
+        let s = FileStorage::new(&root);
+        s.write("cfg.toml", b"x = 1").unwrap();
+        assert!(root.join("cfg.toml").exists());
+        assert_eq!(s.read("cfg.toml").unwrap().unwrap(), b"x = 1");
+        s.remove("cfg.toml").unwrap();
+        assert_eq!(s.read("cfg.toml").unwrap(), None);
+        s.remove("cfg.toml").unwrap();
+    }
+
+    #[test]
+    fn file_storage_replaces_files_whole() {
+        let root = std::env::temp_dir().join("lurhook_storage_replace_test");
+        std::fs::create_dir_all(&root).unwrap();
+        let s = FileStorage::new(&root);
+        s.write("save.json", b"old save").unwrap();
+        s.write("save.json", b"new").unwrap();
+        assert_eq!(s.read("save.json").unwrap().unwrap(), b"new");
+        assert!(!root.join("save.json.tmp").exists());
+        s.remove("save.json").unwrap();
+    }
+
+    #[test]
+    fn shared_storage_sees_writes() {
+        let shared: std::rc::Rc<dyn Storage> = std::rc::Rc::new(MemoryStorage::default());
+        let other = shared.clone();
+        shared.write("k", b"v").unwrap();
+        assert_eq!(other.read("k").unwrap().unwrap(), b"v");
+    }
+
+    #[test]
+    fn read_string_rejects_invalid_utf8() {
+        let s = MemoryStorage::default();
+        s.write("bin", &[0xff, 0xfe]).unwrap();
+        assert!(matches!(s.read_string("bin"), Err(GameError::Parse(_))));
+    }
+
+    #[test]
+    fn hex_round_trip() {
+        let data = [0u8, 15, 16, 255];
+        assert_eq!(hex_encode(&data), "000f10ff");
+        assert_eq!(hex_decode("000f10ff").unwrap(), data);
+        assert!(hex_decode("abc").is_err());
+        assert!(hex_decode("zz").is_err());
+        assert!(hex_decode("+f").is_err());
+    }
+
+    #[derive(Default)]
+    struct FakeRemote {
+        pushed: RefCell<Vec<String>>,
+        files: MemoryStorage,
+    }
+
+    impl RemoteSync for &FakeRemote {
+        fn push(&self, key: &str, data: &[u8]) -> GameResult<()> {
+            self.pushed.borrow_mut().push(key.to_string());
+            self.files.write(key, data)
+        }
+
+        fn pull(&self, key: &str) -> GameResult<Option<Vec<u8>>> {
+            self.files.read(key)
+        }
+    }
+
+    #[test]
+    fn synced_storage_mirrors_and_pulls() {
+        let remote = FakeRemote::default();
+        remote.files.write("codex.json", b"{}").unwrap();
+        let s = SyncedStorage::new(MemoryStorage::default(), &remote);
+        assert_eq!(s.read("codex.json").unwrap().unwrap(), b"{}");
+        assert_eq!(s.local.read("codex.json").unwrap().unwrap(), b"{}");
+        s.write("save", b"1").unwrap();
+        assert_eq!(*remote.pushed.borrow(), vec!["save".to_string()]);
+    }
+}
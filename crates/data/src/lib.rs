@@ -1,10 +1,13 @@
 //! Data loading utilities for Lurhook.
 
 use common::{GameError, GameResult};
-use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+pub mod validate;
 
 /// Fighting behavior for a fish.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FightStyle {
     /// Sudden large tension spikes.
     Aggressive,
@@ -14,6 +17,93 @@ pub enum FightStyle {
     Evasive,
 }
 
+/// Terrain generated on top of the plain land and water of a map.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Biome {
+    /// Coral in the shallows.
+    CoralReef,
+    /// Kelp swaying in the shallows.
+    KelpForest,
+    /// Low sand banks along the shore.
+    Sandbar,
+    /// Ice floes over deep water.
+    Ice,
+}
+
+impl Biome {
+    /// Whether fish can swim in this biome.
+    pub fn is_water(self) -> bool {
+        matches!(self, Biome::CoralReef | Biome::KelpForest)
+    }
+}
+
+/// Time of year; it turns over every few in-game days.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Season {
+    #[default]
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    /// Every season in calendar order.
+    pub const ALL: [Season; 4] = [
+        Season::Spring,
+        Season::Summer,
+        Season::Autumn,
+        Season::Winter,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Season::Spring => "Spring",
+            Season::Summer => "Summer",
+            Season::Autumn => "Autumn",
+            Season::Winter => "Winter",
+        }
+    }
+
+    /// Surface water temperature in degrees Celsius.
+    pub fn water_temperature(self) -> i32 {
+        match self {
+            Season::Spring => 14,
+            Season::Summer => 22,
+            Season::Autumn => 16,
+            Season::Winter => 8,
+        }
+    }
+}
+
+/// How a legendary fish fights on top of its [`FightStyle`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BossPattern {
+    /// Lunges every few turns whatever the angler does.
+    Surge,
+    /// Sounds for the bottom at the start of every stage.
+    Sound,
+    /// Pulls harder with every stage.
+    Frenzy,
+}
+
+/// Extra rules for the fight with a legendary fish.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct BossFight {
+    /// Stages the fight lasts; each one is a full duration bar.
+    pub stages: u32,
+    pub pattern: BossPattern,
+    /// Tension bonus the equipped rod has to lend to hold the fish.
+    #[serde(default)]
+    pub min_rod: i32,
+    /// Points added to the catch's score.
+    #[serde(default)]
+    pub bonus: i32,
+    /// Codex lore unlocked by landing one.
+    #[serde(default)]
+    pub lore: String,
+}
+
 /// Fish species parameters loaded from JSON.
 #[derive(Clone, Debug, Deserialize)]
 pub struct FishType {
@@ -25,7 +115,71 @@ pub struct FishType {
     pub max_depth: i32,
     pub fight_style: FightStyle,
     /// Marks extremely rare boss fish.
+    #[serde(default)]
     pub legendary: bool,
+    /// Flavor text shown in the codex and when the fish is caught.
+    #[serde(default)]
+    pub description: String,
+    /// Short hint about where the species lives.
+    #[serde(default)]
+    pub habitat_note: String,
+    /// Ids of the species this fish hunts.
+    #[serde(default)]
+    pub diet: Vec<String>,
+    /// Lightest individual in kilograms.
+    #[serde(default = "default_weight")]
+    pub min_weight: f32,
+    /// Heaviest individual in kilograms.
+    #[serde(default = "default_weight")]
+    pub max_weight: f32,
+    /// Biomes the species favours; it spawns and lingers there when it can.
+    #[serde(default)]
+    pub biomes: Vec<Biome>,
+    /// Boss fight rules; only legendary fish have them.
+    #[serde(default)]
+    pub boss: Option<BossFight>,
+    /// Seasons the species spawns in; empty means all year round.
+    #[serde(default)]
+    pub seasons: Vec<Season>,
+}
+
+fn default_weight() -> f32 {
+    1.0
+}
+
+impl FishType {
+    /// Whether the species spawns in `season`.
+    pub fn in_season(&self, season: Season) -> bool {
+        self.seasons.is_empty() || self.seasons.contains(&season)
+    }
+
+    /// Weight of a typical individual, halfway through the range.
+    pub fn average_weight(&self) -> f32 {
+        (self.min_weight + self.max_weight) / 2.0
+    }
+
+    /// Rarity from one star for the commonest species to five for
+    /// legendaries and the rarest.
+    pub fn stars(&self) -> u8 {
+        match self.rarity {
+            _ if self.legendary => 5,
+            r if r >= 0.7 => 1,
+            r if r >= 0.5 => 2,
+            r if r >= 0.3 => 3,
+            r if r >= 0.1 => 4,
+            _ => 5,
+        }
+    }
+
+    /// How heavy `weight` is for this species; 1.0 is average.
+    pub fn size_ratio(&self, weight: f32) -> f32 {
+        let average = self.average_weight();
+        if average > 0.0 {
+            weight / average
+        } else {
+            1.0
+        }
+    }
 }
 
 /// Loads a list of [`FishType`] from the given JSON file path.
@@ -40,89 +194,107 @@ pub fn load_fish_types_embedded() -> GameResult<Vec<FishType>> {
 }
 
 fn parse_fish_json(data: &str) -> GameResult<Vec<FishType>> {
-    // extremely naive JSON parser sufficient for the test asset
-    let mut fishes = Vec::new();
-    for obj in data.split('{').skip(1) {
-        if let Some(body) = obj.split('}').next() {
-            let mut id = String::new();
-            let mut name = String::new();
-            let mut rarity = 0.0;
-            let mut strength = 0;
-            let mut min_depth = 0;
-            let mut max_depth = 0;
-            let mut fight_style = FightStyle::Aggressive;
-            let mut legendary = false;
-            for line in body.lines() {
-                let line = line.trim().trim_end_matches(',');
-                if line.is_empty() {
-                    continue;
-                }
-                let mut parts = line.splitn(2, ':');
-                let key = parts.next().unwrap().trim().trim_matches('"');
-                let val = parts.next().unwrap().trim().trim_matches('"');
-                match key {
-                    "id" => id = val.to_string(),
-                    "name" => name = val.to_string(),
-                    "rarity" => rarity = val.parse().unwrap_or(0.0),
-                    "strength" => strength = val.parse().unwrap_or(0),
-                    "min_depth" => min_depth = val.parse().unwrap_or(0),
-                    "max_depth" => max_depth = val.parse().unwrap_or(0),
-                    "fight_style" => {
-                        fight_style = match val {
-                            "Aggressive" => FightStyle::Aggressive,
-                            "Endurance" => FightStyle::Endurance,
-                            "Evasive" => FightStyle::Evasive,
-                            _ => FightStyle::Aggressive,
-                        }
-                    }
-                    "legendary" => {
-                        legendary = matches!(val, "true" | "1");
-                    }
-                    _ => {}
-                }
-            }
-            if !id.is_empty() {
-                fishes.push(FishType {
-                    id,
-                    name,
-                    rarity,
-                    strength,
-                    min_depth,
-                    max_depth,
-                    fight_style,
-                    legendary,
-                });
-            }
+    let fishes: Vec<FishType> = parse_records("fish", data)?;
+    for fish in &fishes {
+        if fish.rarity <= 0.0 {
+            return Err(GameError::Parse(format!(
+                "fish {}: rarity must be positive, got {}",
+                fish.id, fish.rarity
+            )));
+        }
+        if fish.min_depth > fish.max_depth {
+            return Err(GameError::Parse(format!(
+                "fish {}: min_depth {} is deeper than max_depth {}",
+                fish.id, fish.min_depth, fish.max_depth
+            )));
+        }
+        if fish.boss.is_some() && !fish.legendary {
+            return Err(GameError::Parse(format!(
+                "fish {}: only legendary fish can be bosses",
+                fish.id
+            )));
         }
-    }
-    if fishes.is_empty() {
-        return Err(GameError::InvalidOperation);
     }
     Ok(fishes)
 }
 
+/// Reads a JSON array of records, naming the record that fails to load.
+fn parse_records<T: DeserializeOwned>(what: &str, data: &str) -> GameResult<Vec<T>> {
+    let records: Vec<serde_json::Value> =
+        serde_json::from_str(data).map_err(|e| GameError::Parse(format!("{}: {}", what, e)))?;
+    if records.is_empty() {
+        return Err(GameError::Parse(format!("{}: no records", what)));
+    }
+    records
+        .into_iter()
+        .enumerate()
+        .map(|(i, record)| {
+            let id = record
+                .get("id")
+                .and_then(|id| id.as_str())
+                .unwrap_or("?")
+                .to_string();
+            T::deserialize(record)
+                .map_err(|e| GameError::Parse(format!("{} #{} ({}): {}", what, i + 1, id, e)))
+        })
+        .collect()
+}
+
 pub fn init() {
     println!("Initialized crate: data");
 }
 
 /// Kind of gear item.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
 pub enum ItemKind {
     Rod,
     Reel,
     Lure,
     Food,
+    /// Spool of fresh line that restores a worn line.
+    Line,
+    /// Chum thrown into the water to draw fish to a tile for a while.
+    Bait,
+    /// Fishfinder that shows fish in deep water once equipped.
+    Sonar,
+    /// Kit that restores the rod and reel to full durability.
+    Repair,
+    /// Cast net thrown over shallow water to catch several small fish.
+    Net,
+    /// Line of baited hooks set across the water and hauled in later.
+    Longline,
+    /// Crab pot or fish trap left on a water tile and emptied later.
+    Trap,
+    /// Flask of fresh water that quenches thirst when drunk.
+    Water,
 }
 
 /// Gear item parameters loaded from JSON.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Deserialize)]
 pub struct ItemType {
     pub id: String,
     pub name: String,
     pub kind: ItemKind,
+    #[serde(default)]
     pub tension_bonus: i32,
+    #[serde(default = "default_reel_factor")]
     pub reel_factor: f32,
+    #[serde(default)]
     pub bite_bonus: f32,
+    /// Farthest a cast reaches, in tiles, with this item as the reel.
+    #[serde(default = "default_cast_range")]
+    pub max_cast_range: i32,
+}
+
+/// Cast range of reels that do not set one, and without a reel.
+pub const DEFAULT_CAST_RANGE: i32 = 6;
+
+fn default_reel_factor() -> f32 {
+    1.0
+}
+
+fn default_cast_range() -> i32 {
+    DEFAULT_CAST_RANGE
 }
 
 /// Loads a list of [`ItemType`] from the given JSON file path.
@@ -137,57 +309,449 @@ pub fn load_item_types_embedded() -> GameResult<Vec<ItemType>> {
 }
 
 fn parse_item_json(data: &str) -> GameResult<Vec<ItemType>> {
-    let mut items = Vec::new();
-    for obj in data.split('{').skip(1) {
-        if let Some(body) = obj.split('}').next() {
-            let mut id = String::new();
-            let mut name = String::new();
-            let mut kind = ItemKind::Rod;
-            let mut tension_bonus = 0;
-            let mut reel_factor = 1.0;
-            let mut bite_bonus = 0.0;
-            for line in body.lines() {
-                let line = line.trim().trim_end_matches(',');
-                if line.is_empty() {
-                    continue;
-                }
-                let mut parts = line.splitn(2, ':');
-                let key = parts.next().unwrap().trim().trim_matches('"');
-                let val = parts.next().unwrap().trim().trim_matches('"');
-                match key {
-                    "id" => id = val.to_string(),
-                    "name" => name = val.to_string(),
-                    "kind" => {
-                        kind = match val {
-                            "Rod" => ItemKind::Rod,
-                            "Reel" => ItemKind::Reel,
-                            "Lure" => ItemKind::Lure,
-                            "Food" => ItemKind::Food,
-                            _ => ItemKind::Rod,
-                        }
-                    }
-                    "tension_bonus" => tension_bonus = val.parse().unwrap_or(0),
-                    "reel_factor" => reel_factor = val.parse().unwrap_or(1.0),
-                    "bite_bonus" => bite_bonus = val.parse().unwrap_or(0.0),
-                    _ => {}
-                }
-            }
-            if !id.is_empty() {
-                items.push(ItemType {
-                    id,
-                    name,
-                    kind,
-                    tension_bonus,
-                    reel_factor,
-                    bite_bonus,
-                });
-            }
+    parse_records("items", data)
+}
+
+/// An item the merchant sells, with its price and how many are in stock.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct ShopEntry {
+    #[serde(flatten)]
+    pub item: ItemType,
+    /// Price in coins.
+    #[serde(default)]
+    pub price: i32,
+    /// Copies available for the whole run.
+    #[serde(default = "default_stock")]
+    pub stock: u32,
+}
+
+fn default_stock() -> u32 {
+    1
+}
+
+/// Loads the merchant's stock from the given JSON file path.
+pub fn load_shop(path: &str) -> GameResult<Vec<ShopEntry>> {
+    let data = std::fs::read_to_string(path)?;
+    parse_shop_json(&data)
+}
+
+/// Loads the merchant's stock embedded at compile time (used on WASM).
+pub fn load_shop_embedded() -> GameResult<Vec<ShopEntry>> {
+    parse_shop_json(include_str!("../../../assets/shop.json"))
+}
+
+fn parse_shop_json(data: &str) -> GameResult<Vec<ShopEntry>> {
+    parse_records("shop", data)
+}
+
+/// Clean-up passes run over an area's map after the noise lays it out.
+/// Every pass is off by default.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+pub struct GenParams {
+    /// Rounds of cellular-automata smoothing that even out jagged coasts.
+    #[serde(default)]
+    pub smoothing: u32,
+    /// Islands and ponds of fewer tiles than this are filled in with what
+    /// surrounds them; 0 keeps them all.
+    #[serde(default)]
+    pub min_region: usize,
+    /// Whether land gets a ring of beach where it meets the water.
+    #[serde(default)]
+    pub beaches: bool,
+}
+
+/// Fishing area parameters loaded from JSON, listed in progression order.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct AreaType {
+    /// Identifier used by quests, saves and replays, e.g. `DeepSea`.
+    pub id: String,
+    /// Display name, also used as the codex catch location.
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    /// Scales how often hazards appear.
+    #[serde(default = "default_hazard_multiplier")]
+    pub hazard_multiplier: i32,
+    /// Frequency of the coastline noise; higher values give smaller islands.
+    #[serde(default = "default_noise_frequency")]
+    pub noise_frequency: f32,
+    /// Multiplies the depth of every water tile.
+    #[serde(default = "default_depth_scale")]
+    pub depth_scale: f32,
+    /// Ids of the species found here.
+    pub fish: Vec<String>,
+    /// Biomes generated on this area's map.
+    #[serde(default)]
+    pub biomes: Vec<Biome>,
+    /// Area a run has to reach before this one opens; `None` for the first.
+    #[serde(default)]
+    pub requires: Option<String>,
+    /// Whether the sea goes on past the map edges, a map-sized chunk at a
+    /// time.
+    #[serde(default)]
+    pub endless: bool,
+    /// Post-processing of the generated map.
+    #[serde(default)]
+    pub generation: GenParams,
+}
+
+fn default_hazard_multiplier() -> i32 {
+    1
+}
+
+fn default_noise_frequency() -> f32 {
+    0.08
+}
+
+fn default_depth_scale() -> f32 {
+    1.0
+}
+
+impl AreaType {
+    /// A `width` by `height` area with default terrain and no fish.
+    pub fn new(id: &str, width: u32, height: u32) -> Self {
+        Self {
+            id: id.to_string(),
+            name: id.to_string(),
+            width,
+            height,
+            hazard_multiplier: default_hazard_multiplier(),
+            noise_frequency: default_noise_frequency(),
+            depth_scale: default_depth_scale(),
+            fish: Vec::new(),
+            biomes: Vec::new(),
+            requires: None,
+            endless: false,
+            generation: GenParams::default(),
         }
     }
-    if items.is_empty() {
-        return Err(GameError::InvalidOperation);
+
+    /// Whether `fish` can be found in this area.
+    pub fn holds(&self, fish: &FishType) -> bool {
+        self.fish.contains(&fish.id)
+    }
+}
+
+/// Loads a list of [`AreaType`] from the given JSON file path.
+pub fn load_area_types(path: &str) -> GameResult<Vec<AreaType>> {
+    let data = std::fs::read_to_string(path)?;
+    parse_area_json(&data)
+}
+
+/// Loads [`AreaType`] definitions embedded at compile time (used on WASM).
+pub fn load_area_types_embedded() -> GameResult<Vec<AreaType>> {
+    parse_area_json(include_str!("../../../assets/areas.json"))
+}
+
+fn parse_area_json(data: &str) -> GameResult<Vec<AreaType>> {
+    parse_records("areas", data)
+}
+
+/// A school of one species that runs through an area on a schedule,
+/// entering from a map edge and leaving by the far one.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct Migration {
+    pub id: String,
+    /// Id of the area the run passes through.
+    pub area: String,
+    /// Id of the migrating species.
+    pub fish: String,
+    /// Fish in the school.
+    pub school: usize,
+    /// Turns from the start of one run to the next.
+    pub every: u32,
+    /// Turn of the first run.
+    #[serde(default)]
+    pub first: u32,
+    /// Log line when the school arrives.
+    pub announcement: String,
+}
+
+impl Migration {
+    /// Whether a run starts on `turn`.
+    pub fn starts_on(&self, turn: u32) -> bool {
+        turn >= self.first && (turn - self.first).is_multiple_of(self.every)
+    }
+}
+
+/// Loads the migration table from the given JSON file path.
+pub fn load_migrations(path: &str) -> GameResult<Vec<Migration>> {
+    let data = std::fs::read_to_string(path)?;
+    parse_migration_json(&data)
+}
+
+/// Loads the migration table embedded at compile time (used on WASM).
+pub fn load_migrations_embedded() -> GameResult<Vec<Migration>> {
+    parse_migration_json(include_str!("../../../assets/migrations.json"))
+}
+
+fn parse_migration_json(data: &str) -> GameResult<Vec<Migration>> {
+    let runs: Vec<Migration> = parse_records("migrations", data)?;
+    if let Some(run) = runs.iter().find(|m| m.every == 0) {
+        return Err(GameError::Parse(format!(
+            "migration {}: every must be at least 1 turn",
+            run.id
+        )));
     }
-    Ok(items)
+    Ok(runs)
+}
+
+/// Ingredient that any fish fills.
+pub const ANY_FISH: &str = "ANY";
+
+/// What a meal buff improves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BuffKind {
+    /// Added to the reel factor.
+    Reel,
+    /// Added to the maximum line tension.
+    Tension,
+}
+
+/// A temporary boost from a dish.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Buff {
+    pub kind: BuffKind,
+    pub amount: f32,
+    /// Turns the boost lasts.
+    pub turns: u32,
+}
+
+/// A dish cooked at a campfire from a combination of fish.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct Recipe {
+    pub id: String,
+    pub name: String,
+    /// Fish ids the dish takes, one per fish; [`ANY_FISH`] takes any.
+    pub ingredients: Vec<String>,
+    /// Hunger restored by a plain dish.
+    pub hunger: i32,
+    /// HP restored by a plain dish.
+    #[serde(default)]
+    pub hp: i32,
+    #[serde(default)]
+    pub buff: Option<Buff>,
+}
+
+/// Loads the cooking recipes from the given JSON file path.
+pub fn load_recipes(path: &str) -> GameResult<Vec<Recipe>> {
+    let data = std::fs::read_to_string(path)?;
+    parse_recipe_json(&data)
+}
+
+/// Loads the cooking recipes embedded at compile time (used on WASM).
+pub fn load_recipes_embedded() -> GameResult<Vec<Recipe>> {
+    parse_recipe_json(include_str!("../../../assets/recipes.json"))
+}
+
+fn parse_recipe_json(data: &str) -> GameResult<Vec<Recipe>> {
+    parse_records("recipes", data)
+}
+
+/// A starting kit picked on the menu in place of the usual gear. The
+/// first loadout is the usual gear and is always available.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct Loadout {
+    pub id: String,
+    pub name: String,
+    /// Short summary shown on the menu.
+    pub description: String,
+    /// Id of the achievement that unlocks the loadout; none for one
+    /// available from the start.
+    #[serde(default)]
+    pub unlock: Option<String>,
+    /// Item ids of the equipped rod, reel and lure.
+    #[serde(default)]
+    pub rod: Option<String>,
+    #[serde(default)]
+    pub reel: Option<String>,
+    #[serde(default)]
+    pub lure: Option<String>,
+    /// Item ids carried in the bag, repeated for several of one.
+    #[serde(default)]
+    pub items: Vec<String>,
+}
+
+/// Loads the starting loadouts from the given JSON file path.
+pub fn load_loadouts(path: &str) -> GameResult<Vec<Loadout>> {
+    let data = std::fs::read_to_string(path)?;
+    parse_loadout_json(&data)
+}
+
+/// Loads the starting loadouts embedded at compile time (used on WASM).
+pub fn load_loadouts_embedded() -> GameResult<Vec<Loadout>> {
+    parse_loadout_json(include_str!("../../../assets/loadouts.json"))
+}
+
+fn parse_loadout_json(data: &str) -> GameResult<Vec<Loadout>> {
+    parse_records("loadouts", data)
+}
+
+/// Times of day in the order the clock goes through them.
+pub const TIMES_OF_DAY: [&str; 4] = ["Dawn", "Day", "Dusk", "Night"];
+
+/// Kind of map tile an event can happen on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+pub enum Terrain {
+    Land,
+    ShallowWater,
+    DeepWater,
+    CoralReef,
+    KelpForest,
+    Sandbar,
+    Ice,
+}
+
+/// When a scripted event can happen. Empty lists allow anything.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct EventTrigger {
+    /// Tiles the player has to be on.
+    #[serde(default)]
+    pub tiles: Vec<Terrain>,
+    /// Times of day, as in [`TIMES_OF_DAY`].
+    #[serde(default)]
+    pub times: Vec<String>,
+    /// Ids of the areas, as in `areas.json`.
+    #[serde(default)]
+    pub areas: Vec<String>,
+    /// Chance each turn, from 0.0 to 1.0.
+    pub chance: f32,
+    /// First turn of a run it can happen on.
+    #[serde(default)]
+    pub min_turn: u32,
+}
+
+/// What a scripted event or one of its choices does.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(tag = "type")]
+pub enum EventEffect {
+    /// Hit points gained, or lost when negative.
+    Hp { amount: i32 },
+    /// Hunger restored, or lost when negative.
+    Hunger { amount: i32 },
+    /// Tins of canned food found.
+    Food { amount: i32 },
+    /// Coins found, or lost when negative.
+    Coins { amount: i32 },
+    /// An item from `items.json` added to the bag.
+    Item { id: String },
+    /// A hazard near the player; a random kind when none is named.
+    Hazard {
+        #[serde(default)]
+        kind: Option<String>,
+    },
+    /// A line in the message log.
+    Log { text: String },
+}
+
+/// One answer to a scripted event's prompt.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct EventChoice {
+    pub label: String,
+    #[serde(default)]
+    pub effects: Vec<EventEffect>,
+}
+
+/// An encounter from `events.json`: when its trigger fires its text is
+/// logged and its effects applied, then the player picks one of its
+/// choices, if it offers any.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct ScriptedEvent {
+    pub id: String,
+    pub trigger: EventTrigger,
+    /// Log line, and the prompt when there are choices.
+    pub text: String,
+    #[serde(default)]
+    pub effects: Vec<EventEffect>,
+    #[serde(default)]
+    pub choices: Vec<EventChoice>,
+}
+
+/// Loads the scripted events from the given JSON file path.
+pub fn load_events(path: &str) -> GameResult<Vec<ScriptedEvent>> {
+    let data = std::fs::read_to_string(path)?;
+    parse_event_json(&data)
+}
+
+/// Loads the scripted events embedded at compile time (used on WASM).
+pub fn load_events_embedded() -> GameResult<Vec<ScriptedEvent>> {
+    parse_event_json(include_str!("../../../assets/events.json"))
+}
+
+fn parse_event_json(data: &str) -> GameResult<Vec<ScriptedEvent>> {
+    parse_records("events", data)
+}
+
+/// Most rows of a species' art.
+pub const FISH_ART_ROWS: usize = 5;
+/// Most columns of a species' art.
+pub const FISH_ART_WIDTH: usize = 24;
+/// Name of the art shown for species without their own.
+const DEFAULT_ART: &str = "default";
+
+/// ASCII art of the species for the catch card, from `<fish id>.txt` in
+/// `assets/fishart/`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FishArt {
+    art: Vec<(String, Vec<String>)>,
+}
+
+impl FishArt {
+    /// Art for the species `id`, or the default art if it has none.
+    pub fn get(&self, id: &str) -> &[String] {
+        let find = |name: &str| self.art.iter().find(|(n, _)| n == name);
+        find(id)
+            .or_else(|| find(DEFAULT_ART))
+            .map_or(&[], |(_, lines)| lines.as_slice())
+    }
+
+    /// Adds the art `text` under `name`. Art bigger than
+    /// [`FISH_ART_WIDTH`]×[`FISH_ART_ROWS`] is an error.
+    fn add(&mut self, name: &str, text: &str) -> GameResult<()> {
+        let lines: Vec<String> = text.lines().map(|l| l.trim_end().to_string()).collect();
+        let wide = lines.iter().any(|l| l.chars().count() > FISH_ART_WIDTH);
+        if lines.len() > FISH_ART_ROWS || wide {
+            return Err(GameError::Parse(format!(
+                "fish art {} is larger than {}x{}",
+                name, FISH_ART_WIDTH, FISH_ART_ROWS
+            )));
+        }
+        self.art.push((name.to_string(), lines));
+        Ok(())
+    }
+}
+
+/// Loads every `.txt` file in `dir` as fish art named after the file.
+pub fn load_fish_art(dir: &str) -> GameResult<FishArt> {
+    let mut art = FishArt::default();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("txt") {
+            continue;
+        }
+        if let Some(name) = path.file_stem().and_then(|s| s.to_str()) {
+            art.add(name, &std::fs::read_to_string(&path)?)?;
+        }
+    }
+    Ok(art)
+}
+
+/// Loads the fish art embedded at compile time (used on WASM).
+pub fn load_fish_art_embedded() -> GameResult<FishArt> {
+    macro_rules! embedded {
+        ($($name:literal),* $(,)?) => {
+            [$(($name, include_str!(concat!("../../../assets/fishart/", $name, ".txt")))),*]
+        };
+    }
+    let files = embedded!(
+        "default", "TROUT", "LUR1", "SFTU", "SHCR", "CRJE", "IRPI", "TWSA", "AZSH", "GDKO", "PHEL",
+        "RGUP", "MGSP", "NBRY", "STMR",
+    );
+    let mut art = FishArt::default();
+    for (name, text) in files {
+        art.add(name, text)?;
+    }
+    Ok(art)
 }
 
 #[cfg(test)]
@@ -206,10 +770,18 @@ mod tests {
         assert!(!types.is_empty());
     }
 
+    /// One fish record with the required fields plus `extra` ones.
+    fn fish_record(id: &str, extra: &str) -> String {
+        format!(
+            "{{\"id\": \"{}\", \"name\": \"{}\", \"rarity\": 0.5, \"strength\": 3, \"min_depth\": 0, \"max_depth\": 10, \"fight_style\": \"Evasive\"{}}}",
+            id, id, extra
+        )
+    }
+
     #[test]
     fn parse_failure_when_empty() {
-        let res = parse_fish_json("");
-        assert!(matches!(res, Err(GameError::InvalidOperation)));
+        assert!(matches!(parse_fish_json(""), Err(GameError::Parse(_))));
+        assert!(matches!(parse_fish_json("[]"), Err(GameError::Parse(_))));
     }
 
     #[test]
@@ -220,6 +792,153 @@ mod tests {
         assert_eq!(fishes[0].id, "A");
         assert_eq!(fishes[0].fight_style, FightStyle::Aggressive);
         assert!(fishes[0].legendary);
+        assert!(fishes[0].description.is_empty());
+    }
+
+    #[test]
+    fn parse_reformatted_json() {
+        let json = format!(
+            "[{}, {}]",
+            fish_record("A", r#", "description": "Glows: faintly, {at night}.""#),
+            fish_record("B", "")
+        );
+        let fishes = parse_fish_json(&json).expect("fishes");
+        assert_eq!(fishes.len(), 2);
+        assert_eq!(fishes[0].description, "Glows: faintly, {at night}.");
+        assert!(!fishes[1].legendary);
+        assert!(Season::ALL.iter().all(|&s| fishes[1].in_season(s)));
+    }
+
+    #[test]
+    fn parse_spawn_seasons() {
+        let json = format!(
+            "[{}]",
+            fish_record("A", r#", "seasons": ["Summer", "Autumn"]"#)
+        );
+        let fish = &parse_fish_json(&json).expect("fishes")[0];
+        assert!(fish.in_season(Season::Summer) && fish.in_season(Season::Autumn));
+        assert!(!fish.in_season(Season::Winter));
+    }
+
+    #[test]
+    fn parse_boss_fight() {
+        let boss = r#", "legendary": true, "boss": {"stages": 3, "pattern": "Surge", "min_rod": 20, "lore": "Old."}"#;
+        let fishes = parse_fish_json(&format!("[{}]", fish_record("A", boss))).expect("fishes");
+        let fight = fishes[0].boss.as_ref().expect("boss");
+        assert_eq!(fight.stages, 3);
+        assert_eq!(fight.pattern, BossPattern::Surge);
+        assert_eq!(fight.min_rod, 20);
+        assert_eq!(fight.bonus, 0);
+        assert_eq!(fight.lore, "Old.");
+
+        let minor = r#", "boss": {"stages": 2, "pattern": "Sound"}"#;
+        let json = format!("[{}]", fish_record("B", minor));
+        assert!(matches!(parse_fish_json(&json), Err(GameError::Parse(_))));
+    }
+
+    #[test]
+    fn parse_flavor_text() {
+        let json = format!(
+            "[{}]",
+            fish_record(
+                "A",
+                r#", "description": "Glows: faintly, at night.", "habitat_note": "Tide pools.""#
+            )
+        );
+        let fishes = parse_fish_json(&json).expect("fishes");
+        assert_eq!(fishes[0].description, "Glows: faintly, at night.");
+        assert_eq!(fishes[0].habitat_note, "Tide pools.");
+    }
+
+    #[test]
+    fn parse_diet_list() {
+        let json = format!(
+            "[{}, {}]",
+            fish_record("P", r#", "diet": ["A", "B"]"#),
+            fish_record("A", r#", "diet": []"#)
+        );
+        let fishes = parse_fish_json(&json).expect("fishes");
+        assert_eq!(fishes[0].diet, vec!["A".to_string(), "B".to_string()]);
+        assert!(fishes[0].biomes.is_empty());
+        assert!(fishes[1].diet.is_empty());
+    }
+
+    #[test]
+    fn parse_biomes() {
+        let json = format!(
+            "[{}]",
+            fish_record("A", r#", "biomes": ["CoralReef", "KelpForest"]"#)
+        );
+        let fishes = parse_fish_json(&json).expect("fishes");
+        assert_eq!(fishes[0].biomes, [Biome::CoralReef, Biome::KelpForest]);
+        let json = format!("[{}]", fish_record("A", r#", "biomes": ["Lava"]"#));
+        assert!(matches!(parse_fish_json(&json), Err(GameError::Parse(_))));
+    }
+
+    #[test]
+    fn parse_weight_range() {
+        let json = format!(
+            "[{}, {}]",
+            fish_record("A", r#", "min_weight": 0.5, "max_weight": 2.5"#),
+            fish_record("B", "")
+        );
+        let fishes = parse_fish_json(&json).expect("fishes");
+        assert_eq!((fishes[0].min_weight, fishes[0].max_weight), (0.5, 2.5));
+        assert_eq!(fishes[0].average_weight(), 1.5);
+        assert_eq!(fishes[0].size_ratio(3.0), 2.0);
+        assert_eq!((fishes[1].min_weight, fishes[1].max_weight), (1.0, 1.0));
+    }
+
+    #[test]
+    fn parse_errors_name_the_record() {
+        let message = |json: String| match parse_fish_json(&json) {
+            Err(GameError::Parse(msg)) => msg,
+            other => panic!("expected a parse error, got {:?}", other),
+        };
+        let missing = message(r#"[{"id": "A", "name": "A"}]"#.into());
+        assert!(missing.contains("(A)"), "{}", missing);
+        assert!(missing.contains("`rarity`"), "{}", missing);
+        let style = message(format!(
+            "[{}, {}]",
+            fish_record("A", ""),
+            fish_record("B", "").replace("Evasive", "Sleepy")
+        ));
+        assert!(style.contains("#2 (B)"), "{}", style);
+        assert!(style.contains("Sleepy"), "{}", style);
+        let rarity = message(format!("[{}]", fish_record("A", "").replace("0.5", "0.0")));
+        assert!(rarity.contains("rarity"), "{}", rarity);
+        let depth = message(format!(
+            "[{}]",
+            fish_record("A", "").replace("\"min_depth\": 0", "\"min_depth\": 20")
+        ));
+        assert!(depth.contains("min_depth 20"), "{}", depth);
+    }
+
+    #[test]
+    fn bundled_fish_have_flavor_text() {
+        for fish in load_fish_types_embedded().expect("fishes") {
+            assert!(
+                !fish.description.is_empty(),
+                "{} lacks description",
+                fish.id
+            );
+            assert!(!fish.habitat_note.is_empty(), "{} lacks habitat", fish.id);
+        }
+    }
+
+    #[test]
+    fn every_species_has_art_that_fits() {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/../../assets/fishart");
+        let art = load_fish_art(dir).expect("art");
+        let embedded = load_fish_art_embedded().expect("embedded art");
+        let default = art.get("NONE").to_vec();
+        assert!(!default.is_empty());
+        for fish in load_fish_types_embedded().expect("fishes") {
+            assert_ne!(art.get(&fish.id), default, "{} lacks art", fish.id);
+            assert_eq!(art.get(&fish.id), embedded.get(&fish.id));
+        }
+        let mut big = FishArt::default();
+        assert!(big.add("big", &"#".repeat(FISH_ART_WIDTH + 1)).is_err());
     }
 
     #[test]
@@ -243,12 +962,108 @@ mod tests {
 
     #[test]
     fn parse_item_simple() {
-        let json = "[\n  {\n    \"id\": \"I\",\n    \"name\": \"Item\",\n    \"kind\": \"Reel\",\n    \"tension_bonus\": 5,\n    \"reel_factor\": 1.5,\n    \"bite_bonus\": 0.1\n  }\n]";
+        let json = "[\n  {\n    \"id\": \"I\",\n    \"name\": \"Item\",\n    \"kind\": \"Reel\",\n    \"tension_bonus\": 5,\n    \"reel_factor\": 1.5,\n    \"bite_bonus\": 0.1,\n    \"max_cast_range\": 9\n  }\n]";
         let items = parse_item_json(json).expect("items");
         assert_eq!(items.len(), 1);
         assert_eq!(items[0].tension_bonus, 5);
         assert!((items[0].bite_bonus - 0.1).abs() < f32::EPSILON);
         assert_eq!(items[0].kind, ItemKind::Reel);
         assert!((items[0].reel_factor - 1.5).abs() < f32::EPSILON);
+        assert_eq!(items[0].max_cast_range, 9);
+    }
+
+    #[test]
+    fn parse_shop_entries() {
+        let json = "[\n  {\n    \"id\": \"S\",\n    \"name\": \"Spool\",\n    \"kind\": \"Line\",\n    \"price\": 15,\n    \"stock\": 3\n  },\n  {\n    \"id\": \"R\",\n    \"name\": \"Rod\",\n    \"kind\": \"Rod\"\n  }\n]";
+        let shop = parse_shop_json(json).expect("shop");
+        assert_eq!(shop.len(), 2);
+        assert_eq!(shop[0].item.kind, ItemKind::Line);
+        assert_eq!((shop[0].price, shop[0].stock), (15, 3));
+        assert_eq!((shop[1].price, shop[1].stock), (0, 1));
+    }
+
+    #[test]
+    fn parse_area_defaults() {
+        let json = r#"[{"id": "Bay", "name": "Bay", "width": 40, "height": 30, "fish": ["A"]}]"#;
+        let areas = parse_area_json(json).expect("areas");
+        assert_eq!(areas[0].hazard_multiplier, 1);
+        assert_eq!(areas[0].requires, None);
+        let fish = parse_fish_json(&format!("[{}]", fish_record("A", ""))).unwrap();
+        assert!(areas[0].holds(&fish[0]));
+    }
+
+    #[test]
+    fn embedded_areas_load() {
+        let areas = load_area_types_embedded().expect("areas");
+        assert_eq!(areas[0].id, "Coast");
+        assert!(areas[0].requires.is_none());
+    }
+
+    #[test]
+    fn embedded_shop_loads() {
+        let shop = load_shop_embedded().expect("shop");
+        assert!(shop.iter().any(|e| e.item.kind == ItemKind::Line));
+        assert!(shop.iter().any(|e| e.item.kind == ItemKind::Bait));
+        assert!(shop.iter().any(|e| e.item.kind == ItemKind::Sonar));
+        assert!(shop.iter().any(|e| e.item.kind == ItemKind::Repair));
+        assert!(shop.iter().any(|e| e.item.kind == ItemKind::Net));
+        assert!(shop.iter().any(|e| e.item.kind == ItemKind::Longline));
+        assert!(shop.iter().any(|e| e.item.kind == ItemKind::Trap));
+        assert!(shop.iter().any(|e| e.item.kind == ItemKind::Water));
+    }
+
+    #[test]
+    fn load_recipes() {
+        let recipes = load_recipes_embedded().expect("recipes");
+        assert!(recipes
+            .iter()
+            .any(|r| r.ingredients == [ANY_FISH] && r.buff.is_none()));
+        assert!(recipes
+            .iter()
+            .any(|r| r.buff.map(|b| b.kind) == Some(BuffKind::Reel)));
+    }
+
+    #[test]
+    fn migrations_run_on_schedule() {
+        let runs = load_migrations_embedded().expect("migrations");
+        let tuna = runs.iter().find(|m| m.fish == "SFTU").expect("tuna run");
+        assert_eq!(tuna.area, "Offshore");
+        assert!(!tuna.starts_on(tuna.first - 1));
+        assert!(tuna.starts_on(tuna.first));
+        assert!(!tuna.starts_on(tuna.first + 1));
+        assert!(tuna.starts_on(tuna.first + tuna.every));
+        let json = r#"[{"id": "M", "area": "A", "fish": "F", "school": 3, "every": 10,
+            "announcement": "Here they come!"}]"#;
+        let runs = parse_migration_json(json).unwrap();
+        assert!(runs[0].starts_on(0), "first defaults to turn 0");
+        let never = json.replace("\"every\": 10", "\"every\": 0");
+        assert!(matches!(
+            parse_migration_json(&never),
+            Err(GameError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn load_events() {
+        let events = load_events_embedded().expect("events");
+        assert!(events
+            .iter()
+            .any(|e| e.trigger.tiles.contains(&Terrain::Land) && e.choices.is_empty()));
+        assert!(events.iter().any(|e| e.choices.len() > 1));
+        let json = r#"[{"id": "E", "text": "A gull steals a bite.",
+            "trigger": {"chance": 0.5},
+            "effects": [{"type": "Hunger", "amount": -5}, {"type": "Hazard"}]}]"#;
+        let events = parse_event_json(json).unwrap();
+        assert!(events[0].trigger.tiles.is_empty());
+        assert_eq!(
+            events[0].effects,
+            [
+                EventEffect::Hunger { amount: -5 },
+                EventEffect::Hazard { kind: None }
+            ]
+        );
+        let json = r#"[{"id": "E", "text": "?", "trigger": {"chance": 0.5},
+            "effects": [{"type": "Teleport"}]}]"#;
+        assert!(matches!(parse_event_json(json), Err(GameError::Parse(_))));
     }
 }
@@ -0,0 +1,679 @@
+//! Content validation used by the `--check-assets` CLI mode.
+
+use crate::{
+    load_area_types, load_events, load_fish_types, load_item_types, load_loadouts, load_migrations,
+    load_recipes, load_shop, AreaType, EventEffect, FishType, ItemKind, ItemType, Loadout,
+    Migration, Recipe, ScriptedEvent, ShopEntry, ANY_FISH, TIMES_OF_DAY,
+};
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+
+/// Result of loading and validating one data file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AssetCheck {
+    pub file: String,
+    /// Number of entries that were loaded.
+    pub entries: usize,
+    pub errors: Vec<String>,
+}
+
+/// Validation results for every data file.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AssetReport {
+    pub checks: Vec<AssetCheck>,
+}
+
+impl AssetReport {
+    /// Returns `true` when no file reported an error.
+    pub fn is_ok(&self) -> bool {
+        self.checks.iter().all(|c| c.errors.is_empty())
+    }
+
+    /// Total number of errors across all files.
+    pub fn error_count(&self) -> usize {
+        self.checks.iter().map(|c| c.errors.len()).sum()
+    }
+}
+
+impl fmt::Display for AssetReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for check in &self.checks {
+            let status = if check.errors.is_empty() {
+                "ok"
+            } else {
+                "FAIL"
+            };
+            writeln!(f, "[{}] {} ({} entries)", status, check.file, check.entries)?;
+            for err in &check.errors {
+                writeln!(f, "  - {}", err)?;
+            }
+        }
+        if self.is_ok() {
+            writeln!(f, "All assets valid.")
+        } else {
+            writeln!(f, "{} error(s) found.", self.error_count())
+        }
+    }
+}
+
+fn duplicate_ids<'a>(ids: impl Iterator<Item = &'a str>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    ids.filter(|id| !seen.insert(*id))
+        .map(|id| format!("{}: duplicate id", id))
+        .collect()
+}
+
+/// Checks fish definitions for values the game cannot use.
+pub fn validate_fish(fishes: &[FishType]) -> Vec<String> {
+    let mut errors = duplicate_ids(fishes.iter().map(|f| f.id.as_str()));
+    for fish in fishes {
+        if fish.name.is_empty() {
+            errors.push(format!("{}: empty name", fish.id));
+        }
+        if !(fish.rarity > 0.0 && fish.rarity <= 1.0) {
+            errors.push(format!("{}: rarity {} not in (0, 1]", fish.id, fish.rarity));
+        }
+        if fish.strength <= 0 {
+            errors.push(format!("{}: strength must be positive", fish.id));
+        }
+        if fish.min_depth < 0 || fish.min_depth > fish.max_depth {
+            errors.push(format!(
+                "{}: invalid depth range {}-{}",
+                fish.id, fish.min_depth, fish.max_depth
+            ));
+        }
+        if fish.min_weight <= 0.0 || fish.min_weight > fish.max_weight {
+            errors.push(format!(
+                "{}: invalid weight range {}-{}",
+                fish.id, fish.min_weight, fish.max_weight
+            ));
+        }
+        for biome in fish.biomes.iter().filter(|b| !b.is_water()) {
+            errors.push(format!("{}: {:?} is not a water biome", fish.id, biome));
+        }
+        if let Some(boss) = &fish.boss {
+            if boss.stages == 0 {
+                errors.push(format!(
+                    "{}: a boss fight needs at least one stage",
+                    fish.id
+                ));
+            }
+            if boss.min_rod < 0 || boss.bonus < 0 {
+                errors.push(format!("{}: negative boss requirement or bonus", fish.id));
+            }
+        }
+        for prey in &fish.diet {
+            if prey == &fish.id {
+                errors.push(format!("{}: cannot eat itself", fish.id));
+            } else if !fishes.iter().any(|f| &f.id == prey) {
+                errors.push(format!("{}: unknown prey {}", fish.id, prey));
+            }
+        }
+    }
+    errors
+}
+
+/// Checks item definitions for values the game cannot use.
+pub fn validate_items(items: &[ItemType]) -> Vec<String> {
+    let mut errors = duplicate_ids(items.iter().map(|i| i.id.as_str()));
+    for item in items {
+        if item.name.is_empty() {
+            errors.push(format!("{}: empty name", item.id));
+        }
+        if item.reel_factor <= 0.0 {
+            errors.push(format!("{}: reel_factor must be positive", item.id));
+        }
+        if !(0.0..=1.0).contains(&item.bite_bonus) {
+            errors.push(format!(
+                "{}: bite_bonus {} not in [0, 1]",
+                item.id, item.bite_bonus
+            ));
+        }
+        if item.max_cast_range <= 0 {
+            errors.push(format!("{}: max_cast_range must be positive", item.id));
+        }
+    }
+    errors
+}
+
+/// Checks the merchant's stock: valid items at a positive price.
+pub fn validate_shop(entries: &[ShopEntry]) -> Vec<String> {
+    let items: Vec<ItemType> = entries.iter().map(|e| e.item.clone()).collect();
+    let mut errors = validate_items(&items);
+    for entry in entries {
+        if entry.price <= 0 {
+            errors.push(format!("{}: price must be positive", entry.item.id));
+        }
+        if entry.stock == 0 {
+            errors.push(format!("{}: out of stock", entry.item.id));
+        }
+    }
+    errors
+}
+
+/// Checks area definitions: usable maps and a `requires` chain that only
+/// points back to earlier areas.
+pub fn validate_areas(areas: &[AreaType]) -> Vec<String> {
+    let mut errors = duplicate_ids(areas.iter().map(|a| a.id.as_str()));
+    for (i, area) in areas.iter().enumerate() {
+        if area.name.is_empty() {
+            errors.push(format!("{}: empty name", area.id));
+        }
+        if area.width == 0 || area.height == 0 {
+            errors.push(format!(
+                "{}: invalid size {}x{}",
+                area.id, area.width, area.height
+            ));
+        }
+        if area.hazard_multiplier < 0 {
+            errors.push(format!("{}: hazard_multiplier is negative", area.id));
+        }
+        if area.noise_frequency <= 0.0 || area.depth_scale <= 0.0 {
+            errors.push(format!(
+                "{}: noise_frequency and depth_scale must be positive",
+                area.id
+            ));
+        }
+        if area.fish.is_empty() {
+            errors.push(format!("{}: no fish", area.id));
+        }
+        if let Some(req) = &area.requires {
+            if !areas[..i].iter().any(|a| &a.id == req) {
+                errors.push(format!(
+                    "{}: requires unknown or later area {}",
+                    area.id, req
+                ));
+            }
+        }
+    }
+    errors
+}
+
+/// Checks recipes: named dishes that take fish and feed the player.
+pub fn validate_recipes(recipes: &[Recipe]) -> Vec<String> {
+    let mut errors = duplicate_ids(recipes.iter().map(|r| r.id.as_str()));
+    for recipe in recipes {
+        if recipe.name.is_empty() {
+            errors.push(format!("{}: empty name", recipe.id));
+        }
+        if recipe.ingredients.is_empty() {
+            errors.push(format!("{}: no ingredients", recipe.id));
+        }
+        if recipe.hunger < 0 || recipe.hp < 0 {
+            errors.push(format!("{}: hunger and hp must not be negative", recipe.id));
+        }
+        if let Some(buff) = recipe.buff {
+            if buff.amount <= 0.0 || buff.turns == 0 {
+                errors.push(format!("{}: buff must be positive", recipe.id));
+            }
+        }
+    }
+    errors
+}
+
+/// Checks migrations: announced schools of at least one fish that come
+/// round again.
+pub fn validate_migrations(migrations: &[Migration]) -> Vec<String> {
+    let mut errors = duplicate_ids(migrations.iter().map(|m| m.id.as_str()));
+    for migration in migrations {
+        if migration.school == 0 {
+            errors.push(format!("{}: empty school", migration.id));
+        }
+        if migration.every == 0 {
+            errors.push(format!("{}: every must be positive", migration.id));
+        }
+        if migration.announcement.is_empty() {
+            errors.push(format!("{}: empty announcement", migration.id));
+        }
+    }
+    errors
+}
+
+/// Checks starting loadouts: named kits, the first of them available from
+/// the start.
+pub fn validate_loadouts(loadouts: &[Loadout]) -> Vec<String> {
+    let mut errors = duplicate_ids(loadouts.iter().map(|l| l.id.as_str()));
+    for loadout in loadouts {
+        if loadout.name.is_empty() {
+            errors.push(format!("{}: empty name", loadout.id));
+        }
+    }
+    if let Some(first) = loadouts.first().filter(|l| l.unlock.is_some()) {
+        errors.push(format!(
+            "{}: the first loadout must not need unlocking",
+            first.id
+        ));
+    }
+    errors
+}
+
+/// Most choices an event can offer, one per number key.
+const MAX_EVENT_CHOICES: usize = 9;
+
+/// Checks scripted events: logged text, a chance per turn, known times of
+/// day and a prompt with a key for each choice.
+pub fn validate_events(events: &[ScriptedEvent]) -> Vec<String> {
+    let mut errors = duplicate_ids(events.iter().map(|e| e.id.as_str()));
+    for event in events {
+        if event.text.is_empty() {
+            errors.push(format!("{}: empty text", event.id));
+        }
+        if !(event.trigger.chance > 0.0 && event.trigger.chance <= 1.0) {
+            errors.push(format!("{}: chance must be in (0, 1]", event.id));
+        }
+        for time in &event.trigger.times {
+            if !TIMES_OF_DAY.contains(&time.as_str()) {
+                errors.push(format!("{}: unknown time of day {}", event.id, time));
+            }
+        }
+        if event.choices.len() == 1 || event.choices.len() > MAX_EVENT_CHOICES {
+            errors.push(format!(
+                "{}: needs 2 to {} choices or none",
+                event.id, MAX_EVENT_CHOICES
+            ));
+        }
+        for choice in &event.choices {
+            if choice.label.is_empty() {
+                errors.push(format!("{}: empty choice label", event.id));
+            }
+        }
+    }
+    errors
+}
+
+/// Event areas and items that `areas` and `items` do not define.
+fn unknown_event_refs(
+    events: &[ScriptedEvent],
+    areas: &[AreaType],
+    items: &[ItemType],
+) -> Vec<String> {
+    let mut errors = Vec::new();
+    for event in events {
+        for area in &event.trigger.areas {
+            if !areas.iter().any(|a| &a.id == area) {
+                errors.push(format!("{}: unknown area {}", event.id, area));
+            }
+        }
+        let effects = event
+            .effects
+            .iter()
+            .chain(event.choices.iter().flat_map(|c| &c.effects));
+        for effect in effects {
+            if let EventEffect::Item { id } = effect {
+                if !items.iter().any(|i| &i.id == id) {
+                    errors.push(format!("{}: unknown item {}", event.id, id));
+                }
+            }
+        }
+    }
+    errors
+}
+
+/// Loadout gear that `items` does not define, or that goes in the wrong
+/// slot.
+fn unknown_loadout_items(loadouts: &[Loadout], items: &[ItemType]) -> Vec<String> {
+    let mut errors = Vec::new();
+    for loadout in loadouts {
+        let slots = [
+            (&loadout.rod, Some(ItemKind::Rod)),
+            (&loadout.reel, Some(ItemKind::Reel)),
+            (&loadout.lure, Some(ItemKind::Lure)),
+        ];
+        let bag = loadout.items.iter().map(|id| (id, None));
+        let gear = slots
+            .into_iter()
+            .filter_map(|(id, kind)| id.as_ref().map(|id| (id, kind)));
+        for (id, kind) in gear.chain(bag) {
+            match items.iter().find(|i| &i.id == id) {
+                None => errors.push(format!("{}: unknown item {}", loadout.id, id)),
+                Some(item) if kind.is_some_and(|k| k != item.kind) => errors.push(format!(
+                    "{}: {} is not a {:?}",
+                    loadout.id,
+                    id,
+                    kind.unwrap()
+                )),
+                Some(_) => {}
+            }
+        }
+    }
+    errors
+}
+
+/// Migration areas and species that `areas` and `fishes` do not define.
+fn unknown_migration_refs(
+    migrations: &[Migration],
+    areas: &[AreaType],
+    fishes: &[FishType],
+) -> Vec<String> {
+    let mut errors = Vec::new();
+    for m in migrations {
+        if !areas.iter().any(|a| a.id == m.area) {
+            errors.push(format!("{}: unknown area {}", m.id, m.area));
+        }
+        if !fishes.iter().any(|f| f.id == m.fish) {
+            errors.push(format!("{}: unknown fish {}", m.id, m.fish));
+        }
+    }
+    errors
+}
+
+/// Recipe ingredients that `fishes` does not define.
+fn unknown_recipe_fish(recipes: &[Recipe], fishes: &[FishType]) -> Vec<String> {
+    recipes
+        .iter()
+        .flat_map(|r| r.ingredients.iter().map(move |id| (r, id)))
+        .filter(|(_, id)| *id != ANY_FISH && !fishes.iter().any(|f| &f.id == *id))
+        .map(|(r, id)| format!("{}: unknown fish {}", r.id, id))
+        .collect()
+}
+
+/// Area fish that `fishes` does not define.
+fn unknown_area_fish(areas: &[AreaType], fishes: &[FishType]) -> Vec<String> {
+    areas
+        .iter()
+        .flat_map(|a| a.fish.iter().map(move |id| (a, id)))
+        .filter(|(_, id)| !fishes.iter().any(|f| &f.id == *id))
+        .map(|(a, id)| format!("{}: unknown fish {}", a.id, id))
+        .collect()
+}
+
+fn check_file<T>(
+    dir: &Path,
+    file: &str,
+    load: fn(&str) -> common::GameResult<Vec<T>>,
+    validate: fn(&[T]) -> Vec<String>,
+) -> AssetCheck {
+    let path = dir.join(file);
+    match load(&path.to_string_lossy()) {
+        Ok(entries) => AssetCheck {
+            file: file.to_string(),
+            entries: entries.len(),
+            errors: validate(&entries),
+        },
+        Err(e) => AssetCheck {
+            file: file.to_string(),
+            entries: 0,
+            errors: vec![format!("failed to load: {}", e)],
+        },
+    }
+}
+
+/// Loads and validates every data file found in `dir`.
+pub fn check_assets(dir: impl AsRef<Path>) -> AssetReport {
+    let dir = dir.as_ref();
+    let mut areas = check_file(dir, "areas.json", load_area_types, validate_areas);
+    let load = |file: &str| dir.join(file).to_string_lossy().into_owned();
+    if let (Ok(list), Ok(fishes)) = (
+        load_area_types(&load("areas.json")),
+        load_fish_types(&load("fish.json")),
+    ) {
+        areas.errors.extend(unknown_area_fish(&list, &fishes));
+    }
+    let mut recipes = check_file(dir, "recipes.json", load_recipes, validate_recipes);
+    if let (Ok(list), Ok(fishes)) = (
+        load_recipes(&load("recipes.json")),
+        load_fish_types(&load("fish.json")),
+    ) {
+        recipes.errors.extend(unknown_recipe_fish(&list, &fishes));
+    }
+    let mut migrations = check_file(dir, "migrations.json", load_migrations, validate_migrations);
+    if let (Ok(list), Ok(areas), Ok(fishes)) = (
+        load_migrations(&load("migrations.json")),
+        load_area_types(&load("areas.json")),
+        load_fish_types(&load("fish.json")),
+    ) {
+        migrations
+            .errors
+            .extend(unknown_migration_refs(&list, &areas, &fishes));
+    }
+    let mut loadouts = check_file(dir, "loadouts.json", load_loadouts, validate_loadouts);
+    if let (Ok(list), Ok(items)) = (
+        load_loadouts(&load("loadouts.json")),
+        load_item_types(&load("items.json")),
+    ) {
+        loadouts.errors.extend(unknown_loadout_items(&list, &items));
+    }
+    let mut events = check_file(dir, "events.json", load_events, validate_events);
+    if let (Ok(list), Ok(areas), Ok(items)) = (
+        load_events(&load("events.json")),
+        load_area_types(&load("areas.json")),
+        load_item_types(&load("items.json")),
+    ) {
+        events
+            .errors
+            .extend(unknown_event_refs(&list, &areas, &items));
+    }
+    AssetReport {
+        checks: vec![
+            check_file(dir, "fish.json", load_fish_types, validate_fish),
+            check_file(dir, "items.json", load_item_types, validate_items),
+            check_file(dir, "shop.json", load_shop, validate_shop),
+            areas,
+            recipes,
+            migrations,
+            loadouts,
+            events,
+        ],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load_fish_types_embedded, FightStyle, ItemKind, DEFAULT_CAST_RANGE};
+
+    fn fish(id: &str) -> FishType {
+        FishType {
+            id: id.into(),
+            name: id.into(),
+            rarity: 0.5,
+            strength: 3,
+            min_depth: 0,
+            max_depth: 10,
+            fight_style: FightStyle::Endurance,
+            legendary: false,
+            description: String::new(),
+            habitat_note: String::new(),
+            diet: Vec::new(),
+            min_weight: 1.0,
+            max_weight: 1.0,
+            biomes: Vec::new(),
+            boss: None,
+            seasons: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn bundled_assets_are_valid() {
+        let report = check_assets(concat!(env!("CARGO_MANIFEST_DIR"), "/../../assets"));
+        assert!(report.is_ok(), "{}", report);
+        assert_eq!(
+            report.checks[0].entries,
+            load_fish_types_embedded().unwrap().len()
+        );
+    }
+
+    #[test]
+    fn fish_errors_are_reported() {
+        let mut bad = fish("A");
+        bad.rarity = 0.0;
+        bad.min_depth = 20;
+        bad.max_weight = 0.5;
+        let errors = validate_fish(&[bad, fish("A")]);
+        assert_eq!(errors.len(), 4);
+        assert!(errors[0].contains("duplicate"));
+    }
+
+    #[test]
+    fn fish_biomes_must_be_water() {
+        let mut eel = fish("E");
+        eel.biomes = vec![crate::Biome::KelpForest, crate::Biome::Ice];
+        let errors = validate_fish(&[eel]);
+        assert_eq!(errors, ["E: Ice is not a water biome"]);
+    }
+
+    #[test]
+    fn diet_must_name_other_known_species() {
+        let mut pike = fish("P");
+        pike.diet = vec!["A".into(), "P".into(), "X".into()];
+        let errors = validate_fish(&[pike, fish("A")]);
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].contains("itself"));
+        assert!(errors[1].contains("unknown prey X"));
+    }
+
+    #[test]
+    fn item_errors_are_reported() {
+        let item = ItemType {
+            id: "I".into(),
+            name: String::new(),
+            kind: ItemKind::Lure,
+            tension_bonus: 0,
+            reel_factor: 0.0,
+            bite_bonus: 2.0,
+            max_cast_range: 0,
+        };
+        assert_eq!(validate_items(&[item]).len(), 4);
+    }
+
+    #[test]
+    fn shop_errors_are_reported() {
+        let entry = ShopEntry {
+            item: ItemType {
+                id: "S".into(),
+                name: "Spool".into(),
+                kind: ItemKind::Line,
+                tension_bonus: 0,
+                reel_factor: 1.0,
+                bite_bonus: 0.0,
+                max_cast_range: DEFAULT_CAST_RANGE,
+            },
+            price: 0,
+            stock: 0,
+        };
+        let errors = validate_shop(&[entry.clone(), entry]);
+        assert_eq!(errors.len(), 5);
+        assert!(errors[0].contains("duplicate"));
+    }
+
+    fn area(id: &str, requires: Option<&str>) -> AreaType {
+        AreaType {
+            fish: vec!["A".into()],
+            requires: requires.map(String::from),
+            ..AreaType::new(id, 10, 10)
+        }
+    }
+
+    #[test]
+    fn area_errors_are_reported() {
+        let mut bad = area("B", Some("C"));
+        bad.width = 0;
+        bad.fish.clear();
+        let errors = validate_areas(&[area("A", None), bad, area("C", Some("A"))]);
+        assert_eq!(errors.len(), 3);
+        assert!(errors[2].contains("later area C"));
+        let errors = unknown_area_fish(&[area("A", None)], &[fish("X")]);
+        assert_eq!(errors, ["A: unknown fish A"]);
+    }
+
+    #[test]
+    fn recipe_errors_are_reported() {
+        let recipe = Recipe {
+            id: "R".into(),
+            name: "Stew".into(),
+            ingredients: vec!["A".into(), "X".into(), ANY_FISH.into()],
+            hunger: -1,
+            hp: 0,
+            buff: Some(crate::Buff {
+                kind: crate::BuffKind::Reel,
+                amount: 0.5,
+                turns: 0,
+            }),
+        };
+        let errors = validate_recipes(std::slice::from_ref(&recipe));
+        assert_eq!(errors.len(), 2);
+        let errors = unknown_recipe_fish(&[recipe], &[fish("A")]);
+        assert_eq!(errors, ["R: unknown fish X"]);
+    }
+
+    #[test]
+    fn migration_errors_are_reported() {
+        let run = Migration {
+            id: "M".into(),
+            area: "A".into(),
+            fish: "X".into(),
+            school: 0,
+            every: 0,
+            first: 0,
+            announcement: "Here they come!".into(),
+        };
+        let errors = validate_migrations(&[run.clone(), run.clone()]);
+        assert_eq!(errors.len(), 5);
+        assert!(errors[0].contains("duplicate"));
+        let errors = unknown_migration_refs(&[run], &[area("A", None)], &[fish("A")]);
+        assert_eq!(errors, ["M: unknown fish X"]);
+    }
+
+    #[test]
+    fn loadout_errors_are_reported() {
+        let kit = |id: &str, unlock: Option<&str>| Loadout {
+            id: id.into(),
+            name: id.into(),
+            description: String::new(),
+            unlock: unlock.map(str::to_string),
+            rod: Some("LURE".into()),
+            reel: None,
+            lure: None,
+            items: vec!["X".into()],
+        };
+        let errors = validate_loadouts(&[kit("A", Some("first_catch")), kit("A", None)]);
+        assert_eq!(errors.len(), 2);
+        assert!(errors[1].contains("must not need unlocking"));
+        let lure = ItemType {
+            id: "LURE".into(),
+            name: "Lure".into(),
+            kind: ItemKind::Lure,
+            tension_bonus: 0,
+            reel_factor: 1.0,
+            bite_bonus: 0.1,
+            max_cast_range: DEFAULT_CAST_RANGE,
+        };
+        let errors = unknown_loadout_items(&[kit("A", None)], &[lure]);
+        assert_eq!(errors, ["A: LURE is not a Rod", "A: unknown item X"]);
+    }
+
+    #[test]
+    fn event_errors_are_reported() {
+        let event = ScriptedEvent {
+            id: "E".into(),
+            trigger: crate::EventTrigger {
+                tiles: Vec::new(),
+                times: vec!["Noon".into()],
+                areas: vec!["X".into()],
+                chance: 0.0,
+                min_turn: 0,
+            },
+            text: "A crate drifts by.".into(),
+            effects: vec![EventEffect::Item { id: "X".into() }],
+            choices: vec![crate::EventChoice {
+                label: "Open it".into(),
+                effects: Vec::new(),
+            }],
+        };
+        let errors = validate_events(&[event.clone(), event.clone()]);
+        assert_eq!(errors.len(), 7);
+        assert!(errors[0].contains("duplicate"));
+        assert!(errors[2].contains("unknown time of day Noon"));
+        let errors = unknown_event_refs(&[event], &[area("A", None)], &[]);
+        assert_eq!(errors, ["E: unknown area X", "E: unknown item X"]);
+    }
+
+    #[test]
+    fn missing_directory_fails_report() {
+        let report = check_assets("/nonexistent/lurhook-assets");
+        assert!(!report.is_ok());
+        assert_eq!(report.error_count(), 8);
+        assert!(report.to_string().contains("[FAIL] fish.json"));
+    }
+}
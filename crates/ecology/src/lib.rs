@@ -1,46 +1,369 @@
 //! Ecology system stubs.
+pub mod migration;
+pub mod population;
+pub mod spatial;
+
 use bracket_lib::prelude::RandomNumberGenerator;
 use common::{GameError, GameResult, Point};
-use data::FishType;
+use data::{FishType, Season};
 use mapgen::{Map, TileKind};
+use spatial::SpatialIndex;
 
 /// Fish entity placeholder.
 #[derive(Clone, Debug)]
 pub struct Fish {
     pub kind: FishType,
     pub position: Point,
+    /// Hunger from 0 (just fed) to [`MAX_FISH_HUNGER`].
+    pub hunger: i32,
+    /// This individual's weight in kilograms.
+    pub weight: f32,
+    /// Turns left swimming away from [`Fish::flee_from`].
+    pub spooked: u8,
+    /// Where the disturbance that last spooked this fish was.
+    pub flee_from: Point,
+    /// Whether the fish is passing through with a migrating school (see
+    /// [`migration::Run`]) rather than living here.
+    pub migrating: bool,
+}
+
+/// How eagerly a fish is looking for food.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FeedingState {
+    /// Recently fed; ignores food.
+    Sated,
+    /// Drifts toward food it notices.
+    Foraging,
+    /// Starving; bites readily.
+    Ravenous,
+}
+
+impl Fish {
+    /// Creates an average-sized fish halfway between meals.
+    pub fn new(kind: FishType, position: Point) -> Self {
+        Self {
+            weight: kind.average_weight(),
+            kind,
+            position,
+            hunger: MAX_FISH_HUNGER / 2,
+            spooked: 0,
+            flee_from: position,
+            migrating: false,
+        }
+    }
+
+    /// How heavy this fish is for its species; 1.0 is average.
+    pub fn size_ratio(&self) -> f32 {
+        self.kind.size_ratio(self.weight)
+    }
+
+    /// Whether `tile` is one of the biomes this fish's species favours.
+    pub fn favours(&self, tile: TileKind) -> bool {
+        tile.biome().is_some_and(|b| self.kind.biomes.contains(&b))
+    }
+
+    /// Whether this fish hunts `other`'s species.
+    pub fn preys_on(&self, other: &Fish) -> bool {
+        self.kind.diet.contains(&other.kind.id)
+    }
+
+    /// Extra spook distance; evasive species are the most skittish.
+    fn skittishness(&self) -> i32 {
+        match self.kind.fight_style {
+            data::FightStyle::Evasive => SKITTISH_BONUS,
+            _ => 0,
+        }
+    }
+
+    /// Feeding state derived from hunger.
+    pub fn feeding_state(&self) -> FeedingState {
+        if self.hunger < SATED_BELOW {
+            FeedingState::Sated
+        } else if self.hunger < RAVENOUS_FROM {
+            FeedingState::Foraging
+        } else {
+            FeedingState::Ravenous
+        }
+    }
 }
 
 const SCHOOL_RADIUS: i32 = 4;
+/// Upper bound of [`Fish::hunger`].
+pub const MAX_FISH_HUNGER: i32 = 100;
+const SATED_BELOW: i32 = 30;
+const RAVENOUS_FROM: i32 = 70;
+/// Distance at which hungry fish notice bait.
+const FOOD_RADIUS: i32 = 8;
+/// Distance from which chum draws fish, hungry or not.
+pub const CHUM_RADIUS: i32 = 10;
+/// Distance searched for shallows when foraging at dawn and dusk.
+const SHALLOWS_RADIUS: i32 = 4;
+/// Hunger regained per turn spent in the shallows at dawn or dusk.
+const FORAGE_AMOUNT: i32 = 5;
+/// Distance at which hungry predators start chasing prey.
+const HUNT_RADIUS: i32 = 6;
+/// Distance at which prey notice a predator and flee.
+const FLEE_RADIUS: i32 = 4;
+/// Distance at which fish notice an angler and swim off.
+const ANGLER_SPOOK_RADIUS: i32 = 2;
+/// Distance at which a splash spooks fish.
+const SPLASH_SPOOK_RADIUS: i32 = 4;
+/// Added to both spook distances for skittish species.
+const SKITTISH_BONUS: i32 = 2;
+/// Turns a spooked fish keeps swimming away.
+const SPOOK_TURNS: u8 = 3;
+
+/// What the fish sense around them this turn.
+#[derive(Clone, Copy, Debug)]
+pub struct Surroundings<'a> {
+    /// `Dawn`, `Day`, `Dusk` or `Night`.
+    pub time_of_day: &'a str,
+    /// Bait and chum in the water.
+    pub food: &'a [Food],
+    /// Extra speed, e.g. from rough weather.
+    pub speed_bonus: i32,
+    /// Where anglers stand; fish that come close are spooked.
+    pub anglers: &'a [Point],
+    /// Splashes this turn, such as a fish thrashing free, that spook fish
+    /// nearby.
+    pub splashes: &'a [Point],
+    /// Positive while the tide floods, drawing fish in over the shallows;
+    /// negative while it ebbs, drawing them back out to deeper water.
+    pub tide: i32,
+}
+
+impl Default for Surroundings<'_> {
+    fn default() -> Self {
+        Self {
+            time_of_day: "Day",
+            food: &[],
+            speed_bonus: 0,
+            anglers: &[],
+            splashes: &[],
+            tide: 0,
+        }
+    }
+}
 
-/// Applies a directional current to all fish positions.
-pub fn apply_current(map: &Map, fishes: &mut [Fish], drift: Point) {
-    if drift.x == 0 && drift.y == 0 {
-        return;
+impl Surroundings<'_> {
+    /// The nearest angler or splash close enough to spook `fish`.
+    fn disturbance(&self, fish: &Fish) -> Option<Point> {
+        let pos = fish.position;
+        let extra = fish.skittishness();
+        let near = |points: &[Point], radius: i32| -> Vec<Point> {
+            points
+                .iter()
+                .copied()
+                .filter(|p| p.chebyshev(pos) <= radius + extra)
+                .collect()
+        };
+        near(self.anglers, ANGLER_SPOOK_RADIUS)
+            .into_iter()
+            .chain(near(self.splashes, SPLASH_SPOOK_RADIUS))
+            .min_by_key(|p| p.chebyshev(pos))
     }
+}
+
+/// Carries every fish along the current on its tile (see [`Map::drift`]).
+pub fn apply_current(map: &Map, fishes: &mut [Fish]) {
     for fish in fishes.iter_mut() {
-        let mut new = Point::new(fish.position.x + drift.x, fish.position.y + drift.y);
-        new.x = new.x.clamp(0, map.width as i32 - 1);
-        new.y = new.y.clamp(0, map.height as i32 - 1);
-        if matches!(
-            map.tiles[map.idx(new)],
-            TileKind::ShallowWater | TileKind::DeepWater
-        ) {
-            fish.position = new;
+        fish.position = map.drift(fish.position);
+    }
+}
+
+/// Nearest shallow water tile within [`SHALLOWS_RADIUS`] of `pos`.
+fn nearest_shallows(map: &Map, pos: Point) -> Option<Point> {
+    let mut best: Option<Point> = None;
+    for dy in -SHALLOWS_RADIUS..=SHALLOWS_RADIUS {
+        for dx in -SHALLOWS_RADIUS..=SHALLOWS_RADIUS {
+            let pt = pos + Point::new(dx, dy);
+            if !map.bounds().contains(pt) || !map.tiles[map.idx(pt)].is_shallow() {
+                continue;
+            }
+            if !matches!(best, Some(b) if b.chebyshev(pos) <= pt.chebyshev(pos)) {
+                best = Some(pt);
+            }
         }
     }
+    best
+}
+
+/// Neighbouring water a `tide` draws a fish at `pos` towards: shallower on
+/// a flood, deeper on an ebb. A fish left high and dry goes to any water.
+fn tide_pull(map: &Map, pos: Point, tide: i32) -> Option<Point> {
+    if tide == 0 {
+        return None;
+    }
+    let key = |pt: Point| map.depth(pt) * tide.signum();
+    let here = map.tiles[map.idx(pos)].is_water().then(|| key(pos));
+    let mut best: Option<Point> = None;
+    for dy in -1..=1 {
+        for dx in -1..=1 {
+            let pt = pos + Point::new(dx, dy);
+            if !map.bounds().contains(pt) || !map.tiles[map.idx(pt)].is_water() {
+                continue;
+            }
+            if here.is_some_and(|h| key(pt) >= h) {
+                continue;
+            }
+            if !matches!(best, Some(b) if key(b) <= key(pt)) {
+                best = Some(pt);
+            }
+        }
+    }
+    best
+}
+
+/// Position of the nearest other fish within `radius` of fish `i` that
+/// matches `filter`, looked up in `index` of the fish positions.
+fn nearest_fish(
+    fishes: &[Fish],
+    index: &SpatialIndex,
+    i: usize,
+    radius: i32,
+    filter: impl Fn(&Fish) -> bool,
+) -> Option<Point> {
+    let pos = fishes[i].position;
+    index
+        .query_radius(pos, radius)
+        .into_iter()
+        .filter(|&j| j != i && filter(&fishes[j]))
+        .map(|j| fishes[j].position)
+        .min_by_key(|p| p.chebyshev(pos))
+}
+
+/// Indices of the fish within `radius` of `center` (Chebyshev distance)
+/// with their distances, nearest first.
+pub fn fish_near(fishes: &[Fish], center: Point, radius: i32) -> Vec<(usize, i32)> {
+    let mut near: Vec<(usize, i32)> = fishes
+        .iter()
+        .enumerate()
+        .map(|(i, f)| (i, f.position.chebyshev(center)))
+        .filter(|&(_, d)| d <= radius)
+        .collect();
+    near.sort_by_key(|&(i, d)| (d, i));
+    near
+}
+
+/// Fish within this many tiles of a trap may swim into it.
+pub const TRAP_RADIUS: i32 = 1;
+
+/// A trap in the water as the fish around it see it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TrapSite {
+    pub pos: Point,
+    /// Water depth at the trap in metres.
+    pub depth: i32,
+    /// Whether bait in the trap draws fish in.
+    pub baited: bool,
+}
+
+impl TrapSite {
+    /// Chance each turn that `fish`, next to the trap, swims in. Bold fish
+    /// go in more readily than wary ones, bait and hunger draw them, and
+    /// few leave their depth band for it.
+    pub fn entry_chance(&self, fish: &Fish) -> f32 {
+        let temperament: f32 = match fish.kind.fight_style {
+            data::FightStyle::Aggressive => 0.12,
+            data::FightStyle::Endurance => 0.08,
+            data::FightStyle::Evasive => 0.03,
+        };
+        let bait = if self.baited { 0.12 } else { 0.0 };
+        let hunger = match fish.feeding_state() {
+            FeedingState::Sated => 0.5,
+            FeedingState::Foraging => 1.0,
+            FeedingState::Ravenous => 1.5,
+        };
+        let depth = if (fish.kind.min_depth..=fish.kind.max_depth).contains(&self.depth) {
+            1.0
+        } else {
+            0.25
+        };
+        ((temperament + bait) * hunger * depth).min(1.0)
+    }
+}
+
+/// Indices of the fish that swim into `trap` this turn, nearest first.
+pub fn trap_entries(
+    fishes: &[Fish],
+    trap: &TrapSite,
+    rng: &mut RandomNumberGenerator,
+) -> Vec<usize> {
+    fish_near(fishes, trap.pos, TRAP_RADIUS)
+        .into_iter()
+        .map(|(i, _)| i)
+        .filter(|&i| rng.range(0.0, 1.0) < trap.entry_chance(&fishes[i]))
+        .collect()
+}
+
+/// Something in the water that draws fish.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Food {
+    /// Bait on a cast line; only hungry fish within [`FOOD_RADIUS`] notice it.
+    Bait(Point),
+    /// Chum; its scent draws every fish within [`CHUM_RADIUS`], fed or not.
+    Chum(Point),
+}
+
+impl Food {
+    /// Tile the food is on.
+    pub fn position(self) -> Point {
+        match self {
+            Food::Bait(pos) | Food::Chum(pos) => pos,
+        }
+    }
+
+    /// Whether `fish` notices this food from where it is.
+    fn draws(self, fish: &Fish) -> bool {
+        let distance = self.position().chebyshev(fish.position);
+        match self {
+            Food::Bait(_) => fish.feeding_state() != FeedingState::Sated && distance <= FOOD_RADIUS,
+            Food::Chum(_) => distance <= CHUM_RADIUS,
+        }
+    }
+}
+
+/// Where a fish heads: the nearest `food` it notices, or the shallows at
+/// dawn and dusk when hungry.
+fn feeding_target(map: &Map, fish: &Fish, food: &[Food], time_of_day: &str) -> Option<Point> {
+    let pos = fish.position;
+    food.iter()
+        .filter(|f| f.draws(fish))
+        .map(|f| f.position())
+        .min_by_key(|p| p.chebyshev(pos))
+        .or_else(|| {
+            (fish.feeding_state() != FeedingState::Sated && matches!(time_of_day, "Dawn" | "Dusk"))
+                .then(|| nearest_shallows(map, pos))
+                .flatten()
+        })
 }
 
 /// Updates all fish positions with simple AI.
+///
+/// Fish grow hungrier every turn. Unless sated they swim toward bait in
+/// the food and feed there, or forage in the shallows at dawn and dusk;
+/// chum draws even sated fish. Hungry predators chase species in their
+/// diet and are fed by catching up with one; prey near a predator flee
+/// from it. Fish close to an angler or a splash are spooked and swim away
+/// for a few turns, which beats every other urge. Idle fish that reach a
+/// biome their species favours stay inside it. The tide draws fish
+/// towards the shore as it floods and out to deeper water as it ebbs.
 pub fn update_fish(
     map: &Map,
     fishes: &mut [Fish],
     rng: &mut RandomNumberGenerator,
-    time_of_day: &str,
-    drift: Point,
+    around: &Surroundings,
 ) -> GameResult<()> {
+    let (time_of_day, food) = (around.time_of_day, around.food);
+    let mut index = SpatialIndex::build(map, fishes.iter().map(|f| f.position));
     for i in 0..fishes.len() {
+        fishes[i].hunger = (fishes[i].hunger + 1).min(MAX_FISH_HUNGER);
+        if let Some(source) = around.disturbance(&fishes[i]) {
+            fishes[i].spooked = SPOOK_TURNS;
+            fishes[i].flee_from = source;
+        }
         let mut speed = if time_of_day == "Night" { 2 } else { 1 };
+        speed += around.speed_bonus;
         if fishes[i].kind.legendary {
             speed += 1;
         }
@@ -50,18 +373,72 @@ pub fn update_fish(
 
         // schooling: move towards nearest same-species fish within radius
         let pos = fishes[i].position;
-        if let Some(nearest) = fishes
-            .iter()
-            .enumerate()
-            .filter(|(j, f)| *j != i && f.kind.id == fishes[i].kind.id)
-            .map(|(_, f)| f.position)
-            .filter(|p| (p.x - pos.x).abs() + (p.y - pos.y).abs() <= SCHOOL_RADIUS)
-            .min_by_key(|p| (p.x - pos.x).abs() + (p.y - pos.y).abs())
+        if let Some(nearest) = index
+            .query_radius(pos, SCHOOL_RADIUS)
+            .into_iter()
+            .filter(|&j| j != i && fishes[j].kind.id == fishes[i].kind.id)
+            .map(|j| fishes[j].position)
+            .filter(|p| p.manhattan(pos) <= SCHOOL_RADIUS)
+            .min_by_key(|p| p.manhattan(pos))
         {
-            dx += (nearest.x - pos.x).signum();
-            dy += (nearest.y - pos.y).signum();
+            let step = (nearest - pos).signum();
+            dx += step.x;
+            dy += step.y;
+        }
+
+        // tide: a flood carries fish in over the flats, an ebb draws them out
+        if let Some(target) = tide_pull(map, pos, around.tide) {
+            let step = (target - pos).signum();
+            dx += step.x;
+            dy += step.y;
         }
 
+        // feeding: a stronger pull than schooling
+        let feeding = feeding_target(map, &fishes[i], food, time_of_day);
+        if let Some(target) = feeding {
+            let step = (target - pos).signum();
+            dx += step.x * 2;
+            dy += step.y * 2;
+        }
+
+        // hunting: hungry predators chase the nearest prey
+        let hunting = fishes[i].feeding_state() != FeedingState::Sated
+            && nearest_fish(fishes, &index, i, HUNT_RADIUS, |f| fishes[i].preys_on(f))
+                .map(|prey| {
+                    let step = (prey - pos).signum();
+                    dx += step.x * 2;
+                    dy += step.y * 2;
+                })
+                .is_some();
+
+        // fleeing: escaping a predator beats every other urge
+        let predator = nearest_fish(fishes, &index, i, FLEE_RADIUS, |f| f.preys_on(&fishes[i]));
+        if let Some(predator) = predator {
+            let away = (pos - predator).signum();
+            if away != Point::new(0, 0) {
+                dx = away.x * speed;
+                dy = away.y * speed;
+            }
+        }
+
+        // spooked: bolting from an angler or splash beats even predators
+        let spooked = fishes[i].spooked > 0;
+        if spooked {
+            fishes[i].spooked -= 1;
+            let away = (pos - fishes[i].flee_from).signum();
+            if away != Point::new(0, 0) {
+                dx = away.x * speed;
+                dy = away.y * speed;
+            }
+        }
+
+        // lingering: idle fish stay in the biomes their species favours
+        let lingering = feeding.is_none()
+            && !hunting
+            && predator.is_none()
+            && !spooked
+            && fishes[i].favours(map.tiles[map.idx(pos)]);
+
         dx = dx.clamp(-speed, speed);
         dy = dy.clamp(-speed, speed);
 
@@ -70,45 +447,80 @@ pub fn update_fish(
         x = x.clamp(0, map.width as i32 - 1);
         y = y.clamp(0, map.height as i32 - 1);
         let new_pt = Point::new(x, y);
-        if matches!(
-            map.tiles[map.idx(new_pt)],
-            TileKind::ShallowWater | TileKind::DeepWater
-        ) {
+        let tile = map.tiles[map.idx(new_pt)];
+        if tile.is_water() && (!lingering || fishes[i].favours(tile)) {
+            index.relocate(i, pos, new_pt);
             fishes[i].position = new_pt;
         }
+
+        let caught_prey =
+            hunting && nearest_fish(fishes, &index, i, 1, |f| fishes[i].preys_on(f)).is_some();
+        let fish = &mut fishes[i];
+        if food.iter().any(|f| f.position() == fish.position) || caught_prey {
+            fish.hunger = 0;
+        } else if matches!(time_of_day, "Dawn" | "Dusk")
+            && map.tiles[map.idx(fish.position)].is_shallow()
+        {
+            fish.hunger = (fish.hunger - FORAGE_AMOUNT).max(0);
+        }
     }
-    apply_current(map, fishes, drift);
+    apply_current(map, fishes);
     Ok(())
 }
 
+/// The species in `species` that spawn in `season`, or all of them when
+/// none do so the sea is never empty.
+pub fn seasonal_species(species: &[FishType], season: Season) -> Vec<FishType> {
+    let in_season: Vec<FishType> = species
+        .iter()
+        .filter(|f| f.in_season(season))
+        .cloned()
+        .collect();
+    if in_season.is_empty() {
+        species.to_vec()
+    } else {
+        in_season
+    }
+}
+
 /// Spawns a single fish onto the map.
-pub fn spawn_fish(map: &mut Map, fish_types: &[FishType]) -> GameResult<Fish> {
-    let mut fishes = spawn_fish_population(map, fish_types, 1)?;
+pub fn spawn_fish(
+    map: &mut Map,
+    fish_types: &[FishType],
+    rng: &mut RandomNumberGenerator,
+) -> GameResult<Fish> {
+    let mut fishes = spawn_fish_population(map, fish_types, 1, rng)?;
     Ok(fishes.remove(0))
 }
 
-/// Spawns `count` fish on water tiles weighted by rarity.
+/// Spawns `count` fish on water tiles weighted by rarity, keeping to the
+/// main basin on generated maps. A legendary species appears at most once.
+/// Every roll comes from `rng`, so a generator seeded the same way spawns
+/// the same fish.
 pub fn spawn_fish_population(
     map: &mut Map,
     fish_types: &[FishType],
     count: usize,
+    rng: &mut RandomNumberGenerator,
 ) -> GameResult<Vec<Fish>> {
     let mut water = Vec::new();
     for y in 0..map.height as i32 {
         for x in 0..map.width as i32 {
             let pt = Point::new(x, y);
             let tile = map.tiles[map.idx(pt)];
-            if matches!(tile, TileKind::ShallowWater | TileKind::DeepWater) {
+            if tile.is_water() {
                 water.push(pt);
             }
         }
     }
+    if water.iter().any(|&pt| map.in_main_basin(pt)) {
+        water.retain(|&pt| map.in_main_basin(pt));
+    }
 
     if water.is_empty() {
         return Err(GameError::InvalidOperation);
     }
 
-    let mut rng = RandomNumberGenerator::new();
     let mut fishes = Vec::new();
     let total: f32 = fish_types.iter().map(|f| f.rarity).sum();
     let max_attempts = count * 10;
@@ -125,8 +537,11 @@ pub fn spawn_fish_population(
                 break;
             }
         }
+        if chosen.legendary && fishes.iter().any(|f: &Fish| f.kind.id == chosen.id) {
+            continue;
+        }
 
-        let candidates: Vec<usize> = water
+        let mut candidates: Vec<usize> = water
             .iter()
             .enumerate()
             .filter(|(_, pt)| {
@@ -135,6 +550,14 @@ pub fn spawn_fish_population(
             })
             .map(|(i, _)| i)
             .collect();
+        // species with favourite biomes settle there when any are in range
+        let favoured = |&i: &usize| {
+            let biome = map.tiles[map.idx(water[i])].biome();
+            biome.is_some_and(|b| chosen.biomes.contains(&b))
+        };
+        if candidates.iter().any(favoured) {
+            candidates.retain(favoured);
+        }
 
         if candidates.is_empty() {
             continue;
@@ -146,14 +569,26 @@ pub fn spawn_fish_population(
         fishes.push(Fish {
             kind: chosen.clone(),
             position: pos,
+            hunger: rng.range(0, MAX_FISH_HUNGER),
+            weight: roll_weight(chosen, rng),
+            spooked: 0,
+            flee_from: pos,
+            migrating: false,
         });
     }
 
-    println!("Spawned {} fish", fishes.len());
-    println!("Initialized crate: ecology");
     Ok(fishes)
 }
 
+/// Rolls a weight within the species' range, favouring the middle.
+///
+/// Averages two uniform rolls and rounds to 0.1 kg.
+pub fn roll_weight(kind: &FishType, rng: &mut RandomNumberGenerator) -> f32 {
+    let t = (rng.range(0.0, 1.0) + rng.range(0.0, 1.0)) / 2.0;
+    let weight = kind.min_weight + (kind.max_weight - kind.min_weight) * t;
+    ((weight * 10.0).round() / 10.0).clamp(kind.min_weight, kind.max_weight)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -163,41 +598,80 @@ mod tests {
 
     #[test]
     fn spawn_one_fish() {
-        let mut map = generate(0, 120, 80).expect("map");
+        let mut map = generate(0, &data::AreaType::new("Test", 120, 80)).expect("map");
         let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../../assets/fish.json");
         let types = load_fish_types(path).expect("types");
-        let fish = spawn_fish(&mut map, &types).expect("fish");
+        let fish =
+            spawn_fish(&mut map, &types, &mut RandomNumberGenerator::seeded(0)).expect("fish");
         let depth = map.depth(fish.position);
         assert!(depth >= fish.kind.min_depth && depth <= fish.kind.max_depth);
     }
 
     #[test]
     fn spawn_many_fish() {
-        let mut map = generate(0, 120, 80).expect("map");
+        let mut map = generate(0, &data::AreaType::new("Test", 120, 80)).expect("map");
         let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../../assets/fish.json");
         let types = load_fish_types(path).expect("types");
-        let fishes = spawn_fish_population(&mut map, &types, 5).expect("fishes");
+        let fishes =
+            spawn_fish_population(&mut map, &types, 5, &mut RandomNumberGenerator::seeded(0))
+                .expect("fishes");
         assert_eq!(fishes.len(), 5);
         for f in fishes {
             let depth = map.depth(f.position);
             assert!(depth >= f.kind.min_depth && depth <= f.kind.max_depth);
+            assert!(f.weight >= f.kind.min_weight && f.weight <= f.kind.max_weight);
+            assert!(map.in_main_basin(f.position));
         }
     }
 
+    #[test]
+    fn weights_stay_in_range_and_center_on_average() {
+        let kind = FishType {
+            min_weight: 2.0,
+            max_weight: 6.0,
+            ..fish_type()
+        };
+        let mut rng = RandomNumberGenerator::seeded(3);
+        let weights: Vec<f32> = (0..500).map(|_| roll_weight(&kind, &mut rng)).collect();
+        assert!(weights.iter().all(|w| (2.0..=6.0).contains(w)));
+        let mean = weights.iter().sum::<f32>() / weights.len() as f32;
+        assert!((mean - kind.average_weight()).abs() < 0.3);
+        let fish = Fish::new(kind, Point::new(0, 0));
+        assert_eq!(fish.weight, 4.0);
+        assert_eq!(fish.size_ratio(), 1.0);
+    }
+
+    #[test]
+    fn a_seed_spawns_the_same_fish() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../../assets/fish.json");
+        let types = load_fish_types(path).expect("types");
+        let spawn = |seed| {
+            let mut map = generate(0, &data::AreaType::new("Test", 120, 80)).expect("map");
+            let mut rng = RandomNumberGenerator::seeded(seed);
+            spawn_fish_population(&mut map, &types, 8, &mut rng)
+                .expect("fishes")
+                .into_iter()
+                .map(|f| (f.kind.id, f.position, f.weight))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(spawn(9), spawn(9));
+        assert_ne!(spawn(9), spawn(10));
+    }
+
     #[test]
     fn fish_moves_within_water_bounds() {
-        let mut map = generate(0, 120, 80).expect("map");
+        let mut map = generate(0, &data::AreaType::new("Test", 120, 80)).expect("map");
         let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../../assets/fish.json");
         let types = load_fish_types(path).expect("types");
-        let mut fish = spawn_fish(&mut map, &types).expect("fish");
+        let mut fish =
+            spawn_fish(&mut map, &types, &mut RandomNumberGenerator::seeded(0)).expect("fish");
         let mut rng = RandomNumberGenerator::seeded(1);
         for _ in 0..20 {
             update_fish(
                 &map,
                 std::slice::from_mut(&mut fish),
                 &mut rng,
-                "Day",
-                Point::new(0, 0),
+                &Surroundings::default(),
             )
             .unwrap();
             assert!(fish.position.x >= 0 && fish.position.x < map.width as i32);
@@ -212,7 +686,7 @@ mod tests {
         let mut map = Map::new(5, 5);
         let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../../assets/fish.json");
         let types = load_fish_types(path).expect("types");
-        let res = spawn_fish_population(&mut map, &types, 3);
+        let res = spawn_fish_population(&mut map, &types, 3, &mut RandomNumberGenerator::seeded(0));
         assert!(matches!(res, Err(GameError::InvalidOperation)));
     }
 
@@ -231,23 +705,23 @@ mod tests {
             max_depth: 10,
             fight_style: data::FightStyle::Aggressive,
             legendary: false,
+            description: String::new(),
+            habitat_note: String::new(),
+            diet: Vec::new(),
+            min_weight: 1.0,
+            max_weight: 1.0,
+            biomes: Vec::new(),
+            boss: None,
+            seasons: Vec::new(),
         };
         let mut fishes = vec![
-            Fish {
-                kind: ft.clone(),
-                position: Point::new(2, 2),
-            },
-            Fish {
-                kind: ft.clone(),
-                position: Point::new(5, 2),
-            },
+            Fish::new(ft.clone(), Point::new(2, 2)),
+            Fish::new(ft.clone(), Point::new(5, 2)),
         ];
-        let before = (fishes[0].position.x - fishes[1].position.x).abs()
-            + (fishes[0].position.y - fishes[1].position.y).abs();
+        let before = fishes[0].position.manhattan(fishes[1].position);
         let mut rng = RandomNumberGenerator::seeded(1);
-        update_fish(&map, &mut fishes, &mut rng, "Day", Point::new(0, 0)).unwrap();
-        let after = (fishes[0].position.x - fishes[1].position.x).abs()
-            + (fishes[0].position.y - fishes[1].position.y).abs();
+        update_fish(&map, &mut fishes, &mut rng, &Surroundings::default()).unwrap();
+        let after = fishes[0].position.manhattan(fishes[1].position);
         assert!(after < before || after == 0);
     }
 
@@ -266,39 +740,38 @@ mod tests {
             max_depth: 10,
             fight_style: data::FightStyle::Aggressive,
             legendary: false,
+            description: String::new(),
+            habitat_note: String::new(),
+            diet: Vec::new(),
+            min_weight: 1.0,
+            max_weight: 1.0,
+            biomes: Vec::new(),
+            boss: None,
+            seasons: Vec::new(),
         };
-        let mut day_fish = Fish {
-            kind: ft.clone(),
-            position: Point::new(5, 5),
-        };
-        let mut night_fish = Fish {
-            kind: ft.clone(),
-            position: Point::new(5, 5),
-        };
+        let mut day_fish = Fish::new(ft.clone(), Point::new(5, 5));
+        let mut night_fish = Fish::new(ft.clone(), Point::new(5, 5));
         let mut rng_day = RandomNumberGenerator::seeded(1);
         let mut rng_night = RandomNumberGenerator::seeded(1);
         update_fish(
             &map,
             std::slice::from_mut(&mut day_fish),
             &mut rng_day,
-            "Day",
-            Point::new(0, 0),
+            &Surroundings::default(),
         )
         .unwrap();
         update_fish(
             &map,
             std::slice::from_mut(&mut night_fish),
             &mut rng_night,
-            "Night",
-            Point::new(0, 0),
+            &Surroundings {
+                time_of_day: "Night",
+                ..Default::default()
+            },
         )
         .unwrap();
-        let day_dist = (day_fish.position.x - 5)
-            .abs()
-            .max((day_fish.position.y - 5).abs());
-        let night_dist = (night_fish.position.x - 5)
-            .abs()
-            .max((night_fish.position.y - 5).abs());
+        let day_dist = day_fish.position.chebyshev(Point::new(5, 5));
+        let night_dist = night_fish.position.chebyshev(Point::new(5, 5));
         assert!(night_dist >= day_dist);
         assert!(night_dist <= 2);
     }
@@ -318,13 +791,29 @@ mod tests {
             max_depth: 10,
             fight_style: data::FightStyle::Aggressive,
             legendary: false,
+            description: String::new(),
+            habitat_note: String::new(),
+            diet: Vec::new(),
+            min_weight: 1.0,
+            max_weight: 1.0,
+            biomes: Vec::new(),
+            boss: None,
+            seasons: Vec::new(),
         };
-        let mut fish = Fish {
-            kind: ft,
-            position: Point::new(2, 2),
-        };
-        apply_current(&map, std::slice::from_mut(&mut fish), Point::new(1, 0));
-        assert_eq!(fish.position, Point::new(3, 2));
+        let idx = map.idx(Point::new(2, 2));
+        map.flow[idx] = Point::new(1, 0);
+        let idx = map.idx(Point::new(3, 3));
+        map.flow[idx] = Point::new(-2, 2);
+        let mut fishes = [
+            Fish::new(ft.clone(), Point::new(2, 2)),
+            Fish::new(ft.clone(), Point::new(3, 3)),
+            Fish::new(ft, Point::new(0, 0)),
+        ];
+        apply_current(&map, &mut fishes);
+        assert_eq!(fishes[0].position, Point::new(3, 2));
+        // The map edge cuts the strong current short
+        assert_eq!(fishes[1].position, Point::new(2, 4));
+        assert_eq!(fishes[2].position, Point::new(0, 0));
     }
 
     #[test]
@@ -342,21 +831,358 @@ mod tests {
             max_depth: 10,
             fight_style: data::FightStyle::Aggressive,
             legendary: true,
+            description: String::new(),
+            habitat_note: String::new(),
+            diet: Vec::new(),
+            min_weight: 1.0,
+            max_weight: 1.0,
+            biomes: Vec::new(),
+            boss: None,
+            seasons: Vec::new(),
         };
-        let mut fish = Fish {
-            kind: ft,
-            position: Point::new(5, 5),
-        };
+        let mut fish = Fish::new(ft, Point::new(5, 5));
         let mut rng = RandomNumberGenerator::seeded(1);
         update_fish(
             &map,
             std::slice::from_mut(&mut fish),
             &mut rng,
-            "Day",
-            Point::new(0, 0),
+            &Surroundings::default(),
         )
         .unwrap();
-        let dist = (fish.position.x - 5).abs().max((fish.position.y - 5).abs());
+        let dist = fish.position.chebyshev(Point::new(5, 5));
         assert!(dist >= 1);
     }
+
+    #[test]
+    fn only_species_in_season_spawn() {
+        let summer = FishType {
+            id: "S".into(),
+            seasons: vec![Season::Summer],
+            ..fish_type()
+        };
+        let all_year = fish_type();
+        let species = [summer.clone(), all_year];
+        let winter = seasonal_species(&species, Season::Winter);
+        assert_eq!(winter.len(), 1);
+        assert_eq!(winter[0].id, "A");
+        assert_eq!(seasonal_species(&species, Season::Summer).len(), 2);
+        assert_eq!(seasonal_species(&[summer], Season::Winter).len(), 1);
+    }
+
+    #[test]
+    fn legendaries_are_one_of_a_kind() {
+        let mut map = water_map(TileKind::ShallowWater);
+        let legend = FishType {
+            id: "L".into(),
+            legendary: true,
+            ..fish_type()
+        };
+        let mut rng = RandomNumberGenerator::seeded(2);
+        let fishes = spawn_fish_population(&mut map, &[legend, fish_type()], 8, &mut rng).unwrap();
+        assert_eq!(fishes.iter().filter(|f| f.kind.legendary).count(), 1);
+        assert_eq!(fishes.len(), 8);
+    }
+
+    fn water_map(tile: TileKind) -> Map {
+        let mut map = Map::new(12, 12);
+        for t in map.tiles.iter_mut() {
+            *t = tile;
+        }
+        map
+    }
+
+    fn fish_type() -> FishType {
+        FishType {
+            id: "A".into(),
+            name: "A".into(),
+            rarity: 1.0,
+            strength: 1,
+            min_depth: 0,
+            max_depth: 10,
+            fight_style: data::FightStyle::Aggressive,
+            legendary: false,
+            description: String::new(),
+            habitat_note: String::new(),
+            diet: Vec::new(),
+            min_weight: 1.0,
+            max_weight: 1.0,
+            biomes: Vec::new(),
+            boss: None,
+            seasons: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn feeding_state_follows_hunger() {
+        let mut fish = Fish::new(fish_type(), Point::new(0, 0));
+        assert_eq!(fish.feeding_state(), FeedingState::Foraging);
+        fish.hunger = 0;
+        assert_eq!(fish.feeding_state(), FeedingState::Sated);
+        fish.hunger = MAX_FISH_HUNGER;
+        assert_eq!(fish.feeding_state(), FeedingState::Ravenous);
+    }
+
+    #[test]
+    fn fish_near_lists_nearest_first() {
+        let fishes = [
+            Fish::new(fish_type(), Point::new(8, 5)),
+            Fish::new(fish_type(), Point::new(5, 6)),
+            Fish::new(fish_type(), Point::new(2, 2)),
+            Fish::new(fish_type(), Point::new(5, 5)),
+        ];
+        assert_eq!(
+            fish_near(&fishes, Point::new(5, 5), 3),
+            [(3, 0), (1, 1), (0, 3), (2, 3)]
+        );
+        assert!(fish_near(&fishes, Point::new(20, 20), 3).is_empty());
+    }
+
+    #[test]
+    fn hungry_fish_swim_to_bait_and_feed() {
+        let map = water_map(TileKind::DeepWater);
+        let bait = [Food::Bait(Point::new(9, 5))];
+        let mut fish = Fish::new(fish_type(), Point::new(3, 5));
+        fish.hunger = MAX_FISH_HUNGER;
+        let mut rng = RandomNumberGenerator::seeded(1);
+        for _ in 0..20 {
+            update_fish(
+                &map,
+                std::slice::from_mut(&mut fish),
+                &mut rng,
+                &Surroundings {
+                    food: &bait,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            if fish.position == bait[0].position() {
+                break;
+            }
+        }
+        assert_eq!(fish.position, bait[0].position());
+        assert_eq!(fish.feeding_state(), FeedingState::Sated);
+    }
+
+    #[test]
+    fn sated_fish_ignore_bait() {
+        let map = water_map(TileKind::DeepWater);
+        let mut fish = Fish::new(fish_type(), Point::new(3, 5));
+        fish.hunger = 0;
+        assert_eq!(
+            feeding_target(&map, &fish, &[Food::Bait(Point::new(6, 5))], "Day"),
+            None
+        );
+    }
+
+    #[test]
+    fn chum_draws_sated_fish_within_its_radius() {
+        let map = water_map(TileKind::DeepWater);
+        let mut fish = Fish::new(fish_type(), Point::new(3, 5));
+        fish.hunger = 0;
+        let chum = Point::new(9, 5);
+        assert_eq!(
+            feeding_target(&map, &fish, &[Food::Chum(chum)], "Day"),
+            Some(chum)
+        );
+        let far = Point::new(3 + CHUM_RADIUS + 1, 5);
+        assert_eq!(feeding_target(&map, &fish, &[Food::Chum(far)], "Day"), None);
+    }
+
+    #[test]
+    fn foraging_fish_seek_shallows_at_dawn() {
+        let mut map = water_map(TileKind::DeepWater);
+        let shallows = Point::new(6, 5);
+        let idx = map.idx(shallows);
+        map.tiles[idx] = TileKind::ShallowWater;
+        let fish = Fish::new(fish_type(), Point::new(3, 5));
+        assert_eq!(feeding_target(&map, &fish, &[], "Dawn"), Some(shallows));
+        assert_eq!(feeding_target(&map, &fish, &[], "Day"), None);
+    }
+
+    fn species(id: &str, strength: i32, diet: &[&str]) -> FishType {
+        FishType {
+            id: id.into(),
+            strength,
+            diet: diet.iter().map(|d| d.to_string()).collect(),
+            ..fish_type()
+        }
+    }
+
+    #[test]
+    fn predators_chase_and_prey_flee() {
+        let map = water_map(TileKind::DeepWater);
+        let mut fishes = vec![
+            Fish::new(species("PIKE", 9, &["MINNOW"]), Point::new(2, 6)),
+            Fish::new(species("MINNOW", 2, &[]), Point::new(5, 6)),
+        ];
+        fishes[0].hunger = MAX_FISH_HUNGER;
+        assert!(fishes[0].preys_on(&fishes[1]));
+        assert!(!fishes[1].preys_on(&fishes[0]));
+        let mut rng = RandomNumberGenerator::seeded(3);
+        update_fish(&map, &mut fishes, &mut rng, &Surroundings::default()).unwrap();
+        assert_eq!(fishes[0].position.x, 3);
+        assert_eq!(fishes[1].position.x, 6);
+    }
+
+    #[test]
+    fn tide_draws_fish_in_and_out() {
+        let mut map = water_map(TileKind::ShallowWater);
+        for x in 0..12 {
+            for y in 0..12 {
+                let idx = map.idx(Point::new(x, y));
+                map.depths[idx] = x * 5;
+            }
+        }
+        let pos = Point::new(6, 6);
+        assert_eq!(tide_pull(&map, pos, 0), None);
+        assert_eq!(tide_pull(&map, pos, 1).map(|p| p.x), Some(5));
+        assert_eq!(tide_pull(&map, pos, -1).map(|p| p.x), Some(7));
+        let shore = Point::new(0, 6);
+        assert_eq!(tide_pull(&map, shore, 1), None, "already at the shore");
+
+        let dry = map.idx(shore);
+        map.tiles[dry] = TileKind::Sandbar;
+        assert_eq!(tide_pull(&map, shore, -1).map(|p| p.x), Some(1));
+    }
+
+    #[test]
+    fn anglers_and_splashes_spook_fish() {
+        let map = water_map(TileKind::DeepWater);
+        let angler = [Point::new(4, 5)];
+        let mut fish = Fish::new(fish_type(), Point::new(6, 5));
+        fish.hunger = MAX_FISH_HUNGER;
+        let bait = [Food::Bait(Point::new(5, 5))];
+        let around = Surroundings {
+            food: &bait,
+            anglers: &angler,
+            ..Default::default()
+        };
+        let mut rng = RandomNumberGenerator::seeded(1);
+        update_fish(&map, std::slice::from_mut(&mut fish), &mut rng, &around).unwrap();
+        // Swims off even though it is starving and bait is right there
+        assert_eq!(fish.position, Point::new(7, 5));
+        assert_eq!(fish.spooked, SPOOK_TURNS - 1);
+
+        let splash = [Point::new(2, 2)];
+        let around = Surroundings {
+            splashes: &splash,
+            ..Default::default()
+        };
+        let mut calm = Fish::new(fish_type(), Point::new(2 + SPLASH_SPOOK_RADIUS + 1, 2));
+        let mut skittish = Fish::new(
+            FishType {
+                fight_style: data::FightStyle::Evasive,
+                ..fish_type()
+            },
+            calm.position,
+        );
+        update_fish(&map, std::slice::from_mut(&mut calm), &mut rng, &around).unwrap();
+        update_fish(&map, std::slice::from_mut(&mut skittish), &mut rng, &around).unwrap();
+        assert_eq!(calm.spooked, 0);
+        assert_eq!(skittish.spooked, SPOOK_TURNS - 1);
+    }
+
+    #[test]
+    fn predator_is_fed_by_catching_prey() {
+        let map = water_map(TileKind::DeepWater);
+        let mut fishes = vec![
+            Fish::new(species("PIKE", 9, &["MINNOW"]), Point::new(9, 5)),
+            // Prey at the map edge cannot flee further
+            Fish::new(species("MINNOW", 2, &[]), Point::new(11, 5)),
+        ];
+        fishes[0].hunger = MAX_FISH_HUNGER;
+        let mut rng = RandomNumberGenerator::seeded(1);
+        for _ in 0..20 {
+            update_fish(&map, &mut fishes, &mut rng, &Surroundings::default()).unwrap();
+            if fishes[0].feeding_state() == FeedingState::Sated {
+                break;
+            }
+        }
+        assert_eq!(fishes[0].feeding_state(), FeedingState::Sated);
+    }
+
+    #[test]
+    fn sated_predators_do_not_hunt() {
+        let map = water_map(TileKind::DeepWater);
+        let mut fishes = vec![
+            Fish::new(species("PIKE", 9, &["MINNOW"]), Point::new(9, 5)),
+            Fish::new(species("MINNOW", 2, &[]), Point::new(11, 5)),
+        ];
+        fishes[0].hunger = 0;
+        let mut rng = RandomNumberGenerator::seeded(3);
+        update_fish(&map, &mut fishes, &mut rng, &Surroundings::default()).unwrap();
+        assert_eq!(fishes[0].hunger, 1);
+    }
+
+    #[test]
+    fn fish_settle_in_their_biomes() {
+        let mut map = water_map(TileKind::ShallowWater);
+        for y in 4..8 {
+            for x in 4..8 {
+                let idx = map.idx(Point::new(x, y));
+                map.tiles[idx] = TileKind::KelpForest;
+            }
+        }
+        let kelp_fish = FishType {
+            biomes: vec![data::Biome::KelpForest],
+            ..fish_type()
+        };
+        let mut rng = RandomNumberGenerator::seeded(5);
+        let mut fishes = spawn_fish_population(&mut map, &[kelp_fish], 6, &mut rng).unwrap();
+        let mut rovers = spawn_fish_population(&mut map, &[fish_type()], 6, &mut rng).unwrap();
+        let on_kelp = |f: &Fish| map.tiles[map.idx(f.position)] == TileKind::KelpForest;
+        assert!(fishes.iter().all(on_kelp));
+        for _ in 0..30 {
+            update_fish(&map, &mut fishes, &mut rng, &Surroundings::default()).unwrap();
+        }
+        assert!(fishes.iter().all(on_kelp));
+
+        // Species without a favourite biome roam freely
+        for _ in 0..30 {
+            update_fish(&map, &mut rovers, &mut rng, &Surroundings::default()).unwrap();
+        }
+        assert!(!rovers.iter().all(on_kelp));
+    }
+
+    #[test]
+    fn bold_hungry_fish_at_home_depth_enter_baited_traps() {
+        let trap = TrapSite {
+            pos: Point::new(5, 5),
+            depth: 5,
+            baited: false,
+        };
+        let fish = |style, hunger| Fish {
+            hunger,
+            ..Fish::new(
+                FishType {
+                    fight_style: style,
+                    ..fish_type()
+                },
+                Point::new(5, 6),
+            )
+        };
+        let bold = fish(data::FightStyle::Aggressive, 50);
+        let wary = fish(data::FightStyle::Evasive, 50);
+        assert!(trap.entry_chance(&bold) > trap.entry_chance(&wary));
+        let baited = TrapSite {
+            baited: true,
+            ..trap
+        };
+        assert!(baited.entry_chance(&bold) > trap.entry_chance(&bold));
+        let hungry = fish(data::FightStyle::Aggressive, MAX_FISH_HUNGER);
+        assert!(trap.entry_chance(&hungry) > trap.entry_chance(&bold));
+        let too_deep = TrapSite { depth: 40, ..trap };
+        assert!(too_deep.entry_chance(&bold) < trap.entry_chance(&bold));
+
+        let fishes = vec![bold, Fish::new(fish_type(), Point::new(9, 9))];
+        let mut rng = RandomNumberGenerator::seeded(1);
+        let entries: Vec<usize> = (0..100)
+            .flat_map(|_| trap_entries(&fishes, &baited, &mut rng))
+            .collect();
+        assert!(!entries.is_empty());
+        assert!(
+            entries.iter().all(|&i| i == 0),
+            "only fish next to the trap"
+        );
+    }
 }
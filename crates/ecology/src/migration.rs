@@ -0,0 +1,250 @@
+//! Migrations: schools that cross an area from one map edge to the far
+//! one, keeping to the water their species lives in, and leave again.
+use crate::Fish;
+use common::Point;
+use data::FishType;
+use mapgen::Map;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Route tiles between one fish of a migrating school and the next.
+const SPACING: usize = 2;
+/// Cost of crossing water outside the species' depth range; water inside
+/// it costs 1.
+const OFF_DEPTH_COST: u32 = 5;
+
+/// Map edge a migration enters by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Edge {
+    West,
+    East,
+    North,
+    South,
+}
+
+impl Edge {
+    pub const ALL: [Edge; 4] = [Edge::West, Edge::East, Edge::North, Edge::South];
+
+    /// The edge across the map.
+    pub fn opposite(self) -> Edge {
+        match self {
+            Edge::West => Edge::East,
+            Edge::East => Edge::West,
+            Edge::North => Edge::South,
+            Edge::South => Edge::North,
+        }
+    }
+
+    /// Whether `pt` lies on this edge of `map`.
+    fn contains(self, map: &Map, pt: Point) -> bool {
+        match self {
+            Edge::West => pt.x == 0,
+            Edge::East => pt.x == map.width as i32 - 1,
+            Edge::North => pt.y == 0,
+            Edge::South => pt.y == map.height as i32 - 1,
+        }
+    }
+}
+
+/// The water route a school of `kind` takes from edge `from` to the far
+/// edge: the cheapest one, keeping to water of the species' depth where
+/// it can. `None` when no water connects the two edges.
+pub fn route(map: &Map, kind: &FishType, from: Edge) -> Option<Vec<Point>> {
+    let to = from.opposite();
+    let point = |idx: usize| {
+        Point::new(
+            (idx % map.width as usize) as i32,
+            (idx / map.width as usize) as i32,
+        )
+    };
+    let cost = |idx: usize| {
+        if (kind.min_depth..=kind.max_depth).contains(&map.depths[idx]) {
+            1
+        } else {
+            OFF_DEPTH_COST
+        }
+    };
+    let mut dist = vec![u32::MAX; map.tiles.len()];
+    let mut prev: Vec<Option<usize>> = vec![None; map.tiles.len()];
+    let mut open = BinaryHeap::new();
+    for (idx, tile) in map.tiles.iter().enumerate() {
+        if tile.is_water() && from.contains(map, point(idx)) {
+            dist[idx] = cost(idx);
+            open.push(Reverse((dist[idx], idx)));
+        }
+    }
+    while let Some(Reverse((d, idx))) = open.pop() {
+        if d > dist[idx] {
+            continue;
+        }
+        let pt = point(idx);
+        if to.contains(map, pt) {
+            let mut path = vec![pt];
+            let mut at = idx;
+            while let Some(p) = prev[at] {
+                path.push(point(p));
+                at = p;
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                let next = pt + Point::new(dx, dy);
+                if next == pt || !map.bounds().contains(next) {
+                    continue;
+                }
+                let n = map.idx(next);
+                if !map.tiles[n].is_water() {
+                    continue;
+                }
+                let nd = d + cost(n);
+                if nd < dist[n] {
+                    dist[n] = nd;
+                    prev[n] = Some(idx);
+                    open.push(Reverse((nd, n)));
+                }
+            }
+        }
+    }
+    None
+}
+
+/// A migrating school on its way through the area. Its fish are those in
+/// the area's fish with [`Fish::migrating`] set and its species.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Run {
+    /// Id of the migrating species.
+    pub fish: String,
+    pub route: Vec<Point>,
+    /// Route tiles the head of the school has covered.
+    pub progress: usize,
+}
+
+impl Run {
+    pub fn new(fish: &str, route: Vec<Point>) -> Self {
+        Self {
+            fish: fish.to_string(),
+            route,
+            progress: 0,
+        }
+    }
+
+    /// Whether `fish` swims with this school.
+    pub fn carries(&self, fish: &Fish) -> bool {
+        fish.migrating && fish.kind.id == self.fish
+    }
+
+    /// Where the school's `rank`th fish swims now, strung out behind the
+    /// head, or `None` once it has left by the far edge.
+    pub fn position(&self, rank: usize) -> Option<Point> {
+        let at = self.progress.saturating_sub(rank * SPACING);
+        self.route.get(at).copied()
+    }
+
+    /// Moves the school a tile along its route, leaving `held` (a fish on
+    /// the line) where it is. Returns the indices in `fishes` of those
+    /// that left the area, last first.
+    pub fn advance(&mut self, fishes: &mut [Fish], held: Option<usize>) -> Vec<usize> {
+        self.progress += 1;
+        let mut left = Vec::new();
+        let members = (0..fishes.len()).filter(|&i| self.carries(&fishes[i]));
+        for (rank, i) in members.collect::<Vec<_>>().into_iter().enumerate() {
+            if held == Some(i) {
+                continue;
+            }
+            match self.position(rank) {
+                Some(pos) => fishes[i].position = pos,
+                None => left.push(i),
+            }
+        }
+        left.reverse();
+        left
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mapgen::TileKind;
+
+    fn eel() -> FishType {
+        FishType {
+            id: "EEL".into(),
+            name: "Eel".into(),
+            rarity: 1.0,
+            strength: 1,
+            min_depth: 40,
+            max_depth: 100,
+            fight_style: data::FightStyle::Evasive,
+            legendary: false,
+            description: String::new(),
+            habitat_note: String::new(),
+            diet: Vec::new(),
+            min_weight: 1.0,
+            max_weight: 1.0,
+            biomes: Vec::new(),
+            boss: None,
+            seasons: Vec::new(),
+        }
+    }
+
+    /// Open water 5m deep with a 50m channel along row 7 and land on
+    /// row 0.
+    fn channel_map() -> Map {
+        let mut map = Map::new(20, 10);
+        for y in 1..10 {
+            for x in 0..20 {
+                let idx = map.idx(Point::new(x, y));
+                map.tiles[idx] = TileKind::ShallowWater;
+                map.depths[idx] = if y == 7 { 50 } else { 5 };
+            }
+        }
+        map
+    }
+
+    #[test]
+    fn routes_follow_the_deep_channel() {
+        let map = channel_map();
+        let path = route(&map, &eel(), Edge::West).expect("route");
+        assert_eq!(path.first().unwrap().x, 0);
+        assert_eq!(path.last().unwrap().x, 19);
+        assert!(path.iter().all(|p| p.y == 7));
+        let back = route(&map, &eel(), Edge::East).expect("route");
+        assert_eq!(back.last().unwrap().x, 0);
+    }
+
+    #[test]
+    fn no_route_without_water_across() {
+        let map = channel_map();
+        assert_eq!(route(&map, &eel(), Edge::North), None);
+        assert_eq!(route(&map, &eel(), Edge::South), None);
+    }
+
+    #[test]
+    fn schools_swim_the_route_and_leave() {
+        let route: Vec<Point> = (0..5).map(|x| Point::new(x, 7)).collect();
+        let mut run = Run::new("EEL", route);
+        let mut fishes = vec![
+            Fish::new(eel(), Point::new(3, 3)),
+            Fish {
+                migrating: true,
+                ..Fish::new(eel(), Point::new(0, 7))
+            },
+            Fish {
+                migrating: true,
+                ..Fish::new(eel(), Point::new(0, 7))
+            },
+        ];
+        assert!(run.advance(&mut fishes, None).is_empty());
+        assert_eq!(fishes[0].position, Point::new(3, 3), "residents stay");
+        assert_eq!(fishes[1].position, Point::new(1, 7));
+        assert_eq!(fishes[2].position, Point::new(0, 7));
+        for _ in 0..3 {
+            run.advance(&mut fishes, Some(2));
+        }
+        assert_eq!(fishes[1].position, Point::new(4, 7));
+        assert_eq!(fishes[2].position, Point::new(0, 7), "held on the line");
+        assert_eq!(run.advance(&mut fishes, None), vec![1]);
+    }
+}
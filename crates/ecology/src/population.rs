@@ -0,0 +1,356 @@
+//! Fish stocks of an area.
+//!
+//! Schools breed in water that suits their species until the area holds
+//! as many fish as its water can carry. Catching the same species again
+//! and again depletes it: it stops breeding and arriving here until it
+//! recovers, many turns later.
+use crate::{Fish, SCHOOL_RADIUS};
+use bracket_lib::prelude::RandomNumberGenerator;
+use common::Point;
+use data::FishType;
+use mapgen::Map;
+
+/// Water tiles that support one fish.
+const WATER_PER_FISH: usize = 400;
+/// Fewest fish any area holds.
+const MIN_CAPACITY: usize = 5;
+/// Most fish any area holds.
+const MAX_CAPACITY: usize = 20;
+/// Recent catches of one species that fish it out.
+pub const OVERFISHING_CATCHES: u32 = 4;
+/// Turns an overfished species takes to recover.
+pub const DEPLETION_TURNS: u32 = 300;
+/// Turns after which one recent catch is forgotten.
+const RECOVERY_TURNS: u32 = 60;
+/// Turns between breeding seasons of the schools.
+pub const BREEDING_TURNS: u32 = 30;
+/// Chance that a school breeds when it can.
+const BREEDING_CHANCE: f32 = 0.5;
+
+/// Fish stock of one area.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Population {
+    /// Most fish the area's water carries.
+    pub capacity: usize,
+    /// Recent catches as `(fish id, count)`.
+    pub catches: Vec<(String, u32)>,
+    /// Overfished species as `(fish id, turns left)`.
+    pub depleted: Vec<(String, u32)>,
+}
+
+/// How one species is faring in an area.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SpeciesStats {
+    pub id: String,
+    pub name: String,
+    /// Fish of the species in the area now.
+    pub count: usize,
+    /// Catches not yet forgotten.
+    pub recent_catches: u32,
+    /// Turns until the species recovers; zero unless overfished.
+    pub depleted_turns: u32,
+}
+
+impl Population {
+    /// A fresh stock whose capacity follows the water on `map`.
+    pub fn new(map: &Map) -> Self {
+        let water = map.tiles.iter().filter(|t| t.is_water()).count();
+        Self {
+            capacity: (water / WATER_PER_FISH).clamp(MIN_CAPACITY, MAX_CAPACITY),
+            ..Self::default()
+        }
+    }
+
+    /// Turns until `id` recovers from overfishing, or zero.
+    pub fn depleted_turns(&self, id: &str) -> u32 {
+        self.depleted
+            .iter()
+            .find(|(d, _)| d == id)
+            .map_or(0, |&(_, turns)| turns)
+    }
+
+    /// Whether `id` has been fished out here.
+    pub fn is_depleted(&self, id: &str) -> bool {
+        self.depleted_turns(id) > 0
+    }
+
+    /// Counts a catch of `id`. Returns true when it fishes the species out.
+    pub fn record_catch(&mut self, id: &str) -> bool {
+        let count = match self.catches.iter_mut().find(|(c, _)| c == id) {
+            Some((_, count)) => count,
+            None => {
+                self.catches.push((id.to_string(), 0));
+                &mut self.catches.last_mut().expect("catch").1
+            }
+        };
+        *count += 1;
+        if *count < OVERFISHING_CATCHES || self.is_depleted(id) {
+            return false;
+        }
+        self.catches.retain(|(c, _)| c != id);
+        self.depleted.push((id.to_string(), DEPLETION_TURNS));
+        true
+    }
+
+    /// Moves the stock on to `turn`: overfished species recover and old
+    /// catches are forgotten.
+    pub fn advance(&mut self, turn: u32) {
+        for (_, turns) in self.depleted.iter_mut() {
+            *turns -= 1;
+        }
+        self.depleted.retain(|&(_, turns)| turns > 0);
+        if turn.is_multiple_of(RECOVERY_TURNS) {
+            for (_, count) in self.catches.iter_mut() {
+                *count -= 1;
+            }
+            self.catches.retain(|&(_, count)| count > 0);
+        }
+    }
+
+    /// The species in `species` that are not fished out here, or all of
+    /// them when every one is so the sea is never empty.
+    pub fn available(&self, species: &[FishType]) -> Vec<FishType> {
+        let left: Vec<FishType> = species
+            .iter()
+            .filter(|f| !self.is_depleted(&f.id))
+            .cloned()
+            .collect();
+        if left.is_empty() {
+            species.to_vec()
+        } else {
+            left
+        }
+    }
+
+    /// How every species in `fishes` or in this stock's records is faring,
+    /// sorted by id.
+    pub fn report(&self, fishes: &[Fish]) -> Vec<SpeciesStats> {
+        let mut ids: Vec<(&str, &str)> = fishes
+            .iter()
+            .map(|f| (f.kind.id.as_str(), f.kind.name.as_str()))
+            .chain(
+                self.catches
+                    .iter()
+                    .map(|(id, _)| (id.as_str(), id.as_str())),
+            )
+            .chain(
+                self.depleted
+                    .iter()
+                    .map(|(id, _)| (id.as_str(), id.as_str())),
+            )
+            .collect();
+        // Names come from the fish; ids stand in for species none are left of
+        ids.sort_by(|a, b| a.0.cmp(b.0).then((a.0 == a.1).cmp(&(b.0 == b.1))));
+        ids.dedup_by(|a, b| a.0 == b.0);
+        ids.into_iter()
+            .map(|(id, name)| SpeciesStats {
+                id: id.to_string(),
+                name: name.to_string(),
+                count: fishes.iter().filter(|f| f.kind.id == id).count(),
+                recent_catches: self
+                    .catches
+                    .iter()
+                    .find(|(c, _)| c == id)
+                    .map_or(0, |&(_, count)| count),
+                depleted_turns: self.depleted_turns(id),
+            })
+            .collect()
+    }
+}
+
+/// Whether `pt` is water `kind` can live and breed in: within its depth
+/// range and, for species with favourite biomes, inside one.
+fn suits(map: &Map, kind: &FishType, pt: Point) -> bool {
+    if !map.bounds().contains(pt) {
+        return false;
+    }
+    let tile = map.tiles[map.idx(pt)];
+    let depth = map.depth(pt);
+    tile.is_water()
+        && (kind.min_depth..=kind.max_depth).contains(&depth)
+        && (kind.biomes.is_empty() || tile.biome().is_some_and(|b| kind.biomes.contains(&b)))
+}
+
+/// Lets the schools in `fishes` breed. Each pair of fish of a species
+/// schooling together in water that suits it may add a young fish next
+/// to them, while the area holds fewer than its capacity. Legendary and
+/// overfished species do not breed. Returns how many fish were born.
+pub fn breed(
+    map: &Map,
+    fishes: &mut Vec<Fish>,
+    population: &Population,
+    rng: &mut RandomNumberGenerator,
+) -> usize {
+    let parents = fishes.len();
+    for i in 0..parents {
+        if fishes.len() >= population.capacity {
+            break;
+        }
+        let (kind, pos) = (&fishes[i].kind, fishes[i].position);
+        if kind.legendary || population.is_depleted(&kind.id) || !suits(map, kind, pos) {
+            continue;
+        }
+        // Each pair breeds once, through its first fish
+        let schooling = fishes[i + 1..parents]
+            .iter()
+            .any(|f| f.kind.id == kind.id && f.position.manhattan(pos) <= SCHOOL_RADIUS);
+        if !schooling || rng.range(0.0, 1.0) >= BREEDING_CHANCE {
+            continue;
+        }
+        let nursery: Vec<Point> = (-1..=1)
+            .flat_map(|dy| (-1..=1).map(move |dx| pos + Point::new(dx, dy)))
+            .filter(|&pt| suits(map, kind, pt))
+            .collect();
+        let spot = nursery[rng.range(0, nursery.len() as i32) as usize];
+        let kind = kind.clone();
+        fishes.push(Fish {
+            weight: kind.min_weight,
+            ..Fish::new(kind, spot)
+        });
+    }
+    fishes.len() - parents
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mapgen::TileKind;
+
+    fn species(id: &str) -> FishType {
+        FishType {
+            id: id.into(),
+            name: format!("{} fish", id),
+            rarity: 1.0,
+            strength: 1,
+            min_depth: 0,
+            max_depth: 10,
+            fight_style: data::FightStyle::Aggressive,
+            legendary: false,
+            description: String::new(),
+            habitat_note: String::new(),
+            diet: Vec::new(),
+            min_weight: 1.0,
+            max_weight: 2.0,
+            biomes: Vec::new(),
+            boss: None,
+            seasons: Vec::new(),
+        }
+    }
+
+    fn shallows() -> Map {
+        let mut map = Map::new(12, 12);
+        map.tiles.fill(TileKind::ShallowWater);
+        map.depths.fill(5);
+        map
+    }
+
+    #[test]
+    fn schools_breed_up_to_capacity() {
+        let map = shallows();
+        let stock = Population {
+            capacity: 6,
+            ..Population::default()
+        };
+        let mut rng = RandomNumberGenerator::seeded(1);
+        let mut fishes = vec![
+            Fish::new(species("A"), Point::new(3, 3)),
+            Fish::new(species("A"), Point::new(4, 3)),
+            Fish::new(species("B"), Point::new(10, 10)),
+        ];
+        for _ in 0..20 {
+            breed(&map, &mut fishes, &stock, &mut rng);
+        }
+        assert_eq!(fishes.len(), 6);
+        assert_eq!(
+            fishes.iter().filter(|f| f.kind.id == "B").count(),
+            1,
+            "a lone fish has no school to breed with"
+        );
+        let young = &fishes[3];
+        assert_eq!(young.weight, 1.0);
+        assert!(young.position.chebyshev(Point::new(3, 3)) <= 1);
+    }
+
+    #[test]
+    fn no_breeding_out_of_habitat_or_when_fished_out() {
+        let mut map = shallows();
+        map.depths.fill(50);
+        let mut stock = Population {
+            capacity: 10,
+            ..Population::default()
+        };
+        let mut rng = RandomNumberGenerator::seeded(1);
+        let school = vec![
+            Fish::new(species("A"), Point::new(3, 3)),
+            Fish::new(species("A"), Point::new(4, 3)),
+        ];
+        let mut fishes = school.clone();
+        for _ in 0..20 {
+            breed(&map, &mut fishes, &stock, &mut rng);
+        }
+        assert_eq!(fishes.len(), 2, "too deep for the species");
+
+        let map = shallows();
+        stock.depleted.push(("A".into(), 5));
+        for _ in 0..20 {
+            breed(&map, &mut fishes, &stock, &mut rng);
+        }
+        assert_eq!(fishes.len(), 2);
+    }
+
+    #[test]
+    fn overfishing_depletes_until_recovery() {
+        let mut stock = Population::new(&shallows());
+        assert_eq!(stock.capacity, MIN_CAPACITY);
+        for _ in 1..OVERFISHING_CATCHES {
+            assert!(!stock.record_catch("A"));
+        }
+        assert!(stock.record_catch("A"));
+        assert!(stock.is_depleted("A"));
+        assert_eq!(stock.available(&[species("A"), species("B")])[0].id, "B");
+        assert_eq!(stock.available(&[species("A")]).len(), 1);
+        for turn in 1..DEPLETION_TURNS {
+            stock.advance(turn);
+        }
+        assert_eq!(stock.depleted_turns("A"), 1);
+        stock.advance(DEPLETION_TURNS);
+        assert!(!stock.is_depleted("A"));
+
+        stock.record_catch("B");
+        stock.advance(RECOVERY_TURNS);
+        assert!(stock.catches.is_empty(), "old catches are forgotten");
+    }
+
+    #[test]
+    fn report_lists_every_species() {
+        let mut stock = Population::default();
+        stock.depleted.push(("C".into(), 7));
+        stock.record_catch("A");
+        let fishes = vec![
+            Fish::new(species("B"), Point::new(0, 0)),
+            Fish::new(species("A"), Point::new(1, 0)),
+            Fish::new(species("A"), Point::new(2, 0)),
+        ];
+        let report = stock.report(&fishes);
+        let rows: Vec<(&str, &str, usize, u32, u32)> = report
+            .iter()
+            .map(|s| {
+                (
+                    s.id.as_str(),
+                    s.name.as_str(),
+                    s.count,
+                    s.recent_catches,
+                    s.depleted_turns,
+                )
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                ("A", "A fish", 2, 1, 0),
+                ("B", "B fish", 1, 0, 0),
+                ("C", "C", 0, 0, 7),
+            ]
+        );
+    }
+}
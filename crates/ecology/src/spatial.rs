@@ -0,0 +1,109 @@
+//! Spatial index: a grid of buckets over the map so that finding what is
+//! near a point looks at a few buckets rather than everything on the map.
+use common::Point;
+use mapgen::Map;
+
+/// Width and height in tiles of one bucket.
+const CELL_SIZE: i32 = 8;
+
+/// Ids with their positions, bucketed by position. Ids are indices into
+/// whatever the caller indexes, such as the area's fish or hazards.
+#[derive(Clone, Debug, Default)]
+pub struct SpatialIndex {
+    cols: i32,
+    rows: i32,
+    buckets: Vec<Vec<(usize, Point)>>,
+}
+
+impl SpatialIndex {
+    /// An empty index covering `map`.
+    pub fn new(map: &Map) -> Self {
+        let cols = (map.width as i32 + CELL_SIZE - 1) / CELL_SIZE;
+        let rows = (map.height as i32 + CELL_SIZE - 1) / CELL_SIZE;
+        Self {
+            cols: cols.max(1),
+            rows: rows.max(1),
+            buckets: vec![Vec::new(); (cols.max(1) * rows.max(1)) as usize],
+        }
+    }
+
+    /// An index of `positions` over `map`, each under its position in the
+    /// list.
+    pub fn build(map: &Map, positions: impl IntoIterator<Item = Point>) -> Self {
+        let mut index = Self::new(map);
+        for (id, pt) in positions.into_iter().enumerate() {
+            index.insert(id, pt);
+        }
+        index
+    }
+
+    /// Bucket `pt` falls in; points off the map go in the nearest one.
+    fn cell(&self, pt: Point) -> (i32, i32) {
+        (
+            (pt.x / CELL_SIZE).clamp(0, self.cols - 1),
+            (pt.y / CELL_SIZE).clamp(0, self.rows - 1),
+        )
+    }
+
+    fn bucket(&mut self, pt: Point) -> &mut Vec<(usize, Point)> {
+        let (cx, cy) = self.cell(pt);
+        &mut self.buckets[(cy * self.cols + cx) as usize]
+    }
+
+    pub fn insert(&mut self, id: usize, pt: Point) {
+        self.bucket(pt).push((id, pt));
+    }
+
+    /// Moves `id` from `from` to `to`.
+    pub fn relocate(&mut self, id: usize, from: Point, to: Point) {
+        let bucket = self.bucket(from);
+        if let Some(k) = bucket.iter().position(|&(i, _)| i == id) {
+            bucket.swap_remove(k);
+        }
+        self.insert(id, to);
+    }
+
+    /// Ids within `radius` of `center` (Chebyshev distance), in ascending
+    /// order so results match a scan through the list.
+    pub fn query_radius(&self, center: Point, radius: i32) -> Vec<usize> {
+        let (x0, y0) = self.cell(center - Point::new(radius, radius));
+        let (x1, y1) = self.cell(center + Point::new(radius, radius));
+        let mut found: Vec<usize> = (y0..=y1)
+            .flat_map(|cy| (x0..=x1).map(move |cx| (cy * self.cols + cx) as usize))
+            .flat_map(|b| self.buckets[b].iter())
+            .filter(|(_, pt)| pt.chebyshev(center) <= radius)
+            .map(|&(id, _)| id)
+            .collect();
+        found.sort_unstable();
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bracket_lib::prelude::RandomNumberGenerator;
+
+    #[test]
+    fn queries_match_a_full_scan() {
+        let map = Map::new(60, 40);
+        let mut rng = RandomNumberGenerator::seeded(3);
+        let mut points: Vec<Point> = (0..300)
+            .map(|_| Point::new(rng.range(0, 60), rng.range(0, 40)))
+            .collect();
+        let mut index = SpatialIndex::build(&map, points.iter().copied());
+        for (id, pt) in points.iter_mut().enumerate().take(100) {
+            let to = Point::new(rng.range(0, 60), rng.range(0, 40));
+            index.relocate(id, *pt, to);
+            *pt = to;
+        }
+        for _ in 0..50 {
+            let center = Point::new(rng.range(-5, 65), rng.range(-5, 45));
+            let radius = rng.range(0, 12);
+            let scan: Vec<usize> = (0..points.len())
+                .filter(|&i| points[i].chebyshev(center) <= radius)
+                .collect();
+            assert_eq!(index.query_radius(center, radius), scan);
+        }
+    }
+}
@@ -1,7 +1,10 @@
 //! Fishing minigame utilities.
 
-use data::FightStyle;
+use data::{BossFight, BossPattern, FightStyle, FishType};
+use ecology::FeedingState;
 use mapgen::TileKind;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 
 /// Result of a [`TensionMeter::update`] call.
 #[derive(Debug, PartialEq, Eq)]
@@ -16,6 +19,107 @@ pub enum MeterState {
     Lost,
 }
 
+/// What a hooked fish is doing this turn.
+///
+/// Running and diving fish punish reeling, so the angler gives line until
+/// they tire; thrashing fish can be reeled through; tired fish are reeled in
+/// while they get their breath back.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FightPhase {
+    /// Pulling away; reeling spikes the tension.
+    Run,
+    /// Heading for the bottom; reeling spikes the tension hard.
+    Dive,
+    /// Shaking its head; tension jumps unless the line is reeled.
+    Thrash,
+    /// Out of stamina; reeling gains the most line.
+    Tire,
+}
+
+impl FightPhase {
+    /// Name shown on the fight screen.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Run => "Running",
+            Self::Dive => "Diving",
+            Self::Thrash => "Thrashing",
+            Self::Tire => "Tiring",
+        }
+    }
+
+    /// What the angler should do in this phase.
+    pub fn hint(self) -> &'static str {
+        match self {
+            Self::Run | Self::Dive => "give line",
+            Self::Thrash => "reel",
+            Self::Tire => "reel in",
+        }
+    }
+}
+
+/// Turns after a bite in which the hook can still be set.
+pub const HOOK_WINDOW: u8 = 3;
+
+/// Tension an early or late strike takes off or adds.
+const HOOK_TENSION: i32 = 10;
+
+/// How well the strike after a bite was timed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HookTiming {
+    /// Struck before the fish had the bait; the hook barely holds.
+    Early,
+    /// Struck as the fish turned with the bait.
+    Perfect,
+    /// Struck after the fish felt the line.
+    Late,
+}
+
+impl HookTiming {
+    /// Timing of a strike `turns` after the bite, or `None` once the
+    /// fish has dropped the bait.
+    pub fn after(turns: u8) -> Option<Self> {
+        match turns {
+            0 => Some(Self::Early),
+            1 => Some(Self::Perfect),
+            t if t < HOOK_WINDOW => Some(Self::Late),
+            _ => None,
+        }
+    }
+}
+
+/// Turns a fight, or each stage of a boss fight, lasts.
+pub const FIGHT_DURATION: i32 = 5;
+
+/// A surging boss lunges every this many turns.
+const SURGE_INTERVAL: u32 = 3;
+
+/// Stamina a fish starts a fight with.
+pub const MAX_STAMINA: i32 = 10;
+
+/// Stamina a fish spends on a turn the angler gives line.
+const STAMINA_SPENT: i32 = 4;
+
+/// Updates of the tension a [`TensionMeter`] remembers for its trend.
+pub const TENSION_HISTORY: usize = 4;
+
+/// Active phases a fish of `style` alternates between, resting in
+/// [`FightPhase::Tire`] in between.
+fn active_phases(style: FightStyle) -> [FightPhase; 2] {
+    match style {
+        FightStyle::Aggressive => [FightPhase::Thrash, FightPhase::Run],
+        FightStyle::Endurance => [FightPhase::Run, FightPhase::Dive],
+        FightStyle::Evasive => [FightPhase::Run, FightPhase::Thrash],
+    }
+}
+
+/// Stamina a tired fish of `style` recovers each turn it is not reeled.
+fn recovery(style: FightStyle) -> i32 {
+    match style {
+        FightStyle::Endurance => 5,
+        FightStyle::Aggressive | FightStyle::Evasive => 3,
+    }
+}
+
 /// Manages fishing line tension over time.
 #[derive(Debug)]
 pub struct TensionMeter {
@@ -31,59 +135,129 @@ pub struct TensionMeter {
     pub style: FightStyle,
     /// Effectiveness multiplier when reeling.
     pub reel_factor: f32,
+    /// Tension at or below which an evasive fish slips the hook.
+    pub escape_threshold: i32,
+    /// What the fish is doing this turn.
+    pub phase: FightPhase,
+    /// Stamina left before the fish tires, up to [`MAX_STAMINA`].
+    pub stamina: i32,
+    /// How many active phases the fish has been through.
+    pub runs: usize,
+    /// The fish noticed the angler and spends its stamina more slowly.
+    pub alert: bool,
+    /// Highest tension the line has held this fight; it decides how much
+    /// the fight wears the gear.
+    pub peak_tension: i32,
+    /// Stage of a boss fight the fish is in, from 1.
+    pub stage: u32,
+    /// Stages the fight lasts; 1 for everything but bosses.
+    pub stages: u32,
+    /// How a boss fights on top of its style.
+    pub pattern: Option<BossPattern>,
+    /// Turns the fight has gone on.
+    pub turns: u32,
+    /// Tension after each of the last [`TENSION_HISTORY`] updates, oldest
+    /// first.
+    pub history: VecDeque<i32>,
 }
 
 impl TensionMeter {
-    /// Creates a new [`TensionMeter`] with the given fish strength.
-    pub fn new(strength: i32, style: FightStyle, reel_factor: f32) -> Self {
+    /// Creates a new [`TensionMeter`] for a fish of the given strength.
+    ///
+    /// `size` is the fish's weight relative to its species' average and
+    /// scales the strength, so heavy individuals pull harder.
+    pub fn new(strength: i32, style: FightStyle, reel_factor: f32, size: f32) -> Self {
         Self {
             tension: 0,
             max_tension: 100,
-            duration: 5,
-            strength,
+            duration: FIGHT_DURATION,
+            strength: ((strength as f32 * size).round() as i32).max(1),
             style,
             reel_factor,
+            escape_threshold: 5,
+            phase: active_phases(style)[0],
+            stamina: MAX_STAMINA,
+            runs: 0,
+            alert: false,
+            peak_tension: 0,
+            stage: 1,
+            stages: 1,
+            pattern: None,
+            turns: 0,
+            history: VecDeque::with_capacity(TENSION_HISTORY),
+        }
+    }
+
+    /// Turns the fight into a boss fight of several stages.
+    pub fn set_boss(&mut self, fight: &BossFight) {
+        self.stages = fight.stages.max(1);
+        self.pattern = Some(fight.pattern);
+        if fight.pattern == BossPattern::Sound {
+            self.phase = FightPhase::Dive;
+        }
+    }
+
+    /// Applies how well the hook was set: an early strike leaves the line
+    /// slacker, a late one tenser, and either alerts the fish.
+    pub fn set_hook(&mut self, timing: HookTiming) {
+        match timing {
+            HookTiming::Early => self.tension = (self.tension - HOOK_TENSION).max(0),
+            HookTiming::Perfect => {}
+            HookTiming::Late => self.tension += HOOK_TENSION,
         }
+        self.alert = timing != HookTiming::Perfect;
     }
 
     /// Updates internal tension.
     ///
-    /// If `reel` is `true`, the player attempts to reduce tension by reeling
-    /// in the line. Otherwise the fish pulls with its strength. The returned
-    /// [`MeterState`] indicates whether the mini game has finished.
+    /// If `reel` is `true`, the player reels in the line, which lowers the
+    /// tension while the fish thrashes or tires but fights a running or
+    /// diving fish head on. Otherwise the player gives line and the fish
+    /// pulls and spends stamina. The returned [`MeterState`] indicates
+    /// whether the mini game has finished.
     pub fn update(&mut self, reel: bool) -> MeterState {
         let before = self.tension;
-        if reel {
-            let reduction = (10.0 * self.reel_factor).round() as i32;
-            self.tension = (self.tension - reduction).max(0);
+        let reduction = (10.0 * self.reel_factor).round() as i32;
+        let strength = self.strength;
+        let (change, spent) = match (self.phase, reel) {
+            (FightPhase::Run, true) => (strength * 2, 1),
+            (FightPhase::Run, false) => (strength, STAMINA_SPENT),
+            (FightPhase::Dive, true) => (strength * 3, 1),
+            (FightPhase::Dive, false) => (strength / 2, STAMINA_SPENT),
+            (FightPhase::Thrash, true) => (-reduction, STAMINA_SPENT / 2),
+            (FightPhase::Thrash, false) => (strength * 2, STAMINA_SPENT),
+            (FightPhase::Tire, true) => (-reduction * 3 / 2, 0),
+            (FightPhase::Tire, false) => (strength / 2, -recovery(self.style)),
+        };
+        let surge = self.pattern == Some(BossPattern::Surge) && self.turns % SURGE_INTERVAL == 2;
+        let change = if surge { change + strength } else { change };
+        if !reel && self.style == FightStyle::Evasive && self.is_slack() {
+            self.tension = 0;
         } else {
-            match self.style {
-                FightStyle::Aggressive => {
-                    self.tension += self.strength * 2;
-                }
-                FightStyle::Endurance => {
-                    let bonus = if self.duration > 2 {
-                        self.strength
-                    } else {
-                        self.strength / 2
-                    };
-                    self.tension += bonus;
-                }
-                FightStyle::Evasive => {
-                    if self.tension <= 5 {
-                        self.tension = 0;
-                    } else {
-                        self.tension += self.strength;
-                    }
-                }
-            }
+            self.tension = (self.tension + change).max(0);
         }
+        self.turns += 1;
+        let spent = if self.alert && spent > 1 {
+            spent - 1
+        } else {
+            spent
+        };
+        self.stamina = (self.stamina - spent).clamp(0, MAX_STAMINA);
+        self.next_phase();
         self.duration -= 1;
+        self.peak_tension = self.peak_tension.max(before).max(self.tension);
+        if self.history.len() == TENSION_HISTORY {
+            self.history.pop_front();
+        }
+        self.history.push_back(self.tension);
 
         if self.tension >= self.max_tension {
             MeterState::Broken
         } else if before > 0 && self.tension == 0 {
             MeterState::Lost
+        } else if self.duration <= 0 && self.stage < self.stages {
+            self.next_stage();
+            MeterState::Ongoing
         } else if self.duration <= 0 {
             MeterState::Success
         } else {
@@ -91,27 +265,284 @@ impl TensionMeter {
         }
     }
 
+    /// Returns `true` when the line is slack enough for the fish to slip free.
+    pub fn is_slack(&self) -> bool {
+        self.tension <= self.escape_threshold
+    }
+
+    /// How the fish's pull has moved the tension over the remembered
+    /// updates: positive while it rises, negative while it falls.
+    pub fn trend(&self) -> i32 {
+        match (self.history.front(), self.history.back()) {
+            (Some(first), Some(last)) => last - first,
+            _ => 0,
+        }
+    }
+
+    /// Most tension the fish can add in one turn: reeling into a dive,
+    /// with a surging boss lunging on top.
+    pub fn spike(&self) -> i32 {
+        match self.pattern {
+            Some(BossPattern::Surge) => self.strength * 4,
+            _ => self.strength * 3,
+        }
+    }
+
+    /// Returns `true` when one more spike would snap the line.
+    pub fn near_breaking(&self) -> bool {
+        self.tension + self.spike() >= self.max_tension
+    }
+
+    /// Short description of how the fish is currently fighting.
+    pub fn phase_text(&self) -> &'static str {
+        self.phase.name()
+    }
+
+    /// Tires an exhausted fish, and sends a rested one into its next run.
+    fn next_phase(&mut self) {
+        if self.phase != FightPhase::Tire && self.stamina == 0 {
+            self.phase = FightPhase::Tire;
+        } else if self.phase == FightPhase::Tire && self.stamina == MAX_STAMINA {
+            self.runs += 1;
+            self.phase = active_phases(self.style)[self.runs % 2];
+        }
+    }
+
+    /// Starts the next stage of a boss fight with a fresh duration bar and
+    /// the fish rested; a sounding boss dives and a frenzied one pulls
+    /// harder.
+    fn next_stage(&mut self) {
+        self.stage += 1;
+        self.duration = FIGHT_DURATION;
+        self.stamina = MAX_STAMINA;
+        self.runs += 1;
+        self.phase = match self.pattern {
+            Some(BossPattern::Sound) => FightPhase::Dive,
+            _ => active_phases(self.style)[self.runs % 2],
+        };
+        if self.pattern == Some(BossPattern::Frenzy) {
+            self.strength += self.strength / 2;
+        }
+    }
+
     /// Draws the tension meter to stdout.
     pub fn draw(&self) {
         println!("Tension meter: {}/{}", self.tension, self.max_tension);
     }
 }
 
-/// Calculates bite probability based on environment and gear.
+/// Tension per tile of cast distance that a fight starts with.
+pub const CAST_TENSION_PER_TILE: i32 = 3;
+
+/// Tension a fight starts with after a cast of `distance` tiles; the more
+/// line is out, the harder the first pull.
+pub fn cast_tension(distance: i32) -> i32 {
+    distance.max(0) * CAST_TENSION_PER_TILE
+}
+
+/// Bite chance added when the cast lands in chum.
+pub const CHUM_BONUS: f32 = 0.15;
+
+/// How deep the lure runs, chosen while aiming.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LureDepth {
+    Shallow,
+    #[default]
+    Mid,
+    Deep,
+}
+
+impl LureDepth {
+    /// Name shown while aiming.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Shallow => "Shallow",
+            Self::Mid => "Mid",
+            Self::Deep => "Deep",
+        }
+    }
+
+    /// The next depth in the selector, wrapping around.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Shallow => Self::Mid,
+            Self::Mid => Self::Deep,
+            Self::Deep => Self::Shallow,
+        }
+    }
+
+    /// Depths in metres the lure runs through.
+    pub fn range(self) -> (i32, i32) {
+        match self {
+            Self::Shallow => (0, 10),
+            Self::Mid => (10, 40),
+            Self::Deep => (40, i32::MAX),
+        }
+    }
+
+    /// Whether the lure passes through a fish's `min..=max` depth band.
+    fn reaches(self, min: i32, max: i32) -> bool {
+        let (top, bottom) = self.range();
+        top <= max && min <= bottom
+    }
+}
+
+/// How the lure is worked back through the water.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Retrieve {
+    Slow,
+    #[default]
+    Steady,
+    Jerky,
+}
+
+impl Retrieve {
+    /// Name shown while aiming.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Slow => "Slow",
+            Self::Steady => "Steady",
+            Self::Jerky => "Jerky",
+        }
+    }
+
+    /// The next style in the selector, wrapping around.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Slow => Self::Steady,
+            Self::Steady => Self::Jerky,
+            Self::Jerky => Self::Slow,
+        }
+    }
+
+    /// The retrieve a fish of `style` goes for: aggressive fish chase an
+    /// erratic lure, stubborn ones take a steady one and wary ones only a
+    /// slow one.
+    pub fn preferred(style: FightStyle) -> Self {
+        match style {
+            FightStyle::Aggressive => Self::Jerky,
+            FightStyle::Endurance => Self::Steady,
+            FightStyle::Evasive => Self::Slow,
+        }
+    }
+}
+
+/// How a cast is fished: the lure's depth and the retrieve.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Presentation {
+    pub depth: LureDepth,
+    pub retrieve: Retrieve,
+}
+
+/// Bite chance added when the lure runs through a fish's depth band, and
+/// taken off when it misses.
+const DEPTH_MATCH_BONUS: f32 = 0.1;
+const DEPTH_MISS_PENALTY: f32 = 0.2;
+/// Bite chance added for the retrieve a fish prefers, and taken off for
+/// the others.
+const RETRIEVE_BONUS: f32 = 0.1;
+const RETRIEVE_PENALTY: f32 = 0.05;
+
+impl Presentation {
+    /// Bite chance this presentation adds for `fish`, negative when the
+    /// lure runs at the wrong depth or is worked the wrong way.
+    pub fn appeal(self, fish: &FishType) -> f32 {
+        let depth = if self.depth.reaches(fish.min_depth, fish.max_depth) {
+            DEPTH_MATCH_BONUS
+        } else {
+            -DEPTH_MISS_PENALTY
+        };
+        let retrieve = if self.retrieve == Retrieve::preferred(fish.fight_style) {
+            RETRIEVE_BONUS
+        } else {
+            -RETRIEVE_PENALTY
+        };
+        depth + retrieve
+    }
+}
+
+/// Calculates bite probability based on environment, gear and the fish.
 ///
-/// `tile` determines the water depth; `bait_bonus` adds a flat bonus.
-pub fn bite_probability(tile: TileKind, bait_bonus: f32) -> f32 {
+/// `tile` determines the water depth; `bait_bonus` adds a flat bonus;
+/// hungrier fish bite more readily, and casting into chum (`chummed`)
+/// adds [`CHUM_BONUS`]. The cast's `presentation` is compared with the
+/// depth band and fight style of `fish`.
+pub fn bite_probability(
+    tile: TileKind,
+    bait_bonus: f32,
+    feeding: FeedingState,
+    chummed: bool,
+    presentation: Presentation,
+    fish: &FishType,
+) -> f32 {
     let depth_bonus = match tile {
         TileKind::ShallowWater => 0.1,
+        TileKind::CoralReef => 0.15,
+        TileKind::KelpForest => 0.2,
         TileKind::DeepWater => 0.3,
-        TileKind::Land => 0.0,
+        TileKind::Land | TileKind::Sandbar | TileKind::Ice => 0.0,
+    };
+    let hunger_bonus = match feeding {
+        FeedingState::Sated => -0.15,
+        FeedingState::Foraging => 0.0,
+        FeedingState::Ravenous => 0.2,
     };
-    (0.3 + depth_bonus + bait_bonus).clamp(0.0, 1.0)
+    let chum_bonus = if chummed { CHUM_BONUS } else { 0.0 };
+    let appeal = presentation.appeal(fish);
+    (0.3 + depth_bonus + bait_bonus + hunger_bonus + chum_bonus + appeal).clamp(0.0, 1.0)
+}
+
+/// Species living no deeper than this feel the surface temperature.
+const THERMOCLINE_DEPTH: i32 = 40;
+
+/// Water temperature, in degrees Celsius, at which the seasons leave bites
+/// unchanged.
+const NEUTRAL_TEMPERATURE: i32 = 15;
+
+/// Bite chance added per degree the water is warmer than
+/// [`NEUTRAL_TEMPERATURE`].
+const BITE_PER_DEGREE: f32 = 0.01;
+
+/// Bite chance `fish` gains or loses in water of `temperature` degrees.
+/// Warm water livens up species near the surface and cold water slows them;
+/// fish below the thermocline do not notice the seasons.
+pub fn temperature_bonus(temperature: i32, fish: &FishType) -> f32 {
+    if fish.min_depth >= THERMOCLINE_DEPTH {
+        return 0.0;
+    }
+    (temperature - NEUTRAL_TEMPERATURE) as f32 * BITE_PER_DEGREE
+}
+
+/// Bite chance added at the top of the biggest high tide.
+const TIDE_BONUS: f32 = 0.15;
+
+/// Bite chance from the tide, where `high` is how far the water stands
+/// towards the top of a spring high tide, from 0 to 1. Fish feed hardest
+/// on a high tide at dawn or dusk; at other times the tide does not
+/// change bites.
+pub fn tide_bonus(high: f32, twilight: bool) -> f32 {
+    if !twilight {
+        return 0.0;
+    }
+    high.clamp(0.0, 1.0) * TIDE_BONUS
+}
+
+/// Distance from where the cast lands within which fish can strike.
+pub const STRIKE_RADIUS: i32 = 3;
+
+/// Chance that a fish `distance` tiles from the bait strikes, given its
+/// [`bite_probability`] `base`. Fish right at the bait bite at `base`; the
+/// chance falls off linearly to a fifth of it at [`STRIKE_RADIUS`].
+pub fn strike_probability(base: f32, distance: i32) -> f32 {
+    if !(0..=STRIKE_RADIUS).contains(&distance) {
+        return 0.0;
+    }
+    base * (1.0 - 0.8 * distance as f32 / STRIKE_RADIUS as f32)
 }
 
 impl Default for TensionMeter {
     fn default() -> Self {
-        Self::new(5, FightStyle::Aggressive, 1.0)
+        Self::new(5, FightStyle::Aggressive, 1.0, 1.0)
     }
 }
 
@@ -123,6 +554,40 @@ pub fn init() {
 mod tests {
     use super::*;
 
+    fn fish_type(min_depth: i32, max_depth: i32, fight_style: FightStyle) -> FishType {
+        FishType {
+            id: "A".into(),
+            name: "A".into(),
+            rarity: 1.0,
+            strength: 1,
+            min_depth,
+            max_depth,
+            fight_style,
+            legendary: false,
+            description: String::new(),
+            habitat_note: String::new(),
+            diet: Vec::new(),
+            min_weight: 1.0,
+            max_weight: 1.0,
+            biomes: Vec::new(),
+            boss: None,
+            seasons: Vec::new(),
+        }
+    }
+
+    /// Bite chance for a mid-water fish that likes the default presentation.
+    fn plain_bite(tile: TileKind, bait_bonus: f32, feeding: FeedingState, chummed: bool) -> f32 {
+        let fish = fish_type(0, 20, FightStyle::Endurance);
+        bite_probability(
+            tile,
+            bait_bonus,
+            feeding,
+            chummed,
+            Presentation::default(),
+            &fish,
+        )
+    }
+
     #[test]
     fn tension_increases() {
         let mut meter = TensionMeter::default();
@@ -130,9 +595,25 @@ mod tests {
         assert_eq!(meter.tension, meter.strength * 2);
     }
 
+    #[test]
+    fn heavier_fish_pull_harder() {
+        assert_eq!(
+            TensionMeter::new(10, FightStyle::Aggressive, 1.0, 1.5).strength,
+            15
+        );
+        assert_eq!(
+            TensionMeter::new(10, FightStyle::Aggressive, 1.0, 0.5).strength,
+            5
+        );
+        assert_eq!(
+            TensionMeter::new(1, FightStyle::Aggressive, 1.0, 0.1).strength,
+            1
+        );
+    }
+
     #[test]
     fn reel_reduces_tension() {
-        let mut meter = TensionMeter::new(10, FightStyle::Aggressive, 1.0);
+        let mut meter = TensionMeter::new(10, FightStyle::Aggressive, 1.0, 1.0);
         meter.update(false); // tension 20
         meter.update(true); // reel -> 10
         assert!(meter.tension < 20);
@@ -142,7 +623,7 @@ mod tests {
     fn breaks_when_exceeding_max() {
         let mut meter = TensionMeter {
             max_tension: 5,
-            ..TensionMeter::new(10, FightStyle::Aggressive, 1.0)
+            ..TensionMeter::new(10, FightStyle::Aggressive, 1.0, 1.0)
         };
         assert_eq!(meter.update(false), MeterState::Broken);
     }
@@ -151,14 +632,14 @@ mod tests {
     fn succeeds_after_duration() {
         let mut meter = TensionMeter {
             duration: 1,
-            ..TensionMeter::new(1, FightStyle::Aggressive, 1.0)
+            ..TensionMeter::new(1, FightStyle::Aggressive, 1.0, 1.0)
         };
         assert_eq!(meter.update(false), MeterState::Success);
     }
 
     #[test]
     fn repeated_reel_zeroes_tension() {
-        let mut meter = TensionMeter::new(5, FightStyle::Aggressive, 1.0);
+        let mut meter = TensionMeter::new(5, FightStyle::Aggressive, 1.0, 1.0);
         meter.tension = 20;
         for _ in 0..3 {
             meter.update(true);
@@ -168,12 +649,43 @@ mod tests {
 
     #[test]
     fn lost_when_tension_drops_to_zero() {
-        let mut meter = TensionMeter::new(5, FightStyle::Aggressive, 1.0);
+        let mut meter = TensionMeter::new(5, FightStyle::Aggressive, 1.0, 1.0);
         meter.tension = 10;
         let state = meter.update(true);
         assert_eq!(state, MeterState::Lost);
     }
 
+    #[test]
+    fn history_keeps_the_trend_of_the_last_updates() {
+        let mut meter = TensionMeter {
+            duration: 100,
+            ..TensionMeter::new(5, FightStyle::Endurance, 1.0, 1.0)
+        };
+        assert_eq!(meter.trend(), 0);
+        for _ in 0..TENSION_HISTORY + 2 {
+            meter.update(true);
+        }
+        assert_eq!(meter.history.len(), TENSION_HISTORY);
+        assert_eq!(meter.history.back(), Some(&meter.tension));
+        assert!(meter.trend() > 0, "reeling into a run pulls the tension up");
+        meter.tension = 60;
+        meter.phase = FightPhase::Tire;
+        meter.update(true);
+        meter.update(true);
+        assert!(meter.trend() < 0);
+    }
+
+    #[test]
+    fn near_breaking_within_one_spike() {
+        let mut meter = TensionMeter::new(5, FightStyle::Aggressive, 1.0, 1.0);
+        meter.tension = meter.max_tension - meter.spike() - 1;
+        assert!(!meter.near_breaking());
+        meter.tension += 1;
+        assert!(meter.near_breaking());
+        meter.phase = FightPhase::Dive;
+        assert_eq!(meter.update(true), MeterState::Broken);
+    }
+
     #[test]
     fn default_values() {
         let meter = TensionMeter::default();
@@ -184,29 +696,47 @@ mod tests {
 
     #[test]
     fn deep_water_increases_bite_chance() {
-        let shallow = bite_probability(TileKind::ShallowWater, 0.0);
-        let deep = bite_probability(TileKind::DeepWater, 0.0);
+        let shallow = plain_bite(TileKind::ShallowWater, 0.0, FeedingState::Foraging, false);
+        let deep = plain_bite(TileKind::DeepWater, 0.0, FeedingState::Foraging, false);
         assert!(deep > shallow);
     }
 
+    #[test]
+    fn reefs_and_kelp_bite_better_than_open_shallows() {
+        let bite = |tile| plain_bite(tile, 0.0, FeedingState::Foraging, false);
+        assert!(bite(TileKind::CoralReef) > bite(TileKind::ShallowWater));
+        assert!(bite(TileKind::KelpForest) > bite(TileKind::CoralReef));
+        assert!(bite(TileKind::DeepWater) > bite(TileKind::KelpForest));
+        assert_eq!(bite(TileKind::Sandbar), bite(TileKind::Land));
+    }
+
     #[test]
     fn bait_bonus_applied() {
-        let base = bite_probability(TileKind::Land, 0.0);
-        let bonus = bite_probability(TileKind::Land, 0.2);
+        let base = plain_bite(TileKind::Land, 0.0, FeedingState::Foraging, false);
+        let bonus = plain_bite(TileKind::Land, 0.2, FeedingState::Foraging, false);
         assert!(bonus > base);
         assert!(bonus <= 1.0);
     }
 
+    #[test]
+    fn hungry_fish_bite_more_readily() {
+        let tile = TileKind::ShallowWater;
+        let sated = plain_bite(tile, 0.0, FeedingState::Sated, false);
+        let foraging = plain_bite(tile, 0.0, FeedingState::Foraging, false);
+        let ravenous = plain_bite(tile, 0.0, FeedingState::Ravenous, false);
+        assert!(sated < foraging && foraging < ravenous);
+    }
+
     #[test]
     fn aggressive_style_spikes_tension() {
-        let mut meter = TensionMeter::new(2, FightStyle::Aggressive, 1.0);
+        let mut meter = TensionMeter::new(2, FightStyle::Aggressive, 1.0, 1.0);
         meter.update(false);
         assert_eq!(meter.tension, 4);
     }
 
     #[test]
     fn endurance_style_slow_end() {
-        let mut meter = TensionMeter::new(4, FightStyle::Endurance, 1.0);
+        let mut meter = TensionMeter::new(4, FightStyle::Endurance, 1.0, 1.0);
         meter.update(false); // duration 5 -> add 4
         for _ in 0..3 {
             meter.update(false);
@@ -217,17 +747,263 @@ mod tests {
 
     #[test]
     fn evasive_style_can_escape() {
-        let mut meter = TensionMeter::new(3, FightStyle::Evasive, 1.0);
+        let mut meter = TensionMeter::new(3, FightStyle::Evasive, 1.0, 1.0);
         meter.tension = 5;
         let state = meter.update(false);
         assert_eq!(state, MeterState::Lost);
     }
 
+    #[test]
+    fn escape_threshold_controls_evasive_escape() {
+        let mut meter = TensionMeter::new(3, FightStyle::Evasive, 1.0, 1.0);
+        meter.tension = 5;
+        meter.escape_threshold = 2;
+        assert_eq!(meter.update(false), MeterState::Ongoing);
+        assert_eq!(meter.tension, 8);
+    }
+
+    #[test]
+    fn slack_when_at_or_below_threshold() {
+        let mut meter = TensionMeter::default();
+        assert!(meter.is_slack());
+        meter.tension = meter.escape_threshold + 1;
+        assert!(!meter.is_slack());
+    }
+
+    #[test]
+    fn endurance_phase_tires_near_end() {
+        let mut meter = TensionMeter::new(4, FightStyle::Endurance, 1.0, 1.0);
+        assert_eq!(meter.phase_text(), "Running");
+        for _ in 0..3 {
+            meter.update(false);
+        }
+        assert_eq!(meter.stamina, 0);
+        assert_eq!(meter.phase_text(), "Tiring");
+    }
+
+    #[test]
+    fn reeling_a_running_fish_spikes_tension() {
+        let mut meter = TensionMeter::new(5, FightStyle::Endurance, 1.0, 1.0);
+        meter.tension = 20;
+        meter.update(true);
+        assert_eq!(meter.tension, 30);
+        assert_eq!(meter.stamina, MAX_STAMINA - 1);
+        meter.phase = FightPhase::Dive;
+        meter.update(true);
+        assert_eq!(meter.tension, 45);
+    }
+
+    #[test]
+    fn giving_line_then_reeling_beats_mashing_reel() {
+        let fight = |reels: &dyn Fn(&TensionMeter) -> bool| {
+            let mut meter = TensionMeter {
+                duration: 8,
+                ..TensionMeter::new(10, FightStyle::Endurance, 1.0, 1.0)
+            };
+            meter.tension = 15;
+            loop {
+                match meter.update(reels(&meter)) {
+                    MeterState::Ongoing => {}
+                    state => return state,
+                }
+            }
+        };
+        assert_eq!(fight(&|_| true), MeterState::Broken);
+        // reel only while the fish allows it and the line stays taut
+        let alternate = |m: &TensionMeter| m.phase.hint().starts_with("reel") && m.tension > 20;
+        assert_eq!(fight(&alternate), MeterState::Success);
+    }
+
+    #[test]
+    fn strike_timing_sets_tension_and_alertness() {
+        assert_eq!(HookTiming::after(0), Some(HookTiming::Early));
+        assert_eq!(HookTiming::after(1), Some(HookTiming::Perfect));
+        assert_eq!(HookTiming::after(HOOK_WINDOW - 1), Some(HookTiming::Late));
+        assert_eq!(HookTiming::after(HOOK_WINDOW), None);
+
+        let hooked = |timing| {
+            let mut meter = TensionMeter::new(5, FightStyle::Endurance, 1.0, 1.0);
+            meter.tension = 15;
+            meter.set_hook(timing);
+            meter
+        };
+        assert_eq!(hooked(HookTiming::Early).tension, 5);
+        assert_eq!(hooked(HookTiming::Late).tension, 25);
+        let mut perfect = hooked(HookTiming::Perfect);
+        let mut late = hooked(HookTiming::Late);
+        assert!(!perfect.alert && late.alert);
+        perfect.update(false);
+        late.update(false);
+        assert!(late.stamina > perfect.stamina);
+    }
+
+    #[test]
+    fn peak_tension_tracks_the_hardest_pull() {
+        let mut meter = TensionMeter::new(10, FightStyle::Aggressive, 1.0, 1.0);
+        meter.tension = 15;
+        meter.update(false);
+        meter.update(true);
+        assert_eq!(meter.tension, 25);
+        assert_eq!(meter.peak_tension, 35);
+    }
+
+    #[test]
+    fn rested_fish_start_their_next_run() {
+        let mut meter = TensionMeter::new(5, FightStyle::Aggressive, 1.0, 1.0);
+        assert_eq!(meter.phase, FightPhase::Thrash);
+        meter.stamina = 1;
+        meter.update(false);
+        assert_eq!(meter.phase, FightPhase::Tire);
+        // reeling a tired fish gains extra line but it does not recover
+        meter.tension = 40;
+        meter.update(true);
+        assert_eq!(meter.tension, 25);
+        assert_eq!(meter.phase, FightPhase::Tire);
+        for _ in 0..4 {
+            meter.update(false);
+        }
+        assert_eq!(meter.stamina, MAX_STAMINA);
+        assert_eq!(meter.phase, FightPhase::Run);
+        assert_eq!(meter.phase.hint(), "give line");
+    }
+
     #[test]
     fn reel_factor_increases_reduction() {
-        let mut meter = TensionMeter::new(5, FightStyle::Aggressive, 2.0);
+        let mut meter = TensionMeter::new(5, FightStyle::Aggressive, 2.0, 1.0);
         meter.tension = 20;
         meter.update(true);
         assert!(meter.tension < 10); // reduction > default 10
     }
+
+    #[test]
+    fn longer_casts_start_tenser() {
+        assert_eq!(cast_tension(0), 0);
+        assert!(cast_tension(8) > cast_tension(2));
+        assert_eq!(cast_tension(-1), 0);
+    }
+
+    #[test]
+    fn chum_raises_bite_chance() {
+        let plain = plain_bite(TileKind::ShallowWater, 0.0, FeedingState::Foraging, false);
+        let chummed = plain_bite(TileKind::ShallowWater, 0.0, FeedingState::Foraging, true);
+        assert!((chummed - plain - CHUM_BONUS).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn lures_fished_to_the_fish_bite_best() {
+        let bite = |presentation, fish: &FishType| {
+            bite_probability(
+                TileKind::DeepWater,
+                0.0,
+                FeedingState::Foraging,
+                false,
+                presentation,
+                fish,
+            )
+        };
+        let deep_brawler = fish_type(60, 120, FightStyle::Aggressive);
+        let deep_jerky = Presentation {
+            depth: LureDepth::Deep,
+            retrieve: Retrieve::Jerky,
+        };
+        let shallow_jerky = Presentation {
+            depth: LureDepth::Shallow,
+            ..deep_jerky
+        };
+        let deep_slow = Presentation {
+            retrieve: Retrieve::Slow,
+            ..deep_jerky
+        };
+        let best = bite(deep_jerky, &deep_brawler);
+        assert!(
+            (best - bite(shallow_jerky, &deep_brawler) - DEPTH_MATCH_BONUS - DEPTH_MISS_PENALTY)
+                .abs()
+                < 1e-6
+        );
+        assert!(
+            (best - bite(deep_slow, &deep_brawler) - RETRIEVE_BONUS - RETRIEVE_PENALTY).abs()
+                < 1e-6
+        );
+
+        // Bands are inclusive, so a lure reaches fish at its edge
+        let reef_fish = fish_type(5, 10, FightStyle::Evasive);
+        assert!(LureDepth::Shallow.reaches(5, 10) && LureDepth::Mid.reaches(5, 10));
+        assert!(!LureDepth::Deep.reaches(5, 10));
+        let slow = Presentation {
+            depth: LureDepth::Shallow,
+            retrieve: Retrieve::Slow,
+        };
+        assert!(bite(slow, &reef_fish) > bite(deep_jerky, &reef_fish));
+    }
+
+    #[test]
+    fn selectors_cycle_through_every_choice() {
+        assert_eq!(LureDepth::Mid.next().next().next(), LureDepth::Mid);
+        assert_eq!(LureDepth::Deep.next(), LureDepth::Shallow);
+        assert_eq!(Retrieve::Jerky.next(), Retrieve::Slow);
+        assert_eq!(Retrieve::default().next().next().next(), Retrieve::Steady);
+    }
+
+    #[test]
+    fn bosses_fight_through_every_stage() {
+        let fight = |pattern| BossFight {
+            stages: 2,
+            pattern,
+            min_rod: 0,
+            bonus: 0,
+            lore: String::new(),
+        };
+        let mut frenzy = TensionMeter::new(4, FightStyle::Aggressive, 1.0, 1.0);
+        frenzy.max_tension = 10_000;
+        frenzy.set_boss(&fight(BossPattern::Frenzy));
+        let mut turns = 0;
+        while frenzy.update(false) == MeterState::Ongoing {
+            turns += 1;
+        }
+        assert_eq!(turns + 1, FIGHT_DURATION * 2, "two duration bars");
+        assert_eq!(frenzy.stage, 2);
+        assert_eq!(frenzy.strength, 6, "pulls harder in the second stage");
+
+        let mut sound = TensionMeter::new(4, FightStyle::Evasive, 1.0, 1.0);
+        sound.set_boss(&fight(BossPattern::Sound));
+        assert_eq!(sound.phase, FightPhase::Dive);
+
+        // A surging boss lunges on the third turn on top of its pull
+        let mut plain = TensionMeter::new(4, FightStyle::Endurance, 1.0, 1.0);
+        let mut surge = TensionMeter::new(4, FightStyle::Endurance, 1.0, 1.0);
+        surge.set_boss(&fight(BossPattern::Surge));
+        for _ in 0..3 {
+            plain.update(false);
+            surge.update(false);
+        }
+        assert_eq!(surge.tension, plain.tension + 4);
+    }
+
+    #[test]
+    fn warm_water_livens_up_shallow_fish() {
+        let shallow = fish_type(0, 20, FightStyle::Endurance);
+        let deep = fish_type(60, 120, FightStyle::Endurance);
+        let summer = data::Season::Summer.water_temperature();
+        let winter = data::Season::Winter.water_temperature();
+        assert!(temperature_bonus(summer, &shallow) > 0.0);
+        assert!(temperature_bonus(winter, &shallow) < 0.0);
+        assert_eq!(temperature_bonus(winter, &deep), 0.0);
+        assert_eq!(temperature_bonus(NEUTRAL_TEMPERATURE, &shallow), 0.0);
+    }
+
+    #[test]
+    fn twilight_high_tides_bring_bites() {
+        assert_eq!(tide_bonus(1.0, true), TIDE_BONUS);
+        assert!(tide_bonus(0.5, true) < TIDE_BONUS);
+        assert_eq!(tide_bonus(1.0, false), 0.0);
+        assert_eq!(tide_bonus(-1.0, true), 0.0, "low water gives nothing");
+    }
+
+    #[test]
+    fn strikes_fall_off_with_distance() {
+        assert_eq!(strike_probability(0.5, 0), 0.5);
+        assert!(strike_probability(0.5, 1) < 0.5);
+        assert!((strike_probability(0.5, STRIKE_RADIUS) - 0.1).abs() < 1e-6);
+        assert_eq!(strike_probability(0.5, STRIKE_RADIUS + 1), 0.0);
+    }
 }
@@ -0,0 +1,89 @@
+//! Optional rubber-banding of bite rates based on recent fight outcomes.
+
+use std::collections::VecDeque;
+
+/// Number of recent fights considered.
+const WINDOW: usize = 6;
+/// Bite probability shift per net failure (or success) in the window.
+const STEP: f32 = 0.03;
+/// Maximum absolute bite probability adjustment.
+const MAX_BONUS: f32 = 0.12;
+
+/// Tracks recent fight outcomes over a sliding window.
+#[derive(Clone, Debug, Default)]
+pub struct AdaptiveDifficulty {
+    enabled: bool,
+    outcomes: VecDeque<bool>,
+}
+
+impl AdaptiveDifficulty {
+    /// Creates a tracker, optionally disabled.
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            outcomes: VecDeque::with_capacity(WINDOW),
+        }
+    }
+
+    /// Enables or disables adjustments without forgetting history.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Records the result of a finished fight (`true` when the fish was landed).
+    pub fn record(&mut self, caught: bool) {
+        if self.outcomes.len() == WINDOW {
+            self.outcomes.pop_front();
+        }
+        self.outcomes.push_back(caught);
+    }
+
+    /// Bite probability adjustment: positive after failures, negative after catch streaks.
+    pub fn bite_bonus(&self) -> f32 {
+        if !self.enabled {
+            return 0.0;
+        }
+        let caught = self.outcomes.iter().filter(|&&c| c).count() as f32;
+        let failed = self.outcomes.len() as f32 - caught;
+        ((failed - caught) * STEP).clamp(-MAX_BONUS, MAX_BONUS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabled_has_no_effect() {
+        let mut a = AdaptiveDifficulty::new(false);
+        for _ in 0..WINDOW {
+            a.record(false);
+        }
+        assert_eq!(a.bite_bonus(), 0.0);
+    }
+
+    #[test]
+    fn failures_raise_and_catches_lower() {
+        let mut a = AdaptiveDifficulty::new(true);
+        a.record(false);
+        a.record(false);
+        assert!(a.bite_bonus() > 0.0);
+        let mut b = AdaptiveDifficulty::new(true);
+        b.record(true);
+        b.record(true);
+        assert!(b.bite_bonus() < 0.0);
+    }
+
+    #[test]
+    fn window_slides_and_bonus_is_clamped() {
+        let mut a = AdaptiveDifficulty::new(true);
+        for _ in 0..20 {
+            a.record(false);
+        }
+        assert!((a.bite_bonus() - MAX_BONUS).abs() < f32::EPSILON);
+        for _ in 0..WINDOW {
+            a.record(true);
+        }
+        assert!((a.bite_bonus() + MAX_BONUS).abs() < f32::EPSILON);
+    }
+}
@@ -2,100 +2,129 @@ use super::*;
 
 impl LurhookGame {
     pub(super) fn advance_time(&mut self) {
-        if self.storm_turns > 0 {
-            self.storm_turns -= 1;
+        self.advance_clock();
+        self.advance_player();
+        self.check_death();
+    }
+
+    /// Advances the shared turn counter, weather, time of day, tide and
+    /// season.
+    pub(super) fn advance_clock(&mut self) {
+        let was_stormy = self.weather.weather.is_stormy();
+        let season = self.season();
+        if let Some(weather) = self.weather.advance(&mut self.rng, season) {
+            self.events.push(GameEvent::WeatherChanged {
+                announcement: weather.announcement().to_string(),
+                stormy: weather.is_stormy(),
+            });
+            if was_stormy && !weather.is_stormy() {
+                self.events.push(GameEvent::StormPassed);
+            }
         }
         self.turn += 1;
+        self.ui.set_turn(self.turn);
         let idx = (self.turn / TIME_SEGMENT_TURNS) % TIMES.len() as u32;
         self.time_of_day = TIMES[idx as usize];
+        self.advance_tide();
+        self.update_season();
+        self.update_population();
+        self.record_quest(quests::QuestEvent::Turn);
+    }
+
+    /// Applies one turn of hunger, thirst, spoilage, meal boosts, status
+    /// effects and tile events to the current player, and adds what they
+    /// see to the map memory.
+    pub(super) fn advance_player(&mut self) {
         if self.player.hunger > 0 {
-            let loss = self.difficulty.hunger_loss(self.turn);
+            let loss = self.hunger_loss();
             if loss > 0 {
                 self.player.hunger = (self.player.hunger - loss).max(0);
                 if self.player.hunger == 0 {
-                    self.ui.add_log("You are starving!").ok();
+                    self.events.push(GameEvent::HungerCritical);
                 }
             }
-        } else if self.player.hp > 0 {
-            self.player.hp -= 1;
+        } else {
+            self.hurt(1, DeathCause::Starvation);
         }
+        self.update_thirst();
+        self.update_spoilage();
+        self.update_buff();
+        self.update_effects();
         let idx = self.map.idx(self.player.pos);
-        let tile = self.map.tiles[idx];
-        match tile {
-            TileKind::Land => {
-                if self.rng.range(0, 100) < 10 {
-                    if self.rng.range(0, 2) == 0 && self.player.hp < MAX_HP {
-                        self.player.hp += 1;
-                        self.ui.add_log("You rest on the shore.").ok();
-                    } else {
-                        self.player.canned_food += 1;
-                        self.ui.add_log("You found canned food!").ok();
-                    }
-                }
-            }
-            TileKind::DeepWater => {
-                if self.rng.range(0, 100) < 5 {
-                    self.storm_turns = 5;
-                    self.ui.add_log("A storm reduces visibility!").ok();
-                    let _ = self.audio.play(Sound::Storm);
-                }
-                if self.rng.range(0, 100) < self.difficulty.hazard_chance(self.area) {
-                    self.hazards.push(Hazard {
-                        pos: self.player.pos,
-                        turns: HAZARD_DURATION,
-                    });
-                    self.ui.add_log("A jellyfish appears!").ok();
-                }
-            }
-            _ => {}
+        if self.map.tiles[idx] == TileKind::DeepWater
+            && self.rng.range(0, 100) < self.rules.hazard_chance_in(&self.area)
+        {
+            self.spawn_hazard();
         }
+        self.roll_events();
+        self.gather_driftwood();
+        self.metrics.record_turn(self.player.hp, self.player.hunger);
+        self.record_trail();
+        self.explore();
     }
 
-    pub(super) fn current_drift(&self) -> common::Point {
-        if (self.turn / TIDE_TURNS) % 2 == 0 {
-            common::Point::new(1, 0)
-        } else {
-            common::Point::new(-1, 0)
-        }
+    /// Moves the fish one turn; chum and bait in the water draw them, the
+    /// angler and any splashes since the last move spook them, and the
+    /// currents carry them.
+    pub(super) fn move_fish(&mut self) {
+        let food = self.food();
+        let splashes = std::mem::take(&mut self.splashes);
+        let around = ecology::Surroundings {
+            time_of_day: self.time_of_day,
+            food: &food,
+            speed_bonus: self.weather.weather.fish_speed(),
+            anglers: &[self.player.pos],
+            splashes: &splashes,
+            tide: tide::tide_flow(self.turn),
+        };
+        update_fish(&self.map, &mut self.fishes, &mut self.rng, &around).expect("fish update");
     }
 
     pub(super) fn visibility_radius(&self) -> i32 {
         let idx = self.map.idx(self.player.pos);
-        match self.map.tiles[idx] {
-            TileKind::DeepWater => {
-                let base = 5;
-                if self.storm_turns > 0 {
-                    base.min(3)
-                } else {
-                    base
-                }
-            }
+        let base = match self.map.tiles[idx] {
+            TileKind::DeepWater => 5,
             _ => i32::MAX,
+        };
+        self.weather
+            .weather
+            .sight_limit()
+            .map_or(base, |limit| base.min(limit))
+    }
+
+    /// What the player sees: the tiles within their sight radius that are
+    /// not hidden behind land. Land only blocks sight from the water;
+    /// ashore the player looks out over it.
+    fn field_of_view(&self) -> std::cell::Ref<'_, FieldOfView> {
+        let origin = self.player.pos;
+        let radius = self
+            .visibility_radius()
+            .min(self.map.width.max(self.map.height) as i32);
+        let stale = !matches!(
+            self.sight.borrow().as_ref(),
+            Some(fov) if fov.origin() == origin && fov.radius() == radius
+        );
+        if stale {
+            let ashore = !self.map.tiles[self.map.idx(origin)].is_water();
+            let blocks =
+                |p: common::Point| !ashore && self.map.tiles[self.map.idx(p)] == TileKind::Land;
+            *self.sight.borrow_mut() = Some(field_of_view(&self.map, origin, radius, blocks));
         }
+        std::cell::Ref::map(self.sight.borrow(), |sight| {
+            sight.as_ref().expect("field of view")
+        })
     }
 
     pub(super) fn is_visible(&self, pt: common::Point) -> bool {
-        let r = self.visibility_radius();
-        (pt.x - self.player.pos.x).abs() <= r && (pt.y - self.player.pos.y).abs() <= r
+        self.field_of_view().contains(pt)
     }
 
-    pub(super) fn update_hazards(&mut self) {
-        for hazard in self.hazards.iter_mut() {
-            if hazard.turns > 0 {
-                hazard.turns -= 1;
-            }
-        }
-        for hazard in &self.hazards {
-            if hazard.pos == self.player.pos {
-                if self.player.hp > 0 {
-                    self.player.hp -= HAZARD_DAMAGE;
-                    self.ui.add_log("A jellyfish stings you!").ok();
-                }
-                if self.player.line > 0 {
-                    self.player.line = (self.player.line - LINE_DAMAGE).max(0);
-                }
-            }
+    /// Adds the tiles in sight to the map memory.
+    pub(super) fn explore(&mut self) {
+        let seen: Vec<common::Point> = self.field_of_view().points().collect();
+        for pt in seen {
+            let idx = self.map.idx(pt);
+            self.map.explored[idx] = true;
         }
-        self.hazards.retain(|h| h.turns > 0);
     }
 }
@@ -1,20 +1,150 @@
-use super::{Difficulty, GameMode, LurhookGame};
+#[cfg(feature = "presence")]
+use super::presence::{Presence, PresenceHook};
+#[cfg(feature = "spectate")]
+use super::Spectator;
+use super::{
+    daily::{today, DailyRun},
+    input::{key_name, Gamepad, InputConfig, MouseButtons, PadInput},
+    loadout::LoadoutMenu,
+    rewards::Profile,
+    ui::draw_summary,
+    Difficulty, ExportFormat, Hotseat, LurhookGame, CONFIG_PATH, PROFILE_PATH,
+};
 use bracket_lib::prelude::*;
+use common::{default_storage, GameResult};
+use progression::Progress;
+use ui_crate::Renderer;
 
 pub enum AppState {
     Menu,
     Running(Box<LurhookGame>),
-    Summary(i32),
+    /// Local two-player match on one map.
+    Hotseat(Box<Hotseat>),
+    /// Networked co-op with another player.
+    #[cfg(feature = "net")]
+    Coop(Box<super::CoopSession>),
+    Summary {
+        score: i32,
+        game: Box<LurhookGame>,
+        /// Result of the last codex export, shown under the charts.
+        notice: Option<String>,
+    },
 }
 
 pub struct LurhookApp {
     state: AppState,
+    /// Codex title earned so far, shown on the menu.
+    title: Option<&'static str>,
+    /// Angler level and experience, shown on the menu.
+    progress: Progress,
+    /// Starting loadouts to pick from on the menu.
+    loadouts: LoadoutMenu,
+    /// Seed the menu starts runs with.
+    seed: SeedEntry,
+    /// Viewers mirroring this app's screen.
+    #[cfg(feature = "spectate")]
+    spectator: Option<Spectator>,
+    /// Rich-status integration kept in sync with the run.
+    #[cfg(feature = "presence")]
+    presence: Option<PresenceHook>,
+    /// Controller driving the game alongside the keyboard.
+    gamepad: Option<PadInput>,
+    /// Bindings the controller uses while no game is on screen.
+    input: InputConfig,
+    /// Mouse buttons for the next update.
+    mouse: MouseButtons,
+    /// Whether the right button was down last frame.
+    right_down: bool,
 }
 
 impl LurhookApp {
     pub fn new() -> Self {
+        let profile = Profile::load(default_storage().as_ref(), PROFILE_PATH).unwrap_or_default();
         Self {
-            state: AppState::Menu,
+            progress: profile.progress.clone(),
+            loadouts: LoadoutMenu::load(default_storage()),
+            ..Self::with_state(AppState::Menu, profile.title())
+        }
+    }
+
+    pub(crate) fn with_state(state: AppState, title: Option<&'static str>) -> Self {
+        Self {
+            state,
+            title,
+            progress: Progress::default(),
+            loadouts: LoadoutMenu::default(),
+            seed: SeedEntry::default(),
+            #[cfg(feature = "spectate")]
+            spectator: None,
+            #[cfg(feature = "presence")]
+            presence: None,
+            gamepad: None,
+            input: InputConfig::default(),
+            mouse: MouseButtons::default(),
+            right_down: false,
+        }
+    }
+
+    /// Starts directly in a connected co-op session.
+    #[cfg(feature = "net")]
+    pub fn coop(session: super::CoopSession) -> Self {
+        Self::with_state(AppState::Coop(Box::new(session)), None)
+    }
+
+    /// Starts in the run recorded under `key`, played back to its last
+    /// input.
+    pub fn replay(key: &str) -> GameResult<Self> {
+        let game = LurhookGame::replay(key)?;
+        Ok(Self::with_state(AppState::Running(Box::new(game)), None))
+    }
+
+    /// Mirrors every screen change to `spectator`'s viewers.
+    #[cfg(feature = "spectate")]
+    pub fn with_spectator(mut self, spectator: Spectator) -> Self {
+        self.spectator = Some(spectator);
+        self
+    }
+
+    /// Shows the current run through `presence`.
+    #[cfg(feature = "presence")]
+    pub fn with_presence(mut self, presence: Box<dyn Presence>) -> Self {
+        self.presence = Some(PresenceHook::new(presence));
+        self
+    }
+
+    /// Reads `gamepad` as well as the keyboard, with the button mapping
+    /// from `lurhook.toml`.
+    pub fn with_gamepad(mut self, gamepad: Box<dyn Gamepad>) -> Self {
+        self.input = InputConfig::load(default_storage().as_ref(), CONFIG_PATH).unwrap_or_default();
+        self.gamepad = Some(PadInput::new(gamepad));
+        self
+    }
+
+    /// Sets the mouse buttons for the next update; backends call this each
+    /// frame alongside filling in `BTerm`.
+    pub fn set_mouse_buttons(&mut self, mouse: MouseButtons) {
+        self.mouse = mouse;
+    }
+
+    /// The game on screen, if any.
+    pub(crate) fn game(&self) -> Option<&LurhookGame> {
+        match &self.state {
+            AppState::Running(game) | AppState::Summary { game, .. } => Some(game),
+            AppState::Hotseat(hotseat) => Some(hotseat.game()),
+            _ => None,
+        }
+    }
+
+    /// Short name of the screen showing, used in spectator frames.
+    #[cfg(feature = "spectate")]
+    pub(crate) fn screen_name(&self) -> &'static str {
+        match &self.state {
+            AppState::Menu => "menu",
+            AppState::Running(_) => "game",
+            AppState::Hotseat(_) => "hotseat",
+            #[cfg(feature = "net")]
+            AppState::Coop(_) => "coop",
+            AppState::Summary { .. } => "summary",
         }
     }
 
@@ -22,40 +152,147 @@ impl LurhookApp {
         use VirtualKeyCode::*;
         let key = ctx.key;
         match &mut self.state {
+            AppState::Menu if self.seed.typing => {
+                if let Some(key) = key {
+                    self.seed.type_key(key);
+                }
+                false
+            }
             AppState::Menu => match key {
                 Some(Key1) => {
-                    self.state = AppState::Running(Box::new(
-                        LurhookGame::new_with_difficulty(0, Difficulty::Easy).unwrap(),
-                    ));
+                    let game = recorded_run(self.seed.value, Difficulty::Easy);
+                    self.state = AppState::Running(Box::new(game));
                     false
                 }
                 Some(Key2) => {
-                    self.state = AppState::Running(Box::new(
-                        LurhookGame::new_with_difficulty(0, Difficulty::Normal).unwrap(),
-                    ));
+                    let game = recorded_run(self.seed.value, Difficulty::Normal);
+                    self.state = AppState::Running(Box::new(game));
                     false
                 }
                 Some(Key3) => {
-                    self.state = AppState::Running(Box::new(
-                        LurhookGame::new_with_difficulty(0, Difficulty::Hard).unwrap(),
-                    ));
+                    let game = recorded_run(self.seed.value, Difficulty::Hard);
+                    self.state = AppState::Running(Box::new(game));
+                    false
+                }
+                Some(Key4) => {
+                    let game = recorded_run(self.seed.value, Difficulty::Custom);
+                    self.state = AppState::Running(Box::new(game));
+                    false
+                }
+                Some(S) => {
+                    self.seed.start_typing();
+                    false
+                }
+                Some(R) => {
+                    self.seed.roll();
+                    false
+                }
+                Some(D) => {
+                    if let Some(Ok(mut game)) = LurhookGame::daily() {
+                        game.record_replay();
+                        game.enable_autosave();
+                        self.state = AppState::Running(Box::new(game));
+                    }
+                    false
+                }
+                Some(T) => {
+                    if let Ok(game) = LurhookGame::tutorial() {
+                        self.state = AppState::Running(Box::new(game));
+                    }
+                    false
+                }
+                Some(L) => {
+                    self.loadouts.next(default_storage().as_ref());
+                    false
+                }
+                Some(H) => {
+                    self.state = AppState::Hotseat(Box::new(Hotseat::new(
+                        LurhookGame::new_with_difficulty(self.seed.value, Difficulty::Normal)
+                            .unwrap(),
+                    )));
+                    false
+                }
+                Some(C) => {
+                    if let Ok(mut game) = LurhookGame::competition(self.seed.value) {
+                        game.enable_autosave();
+                        self.state = AppState::Running(Box::new(game));
+                    }
                     false
                 }
                 Some(Q) => true,
                 _ => false,
             },
+            AppState::Hotseat(hotseat) => {
+                if !hotseat.is_finished() {
+                    hotseat.update(ctx);
+                    return false;
+                }
+                match key {
+                    Some(Return) => {
+                        self.state = AppState::Menu;
+                        false
+                    }
+                    Some(Q) => true,
+                    _ => false,
+                }
+            }
+            #[cfg(feature = "net")]
+            AppState::Coop(session) => {
+                if !session.is_closed() {
+                    session.update(ctx);
+                    return false;
+                }
+                match key {
+                    Some(Return) => {
+                        self.state = AppState::Menu;
+                        false
+                    }
+                    Some(Q) => true,
+                    _ => false,
+                }
+            }
             AppState::Running(game) => {
-                game.tick(ctx);
-                if let GameMode::End { score } = game.mode() {
-                    self.state = AppState::Summary(score);
+                game.update(ctx);
+                if let Some(score) = game.final_score() {
+                    if game.in_tutorial() {
+                        // The tutorial has no score worth summing up
+                        self.state = AppState::Menu;
+                        return false;
+                    }
+                    game.write_journal();
+                    if let AppState::Running(game) =
+                        std::mem::replace(&mut self.state, AppState::Menu)
+                    {
+                        self.state = AppState::Summary {
+                            score,
+                            game,
+                            notice: None,
+                        };
+                    }
                 }
                 false
             }
-            AppState::Summary(_) => match key {
+            AppState::Summary { game, notice, .. } => match key {
                 Some(Return) => {
+                    self.title = game.title();
+                    self.progress = game.progress().clone();
+                    // The run may have unlocked more of them
+                    self.loadouts = LoadoutMenu::load(default_storage());
                     self.state = AppState::Menu;
                     false
                 }
+                Some(E) | Some(J) => {
+                    let format = if key == Some(E) {
+                        ExportFormat::Csv
+                    } else {
+                        ExportFormat::Json
+                    };
+                    *notice = Some(match game.export_codex(format) {
+                        Ok(key) => format!("Codex exported to {}", key),
+                        Err(e) => format!("Export failed: {}", e),
+                    });
+                    false
+                }
                 Some(Q) => true,
                 _ => false,
             },
@@ -63,6 +300,77 @@ impl LurhookApp {
     }
 }
 
+/// The summary row with the day's best scores.
+fn daily_line(daily: &DailyRun) -> String {
+    let scores: Vec<String> = daily.top.iter().map(i32::to_string).collect();
+    format!("Daily {} best: {}", daily.date, scores.join("  "))
+}
+
+/// The seed the menu starts runs with, typed in digit by digit or rolled
+/// at random. It starts at 0.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct SeedEntry {
+    value: u64,
+    /// Whether digit keys type the seed rather than pick a difficulty.
+    typing: bool,
+}
+
+impl SeedEntry {
+    /// Clears the seed to type a new one.
+    fn start_typing(&mut self) {
+        self.value = 0;
+        self.typing = true;
+    }
+
+    /// Handles a key while typing: digits add to the seed, Backspace
+    /// erases the last, R rolls one and Enter or Escape finishes.
+    fn type_key(&mut self, key: VirtualKeyCode) {
+        if let Some(digit) = key_name(key).and_then(|name| name.parse::<u64>().ok()) {
+            // Digits past what a u64 holds are dropped
+            if let Some(value) = self
+                .value
+                .checked_mul(10)
+                .and_then(|v| v.checked_add(digit))
+            {
+                self.value = value;
+            }
+            return;
+        }
+        match key {
+            VirtualKeyCode::Back => self.value /= 10,
+            VirtualKeyCode::R => self.roll(),
+            VirtualKeyCode::Return | VirtualKeyCode::Escape | VirtualKeyCode::S => {
+                self.typing = false;
+            }
+            _ => {}
+        }
+    }
+
+    /// Picks a random seed and stops typing.
+    fn roll(&mut self) {
+        self.value = RandomNumberGenerator::new().next_u64();
+        self.typing = false;
+    }
+
+    /// The menu row for the seed.
+    fn line(&self) -> String {
+        if self.typing {
+            format!("Seed: {}_  Enter: Done  R: Random", self.value)
+        } else {
+            format!("Seed: {}  S: Type a seed  R: Random", self.value)
+        }
+    }
+}
+
+/// A new single-player run of `seed` that records its inputs for a replay
+/// and autosaves.
+fn recorded_run(seed: u64, difficulty: Difficulty) -> LurhookGame {
+    let mut game = LurhookGame::new_with_difficulty(seed, difficulty).unwrap();
+    game.record_replay();
+    game.enable_autosave();
+    game
+}
+
 impl Default for LurhookApp {
     fn default() -> Self {
         Self::new()
@@ -71,26 +379,97 @@ impl Default for LurhookApp {
 
 impl GameState for LurhookApp {
     fn tick(&mut self, ctx: &mut BTerm) {
-        let quit = self.update_state(ctx);
-        if quit {
-            ctx.quit();
-            return;
+        let (left_held, right_down) = {
+            let input = INPUT.lock();
+            (
+                input.is_mouse_button_pressed(0),
+                input.is_mouse_button_pressed(1),
+            )
+        };
+        self.set_mouse_buttons(MouseButtons {
+            left_held,
+            right_click: right_down && !self.right_down,
+        });
+        self.right_down = right_down;
+        self.update(ctx);
+        if !ctx.quitting {
+            self.render(ctx);
         }
+    }
+}
+
+impl LurhookApp {
+    /// Applies one frame of input, requesting quit on `ctx` when asked to.
+    pub fn update(&mut self, ctx: &mut BTerm) {
+        if let Some(mut gamepad) = self.gamepad.take() {
+            // Keys win over the controller within a frame
+            let input = self.game().map_or(&self.input, |game| &game.input);
+            let key = gamepad.key(input, ctx.frame_time_ms);
+            ctx.key = ctx.key.or(key);
+            self.gamepad = Some(gamepad);
+        }
+        let mouse = std::mem::take(&mut self.mouse);
         match &mut self.state {
+            AppState::Running(game) => game.set_mouse_buttons(mouse),
+            AppState::Hotseat(hotseat) => hotseat.game_mut().set_mouse_buttons(mouse),
+            _ => {}
+        }
+        if self.update_state(ctx) {
+            ctx.quit();
+        }
+        #[cfg(feature = "spectate")]
+        if let Some(mut spectator) = self.spectator.take() {
+            spectator.publish(self);
+            self.spectator = Some(spectator);
+        }
+        #[cfg(feature = "presence")]
+        if let Some(mut presence) = self.presence.take() {
+            presence.refresh(self.game());
+            self.presence = Some(presence);
+        }
+    }
+
+    /// Draws the menu and summary screens; running games draw themselves.
+    pub fn render(&self, ctx: &mut dyn Renderer) {
+        match &self.state {
             AppState::Menu => {
                 ctx.cls();
                 ctx.print_centered(10, "Lurhook");
-                ctx.print_centered(12, "1: Easy  2: Normal  3: Hard");
-                ctx.print_centered(14, "Press Q to Quit");
-            }
-            AppState::Running(_) => {
-                // game.tick already rendered
+                let mut header = self.progress.summary();
+                if let Some(title) = self.title {
+                    header.push_str(&format!("  Title: {}", title));
+                }
+                ctx.print_centered(11, &header);
+                ctx.print_centered(12, "1: Easy  2: Normal  3: Hard  4: Custom");
+                if let Some(date) = today() {
+                    ctx.print_centered(13, &format!("D: Daily run ({})", date));
+                }
+                ctx.print_centered(14, "H: Hotseat (2 players)  C: Competition");
+                ctx.print_centered(15, "T: Tutorial");
+                ctx.print_centered(16, &self.seed.line());
+                if let Some(loadout) = self.loadouts.line() {
+                    ctx.print_centered(17, &loadout);
+                }
+                ctx.print_centered(18, "Press Q to Quit");
             }
-            AppState::Summary(score) => {
-                ctx.cls();
-                ctx.print_centered(10, "Run Complete!");
-                ctx.print_centered(12, format!("Final score: {}", score));
-                ctx.print_centered(14, "Press Enter for Menu, Q to Quit");
+            AppState::Running(game) => game.render(ctx),
+            AppState::Hotseat(hotseat) => hotseat.render(ctx),
+            #[cfg(feature = "net")]
+            AppState::Coop(session) => session.render(ctx),
+            AppState::Summary {
+                score,
+                game,
+                notice,
+            } => {
+                draw_summary(ctx, &game.summary_heading(*score), game.metrics());
+                if let Some(daily) = game.daily_run() {
+                    ctx.print_centered(18, &daily_line(daily));
+                }
+                if let Some(notice) = notice {
+                    ctx.print_centered(19, notice);
+                }
+                ctx.print_centered(20, "E: Export CSV  J: Export JSON");
+                ctx.print_centered(21, "Press Enter for Menu, Q to Quit");
             }
         }
     }
@@ -99,36 +478,13 @@ impl GameState for LurhookApp {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use bracket_lib::prelude::{BTerm, VirtualKeyCode, RGB};
-
-    fn dummy_ctx(key: VirtualKeyCode) -> BTerm {
-        BTerm {
-            width_pixels: 0,
-            height_pixels: 0,
-            original_height_pixels: 0,
-            original_width_pixels: 0,
-            fps: 0.0,
-            frame_time_ms: 0.0,
-            active_console: 0,
-            key: Some(key),
-            mouse_pos: (0, 0),
-            left_click: false,
-            shift: false,
-            control: false,
-            alt: false,
-            web_button: None,
-            quitting: false,
-            post_scanlines: false,
-            post_screenburn: false,
-            screen_burn_color: RGB::from_f32(0.0, 0.0, 0.0),
-            mouse_visible: true,
-        }
-    }
+    use crate::test_support::{key_frame, memory_game};
+    use bracket_lib::prelude::VirtualKeyCode;
 
     #[test]
     fn enter_from_menu_starts_game() {
         let mut app = LurhookApp::new();
-        let mut ctx = dummy_ctx(VirtualKeyCode::Key1);
+        let mut ctx = key_frame(VirtualKeyCode::Key1);
         app.update_state(&mut ctx);
         match app.state {
             AppState::Running(_) => {}
@@ -136,13 +492,144 @@ mod tests {
         }
     }
 
+    #[test]
+    fn tutorial_starts_from_the_menu_and_returns_to_it() {
+        let mut app = LurhookApp::new();
+        app.update_state(&mut key_frame(VirtualKeyCode::T));
+        assert!(app.game().is_some_and(|game| game.in_tutorial()));
+        app.update_state(&mut key_frame(VirtualKeyCode::Escape));
+        assert!(matches!(app.state, AppState::Menu));
+    }
+
     #[test]
     fn summary_return_goes_to_menu() {
-        let mut app = LurhookApp {
-            state: AppState::Summary(10),
-        };
-        let mut ctx = dummy_ctx(VirtualKeyCode::Return);
+        let mut app = LurhookApp::with_state(
+            AppState::Summary {
+                score: 10,
+                game: Box::new(LurhookGame::default()),
+                notice: None,
+            },
+            None,
+        );
+        let mut ctx = key_frame(VirtualKeyCode::Return);
         app.update_state(&mut ctx);
         assert!(matches!(app.state, AppState::Menu));
     }
+
+    #[test]
+    fn summary_return_refreshes_title() {
+        let storage = Box::new(common::MemoryStorage::default());
+        let mut profile = Profile::default();
+        profile.unlock_milestones("Coast", 100.0);
+        profile.save(storage.as_ref(), PROFILE_PATH).unwrap();
+        let game = LurhookGame::new_with_storage(0, Difficulty::Normal, None, storage).unwrap();
+        let mut app = LurhookApp::with_state(
+            AppState::Summary {
+                score: 0,
+                game: Box::new(game),
+                notice: None,
+            },
+            None,
+        );
+        let mut ctx = key_frame(VirtualKeyCode::Return);
+        app.update_state(&mut ctx);
+        assert_eq!(app.title, Some("Shore Warden"));
+    }
+
+    #[test]
+    fn menu_renders_title() {
+        let app = LurhookApp::with_state(AppState::Menu, Some("Reef Ranger"));
+        let mut buf = ui_crate::TextBuffer::new(80, 25);
+        app.render(&mut buf);
+        assert!(buf.row(10).contains("Lurhook"));
+        assert!(buf.row(11).contains("Angler level 1 (0/100 XP)"));
+        assert!(buf.row(11).contains("Title: Reef Ranger"));
+    }
+
+    #[test]
+    fn daily_summary_shows_best_scores() {
+        let storage = Box::new(common::MemoryStorage::default());
+        let mut game = LurhookGame::daily_with_storage("2026-10-16", storage).unwrap();
+        game.record_daily_score(40);
+        game.end_run();
+        let app = LurhookApp::with_state(
+            AppState::Summary {
+                score: 0,
+                game: Box::new(game),
+                notice: None,
+            },
+            None,
+        );
+        let mut buf = ui_crate::TextBuffer::new(80, 25);
+        app.render(&mut buf);
+        assert!(buf.row(18).contains("Daily 2026-10-16 best: 40  0"));
+    }
+
+    #[test]
+    fn summary_export_sets_notice() {
+        let game = memory_game(0);
+        let mut app = LurhookApp::with_state(
+            AppState::Summary {
+                score: 0,
+                game: Box::new(game),
+                notice: None,
+            },
+            None,
+        );
+        let mut ctx = key_frame(VirtualKeyCode::J);
+        app.update_state(&mut ctx);
+        match &app.state {
+            AppState::Summary {
+                game,
+                notice: Some(notice),
+                ..
+            } => {
+                assert_eq!(notice, "Codex exported to codex_report.json");
+                assert!(game.storage.read("codex_report.json").unwrap().is_some());
+            }
+            _ => panic!("notice missing"),
+        }
+    }
+
+    #[test]
+    fn typed_seeds_start_runs() {
+        use VirtualKeyCode::*;
+        let mut app = LurhookApp::with_state(AppState::Menu, None);
+        for key in [S, Key4, Key2, Back, Key7, Return] {
+            app.update_state(&mut key_frame(key));
+        }
+        assert!(matches!(app.state, AppState::Menu), "digits typed the seed");
+        assert_eq!(app.seed.value, 47);
+        let mut buf = ui_crate::TextBuffer::new(80, 25);
+        app.render(&mut buf);
+        assert!(buf.row(16).contains("Seed: 47  S: Type a seed  R: Random"));
+        app.update_state(&mut key_frame(Key2));
+        assert_eq!(app.game().map(|game| game.seed), Some(47));
+
+        let mut seed = SeedEntry::default();
+        seed.start_typing();
+        for _ in 0..25 {
+            seed.type_key(Key9);
+        }
+        assert_eq!(seed.value, 9_999_999_999_999_999_999, "u64 digits only");
+        seed.type_key(R);
+        assert!(!seed.typing);
+    }
+
+    #[test]
+    fn menu_h_starts_hotseat() {
+        let mut app = LurhookApp::new();
+        app.update_state(&mut key_frame(VirtualKeyCode::H));
+        assert!(matches!(app.state, AppState::Hotseat(_)));
+        let mut buf = ui_crate::TextBuffer::new(80, 25);
+        app.render(&mut buf);
+        assert!(buf.contains("Pass to Player 1"));
+    }
+
+    #[test]
+    fn menu_c_starts_a_competition() {
+        let mut app = LurhookApp::new();
+        app.update_state(&mut key_frame(VirtualKeyCode::C));
+        assert!(app.game().is_some_and(|game| game.in_competition()));
+    }
 }
@@ -0,0 +1,89 @@
+//! Autosave: runs started from the menu save themselves every
+//! [`AUTOSAVE_TURNS`] turns spent exploring and whenever they move to
+//! another area or sea chart. Autosaves take turns between two slots, so
+//! the older one survives a bad write of the newer.
+
+use super::*;
+
+/// Turns between autosaves.
+pub(crate) const AUTOSAVE_TURNS: u32 = 50;
+/// Keys of the autosave slots, written in turn.
+pub(crate) const AUTOSAVE_PATHS: [&str; 2] = ["autosave1.json", "autosave2.json"];
+
+impl LurhookGame {
+    /// Starts autosaving this run.
+    pub(crate) fn enable_autosave(&mut self) {
+        self.autosave = Some(0);
+    }
+
+    /// Autosaves when the last autosave is [`AUTOSAVE_TURNS`] behind and
+    /// the angler is exploring.
+    pub(crate) fn autosave_if_due(&mut self) {
+        if let Some(slot) = self.autosave {
+            if self.turn >= self.last_autosave + AUTOSAVE_TURNS && self.mode == GameMode::Exploring
+            {
+                self.write_autosave(slot);
+            }
+        }
+    }
+
+    /// Autosaves now, as on arriving in another area.
+    pub(crate) fn autosave_now(&mut self) {
+        if let Some(slot) = self.autosave {
+            self.write_autosave(slot);
+        }
+    }
+
+    fn write_autosave(&mut self, slot: usize) {
+        self.last_autosave = self.turn;
+        self.flush_codex();
+        let written = SaveState::of(self)
+            .to_json()
+            .and_then(|json| save::encode(&json))
+            .and_then(|bytes| self.storage.write(AUTOSAVE_PATHS[slot], &bytes));
+        if let Err(e) = written {
+            self.ui.add_log(&format!("Autosave failed: {}", e)).ok();
+        }
+        self.autosave = Some((slot + 1) % AUTOSAVE_PATHS.len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn autosaves_take_turns_between_the_slots() {
+        let storage: Rc<dyn Storage> = Rc::new(MemoryStorage::default());
+        let mut game =
+            LurhookGame::new_with_storage(0, Difficulty::Normal, None, Box::new(storage.clone()))
+                .unwrap();
+        game.autosave_if_due();
+        game.turn = AUTOSAVE_TURNS;
+        game.autosave_if_due();
+        assert_eq!(
+            storage.read(AUTOSAVE_PATHS[0]).unwrap(),
+            None,
+            "not enabled"
+        );
+
+        game.enable_autosave();
+        game.autosave_if_due();
+        assert!(storage.read(AUTOSAVE_PATHS[0]).unwrap().is_some());
+        assert_eq!(storage.read(AUTOSAVE_PATHS[1]).unwrap(), None);
+        game.turn += 1;
+        game.autosave_if_due();
+        assert_eq!(storage.read(AUTOSAVE_PATHS[1]).unwrap(), None, "not due");
+
+        game.autosave_now();
+        let loaded = LurhookGame::load_game_from(Box::new(storage.clone()), AUTOSAVE_PATHS[1]);
+        assert_eq!(loaded.unwrap().turn, AUTOSAVE_TURNS + 1);
+        game.turn = AUTOSAVE_TURNS * 3;
+        game.mode = GameMode::Aiming {
+            target: game.player.pos,
+        };
+        game.autosave_if_due();
+        assert_eq!(game.autosave, Some(0), "waits while busy");
+    }
+}
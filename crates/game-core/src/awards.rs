@@ -0,0 +1,128 @@
+//! Awards achievements for game events and lists them on their screen.
+
+use super::*;
+
+impl LurhookGame {
+    /// Unlocks `id` through the achievement sink, announcing it in the log
+    /// the first time.
+    pub(crate) fn unlock_achievement(&mut self, id: &str) {
+        if let Ok(true) = self.achievements.unlock(id) {
+            let name = achievements::find(id).map_or(id, |a| a.name);
+            self.ui
+                .add_entry(
+                    LogCategory::Event,
+                    &format!("Achievement unlocked: {}!", name),
+                )
+                .ok();
+        }
+    }
+
+    /// Unlocks the gameplay achievements `event` completes. Runs after the
+    /// codex has recorded the event.
+    pub(crate) fn check_achievements(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::FishCaught { id, .. } => {
+                self.unlock_achievement("first_catch");
+                if self.fish_types.iter().any(|f| &f.id == id && f.legendary) {
+                    self.unlock_achievement("legendary");
+                    let legends = self.fish_types.iter().filter(|f| f.legendary);
+                    if legends.clone().all(|f| self.codex.count(&f.id) > 0) {
+                        self.unlock_achievement("legend_hunter");
+                    }
+                }
+                if self.fish_types.iter().all(|f| self.codex.count(&f.id) > 0) {
+                    self.unlock_achievement("every_species");
+                }
+            }
+            GameEvent::StormPassed if self.player.hp == 1 => {
+                self.unlock_achievement("storm_survivor");
+            }
+            _ => {}
+        }
+    }
+
+    /// Rows for the achievements screen.
+    pub(super) fn achievement_views(&self) -> Vec<AchievementView> {
+        achievements::ACHIEVEMENTS
+            .iter()
+            .map(|a| AchievementView {
+                name: a.name,
+                description: a.description,
+                unlocked: self.achievements.is_unlocked(a.id),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_game;
+
+    fn caught(kind: &data::FishType) -> GameEvent {
+        GameEvent::FishCaught {
+            id: kind.id.clone(),
+            name: kind.name.clone(),
+            weight: kind.average_weight(),
+            description: String::new(),
+            habitat_note: String::new(),
+            location: "Coast".to_string(),
+            turn: 1,
+            timestamp: None,
+        }
+    }
+
+    #[test]
+    fn first_catch_is_announced_once() {
+        let mut game = memory_game(9);
+        let trout = game.fish_types[0].clone();
+        game.events.push(caught(&trout));
+        game.events.push(caught(&trout));
+        game.dispatch_events();
+        assert!(game.achievements.is_unlocked("first_catch"));
+        let toasts = game
+            .ui
+            .logs()
+            .iter()
+            .filter(|l| *l == "Achievement unlocked: First Catch!")
+            .count();
+        assert_eq!(toasts, 1);
+        assert!(!game.achievements.is_unlocked("legendary"));
+    }
+
+    #[test]
+    fn legendary_and_every_species() {
+        let mut game = memory_game(9);
+        let kinds = game.fish_types.clone();
+        for kind in &kinds {
+            game.events.push(caught(kind));
+        }
+        game.dispatch_events();
+        assert!(game.achievements.is_unlocked("legendary"));
+        assert!(game.achievements.is_unlocked("legend_hunter"));
+        assert!(game.achievements.is_unlocked("every_species"));
+    }
+
+    #[test]
+    fn storm_survivor_needs_one_hp() {
+        let mut game = memory_game(9);
+        game.player.hp = 2;
+        game.events.push(GameEvent::StormPassed);
+        game.dispatch_events();
+        assert!(!game.achievements.is_unlocked("storm_survivor"));
+        game.player.hp = 1;
+        game.events.push(GameEvent::StormPassed);
+        game.dispatch_events();
+        assert!(game.achievements.is_unlocked("storm_survivor"));
+    }
+
+    #[test]
+    fn achievements_screen_lists_unlocks() {
+        let mut game = memory_game(9);
+        game.unlock_achievement("first_catch");
+        let views = game.achievement_views();
+        assert_eq!(views.len(), achievements::ACHIEVEMENTS.len());
+        assert!(views[0].unlocked);
+        assert!(!views[1].unlocked);
+    }
+}
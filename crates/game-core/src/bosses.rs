@@ -0,0 +1,92 @@
+//! Boss fights: legendary fish fight in several stages with their own
+//! tension pattern, only a strong enough rod holds them, and landing one
+//! is worth a large score bonus and its codex lore.
+
+use super::*;
+
+impl LurhookGame {
+    /// Whether the equipped rod is strong enough to hold `kind`. Only
+    /// bosses ask anything of it.
+    pub(crate) fn rod_holds(&self, kind: &data::FishType) -> bool {
+        match &kind.boss {
+            None => true,
+            Some(b) => self.player.rod_tension_bonus() >= b.min_rod,
+        }
+    }
+
+    /// Logs the start of a new stage of a boss fight.
+    pub(crate) fn announce_stage(&mut self, meter: &TensionMeter) {
+        let name = self
+            .hooked_fish()
+            .map_or("fish".to_string(), |f| f.kind.name.clone());
+        let msg = format!(
+            "The {} finds its second wind! Stage {}/{}",
+            name, meter.stage, meter.stages
+        );
+        self.ui.add_entry(LogCategory::Fishing, &msg).ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hookset::Bite;
+
+    /// A game where a Nebula Ray has taken the bait and the player strikes.
+    fn ray_struck(rod: i32) -> LurhookGame {
+        let mut game = LurhookGame::default();
+        game.player.tension_bonus = rod;
+        let ray = game.fish_types.iter().find(|f| f.id == "NBRY").unwrap();
+        let pos = game.player.pos + common::Point::new(1, 0);
+        game.fishes = vec![Fish::new(ray.clone(), pos)];
+        game.hooked = Some(0);
+        game.update_bite(Bite {
+            turns: 1,
+            struck: true,
+        });
+        game
+    }
+
+    #[test]
+    fn a_weak_rod_cannot_hold_a_boss() {
+        let mut game = ray_struck(0);
+        assert!(game.meter.is_none());
+        assert!(game.hooked.is_none());
+        assert_eq!(game.mode, GameMode::Exploring);
+        assert!(game
+            .ui
+            .logs()
+            .contains(&"The Nebula Ray is too strong for your rod and tears free!".to_string()));
+        assert!(game
+            .events
+            .drain()
+            .any(|e| matches!(e, GameEvent::FishEscaped { .. })));
+    }
+
+    #[test]
+    fn bosses_fight_in_stages_and_score_big() {
+        let mut game = ray_struck(20);
+        let meter = game.meter.as_mut().expect("hooked");
+        assert_eq!((meter.stage, meter.stages), (1, 3));
+        assert_eq!(meter.phase, fishing::FightPhase::Dive, "the ray sounds");
+        meter.max_tension = 10_000;
+        meter.duration = 1;
+        game.update_fishing();
+        assert_eq!(game.meter.as_ref().unwrap().stage, 2);
+        assert_eq!(
+            game.ui.logs().last().unwrap(),
+            "The Nebula Ray finds its second wind! Stage 2/3"
+        );
+
+        let kind = game.hooked_fish().unwrap().kind.clone();
+        let plain = data::FishType {
+            boss: None,
+            ..kind.clone()
+        };
+        let weight = kind.average_weight();
+        assert_eq!(
+            Catch::new(kind, weight).score(),
+            Catch::new(plain, weight).score() + 500
+        );
+    }
+}
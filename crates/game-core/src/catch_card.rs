@@ -0,0 +1,28 @@
+//! The catch card: a fish just landed shown over the map with its art,
+//! size, rarity and the score it brings, until a key is pressed.
+
+use super::*;
+use ui_crate::CatchCard;
+
+impl LurhookGame {
+    /// Shows the card of a fish of species `id` weighing `weight` that
+    /// was just landed and recorded in the codex.
+    pub(crate) fn show_catch_card(&mut self, id: &str, weight: f32) {
+        let Some(kind) = self.fish_types.iter().find(|f| f.id == id) else {
+            return;
+        };
+        let catch = Catch::new(kind.clone(), weight);
+        let score = catch.score() as f32 * self.quests.score_multiplier();
+        self.ui.show_catch_card(CatchCard {
+            name: kind.name.clone(),
+            art: self.fish_art.get(id).to_vec(),
+            weight,
+            size: kind.size_ratio(weight),
+            stars: kind.stars(),
+            first: self.codex.count(id) == 1,
+            score: score.round() as i32,
+            description: kind.description.clone(),
+            habitat_note: kind.habitat_note.clone(),
+        });
+    }
+}
@@ -0,0 +1,161 @@
+//! Chum: bait items thrown into the water that draw fish to a tile for a
+//! while and make casts into them bite more often.
+
+use super::*;
+use ecology::Food;
+
+/// Turns thrown chum keeps drawing fish.
+const CHUM_DURATION: u8 = 20;
+
+impl LurhookGame {
+    /// Starts aiming a chum throw if the player carries any.
+    pub(crate) fn start_chum(&mut self) {
+        if !self
+            .player
+            .items
+            .iter()
+            .any(|i| i.kind == data::ItemKind::Bait)
+        {
+            self.ui
+                .add_entry(LogCategory::Fishing, "You have no chum.")
+                .ok();
+            return;
+        }
+        self.ui
+            .add_entry(LogCategory::Fishing, "Throw chum where?")
+            .ok();
+        self.inventory_focus = false;
+        self.throwing = Some(Throw::Chum);
+        self.mode = GameMode::Aiming {
+            target: self.player.pos,
+        };
+    }
+
+    /// Throws one bait item at the aimed tile, which must be water.
+    pub(crate) fn throw_chum(&mut self) {
+        let GameMode::Aiming { target } = self.mode else {
+            return;
+        };
+        self.throwing = None;
+        self.mode = GameMode::Exploring;
+        if !self.map.tiles[self.map.idx(target)].is_water() {
+            self.ui
+                .add_entry(LogCategory::Fishing, "Chum has to land in the water.")
+                .ok();
+            return;
+        }
+        let Some(idx) = self
+            .player
+            .items
+            .iter()
+            .position(|i| i.kind == data::ItemKind::Bait)
+        else {
+            return;
+        };
+        self.player.items.remove(idx);
+        self.chum.push(Chum {
+            pos: target,
+            turns: CHUM_DURATION,
+        });
+        self.ui
+            .add_entry(LogCategory::Fishing, "You throw chum into the water.")
+            .ok();
+    }
+
+    /// Chum thins out each turn until it is gone.
+    pub(crate) fn update_chum(&mut self) {
+        for chum in self.chum.iter_mut() {
+            chum.turns = chum.turns.saturating_sub(1);
+        }
+        self.chum.retain(|c| c.turns > 0);
+    }
+
+    /// Whether a cast landing on `pt` lands in chum.
+    pub(crate) fn chummed(&self, pt: common::Point) -> bool {
+        self.chum.iter().any(|c| c.covers(pt))
+    }
+
+    /// Food in the water for the fish: chum, and the bait while fishing.
+    pub(crate) fn food(&self) -> Vec<Food> {
+        let bait = match self.mode {
+            GameMode::Fishing { .. } => self.bait,
+            _ => None,
+        };
+        self.chum
+            .iter()
+            .map(|c| Food::Chum(c.pos))
+            .chain(bait.map(Food::Bait))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chum_item() -> data::ItemType {
+        data::ItemType {
+            id: "CHUM".into(),
+            name: "Chum".into(),
+            kind: data::ItemKind::Bait,
+            tension_bonus: 0,
+            reel_factor: 1.0,
+            bite_bonus: 0.0,
+            max_cast_range: data::DEFAULT_CAST_RANGE,
+        }
+    }
+
+    fn water_game() -> LurhookGame {
+        let mut game = LurhookGame::default();
+        game.map.tiles.fill(TileKind::DeepWater);
+        game.player.items = vec![chum_item()];
+        game
+    }
+
+    #[test]
+    fn thrown_chum_lasts_a_while() {
+        let mut game = water_game();
+        let target = game.player.pos + common::Point::new(3, 0);
+        game.start_chum();
+        game.mode = GameMode::Aiming { target };
+        game.throw_chum();
+        assert!(game.player.items.is_empty());
+        assert!(game.chummed(target + common::Point::new(1, 1)));
+        assert!(!game.chummed(target + common::Point::new(2, 0)));
+        assert_eq!(game.food(), [Food::Chum(target)]);
+        for _ in 0..CHUM_DURATION {
+            game.update_chum();
+        }
+        assert!(!game.chummed(target));
+    }
+
+    #[test]
+    fn chum_needs_an_item_and_water() {
+        let mut game = water_game();
+        game.player.items.clear();
+        game.start_chum();
+        assert_eq!(game.mode, GameMode::Exploring);
+
+        let mut game = water_game();
+        let target = game.player.pos;
+        let idx = game.map.idx(target);
+        game.map.tiles[idx] = TileKind::Land;
+        game.start_chum();
+        game.throw_chum();
+        assert!(game.chum.is_empty());
+        assert_eq!(game.player.items.len(), 1);
+        assert_eq!(game.mode, GameMode::Exploring);
+    }
+
+    #[test]
+    fn chum_key_throws_at_the_cursor() {
+        let mut game = water_game();
+        game.handle_input_key(Some(game.input.chum));
+        assert!(matches!(game.mode, GameMode::Aiming { .. }));
+        game.handle_input_key(Some(game.input.right));
+        game.handle_input_key(Some(game.input.cast));
+        assert_eq!(game.chum[0].pos, game.player.pos + common::Point::new(1, 0));
+        assert!(game.meter.is_none());
+        assert!(game.cast_path.is_none());
+    }
+}
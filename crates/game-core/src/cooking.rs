@@ -0,0 +1,299 @@
+//! Cooking: driftwood gathered on the shore builds a campfire, and by the
+//! fire the catch is cooked into the best dish from `recipes.json` its fish
+//! make. Fresher fish make better dishes.
+
+use super::*;
+use crate::types::RAW_SHELF_LIFE;
+use data::{Recipe, ANY_FISH};
+use std::cmp::Reverse;
+
+/// Driftwood a campfire takes.
+pub(crate) const CAMPFIRE_WOOD: i32 = 3;
+/// Turns a campfire burns.
+const CAMPFIRE_TURNS: u8 = 60;
+/// Percent chance each turn on the shore of finding driftwood.
+const DRIFTWOOD_CHANCE: i32 = 8;
+
+/// How well a dish came out, from how fresh its fish were.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Quality {
+    Plain,
+    Fine,
+    Superb,
+}
+
+impl Quality {
+    /// Quality of a dish whose least fresh fish had `left` turns before
+    /// spoiling: the top third of a raw fish's shelf life is superb, the
+    /// middle third fine.
+    fn of(left: u32) -> Self {
+        if left * 3 >= RAW_SHELF_LIFE * 2 {
+            Quality::Superb
+        } else if left * 3 >= RAW_SHELF_LIFE {
+            Quality::Fine
+        } else {
+            Quality::Plain
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Quality::Plain => "plain",
+            Quality::Fine => "fine",
+            Quality::Superb => "superb",
+        }
+    }
+
+    /// Scales what the dish restores and how long its boost lasts.
+    fn factor(self) -> f32 {
+        match self {
+            Quality::Plain => 1.0,
+            Quality::Fine => 1.25,
+            Quality::Superb => 1.5,
+        }
+    }
+}
+
+/// Fish from `pantry`, given as `(index, id)`, that make `recipe`, or
+/// `None` if some are missing. Named fish are picked before any-fish
+/// ingredients take what is left, earliest in the pantry first.
+fn gather(recipe: &Recipe, pantry: &[(usize, &str)]) -> Option<Vec<usize>> {
+    let mut left = pantry.to_vec();
+    let mut wanted: Vec<&str> = recipe.ingredients.iter().map(String::as_str).collect();
+    wanted.sort_by_key(|&id| id == ANY_FISH);
+    wanted
+        .into_iter()
+        .map(|id| {
+            let pos = left
+                .iter()
+                .position(|&(_, kind)| id == ANY_FISH || kind == id)?;
+            Some(left.remove(pos).0)
+        })
+        .collect()
+}
+
+/// Ingredients that name a particular fish.
+fn named(recipe: &Recipe) -> usize {
+    recipe
+        .ingredients
+        .iter()
+        .filter(|id| *id != ANY_FISH)
+        .count()
+}
+
+impl LurhookGame {
+    /// Sometimes finds driftwood while the player walks the shore.
+    pub(crate) fn gather_driftwood(&mut self) {
+        if !shop::is_shore(&self.map, self.player.pos) {
+            return;
+        }
+        if self.rng.range(0, 100) < DRIFTWOOD_CHANCE {
+            self.player.driftwood += 1;
+            let msg = format!(
+                "You find a piece of driftwood ({}/{}).",
+                self.player.driftwood, CAMPFIRE_WOOD
+            );
+            self.ui.add_log(&msg).ok();
+        }
+    }
+
+    /// Builds a campfire where the player stands from driftwood.
+    pub(crate) fn build_campfire(&mut self) {
+        let pos = self.player.pos;
+        if self.map.tiles[self.map.idx(pos)] != TileKind::Land {
+            self.ui
+                .add_log("A campfire has to be built on dry land.")
+                .ok();
+            return;
+        }
+        if self.near_campfire() {
+            self.ui.add_log("A campfire is already burning here.").ok();
+            return;
+        }
+        if self.player.driftwood < CAMPFIRE_WOOD {
+            let msg = format!(
+                "You need {} pieces of driftwood for a campfire.",
+                CAMPFIRE_WOOD
+            );
+            self.ui.add_log(&msg).ok();
+            return;
+        }
+        self.player.driftwood -= CAMPFIRE_WOOD;
+        self.campfires.push(Campfire {
+            pos,
+            turns: CAMPFIRE_TURNS,
+        });
+        self.ui.add_log("You build a campfire.").ok();
+    }
+
+    /// Whether a campfire burns on or next to the player's tile.
+    pub(crate) fn near_campfire(&self) -> bool {
+        self.campfires
+            .iter()
+            .any(|f| f.pos.chebyshev(self.player.pos) <= 1)
+    }
+
+    /// Burns the campfires down.
+    pub(crate) fn update_campfires(&mut self) {
+        let before = self.campfires.len();
+        for fire in &mut self.campfires {
+            fire.turns = fire.turns.saturating_sub(1);
+        }
+        self.campfires.retain(|f| f.turns > 0);
+        if self.campfires.len() < before {
+            self.ui.add_log("Your campfire burns out.").ok();
+        }
+    }
+
+    /// Counts down the current player's meal boost.
+    pub(crate) fn update_buff(&mut self) {
+        let Some(buff) = self.player.buff.as_mut() else {
+            return;
+        };
+        buff.turns = buff.turns.saturating_sub(1);
+        if buff.turns == 0 {
+            self.player.buff = None;
+            self.ui.add_log("The boost from your meal wears off.").ok();
+        }
+    }
+
+    /// Cooks and eats the best dish the fresh raw fish make: the one
+    /// naming the most particular fish, then the biggest, then the first
+    /// listed, and leaves the player well fed. Whether there was anything
+    /// to cook.
+    pub(crate) fn cook_dish(&mut self) -> bool {
+        let turn = self.turn;
+        let inventory = &self.player.inventory;
+        let mut pantry: Vec<(usize, &str)> = inventory
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| !c.cooked && !c.is_spoiled(turn))
+            .map(|(i, c)| (i, c.kind.id.as_str()))
+            .collect();
+        // Fish closest to spoiling go into the pot first
+        pantry.sort_by_key(|&(i, _)| inventory[i].keeps_for(turn));
+        let best = self
+            .recipes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, r)| gather(r, &pantry).map(|fish| (i, r, fish)))
+            .max_by_key(|(i, r, _)| (named(r), r.ingredients.len(), Reverse(*i)));
+        let Some((_, recipe, mut fish)) = best else {
+            return false;
+        };
+        let recipe = recipe.clone();
+        let left = fish
+            .iter()
+            .map(|&i| inventory[i].keeps_for(turn))
+            .min()
+            .unwrap_or(0);
+        let quality = Quality::of(left);
+        fish.sort_unstable_by(|a, b| b.cmp(a));
+        for i in fish {
+            self.player.inventory.remove(i);
+        }
+        let scaled = |n: i32| (n as f32 * quality.factor()).round() as i32;
+        self.player.hunger = (self.player.hunger + scaled(recipe.hunger)).min(MAX_HUNGER);
+        self.player.hp = (self.player.hp + scaled(recipe.hp)).min(MAX_HP);
+        let msg = format!("You cook and eat a {} {}.", quality.name(), recipe.name);
+        self.ui.add_log(&msg).ok();
+        if let Some(buff) = recipe.buff {
+            self.player.buff = Some(data::Buff {
+                turns: (buff.turns as f32 * quality.factor()) as u32,
+                ..buff
+            });
+            let msg = match buff.kind {
+                data::BuffKind::Reel => "Your arms feel strong.",
+                data::BuffKind::Tension => "Your grip feels steady.",
+            };
+            self.ui.add_log(msg).ok();
+        }
+        self.apply_effect(StatusKind::WellFed);
+        self.events.push(GameEvent::DishCooked);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn catch(game: &LurhookGame, id: &str, stamp: u32) -> Catch {
+        let kind = game.fish_types.iter().find(|f| f.id == id).unwrap();
+        Catch {
+            stamp,
+            ..Catch::average(kind.clone())
+        }
+    }
+
+    /// A game on dry land with a campfire burning where the player stands.
+    fn by_the_fire() -> LurhookGame {
+        let mut game = LurhookGame::default();
+        game.map.tiles.fill(TileKind::Land);
+        game.player.driftwood = CAMPFIRE_WOOD;
+        game.build_campfire();
+        game
+    }
+
+    #[test]
+    fn campfires_take_driftwood_and_burn_out() {
+        let mut game = by_the_fire();
+        assert_eq!(game.player.driftwood, 0);
+        assert!(game.near_campfire());
+        game.player.driftwood = CAMPFIRE_WOOD;
+        game.build_campfire();
+        assert_eq!(game.campfires.len(), 1, "one fire per spot");
+
+        for _ in 0..CAMPFIRE_TURNS {
+            game.update_campfires();
+        }
+        assert!(!game.near_campfire());
+        game.player.inventory = vec![catch(&game, "TROUT", 0)];
+        game.cook_fish();
+        assert_eq!(game.player.inventory.len(), 1, "no fire, no cooking");
+    }
+
+    #[test]
+    fn the_best_dish_the_catch_makes_is_cooked() {
+        let mut game = by_the_fire();
+        game.player.hunger = 10;
+        game.player.inventory = vec![
+            catch(&game, "RGUP", 0),
+            catch(&game, "TWSA", 0),
+            catch(&game, "TROUT", 0),
+        ];
+        assert!(game.cook_dish());
+        assert_eq!(game.player.inventory.len(), 1);
+        assert_eq!(game.player.inventory[0].kind.id, "RGUP");
+        let buff = game.player.buff.expect("boost");
+        assert_eq!(buff.kind, data::BuffKind::Reel);
+        assert_eq!(buff.turns, 90, "a superb dish boosts half again as long");
+        assert_eq!(
+            game.ui.logs().iter().rev().nth(2).unwrap(),
+            "You cook and eat a superb River Platter."
+        );
+        let reel = game.player.reel_factor;
+        assert_eq!(game.player.reel_strength(), reel + buff.amount);
+
+        for _ in 0..buff.turns {
+            game.update_buff();
+        }
+        assert!(game.player.buff.is_none());
+    }
+
+    #[test]
+    fn stale_fish_make_plain_dishes() {
+        let mut game = by_the_fire();
+        game.turn = RAW_SHELF_LIFE - 10;
+        game.player.hunger = 10;
+        game.player.inventory = vec![catch(&game, "TROUT", 0)];
+        assert!(game.cook_dish());
+        assert_eq!(
+            game.ui.logs().iter().rev().nth(1).unwrap(),
+            "You cook and eat a plain Grilled Fish."
+        );
+        assert!(game.player.has_effect(StatusKind::WellFed));
+        assert_eq!(game.player.hunger, 50);
+        assert!(!game.cook_dish(), "nothing left to cook");
+    }
+}
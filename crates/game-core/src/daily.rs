@@ -0,0 +1,174 @@
+//! Daily runs: one seed per calendar day, always on Normal, and a local
+//! leaderboard of their scores.
+
+use super::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Best scores kept for each day.
+const LEADERBOARD_SIZE: usize = 5;
+
+/// Today's UTC date as `YYYY-MM-DD`, where a clock is available.
+pub(crate) fn today() -> Option<String> {
+    unix_time().map(|secs| date_string(secs / 86_400))
+}
+
+/// Formats the date `days` after 1970-01-01 (proleptic Gregorian).
+fn date_string(days: u64) -> String {
+    // Civil-from-days with eras of 400 years starting on March 1st
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Seed every daily run on `date` shares (FNV-1a of the date).
+pub(crate) fn daily_seed(date: &str) -> u64 {
+    date.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// The day a daily run belongs to and, once it ended, that day's best
+/// scores.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct DailyRun {
+    pub date: String,
+    pub top: Vec<i32>,
+}
+
+/// Best daily-run scores per date, stored in `leaderboard.json`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Leaderboard {
+    days: BTreeMap<String, Vec<i32>>,
+}
+
+impl Leaderboard {
+    /// Loads the leaderboard stored under `key`, or an empty one.
+    pub fn load(storage: &dyn Storage, key: &str) -> GameResult<Self> {
+        match storage.read_string(key)? {
+            Some(data) => serde_json::from_str(&data)
+                .map_err(|e| GameError::Parse(format!("leaderboard: {}", e))),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Saves the leaderboard under `key`.
+    pub fn save(&self, storage: &dyn Storage, key: &str) -> GameResult<()> {
+        let out = serde_json::to_string_pretty(self)
+            .map_err(|e| GameError::Parse(format!("leaderboard: {}", e)))?;
+        storage.write(key, out.as_bytes())
+    }
+
+    /// Adds `score` to the scores of `date`, keeping only the best ones.
+    pub fn record(&mut self, date: &str, score: i32) {
+        let scores = self.days.entry(date.to_string()).or_default();
+        let at = scores.partition_point(|&s| s >= score);
+        scores.insert(at, score);
+        scores.truncate(LEADERBOARD_SIZE);
+    }
+
+    /// Best scores of `date`, highest first.
+    pub fn top(&self, date: &str) -> &[i32] {
+        self.days.get(date).map_or(&[], Vec::as_slice)
+    }
+}
+
+impl LurhookGame {
+    /// Starts today's daily run: the date's seed on Normal difficulty.
+    /// Returns `None` where no clock is available.
+    pub fn daily() -> Option<GameResult<Self>> {
+        let date = today()?;
+        Some(Self::daily_with_storage(&date, default_storage()))
+    }
+
+    /// Starts the daily run of `date`, persisting through `storage`.
+    pub(crate) fn daily_with_storage(date: &str, storage: Box<dyn Storage>) -> GameResult<Self> {
+        let mut game = Self::new_with_storage(daily_seed(date), Difficulty::Normal, None, storage)?;
+        game.daily = Some(DailyRun {
+            date: date.to_string(),
+            top: Vec::new(),
+        });
+        Ok(game)
+    }
+
+    /// The daily run this game is playing, if any.
+    pub(crate) fn daily_run(&self) -> Option<&DailyRun> {
+        self.daily.as_ref()
+    }
+
+    /// Adds a finished daily run's score to the leaderboard.
+    pub(crate) fn record_daily_score(&mut self, score: i32) {
+        let Some(daily) = &mut self.daily else {
+            return;
+        };
+        let mut board =
+            Leaderboard::load(self.storage.as_ref(), LEADERBOARD_PATH).unwrap_or_default();
+        board.record(&daily.date, score);
+        daily.top = board.top(&daily.date).to_vec();
+        if board.save(self.storage.as_ref(), LEADERBOARD_PATH).is_err() {
+            self.ui.add_log("Could not save the leaderboard.").ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dates_follow_the_calendar() {
+        assert_eq!(date_string(0), "1970-01-01");
+        assert_eq!(date_string(59), "1970-03-01");
+        assert_eq!(date_string(11_016), "2000-02-29");
+        assert_eq!(date_string(20_742), "2026-10-16");
+    }
+
+    #[test]
+    fn seeds_depend_only_on_the_date() {
+        assert_eq!(daily_seed("2026-10-16"), daily_seed("2026-10-16"));
+        assert_ne!(daily_seed("2026-10-16"), daily_seed("2026-10-17"));
+    }
+
+    #[test]
+    fn leaderboard_keeps_best_scores_per_day() {
+        let mut board = Leaderboard::default();
+        for score in [30, 90, 10, 50, 70, 20, 90] {
+            board.record("2026-10-16", score);
+        }
+        board.record("2026-10-17", 5);
+        assert_eq!(board.top("2026-10-16"), [90, 90, 70, 50, 30]);
+        assert_eq!(board.top("2026-10-17"), [5]);
+        assert!(board.top("2026-10-18").is_empty());
+
+        let storage = MemoryStorage::default();
+        board.save(&storage, LEADERBOARD_PATH).unwrap();
+        assert_eq!(
+            Leaderboard::load(&storage, LEADERBOARD_PATH).unwrap(),
+            board
+        );
+    }
+
+    #[test]
+    fn daily_runs_share_a_map_and_record_scores() {
+        let date = "2026-10-16";
+        let storage = || Box::new(MemoryStorage::default());
+        let mut game = LurhookGame::daily_with_storage(date, storage()).unwrap();
+        let other = LurhookGame::daily_with_storage(date, storage()).unwrap();
+        assert_eq!(game.difficulty, Difficulty::Normal);
+        assert_eq!(game.map.tiles, other.map.tiles);
+
+        game.end_run();
+        let score = game.score();
+        assert_eq!(game.daily_run().unwrap().top, [score]);
+        let board = Leaderboard::load(game.storage.as_ref(), LEADERBOARD_PATH).unwrap();
+        assert_eq!(board.top(date), [score]);
+    }
+}
@@ -0,0 +1,157 @@
+//! Death: what hurt the angler last is remembered, and once their hit
+//! points run out the run is over, unless the difficulty lets them come
+//! to or a rescue is still owed to them.
+
+use super::*;
+
+/// What took the angler's last hit point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum DeathCause {
+    Starvation,
+    Thirst,
+    Poison,
+    SpoiledFish,
+    Hazard(HazardKind),
+    /// Harm done by a scripted event.
+    Mishap,
+}
+
+impl DeathCause {
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            DeathCause::Starvation => "Starvation",
+            DeathCause::Thirst => "Thirst",
+            DeathCause::Poison => "Poison",
+            DeathCause::SpoiledFish => "Spoiled fish",
+            DeathCause::Mishap => "Mishap",
+            DeathCause::Hazard(kind) => kind.name(),
+        }
+    }
+}
+
+impl LurhookGame {
+    /// Takes `damage` hit points from the angler, down to zero.
+    pub(crate) fn hurt(&mut self, damage: i32, cause: DeathCause) {
+        if damage <= 0 {
+            return;
+        }
+        self.player.hp = (self.player.hp - damage).max(0);
+        self.harm = Some(cause);
+    }
+
+    /// Once the angler's hit points run out: the run is over under
+    /// permadeath, save for one rescue where the difficulty allows it;
+    /// otherwise they come to with half their hit points.
+    pub(crate) fn check_death(&mut self) {
+        if self.player.hp > 0 || self.mode.is_over() {
+            return;
+        }
+        if !self.rules.permadeath {
+            self.player.hp = MAX_HP / 2;
+            self.ui
+                .add_entry(
+                    LogCategory::Combat,
+                    "You collapse, and come to some time later.",
+                )
+                .ok();
+        } else if self.rules.rescue && !self.rescued {
+            self.rescue();
+        } else {
+            self.game_over();
+        }
+    }
+
+    /// A passing boat brings the angler round, at the cost of the fish
+    /// in the bag and half their coins.
+    fn rescue(&mut self) {
+        self.rescued = true;
+        self.player.hp = MAX_HP / 2;
+        self.player.hunger = self.player.hunger.max(MAX_HUNGER / 2);
+        self.player.thirst = self.player.thirst.max(MAX_THIRST / 2);
+        self.player.inventory.clear();
+        self.player.coins /= 2;
+        self.ui
+            .add_entry(
+                LogCategory::Combat,
+                "You collapse. A passing boat pulls you round, but your catch is lost.",
+            )
+            .ok();
+    }
+
+    /// Ends the run with the angler dead, scoring the catch so far.
+    fn game_over(&mut self) {
+        let cause = self.harm.unwrap_or(DeathCause::Starvation);
+        let score = self.score();
+        self.ui
+            .add_entry(
+                LogCategory::Combat,
+                &format!("You died. Cause: {}. Score: {}", cause.name(), score),
+            )
+            .ok();
+        self.announce_standings();
+        self.mode = GameMode::GameOver { cause, score };
+        self.record_daily_score(score);
+        self.write_replay();
+        self.flush_codex();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn without_permadeath_the_angler_comes_to() {
+        let mut game = LurhookGame::new_with_difficulty(0, Difficulty::Easy).unwrap();
+        game.hurt(MAX_HP, DeathCause::Starvation);
+        game.check_death();
+        assert_eq!(game.player.hp, MAX_HP / 2);
+        assert_eq!(game.mode, GameMode::Exploring);
+    }
+
+    #[test]
+    fn one_rescue_costs_the_catch_then_death_ends_the_run() {
+        let mut game = LurhookGame::new_with_difficulty(0, Difficulty::Normal).unwrap();
+        let kind = game.fish_types[0].clone();
+        game.player.inventory.push(Catch::average(kind.clone()));
+        game.player.coins = 10;
+        game.hurt(MAX_HP, DeathCause::Thirst);
+        game.check_death();
+        assert_eq!(game.mode, GameMode::Exploring);
+        assert_eq!(game.player.hp, MAX_HP / 2);
+        assert!(game.player.inventory.is_empty());
+        assert_eq!(game.player.coins, 5);
+
+        game.player.inventory.push(Catch::average(kind));
+        let score = game.score();
+        game.hurt(MAX_HP, DeathCause::Hazard(HazardKind::Shark));
+        game.check_death();
+        assert_eq!(
+            game.mode,
+            GameMode::GameOver {
+                cause: DeathCause::Hazard(HazardKind::Shark),
+                score
+            }
+        );
+        let mut buf = TextBuffer::new(SCREEN_WIDTH, SCREEN_HEIGHT);
+        game.render(&mut buf);
+        assert!(buf.contains("Game Over"));
+        assert!(buf.contains("Cause of death: Shark"));
+        assert!(buf.contains(&format!("Score at death: {}", score)));
+    }
+
+    #[test]
+    fn hard_runs_end_at_the_first_death() {
+        let mut game = LurhookGame::new_with_difficulty(0, Difficulty::Hard).unwrap();
+        game.player.hunger = 0;
+        game.player.hp = 1;
+        game.advance_time();
+        assert_eq!(
+            game.mode,
+            GameMode::GameOver {
+                cause: DeathCause::Starvation,
+                score: 0
+            }
+        );
+    }
+}
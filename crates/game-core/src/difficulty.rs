@@ -0,0 +1,255 @@
+//! Difficulty: the rules a run is played by. Easy, Normal and Hard are
+//! built-in profiles; a `[difficulty]` section in `lurhook.toml` tunes a
+//! custom one, picked from the menu like the presets.
+
+use super::*;
+
+/// Difficulty chosen for a run: a built-in preset or the custom profile.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    /// The `[difficulty]` profile from `lurhook.toml`, Normal without one.
+    Custom,
+}
+
+impl Difficulty {
+    /// Rules of the preset; the custom profile starts from Normal's.
+    pub fn profile(self) -> DifficultyProfile {
+        match self {
+            Difficulty::Easy => DifficultyProfile {
+                hunger_rate: 50,
+                hazard_chance: HAZARD_CHANCE / 2,
+                fish_strength: 75,
+                line_damage: LINE_DAMAGE / 2,
+                bite_bonus: 0.1,
+                escape_threshold: 2,
+                starting_items: Vec::new(),
+                permadeath: false,
+                rescue: false,
+            },
+            Difficulty::Normal | Difficulty::Custom => DifficultyProfile::default(),
+            Difficulty::Hard => DifficultyProfile {
+                hunger_rate: 200,
+                hazard_chance: HAZARD_CHANCE * 2,
+                fish_strength: 125,
+                line_damage: LINE_DAMAGE * 2,
+                bite_bonus: -0.1,
+                escape_threshold: 10,
+                starting_items: Vec::new(),
+                permadeath: true,
+                rescue: false,
+            },
+        }
+    }
+
+    /// Rules a run on this difficulty plays by, taking the custom profile
+    /// from `input`.
+    pub(crate) fn rules(self, input: &InputConfig) -> DifficultyProfile {
+        match (self, &input.difficulty) {
+            (Difficulty::Custom, Some(custom)) => custom.clone(),
+            _ => self.profile(),
+        }
+    }
+}
+
+/// Rules scaling survival pressure and the fishing fight.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DifficultyProfile {
+    /// Hunger lost every 100 turns.
+    pub hunger_rate: u32,
+    /// Percent chance per turn in deep water that a hazard appears, before
+    /// the area's multiplier.
+    pub hazard_chance: i32,
+    /// Strength of a hooked fish, in percent of its species'.
+    pub fish_strength: i32,
+    /// Line strength lost when the line snaps.
+    pub line_damage: i32,
+    /// Flat modifier added to the bite probability.
+    pub bite_bonus: f32,
+    /// Tension at or below which evasive fish escape.
+    pub escape_threshold: i32,
+    /// Ids of items from `items.json` added to the starting gear.
+    pub starting_items: Vec<String>,
+    /// Running out of hit points ends the run; otherwise the angler comes
+    /// to with half of them.
+    pub permadeath: bool,
+    /// Under permadeath, the first death is a rescue that costs the catch.
+    #[serde(default)]
+    pub rescue: bool,
+}
+
+impl Default for DifficultyProfile {
+    fn default() -> Self {
+        Self {
+            hunger_rate: 100,
+            hazard_chance: HAZARD_CHANCE,
+            fish_strength: 100,
+            line_damage: LINE_DAMAGE,
+            bite_bonus: 0.0,
+            escape_threshold: 5,
+            starting_items: Vec::new(),
+            permadeath: true,
+            rescue: true,
+        }
+    }
+}
+
+impl DifficultyProfile {
+    /// Hunger lost on `turn`, spreading the rate evenly over the turns.
+    pub(crate) fn hunger_loss(&self, turn: u32) -> i32 {
+        let lost = |turn: u32| (turn as u64 * self.hunger_rate as u64).div_ceil(100);
+        (lost(turn + 1) - lost(turn)) as i32
+    }
+
+    pub(crate) fn hazard_chance_in(&self, area: &data::AreaType) -> i32 {
+        self.hazard_chance * area.hazard_multiplier
+    }
+
+    /// Scales a hooked fish's strength.
+    pub(crate) fn scale_strength(&self, strength: i32) -> i32 {
+        (strength * self.fish_strength / 100).max(1)
+    }
+
+    /// Sets the rule named `key` in `lurhook.toml` from `val`; `base`
+    /// starts over from a preset. Unknown keys and bad values are ignored.
+    pub(crate) fn set(&mut self, key: &str, val: &str) {
+        match key {
+            "base" => {
+                let base = match val {
+                    "Easy" => Difficulty::Easy,
+                    "Hard" => Difficulty::Hard,
+                    _ => Difficulty::Normal,
+                };
+                *self = base.profile();
+            }
+            "hunger_rate" => self.hunger_rate = val.parse().unwrap_or(self.hunger_rate),
+            "hazard_chance" => {
+                self.hazard_chance = val.parse().unwrap_or(self.hazard_chance).clamp(0, 100)
+            }
+            "fish_strength" => {
+                self.fish_strength = val.parse().unwrap_or(self.fish_strength).max(1)
+            }
+            "line_damage" => self.line_damage = val.parse().unwrap_or(self.line_damage).max(0),
+            "bite_bonus" => self.bite_bonus = val.parse().unwrap_or(self.bite_bonus),
+            "escape_threshold" => {
+                self.escape_threshold = val.parse().unwrap_or(self.escape_threshold)
+            }
+            "starting_items" => {
+                self.starting_items = val
+                    .trim_start_matches('[')
+                    .trim_end_matches(']')
+                    .split(',')
+                    .map(|id| id.trim().trim_matches('"'))
+                    .filter(|id| !id.is_empty())
+                    .map(str::to_string)
+                    .collect();
+            }
+            "permadeath" => self.permadeath = val.parse().unwrap_or(self.permadeath),
+            "rescue" => self.rescue = val.parse().unwrap_or(self.rescue),
+            _ => {}
+        }
+    }
+
+    /// The profile as a `[difficulty]` section of `lurhook.toml`.
+    pub(crate) fn to_toml(&self) -> String {
+        let items: Vec<String> = self
+            .starting_items
+            .iter()
+            .map(|id| format!("\"{}\"", id))
+            .collect();
+        format!(
+            "\n[difficulty]\nhunger_rate = {}\nhazard_chance = {}\nfish_strength = {}\n\
+             line_damage = {}\nbite_bonus = {}\nescape_threshold = {}\n\
+             starting_items = [{}]\npermadeath = {}\nrescue = {}\n",
+            self.hunger_rate,
+            self.hazard_chance,
+            self.fish_strength,
+            self.line_damage,
+            self.bite_bonus,
+            self.escape_threshold,
+            items.join(", "),
+            self.permadeath,
+            self.rescue
+        )
+    }
+}
+
+impl LurhookGame {
+    /// Adds the run's starting items from the catalogue to the bag, noting
+    /// any id it does not know.
+    pub(crate) fn give_starting_items(&mut self) {
+        for id in self.rules.starting_items.clone() {
+            match self.catalogue.iter().find(|item| item.id == id) {
+                Some(item) => self.player.items.push(item.clone()),
+                None => {
+                    self.ui
+                        .add_entry(
+                            LogCategory::System,
+                            &format!("Unknown starting item {}.", id),
+                        )
+                        .ok();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_spread_hunger_over_the_turns() {
+        let lost = |difficulty: Difficulty| -> i32 {
+            let rules = difficulty.profile();
+            (0..10).map(|turn| rules.hunger_loss(turn)).sum()
+        };
+        assert_eq!(lost(Difficulty::Easy), 5);
+        assert_eq!(lost(Difficulty::Normal), 10);
+        assert_eq!(lost(Difficulty::Hard), 20);
+        assert_eq!(Difficulty::Easy.profile().hunger_loss(1), 0);
+        assert_eq!(Difficulty::Easy.profile().hunger_loss(2), 1);
+    }
+
+    #[test]
+    fn custom_profile_loads_from_the_config() {
+        let storage = MemoryStorage::default();
+        storage
+            .write(
+                CONFIG_PATH,
+                b"cast = \"C\"\n\n[difficulty]\nbase = \"Hard\"\nhunger_rate = 25\n\
+                  starting_items = [\"CANNED_MEAL\", \"NO_SUCH_ITEM\"]\npermadeath = false\n",
+            )
+            .unwrap();
+        let input = InputConfig::load(&storage, CONFIG_PATH).unwrap();
+        let custom = input.difficulty.clone().expect("custom profile");
+        assert_eq!(custom.hunger_rate, 25);
+        assert_eq!(custom.line_damage, LINE_DAMAGE * 2, "from the Hard base");
+        assert!(!custom.permadeath);
+        assert_eq!(Difficulty::Hard.rules(&input), Difficulty::Hard.profile());
+        assert_eq!(Difficulty::Custom.rules(&input), custom);
+
+        input.save(&storage, CONFIG_PATH).unwrap();
+        let saved = InputConfig::load(&storage, CONFIG_PATH).unwrap();
+        assert_eq!(saved.difficulty, Some(custom));
+
+        let game =
+            LurhookGame::new_with_storage(0, Difficulty::Custom, None, Box::new(storage)).unwrap();
+        assert_eq!(game.rules.hunger_rate, 25);
+        let meals = game.player.items.iter().filter(|i| i.id == "CANNED_MEAL");
+        assert_eq!(meals.count(), 2, "one more than the usual gear");
+        assert!(game.ui.logs().iter().any(|l| l.contains("NO_SUCH_ITEM")));
+    }
+
+    #[test]
+    fn custom_falls_back_to_normal() {
+        let input = InputConfig::default();
+        assert_eq!(
+            Difficulty::Custom.rules(&input),
+            Difficulty::Normal.profile()
+        );
+    }
+}
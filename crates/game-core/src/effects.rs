@@ -0,0 +1,178 @@
+//! Status effects: temporary conditions on the angler such as poison from
+//! a jellyfish sting or being soaked by rain. Each lasts a number of turns,
+//! is refreshed when applied again and ticks once per player turn.
+
+use super::*;
+use serde::{Deserialize, Serialize};
+
+/// HP lost to poison every this many turns.
+const POISON_INTERVAL: u32 = 5;
+/// Reeling strength adrenaline adds.
+pub(crate) const ADRENALINE_REEL: f32 = 0.25;
+
+/// A kind of temporary condition.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StatusKind {
+    /// Stung by a jellyfish; loses HP now and then.
+    Poisoned,
+    /// After a hot dish; hunger does not drop.
+    WellFed,
+    /// Caught in the rain; hunger drops twice as fast until dried off.
+    Soaked,
+    /// A fish on the line; reels harder.
+    Adrenaline,
+}
+
+impl StatusKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            StatusKind::Poisoned => "Poisoned",
+            StatusKind::WellFed => "Well fed",
+            StatusKind::Soaked => "Soaked",
+            StatusKind::Adrenaline => "Adrenaline",
+        }
+    }
+
+    /// Turns the effect lasts when applied.
+    fn duration(self) -> u32 {
+        match self {
+            StatusKind::Poisoned => 15,
+            StatusKind::WellFed => 40,
+            StatusKind::Soaked => 20,
+            StatusKind::Adrenaline => 10,
+        }
+    }
+
+    fn onset(self) -> &'static str {
+        match self {
+            StatusKind::Poisoned => "You are poisoned!",
+            StatusKind::WellFed => "You feel well fed.",
+            StatusKind::Soaked => "You are soaked through.",
+            StatusKind::Adrenaline => "Adrenaline surges through you!",
+        }
+    }
+
+    fn wear_off(self) -> &'static str {
+        match self {
+            StatusKind::Poisoned => "The poison wears off.",
+            StatusKind::WellFed => "You no longer feel well fed.",
+            StatusKind::Soaked => "You have dried off.",
+            StatusKind::Adrenaline => "Your adrenaline fades.",
+        }
+    }
+}
+
+/// A status effect on the player and the turns it has left.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusEffect {
+    pub kind: StatusKind,
+    pub turns: u32,
+}
+
+impl LurhookGame {
+    /// Puts `kind` on the current player for its full duration.
+    pub(crate) fn apply_effect(&mut self, kind: StatusKind) {
+        let turns = kind.duration();
+        let effects = &mut self.player.effects;
+        if let Some(effect) = effects.iter_mut().find(|e| e.kind == kind) {
+            effect.turns = effect.turns.max(turns);
+            return;
+        }
+        effects.push(StatusEffect { kind, turns });
+        self.ui.add_entry(LogCategory::Combat, kind.onset()).ok();
+    }
+
+    /// Takes `kind` off the current player.
+    pub(crate) fn clear_effect(&mut self, kind: StatusKind) {
+        let before = self.player.effects.len();
+        self.player.effects.retain(|e| e.kind != kind);
+        if self.player.effects.len() < before {
+            self.ui.add_entry(LogCategory::Combat, kind.wear_off()).ok();
+        }
+    }
+
+    /// Hunger the current player loses this turn.
+    pub(crate) fn hunger_loss(&self) -> i32 {
+        let loss = self.rules.hunger_loss(self.turn);
+        if self.player.has_effect(StatusKind::WellFed) {
+            0
+        } else if self.player.has_effect(StatusKind::Soaked) {
+            loss * 2
+        } else {
+            loss
+        }
+    }
+
+    /// Applies one turn of status effects: rain soaks the player and a
+    /// campfire dries them, poison hurts, and every effect counts down.
+    pub(crate) fn update_effects(&mut self) {
+        if self.near_campfire() {
+            self.clear_effect(StatusKind::Soaked);
+        } else if self.weather.weather.is_wet() {
+            self.apply_effect(StatusKind::Soaked);
+        }
+        let mut worn_off = Vec::new();
+        let mut poison = 0;
+        for effect in &mut self.player.effects {
+            if effect.kind == StatusKind::Poisoned && effect.turns % POISON_INTERVAL == 0 {
+                poison += 1;
+            }
+            effect.turns = effect.turns.saturating_sub(1);
+            if effect.turns == 0 {
+                worn_off.push(effect.kind);
+            }
+        }
+        self.player.effects.retain(|e| e.turns > 0);
+        self.hurt(poison, DeathCause::Poison);
+        for kind in worn_off {
+            self.ui.add_entry(LogCategory::Combat, kind.wear_off()).ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn poison_hurts_until_it_wears_off() {
+        let mut game = LurhookGame::default();
+        game.player.hp = 10;
+        game.weather = WeatherState::new(Weather::Clear, 50);
+        game.apply_effect(StatusKind::Poisoned);
+        game.player.effects[0].turns = 5;
+        game.apply_effect(StatusKind::Poisoned);
+        assert_eq!(game.player.effects.len(), 1, "applying again refreshes");
+        let turns = game.player.effects[0].turns;
+        assert_eq!(turns, StatusKind::Poisoned.duration());
+
+        for _ in 0..turns {
+            game.update_effects();
+        }
+        assert_eq!(game.player.hp, 10 - 3);
+        assert!(game.player.effects.is_empty());
+        assert_eq!(game.ui.logs().last().unwrap(), "The poison wears off.");
+    }
+
+    #[test]
+    fn rain_soaks_and_a_campfire_dries() {
+        let mut game = LurhookGame::default();
+        game.map.tiles.fill(TileKind::Land);
+        game.rules = Difficulty::Normal.profile();
+        game.weather = WeatherState::new(Weather::Rain, 50);
+        game.update_effects();
+        assert!(game.player.has_effect(StatusKind::Soaked));
+        assert_eq!(game.hunger_loss(), 2);
+
+        game.campfires.push(Campfire {
+            pos: game.player.pos,
+            turns: 10,
+        });
+        game.update_effects();
+        assert!(!game.player.has_effect(StatusKind::Soaked));
+        assert_eq!(game.ui.logs().last().unwrap(), "You have dried off.");
+
+        game.apply_effect(StatusKind::WellFed);
+        assert_eq!(game.hunger_loss(), 0);
+    }
+}
@@ -0,0 +1,228 @@
+//! Scripted events: encounters from `events.json` that may happen on a
+//! turn spent exploring, where the player stands on the tiles, at the time
+//! of day and in the area their trigger names. An event logs its text and
+//! applies its effects; one with choices then asks the player to pick
+//! one.
+
+use super::*;
+use data::{EventEffect, ScriptedEvent, Terrain};
+
+/// Loads the scripted events from `assets/events.json`.
+pub(crate) fn load_events() -> GameResult<Vec<ScriptedEvent>> {
+    let events = {
+        #[cfg(target_arch = "wasm32")]
+        {
+            data::load_events_embedded()?
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../../assets/events.json");
+            data::load_events(path)?
+        }
+    };
+    let effects = events.iter().flat_map(|e| {
+        e.effects
+            .iter()
+            .chain(e.choices.iter().flat_map(|c| &c.effects))
+    });
+    for effect in effects {
+        if let EventEffect::Hazard { kind: Some(kind) } = effect {
+            hazard_kind(kind)?;
+        }
+    }
+    Ok(events)
+}
+
+/// The hazard named `name`, as in the save files.
+fn hazard_kind(name: &str) -> GameResult<HazardKind> {
+    serde_json::from_value(serde_json::Value::String(name.to_string()))
+        .map_err(|_| GameError::Parse(format!("events: unknown hazard {}", name)))
+}
+
+fn terrain(tile: TileKind) -> Terrain {
+    match tile {
+        TileKind::Land => Terrain::Land,
+        TileKind::ShallowWater => Terrain::ShallowWater,
+        TileKind::DeepWater => Terrain::DeepWater,
+        TileKind::CoralReef => Terrain::CoralReef,
+        TileKind::KelpForest => Terrain::KelpForest,
+        TileKind::Sandbar => Terrain::Sandbar,
+        TileKind::Ice => Terrain::Ice,
+    }
+}
+
+impl LurhookGame {
+    /// Rolls the chance of each event whose trigger fits this turn, in
+    /// file order; the first to come up happens.
+    pub(crate) fn roll_events(&mut self) {
+        if !matches!(self.mode, GameMode::Exploring) || self.question.is_some() {
+            return;
+        }
+        let tile = terrain(self.map.tiles[self.map.idx(self.player.pos)]);
+        for i in 0..self.scripted_events.len() {
+            let trigger = &self.scripted_events[i].trigger;
+            let fits = self.turn >= trigger.min_turn
+                && (trigger.tiles.is_empty() || trigger.tiles.contains(&tile))
+                && (trigger.times.is_empty()
+                    || trigger.times.iter().any(|t| t == self.time_of_day))
+                && (trigger.areas.is_empty() || trigger.areas.contains(&self.area.id));
+            let chance = trigger.chance;
+            if fits && self.rng.range(0.0, 1.0) < chance {
+                self.start_event(i);
+                return;
+            }
+        }
+    }
+
+    /// Logs event `i` and applies its effects, then asks for a choice if it
+    /// offers any.
+    pub(crate) fn start_event(&mut self, i: usize) {
+        let event = self.scripted_events[i].clone();
+        self.ui.add_entry(LogCategory::Event, &event.text).ok();
+        for effect in &event.effects {
+            self.apply_event_effect(effect);
+        }
+        if !event.choices.is_empty() {
+            let labels = event.choices.iter().map(|c| c.label.clone()).collect();
+            let prompt = ui_crate::Prompt::new("What do you do?", &event.text, labels);
+            self.ask(prompts::Question::Event(i), prompt);
+        }
+    }
+
+    fn apply_event_effect(&mut self, effect: &EventEffect) {
+        let player = &mut self.player;
+        match effect {
+            EventEffect::Hp { amount } if *amount < 0 => self.hurt(-amount, DeathCause::Mishap),
+            EventEffect::Hp { amount } => player.hp = (player.hp + amount).min(MAX_HP),
+            EventEffect::Hunger { amount } => {
+                player.hunger = (player.hunger + amount).clamp(0, MAX_HUNGER);
+            }
+            EventEffect::Food { amount } => {
+                player.canned_food = (player.canned_food + amount).max(0);
+            }
+            EventEffect::Coins { amount } => player.coins = (player.coins + amount).max(0),
+            EventEffect::Item { id } => {
+                if let Some(item) = self.catalogue.iter().find(|i| &i.id == id) {
+                    player.items.push(item.clone());
+                }
+            }
+            EventEffect::Hazard { kind: None } => self.spawn_hazard(),
+            EventEffect::Hazard { kind: Some(kind) } => {
+                if let Ok(kind) = hazard_kind(kind) {
+                    self.spawn_hazard_of(kind);
+                }
+            }
+            EventEffect::Log { text } => {
+                self.ui.add_entry(LogCategory::Event, text).ok();
+            }
+        }
+    }
+
+    /// Takes choice `option` of event `i`.
+    pub(crate) fn choose_event_option(&mut self, i: usize, option: usize) {
+        let Some(choice) = self.scripted_events[i].choices.get(option).cloned() else {
+            return;
+        };
+        for effect in &choice.effects {
+            self.apply_event_effect(effect);
+        }
+        self.check_death();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(game: &LurhookGame, id: &str) -> usize {
+        game.scripted_events
+            .iter()
+            .position(|e| e.id == id)
+            .unwrap()
+    }
+
+    #[test]
+    fn bundled_events_load() {
+        let events = load_events().unwrap();
+        assert!(events.iter().any(|e| e.id == "MESSAGE_IN_A_BOTTLE"));
+        assert!(matches!(hazard_kind("Kraken"), Err(GameError::Parse(_))));
+        assert_eq!(hazard_kind("Shark").unwrap(), HazardKind::Shark);
+    }
+
+    #[test]
+    fn events_happen_only_where_their_trigger_fits() {
+        let mut game = LurhookGame::default();
+        game.scripted_events.truncate(1);
+        game.scripted_events[0].trigger.chance = 1.0;
+        game.scripted_events[0].trigger.tiles = vec![Terrain::Ice];
+        game.player.hp = 1;
+        game.roll_events();
+        assert_eq!(game.player.hp, 1, "not on ice");
+
+        game.scripted_events[0].trigger.tiles.clear();
+        game.scripted_events[0].trigger.min_turn = game.turn + 1;
+        game.roll_events();
+        assert_eq!(game.player.hp, 1, "too early");
+
+        game.scripted_events[0].trigger.min_turn = 0;
+        game.scripted_events[0].trigger.areas = vec![game.area.id.clone()];
+        game.roll_events();
+        assert_eq!(game.player.hp, 2);
+        assert!(game.ui.logs().iter().any(|l| l == "You rest on the shore."));
+    }
+
+    #[test]
+    fn events_with_choices_ask_for_one() {
+        let mut game = LurhookGame::default();
+        game.ui.set_layout(UILayout::Standard);
+        let crate_event = event(&game, "DRIFTING_CRATE");
+        let hunger = game.player.hunger;
+        let items = game.player.items.len();
+        game.start_event(crate_event);
+        assert_eq!(game.question, Some(prompts::Question::Event(crate_event)));
+        let mut buf = TextBuffer::new(SCREEN_WIDTH, SCREEN_HEIGHT);
+        game.render(&mut buf);
+        assert!(buf.contains(" What do you do? "));
+        assert!(buf.contains("1: Haul it in"));
+
+        game.choose_event_option(crate_event, 0);
+        assert_eq!(game.player.hunger, hunger - 10);
+        assert_eq!(game.player.items.len(), items + 1);
+        assert_eq!(game.player.items.last().unwrap().id, "CANNED_MEAL");
+    }
+
+    #[test]
+    fn a_pending_choice_holds_the_turn() {
+        let mut game = LurhookGame::default();
+        game.ui.set_layout(UILayout::Standard);
+        game.start_event(event(&game, "MESSAGE_IN_A_BOTTLE"));
+        let (pos, turn) = (game.player.pos, game.turn);
+        let mut ctx = empty_frame(0.0);
+        ctx.key = Some(VirtualKeyCode::Right);
+        game.update_frame(&mut ctx, false);
+        assert_eq!((game.player.pos, game.turn), (pos, turn));
+        ctx.key = Some(VirtualKeyCode::Escape);
+        game.update_frame(&mut ctx, false);
+        assert!(game.ui.prompt().is_some(), "events need an answer");
+        let coins = game.player.coins;
+        ctx.key = Some(VirtualKeyCode::Key1);
+        game.update_frame(&mut ctx, false);
+        assert_eq!(game.player.coins, coins + 15);
+        assert_eq!(game.turn, turn, "choosing takes no turn");
+    }
+
+    #[test]
+    fn harmful_events_can_kill() {
+        let mut game = LurhookGame::new_with_difficulty(0, Difficulty::Hard).unwrap();
+        game.scripted_events[0].effects = vec![EventEffect::Hp { amount: -MAX_HP }];
+        game.start_event(0);
+        game.check_death();
+        assert_eq!(
+            game.mode,
+            GameMode::GameOver {
+                cause: DeathCause::Mishap,
+                score: 0
+            }
+        );
+    }
+}
@@ -0,0 +1,246 @@
+//! Map export: the whole map of the area, fog lifted, with its fish,
+//! hazards, boat, merchant and the angler, written as plain ASCII text or,
+//! built with the `png` feature, as an image with a block of pixels per
+//! tile. Both carry the seed, area and turn, so an interesting seed can be
+//! shared and played again.
+
+use super::*;
+
+/// Pixels along each side of a tile in an exported image.
+#[cfg(feature = "png")]
+const TILE_PIXELS: u32 = 4;
+
+/// What the characters of a text export stand for.
+const LEGEND: &str = "Legend: @ you  B boat  $ merchant  f fish  F deep fish  \
+! jellyfish  ^ shark  # debris  O whirlpool  . land  ~ shallows  = deep  \
+& coral  \" kelp  : sandbar  % ice";
+
+/// File format of a map export.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MapFormat {
+    /// One ASCII character per tile under a metadata header.
+    Text,
+    /// An RGB image with the metadata in text chunks.
+    #[cfg(feature = "png")]
+    Png,
+}
+
+impl MapFormat {
+    /// What the export key writes: an image when the game can make one.
+    fn preferred() -> Self {
+        #[cfg(feature = "png")]
+        {
+            MapFormat::Png
+        }
+        #[cfg(not(feature = "png"))]
+        {
+            MapFormat::Text
+        }
+    }
+
+    /// Conventional file extension without the dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            MapFormat::Text => "txt",
+            #[cfg(feature = "png")]
+            MapFormat::Png => "png",
+        }
+    }
+}
+
+/// The ASCII stand-in for a map glyph.
+fn ascii(glyph: char) -> char {
+    match glyph {
+        '≈' => '=',
+        '♣' => '&',
+        '░' => ':',
+        '▒' => '%',
+        'ƒ' => 'F',
+        'Ω' => 'O',
+        c if c.is_ascii() => c,
+        _ => '?',
+    }
+}
+
+impl LurhookGame {
+    /// Every tile of the map in full view with what stands on it, row by
+    /// row.
+    fn map_export_cells(&self) -> Vec<Vec<(char, RGB)>> {
+        let mut cells: Vec<Vec<(char, RGB)>> = (0..self.map.height as i32)
+            .map(|y| {
+                (0..self.map.width as i32)
+                    .map(|x| {
+                        let tile = self.map.tiles[self.map.idx(common::Point::new(x, y))];
+                        self.tile_style(tile, true)
+                    })
+                    .collect()
+            })
+            .collect();
+        let mut put = |pt: common::Point, cell: (char, RGB)| {
+            if let Some(row) = cells.get_mut(pt.y as usize) {
+                if let Some(c) = row.get_mut(pt.x as usize) {
+                    *c = cell;
+                }
+            }
+        };
+        for fish in &self.fishes {
+            put(fish.position, self.fish_style(fish));
+        }
+        for h in &self.hazards {
+            put(h.pos, (h.kind.glyph(), self.hazard_color(h.kind)));
+        }
+        if !self.player.aboard {
+            put(self.player.boat.pos, ('B', RGB::named(ORANGE)));
+        }
+        if let Some(pos) = self.merchant {
+            put(pos, ('$', RGB::named(YELLOW)));
+        }
+        if let Some(pos) = self.companion {
+            put(pos, ('@', RGB::named(CYAN)));
+        }
+        put(self.player.pos, ('@', self.palette.player));
+        cells
+    }
+
+    /// The seed, area and turn line of an export.
+    fn map_export_metadata(&self) -> String {
+        format!(
+            "Seed {}  Area {}  Turn {}",
+            self.seed, self.area.id, self.turn
+        )
+    }
+
+    /// The map as text: a title and metadata line, the rows, then a legend.
+    pub(crate) fn map_export_text(&self) -> String {
+        let mut text = format!(
+            "Lurhook map - {}\n{}\n",
+            self.area.name,
+            self.map_export_metadata()
+        );
+        for row in self.map_export_cells() {
+            text.extend(row.iter().map(|&(glyph, _)| ascii(glyph)));
+            text.push('\n');
+        }
+        text.push_str(LEGEND);
+        text.push('\n');
+        text
+    }
+
+    /// The map as a PNG image with the seed, area and turn as text chunks.
+    #[cfg(feature = "png")]
+    pub(crate) fn map_export_png(&self) -> GameResult<Vec<u8>> {
+        fn failed(e: png::EncodingError) -> GameError {
+            GameError::Io(std::io::Error::other(e))
+        }
+        let cells = self.map_export_cells();
+        let (width, height) = (self.map.width * TILE_PIXELS, self.map.height * TILE_PIXELS);
+        let mut pixels = Vec::with_capacity((width * height * 3) as usize);
+        for row in &cells {
+            let line: Vec<u8> = row
+                .iter()
+                .flat_map(|&(_, color)| {
+                    let rgb = [color.r, color.g, color.b].map(|c| (c * 255.0).round() as u8);
+                    std::iter::repeat_n(rgb, TILE_PIXELS as usize).flatten()
+                })
+                .collect();
+            for _ in 0..TILE_PIXELS {
+                pixels.extend_from_slice(&line);
+            }
+        }
+        let mut bytes = Vec::new();
+        let mut encoder = png::Encoder::new(&mut bytes, width, height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let metadata = [
+            ("Title", format!("Lurhook map - {}", self.area.name)),
+            ("Seed", self.seed.to_string()),
+            ("Area", self.area.id.clone()),
+            ("Turn", self.turn.to_string()),
+        ];
+        for (keyword, text) in metadata {
+            encoder
+                .add_text_chunk(keyword.to_string(), text)
+                .map_err(failed)?;
+        }
+        let mut writer = encoder.write_header().map_err(failed)?;
+        writer.write_image_data(&pixels).map_err(failed)?;
+        writer.finish().map_err(failed)?;
+        Ok(bytes)
+    }
+
+    /// Writes the map in `format`; returns the key written.
+    pub fn export_map(&self, format: MapFormat) -> GameResult<String> {
+        let key = format!("map_{}_{}.{}", self.seed, self.turn, format.extension());
+        let bytes = match format {
+            MapFormat::Text => self.map_export_text().into_bytes(),
+            #[cfg(feature = "png")]
+            MapFormat::Png => self.map_export_png()?,
+        };
+        self.storage.write(&key, &bytes)?;
+        Ok(key)
+    }
+
+    /// Exports the map from the export key, logging where it went.
+    pub(crate) fn save_map_export(&mut self) {
+        match self.export_map(MapFormat::preferred()) {
+            Ok(key) => self.ui.add_log(&format!("Map exported to {}", key)).ok(),
+            Err(e) => self.ui.add_log(&format!("Map export failed: {}", e)).ok(),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_game;
+
+    #[test]
+    fn the_text_map_shows_everything_with_its_metadata() {
+        let mut game = memory_game(7);
+        game.hazards.clear();
+        game.fishes.truncate(1);
+        let fish = game.fishes[0].position;
+        let text = game.map_export_text();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], format!("Lurhook map - {}", game.area.name));
+        assert_eq!(lines[1], format!("Seed 7  Area {}  Turn 0", game.area.id));
+        assert!(text.is_ascii());
+        let rows = &lines[2..2 + game.map.height as usize];
+        assert!(rows.iter().all(|r| r.len() == game.map.width as usize));
+        let at = |pt: common::Point| rows[pt.y as usize].as_bytes()[pt.x as usize] as char;
+        assert_eq!(at(game.player.pos), '@');
+        assert!(matches!(at(fish), 'f' | 'F'));
+        assert!(lines.last().unwrap().starts_with("Legend:"));
+    }
+
+    #[test]
+    fn the_export_key_writes_the_map() {
+        let mut game = memory_game(7);
+        game.ui.set_layout(UILayout::Standard);
+        game.handle_input_key(Some(game.input.export_map));
+        let key = format!("map_7_0.{}", MapFormat::preferred().extension());
+        assert!(game.storage.read(&key).unwrap().is_some());
+        let logged = format!("Map exported to {}", key);
+        assert!(game.ui.logs().contains(&logged));
+        assert_eq!(game.turn, 0, "exporting takes no turn");
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    fn the_png_map_carries_its_metadata() {
+        let game = memory_game(7);
+        let bytes = game.map_export_png().unwrap();
+        let decoder = png::Decoder::new(bytes.as_slice());
+        let reader = decoder.read_info().unwrap();
+        let info = reader.info();
+        assert_eq!(info.width, game.map.width * TILE_PIXELS);
+        let text = |k: &str| {
+            info.uncompressed_latin1_text
+                .iter()
+                .find(|c| c.keyword == k)
+                .map(|c| c.text.clone())
+        };
+        assert_eq!(text("Seed").as_deref(), Some("7"));
+        assert_eq!(text("Area"), Some(game.area.id.clone()));
+    }
+}
@@ -0,0 +1,189 @@
+//! Food and water: landed fish spoil after a while, raw ones sooner than
+//! cooked, and thirst drains away from land until the angler drinks ashore
+//! or from a water flask.
+
+use super::*;
+
+/// Thirst quenched each turn spent ashore, where there is fresh water.
+const DRINK_ASHORE: i32 = 10;
+/// Thirst quenched by a water flask.
+const DRINK_FLASK: i32 = 50;
+/// HP lost eating a spoiled fish.
+const SPOILED_FISH_DAMAGE: i32 = 1;
+
+impl LurhookGame {
+    /// Applies one turn of thirst: the player drinks ashore, dries out
+    /// elsewhere and loses HP once parched.
+    pub(crate) fn update_thirst(&mut self) {
+        let tile = self.map.tiles[self.map.idx(self.player.pos)];
+        if tile == TileKind::Land {
+            self.player.thirst = (self.player.thirst + DRINK_ASHORE).min(MAX_THIRST);
+        } else if self.player.thirst > 0 {
+            let loss = self.rules.hunger_loss(self.turn);
+            if loss > 0 {
+                self.player.thirst = (self.player.thirst - loss).max(0);
+                if self.player.thirst == 0 {
+                    self.events.push(GameEvent::ThirstCritical);
+                }
+            }
+        } else {
+            self.hurt(1, DeathCause::Thirst);
+        }
+    }
+
+    /// Tells the player about fish that spoiled this turn.
+    pub(crate) fn update_spoilage(&mut self) {
+        let turn = self.turn;
+        let spoiled = self
+            .player
+            .inventory
+            .iter()
+            .filter(|c| c.is_spoiled(turn) && !c.is_spoiled(turn.saturating_sub(1)))
+            .count();
+        let msg = match spoiled {
+            0 => return,
+            1 => "A fish in your bag has spoiled.".to_string(),
+            n => format!("{} fish in your bag have spoiled.", n),
+        };
+        self.ui.add_log(&msg).ok();
+    }
+
+    /// Drinks from a water flask, using it up.
+    pub(crate) fn drink_water(&mut self) {
+        self.player.thirst = (self.player.thirst + DRINK_FLASK).min(MAX_THIRST);
+        self.ui.add_log("You drink from your water flask.").ok();
+    }
+
+    /// The fish eaten next: the one closest to spoiling, spoiled ones last.
+    pub(crate) fn next_meal(&self) -> Option<usize> {
+        let turn = self.turn;
+        self.player
+            .inventory
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, c)| match c.keeps_for(turn) {
+                0 => u32::MAX,
+                left => left,
+            })
+            .map(|(i, _)| i)
+    }
+
+    /// Eats the fish at `idx` in the catch. Cooked fish fill more and
+    /// spoiled ones make the player sick.
+    pub(crate) fn eat_catch(&mut self, idx: usize) {
+        let fish = self.player.inventory.remove(idx);
+        let msg = if fish.is_spoiled(self.turn) {
+            self.hurt(SPOILED_FISH_DAMAGE, DeathCause::SpoiledFish);
+            "You ate a spoiled fish and feel sick."
+        } else if fish.cooked {
+            self.player.hunger = (self.player.hunger + EAT_COOKED_FISH).min(MAX_HUNGER);
+            "You ate a cooked fish."
+        } else {
+            self.player.hunger = (self.player.hunger + EAT_RAW_FISH).min(MAX_HUNGER);
+            "You ate a raw fish."
+        };
+        self.ui.add_log(msg).ok();
+        self.events.push(GameEvent::FishEaten);
+    }
+
+    /// Cooks every raw fish that has not spoiled yet so it keeps longer;
+    /// how many were cooked.
+    pub(crate) fn cook_catch(&mut self) -> usize {
+        let turn = self.turn;
+        let mut cooked = 0;
+        for fish in &mut self.player.inventory {
+            if !fish.cooked && !fish.is_spoiled(turn) {
+                fish.cooked = true;
+                fish.stamp = turn;
+                cooked += 1;
+            }
+        }
+        cooked
+    }
+
+    /// Inventory label of a landed fish.
+    pub(crate) fn catch_label(&self, fish: &Catch) -> String {
+        if fish.is_spoiled(self.turn) {
+            format!("Spoiled {}", fish.kind.name)
+        } else if fish.cooked {
+            format!("Cooked {}", fish.kind.name)
+        } else {
+            fish.kind.name.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{COOKED_SHELF_LIFE, RAW_SHELF_LIFE};
+
+    fn catch(game: &LurhookGame, stamp: u32, cooked: bool) -> Catch {
+        Catch {
+            stamp,
+            cooked,
+            ..Catch::average(game.fish_types[0].clone())
+        }
+    }
+
+    #[test]
+    fn raw_fish_spoil_before_cooked_ones() {
+        let mut game = LurhookGame::default();
+        game.player.inventory = vec![catch(&game, 0, false), catch(&game, 0, true)];
+        game.turn = RAW_SHELF_LIFE;
+        game.update_spoilage();
+        assert_eq!(
+            game.ui.logs().last().unwrap(),
+            "A fish in your bag has spoiled."
+        );
+        assert!(game.player.inventory[0].is_spoiled(game.turn));
+        assert!(!game.player.inventory[1].is_spoiled(game.turn));
+        assert_eq!(game.next_meal(), Some(1), "spoiled fish are eaten last");
+
+        game.player.hp = 5;
+        game.eat_catch(0);
+        assert_eq!(game.player.hp, 5 - SPOILED_FISH_DAMAGE);
+        assert_eq!(game.player.hunger, MAX_HUNGER);
+    }
+
+    #[test]
+    fn cooking_restamps_the_catch() {
+        let mut game = LurhookGame::default();
+        game.player.inventory = vec![
+            catch(&game, 0, false),
+            catch(&game, 50, false),
+            catch(&game, 0, true),
+        ];
+        game.turn = RAW_SHELF_LIFE + 10;
+        assert_eq!(game.cook_catch(), 1, "only the fresh raw fish cooks");
+        let fish = &game.player.inventory[1];
+        assert!(fish.cooked);
+        assert_eq!(fish.keeps_for(game.turn), COOKED_SHELF_LIFE);
+        assert_eq!(
+            game.catch_label(&game.player.inventory[0]),
+            format!("Spoiled {}", game.fish_types[0].name)
+        );
+    }
+
+    #[test]
+    fn thirst_drains_at_sea_and_is_quenched_ashore() {
+        let mut game = LurhookGame::default();
+        game.map.tiles.fill(TileKind::ShallowWater);
+        game.player.thirst = 1;
+        game.update_thirst();
+        assert_eq!(game.player.thirst, 0);
+        assert!(game
+            .events
+            .drain()
+            .any(|e| matches!(e, GameEvent::ThirstCritical)));
+        let hp = game.player.hp;
+        game.update_thirst();
+        assert_eq!(game.player.hp, hp - 1);
+
+        game.map.tiles.fill(TileKind::Land);
+        game.update_thirst();
+        assert_eq!(game.player.thirst, DRINK_ASHORE);
+        game.drink_water();
+        assert_eq!(game.player.thirst, DRINK_ASHORE + DRINK_FLASK);
+    }
+}
@@ -0,0 +1,78 @@
+//! Controller input read through gilrs.
+//!
+//! [`GilrsGamepad`] only translates gilrs events into [`PadEvent`]s; the
+//! button mapping and key repeat live in [`input::PadInput`].
+
+use super::*;
+use gilrs::{Axis, Button, EventType, Gilrs};
+
+/// Every controller gilrs finds, read as one.
+pub struct GilrsGamepad {
+    gilrs: Gilrs,
+    /// Last left stick position, since gilrs reports each axis on its own.
+    stick: (f32, f32),
+}
+
+impl GilrsGamepad {
+    /// Opens the platform's controller backend.
+    pub fn connect() -> GameResult<Self> {
+        let gilrs =
+            Gilrs::new().map_err(|e| GameError::Io(std::io::Error::other(e.to_string())))?;
+        Ok(Self {
+            gilrs,
+            stick: (0.0, 0.0),
+        })
+    }
+}
+
+/// Our name for a gilrs button; gilrs calls the bumpers triggers and the
+/// triggers `Trigger2`.
+fn pad_button(button: Button) -> Option<PadButton> {
+    Some(match button {
+        Button::South => PadButton::South,
+        Button::East => PadButton::East,
+        Button::West => PadButton::West,
+        Button::North => PadButton::North,
+        Button::LeftTrigger => PadButton::LeftBumper,
+        Button::RightTrigger => PadButton::RightBumper,
+        Button::LeftTrigger2 => PadButton::LeftTrigger,
+        Button::RightTrigger2 => PadButton::RightTrigger,
+        Button::Select => PadButton::Select,
+        Button::Start => PadButton::Start,
+        Button::DPadUp => PadButton::DPadUp,
+        Button::DPadDown => PadButton::DPadDown,
+        Button::DPadLeft => PadButton::DPadLeft,
+        Button::DPadRight => PadButton::DPadRight,
+        _ => return None,
+    })
+}
+
+impl Gamepad for GilrsGamepad {
+    fn poll(&mut self) -> Vec<PadEvent> {
+        let mut events = Vec::new();
+        while let Some(event) = self.gilrs.next_event() {
+            match event.event {
+                EventType::ButtonPressed(button, _) => {
+                    events.extend(pad_button(button).map(PadEvent::Pressed))
+                }
+                EventType::ButtonReleased(button, _) => {
+                    events.extend(pad_button(button).map(PadEvent::Released))
+                }
+                EventType::AxisChanged(Axis::LeftStickX, x, _) => {
+                    self.stick.0 = x;
+                    events.push(PadEvent::Stick(self.stick.0, self.stick.1));
+                }
+                EventType::AxisChanged(Axis::LeftStickY, y, _) => {
+                    self.stick.1 = y;
+                    events.push(PadEvent::Stick(self.stick.0, self.stick.1));
+                }
+                EventType::Disconnected => {
+                    self.stick = (0.0, 0.0);
+                    events.push(PadEvent::Disconnected);
+                }
+                _ => {}
+            }
+        }
+        events
+    }
+}
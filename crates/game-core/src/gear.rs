@@ -0,0 +1,152 @@
+//! Gear wear: every fight wears the rod, reel and line in proportion to the
+//! hardest pull, repair kits restore the rod and reel, and a worn line that
+//! snaps takes the lure with it.
+
+use super::*;
+
+/// Peak tension that costs a rod or reel one point of durability.
+const TENSION_PER_GEAR_WEAR: i32 = 10;
+/// Peak tension that costs the line one point.
+const TENSION_PER_LINE_WEAR: i32 = 20;
+/// Line below which a snap parts above the lure and takes it along.
+const LURE_LOSS_LINE: i32 = 50;
+
+impl LurhookGame {
+    /// Wears the rod, reel and line after a fight that peaked at `peak`
+    /// tension, warning when a piece of gear wears out.
+    pub(crate) fn wear_gear(&mut self, peak: i32) {
+        let wear = peak / TENSION_PER_GEAR_WEAR;
+        if self.player.rod.is_some() && self.player.rod_durability > 0 {
+            self.player.rod_durability = (self.player.rod_durability - wear).max(0);
+            if self.player.rod_durability == 0 {
+                self.ui
+                    .add_entry(LogCategory::Fishing, "Your rod is worn out.")
+                    .ok();
+            }
+        }
+        if self.player.reel.is_some() && self.player.reel_durability > 0 {
+            self.player.reel_durability = (self.player.reel_durability - wear).max(0);
+            if self.player.reel_durability == 0 {
+                self.ui
+                    .add_entry(LogCategory::Fishing, "Your reel is worn out.")
+                    .ok();
+            }
+        }
+        if self.player.line > 0 {
+            self.player.line = (self.player.line - peak / TENSION_PER_LINE_WEAR).max(0);
+            if self.player.line == 0 {
+                self.events.push(GameEvent::LineRuined);
+            }
+        }
+    }
+
+    /// Restores the rod and reel with a repair kit; whether there was
+    /// anything to repair.
+    pub(crate) fn repair_gear(&mut self) -> bool {
+        let player = &mut self.player;
+        let worn = (player.rod.is_some() && player.rod_durability < MAX_GEAR_DURABILITY)
+            || (player.reel.is_some() && player.reel_durability < MAX_GEAR_DURABILITY);
+        if !worn {
+            self.ui
+                .add_entry(LogCategory::Fishing, "Your gear needs no repair.")
+                .ok();
+            return false;
+        }
+        player.rod_durability = MAX_GEAR_DURABILITY;
+        player.reel_durability = MAX_GEAR_DURABILITY;
+        self.ui
+            .add_entry(LogCategory::Fishing, "You repair your rod and reel.")
+            .ok();
+        true
+    }
+
+    /// Takes the equipped lure if the line is worn enough to part above
+    /// it when it snaps; whether the lure was lost.
+    pub(crate) fn snap_takes_lure(&mut self) -> bool {
+        if self.player.line >= LURE_LOSS_LINE || self.player.lure.take().is_none() {
+            return false;
+        }
+        self.player.bait_bonus = 0.0;
+        true
+    }
+
+    /// Durability of the equipped rod and reel for the fight screen.
+    pub(crate) fn gear_condition(&self) -> (Option<i32>, Option<i32>) {
+        let player = &self.player;
+        (
+            player.rod.as_ref().map(|_| player.rod_durability),
+            player.reel.as_ref().map(|_| player.reel_durability),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kit() -> data::ItemType {
+        data::ItemType {
+            id: "REPAIR_KIT".into(),
+            name: "Repair Kit".into(),
+            kind: data::ItemKind::Repair,
+            tension_bonus: 0,
+            reel_factor: 1.0,
+            bite_bonus: 0.0,
+            max_cast_range: data::DEFAULT_CAST_RANGE,
+        }
+    }
+
+    #[test]
+    fn hard_fights_wear_the_gear_until_it_fails() {
+        let mut game = LurhookGame::default();
+        assert!(game.player.rod.is_some() && game.player.reel.is_some());
+        game.player.tension_bonus = 20;
+        game.player.reel_factor = 1.5;
+        game.wear_gear(45);
+        assert_eq!(game.player.rod_durability, MAX_GEAR_DURABILITY - 4);
+        assert_eq!(game.player.reel_durability, MAX_GEAR_DURABILITY - 4);
+        assert_eq!(game.player.line, MAX_LINE - 2);
+        assert_eq!(game.player.rod_tension_bonus(), 20);
+
+        game.player.rod_durability = 3;
+        game.player.reel_durability = 3;
+        game.wear_gear(90);
+        assert_eq!(game.player.rod_durability, 0);
+        assert_eq!(game.player.rod_tension_bonus(), 0);
+        assert!((game.player.reel_strength() - 0.75).abs() < f32::EPSILON);
+        assert!(game.ui.logs().iter().any(|l| l == "Your rod is worn out."));
+    }
+
+    #[test]
+    fn repair_kits_restore_worn_gear_and_are_used_up() {
+        let mut game = LurhookGame::default();
+        game.player.items = vec![kit()];
+        game.inventory_cursor = 0;
+        game.activate_selected_item();
+        assert_eq!(
+            game.player.items.len(),
+            1,
+            "nothing to repair keeps the kit"
+        );
+
+        game.player.rod_durability = 0;
+        game.player.reel_durability = 40;
+        game.activate_selected_item();
+        assert!(game.player.items.is_empty());
+        assert_eq!(game.player.rod_durability, MAX_GEAR_DURABILITY);
+        assert_eq!(game.player.reel_durability, MAX_GEAR_DURABILITY);
+    }
+
+    #[test]
+    fn a_worn_line_takes_the_lure_when_it_snaps() {
+        let mut game = LurhookGame::default();
+        assert!(game.player.lure.is_some());
+        assert!(!game.snap_takes_lure(), "a sound line keeps the lure");
+
+        game.player.line = LURE_LOSS_LINE - 1;
+        assert!(game.snap_takes_lure());
+        assert!(game.player.lure.is_none());
+        assert_eq!(game.player.bait_bonus, 0.0);
+        assert!(!game.snap_takes_lure());
+    }
+}
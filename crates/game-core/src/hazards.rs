@@ -0,0 +1,315 @@
+//! Hazards: jellyfish drift in place, sharks hunt the angler or the fish on
+//! their line, floating debris rides the current and whirlpools drag
+//! anything nearby into them. Each hurts in its own way on contact.
+
+use super::*;
+use ecology::spatial::SpatialIndex;
+use serde::{Deserialize, Serialize};
+
+/// Farthest from the player a shark, debris or whirlpool appears, in tiles.
+const SPAWN_RANGE: i32 = 4;
+/// Farthest a whirlpool drags the player from, in tiles.
+const WHIRLPOOL_PULL: i32 = 2;
+
+/// What kind of hazard it is.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HazardKind {
+    /// Stays where it appeared; stings and poisons.
+    #[default]
+    Jellyfish,
+    /// Swims after the player, or the hooked fish during a fight.
+    Shark,
+    /// Drifts with the current and tangles the line.
+    Debris,
+    /// Stays put and drags the player in.
+    Whirlpool,
+}
+
+impl HazardKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            HazardKind::Jellyfish => "Jellyfish",
+            HazardKind::Shark => "Shark",
+            HazardKind::Debris => "Debris",
+            HazardKind::Whirlpool => "Whirlpool",
+        }
+    }
+
+    pub fn glyph(self) -> char {
+        match self {
+            HazardKind::Jellyfish => '!',
+            HazardKind::Shark => '^',
+            HazardKind::Debris => '#',
+            HazardKind::Whirlpool => 'Ω',
+        }
+    }
+
+    /// Turns it stays around.
+    fn duration(self) -> u8 {
+        match self {
+            HazardKind::Jellyfish => 3,
+            HazardKind::Shark => 20,
+            HazardKind::Debris => 15,
+            HazardKind::Whirlpool => 12,
+        }
+    }
+
+    /// HP, or hull points aboard, lost on contact.
+    fn damage(self) -> i32 {
+        match self {
+            HazardKind::Shark => 2,
+            HazardKind::Jellyfish | HazardKind::Debris | HazardKind::Whirlpool => HAZARD_DAMAGE,
+        }
+    }
+
+    /// Line strength lost on contact.
+    fn line_damage(self) -> i32 {
+        match self {
+            HazardKind::Jellyfish => LINE_DAMAGE,
+            HazardKind::Debris => LINE_DAMAGE * 2,
+            HazardKind::Shark | HazardKind::Whirlpool => 0,
+        }
+    }
+
+    fn sighting(self) -> &'static str {
+        match self {
+            HazardKind::Jellyfish => "A jellyfish appears!",
+            HazardKind::Shark => "A shark fin cuts the water!",
+            HazardKind::Debris => "Debris floats by on the current.",
+            HazardKind::Whirlpool => "A whirlpool opens up nearby!",
+        }
+    }
+
+    /// Log lines for contact while in the boat and out of it.
+    fn contact(self) -> (&'static str, &'static str) {
+        match self {
+            HazardKind::Jellyfish => ("A jellyfish batters the boat!", "A jellyfish stings you!"),
+            HazardKind::Shark => ("A shark rams the boat!", "A shark bites you!"),
+            HazardKind::Debris => (
+                "Debris scrapes the hull!",
+                "Floating debris knocks into you!",
+            ),
+            HazardKind::Whirlpool => (
+                "The whirlpool spins the boat!",
+                "The whirlpool pulls you under!",
+            ),
+        }
+    }
+}
+
+impl LurhookGame {
+    /// Brings a random hazard up.
+    pub(crate) fn spawn_hazard(&mut self) {
+        let kind = match self.rng.range(0, 100) {
+            0..=49 => HazardKind::Jellyfish,
+            50..=69 => HazardKind::Shark,
+            70..=89 => HazardKind::Debris,
+            _ => HazardKind::Whirlpool,
+        };
+        self.spawn_hazard_of(kind);
+    }
+
+    /// Brings a hazard of `kind` up. Jellyfish appear on the player's
+    /// tile, the rest on water a few tiles away.
+    pub(crate) fn spawn_hazard_of(&mut self, kind: HazardKind) {
+        let pos = if kind == HazardKind::Jellyfish {
+            self.player.pos
+        } else {
+            let offset = common::Point::new(
+                self.rng.range(-SPAWN_RANGE, SPAWN_RANGE + 1),
+                self.rng.range(-SPAWN_RANGE, SPAWN_RANGE + 1),
+            );
+            self.player.pos + offset
+        };
+        if !self.is_open_water(pos) {
+            return;
+        }
+        self.hazards.push(Hazard {
+            pos,
+            turns: kind.duration(),
+            kind,
+        });
+        self.ui.add_entry(LogCategory::Combat, kind.sighting()).ok();
+    }
+
+    /// Moves the hazards and predators, lets them at the hooked fish, hurts
+    /// the current player on contact and clears the hazards that have gone.
+    pub(super) fn update_hazards(&mut self) {
+        self.move_hazards();
+        self.stalk_hooked_fish();
+        self.predator_strike();
+        let index = SpatialIndex::build(&self.map, self.hazards.iter().map(|h| h.pos));
+        self.drag_player(&index);
+        for hazard in self.hazards.iter_mut() {
+            if hazard.turns > 0 {
+                hazard.turns -= 1;
+            }
+        }
+        self.sting_player();
+        self.hazards.retain(|h| h.turns > 0);
+        self.check_death();
+    }
+
+    /// Damages the current player if they stand on a hazard, and poisons
+    /// them if it is a jellyfish. Aboard, the boat takes the damage until
+    /// it is wrecked.
+    pub(super) fn sting_player(&mut self) {
+        let mut stung = false;
+        let pos = self.player.pos;
+        let contacts: Vec<HazardKind> = self
+            .hazards
+            .iter()
+            .filter(|h| h.pos == pos)
+            .map(|h| h.kind)
+            .collect();
+        for kind in contacts {
+            let (battered, hurt) = kind.contact();
+            let boat = &mut self.player.boat;
+            if self.player.aboard && !boat.is_wrecked() {
+                boat.durability = (boat.durability - kind.damage()).max(0);
+                if boat.is_wrecked() {
+                    self.ui
+                        .add_entry(LogCategory::Combat, "Your boat is wrecked!")
+                        .ok();
+                } else {
+                    self.ui.add_entry(LogCategory::Combat, battered).ok();
+                }
+            } else if self.player.hp > 0 {
+                self.hurt(kind.damage(), DeathCause::Hazard(kind));
+                self.ui.add_entry(LogCategory::Combat, hurt).ok();
+                stung |= kind == HazardKind::Jellyfish;
+            }
+            if self.player.line > 0 {
+                self.player.line = (self.player.line - kind.line_damage()).max(0);
+            }
+        }
+        if stung {
+            self.apply_effect(StatusKind::Poisoned);
+        }
+    }
+
+    pub(crate) fn is_open_water(&self, pt: common::Point) -> bool {
+        self.map.bounds().contains(pt) && self.map.tiles[self.map.idx(pt)].is_water()
+    }
+
+    /// Sharks swim a tile towards the hooked fish while one is fought, and
+    /// towards the player otherwise; debris drifts with the current.
+    fn move_hazards(&mut self) {
+        let prey = self
+            .meter
+            .as_ref()
+            .and(self.hooked_fish())
+            .map_or(self.player.pos, |f| f.position);
+        for h in 0..self.hazards.len() {
+            let pos = self.hazards[h].pos;
+            let next = match self.hazards[h].kind {
+                HazardKind::Shark => pos + (prey - pos).signum(),
+                HazardKind::Debris => self.map.drift(pos),
+                HazardKind::Jellyfish | HazardKind::Whirlpool => continue,
+            };
+            if self.is_open_water(next) {
+                self.hazards[h].pos = next;
+            }
+        }
+    }
+
+    /// Pulls the player a tile towards the nearest whirlpool in reach,
+    /// unless they are ashore or riding at anchor.
+    fn drag_player(&mut self, index: &SpatialIndex) {
+        let pos = self.player.pos;
+        if !self.is_open_water(pos) || (self.player.aboard && self.player.boat.anchored) {
+            return;
+        }
+        let Some(pool) = index
+            .query_radius(pos, WHIRLPOOL_PULL)
+            .into_iter()
+            .map(|h| &self.hazards[h])
+            .filter(|h| h.kind == HazardKind::Whirlpool)
+            .map(|h| h.pos)
+            .filter(|p| *p != pos && p.chebyshev(pos) <= WHIRLPOOL_PULL)
+            .min_by_key(|p| p.chebyshev(pos))
+        else {
+            return;
+        };
+        let next = pos + (pool - pos).signum();
+        if !self.is_open_water(next) {
+            return;
+        }
+        self.player.pos = next;
+        if self.player.aboard {
+            self.player.boat.pos = next;
+        }
+        self.depth = self.map.depth(next);
+        self.ui
+            .add_entry(LogCategory::Combat, "The whirlpool drags you in!")
+            .ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hazard(kind: HazardKind, pos: common::Point) -> Hazard {
+        Hazard {
+            pos,
+            turns: kind.duration(),
+            kind,
+        }
+    }
+
+    /// A game on open deep water with the player out of the boat.
+    fn at_sea() -> LurhookGame {
+        let mut game = LurhookGame::default();
+        game.map.tiles.fill(TileKind::DeepWater);
+        game.player.aboard = false;
+        game
+    }
+
+    #[test]
+    fn sharks_chase_the_player_and_bite() {
+        let mut game = at_sea();
+        let start = game.player.pos;
+        game.hazards = vec![hazard(HazardKind::Shark, start + common::Point::new(3, 0))];
+        let hp = game.player.hp;
+        game.update_hazards();
+        assert_eq!(game.hazards[0].pos, start + common::Point::new(2, 0));
+        game.update_hazards();
+        game.update_hazards();
+        assert_eq!(game.hazards[0].pos, start);
+        assert_eq!(game.player.hp, hp - HazardKind::Shark.damage());
+        assert!(!game.player.has_effect(StatusKind::Poisoned));
+    }
+
+    #[test]
+    fn debris_rides_the_current() {
+        let mut game = at_sea();
+        for (i, depth) in game.map.depths.iter_mut().enumerate() {
+            let x = (i % game.map.width as usize) as i32;
+            *depth = x * x;
+        }
+        game.map.compute_flow();
+        let pos = game.player.pos + common::Point::new(-4, -4);
+        game.hazards = vec![hazard(HazardKind::Debris, pos)];
+        game.update_hazards();
+        assert_eq!(game.hazards[0].pos, game.map.drift(pos));
+        assert!(game.hazards[0].pos.x > pos.x);
+    }
+
+    #[test]
+    fn whirlpools_drag_the_player_unless_anchored() {
+        let mut game = at_sea();
+        let start = game.player.pos;
+        let pool = start + common::Point::new(2, 2);
+        game.hazards = vec![hazard(HazardKind::Whirlpool, pool)];
+        game.update_hazards();
+        assert_eq!(game.player.pos, start + common::Point::new(1, 1));
+
+        game.player.pos = start;
+        game.player.aboard = true;
+        game.player.boat.pos = start;
+        game.player.boat.anchored = true;
+        game.update_hazards();
+        assert_eq!(game.player.pos, start);
+    }
+}
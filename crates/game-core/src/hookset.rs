@@ -0,0 +1,223 @@
+//! Setting the hook: a fish that takes the bait gives the angler a few
+//! turns to strike with the cast key, and how well the strike is timed
+//! shapes the fight that follows.
+
+use super::*;
+use fishing::{HookTiming, HOOK_WINDOW};
+
+/// A fish holding the bait before the hook is set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Bite {
+    /// Turns since the fish took the bait.
+    pub turns: u8,
+    /// Whether the cast key was pressed to strike this turn.
+    pub struck: bool,
+}
+
+impl LurhookGame {
+    /// The fish at `idx` takes the bait and waits for the strike.
+    pub(crate) fn take_bait(&mut self, idx: usize) {
+        self.hooked = Some(idx);
+        if self.instant_hook {
+            self.set_hook(HookTiming::Perfect);
+            return;
+        }
+        self.mode = GameMode::Fishing {
+            wait: 0,
+            bite: Some(Bite {
+                turns: 0,
+                struck: false,
+            }),
+        };
+        self.events.push(GameEvent::FishBite);
+    }
+
+    /// Strikes at a biting fish; the hook is set on the next turn.
+    pub(crate) fn strike_bite(&mut self) {
+        if let GameMode::Fishing {
+            bite: Some(bite), ..
+        } = &mut self.mode
+        {
+            bite.struck = true;
+        }
+    }
+
+    /// Sets the hook if the player struck, or lets the fish drop the bait
+    /// once the window has passed.
+    pub(crate) fn update_bite(&mut self, bite: Bite) {
+        match HookTiming::after(bite.turns) {
+            Some(timing) if bite.struck => self.set_hook(timing),
+            _ if bite.turns + 1 >= HOOK_WINDOW => self.lose_bite(),
+            _ => {
+                self.mode = GameMode::Fishing {
+                    wait: 0,
+                    bite: Some(Bite {
+                        turns: bite.turns + 1,
+                        struck: false,
+                    }),
+                };
+            }
+        }
+    }
+
+    /// The biting fish gets away before the fight starts.
+    pub(crate) fn lose_bite(&mut self) {
+        self.adaptive.record(false);
+        self.events.push(GameEvent::FishEscaped {
+            id: self.hooked_id(),
+        });
+        self.hooked = None;
+        self.splash();
+        self.mode = GameMode::Exploring;
+        self.ui.set_layout(UILayout::Standard);
+    }
+
+    /// Starts the fight with the biting fish, adrenaline lending the
+    /// reel strength. A boss the rod cannot hold tears free at once.
+    fn set_hook(&mut self, timing: HookTiming) {
+        let Some(f) = self.hooked.and_then(|i| self.fishes.get(i)) else {
+            return;
+        };
+        if !self.rod_holds(&f.kind) {
+            let msg = format!(
+                "The {} is too strong for your rod and tears free!",
+                f.kind.name
+            );
+            self.ui.add_entry(LogCategory::Fishing, &msg).ok();
+            self.lose_bite();
+            return;
+        }
+        let boss = f.kind.boss.clone();
+        let strength = self.rules.scale_strength(f.kind.strength);
+        let (style, size) = (f.kind.fight_style, f.size_ratio());
+        self.apply_effect(StatusKind::Adrenaline);
+        let mut m = TensionMeter::new(strength, style, self.reel_strength(), size);
+        let distance = self.bait.map_or(0, |b| b.chebyshev(self.player.pos));
+        m.tension = fishing::cast_tension(distance);
+        m.max_tension += self.line_tension_bonus();
+        m.escape_threshold = self.rules.escape_threshold;
+        m.set_hook(timing);
+        if let Some(boss) = &boss {
+            m.set_boss(boss);
+        }
+        self.meter = Some(m);
+        self.mode = GameMode::Fishing {
+            wait: 0,
+            bite: None,
+        };
+        let msg = match timing {
+            HookTiming::Early => "You strike too early; the hook barely holds.",
+            HookTiming::Perfect => "A perfect hook set!",
+            HookTiming::Late => "You strike late; the fish felt the line.",
+        };
+        self.ui.add_entry(LogCategory::Fishing, msg).ok();
+        if boss.is_some() {
+            self.ui
+                .add_entry(
+                    LogCategory::Fishing,
+                    "A legendary fish! Brace for a long fight.",
+                )
+                .ok();
+        }
+        self.events.push(GameEvent::FishHooked);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_game;
+
+    /// A game where the fish next to the player has just taken the bait.
+    fn bitten() -> LurhookGame {
+        bitten_with(false)
+    }
+
+    fn bitten_with(instant_hook: bool) -> LurhookGame {
+        let mut game = memory_game(0);
+        game.instant_hook = instant_hook;
+        game.player.bait_bonus = 1.0;
+        let target = game.player.pos + common::Point::new(1, 0);
+        game.fishes.truncate(1);
+        game.fishes[0].position = target;
+        game.mode = GameMode::Aiming { target };
+        game.confirm_cast();
+        game.mode = GameMode::Fishing {
+            wait: 0,
+            bite: None,
+        };
+        game.update_fishing();
+        game
+    }
+
+    /// Presses the cast key `turns` turns after the bite.
+    fn strike_after(turns: u8) -> TensionMeter {
+        let mut game = bitten();
+        for _ in 0..turns {
+            game.update_fishing();
+        }
+        game.handle_input_key(Some(game.input.cast));
+        game.update_fishing();
+        assert_eq!(
+            game.mode,
+            GameMode::Fishing {
+                wait: 0,
+                bite: None
+            }
+        );
+        game.meter.take().expect("hooked")
+    }
+
+    #[test]
+    fn strike_timing_shapes_the_fight() {
+        let early = strike_after(0);
+        let perfect = strike_after(1);
+        let late = strike_after(2);
+        assert!(early.tension < perfect.tension && perfect.tension < late.tension);
+        assert!(early.alert && !perfect.alert && late.alert);
+    }
+
+    #[test]
+    fn the_fish_drops_the_bait_without_a_strike() {
+        let mut game = bitten();
+        assert_eq!(game.hooked, Some(0));
+        assert!(game.meter.is_none());
+        for _ in 0..HOOK_WINDOW {
+            assert!(matches!(game.mode, GameMode::Fishing { bite: Some(_), .. }));
+            game.update_fishing();
+        }
+        assert_eq!(game.mode, GameMode::Exploring);
+        assert!(game.hooked.is_none());
+        assert_eq!(game.fishes.len(), 1);
+        assert!(game
+            .events
+            .drain()
+            .any(|e| matches!(e, GameEvent::FishEscaped { .. })));
+    }
+
+    #[test]
+    fn older_replays_hook_every_bite_at_once() {
+        let game = bitten_with(true);
+        let meter = game.meter.as_ref().expect("hooked");
+        assert!(!meter.alert);
+        assert!(game.player.has_effect(StatusKind::Adrenaline));
+        assert_eq!(
+            game.mode,
+            GameMode::Fishing {
+                wait: 0,
+                bite: None
+            }
+        );
+    }
+
+    #[test]
+    fn bites_show_over_the_bobber() {
+        let mut game = bitten();
+        game.cast_path = None;
+        let mut buf = ui_crate::TextBuffer::new(80, 25);
+        game.render(&mut buf);
+        let bobber = game.camera().to_local(game.bait.unwrap());
+        assert_eq!(buf.glyph(bobber.x, bobber.y), Some('o'));
+        assert_eq!(buf.glyph(bobber.x, bobber.y - 1), Some('!'));
+    }
+}
@@ -0,0 +1,348 @@
+//! Local two-player mode taking turns on one shared map.
+
+use super::*;
+
+/// Turns a player takes before handing over to the other.
+pub(crate) const SHIFT_TURNS: u32 = 15;
+/// Shifts each player gets in a match.
+pub(crate) const SHIFTS_PER_PLAYER: u32 = 3;
+
+/// State a player keeps while the other one is at the keyboard.
+#[derive(Clone, Debug)]
+struct Seat {
+    player: Player,
+    metrics: RunMetrics,
+}
+
+/// Final numbers for one player.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct SeatResult {
+    pub score: i32,
+    pub catches: usize,
+    pub turns: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Phase {
+    /// Waiting for the next player to take the keyboard.
+    Handover,
+    Playing,
+    Finished,
+}
+
+/// Two players alternating fixed shifts in one game.
+///
+/// Map, fish, hazards and time of day are shared; each player's
+/// [`Player`] (position, stats and inventory) and metrics are swapped in
+/// and out of the game at every handover.
+pub struct Hotseat {
+    game: Box<LurhookGame>,
+    /// Index of the player currently in `game`.
+    active: usize,
+    /// The other player's state while they wait.
+    waiting: Seat,
+    /// Turns each player has played in finished shifts.
+    turns: [u32; 2],
+    /// Players out of the match: every shift used or the run ended early.
+    finished: [bool; 2],
+    /// Game turn at which the current shift began.
+    shift_start: u32,
+    phase: Phase,
+}
+
+impl Hotseat {
+    /// Starts a match where both players begin with the same gear and spot.
+    pub fn new(game: LurhookGame) -> Self {
+        let waiting = Seat {
+            player: game.player.clone(),
+            metrics: RunMetrics::default(),
+        };
+        Self {
+            shift_start: game.turn,
+            game: Box::new(game),
+            active: 0,
+            waiting,
+            turns: [0; 2],
+            finished: [false; 2],
+            phase: Phase::Handover,
+        }
+    }
+
+    /// Returns `true` once both players are done.
+    pub fn is_finished(&self) -> bool {
+        self.phase == Phase::Finished
+    }
+
+    /// The shared game with the active player's seat.
+    pub(crate) fn game(&self) -> &LurhookGame {
+        &self.game
+    }
+
+    pub(crate) fn game_mut(&mut self) -> &mut LurhookGame {
+        &mut self.game
+    }
+
+    fn shift_turns(&self) -> u32 {
+        self.game.turn - self.shift_start
+    }
+
+    /// Results for both players in seat order.
+    pub(crate) fn results(&self) -> [SeatResult; 2] {
+        let active = SeatResult {
+            score: self.game.score(),
+            catches: self.game.player.inventory.len(),
+            turns: self.turns[self.active] + self.shift_turns(),
+        };
+        let waiting = SeatResult {
+            score: catch_score(&self.waiting.player.inventory),
+            catches: self.waiting.player.inventory.len(),
+            turns: self.turns[1 - self.active],
+        };
+        if self.active == 0 {
+            [active, waiting]
+        } else {
+            [waiting, active]
+        }
+    }
+
+    /// Applies one frame of input for whichever screen is showing.
+    pub fn update(&mut self, ctx: &mut BTerm) {
+        match self.phase {
+            Phase::Handover => {
+                if ctx.key == Some(VirtualKeyCode::Return) {
+                    self.start_shift();
+                }
+            }
+            Phase::Playing => {
+                self.game.update(ctx);
+                self.check_shift();
+            }
+            Phase::Finished => {}
+        }
+    }
+
+    fn start_shift(&mut self) {
+        self.shift_start = self.game.turn;
+        self.phase = Phase::Playing;
+        self.game
+            .ui
+            .add_log(&format!(
+                "Player {}: {} turns this shift.",
+                self.active + 1,
+                SHIFT_TURNS
+            ))
+            .ok();
+    }
+
+    /// Ends the shift when its turns are used (outside a fight) or the run ends.
+    fn check_shift(&mut self) {
+        let ended = self.game.mode.is_over();
+        let used = self.shift_turns();
+        let total = self.turns[self.active] + used;
+        let idle = self.game.mode == GameMode::Exploring;
+        if ended || (idle && total >= SHIFT_TURNS * SHIFTS_PER_PLAYER) {
+            self.finished[self.active] = true;
+        } else if !(idle && used >= SHIFT_TURNS) {
+            return;
+        }
+        self.turns[self.active] = total;
+        self.shift_start = self.game.turn;
+        self.game.mode = GameMode::Exploring;
+        if self.finished[1 - self.active] {
+            if self.finished[self.active] {
+                self.phase = Phase::Finished;
+            }
+            // Otherwise nobody is left to hand over to: keep playing.
+            return;
+        }
+        self.swap_seats();
+        self.phase = Phase::Handover;
+    }
+
+    fn swap_seats(&mut self) {
+        std::mem::swap(&mut self.game.player, &mut self.waiting.player);
+        std::mem::swap(&mut self.game.metrics, &mut self.waiting.metrics);
+        self.active = 1 - self.active;
+        self.game.meter = None;
+        self.game.hooked = None;
+        self.game.throwing = None;
+        self.game.cast_path = None;
+        self.game.inventory_focus = false;
+    }
+
+    /// Draws the game, the handover screen or the final comparison.
+    pub fn render(&self, ctx: &mut dyn Renderer) {
+        match self.phase {
+            Phase::Handover => {
+                ctx.cls();
+                ctx.print_centered(9, &format!("Pass to Player {}", self.active + 1));
+                let results = self.results();
+                ctx.print_centered(
+                    11,
+                    &format!("Score  P1: {}  P2: {}", results[0].score, results[1].score),
+                );
+                let left =
+                    (SHIFT_TURNS * SHIFTS_PER_PLAYER).saturating_sub(self.turns[self.active]);
+                ctx.print_centered(12, &format!("Turns left: {}", left));
+                ctx.print_centered(14, "Press Enter to start your shift");
+            }
+            Phase::Playing => {
+                self.game.render(ctx);
+                let label = format!(
+                    " P{} {}/{} ",
+                    self.active + 1,
+                    self.shift_turns().min(SHIFT_TURNS),
+                    SHIFT_TURNS
+                );
+                ctx.print(self.game.view_panel().width - label.len() as i32, 0, &label);
+            }
+            Phase::Finished => {
+                ctx.cls();
+                for (i, line) in comparison_lines(&self.results()).iter().enumerate() {
+                    ctx.print_centered(7 + i as i32, line);
+                }
+                ctx.print_centered(20, "Press Enter for Menu, Q to Quit");
+            }
+        }
+    }
+}
+
+/// Summary rows comparing both players, ending with the winner.
+pub(crate) fn comparison_lines(results: &[SeatResult; 2]) -> Vec<String> {
+    let mut lines = vec![
+        "Hotseat Results".to_string(),
+        String::new(),
+        format!("{:<8}{:>10}{:>10}", "", "Player 1", "Player 2"),
+        format!(
+            "{:<8}{:>10}{:>10}",
+            "Score", results[0].score, results[1].score
+        ),
+        format!(
+            "{:<8}{:>10}{:>10}",
+            "Fish", results[0].catches, results[1].catches
+        ),
+        format!(
+            "{:<8}{:>10}{:>10}",
+            "Turns", results[0].turns, results[1].turns
+        ),
+        String::new(),
+    ];
+    lines.push(match results[0].score.cmp(&results[1].score) {
+        std::cmp::Ordering::Greater => "Player 1 wins!".to_string(),
+        std::cmp::Ordering::Less => "Player 2 wins!".to_string(),
+        std::cmp::Ordering::Equal => "It's a draw!".to_string(),
+    });
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_game;
+
+    fn hotseat() -> Hotseat {
+        let game = memory_game(0);
+        let mut hotseat = Hotseat::new(game);
+        hotseat.start_shift();
+        hotseat
+    }
+
+    fn play_turns(hotseat: &mut Hotseat, turns: u32) {
+        hotseat.game.turn += turns;
+        hotseat.check_shift();
+    }
+
+    #[test]
+    fn shift_hands_over_with_separate_inventories() {
+        let mut hotseat = hotseat();
+        let fish = hotseat.game.fish_types[0].clone();
+        hotseat.game.player.inventory.push(Catch::average(fish));
+        play_turns(&mut hotseat, SHIFT_TURNS - 1);
+        assert_eq!(hotseat.phase, Phase::Playing);
+        play_turns(&mut hotseat, 1);
+        assert_eq!(hotseat.phase, Phase::Handover);
+        assert_eq!(hotseat.active, 1);
+        assert!(hotseat.game.player.inventory.is_empty());
+        let results = hotseat.results();
+        assert!(results[0].score > 0);
+        assert_eq!(results[0].turns, SHIFT_TURNS);
+        assert_eq!(
+            results[1],
+            SeatResult {
+                score: 0,
+                catches: 0,
+                turns: 0
+            }
+        );
+    }
+
+    #[test]
+    fn shift_waits_for_fight_to_finish() {
+        let mut hotseat = hotseat();
+        hotseat.game.mode = GameMode::Fishing {
+            wait: 0,
+            bite: None,
+        };
+        play_turns(&mut hotseat, SHIFT_TURNS);
+        assert_eq!(hotseat.phase, Phase::Playing);
+        hotseat.game.mode = GameMode::Exploring;
+        play_turns(&mut hotseat, 1);
+        assert_eq!(hotseat.phase, Phase::Handover);
+    }
+
+    #[test]
+    fn match_finishes_after_all_shifts() {
+        let mut hotseat = hotseat();
+        for _ in 0..SHIFTS_PER_PLAYER * 2 {
+            assert!(!hotseat.is_finished());
+            hotseat.start_shift();
+            play_turns(&mut hotseat, SHIFT_TURNS);
+        }
+        assert!(hotseat.is_finished());
+        let results = hotseat.results();
+        assert_eq!(results[0].turns, SHIFT_TURNS * SHIFTS_PER_PLAYER);
+        assert_eq!(results[1].turns, SHIFT_TURNS * SHIFTS_PER_PLAYER);
+    }
+
+    #[test]
+    fn ending_run_early_leaves_other_player_alone() {
+        let mut hotseat = hotseat();
+        hotseat.game.end_run();
+        hotseat.check_shift();
+        assert_eq!(hotseat.active, 1);
+        hotseat.start_shift();
+        play_turns(&mut hotseat, SHIFT_TURNS);
+        // Player 1 is out, so player 2 keeps the keyboard.
+        assert_eq!(hotseat.phase, Phase::Playing);
+        assert_eq!(hotseat.active, 1);
+        hotseat.game.end_run();
+        hotseat.check_shift();
+        assert!(hotseat.is_finished());
+    }
+
+    #[test]
+    fn comparison_names_winner() {
+        let result = |score| SeatResult {
+            score,
+            catches: 1,
+            turns: 45,
+        };
+        let lines = comparison_lines(&[result(10), result(30)]);
+        assert!(lines[3].starts_with("Score"));
+        assert_eq!(lines.last().unwrap(), "Player 2 wins!");
+        let draw = comparison_lines(&[result(5), result(5)]);
+        assert_eq!(draw.last().unwrap(), "It's a draw!");
+    }
+
+    #[test]
+    fn renders_handover_and_results() {
+        let mut hotseat = hotseat();
+        play_turns(&mut hotseat, SHIFT_TURNS);
+        let mut buf = ui_crate::TextBuffer::new(80, 25);
+        hotseat.render(&mut buf);
+        assert!(buf.contains("Pass to Player 2"));
+        hotseat.phase = Phase::Finished;
+        hotseat.render(&mut buf);
+        assert!(buf.contains("Hotseat Results"));
+    }
+}
@@ -1,5 +1,6 @@
+use crate::DifficultyProfile;
 use bracket_lib::prelude::VirtualKeyCode;
-use common::GameResult;
+use common::{GameError, GameResult, Storage};
 
 /// Configuration for keyboard controls.
 #[derive(Clone, Debug)]
@@ -25,9 +26,58 @@ pub struct InputConfig {
     pub scroll_down: VirtualKeyCode,
     pub help: VirtualKeyCode,
     pub options: VirtualKeyCode,
+    /// Enters and leaves photo mode.
+    pub photo: VirtualKeyCode,
+    /// Boards the boat or steps off it.
+    pub board: VirtualKeyCode,
+    /// Drops or weighs the boat's anchor.
+    pub anchor: VirtualKeyCode,
+    /// Opens and closes the codex screen.
+    pub codex: VirtualKeyCode,
+    /// Aims a chum throw.
+    pub chum: VirtualKeyCode,
+    /// Shows and hides the minimap.
+    pub minimap: VirtualKeyCode,
+    /// Cycles the lure depth while aiming a cast.
+    pub lure_depth: VirtualKeyCode,
+    /// Builds a campfire from driftwood.
+    pub campfire: VirtualKeyCode,
+    /// Opens and closes the full message log.
+    pub message_log: VirtualKeyCode,
+    /// Opens and closes the travel journal.
+    pub journal: VirtualKeyCode,
+    /// Shows and hides the trail through the area.
+    pub trail: VirtualKeyCode,
+    /// Writes the whole map to a file.
+    pub export_map: VirtualKeyCode,
     pub colorblind: bool,
-    pub volume: u8,
+    /// Sound effects volume (0-10).
+    pub sfx_volume: u8,
+    /// Music volume (0-10), apart from the sound effects.
+    pub music_volume: u8,
+    /// Silences all sound, keeping both volumes.
+    pub muted: bool,
     pub font_scale: u8,
+    /// Enables rubber-banding of bite rates based on recent fights.
+    pub adaptive: bool,
+    /// Fishing plays out on a timer; `false` keeps it turn by turn, one
+    /// turn per key press.
+    pub realtime_fights: bool,
+    /// Selected map palette; `Default` or an unlocked reward palette.
+    pub palette: String,
+    /// Opens the graphical window fullscreen.
+    pub fullscreen: bool,
+    /// Synchronizes the graphical window with the display refresh rate.
+    pub vsync: bool,
+    /// Window size in pixels; `None` sizes the window from `font_scale`.
+    pub window_size: Option<(u32, u32)>,
+    /// Console size in cells from the `[display]` section; `None` opens an
+    /// 80x25 window and follows the terminal's size in text mode.
+    pub console_size: Option<(i32, i32)>,
+    /// Controller mapping from the `[gamepad]` section.
+    pub gamepad: PadConfig,
+    /// Custom difficulty from the `[difficulty]` section.
+    pub difficulty: Option<DifficultyProfile>,
 }
 
 impl Default for InputConfig {
@@ -55,194 +105,684 @@ impl Default for InputConfig {
             scroll_down: PageDown,
             help: F1,
             options: O,
+            photo: P,
+            board: T,
+            anchor: M,
+            codex: D,
+            chum: V,
+            minimap: Tab,
+            lure_depth: Z,
+            campfire: W,
+            message_log: Slash,
+            journal: E,
+            trail: A,
+            export_map: F2,
             colorblind: false,
-            volume: 5,
+            sfx_volume: 5,
+            music_volume: 5,
+            muted: false,
             font_scale: 1,
+            adaptive: false,
+            realtime_fights: true,
+            palette: "Default".to_string(),
+            fullscreen: false,
+            vsync: true,
+            window_size: None,
+            console_size: None,
+            gamepad: PadConfig::default(),
+            difficulty: None,
         }
     }
 }
 
+/// Declares the key-bound actions: [`ACTIONS`] and the lookups by name.
+macro_rules! bindings {
+    ($($action:ident),* $(,)?) => {
+        /// Actions that can be bound to a key, by their names in `lurhook.toml`.
+        pub(crate) const ACTIONS: &[&str] = &[$(stringify!($action)),*];
+
+        impl InputConfig {
+            /// Key bound to `action`.
+            pub(crate) fn binding(&self, action: &str) -> Option<VirtualKeyCode> {
+                match action {
+                    $(stringify!($action) => Some(self.$action),)*
+                    _ => None,
+                }
+            }
+
+            fn binding_mut(&mut self, action: &str) -> Option<&mut VirtualKeyCode> {
+                match action {
+                    $(stringify!($action) => Some(&mut self.$action),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+bindings!(
+    left,
+    right,
+    up,
+    down,
+    up_left,
+    up_right,
+    down_left,
+    down_right,
+    cast,
+    reel,
+    inventory,
+    eat,
+    cook,
+    snack,
+    save,
+    quit,
+    end_run,
+    scroll_up,
+    scroll_down,
+    help,
+    options,
+    photo,
+    board,
+    anchor,
+    codex,
+    chum,
+    minimap,
+    lure_depth,
+    campfire,
+    message_log,
+    journal,
+    trail,
+    export_map,
+);
+
 impl InputConfig {
-    /// Loads configuration from a file if it exists.
-    pub fn load(path: &str) -> GameResult<Self> {
+    /// Loads configuration stored under `key` if it exists.
+    pub fn load(storage: &dyn Storage, key: &str) -> GameResult<Self> {
         let mut cfg = Self::default();
-        let data = match std::fs::read_to_string(path) {
-            Ok(d) => d,
-            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(cfg),
-            Err(e) => return Err(e.into()),
+        let data = match storage.read_string(key)? {
+            Some(d) => d,
+            None => return Ok(cfg),
         };
+        let mut section = String::new();
+        let (mut columns, mut rows) = (None, None);
         for line in data.lines() {
             let line = line.trim();
             if line.is_empty() || line.starts_with('#') {
                 continue;
             }
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = name.trim().to_string();
+                continue;
+            }
             let (key, val) = match line.split_once('=') {
                 Some(v) => v,
                 None => continue,
             };
             let key = key.trim();
             let val = val.trim().trim_matches('"');
+            match section.as_str() {
+                "" => {}
+                "display" => {
+                    let cells = val.parse::<i32>().ok().filter(|&n| n > 0);
+                    match key {
+                        "columns" => columns = cells.or(columns),
+                        "rows" => rows = cells.or(rows),
+                        _ => {}
+                    }
+                    continue;
+                }
+                "gamepad" => {
+                    cfg.gamepad.set(key, val);
+                    continue;
+                }
+                "difficulty" => {
+                    cfg.difficulty
+                        .get_or_insert_with(Default::default)
+                        .set(key, val);
+                    continue;
+                }
+                _ => continue,
+            }
             if key == "colorblind" {
                 cfg.colorblind = val.parse().unwrap_or(false);
                 continue;
             }
-            if key == "volume" {
-                cfg.volume = val.parse().unwrap_or(cfg.volume);
+            // `volume` is the name older configs gave the effects volume
+            if key == "sfx_volume" || key == "volume" {
+                cfg.sfx_volume = val.parse().unwrap_or(cfg.sfx_volume);
+                continue;
+            }
+            if key == "muted" {
+                cfg.muted = val.parse().unwrap_or(false);
+                continue;
+            }
+            if key == "music_volume" {
+                cfg.music_volume = val.parse().unwrap_or(cfg.music_volume);
                 continue;
             }
             if key == "font_scale" {
                 cfg.font_scale = val.parse().unwrap_or(cfg.font_scale);
                 continue;
             }
-            if let Some(kc) = parse_key(val) {
-                match key {
-                    "left" => cfg.left = kc,
-                    "right" => cfg.right = kc,
-                    "up" => cfg.up = kc,
-                    "down" => cfg.down = kc,
-                    "up_left" => cfg.up_left = kc,
-                    "up_right" => cfg.up_right = kc,
-                    "down_left" => cfg.down_left = kc,
-                    "down_right" => cfg.down_right = kc,
-                    "cast" => cfg.cast = kc,
-                    "reel" => cfg.reel = kc,
-                    "inventory" => cfg.inventory = kc,
-                    "eat" => cfg.eat = kc,
-                    "cook" => cfg.cook = kc,
-                    "snack" => cfg.snack = kc,
-                    "save" => cfg.save = kc,
-                    "quit" => cfg.quit = kc,
-                    "end_run" => cfg.end_run = kc,
-                    "scroll_up" => cfg.scroll_up = kc,
-                    "scroll_down" => cfg.scroll_down = kc,
-                    "help" => cfg.help = kc,
-                    "options" => cfg.options = kc,
-                    _ => {}
+            if key == "adaptive" {
+                cfg.adaptive = val.parse().unwrap_or(false);
+                continue;
+            }
+            if key == "realtime_fights" {
+                cfg.realtime_fights = val.parse().unwrap_or(cfg.realtime_fights);
+                continue;
+            }
+            if key == "palette" {
+                cfg.palette = val.to_string();
+                continue;
+            }
+            if key == "fullscreen" {
+                cfg.fullscreen = val.parse().unwrap_or(false);
+                continue;
+            }
+            if key == "vsync" {
+                cfg.vsync = val.parse().unwrap_or(cfg.vsync);
+                continue;
+            }
+            if key == "window_size" {
+                cfg.window_size = parse_window_size(val);
+                continue;
+            }
+            if let (Some(kc), Some(slot)) = (parse_key(val), cfg.binding_mut(key)) {
+                if !is_reserved(kc) {
+                    *slot = kc;
                 }
             }
         }
+        if columns.is_some() || rows.is_some() {
+            cfg.console_size = Some((
+                columns.unwrap_or(crate::SCREEN_WIDTH),
+                rows.unwrap_or(crate::SCREEN_HEIGHT),
+            ));
+        }
         Ok(cfg)
     }
 
-    /// Saves the configuration to `path`.
-    pub fn save(&self, path: &str) -> GameResult<()> {
-        use std::io::Write;
-        let mut file = std::fs::File::create(path)?;
-        macro_rules! write_key {
-            ($key:expr, $name:expr) => {
-                writeln!(file, "{} = \"{}\"", $name, key_name($key))?;
-            };
+    /// Saves the configuration under `key`.
+    pub fn save(&self, storage: &dyn Storage, key: &str) -> GameResult<()> {
+        let mut out = String::new();
+        for &action in ACTIONS {
+            let key = self.binding(action).and_then(key_name).unwrap_or_default();
+            out.push_str(&format!("{} = \"{}\"\n", action, key));
+        }
+        out.push_str(&format!("colorblind = {}\n", self.colorblind));
+        out.push_str(&format!("sfx_volume = {}\n", self.sfx_volume));
+        out.push_str(&format!("music_volume = {}\n", self.music_volume));
+        out.push_str(&format!("muted = {}\n", self.muted));
+        out.push_str(&format!("font_scale = {}\n", self.font_scale));
+        out.push_str(&format!("adaptive = {}\n", self.adaptive));
+        out.push_str(&format!("realtime_fights = {}\n", self.realtime_fights));
+        out.push_str(&format!("palette = \"{}\"\n", self.palette));
+        out.push_str(&format!("fullscreen = {}\n", self.fullscreen));
+        out.push_str(&format!("vsync = {}\n", self.vsync));
+        out.push_str(&format!(
+            "window_size = \"{}\"\n",
+            window_size_name(self.window_size)
+        ));
+        if let Some((columns, rows)) = self.console_size {
+            out.push_str(&format!(
+                "\n[display]\ncolumns = {}\nrows = {}\n",
+                columns, rows
+            ));
+        }
+        out.push_str(&format!(
+            "\n[gamepad]\ndeadzone = {}\n",
+            self.gamepad.deadzone
+        ));
+        for (button, name) in PAD_BUTTON_NAMES {
+            let action = self.gamepad.action(button).unwrap_or_default();
+            out.push_str(&format!("{} = \"{}\"\n", name, action));
+        }
+        if let Some(difficulty) = &self.difficulty {
+            out.push_str(&difficulty.to_toml());
+        }
+        storage.write(key, out.as_bytes())
+    }
+
+    /// Binds `key` to `action`. An action already on `key` takes over
+    /// `action`'s old key, so no two actions share one; its name is
+    /// returned.
+    pub(crate) fn rebind(
+        &mut self,
+        action: &str,
+        key: VirtualKeyCode,
+    ) -> GameResult<Option<&'static str>> {
+        let Some(old) = self.binding(action) else {
+            return Err(GameError::Parse(format!("unknown action {}", action)));
+        };
+        if is_reserved(key) || key_name(key).is_none() {
+            return Err(GameError::Parse(format!("{:?} cannot be bound", key)));
+        }
+        let other = ACTIONS
+            .iter()
+            .copied()
+            .find(|&a| a != action && self.binding(a) == Some(key));
+        if let Some(slot) = other.and_then(|a| self.binding_mut(a)) {
+            *slot = old;
         }
-        write_key!(self.left, "left");
-        write_key!(self.right, "right");
-        write_key!(self.up, "up");
-        write_key!(self.down, "down");
-        write_key!(self.up_left, "up_left");
-        write_key!(self.up_right, "up_right");
-        write_key!(self.down_left, "down_left");
-        write_key!(self.down_right, "down_right");
-        write_key!(self.cast, "cast");
-        write_key!(self.reel, "reel");
-        write_key!(self.inventory, "inventory");
-        write_key!(self.eat, "eat");
-        write_key!(self.cook, "cook");
-        write_key!(self.snack, "snack");
-        write_key!(self.save, "save");
-        write_key!(self.quit, "quit");
-        write_key!(self.end_run, "end_run");
-        write_key!(self.scroll_up, "scroll_up");
-        write_key!(self.scroll_down, "scroll_down");
-        write_key!(self.help, "help");
-        write_key!(self.options, "options");
-        writeln!(file, "colorblind = {}", self.colorblind)?;
-        writeln!(file, "volume = {}", self.volume)?;
-        writeln!(file, "font_scale = {}", self.font_scale)?;
-        Ok(())
+        if let Some(slot) = self.binding_mut(action) {
+            *slot = key;
+        }
+        Ok(other)
+    }
+
+    /// Whether another action is bound to the same key as `action`.
+    pub(crate) fn conflicts(&self, action: &str) -> bool {
+        let key = self.binding(action);
+        key.is_some()
+            && ACTIONS
+                .iter()
+                .any(|&a| a != action && self.binding(a) == key)
     }
 }
 
-fn parse_key(name: &str) -> Option<VirtualKeyCode> {
-    use VirtualKeyCode::*;
-    match name.to_ascii_lowercase().as_str() {
-        "left" => Some(Left),
-        "right" => Some(Right),
-        "up" => Some(Up),
-        "down" => Some(Down),
-        "y" => Some(Y),
-        "u" => Some(U),
-        "h" => Some(H),
-        "j" => Some(J),
-        "k" => Some(K),
-        "l" => Some(L),
-        "b" => Some(B),
-        "n" => Some(N),
-        "c" => Some(C),
-        "f" => Some(F),
-        "g" => Some(G),
-        "x" => Some(X),
-        "e" => Some(E),
-        "r" => Some(R),
-        "i" => Some(I),
-        "s" => Some(S),
-        "q" => Some(Q),
-        "return" => Some(Return),
-        "pageup" => Some(PageUp),
-        "pagedown" => Some(PageDown),
-        "f1" => Some(F1),
-        "plus" => Some(Plus),
-        "minus" => Some(Minus),
-        "lbracket" => Some(LBracket),
-        "rbracket" => Some(RBracket),
-        "o" => Some(O),
-        _ => None,
+/// Parses a `WIDTHxHEIGHT` window size; anything else means automatic.
+fn parse_window_size(val: &str) -> Option<(u32, u32)> {
+    let (w, h) = val.split_once('x')?;
+    let size = (w.trim().parse().ok()?, h.trim().parse().ok()?);
+    (size.0 > 0 && size.1 > 0).then_some(size)
+}
+
+/// Formats a window size the way [`parse_window_size`] reads it.
+pub(crate) fn window_size_name(size: Option<(u32, u32)>) -> String {
+    match size {
+        Some((w, h)) => format!("{}x{}", w, h),
+        None => "Auto".to_string(),
     }
 }
 
-fn key_name(key: VirtualKeyCode) -> &'static str {
+/// Keys that can be bound or recorded, with their names in `lurhook.toml`:
+/// every key a keyboard sends, digits by their number.
+const KEY_NAMES: [(VirtualKeyCode, &str); 108] = {
     use VirtualKeyCode::*;
-    match key {
-        Left => "Left",
-        Right => "Right",
-        Up => "Up",
-        Down => "Down",
-        Y => "Y",
-        U => "U",
-        H => "H",
-        J => "J",
-        K => "K",
-        L => "L",
-        B => "B",
-        N => "N",
-        C => "C",
-        F => "F",
-        G => "G",
-        X => "X",
-        E => "E",
-        R => "R",
-        I => "I",
-        S => "S",
-        Q => "Q",
-        Return => "Return",
-        PageUp => "PageUp",
-        PageDown => "PageDown",
-        Plus => "Plus",
-        Minus => "Minus",
-        F1 => "F1",
-        O => "O",
-        LBracket => "LBracket",
-        RBracket => "RBracket",
-        other => panic!("unsupported key {:?}", other),
+    [
+        (Left, "Left"),
+        (Right, "Right"),
+        (Up, "Up"),
+        (Down, "Down"),
+        (A, "A"),
+        (B, "B"),
+        (C, "C"),
+        (D, "D"),
+        (E, "E"),
+        (F, "F"),
+        (G, "G"),
+        (H, "H"),
+        (I, "I"),
+        (J, "J"),
+        (K, "K"),
+        (L, "L"),
+        (M, "M"),
+        (N, "N"),
+        (O, "O"),
+        (P, "P"),
+        (Q, "Q"),
+        (R, "R"),
+        (S, "S"),
+        (T, "T"),
+        (U, "U"),
+        (V, "V"),
+        (W, "W"),
+        (X, "X"),
+        (Y, "Y"),
+        (Z, "Z"),
+        (Key0, "0"),
+        (Key1, "1"),
+        (Key2, "2"),
+        (Key3, "3"),
+        (Key4, "4"),
+        (Key5, "5"),
+        (Key6, "6"),
+        (Key7, "7"),
+        (Key8, "8"),
+        (Key9, "9"),
+        (Return, "Return"),
+        (Escape, "Escape"),
+        (Space, "Space"),
+        (PageUp, "PageUp"),
+        (PageDown, "PageDown"),
+        (F1, "F1"),
+        (Plus, "Plus"),
+        (Minus, "Minus"),
+        (LBracket, "LBracket"),
+        (RBracket, "RBracket"),
+        (Tab, "Tab"),
+        (Back, "Back"),
+        (F2, "F2"),
+        (F3, "F3"),
+        (F4, "F4"),
+        (F5, "F5"),
+        (F6, "F6"),
+        (F7, "F7"),
+        (F8, "F8"),
+        (F9, "F9"),
+        (F10, "F10"),
+        (F11, "F11"),
+        (F12, "F12"),
+        (Insert, "Insert"),
+        (Delete, "Delete"),
+        (Home, "Home"),
+        (End, "End"),
+        (Snapshot, "Snapshot"),
+        (Scroll, "Scroll"),
+        (Pause, "Pause"),
+        (Compose, "Compose"),
+        (Caret, "Caret"),
+        (Numlock, "Numlock"),
+        (Numpad0, "Numpad0"),
+        (Numpad1, "Numpad1"),
+        (Numpad2, "Numpad2"),
+        (Numpad3, "Numpad3"),
+        (Numpad4, "Numpad4"),
+        (Numpad5, "Numpad5"),
+        (Numpad6, "Numpad6"),
+        (Numpad7, "Numpad7"),
+        (Numpad8, "Numpad8"),
+        (Numpad9, "Numpad9"),
+        (NumpadAdd, "NumpadAdd"),
+        (NumpadSubtract, "NumpadSubtract"),
+        (NumpadMultiply, "NumpadMultiply"),
+        (NumpadDivide, "NumpadDivide"),
+        (NumpadDecimal, "NumpadDecimal"),
+        (NumpadComma, "NumpadComma"),
+        (NumpadEnter, "NumpadEnter"),
+        (NumpadEquals, "NumpadEquals"),
+        (Apostrophe, "Apostrophe"),
+        (Asterisk, "Asterisk"),
+        (At, "At"),
+        (Backslash, "Backslash"),
+        (Colon, "Colon"),
+        (Comma, "Comma"),
+        (Equals, "Equals"),
+        (Grave, "Grave"),
+        (Period, "Period"),
+        (Semicolon, "Semicolon"),
+        (Slash, "Slash"),
+        (LShift, "LShift"),
+        (RShift, "RShift"),
+        (LControl, "LControl"),
+        (RControl, "RControl"),
+        (LAlt, "LAlt"),
+        (RAlt, "RAlt"),
+    ]
+};
+
+/// Keys that are never bound: Escape backs out of screens, and replays
+/// play F12 back for keys without a name.
+const RESERVED_KEYS: [VirtualKeyCode; 2] = [VirtualKeyCode::Escape, VirtualKeyCode::F12];
+
+/// Whether `key` is kept out of the bindings.
+pub(crate) fn is_reserved(key: VirtualKeyCode) -> bool {
+    RESERVED_KEYS.contains(&key)
+}
+
+/// Key with the (case-insensitive) `name`.
+pub(crate) fn parse_key(name: &str) -> Option<VirtualKeyCode> {
+    KEY_NAMES
+        .iter()
+        .find(|(_, n)| n.eq_ignore_ascii_case(name))
+        .map(|(key, _)| *key)
+}
+
+/// Name of `key`, or `None` for keys that cannot be bound.
+pub(crate) fn key_name(key: VirtualKeyCode) -> Option<&'static str> {
+    KEY_NAMES
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, name)| *name)
+}
+
+/// Mouse buttons beyond the left click `BTerm` reports, filled in by the
+/// backend each frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MouseButtons {
+    /// The left button is down.
+    pub left_held: bool,
+    /// The right button went down this frame.
+    pub right_click: bool,
+}
+
+/// Controller buttons, by their names in the `[gamepad]` section.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PadButton {
+    /// Bottom face button (A on Xbox pads, cross on PlayStation).
+    South,
+    East,
+    West,
+    North,
+    LeftBumper,
+    RightBumper,
+    LeftTrigger,
+    RightTrigger,
+    Select,
+    Start,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+const PAD_BUTTON_NAMES: [(PadButton, &str); 14] = {
+    use PadButton::*;
+    [
+        (South, "south"),
+        (East, "east"),
+        (West, "west"),
+        (North, "north"),
+        (LeftBumper, "left_bumper"),
+        (RightBumper, "right_bumper"),
+        (LeftTrigger, "left_trigger"),
+        (RightTrigger, "right_trigger"),
+        (Select, "select"),
+        (Start, "start"),
+        (DPadUp, "dpad_up"),
+        (DPadDown, "dpad_down"),
+        (DPadLeft, "dpad_left"),
+        (DPadRight, "dpad_right"),
+    ]
+};
+
+/// Controller input read by a [`Gamepad`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PadEvent {
+    Pressed(PadButton),
+    Released(PadButton),
+    /// The movement stick is at `(x, y)`, each in `-1.0..=1.0` with y up.
+    Stick(f32, f32),
+    /// The controller went away; nothing is held any more.
+    Disconnected,
+}
+
+/// A source of controller input, such as the gilrs backend.
+pub trait Gamepad {
+    /// Events since the last poll, oldest first.
+    fn poll(&mut self) -> Vec<PadEvent>;
+}
+
+/// Which action each controller button triggers.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PadConfig {
+    /// Button bindings by action name; unlisted buttons do nothing.
+    pub buttons: Vec<(PadButton, &'static str)>,
+    /// Stick deflection, from 0 to 1, below which it counts as centred.
+    pub deadzone: f32,
+}
+
+impl Default for PadConfig {
+    fn default() -> Self {
+        use PadButton::*;
+        Self {
+            buttons: vec![
+                (South, "cast"),
+                (East, "inventory"),
+                (West, "chum"),
+                (North, "board"),
+                (LeftBumper, "scroll_up"),
+                (RightBumper, "scroll_down"),
+                (LeftTrigger, "anchor"),
+                (RightTrigger, "reel"),
+                (Select, "minimap"),
+                (Start, "options"),
+                (DPadUp, "up"),
+                (DPadDown, "down"),
+                (DPadLeft, "left"),
+                (DPadRight, "right"),
+            ],
+            deadzone: 0.5,
+        }
+    }
+}
+
+impl PadConfig {
+    /// Action bound to `button`.
+    pub fn action(&self, button: PadButton) -> Option<&'static str> {
+        self.buttons
+            .iter()
+            .find(|(b, _)| *b == button)
+            .map(|(_, action)| *action)
+    }
+
+    /// Applies one `[gamepad]` line: `deadzone`, or a button and the action
+    /// it triggers (empty to leave it unbound). Unknown names are ignored.
+    fn set(&mut self, key: &str, val: &str) {
+        if key == "deadzone" {
+            if let Ok(deadzone) = val.parse::<f32>() {
+                self.deadzone = deadzone.clamp(0.0, 1.0);
+            }
+            return;
+        }
+        let Some(&(button, _)) = PAD_BUTTON_NAMES.iter().find(|(_, n)| *n == key) else {
+            return;
+        };
+        let action = ACTIONS.iter().copied().find(|a| *a == val);
+        if action.is_none() && !val.is_empty() {
+            return;
+        }
+        self.buttons.retain(|(b, _)| *b != button);
+        if let Some(action) = action {
+            self.buttons.push((button, action));
+        }
+    }
+
+    /// Movement action for the stick at `(x, y)`: one of eight directions,
+    /// or `None` inside the deadzone.
+    fn stick_action(&self, x: f32, y: f32) -> Option<&'static str> {
+        const DIRECTIONS: [&str; 8] = [
+            "right",
+            "up_right",
+            "up",
+            "up_left",
+            "left",
+            "down_left",
+            "down",
+            "down_right",
+        ];
+        if x.hypot(y) < self.deadzone.max(f32::EPSILON) {
+            return None;
+        }
+        let sector = (y.atan2(x) / std::f32::consts::FRAC_PI_4).round() as i32;
+        Some(DIRECTIONS[sector.rem_euclid(8) as usize])
+    }
+}
+
+/// Delay before a held button, stick or mouse button repeats its action.
+pub(crate) const REPEAT_DELAY_MS: f32 = 300.0;
+/// Time between repeats after that; holding the reel trigger or the left
+/// mouse button keeps reeling at this pace.
+pub(crate) const REPEAT_MS: f32 = 120.0;
+
+/// Turns controller events into the keys bound to their actions, so a
+/// controller drives the game like a keyboard.
+pub struct PadInput {
+    pad: Box<dyn Gamepad>,
+    /// Buttons held down that trigger an action, latest last.
+    held: Vec<PadButton>,
+    /// Direction the stick points in, outside the deadzone.
+    stick: Option<&'static str>,
+    /// Milliseconds until the held button or stick repeats.
+    repeat_in: f32,
+}
+
+impl PadInput {
+    pub fn new(pad: Box<dyn Gamepad>) -> Self {
+        Self {
+            pad,
+            held: Vec::new(),
+            stick: None,
+            repeat_in: 0.0,
+        }
+    }
+
+    /// Key for this frame under `input`'s bindings: a button just pressed
+    /// or the stick just tilted, else a repeat of what is held.
+    pub(crate) fn key(
+        &mut self,
+        input: &InputConfig,
+        frame_time_ms: f32,
+    ) -> Option<VirtualKeyCode> {
+        let config = &input.gamepad;
+        let mut fresh = None;
+        for event in self.pad.poll() {
+            match event {
+                PadEvent::Pressed(button) => {
+                    if let Some(action) = config.action(button) {
+                        self.held.retain(|&b| b != button);
+                        self.held.push(button);
+                        fresh = Some(action);
+                    }
+                }
+                PadEvent::Released(button) => self.held.retain(|&b| b != button),
+                PadEvent::Stick(x, y) => {
+                    let direction = config.stick_action(x, y);
+                    if direction != self.stick {
+                        self.stick = direction;
+                        fresh = direction.or(fresh);
+                    }
+                }
+                PadEvent::Disconnected => {
+                    self.held.clear();
+                    self.stick = None;
+                }
+            }
+        }
+        let action = match fresh {
+            Some(action) => {
+                self.repeat_in = REPEAT_DELAY_MS;
+                action
+            }
+            None => {
+                let held = self
+                    .held
+                    .last()
+                    .and_then(|&b| config.action(b))
+                    .or(self.stick)?;
+                self.repeat_in -= frame_time_ms;
+                if self.repeat_in > 0.0 {
+                    return None;
+                }
+                self.repeat_in += REPEAT_MS;
+                held
+            }
+        };
+        input.binding(action)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::{FileStorage, MemoryStorage};
     use std::io::Write;
 
     #[test]
     fn load_nonexistent_returns_default() {
-        let cfg = InputConfig::load("/no/such/file.toml").unwrap();
+        let cfg = InputConfig::load(&FileStorage::default(), "/no/such/file.toml").unwrap();
         assert_eq!(cfg.cast, VirtualKeyCode::C);
         assert_eq!(cfg.eat, VirtualKeyCode::X);
         assert_eq!(cfg.cook, VirtualKeyCode::F);
@@ -250,7 +790,7 @@ mod tests {
         assert_eq!(cfg.help, VirtualKeyCode::F1);
         assert_eq!(cfg.options, VirtualKeyCode::O);
         assert!(!cfg.colorblind);
-        assert_eq!(cfg.volume, 5);
+        assert_eq!(cfg.sfx_volume, 5);
         assert_eq!(cfg.font_scale, 1);
     }
 
@@ -264,14 +804,16 @@ mod tests {
         writeln!(file, "cook = \"G\"").unwrap();
         writeln!(file, "snack = \"H\"").unwrap();
         writeln!(file, "volume = 7").unwrap();
-        let cfg = InputConfig::load(path.to_str().unwrap()).unwrap();
+        writeln!(file, "music_volume = 2").unwrap();
+        let cfg = InputConfig::load(&FileStorage::default(), path.to_str().unwrap()).unwrap();
         std::fs::remove_file(path).unwrap();
         assert_eq!(cfg.cast, VirtualKeyCode::X);
         assert_eq!(cfg.eat, VirtualKeyCode::E);
         assert_eq!(cfg.cook, VirtualKeyCode::G);
         assert_eq!(cfg.snack, VirtualKeyCode::H);
         assert!(!cfg.colorblind);
-        assert_eq!(cfg.volume, 7);
+        assert_eq!(cfg.sfx_volume, 7);
+        assert_eq!(cfg.music_volume, 2);
         assert_eq!(cfg.font_scale, 1);
     }
 
@@ -282,10 +824,10 @@ mod tests {
         let mut file = std::fs::File::create(&path).unwrap();
         writeln!(file, "colorblind = true").unwrap();
         writeln!(file, "volume = 3").unwrap();
-        let cfg = InputConfig::load(path.to_str().unwrap()).unwrap();
+        let cfg = InputConfig::load(&FileStorage::default(), path.to_str().unwrap()).unwrap();
         std::fs::remove_file(path).unwrap();
         assert!(cfg.colorblind);
-        assert_eq!(cfg.volume, 3);
+        assert_eq!(cfg.sfx_volume, 3);
     }
 
     #[test]
@@ -294,7 +836,7 @@ mod tests {
         path.push("test_help.toml");
         let mut file = std::fs::File::create(&path).unwrap();
         writeln!(file, "help = \"F1\"").unwrap();
-        let cfg = InputConfig::load(path.to_str().unwrap()).unwrap();
+        let cfg = InputConfig::load(&FileStorage::default(), path.to_str().unwrap()).unwrap();
         std::fs::remove_file(path).unwrap();
         assert_eq!(cfg.help, VirtualKeyCode::F1);
     }
@@ -305,7 +847,7 @@ mod tests {
         path.push("test_options.toml");
         let mut file = std::fs::File::create(&path).unwrap();
         writeln!(file, "options = \"O\"").unwrap();
-        let cfg = InputConfig::load(path.to_str().unwrap()).unwrap();
+        let cfg = InputConfig::load(&FileStorage::default(), path.to_str().unwrap()).unwrap();
         std::fs::remove_file(path).unwrap();
         assert_eq!(cfg.options, VirtualKeyCode::O);
     }
@@ -313,14 +855,204 @@ mod tests {
     #[test]
     fn save_round_trip() {
         let cfg = InputConfig::default();
-        let mut path = std::env::temp_dir();
-        path.push("test_save_round_trip.toml");
-        cfg.save(path.to_str().unwrap()).unwrap();
-        let loaded = InputConfig::load(path.to_str().unwrap()).unwrap();
-        std::fs::remove_file(path).unwrap();
+        let storage = MemoryStorage::default();
+        cfg.save(&storage, "lurhook.toml").unwrap();
+        let loaded = InputConfig::load(&storage, "lurhook.toml").unwrap();
         assert_eq!(loaded.left, cfg.left);
+        assert_eq!(loaded.minimap, VirtualKeyCode::Tab);
         assert_eq!(loaded.colorblind, cfg.colorblind);
-        assert_eq!(loaded.volume, cfg.volume);
+        assert_eq!(loaded.sfx_volume, cfg.sfx_volume);
         assert_eq!(loaded.font_scale, cfg.font_scale);
+        assert_eq!(loaded.adaptive, cfg.adaptive);
+        assert_eq!(loaded.palette, cfg.palette);
+        assert!(loaded.realtime_fights);
+
+        storage
+            .write("lurhook.toml", b"realtime_fights = false\n")
+            .unwrap();
+        let loaded = InputConfig::load(&storage, "lurhook.toml").unwrap();
+        assert!(!loaded.realtime_fights);
+    }
+
+    #[test]
+    fn load_adaptive_flag() {
+        let mut path = std::env::temp_dir();
+        path.push("test_input_adaptive.toml");
+        let mut file = std::fs::File::create(&path).unwrap();
+        writeln!(file, "adaptive = true").unwrap();
+        let cfg = InputConfig::load(&FileStorage::default(), path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert!(cfg.adaptive);
+    }
+
+    #[test]
+    fn load_palette_name() {
+        let storage = MemoryStorage::default();
+        storage
+            .write("lurhook.toml", b"palette = \"Abyss\"\n")
+            .unwrap();
+        let cfg = InputConfig::load(&storage, "lurhook.toml").unwrap();
+        assert_eq!(cfg.palette, "Abyss");
+    }
+
+    #[test]
+    fn window_options_round_trip() {
+        let storage = MemoryStorage::default();
+        let cfg = InputConfig {
+            fullscreen: true,
+            vsync: false,
+            window_size: Some((1280, 400)),
+            ..InputConfig::default()
+        };
+        cfg.save(&storage, "lurhook.toml").unwrap();
+        let loaded = InputConfig::load(&storage, "lurhook.toml").unwrap();
+        assert!(loaded.fullscreen);
+        assert!(!loaded.vsync);
+        assert_eq!(loaded.window_size, Some((1280, 400)));
+    }
+
+    #[test]
+    fn display_section_sets_the_console_size() {
+        let storage = MemoryStorage::default();
+        let cfg = InputConfig {
+            console_size: Some((132, 43)),
+            ..InputConfig::default()
+        };
+        cfg.save(&storage, "lurhook.toml").unwrap();
+        let loaded = InputConfig::load(&storage, "lurhook.toml").unwrap();
+        assert_eq!(loaded.console_size, Some((132, 43)));
+        assert_eq!(loaded.cast, cfg.cast);
+
+        storage
+            .write(
+                "lurhook.toml",
+                b"volume = 3\n[display]\nrows = 50\nvolume = 9\n",
+            )
+            .unwrap();
+        let loaded = InputConfig::load(&storage, "lurhook.toml").unwrap();
+        assert_eq!(loaded.console_size, Some((80, 50)));
+        assert_eq!(loaded.sfx_volume, 3);
+        let loaded = InputConfig::load(&MemoryStorage::default(), "lurhook.toml").unwrap();
+        assert_eq!(loaded.console_size, None);
+    }
+
+    #[test]
+    fn every_key_name_round_trips() {
+        for (key, name) in KEY_NAMES {
+            assert_eq!(key_name(key), Some(name));
+            assert_eq!(parse_key(&name.to_lowercase()), Some(key));
+        }
+        assert_eq!(key_name(VirtualKeyCode::NumpadEnter), Some("NumpadEnter"));
+        assert_eq!(parse_key("Hyper"), None);
+    }
+
+    #[test]
+    fn rebind_swaps_taken_keys() {
+        let mut cfg = InputConfig::default();
+        assert_eq!(cfg.rebind("eat", VirtualKeyCode::C).unwrap(), Some("cast"));
+        assert_eq!((cfg.eat, cfg.cast), (VirtualKeyCode::C, VirtualKeyCode::X));
+        assert_eq!(cfg.rebind("eat", VirtualKeyCode::F9).unwrap(), None);
+        assert!(cfg.rebind("eat", VirtualKeyCode::Escape).is_err());
+        assert!(cfg.rebind("dance", VirtualKeyCode::F8).is_err());
+        assert!(ACTIONS.iter().all(|a| !cfg.conflicts(a)));
+        cfg.help = cfg.cast;
+        assert!(cfg.conflicts("help") && cfg.conflicts("cast"));
+    }
+
+    #[test]
+    fn reserved_keys_are_not_loaded() {
+        let storage = MemoryStorage::default();
+        storage
+            .write("lurhook.toml", b"quit = \"Escape\"\nsave = \"F2\"\n")
+            .unwrap();
+        let cfg = InputConfig::load(&storage, "lurhook.toml").unwrap();
+        assert_eq!(cfg.quit, VirtualKeyCode::Q);
+        assert_eq!(cfg.save, VirtualKeyCode::F2);
+    }
+
+    /// Controller replaying queued events, one batch per poll.
+    struct FakePad(std::rc::Rc<std::cell::RefCell<Vec<Vec<PadEvent>>>>);
+
+    impl Gamepad for FakePad {
+        fn poll(&mut self) -> Vec<PadEvent> {
+            self.0.borrow_mut().pop().unwrap_or_default()
+        }
+    }
+
+    fn fake_pad() -> (
+        PadInput,
+        std::rc::Rc<std::cell::RefCell<Vec<Vec<PadEvent>>>>,
+    ) {
+        let queue = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        (PadInput::new(Box::new(FakePad(queue.clone()))), queue)
+    }
+
+    #[test]
+    fn stick_moves_outside_the_deadzone() {
+        let cfg = InputConfig::default();
+        let (mut pad, queue) = fake_pad();
+        queue.borrow_mut().push(vec![PadEvent::Stick(0.2, 0.3)]);
+        assert_eq!(pad.key(&cfg, 16.0), None);
+        queue.borrow_mut().push(vec![PadEvent::Stick(0.7, 0.7)]);
+        assert_eq!(pad.key(&cfg, 16.0), Some(cfg.up_right));
+        queue.borrow_mut().push(vec![PadEvent::Stick(-0.1, -0.9)]);
+        assert_eq!(pad.key(&cfg, 16.0), Some(cfg.down));
+        queue.borrow_mut().push(vec![PadEvent::Stick(0.0, 0.0)]);
+        assert_eq!(pad.key(&cfg, 1000.0), None);
+    }
+
+    #[test]
+    fn held_trigger_keeps_reeling_until_released() {
+        let cfg = InputConfig::default();
+        let (mut pad, queue) = fake_pad();
+        queue
+            .borrow_mut()
+            .push(vec![PadEvent::Pressed(PadButton::RightTrigger)]);
+        assert_eq!(pad.key(&cfg, 16.0), Some(cfg.reel));
+        assert_eq!(pad.key(&cfg, REPEAT_DELAY_MS - 1.0), None);
+        assert_eq!(pad.key(&cfg, 1.0), Some(cfg.reel));
+        assert_eq!(pad.key(&cfg, REPEAT_MS), Some(cfg.reel));
+        queue
+            .borrow_mut()
+            .push(vec![PadEvent::Released(PadButton::RightTrigger)]);
+        assert_eq!(pad.key(&cfg, 1000.0), None);
+
+        queue.borrow_mut().push(vec![
+            PadEvent::Pressed(PadButton::South),
+            PadEvent::Disconnected,
+        ]);
+        assert_eq!(pad.key(&cfg, 16.0), Some(cfg.cast));
+        assert_eq!(pad.key(&cfg, 1000.0), None);
+    }
+
+    #[test]
+    fn gamepad_section_remaps_buttons() {
+        let storage = MemoryStorage::default();
+        storage
+            .write(
+                "lurhook.toml",
+                b"[gamepad]\ndeadzone = 0.25\nsouth = \"reel\"\nright_trigger = \"\"\nnorth = \"dance\"\n",
+            )
+            .unwrap();
+        let cfg = InputConfig::load(&storage, "lurhook.toml").unwrap();
+        assert_eq!(cfg.gamepad.deadzone, 0.25);
+        assert_eq!(cfg.gamepad.action(PadButton::South), Some("reel"));
+        assert_eq!(cfg.gamepad.action(PadButton::RightTrigger), None);
+        assert_eq!(cfg.gamepad.action(PadButton::North), Some("board"));
+
+        cfg.save(&storage, "lurhook.toml").unwrap();
+        let loaded = InputConfig::load(&storage, "lurhook.toml").unwrap();
+        assert_eq!(loaded.gamepad.deadzone, 0.25);
+        for (button, _) in PAD_BUTTON_NAMES {
+            assert_eq!(loaded.gamepad.action(button), cfg.gamepad.action(button));
+        }
+    }
+
+    #[test]
+    fn invalid_window_size_means_auto() {
+        assert_eq!(parse_window_size("800x0"), None);
+        assert_eq!(parse_window_size("Auto"), None);
+        assert_eq!(parse_window_size("960 x 300"), Some((960, 300)));
+        assert_eq!(window_size_name(None), "Auto");
     }
 }
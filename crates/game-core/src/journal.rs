@@ -0,0 +1,314 @@
+//! Travel journal: the path the angler took over the run, kept as runs of
+//! turns spent on the same tile, the distance it covers and a diary of
+//! each day (fish caught, storms weathered, places found). The journal
+//! screen shows the diary, the map can show the trail through the current
+//! area, and the app writes the diary out as text when the run ends.
+
+use super::*;
+use seasons::DAY_TURNS;
+use serde::{Deserialize, Serialize};
+
+/// Key the journal is written to when a run ends.
+pub(crate) const JOURNAL_PATH: &str = "journal.txt";
+
+/// Where the angler has been, one entry per stretch of turns on a tile.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct Trail {
+    /// `(x, y, turns)`: a position counted from the corner of the area's
+    /// first map, held for `turns` turns.
+    pub steps: Vec<(i32, i32, u32)>,
+    /// Index in `steps` where the current area begins.
+    pub area_start: usize,
+    /// Tiles moved over the whole run.
+    pub distance: u32,
+}
+
+impl Trail {
+    /// Records the end of a turn at `pos`; returns the tiles moved since
+    /// the last one.
+    fn record(&mut self, pos: common::Point) -> u32 {
+        let in_area = self.steps.len() > self.area_start;
+        let moved = match self.steps.last_mut() {
+            Some((x, y, turns)) if (*x, *y) == (pos.x, pos.y) => {
+                *turns += 1;
+                return 0;
+            }
+            Some(&mut (x, y, _)) if in_area => pos.chebyshev(common::Point::new(x, y)) as u32,
+            _ => 0,
+        };
+        self.steps.push((pos.x, pos.y, 1));
+        self.distance += moved;
+        moved
+    }
+
+    /// Positions visited in the current area.
+    pub fn here(&self) -> impl Iterator<Item = common::Point> + '_ {
+        self.steps[self.area_start.min(self.steps.len())..]
+            .iter()
+            .map(|&(x, y, _)| common::Point::new(x, y))
+    }
+}
+
+/// What happened on one day of the run.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct JournalDay {
+    /// Day of the run, from 1.
+    pub day: u32,
+    pub fish: u32,
+    pub storms: u32,
+    /// Tiles moved.
+    pub distance: u32,
+    /// Areas and places found, in order.
+    pub found: Vec<String>,
+}
+
+impl JournalDay {
+    /// One line of the diary.
+    fn line(&self) -> String {
+        let mut line = format!(
+            "Day {}: {} fish, {} {} weathered, {} tiles.",
+            self.day,
+            self.fish,
+            self.storms,
+            if self.storms == 1 { "storm" } else { "storms" },
+            self.distance
+        );
+        if !self.found.is_empty() {
+            line.push_str(&format!(" Found {}.", self.found.join(", ")));
+        }
+        line
+    }
+}
+
+/// The trail and diary of a run.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct Journal {
+    pub trail: Trail,
+    pub days: Vec<JournalDay>,
+}
+
+impl Journal {
+    /// The diary entry of the day `turn` falls on.
+    fn day_mut(&mut self, turn: u32) -> &mut JournalDay {
+        let day = turn / DAY_TURNS + 1;
+        if self.days.last().is_none_or(|d| d.day != day) {
+            self.days.push(JournalDay {
+                day,
+                ..JournalDay::default()
+            });
+        }
+        self.days.last_mut().expect("a day was just pushed")
+    }
+
+    /// Days passed so far.
+    pub fn day_count(&self) -> u32 {
+        self.days.last().map_or(0, |d| d.day)
+    }
+
+    /// The totals line.
+    fn total(&self) -> String {
+        let fish: u32 = self.days.iter().map(|d| d.fish).sum();
+        let storms: u32 = self.days.iter().map(|d| d.storms).sum();
+        format!(
+            "{} fish, {} storms weathered, {} tiles traveled over {} days.",
+            fish,
+            storms,
+            self.trail.distance,
+            self.day_count()
+        )
+    }
+
+    /// The journal as text: a heading, the totals and a line per day.
+    pub fn text(&self) -> String {
+        let mut text = format!("Lurhook travel journal\n{}\n\n", self.total());
+        for day in &self.days {
+            text.push_str(&day.line());
+            text.push('\n');
+        }
+        text
+    }
+}
+
+impl LurhookGame {
+    /// Adds the turn just played to the trail and the day's distance.
+    pub(crate) fn record_trail(&mut self) {
+        let moved = self.journal.trail.record(self.world_pos());
+        self.journal.day_mut(self.turn).distance += moved;
+    }
+
+    /// Starts the trail over on arriving in another area.
+    pub(crate) fn leave_trail(&mut self) {
+        self.journal.trail.area_start = self.journal.trail.steps.len();
+    }
+
+    /// Notes a landed fish or a storm passing in the day's entry.
+    pub(crate) fn journal_event(&mut self, event: &GameEvent) {
+        let day = self.journal.day_mut(self.turn);
+        match event {
+            GameEvent::FishCaught { .. } => day.fish += 1,
+            GameEvent::StormPassed => day.storms += 1,
+            _ => {}
+        }
+    }
+
+    /// Notes `name` in the day's entry the first time the run finds it.
+    pub(crate) fn journal_found(&mut self, name: &str) {
+        if self
+            .journal
+            .days
+            .iter()
+            .any(|d| d.found.iter().any(|f| f == name))
+        {
+            return;
+        }
+        self.journal.day_mut(self.turn).found.push(name.to_string());
+    }
+
+    /// Lines of the journal screen: the totals, then the days.
+    pub(crate) fn journal_lines(&self) -> Vec<String> {
+        std::iter::once(self.journal.total())
+            .chain(self.journal.days.iter().map(JournalDay::line))
+            .collect()
+    }
+
+    /// Tiles moved over the run.
+    pub(crate) fn distance(&self) -> u32 {
+        self.journal.trail.distance
+    }
+
+    /// Writes the journal to [`JOURNAL_PATH`], logging a failure.
+    pub(crate) fn write_journal(&mut self) {
+        let text = self.journal.text();
+        if self.storage.write(JOURNAL_PATH, text.as_bytes()).is_err() {
+            self.ui.add_log("Could not save the journal.").ok();
+        }
+    }
+
+    /// Dots the explored tiles of the trail through the current area.
+    pub(super) fn draw_trail(&self, ctx: &mut dyn Renderer) {
+        if !self.show_trail {
+            return;
+        }
+        let view = self.camera();
+        let origin = self.ocean.world(self.chunk, common::Point::new(0, 0));
+        for pt in self.journal.trail.here() {
+            let pt = pt - origin;
+            if view.contains(pt) && self.map.is_explored(pt) {
+                let screen = view.to_local(pt);
+                let (_, color) = self.map_cell(pt);
+                ctx.set(screen.x, screen.y, color * 0.6, RGB::named(BLACK), '·');
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_game;
+
+    #[test]
+    fn the_trail_keeps_runs_of_turns_and_counts_tiles() {
+        let mut trail = Trail::default();
+        let at = common::Point::new;
+        assert_eq!(trail.record(at(2, 2)), 0);
+        trail.record(at(2, 2));
+        trail.record(at(2, 2));
+        assert_eq!(trail.record(at(3, 3)), 1);
+        assert_eq!(trail.steps, vec![(2, 2, 3), (3, 3, 1)]);
+        trail.area_start = trail.steps.len();
+        assert_eq!(trail.record(at(40, 9)), 0, "no distance across areas");
+        trail.record(at(41, 9));
+        assert_eq!(trail.distance, 2);
+        assert_eq!(trail.here().collect::<Vec<_>>(), vec![at(40, 9), at(41, 9)]);
+    }
+
+    #[test]
+    fn days_sum_up_catches_storms_and_finds() {
+        let mut game = memory_game(0);
+        game.journal = Journal::default();
+        game.turn = 1;
+        game.journal_event(&GameEvent::StormPassed);
+        game.journal_found("Gullwing Bay");
+        game.turn = DAY_TURNS;
+        game.journal_found("Gullwing Bay");
+        game.journal_event(&GameEvent::FishCaught {
+            id: "A".into(),
+            name: "A".into(),
+            weight: 1.0,
+            description: String::new(),
+            habitat_note: String::new(),
+            location: "Coast".into(),
+            turn: DAY_TURNS,
+            timestamp: None,
+        });
+        let days = &game.journal.days;
+        assert_eq!(days.len(), 2);
+        assert_eq!((days[0].day, days[0].storms), (1, 1));
+        assert_eq!((days[1].day, days[1].fish), (2, 1));
+        assert!(days[1].found.is_empty(), "found once a run");
+        assert_eq!(
+            days[0].line(),
+            "Day 1: 0 fish, 1 storm weathered, 0 tiles. Found Gullwing Bay."
+        );
+        let text = game.journal.text();
+        assert!(text.starts_with("Lurhook travel journal\n1 fish, 1 storms"));
+        assert!(text.ends_with("Day 2: 1 fish, 0 storms weathered, 0 tiles.\n"));
+    }
+
+    #[test]
+    fn walking_adds_to_the_distance_and_the_trail_overlay() {
+        let mut game = memory_game(0);
+        game.ui.set_layout(UILayout::Standard);
+        let start = game.player.pos;
+        game.pass_turn();
+        let step = [(1, 0), (-1, 0), (0, 1), (0, -1)]
+            .into_iter()
+            .map(|(x, y)| common::Point::new(x, y))
+            .find(|&d| {
+                game.blocked_reason(start + d).is_none()
+                    && game.blocked_reason(start + d + d).is_none()
+            })
+            .unwrap();
+        for _ in 0..2 {
+            game.try_move(step);
+            game.pass_turn();
+        }
+        assert_eq!(game.player.pos, start + step + step);
+        assert_eq!(game.distance(), 2);
+        assert_eq!(game.journal.days[0].distance, 2);
+
+        let mut buf = TextBuffer::new(SCREEN_WIDTH, SCREEN_HEIGHT);
+        let screen = game.camera().to_local(start + step);
+        game.render(&mut buf);
+        assert_ne!(buf.glyph(screen.x, screen.y), Some('·'));
+        game.handle_input_key(Some(game.input.trail));
+        assert!(game.show_trail);
+        game.render(&mut buf);
+        assert_eq!(buf.glyph(screen.x, screen.y), Some('·'));
+    }
+
+    #[test]
+    fn the_journal_screen_lists_the_days() {
+        let mut game = memory_game(0);
+        game.ui.set_layout(UILayout::Standard);
+        game.journal_found("Gullwing Bay");
+        game.handle_input_key(Some(game.input.journal));
+        assert_eq!(game.ui.layout(), UILayout::Journal);
+        let mut buf = TextBuffer::new(SCREEN_WIDTH, SCREEN_HEIGHT);
+        game.render(&mut buf);
+        assert!(buf.contains("Travel Journal"));
+        assert!(buf.contains(&format!("Found {}, Gullwing Bay.", game.area.name)));
+        game.handle_input_key(Some(VirtualKeyCode::Escape));
+        assert_eq!(game.ui.layout(), UILayout::Standard);
+    }
+
+    #[test]
+    fn the_journal_is_written_as_text() {
+        let mut game = memory_game(0);
+        game.journal_found("Gullwing Bay");
+        game.write_journal();
+        let text = game.storage.read_string(JOURNAL_PATH).unwrap().unwrap();
+        assert!(text.contains("Day 1: 0 fish"));
+    }
+}
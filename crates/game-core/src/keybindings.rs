@@ -0,0 +1,144 @@
+//! The key binding editor opened from the options: pick an action, then
+//! press its new key.
+
+use super::*;
+use input::{key_name, ACTIONS};
+use ui_crate::{BindingView, KeybindingsView};
+
+impl LurhookGame {
+    /// Opens the editor on the first action.
+    pub(crate) fn open_keybindings(&mut self) {
+        self.binding_cursor = 0;
+        self.capturing_key = false;
+        self.ui.set_layout(UILayout::Keybindings);
+    }
+
+    /// Handles a key in the editor. Time does not pass.
+    pub(crate) fn handle_binding_key(&mut self, key: VirtualKeyCode) {
+        if self.capturing_key {
+            self.capturing_key = false;
+            if key != VirtualKeyCode::Escape {
+                self.bind_selected(key);
+            }
+            return;
+        }
+        if key == VirtualKeyCode::Escape || key == self.input.options {
+            self.ui.set_layout(UILayout::Options);
+            return;
+        }
+        if key == VirtualKeyCode::Return {
+            self.capturing_key = true;
+            return;
+        }
+        let count = ACTIONS.len();
+        match self.key_direction(key).map(|d| d.delta().y) {
+            Some(dy) if dy < 0 => self.binding_cursor = (self.binding_cursor + count - 1) % count,
+            Some(dy) if dy > 0 => self.binding_cursor = (self.binding_cursor + 1) % count,
+            _ => {}
+        }
+    }
+
+    /// Binds `key` to the selected action and saves the bindings. An action
+    /// already on `key` swaps keys with it.
+    fn bind_selected(&mut self, key: VirtualKeyCode) {
+        let action = ACTIONS[self.binding_cursor];
+        let old = self.input.binding(action).and_then(key_name);
+        let name = key_name(key).unwrap_or("That key");
+        let msg = match self.input.rebind(action, key) {
+            Ok(Some(other)) => format!(
+                "{} is now {}; {} moves to {}.",
+                action,
+                name,
+                other,
+                old.unwrap_or_default()
+            ),
+            Ok(None) => format!("{} is now {}.", action, name),
+            Err(_) => format!("{} cannot be bound.", name),
+        };
+        self.ui.add_log(&msg).ok();
+        let _ = self.input.save(self.storage.as_ref(), CONFIG_PATH);
+    }
+
+    /// Every action with its key, for the editor.
+    pub(super) fn keybindings_view(&self) -> KeybindingsView<'static> {
+        let bindings = ACTIONS
+            .iter()
+            .map(|&action| BindingView {
+                action,
+                key: self.input.binding(action).and_then(key_name).unwrap_or("?"),
+                conflict: self.input.conflicts(action),
+            })
+            .collect();
+        KeybindingsView {
+            bindings,
+            cursor: self.binding_cursor,
+            capturing: self.capturing_key,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_game;
+
+    fn editor() -> LurhookGame {
+        let mut game = memory_game(0);
+        game.ui.set_layout(UILayout::Options);
+        game
+    }
+
+    fn press(game: &mut LurhookGame, key: VirtualKeyCode) {
+        let mut ctx = empty_frame(0.0);
+        ctx.key = Some(key);
+        game.update(&mut ctx);
+    }
+
+    fn select(game: &mut LurhookGame, action: &str) {
+        while ACTIONS[game.binding_cursor] != action {
+            press(game, VirtualKeyCode::Down);
+        }
+    }
+
+    #[test]
+    fn rebinding_saves_the_new_key_without_using_turns() {
+        let mut game = editor();
+        press(&mut game, VirtualKeyCode::K);
+        assert_eq!(game.ui.layout(), UILayout::Keybindings);
+        let turn = game.turn;
+        select(&mut game, "photo");
+        press(&mut game, VirtualKeyCode::Return);
+        assert!(game.keybindings_view().capturing);
+        press(&mut game, VirtualKeyCode::F5);
+        assert_eq!(game.input.photo, VirtualKeyCode::F5);
+        assert_eq!(game.turn, turn);
+        let saved = InputConfig::load(game.storage.as_ref(), CONFIG_PATH).unwrap();
+        assert_eq!(saved.photo, VirtualKeyCode::F5);
+        press(&mut game, VirtualKeyCode::Escape);
+        assert_eq!(game.ui.layout(), UILayout::Options);
+    }
+
+    #[test]
+    fn taken_keys_swap_and_reserved_keys_are_refused() {
+        let mut game = editor();
+        game.open_keybindings();
+        select(&mut game, "reel");
+        press(&mut game, VirtualKeyCode::Return);
+        press(&mut game, VirtualKeyCode::C);
+        assert_eq!(game.input.reel, VirtualKeyCode::C);
+        assert_eq!(game.input.cast, VirtualKeyCode::R);
+        assert!(game.ui.logs().last().unwrap().contains("cast moves to R"));
+
+        press(&mut game, VirtualKeyCode::Return);
+        press(&mut game, VirtualKeyCode::F12);
+        assert_eq!(game.input.reel, VirtualKeyCode::C);
+        assert!(game.ui.logs().last().unwrap().contains("cannot be bound"));
+
+        // Escape while waiting cancels instead of binding
+        press(&mut game, VirtualKeyCode::Return);
+        press(&mut game, VirtualKeyCode::Escape);
+        assert_eq!(game.input.reel, VirtualKeyCode::C);
+        assert_eq!(game.ui.layout(), UILayout::Keybindings);
+        assert!(game.keybindings_view().bindings.iter().all(|b| !b.conflict));
+    }
+}
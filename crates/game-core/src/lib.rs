@@ -1,95 +1,242 @@
 //! Game engine entry point.
 
+mod adaptive;
 mod ai;
 mod app;
+mod autosave;
+mod awards;
+mod bosses;
+mod catch_card;
+mod chum;
+mod cooking;
+mod daily;
+mod death;
+mod difficulty;
+mod effects;
+mod events;
+mod export;
+mod food;
+#[cfg(feature = "gamepad")]
+mod gamepad;
+mod gear;
+mod hazards;
+mod hookset;
+mod hotseat;
 mod input;
+mod journal;
+mod keybindings;
+mod loadout;
+mod message_log;
+mod methods;
+mod metrics;
+mod migrations;
+mod mouse;
+mod music;
+#[cfg(feature = "net")]
+mod net;
+mod ocean;
+mod photo;
+mod places;
+mod population;
+mod predators;
+#[cfg(feature = "presence")]
+mod presence;
+mod presentation;
+mod prompts;
+mod quest;
+mod realtime;
+mod replay;
+mod rewards;
+mod rivals;
+mod save;
+mod savestate;
+mod seasons;
+mod shop;
+mod sim;
+mod skills;
+#[cfg(feature = "spectate")]
+mod spectate;
+#[cfg(feature = "tui")]
+mod terminal;
+#[cfg(test)]
+pub(crate) mod test_support;
+mod tide;
+mod traps;
+mod tutorial;
 mod types;
 mod ui;
+mod weather;
 
 extern crate ui as ui_crate;
 
-use crate::types::Area;
+use crate::adaptive::AdaptiveDifficulty;
+use crate::death::DeathCause;
+use crate::hookset::Bite;
+use crate::metrics::RunMetrics;
+use crate::rewards::{milestone_achievement, Profile, Reward, MILESTONES};
+use crate::savestate::SaveState;
+use crate::weather::WeatherState;
 use bracket_lib::prelude::*;
 
-use audio::{AudioManager, Sound};
-use codex::Codex;
-use common::{GameError, GameResult, Point};
+use achievements::{AchievementSink, LocalAchievements};
+use audio::AudioManager;
+use codex::{Codex, CodexRecorder, ExportFormat};
+use common::{
+    default_storage, Direction, EventListener, EventQueue, GameError, GameEvent, GameResult,
+    MemoryStorage, Point, Rect, Storage,
+};
 use ecology::update_fish;
 use ecology::{spawn_fish_population, Fish};
 use fishing::{init as fishing_init, TensionMeter};
+use mapgen::fov::{field_of_view, FieldOfView};
 use mapgen::{generate, Map, TileKind};
-use ui_crate::{init as ui_init, ColorPalette, UIContext, UILayout};
-
-const VIEW_WIDTH: i32 = 60;
-const VIEW_HEIGHT: i32 = 17;
+use ui_crate::{
+    bar_chart, init as ui_init, sparkline, AchievementView, BoatView, CodexView, ColorPalette,
+    FightView, LogCategory, MinimapTile, MinimapView, OptionsView, QuestView, Renderer,
+    StandingsView, StatusIcon, StatusView, TextBuffer, UIContext, UILayout,
+};
+
+/// Default console size in cells, the smallest the panels fit in.
+const SCREEN_WIDTH: i32 = ui_crate::MIN_SCREEN_WIDTH;
+const SCREEN_HEIGHT: i32 = ui_crate::MIN_SCREEN_HEIGHT;
+/// Window sizes selectable in Options, in pixels (square 12-24px tiles).
+const WINDOW_SIZES: [(u32, u32); 4] = [(960, 300), (1280, 400), (1600, 500), (1920, 600)];
 const LINE_DAMAGE: i32 = 15;
 const HAZARD_DAMAGE: i32 = 1;
-const HAZARD_DURATION: u8 = 3;
 const HAZARD_CHANCE: i32 = 8; // percent chance per turn
 const MAX_HUNGER: i32 = 100;
+const MAX_THIRST: i32 = 100;
 const EAT_RAW_FISH: i32 = 20;
 const EAT_COOKED_FISH: i32 = 40;
 const EAT_CANNED_FOOD: i32 = 60;
-const COOK_HP_RESTORE: i32 = 2;
 const MAX_HP: i32 = 10;
+const MAX_LINE: i32 = 100;
+/// Durability of a rod or reel in full condition.
+const MAX_GEAR_DURABILITY: i32 = 100;
+const BOAT_DURABILITY: i32 = 10;
+const BOAT_SPEED: i32 = 2;
+/// Extra maximum tension when hooking a fish from an anchored boat.
+const ANCHOR_TENSION_BONUS: i32 = 15;
 const TIME_SEGMENT_TURNS: u32 = 10;
-const TIDE_TURNS: u32 = 20;
-const TIMES: [&str; 4] = ["Dawn", "Day", "Dusk", "Night"];
-const SAVE_PATH: &str = "savegame.ron";
+const TIMES: [&str; 4] = data::TIMES_OF_DAY;
+const SAVE_PATH: &str = "savegame.json";
+/// Save slot written before saves held the full run as JSON.
+const LEGACY_SAVE_PATH: &str = "savegame.ron";
 const CONFIG_PATH: &str = "lurhook.toml";
 const CODEX_PATH: &str = "codex.json";
+const CODEX_REPORT_PATH: &str = "codex_report";
+const PROFILE_PATH: &str = "profile.json";
+const ACHIEVEMENTS_PATH: &str = "achievements.json";
+const LEADERBOARD_PATH: &str = "leaderboard.json";
+/// Where the inputs of a recorded run are written.
+pub const REPLAY_PATH: &str = "last_run.replay";
+/// Palette name that selects the built-in colors.
+const DEFAULT_PALETTE: &str = "Default";
 pub use app::LurhookApp;
+pub use daily::Leaderboard;
+pub use difficulty::{Difficulty, DifficultyProfile};
+pub use ecology::population::SpeciesStats;
+pub use effects::{StatusEffect, StatusKind};
+pub use export::MapFormat;
+#[cfg(feature = "gamepad")]
+pub use gamepad::GilrsGamepad;
+pub use hazards::HazardKind;
+pub use hotseat::Hotseat;
 use input::InputConfig;
+pub use input::{Gamepad, MouseButtons, PadButton, PadEvent};
+#[cfg(feature = "net")]
+pub use net::{CoopSession, DEFAULT_PORT};
+#[cfg(feature = "presence")]
+pub use presence::{DiscordPresence, Presence, RunStatus, DISCORD_CLIENT_ID_VAR};
+pub use replay::Replay;
+pub use sim::{Action, Phase, StepReport};
+#[cfg(feature = "spectate")]
+pub use spectate::{Frame, Spectator, Status, DEFAULT_SPECTATE_PORT};
+#[cfg(feature = "tui")]
+pub use terminal::{run_terminal, run_terminal_app};
+pub use weather::Weather;
+
+/// Input state for one frame with no key pressed.
+pub(crate) fn empty_frame(frame_time_ms: f32) -> BTerm {
+    BTerm {
+        width_pixels: 0,
+        height_pixels: 0,
+        original_height_pixels: 0,
+        original_width_pixels: 0,
+        fps: 0.0,
+        frame_time_ms,
+        active_console: 0,
+        key: None,
+        mouse_pos: (0, 0),
+        left_click: false,
+        shift: false,
+        control: false,
+        alt: false,
+        web_button: None,
+        quitting: false,
+        post_scanlines: false,
+        post_screenburn: false,
+        screen_burn_color: RGB::from_f32(0.0, 0.0, 0.0),
+        mouse_visible: false,
+    }
+}
+
+/// Score for a set of caught fish; rarer fish are worth more.
+fn catch_score(inventory: &[Catch]) -> i32 {
+    inventory.iter().map(Catch::score).sum()
+}
 
 /// Current game mode.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum GameMode {
     Exploring,
-    Aiming { target: common::Point },
-    Fishing { wait: u8 },
-    End { score: i32 },
-}
-
-/// Difficulty settings scaling hunger loss and hazard rate.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum Difficulty {
-    Easy,
-    Normal,
-    Hard,
+    Aiming {
+        target: common::Point,
+    },
+    /// Line out: `wait` turns until a bite is rolled, then `bite` while a
+    /// fish holds the bait but is not hooked yet.
+    Fishing {
+        wait: u8,
+        bite: Option<Bite>,
+    },
+    /// The run was ended by the player.
+    End {
+        score: i32,
+    },
+    /// The angler died of `cause`, scoring `score` for the catch so far.
+    GameOver {
+        cause: DeathCause,
+        score: i32,
+    },
+    /// Simulation frozen while a free camera frames a shot around `focus`.
+    Photo {
+        focus: common::Point,
+    },
+    /// At the merchant's counter; `cursor` points into the sell list
+    /// (the player's catches) or, when `buying`, the shop stock.
+    Trading {
+        cursor: usize,
+        buying: bool,
+    },
 }
 
-impl Default for Difficulty {
-    fn default() -> Self {
-        Difficulty::Normal
+impl GameMode {
+    /// Whether the run is over, ended or by death.
+    fn is_over(self) -> bool {
+        matches!(self, GameMode::End { .. } | GameMode::GameOver { .. })
     }
 }
 
-impl Difficulty {
-    fn hunger_loss(self, turn: u32) -> i32 {
-        match self {
-            Difficulty::Easy => {
-                if turn % 2 == 0 {
-                    1
-                } else {
-                    0
-                }
-            }
-            Difficulty::Normal => 1,
-            Difficulty::Hard => 2,
-        }
-    }
-
-    fn hazard_chance(self, area: Area) -> i32 {
-        let base = match self {
-            Difficulty::Easy => HAZARD_CHANCE / 2,
-            Difficulty::Normal => HAZARD_CHANCE,
-            Difficulty::Hard => HAZARD_CHANCE * 2,
-        };
-        base * area.hazard_multiplier()
-    }
+/// What the aiming cursor throws instead of casting the line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Throw {
+    Chum,
+    Net,
+    Longline,
+    Trap,
 }
 
-pub use types::{Hazard, Player};
+pub use types::{Boat, Campfire, Catch, Chum, Hazard, Longline, Player, Trap};
 
 /// Basic game state implementing [`GameState`].
 pub struct LurhookGame {
@@ -103,26 +250,152 @@ pub struct LurhookGame {
     turn: u32,
     rng: RandomNumberGenerator,
     difficulty: Difficulty,
+    /// Rules the run plays by, from its difficulty.
+    rules: DifficultyProfile,
+    /// What last hurt the angler, the cause should they die.
+    harm: Option<DeathCause>,
+    /// Whether the run's one rescue from death has been used.
+    rescued: bool,
+    /// Modifiers of the angler skills the run started with.
+    skills: progression::Modifiers,
+    adaptive: AdaptiveDifficulty,
+    metrics: RunMetrics,
     mode: GameMode,
     meter: Option<TensionMeter>,
     reeling: bool,
     palette: ColorPalette,
-    storm_turns: u8,
+    weather: WeatherState,
     hazards: Vec<Hazard>,
+    /// Chum thrown into the water.
+    chum: Vec<Chum>,
+    /// Longlines set and not yet hauled in.
+    longlines: Vec<Longline>,
+    /// Turns until the cast net can be thrown again.
+    net_cooldown: u8,
+    /// Traps set in the water.
+    traps: Vec<Trap>,
+    /// Campfires burning ashore.
+    campfires: Vec<Campfire>,
+    /// Encounters from `events.json` that can happen on a turn.
+    scripted_events: Vec<data::ScriptedEvent>,
+    /// What the open prompt asks.
+    question: Option<prompts::Question>,
+    /// Scheduled migration runs of every area.
+    migrations: Vec<data::Migration>,
+    /// Migrating schools passing through the current area.
+    runs: Vec<ecology::migration::Run>,
+    /// Chunks of the current area, for areas that go on past the map.
+    ocean: mapgen::chunks::Ocean,
+    /// Chunk of the area the map shows; (0, 0) outside endless areas.
+    chunk: common::Point,
+    /// Named places on the current map.
+    features: mapgen::features::Features,
+    /// Index in `features` of the place the player is in.
+    place: Option<usize>,
+    /// Fish stock of every area visited, by area id.
+    populations: std::collections::BTreeMap<String, ecology::population::Population>,
+    /// Where failed catches splashed since the fish last moved.
+    splashes: Vec<common::Point>,
+    /// What the player saw from where they last looked; recomputed when
+    /// they move, their sight changes or the map is replaced.
+    sight: std::cell::RefCell<Option<FieldOfView>>,
+    /// Whether the minimap is drawn over the map.
+    show_minimap: bool,
+    /// Whether the trail through the area is drawn on the map.
+    show_trail: bool,
+    /// The path, distance and diary of the run.
+    journal: journal::Journal,
+    /// Mouse buttons held or clicked this frame.
+    mouse: MouseButtons,
+    /// Milliseconds until a held left button reels again.
+    reel_repeat_in: f32,
+    /// Milliseconds towards the next real-time fishing turn.
+    fight_clock: f32,
+    /// Whether the reel key was pressed since the last real-time turn.
+    reel_queued: bool,
+    /// Keeps fishing turn by turn whatever the config: co-op turns wait
+    /// for both anglers, and older replays were recorded that way.
+    turn_based_fishing: bool,
+    /// Hooks a biting fish at once, as replays recorded before hook
+    /// setting expect.
+    instant_hook: bool,
+    /// Console cell under the mouse pointer.
+    hover: Option<common::Point>,
     cast_path: Option<Vec<common::Point>>,
+    /// Where the last cast landed; bait that draws hungry fish while fishing.
+    bait: Option<common::Point>,
+    /// Index in `fishes` of the fish on the line.
+    hooked: Option<usize>,
+    /// What the aiming cursor throws, if not the line.
+    throwing: Option<Throw>,
     cast_step: usize,
     inventory_cursor: usize,
     inventory_focus: bool,
+    /// Selected action in the key binding editor.
+    binding_cursor: usize,
+    /// Whether the key binding editor waits for the selected action's key.
+    capturing_key: bool,
     codex: codex::Codex,
     audio: AudioManager,
-    area: Area,
+    /// Areas from `areas.json`, in progression order.
+    areas: Vec<data::AreaType>,
+    area: data::AreaType,
     seed: u64,
     fish_types: Vec<data::FishType>,
+    /// ASCII art of the species for the catch card.
+    fish_art: data::FishArt,
+    /// Every item in `items.json`, whether carried or not.
+    catalogue: Vec<data::ItemType>,
+    profile: Profile,
+    storage: Box<dyn Storage>,
+    /// Where unlocks are reported; local file unless a platform sink is set.
+    achievements: Box<dyn AchievementSink>,
+    /// Position of another angler sharing the map (co-op partner).
+    companion: Option<common::Point>,
+    /// Rival anglers of a competition.
+    rivals: Vec<rivals::Rival>,
+    /// Shore tile where the merchant trades, if the map has one.
+    merchant: Option<common::Point>,
+    /// What the merchant has left to sell this run.
+    shop: Vec<data::ShopEntry>,
+    /// Dishes that can be cooked at a campfire.
+    recipes: Vec<data::Recipe>,
+    /// Quests of this run and their progress.
+    quests: quests::QuestLog,
+    /// Events raised since the last dispatch.
+    events: EventQueue,
+    /// Extra subscribers beyond the log, audio and codex.
+    listeners: Vec<Box<dyn EventListener>>,
+    /// Inputs of this run while it is being recorded.
+    replay: Option<Replay>,
+    /// Slot the next autosave goes to, while autosaving.
+    autosave: Option<usize>,
+    /// Turn of the last autosave.
+    last_autosave: u32,
+    /// Set when this is a daily run.
+    daily: Option<daily::DailyRun>,
+    /// Progress through the tutorial when this run is one.
+    tutorial: Option<tutorial::Tutorial>,
 }
 
 impl LurhookGame {
-    /// Creates a new game with a generated map in the given area.
-    pub fn new_with_area(seed: u64, difficulty: Difficulty, area: Area) -> GameResult<Self> {
+    /// Creates a new game with a generated map in the area with id `area`,
+    /// or in the first area when `None`.
+    pub fn new_with_area(
+        seed: u64,
+        difficulty: Difficulty,
+        area: Option<&str>,
+    ) -> GameResult<Self> {
+        Self::new_with_storage(seed, difficulty, area, default_storage())
+    }
+
+    /// Creates a new game that persists config, codex and saves through `storage`.
+    pub fn new_with_storage(
+        seed: u64,
+        difficulty: Difficulty,
+        area: Option<&str>,
+        storage: Box<dyn Storage>,
+    ) -> GameResult<Self> {
         let fish_types = {
             #[cfg(target_arch = "wasm32")]
             {
@@ -134,7 +407,7 @@ impl LurhookGame {
                 data::load_fish_types(path)?
             }
         };
-        let mut items = {
+        let catalogue = {
             #[cfg(target_arch = "wasm32")]
             {
                 data::load_item_types_embedded()?
@@ -145,70 +418,148 @@ impl LurhookGame {
                 data::load_item_types(item_path)?
             }
         };
-        let rod_pos = items
-            .iter()
-            .position(|i| matches!(i.kind, data::ItemKind::Rod));
-        let reel_pos = items
-            .iter()
-            .position(|i| matches!(i.kind, data::ItemKind::Reel));
-        let lure_pos = items
-            .iter()
-            .position(|i| matches!(i.kind, data::ItemKind::Lure));
-        let rod = rod_pos.map(|p| items.remove(p));
-        // adjust indices if necessary
-        let reel = reel_pos.map(|p| {
-            items.remove(
-                p - if rod_pos.map_or(false, |r| p > r) {
-                    1
-                } else {
-                    0
-                },
-            )
-        });
-        let lure = lure_pos.map(|p| {
-            let mut idx = p;
-            if let Some(r) = rod_pos {
-                if p > r {
-                    idx -= 1;
-                }
+        let loadouts = loadout::load_loadouts()?;
+        let shop = {
+            #[cfg(target_arch = "wasm32")]
+            {
+                data::load_shop_embedded()?
             }
-            if let Some(r) = reel_pos {
-                if p > r {
-                    idx -= 1;
-                }
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                let shop_path = concat!(env!("CARGO_MANIFEST_DIR"), "/../../assets/shop.json");
+                data::load_shop(shop_path)?
             }
-            items.remove(idx)
-        });
+        };
+        let recipes = {
+            #[cfg(target_arch = "wasm32")]
+            {
+                data::load_recipes_embedded()?
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                let recipe_path = concat!(env!("CARGO_MANIFEST_DIR"), "/../../assets/recipes.json");
+                data::load_recipes(recipe_path)?
+            }
+        };
+        let migrations = {
+            #[cfg(target_arch = "wasm32")]
+            {
+                data::load_migrations_embedded()?
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                let migration_path =
+                    concat!(env!("CARGO_MANIFEST_DIR"), "/../../assets/migrations.json");
+                data::load_migrations(migration_path)?
+            }
+        };
+        let scripted_events = events::load_events()?;
+        let quest_list = {
+            #[cfg(target_arch = "wasm32")]
+            {
+                quests::load_quests_embedded()?
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                let quest_path = concat!(env!("CARGO_MANIFEST_DIR"), "/../../assets/quests.json");
+                quests::load_quests(quest_path)?
+            }
+        };
+        let fish_art = {
+            #[cfg(target_arch = "wasm32")]
+            {
+                data::load_fish_art_embedded()?
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                let art_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/../../assets/fishart");
+                data::load_fish_art(art_dir)?
+            }
+        };
+        let areas = {
+            #[cfg(target_arch = "wasm32")]
+            {
+                data::load_area_types_embedded()?
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                let area_path = concat!(env!("CARGO_MANIFEST_DIR"), "/../../assets/areas.json");
+                data::load_area_types(area_path)?
+            }
+        };
+        let area = match area {
+            Some(id) => find_area(&areas, id)?,
+            None => areas[0].clone(),
+        };
+        let mut map = generate(seed, &area)?;
+        // Spawning draws from the run's generator so the seed fixes the
+        // starting fish too
+        let mut rng = RandomNumberGenerator::seeded(seed);
+        let species = area_fish(&area, &fish_types, data::Season::default());
+        let fishes = spawn_fish_population(&mut map, &species, 5, &mut rng)?;
+        let populations = std::collections::BTreeMap::from([(
+            area.id.clone(),
+            ecology::population::Population::new(&map),
+        )]);
+        let storage: std::rc::Rc<dyn Storage> = storage.into();
+        let input = InputConfig::load(storage.as_ref(), CONFIG_PATH)?;
+        let volume = input.sfx_volume;
+        let music_volume = input.music_volume;
+        let muted = input.muted;
+        let adaptive = AdaptiveDifficulty::new(input.adaptive);
+        let rules = difficulty.rules(&input);
+        let profile = Profile::load(storage.as_ref(), PROFILE_PATH)?;
+        let achievements = LocalAchievements::load(Box::new(storage.clone()), ACHIEVEMENTS_PATH)?;
+        let loadout::Kit {
+            rod,
+            reel,
+            lure,
+            mut items,
+        } = loadout::Kit::new(
+            loadout::chosen(&loadouts, &profile, &achievements),
+            &catalogue,
+        )?;
+        items.extend(profile.lures());
         let bait_bonus = lure.as_ref().map(|l| l.bite_bonus).unwrap_or(0.0);
         let tension_bonus = rod.as_ref().map(|r| r.tension_bonus).unwrap_or(0);
         let reel_factor = reel.as_ref().map(|r| r.reel_factor).unwrap_or(1.0);
-        let (w, h) = area.size();
-        let mut map = generate(seed, w, h)?;
-        let fishes = spawn_fish_population(&mut map, &fish_types, 5)?;
-        let input = InputConfig::load(CONFIG_PATH)?;
-        let volume = input.volume;
-        let palette = if input.colorblind {
-            ColorPalette::colorblind()
-        } else {
-            ColorPalette::default()
-        };
-        let start = common::Point::new(map.width as i32 / 2, map.height as i32 / 2);
+        let cast_range = reel
+            .as_ref()
+            .map_or(data::DEFAULT_CAST_RANGE, |r| r.max_cast_range);
+        let palette = Self::resolve_palette(&input, &profile);
+        let center = common::Point::new(map.width as i32 / 2, map.height as i32 / 2);
+        let start = mapgen::basins::start_point(&map, center);
         let depth = map.depth(start);
+        let merchant = shop::merchant_spot(&map, start);
+        let ocean = mapgen::chunks::Ocean::new(seed, &area);
         let mut game = Self {
             player: Player {
                 pos: start,
                 hp: MAX_HP,
                 hunger: MAX_HUNGER,
-                line: 100,
+                thirst: MAX_THIRST,
+                line: MAX_LINE,
                 bait_bonus,
                 tension_bonus,
                 reel_factor,
+                cast_range,
                 canned_food: 0,
+                driftwood: 0,
+                buff: None,
+                effects: Vec::new(),
+                coins: 0,
                 inventory: Vec::new(),
                 items,
                 rod,
                 reel,
+                rod_durability: MAX_GEAR_DURABILITY,
+                reel_durability: MAX_GEAR_DURABILITY,
                 lure,
+                presentation: fishing::Presentation::default(),
+                sonar: None,
+                boat: Boat::new(start, BOAT_DURABILITY, BOAT_SPEED),
+                // Starting out at sea means starting in the boat
+                aboard: map.tiles[map.idx(start)] == TileKind::DeepWater,
             },
             map,
             fishes,
@@ -217,31 +568,149 @@ impl LurhookGame {
             depth,
             time_of_day: TIMES[0],
             turn: 0,
-            rng: RandomNumberGenerator::seeded(seed),
+            rng,
             difficulty,
+            rules,
+            harm: None,
+            rescued: false,
+            skills: progression::Modifiers::default(),
+            adaptive,
+            metrics: RunMetrics::default(),
             mode: GameMode::Exploring,
             meter: None,
             reeling: false,
             palette,
-            storm_turns: 0,
+            weather: WeatherState::default(),
             hazards: Vec::new(),
+            chum: Vec::new(),
+            longlines: Vec::new(),
+            net_cooldown: 0,
+            traps: Vec::new(),
+            campfires: Vec::new(),
+            scripted_events,
+            question: None,
+            migrations,
+            runs: Vec::new(),
+            ocean,
+            chunk: common::Point::new(0, 0),
+            features: Default::default(),
+            place: None,
+            populations,
+            splashes: Vec::new(),
+            sight: Default::default(),
+            show_minimap: false,
+            show_trail: false,
+            journal: Default::default(),
+            mouse: MouseButtons::default(),
+            reel_repeat_in: 0.0,
+            fight_clock: 0.0,
+            reel_queued: false,
+            turn_based_fishing: false,
+            instant_hook: false,
+            hover: None,
             cast_path: None,
+            bait: None,
+            hooked: None,
+            throwing: None,
             cast_step: 0,
             inventory_cursor: 0,
             inventory_focus: false,
-            codex: Codex::load(CODEX_PATH)?,
-            audio: AudioManager::new(volume),
+            binding_cursor: 0,
+            capturing_key: false,
+            codex: Codex::load(storage.as_ref(), CODEX_PATH)?,
+            audio: {
+                #[cfg(target_arch = "wasm32")]
+                let mut audio = AudioManager::new(volume);
+                #[cfg(not(target_arch = "wasm32"))]
+                let mut audio = {
+                    let sound_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/../../assets/sounds");
+                    AudioManager::load(volume, sound_dir)?
+                };
+                audio.set_music_volume(music_volume);
+                audio.set_muted(muted);
+                audio
+            },
+            areas,
             area,
             seed,
             fish_types,
+            fish_art,
+            catalogue,
+            profile,
+            achievements: Box::new(achievements),
+            storage: Box::new(storage),
+            companion: None,
+            rivals: Vec::new(),
+            merchant,
+            shop,
+            recipes,
+            quests: quests::QuestLog::new(quest_list),
+            events: EventQueue::default(),
+            listeners: Vec::new(),
+            replay: None,
+            autosave: None,
+            last_autosave: 0,
+            daily: None,
+            tutorial: None,
         };
+        game.apply_skills();
+        game.give_starting_items();
+        game.chart_features();
+        game.explore();
+        let area = game.area.name.clone();
+        game.journal_found(&area);
         game.ui.set_layout(UILayout::Help);
+        game.ui.set_theme(Self::resolve_theme(&game.input));
         Ok(game)
     }
 
-    /// Creates a new game with a specified difficulty in the default coastal area.
+    /// Adds `listener` to the subscribers of game events.
+    pub fn subscribe(&mut self, listener: Box<dyn EventListener>) {
+        self.listeners.push(listener);
+    }
+
+    /// Hands the queued events to the log, audio, codex and subscribers,
+    /// then applies the game's own reactions (codex rewards, quests).
+    pub(crate) fn dispatch_events(&mut self) {
+        let events: Vec<GameEvent> = self.events.drain().collect();
+        for event in &events {
+            self.ui.on_event(event);
+            self.journal_event(event);
+            self.audio.on_event(event);
+            CodexRecorder {
+                codex: &mut self.codex,
+            }
+            .on_event(event);
+            for listener in &mut self.listeners {
+                listener.on_event(event);
+            }
+            if let Some(tutorial) = &mut self.tutorial {
+                tutorial.on_event(event);
+            }
+            self.check_achievements(event);
+            if let GameEvent::FishCaught { id, weight, .. } = event {
+                self.show_catch_card(id, *weight);
+                self.gain_xp(id, *weight);
+                self.check_codex_rewards();
+                if let Some(kind) = self.fish_types.iter().find(|f| &f.id == id).cloned() {
+                    let area = self.area.id.clone();
+                    self.record_quest(quests::QuestEvent::Catch {
+                        fish: &kind,
+                        area: &area,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Reports achievement unlocks to `sink` instead of the local file.
+    pub fn set_achievement_sink(&mut self, sink: Box<dyn AchievementSink>) {
+        self.achievements = sink;
+    }
+
+    /// Creates a new game with a specified difficulty in the first area.
     pub fn new_with_difficulty(seed: u64, difficulty: Difficulty) -> GameResult<Self> {
-        Self::new_with_area(seed, difficulty, Area::Coast)
+        Self::new_with_area(seed, difficulty, None)
     }
 
     /// Creates a new game with default (Normal) difficulty.
@@ -249,19 +718,43 @@ impl LurhookGame {
         Self::new_with_difficulty(seed, Difficulty::Normal)
     }
 
-    /// Returns the current game mode.
-    pub(crate) fn mode(&self) -> GameMode {
-        self.mode
+    /// Score of the run once it is over, ended or by death.
+    pub(crate) fn final_score(&self) -> Option<i32> {
+        match self.mode {
+            GameMode::End { score } | GameMode::GameOver { score, .. } => Some(score),
+            _ => None,
+        }
+    }
+
+    /// Returns the highest codex title unlocked in the profile.
+    pub(crate) fn title(&self) -> Option<&'static str> {
+        self.profile.title()
     }
 
-    fn camera(&self) -> (i32, i32) {
-        let half_w = VIEW_WIDTH / 2;
-        let half_h = VIEW_HEIGHT / 2;
-        let mut x = self.player.pos.x - half_w;
-        let mut y = self.player.pos.y - half_h;
-        x = x.clamp(0, self.map.width as i32 - VIEW_WIDTH);
-        y = y.clamp(0, self.map.height as i32 - VIEW_HEIGHT);
-        (x, y)
+    /// Returns the per-turn metrics recorded so far.
+    pub(crate) fn metrics(&self) -> &RunMetrics {
+        &self.metrics
+    }
+
+    /// Screen area showing the map, sized by the console last drawn to.
+    fn view_panel(&self) -> Rect {
+        self.ui.screen().map
+    }
+
+    /// Map area visible on screen, centered on the player (or the photo
+    /// camera) where possible. Maps smaller than the view panel are shown
+    /// whole.
+    fn camera(&self) -> Rect {
+        let center = match self.mode {
+            GameMode::Photo { focus } => focus,
+            _ => self.player.pos,
+        };
+        let view = self.view_panel();
+        let width = view.width.min(self.map.width as i32);
+        let height = view.height.min(self.map.height as i32);
+        let x = (center.x - width / 2).clamp(0, self.map.width as i32 - width);
+        let y = (center.y - height / 2).clamp(0, self.map.height as i32 - height);
+        Rect::new(x, y, width, height)
     }
 
     fn line_path(start: common::Point, end: common::Point) -> Vec<common::Point> {
@@ -296,7 +789,7 @@ impl LurhookGame {
 
     fn inventory_lines(&self) -> Vec<String> {
         let mut lines: Vec<String> = self.player.items.iter().map(|i| i.name.clone()).collect();
-        lines.extend(self.player.inventory.iter().map(|f| f.name.clone()));
+        lines.extend(self.player.inventory.iter().map(|f| self.catch_label(f)));
         if lines.is_empty() {
             lines.push("(empty)".to_string());
         }
@@ -304,40 +797,203 @@ impl LurhookGame {
     }
 
     /// Moves the player by the given delta, clamped to screen bounds.
+    /// Aboard, the boat covers up to its speed in tiles per step. Walking
+    /// into the merchant opens the trade screen instead.
     fn try_move(&mut self, delta: common::Point) {
-        let mut x = self.player.pos.x + delta.x;
-        let mut y = self.player.pos.y + delta.y;
-        x = x.clamp(0, self.map.width as i32 - 1);
-        y = y.clamp(0, self.map.height as i32 - 1);
-        self.player.pos.x = x;
-        self.player.pos.y = y;
-        self.depth = self.map.depth(self.player.pos);
+        if self.merchant == Some(self.player.pos + delta) {
+            self.enter_trading();
+            return;
+        }
+        if self.player.aboard && self.player.boat.anchored {
+            self.ui.add_log("Weigh anchor before moving.").ok();
+            return;
+        }
+        let steps = if self.player.aboard {
+            self.player.boat.moves()
+        } else {
+            1
+        };
+        for step in 0..steps {
+            let target = self.player.pos + delta;
+            if self.area.endless && !self.map.bounds().contains(target) {
+                if step == 0 {
+                    self.sail_on(delta);
+                }
+                break;
+            }
+            let next = self.map.bounds().clamp(target);
+            if next == self.player.pos {
+                break;
+            }
+            if let Some(reason) = self.blocked_reason(next) {
+                if step == 0 {
+                    self.ui.add_log(reason).ok();
+                }
+                break;
+            }
+            self.place_player(next);
+        }
+    }
+
+    /// Why the player cannot move onto `target` right now, if they cannot.
+    fn blocked_reason(&self, target: common::Point) -> Option<&'static str> {
+        match (self.player.aboard, self.map.tiles[self.map.idx(target)]) {
+            (false, TileKind::DeepWater) => Some("Too deep to wade. Board your boat first."),
+            (true, tile) if !tile.is_water() => Some("The boat cannot go ashore."),
+            _ => None,
+        }
+    }
+
+    /// Puts the player (and their boat, if aboard) on `target`.
+    fn place_player(&mut self, target: common::Point) {
+        self.player.pos = target;
+        if self.player.aboard {
+            self.player.boat.pos = target;
+        }
+        self.depth = self.map.depth(target);
+        self.enter_place();
+    }
+
+    /// Boards the boat when next to it, or steps off it outside deep water.
+    fn toggle_boat(&mut self) {
+        let boat = &mut self.player.boat;
+        if self.player.aboard {
+            if self.map.tiles[self.map.idx(self.player.pos)] == TileKind::DeepWater {
+                self.ui.add_log("Too deep to step off here.").ok();
+                return;
+            }
+            self.player.aboard = false;
+            boat.anchored = false;
+            self.ui.add_log("You step off the boat.").ok();
+        } else if self.player.pos.chebyshev(boat.pos) <= 1 {
+            self.player.aboard = true;
+            let pos = boat.pos;
+            self.place_player(pos);
+            self.ui.add_log("You board the boat.").ok();
+        } else {
+            self.ui.add_log("Your boat is not within reach.").ok();
+        }
+    }
+
+    /// Drops or weighs the boat's anchor.
+    fn toggle_anchor(&mut self) {
+        if !self.player.aboard {
+            self.ui.add_log("You are not in the boat.").ok();
+            return;
+        }
+        let boat = &mut self.player.boat;
+        boat.anchored = !boat.anchored;
+        let msg = if boat.anchored {
+            "You drop anchor."
+        } else {
+            "You weigh anchor."
+        };
+        self.ui.add_log(msg).ok();
+    }
+
+    /// Rod bonus to maximum tension, plus the anchor's when anchored and
+    /// any meal boost.
+    fn line_tension_bonus(&self) -> i32 {
+        let anchored = self.player.aboard && self.player.boat.anchored;
+        let meal = self.player.meal_bonus(data::BuffKind::Tension) as i32;
+        self.player.rod_tension_bonus() + meal + if anchored { ANCHOR_TENSION_BONUS } else { 0 }
     }
 
     fn score(&self) -> i32 {
-        self.player
-            .inventory
-            .iter()
-            .map(|f| ((1.0 / f.rarity) * 10.0) as i32)
-            .sum()
+        (catch_score(&self.player.inventory) as f32 * self.quests.score_multiplier()).round() as i32
     }
 
     fn end_run(&mut self) {
         let score = self.score();
         self.ui
-            .add_log(&format!("Run ended! Final score: {}", score))
+            .add_entry(
+                LogCategory::Event,
+                &format!("Run ended! Final score: {}", score),
+            )
             .ok();
+        self.announce_standings();
         self.mode = GameMode::End { score };
+        self.record_daily_score(score);
+        self.write_replay();
+        self.flush_codex();
+    }
+
+    /// Widget colors for the configured colorblind setting.
+    fn resolve_theme(input: &InputConfig) -> ui_crate::widgets::Theme {
+        if input.colorblind {
+            ui_crate::widgets::Theme::high_contrast()
+        } else {
+            ui_crate::widgets::Theme::default()
+        }
+    }
+
+    /// Colorblind colors win; otherwise the configured palette if unlocked.
+    fn resolve_palette(input: &InputConfig, profile: &Profile) -> ColorPalette {
+        if input.colorblind {
+            return ColorPalette::colorblind();
+        }
+        profile
+            .palettes()
+            .into_iter()
+            .find(|name| *name == input.palette)
+            .and_then(ColorPalette::named)
+            .unwrap_or_default()
     }
 
     fn toggle_colorblind(&mut self) {
         self.input.colorblind = !self.input.colorblind;
-        self.palette = if self.input.colorblind {
-            ColorPalette::colorblind()
-        } else {
-            ColorPalette::default()
+        self.palette = Self::resolve_palette(&self.input, &self.profile);
+        self.ui.set_theme(Self::resolve_theme(&self.input));
+        let _ = self.input.save(self.storage.as_ref(), CONFIG_PATH);
+    }
+
+    /// Mutes or unmutes all sound, keeping the volumes.
+    fn toggle_mute(&mut self) {
+        self.input.muted = !self.input.muted;
+        self.audio.set_muted(self.input.muted);
+        let _ = self.input.save(self.storage.as_ref(), CONFIG_PATH);
+    }
+
+    /// Cycles through the default palette and those unlocked via the codex.
+    fn cycle_palette(&mut self) {
+        let mut names = vec![DEFAULT_PALETTE];
+        names.extend(self.profile.palettes());
+        let next = names
+            .iter()
+            .position(|n| *n == self.input.palette)
+            .map_or(0, |i| (i + 1) % names.len());
+        self.input.palette = names[next].to_string();
+        self.palette = Self::resolve_palette(&self.input, &self.profile);
+        let _ = self.input.save(self.storage.as_ref(), CONFIG_PATH);
+    }
+
+    fn toggle_adaptive(&mut self) {
+        self.input.adaptive = !self.input.adaptive;
+        self.adaptive.set_enabled(self.input.adaptive);
+        let _ = self.input.save(self.storage.as_ref(), CONFIG_PATH);
+    }
+
+    fn toggle_fullscreen(&mut self) {
+        self.input.fullscreen = !self.input.fullscreen;
+        let _ = self.input.save(self.storage.as_ref(), CONFIG_PATH);
+    }
+
+    fn toggle_vsync(&mut self) {
+        self.input.vsync = !self.input.vsync;
+        let _ = self.input.save(self.storage.as_ref(), CONFIG_PATH);
+    }
+
+    /// Cycles automatic sizing and the preset window sizes.
+    fn cycle_window_size(&mut self) {
+        self.input.window_size = match self.input.window_size {
+            None => Some(WINDOW_SIZES[0]),
+            Some(size) => WINDOW_SIZES
+                .iter()
+                .position(|s| *s == size)
+                .and_then(|i| WINDOW_SIZES.get(i + 1))
+                .copied(),
         };
-        let _ = self.input.save(CONFIG_PATH);
+        let _ = self.input.save(self.storage.as_ref(), CONFIG_PATH);
     }
 
     fn cycle_cast_key(&mut self) {
@@ -348,7 +1004,7 @@ impl LurhookGame {
             Z => C,
             _ => C,
         };
-        let _ = self.input.save(CONFIG_PATH);
+        let _ = self.input.save(self.storage.as_ref(), CONFIG_PATH);
     }
 
     /// Handles input and updates the player position accordingly.
@@ -356,32 +1012,81 @@ impl LurhookGame {
         self.reeling = false;
         if ctx.left_click {
             let (mx, my) = ctx.mouse_pos;
-            if mx < VIEW_WIDTH as i32 && my < VIEW_HEIGHT as i32 {
-                let (cam_x, cam_y) = self.camera();
-                let target = Point::new(cam_x + mx, cam_y + my);
+            let mouse = Point::new(mx, my);
+            let view = self.camera();
+            if Rect::new(0, 0, view.width, view.height).contains(mouse) {
+                let target = view.origin() + mouse;
                 match &mut self.mode {
-                    GameMode::Exploring => {
-                        self.player.pos = target;
-                        self.depth = self.map.depth(target);
-                    }
-                    GameMode::Aiming { target: t } => {
-                        t.x = target.x.clamp(0, self.map.width as i32 - 1);
-                        t.y = target.y.clamp(0, self.map.height as i32 - 1);
-                    }
+                    GameMode::Exploring => match self.blocked_reason(target) {
+                        Some(reason) => {
+                            self.ui.add_log(reason).ok();
+                        }
+                        None if self.player.aboard && self.player.boat.anchored => {
+                            self.ui.add_log("Weigh anchor before moving.").ok();
+                        }
+                        None => self.place_player(target),
+                    },
+                    GameMode::Aiming { .. } => self.aim_at(target),
                     _ => {}
                 }
             }
         }
         if let Some(key) = ctx.key {
-            self.handle_input_key(Some(key), ctx);
+            self.handle_input_key(Some(key));
+        }
+    }
+
+    /// Movement direction bound to `key`, if any.
+    fn key_direction(&self, key: VirtualKeyCode) -> Option<Direction> {
+        use VirtualKeyCode::*;
+        match key {
+            k if k == Left || k == self.input.left => Some(Direction::West),
+            k if k == Right || k == self.input.right => Some(Direction::East),
+            k if k == Up || k == self.input.up => Some(Direction::North),
+            k if k == Down || k == self.input.down => Some(Direction::South),
+            k if k == self.input.up_left => Some(Direction::NorthWest),
+            k if k == self.input.up_right => Some(Direction::NorthEast),
+            k if k == self.input.down_left => Some(Direction::SouthWest),
+            k if k == self.input.down_right => Some(Direction::SouthEast),
+            _ => None,
         }
     }
 
     /// Handles an input key without relying on BTerm.
-    fn handle_input_key(&mut self, key: Option<VirtualKeyCode>, ctx: &mut BTerm) {
+    fn handle_input_key(&mut self, key: Option<VirtualKeyCode>) {
         self.reeling = false;
         if let Some(key) = key {
-            use VirtualKeyCode::*;
+            if self.ui.layout() == UILayout::Achievements {
+                // The list is read-only; only closing it is handled
+                if key == self.input.options || key == VirtualKeyCode::T {
+                    self.ui.set_layout(UILayout::Options);
+                }
+                return;
+            }
+            if self.ui.layout() == UILayout::Codex {
+                if key == self.input.codex || key == VirtualKeyCode::Escape {
+                    self.ui.set_layout(UILayout::Standard);
+                }
+                return;
+            }
+            if self.ui.layout() == UILayout::Journal {
+                if key == self.input.journal || key == VirtualKeyCode::Escape {
+                    self.ui.set_layout(UILayout::Standard);
+                }
+                return;
+            }
+            if key == VirtualKeyCode::Escape && matches!(self.mode, GameMode::Aiming { .. }) {
+                self.cancel_aim();
+                return;
+            }
+            if key == VirtualKeyCode::Escape && self.in_tutorial() {
+                self.skip_tutorial();
+                return;
+            }
+            if key == VirtualKeyCode::Escape || key == self.input.quit {
+                self.pause();
+                return;
+            }
             if key == self.input.cast {
                 match &mut self.mode {
                     GameMode::Exploring => {
@@ -389,7 +1094,17 @@ impl LurhookGame {
                         return;
                     }
                     GameMode::Aiming { .. } => {
-                        self.confirm_cast();
+                        match self.throwing {
+                            Some(Throw::Chum) => self.throw_chum(),
+                            Some(Throw::Net) => self.throw_net(),
+                            Some(Throw::Longline) => self.set_longline(),
+                            Some(Throw::Trap) => self.set_trap(),
+                            None => self.confirm_cast(),
+                        }
+                        return;
+                    }
+                    GameMode::Fishing { bite: Some(_), .. } => {
+                        self.strike_bite();
                         return;
                     }
                     _ => {}
@@ -399,6 +1114,16 @@ impl LurhookGame {
                 self.reeling = true;
                 return;
             }
+            if matches!(self.mode, GameMode::Aiming { .. }) && self.throwing.is_none() {
+                if key == self.input.lure_depth {
+                    self.cycle_lure_depth();
+                    return;
+                }
+                if key == self.input.reel {
+                    self.cycle_retrieve();
+                    return;
+                }
+            }
             if key == self.input.scroll_up {
                 self.ui.scroll_up();
                 return;
@@ -428,59 +1153,102 @@ impl LurhookGame {
             if self.ui.layout() == UILayout::Options {
                 match key {
                     VirtualKeyCode::C => self.toggle_colorblind(),
-                    VirtualKeyCode::Plus => {
-                        if self.input.volume < 10 {
-                            self.input.volume += 1;
-                            let _ = self.input.save(CONFIG_PATH);
-                            self.audio.set_volume(self.input.volume);
-                        }
+                    VirtualKeyCode::Plus if self.input.sfx_volume < 10 => {
+                        self.input.sfx_volume += 1;
+                        let _ = self.input.save(self.storage.as_ref(), CONFIG_PATH);
+                        self.audio.set_sfx_volume(self.input.sfx_volume);
                     }
-                    VirtualKeyCode::Minus => {
-                        if self.input.volume > 0 {
-                            self.input.volume -= 1;
-                            let _ = self.input.save(CONFIG_PATH);
-                            self.audio.set_volume(self.input.volume);
-                        }
+                    VirtualKeyCode::Minus if self.input.sfx_volume > 0 => {
+                        self.input.sfx_volume -= 1;
+                        let _ = self.input.save(self.storage.as_ref(), CONFIG_PATH);
+                        self.audio.set_sfx_volume(self.input.sfx_volume);
                     }
-                    VirtualKeyCode::LBracket => {
-                        if self.input.font_scale > 1 {
-                            self.input.font_scale -= 1;
-                            let _ = self.input.save(CONFIG_PATH);
-                        }
+                    VirtualKeyCode::Period if self.input.music_volume < 10 => {
+                        self.input.music_volume += 1;
+                        let _ = self.input.save(self.storage.as_ref(), CONFIG_PATH);
+                        self.audio.set_music_volume(self.input.music_volume);
                     }
-                    VirtualKeyCode::RBracket => {
-                        if self.input.font_scale < 4 {
-                            self.input.font_scale += 1;
-                            let _ = self.input.save(CONFIG_PATH);
-                        }
+                    VirtualKeyCode::Comma if self.input.music_volume > 0 => {
+                        self.input.music_volume -= 1;
+                        let _ = self.input.save(self.storage.as_ref(), CONFIG_PATH);
+                        self.audio.set_music_volume(self.input.music_volume);
+                    }
+                    VirtualKeyCode::M => self.toggle_mute(),
+                    VirtualKeyCode::LBracket if self.input.font_scale > 1 => {
+                        self.input.font_scale -= 1;
+                        let _ = self.input.save(self.storage.as_ref(), CONFIG_PATH);
+                    }
+                    VirtualKeyCode::RBracket if self.input.font_scale < 4 => {
+                        self.input.font_scale += 1;
+                        let _ = self.input.save(self.storage.as_ref(), CONFIG_PATH);
                     }
                     VirtualKeyCode::Key1 => {
                         self.cycle_cast_key();
                     }
+                    VirtualKeyCode::A => self.toggle_adaptive(),
+                    VirtualKeyCode::R => self.toggle_realtime_fights(),
+                    VirtualKeyCode::P => self.cycle_palette(),
+                    VirtualKeyCode::F => self.toggle_fullscreen(),
+                    VirtualKeyCode::V => self.toggle_vsync(),
+                    VirtualKeyCode::W => self.cycle_window_size(),
+                    VirtualKeyCode::T => self.ui.set_layout(UILayout::Achievements),
+                    VirtualKeyCode::K => self.open_keybindings(),
                     _ => {}
                 }
                 return;
             }
-            if key == self.input.save {
-                match self.save_game(SAVE_PATH) {
-                    Ok(_) => {
-                        self.ui.add_log("Game saved.").ok();
-                    }
-                    Err(e) => {
-                        self.ui.add_log(&format!("Save failed: {}", e)).ok();
-                    }
-                }
+            if key == self.input.codex {
+                self.ui.set_layout(UILayout::Codex);
+                return;
+            }
+            if key == self.input.minimap {
+                self.show_minimap = !self.show_minimap;
+                return;
+            }
+            if key == self.input.journal {
+                self.ui.set_layout(UILayout::Journal);
+                return;
+            }
+            if key == self.input.trail {
+                self.show_trail = !self.show_trail;
+                return;
+            }
+            if key == self.input.export_map {
+                self.save_map_export();
+                return;
+            }
+            if key == self.input.photo && self.mode == GameMode::Exploring && !self.inventory_focus
+            {
+                self.enter_photo();
                 return;
             }
-            if key == self.input.quit {
-                ctx.quit();
+            if self.mode == GameMode::Exploring && !self.inventory_focus {
+                if key == self.input.board {
+                    self.toggle_boat();
+                    return;
+                }
+                if key == self.input.anchor {
+                    self.toggle_anchor();
+                    return;
+                }
+                if key == self.input.chum {
+                    self.start_chum();
+                    return;
+                }
+                if key == self.input.campfire {
+                    self.build_campfire();
+                    return;
+                }
+            }
+            if key == self.input.save {
+                self.quick_save();
                 return;
             }
             if key == self.input.end_run {
                 if self.inventory_focus {
                     self.activate_selected_item();
                 } else if matches!(self.mode, GameMode::Exploring) {
-                    self.end_run();
+                    self.confirm_end_run();
                 }
                 return;
             }
@@ -503,18 +1271,8 @@ impl LurhookGame {
                 self.eat_canned_food();
                 return;
             }
-            let delta = match key {
-                k if k == Left || k == self.input.left => Point::new(-1, 0),
-                k if k == Right || k == self.input.right => Point::new(1, 0),
-                k if k == Up || k == self.input.up => Point::new(0, -1),
-                k if k == Down || k == self.input.down => Point::new(0, 1),
-                k if k == self.input.up_left => Point::new(-1, -1),
-                k if k == self.input.up_right => Point::new(1, -1),
-                k if k == self.input.down_left => Point::new(-1, 1),
-                k if k == self.input.down_right => Point::new(1, 1),
-                _ => Point::new(0, 0),
-            };
-            if delta.x != 0 || delta.y != 0 {
+            if let Some(direction) = self.key_direction(key) {
+                let delta = direction.delta();
                 if self.inventory_focus {
                     let total = self.player.items.len() + self.player.inventory.len();
                     if delta.y < 0 && self.inventory_cursor > 0 {
@@ -524,11 +1282,8 @@ impl LurhookGame {
                         self.inventory_cursor += 1;
                     }
                 } else {
-                    match &mut self.mode {
-                        GameMode::Aiming { target } => {
-                            target.x = (target.x + delta.x).clamp(0, self.map.width as i32 - 1);
-                            target.y = (target.y + delta.y).clamp(0, self.map.height as i32 - 1);
-                        }
+                    match self.mode {
+                        GameMode::Aiming { target } => self.aim_at(target + delta),
                         _ => {
                             self.try_move(delta);
                         }
@@ -540,31 +1295,68 @@ impl LurhookGame {
 
     fn cast(&mut self) {
         if self.player.line <= 0 {
-            self.ui.add_log("Your line is broken!").ok();
+            self.ui
+                .add_entry(LogCategory::Fishing, "Your line is broken!")
+                .ok();
             return;
         }
         if self.fishes.is_empty() {
-            self.ui.add_log("No fish around.").ok();
+            self.ui
+                .add_entry(LogCategory::Fishing, "No fish around.")
+                .ok();
             return;
         }
-        self.ui.add_log("Select target...").ok();
+        self.ui
+            .add_entry(LogCategory::Fishing, "Select target...")
+            .ok();
         self.mode = GameMode::Aiming {
             target: self.player.pos,
         };
     }
 
+    /// Moves the aiming cursor to `target`, kept on the map and within
+    /// [`Self::aim_range`] of the player.
+    fn aim_at(&mut self, target: common::Point) {
+        let range = self.aim_range();
+        let pos = self.player.pos;
+        let target = common::Point::new(
+            target.x.clamp(pos.x - range, pos.x + range),
+            target.y.clamp(pos.y - range, pos.y + range),
+        );
+        if let GameMode::Aiming { target: t } = &mut self.mode {
+            *t = self.map.bounds().clamp(target);
+        }
+    }
+
     fn confirm_cast(&mut self) {
         if let GameMode::Aiming { target } = self.mode {
-            self.ui.add_log("Casting...").ok();
+            self.ui.add_entry(LogCategory::Fishing, "Casting...").ok();
             self.cast_path = Some(Self::line_path(self.player.pos, target));
+            self.bait = Some(target);
             self.cast_step = 0;
             self.ui.set_layout(UILayout::Fishing);
-            self.mode = GameMode::Fishing { wait: 2 };
+            self.mode = GameMode::Fishing {
+                wait: 2,
+                bite: None,
+            };
         }
     }
 
+    /// Leaves aiming without casting or throwing anything.
+    fn cancel_aim(&mut self) {
+        let msg = match self.throwing.take() {
+            Some(Throw::Chum) => "You keep your chum.",
+            Some(Throw::Net) => "You fold up your net.",
+            Some(Throw::Longline) => "You coil up your longline.",
+            Some(Throw::Trap) => "You keep your trap.",
+            None => "Cast cancelled.",
+        };
+        self.mode = GameMode::Exploring;
+        self.ui.add_entry(LogCategory::Fishing, msg).ok();
+    }
+
     fn update_fishing(&mut self) {
-        if let GameMode::Fishing { ref mut wait } = self.mode {
+        if let GameMode::Fishing { ref mut wait, .. } = self.mode {
             if *wait > 0 {
                 if let Some(path) = &self.cast_path {
                     if self.cast_step < path.len() {
@@ -573,103 +1365,130 @@ impl LurhookGame {
                         self.cast_path = None;
                     }
                 }
+                // The current carries the line while it waits for a bite
+                self.bait = self.bait.map(|b| self.map.drift(b));
                 *wait -= 1;
                 return;
             }
 
             if self.meter.is_none() {
-                let tile = if let Some(f) = self.fishes.first() {
-                    self.map.tiles[self.map.idx(f.position)]
-                } else {
-                    TileKind::ShallowWater
-                };
-                let chance = fishing::bite_probability(tile, self.player.bait_bonus);
-                let bite = self.rng.range(0.0, 1.0) < chance;
-                if bite {
-                    self.ui.add_log("Hooked a fish!").ok();
-                    let _ = self.audio.play(Sound::Hit);
-                    if let Some(f) = self.fishes.first() {
-                        let mut m = TensionMeter::new(
-                            f.kind.strength,
-                            f.kind.fight_style,
-                            self.player.reel_factor,
-                        );
-                        m.max_tension += self.player.tension_bonus;
-                        self.meter = Some(m);
-                    } else {
-                        let mut m = TensionMeter::default();
-                        m.max_tension += self.player.tension_bonus;
-                        self.meter = Some(m);
+                if let GameMode::Fishing {
+                    bite: Some(bite), ..
+                } = self.mode
+                {
+                    self.update_bite(bite);
+                    return;
+                }
+                match self.strike() {
+                    Some(i) => self.take_bait(i),
+                    None => {
+                        self.events.push(GameEvent::NoBite);
+                        self.splash();
+                        self.mode = GameMode::Exploring;
+                        self.ui.set_layout(UILayout::Standard);
                     }
-                } else {
-                    self.ui.add_log("The fish got away...").ok();
-                    self.mode = GameMode::Exploring;
-                    self.ui.set_layout(UILayout::Standard);
                 }
                 return;
             }
 
             if let Some(mut meter) = self.meter.take() {
                 use fishing::MeterState;
-                match meter.update(self.reeling) {
+                let stage = meter.stage;
+                let state = meter.update(self.reeling);
+                let peak = meter.peak_tension;
+                match state {
                     MeterState::Ongoing => {
+                        if meter.stage > stage {
+                            self.announce_stage(&meter);
+                        }
                         self.meter = Some(meter);
                     }
                     MeterState::Success => {
-                        if let Some(fish) = self.fishes.pop() {
-                            let id = fish.kind.id.clone();
-                            self.player.inventory.push(fish.kind);
-                            let _ = self.codex.record_capture(CODEX_PATH, &id);
-                            self.ui.add_log("Caught a fish!").ok();
-                            let _ = self.audio.play(Sound::Catch);
-                            self.check_area_upgrade();
+                        self.adaptive.record(true);
+                        let hooked = self.hooked.take().filter(|&i| i < self.fishes.len());
+                        if let Some(i) = hooked {
+                            let fish = self.fishes.remove(i);
+                            self.land(Catch::new(fish.kind, fish.weight));
                         }
                         self.mode = GameMode::Exploring;
                         self.ui.set_layout(UILayout::Standard);
                     }
                     MeterState::Broken => {
-                        self.ui.add_log("Line snapped!").ok();
-                        let _ = self.audio.play(Sound::LineSnap);
+                        self.adaptive.record(false);
+                        let lure_lost = self.snap_takes_lure();
+                        self.events.push(GameEvent::LineSnapped {
+                            id: self.hooked_id(),
+                            lure_lost,
+                        });
+                        self.hooked = None;
+                        self.splash();
                         if self.player.line > 0 {
-                            self.player.line = (self.player.line - LINE_DAMAGE).max(0);
+                            self.player.line = (self.player.line - self.rules.line_damage).max(0);
                             if self.player.line == 0 {
-                                self.ui.add_log("Your line is ruined.").ok();
+                                self.events.push(GameEvent::LineRuined);
                             }
                         }
                         self.mode = GameMode::Exploring;
                         self.ui.set_layout(UILayout::Standard);
                     }
                     MeterState::Lost => {
-                        self.ui.add_log("The fish escaped!").ok();
+                        self.adaptive.record(false);
+                        self.events.push(GameEvent::FishEscaped {
+                            id: self.hooked_id(),
+                        });
+                        self.hooked = None;
+                        self.splash();
                         self.mode = GameMode::Exploring;
                         self.ui.set_layout(UILayout::Standard);
                     }
                 }
+                if self.meter.is_none() {
+                    self.wear_gear(peak);
+                }
             }
         }
     }
 
+    /// Adds a landed fish to the catch and records it.
+    fn land(&mut self, mut catch: Catch) {
+        self.metrics.record_catch();
+        self.record_stock(&catch.kind);
+        self.events.push(GameEvent::FishCaught {
+            id: catch.kind.id.clone(),
+            name: catch.kind.name.clone(),
+            weight: catch.weight,
+            description: catch.kind.description.clone(),
+            habitat_note: catch.kind.habitat_note.clone(),
+            location: self.area.name.clone(),
+            turn: self.turn,
+            timestamp: unix_time(),
+        });
+        catch.stamp = self.turn;
+        self.player.inventory.push(catch);
+    }
+
     fn eat_fish(&mut self) {
-        if let Some(_fish) = self.player.inventory.pop() {
-            self.player.hunger = (self.player.hunger + EAT_RAW_FISH).min(MAX_HUNGER);
-            self.ui.add_log("You ate a raw fish.").ok();
+        if let Some(idx) = self.next_meal() {
+            self.eat_catch(idx);
         } else {
             self.ui.add_log("No fish to eat.").ok();
         }
     }
 
     fn cook_fish(&mut self) {
-        let idx = self.map.idx(self.player.pos);
-        if self.map.tiles[idx] != TileKind::Land {
-            self.ui.add_log("You need to be on land to cook.").ok();
+        if !self.near_campfire() {
+            self.ui
+                .add_log("You need a campfire to cook. Build one from driftwood.")
+                .ok();
             return;
         }
-        if let Some(_fish) = self.player.inventory.pop() {
-            self.player.hunger = (self.player.hunger + EAT_COOKED_FISH).min(MAX_HUNGER);
-            self.player.hp = (self.player.hp + COOK_HP_RESTORE).min(MAX_HP);
-            self.ui.add_log("You cooked and ate a fish.").ok();
-        } else {
+        if !self.cook_dish() {
             self.ui.add_log("No fish to cook.").ok();
+            return;
+        }
+        // What the dish left over keeps for later
+        if self.cook_catch() > 0 {
+            self.ui.add_log("You cook the rest of your catch.").ok();
         }
     }
 
@@ -694,12 +1513,15 @@ impl LurhookGame {
                         self.player.items.push(old);
                     }
                     self.player.tension_bonus = item.tension_bonus;
+                    self.player.rod_durability = MAX_GEAR_DURABILITY;
                 }
                 Reel => {
                     if let Some(old) = self.player.reel.replace(item.clone()) {
                         self.player.items.push(old);
                     }
                     self.player.reel_factor = item.reel_factor;
+                    self.player.reel_durability = MAX_GEAR_DURABILITY;
+                    self.player.cast_range = item.max_cast_range;
                 }
                 Lure => {
                     if let Some(old) = self.player.lure.replace(item.clone()) {
@@ -711,14 +1533,46 @@ impl LurhookGame {
                     self.player.hunger = (self.player.hunger + EAT_CANNED_FOOD).min(MAX_HUNGER);
                     self.ui.add_log("You ate food.").ok();
                 }
-            }
-        } else {
-            let fidx = idx - self.player.items.len();
-            if fidx < self.player.inventory.len() {
-                self.player.inventory.remove(fidx);
-                self.player.hunger = (self.player.hunger + EAT_RAW_FISH).min(MAX_HUNGER);
-                self.ui.add_log("You ate a raw fish.").ok();
-            }
+                Line => {
+                    self.player.line = MAX_LINE;
+                    self.ui.add_log("You respool your line.").ok();
+                }
+                Sonar => {
+                    if let Some(old) = self.player.sonar.replace(item) {
+                        self.player.items.push(old);
+                    }
+                    self.ui.add_log("Your fishfinder hums to life.").ok();
+                }
+                Repair => {
+                    if !self.repair_gear() {
+                        self.player.items.insert(idx, item);
+                    }
+                }
+                Bait => {
+                    // Thrown from the aiming cursor, which uses it up
+                    self.player.items.insert(idx, item);
+                    self.start_chum();
+                }
+                Net => {
+                    self.player.items.insert(idx, item);
+                    self.start_net();
+                }
+                Longline => {
+                    // Set from the aiming cursor, which takes it along
+                    self.player.items.insert(idx, item);
+                    self.start_longline();
+                }
+                Trap => {
+                    self.player.items.insert(idx, item);
+                    self.start_trap();
+                }
+                Water => self.drink_water(),
+            }
+        } else {
+            let fidx = idx - self.player.items.len();
+            if fidx < self.player.inventory.len() {
+                self.eat_catch(fidx);
+            }
         }
         let total = self.player.items.len() + self.player.inventory.len();
         if self.inventory_cursor >= total && total > 0 {
@@ -726,98 +1580,157 @@ impl LurhookGame {
         }
     }
 
-    /// Saves a minimal game state to a RON-like file at `path`.
-    pub fn save_game(&self, path: &str) -> GameResult<()> {
-        let content = format!(
-            "(player:(pos:(x:{}, y:{}), hp:{}, hunger:{}, food:{}), time_of_day:\"{}\")",
-            self.player.pos.x,
-            self.player.pos.y,
-            self.player.hp,
-            self.player.hunger,
-            self.player.canned_food,
-            self.time_of_day
-        );
-        std::fs::write(path, content)?;
-        Ok(())
-    }
-
-    /// Loads a minimal game state from a RON-like file at `path`.
-    pub fn load_game(path: &str) -> GameResult<Self> {
-        let data = std::fs::read_to_string(path)?;
-        // very small parser for the expected format
-        fn parse_i32(s: &str, key: &str) -> GameResult<i32> {
-            let start = s
-                .find(key)
-                .ok_or_else(|| GameError::Parse(format!("missing {}", key)))?;
-            let s = &s[start + key.len()..];
-            let end = s
-                .find(|c: char| [',', ')'].contains(&c))
-                .ok_or_else(|| GameError::Parse(format!("malformed {}", key)))?;
-            s[..end]
-                .trim()
-                .parse()
-                .map_err(|_| GameError::Parse(format!("invalid {}", key)))
-        }
-
-        fn parse_str<'a>(s: &'a str, key: &str) -> GameResult<&'a str> {
-            let start = s
-                .find(key)
-                .ok_or_else(|| GameError::Parse(format!("missing {}", key)))?;
-            let s = &s[start + key.len()..];
-            let start_quote = s
-                .find('"')
-                .ok_or_else(|| GameError::Parse(format!("malformed {}", key)))?
-                + 1;
-            let end_quote = s[start_quote..]
-                .find('"')
-                .ok_or_else(|| GameError::Parse(format!("malformed {}", key)))?;
-            Ok(&s[start_quote..start_quote + end_quote])
-        }
-
-        let mut game = Self::new(0)?;
-        game.player.pos.x = parse_i32(&data, "x:")?;
-        game.player.pos.y = parse_i32(&data, "y:")?;
-        game.player.hp = parse_i32(&data, "hp:")?;
-        game.player.hunger = parse_i32(&data, "hunger:")?;
-        game.player.canned_food = parse_i32(&data, "food:")?;
-        let tod = parse_str(&data, "time_of_day:")?;
-        game.time_of_day = match tod {
-            "Dawn" => "Dawn",
-            "Day" => "Day",
-            "Dusk" => "Dusk",
-            "Night" => "Night",
-            other => return Err(GameError::Parse(format!("invalid time_of_day {}", other))),
+    /// Saves the full run state as compressed JSON under `key`, keeping the
+    /// previous save as a backup.
+    pub fn save_game(&self, key: &str) -> GameResult<()> {
+        let content = SaveState::of(self).to_json()?;
+        save::write_save(self.storage.as_ref(), key, &content)
+    }
+
+    /// Writes the codex file if there were catches or escapes since it
+    /// was last written, logging a failure. Saves, area changes and the
+    /// end of the run call this.
+    pub fn flush_codex(&mut self) {
+        if let Err(e) = self.codex.flush(self.storage.as_ref()) {
+            self.ui.add_log(&format!("Codex save failed: {}", e)).ok();
+        }
+    }
+
+    /// Saves the run in the save slot and logs how it went. The tutorial
+    /// is never saved.
+    pub(crate) fn quick_save(&mut self) {
+        if self.in_tutorial() {
+            self.ui.add_log("The tutorial cannot be saved.").ok();
+            return;
+        }
+        self.flush_codex();
+        match self.save_game(SAVE_PATH) {
+            Ok(_) => {
+                self.ui.add_log("Game saved.").ok();
+            }
+            Err(e) => {
+                self.ui.add_log(&format!("Save failed: {}", e)).ok();
+            }
+        }
+    }
+
+    /// Loads a saved run from the default storage.
+    pub fn load_game(key: &str) -> GameResult<Self> {
+        Self::load_game_from(default_storage(), key)
+    }
+
+    /// Loads the run saved under `key`, using the backup if the save is
+    /// corrupted. An empty save slot falls back to the slot older versions
+    /// wrote; the next save moves the run over. The loaded game keeps using
+    /// `storage`.
+    pub fn load_game_from(storage: Box<dyn Storage>, key: &str) -> GameResult<Self> {
+        let legacy = key == SAVE_PATH
+            && storage.read(SAVE_PATH)?.is_none()
+            && storage.read(LEGACY_SAVE_PATH)?.is_some();
+        let key = if legacy { LEGACY_SAVE_PATH } else { key };
+        let data = save::read_save(storage.as_ref(), key)?;
+        let mut game = if data.starts_with('(') {
+            savestate::restore_legacy(&data, storage)?
+        } else {
+            SaveState::from_json(&data)?.restore(storage)?
         };
+        if legacy {
+            game.ui
+                .add_log(&format!(
+                    "Loaded {} from an older version; saving writes {}.",
+                    LEGACY_SAVE_PATH, SAVE_PATH
+                ))
+                .ok();
+        }
         Ok(game)
     }
 
-    fn check_area_upgrade(&mut self) {
-        let total = self.codex.total_captures();
-        match self.area {
-            Area::Coast if total >= 3 => {
-                self.area = Area::Offshore;
-                self.seed += 1;
-                let (w, h) = self.area.size();
-                self.map = generate(self.seed, w, h).expect("map");
-                self.fishes =
-                    spawn_fish_population(&mut self.map, &self.fish_types, 5).expect("fish");
-                self.player.pos =
-                    common::Point::new(self.map.width as i32 / 2, self.map.height as i32 / 2);
-                self.ui.add_log("Unlocked offshore area!").ok();
-            }
-            Area::Offshore if total >= 6 => {
-                self.area = Area::DeepSea;
-                self.seed += 1;
-                let (w, h) = self.area.size();
-                self.map = generate(self.seed, w, h).expect("map");
-                self.fishes =
-                    spawn_fish_population(&mut self.map, &self.fish_types, 5).expect("fish");
-                self.player.pos =
-                    common::Point::new(self.map.width as i32 / 2, self.map.height as i32 / 2);
-                self.ui.add_log("Unlocked deep sea!").ok();
-            }
-            _ => {}
+    /// Writes the codex and lifetime stats as a report; returns the key written.
+    pub(crate) fn export_codex(&self, format: ExportFormat) -> GameResult<String> {
+        let key = format!("{}.{}", CODEX_REPORT_PATH, format.extension());
+        self.codex
+            .export(self.storage.as_ref(), &key, &self.fish_types, format)?;
+        Ok(key)
+    }
+
+    /// A failed catch splashes where the line was, spooking fish nearby.
+    fn splash(&mut self) {
+        if let Some(bait) = self.bait {
+            self.splashes.push(bait);
+        }
+    }
+
+    /// The fish on the line.
+    fn hooked_fish(&self) -> Option<&Fish> {
+        self.hooked.and_then(|i| self.fishes.get(i))
+    }
+
+    /// Species id of the fish on the line.
+    fn hooked_id(&self) -> Option<String> {
+        self.hooked_fish().map(|f| f.kind.id.clone())
+    }
+
+    /// Rolls a strike for each fish within [`fishing::STRIKE_RADIUS`] of
+    /// where the cast landed, nearest first; returns the index of the first
+    /// fish that bites.
+    fn strike(&mut self) -> Option<usize> {
+        let target = self.bait.unwrap_or(self.player.pos);
+        let chummed = self.chummed(target);
+        let bonus = self.player.bait_bonus
+            + self.rules.bite_bonus
+            + self.adaptive.bite_bonus()
+            + self.weather.weather.bite_bonus();
+        for (i, distance) in ecology::fish_near(&self.fishes, target, fishing::STRIKE_RADIUS) {
+            let fish = &self.fishes[i];
+            let tile = self.map.tiles[self.map.idx(fish.position)];
+            let temperature = self.season().water_temperature();
+            let twilight = matches!(self.time_of_day, "Dawn" | "Dusk");
+            let base = fishing::bite_probability(
+                tile,
+                bonus
+                    + fishing::temperature_bonus(temperature, &fish.kind)
+                    + fishing::tide_bonus(self.high_water(), twilight),
+                fish.feeding_state(),
+                chummed,
+                self.player.presentation,
+                &fish.kind,
+            );
+            if self.rng.range(0.0, 1.0) < fishing::strike_probability(base, distance) {
+                return Some(i);
+            }
+        }
+        None
+    }
+
+    /// Grants rewards for codex milestones newly reached in the current area.
+    fn check_codex_rewards(&mut self) {
+        let area = self.area.clone();
+        let completion = self
+            .codex
+            .completion(self.fish_types.iter().filter(|f| area.holds(f)));
+        let rewards = self.profile.unlock_milestones(&area.id, completion);
+        for percent in MILESTONES {
+            if self.profile.reached(&area.id, percent) {
+                self.unlock_achievement(&milestone_achievement(&area.id, percent));
+            }
+        }
+        if rewards.is_empty() {
+            return;
         }
+        for reward in &rewards {
+            self.ui
+                .add_log(&format!(
+                    "{} codex {:.0}%: unlocked {}!",
+                    area.name,
+                    completion,
+                    reward.describe()
+                ))
+                .ok();
+            if let Reward::Lure(item) = reward {
+                self.player.items.push(item.clone());
+            }
+        }
+        let _ = self.profile.save(self.storage.as_ref(), PROFILE_PATH);
     }
 }
 
@@ -829,101 +1742,287 @@ impl Default for LurhookGame {
 
 impl GameState for LurhookGame {
     fn tick(&mut self, ctx: &mut BTerm) {
+        self.update(ctx);
+        self.render(ctx);
+    }
+}
+
+impl LurhookGame {
+    /// Applies one frame of input from `ctx` and advances the simulation.
+    ///
+    /// Only the input fields of `ctx` are used, so any backend can drive the
+    /// game by filling them in.
+    pub fn update(&mut self, ctx: &mut BTerm) {
+        self.mouse_input(ctx);
+        let tick = self.fight_tick(ctx.frame_time_ms);
+        self.update_frame(ctx, tick);
+        self.update_music(ctx.frame_time_ms);
+    }
+
+    /// Applies a frame of input, with a real-time fishing turn after it
+    /// when `tick` is set.
+    pub(crate) fn update_frame(&mut self, ctx: &mut BTerm, tick: bool) {
+        self.record_input(ctx, tick);
+        self.step(ctx, tick);
+        self.dispatch_events();
+        self.update_tutorial();
+    }
+
+    fn step(&mut self, ctx: &mut BTerm, tick: bool) {
+        self.ui.next_frame();
+        self.ui.set_turn(self.turn);
+        if ctx.key.is_some() && (self.ui.dismiss_popup() || self.ui.dismiss_catch_card()) {
+            // The key press only closes the popup or the catch card.
+            ctx.key = None;
+        }
+        if self.ui.prompt().is_some() {
+            self.handle_prompt_input(ctx);
+            return;
+        }
+        if let GameMode::Photo { .. } = self.mode {
+            if let Some(key) = ctx.key {
+                self.handle_photo_key(key);
+            }
+            return;
+        }
+        if let GameMode::Trading { .. } = self.mode {
+            if let Some(key) = ctx.key {
+                self.handle_trading_key(key);
+            }
+            return;
+        }
+        if self.ui.layout() == UILayout::Keybindings {
+            if let Some(key) = ctx.key {
+                self.handle_binding_key(key);
+            }
+            return;
+        }
+        if self.ui.layout() == UILayout::MessageLog {
+            if let Some(key) = ctx.key {
+                self.handle_message_log_key(key);
+            }
+            return;
+        }
+        if ctx.key == Some(self.input.message_log) {
+            // Reading the log does not use up a turn
+            self.ui.open_message_log();
+            return;
+        }
         let key = ctx.key;
         let click = ctx.left_click;
+        let realtime = self.realtime_fishing();
         self.handle_input(ctx);
+        if let GameMode::Photo { .. } | GameMode::Trading { .. } = self.mode {
+            // Entering photo mode or trading does not use up a turn
+            return;
+        }
+        if self.ui.prompt().is_some() {
+            // Nor does being asked something
+            return;
+        }
+        if realtime {
+            // Keys only steer the fight; the clock passes the turns
+            self.reel_queued |= self.reeling;
+            if tick {
+                self.reeling = std::mem::take(&mut self.reel_queued);
+                self.pass_turn();
+            }
+            return;
+        }
+        self.reel_queued = false;
         if key.is_some() || click {
-            self.advance_time();
-            match self.mode {
-                GameMode::Exploring => {
-                    let drift = self.current_drift();
-                    update_fish(
-                        &self.map,
-                        &mut self.fishes,
-                        &mut self.rng,
-                        self.time_of_day,
-                        drift,
-                    )
-                    .expect("fish update");
-                }
-                GameMode::Aiming { .. } => {}
-                GameMode::Fishing { .. } => self.update_fishing(),
-                GameMode::End { score } => {
-                    ctx.cls();
-                    ctx.print_centered(12, "Run Complete!");
-                    ctx.print_centered(13, format!("Final score: {}", score));
-                    return;
+            self.pass_turn();
+        }
+    }
+
+    /// Plays out the rest of a turn after the player has acted.
+    fn pass_turn(&mut self) {
+        self.advance_time();
+        match self.mode {
+            GameMode::Exploring => self.move_fish(),
+            GameMode::Aiming { .. } => {}
+            GameMode::Fishing { bite, .. } => {
+                // Fish keep swimming (toward the bait) until one bites
+                if self.meter.is_none() && bite.is_none() {
+                    self.move_fish();
                 }
+                self.update_fishing();
             }
-            self.update_hazards();
-        } else if matches!(self.mode, GameMode::End { .. }) {
-            if let GameMode::End { score } = self.mode {
-                ctx.cls();
-                ctx.print_centered(12, "Run Complete!");
-                ctx.print_centered(13, format!("Final score: {}", score));
-                return;
-            }
+            GameMode::End { .. }
+            | GameMode::GameOver { .. }
+            | GameMode::Photo { .. }
+            | GameMode::Trading { .. } => return,
+        }
+        self.update_migrations();
+        self.update_rivals();
+        self.update_hazards();
+        self.update_chum();
+        self.update_methods();
+        self.update_traps();
+        self.update_campfires();
+        self.autosave_if_due();
+    }
+
+    /// Draws the current screen through any [`Renderer`] backend.
+    pub fn render(&self, ctx: &mut dyn Renderer) {
+        self.ui.fit(ctx);
+        if let Some(score) = self.final_score() {
+            ui::draw_summary(ctx, &self.summary_heading(score), &self.metrics);
+            return;
         }
         ctx.cls();
         if self.ui.layout() == UILayout::Help {
             self.ui.draw_help(ctx).ok();
             return;
         }
+        if self.ui.layout() == UILayout::Achievements {
+            self.ui
+                .draw_achievements(ctx, &self.achievement_views())
+                .ok();
+            return;
+        }
+        if self.ui.layout() == UILayout::Codex {
+            self.ui.draw_codex(ctx, &self.codex_views()).ok();
+            return;
+        }
+        if self.ui.layout() == UILayout::Journal {
+            self.ui.draw_journal(ctx, &self.journal_lines()).ok();
+            return;
+        }
+        if self.ui.layout() == UILayout::Keybindings {
+            self.ui.draw_keybindings(ctx, &self.keybindings_view()).ok();
+            return;
+        }
+        if self.ui.layout() == UILayout::MessageLog {
+            self.ui.draw_message_log(ctx).ok();
+            return;
+        }
         if self.ui.layout() == UILayout::Options {
             self.ui
                 .draw_options(
                     ctx,
-                    self.input.colorblind,
-                    self.input.volume,
-                    self.input.cast,
-                    self.input.font_scale,
+                    &OptionsView {
+                        colorblind: self.input.colorblind,
+                        sfx_volume: self.input.sfx_volume,
+                        music_volume: self.input.music_volume,
+                        muted: self.input.muted,
+                        cast_key: self.input.cast,
+                        font_scale: self.input.font_scale,
+                        adaptive: self.input.adaptive,
+                        realtime_fights: self.input.realtime_fights,
+                        palette: &self.input.palette,
+                        fullscreen: self.input.fullscreen,
+                        vsync: self.input.vsync,
+                        window_size: input::window_size_name(self.input.window_size),
+                    },
                 )
                 .ok();
             return;
         }
-        self.draw_map(ctx);
-        self.draw_fish(ctx);
-        self.draw_hazards(ctx);
-        let (cam_x, cam_y) = self.camera();
-        ctx.set(
-            self.player.pos.x - cam_x,
-            self.player.pos.y - cam_y,
-            self.palette.player,
-            RGB::named(BLACK),
-            to_cp437('@'),
-        );
-        if let Some(m) = &self.meter {
-            self.ui.draw_tension(ctx, m.tension, m.max_tension).ok();
+        if let Some(fight) = self.fight_view() {
+            self.ui.draw_fight(ctx, &fight).ok();
+        } else if let GameMode::Trading { .. } = self.mode {
+            self.draw_trading(ctx);
+        } else {
+            self.draw_scene(ctx);
+            if let GameMode::Photo { .. } = self.mode {
+                self.draw_photo_banner(ctx);
+            } else {
+                if self.show_minimap {
+                    self.ui.draw_minimap(ctx, &self.minimap_view()).ok();
+                }
+                self.draw_tooltip(ctx);
+            }
         }
         self.ui.draw_logs(ctx).ok();
-        self.ui
-            .draw_status(
-                ctx,
-                self.player.hp,
-                self.player.line,
-                self.player.hunger,
-                self.depth,
-                self.time_of_day,
-            )
-            .ok();
+        self.ui.draw_status(ctx, &self.status_view()).ok();
         let lines = self.inventory_lines();
         self.ui
             .draw_inventory(ctx, &lines, self.inventory_cursor, self.inventory_focus)
             .ok();
+        self.ui.draw_quests(ctx, &self.quest_views()).ok();
+        self.draw_tutorial(ctx);
+        self.ui.draw_prompt(ctx).ok();
+        self.ui.draw_popup(ctx).ok();
+        self.ui.draw_catch_card(ctx).ok();
+    }
+}
+
+/// Current Unix time in seconds, or `None` where no system clock is available.
+fn unix_time() -> Option<u64> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        None
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .ok()
+            .map(|d| d.as_secs())
+    }
+}
+
+/// Console size in cells for the graphical window.
+fn console_size(cfg: &InputConfig) -> (i32, i32) {
+    let (columns, rows) = cfg.console_size.unwrap_or((SCREEN_WIDTH, SCREEN_HEIGHT));
+    (columns.max(SCREEN_WIDTH), rows.max(SCREEN_HEIGHT))
+}
+
+/// Tile size in pixels for the configured window size or font scale.
+fn tile_dimensions(cfg: &InputConfig) -> (u32, u32) {
+    let (columns, rows) = console_size(cfg);
+    match cfg.window_size {
+        Some((w, h)) => ((w / columns as u32).max(8), (h / rows as u32).max(8)),
+        None => (8 * cfg.font_scale as u32, 8 * cfg.font_scale as u32),
     }
 }
 
 /// Runs the game loop using [`bracket-lib`].
+///
+/// Window options (fullscreen, vsync, size, console size) are read from the config and
+/// apply from the next launch.
 pub fn run() -> BError {
+    run_app(app::LurhookApp::new())
+}
+
+/// Runs `app` in a [`bracket-lib`] window.
+pub fn run_app(app: LurhookApp) -> BError {
     println!("Welcome to Lurhook! (engine stub)");
     init_subsystems()?;
-    let cfg = InputConfig::load(CONFIG_PATH).unwrap_or_default();
-    let context = BTermBuilder::simple(80, 25)?
+    let cfg = InputConfig::load(default_storage().as_ref(), CONFIG_PATH).unwrap_or_default();
+    let (columns, rows) = console_size(&cfg);
+    let (tile_width, tile_height) = tile_dimensions(&cfg);
+    let context = BTermBuilder::simple(columns, rows)?
         .with_title("Lurhook")
-        .with_tile_dimensions(8 * cfg.font_scale as u32, 8 * cfg.font_scale as u32)
+        .with_tile_dimensions(tile_width, tile_height)
+        .with_fullscreen(cfg.fullscreen)
+        .with_vsync(cfg.vsync)
         .build()?;
-    let gs = app::LurhookApp::new();
-    main_loop(context, gs)
+    main_loop(context, app)
+}
+
+/// The area with id `id` among `areas`.
+fn find_area(areas: &[data::AreaType], id: &str) -> GameResult<data::AreaType> {
+    areas
+        .iter()
+        .find(|a| a.id == id)
+        .cloned()
+        .ok_or_else(|| GameError::Parse(format!("unknown area {}", id)))
+}
+
+/// Species of `area` that spawn in `season`.
+fn area_fish(
+    area: &data::AreaType,
+    fish_types: &[data::FishType],
+    season: data::Season,
+) -> Vec<data::FishType> {
+    let species: Vec<data::FishType> = fish_types
+        .iter()
+        .filter(|f| area.holds(f))
+        .cloned()
+        .collect();
+    ecology::seasonal_species(&species, season)
 }
 
 fn init_subsystems() -> GameResult<()> {
@@ -931,7 +2030,7 @@ fn init_subsystems() -> GameResult<()> {
     ui_init();
     ui.add_log("UI initialized")?;
 
-    let map = generate(0, 120, 80)?;
+    let map = generate(0, &data::AreaType::new("Test", 120, 80))?;
     ui.add_log(&format!("Map {}x{} generated", map.width, map.height))?;
     fishing_init();
     audio::init();
@@ -946,7 +2045,10 @@ fn init_subsystems() -> GameResult<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use bracket_lib::prelude::{BTerm, VirtualKeyCode, RGB};
+    use crate::test_support::{click_frame, key_frame, memory_game};
+    use bracket_lib::prelude::VirtualKeyCode;
+    use codex::Capture;
+    use common::{FileStorage, MemoryStorage};
 
     #[test]
     fn init_ok() {
@@ -971,7 +2073,7 @@ mod tests {
         assert_eq!(game.fishes.len(), 5);
         let fish = &game.fishes[0];
         let tile = game.map.tiles[game.map.idx(fish.position)];
-        assert!(matches!(tile, TileKind::ShallowWater | TileKind::DeepWater));
+        assert!(tile.is_water());
     }
 
     #[test]
@@ -1022,7 +2124,7 @@ mod tests {
         let mut game = LurhookGame::default();
         game.cast();
         game.confirm_cast();
-        if let GameMode::Fishing { ref mut wait } = game.mode {
+        if let GameMode::Fishing { ref mut wait, .. } = game.mode {
             *wait = 0;
         }
         game.meter = Some(TensionMeter {
@@ -1057,16 +2159,63 @@ mod tests {
         assert_eq!(loaded.time_of_day, game.time_of_day);
     }
 
+    #[test]
+    fn corrupted_save_loads_backup() {
+        let mut game = LurhookGame::default();
+        let path = "test_save_corrupted.ron";
+        game.player.hp = 4;
+        game.save_game(path).unwrap();
+        game.player.hp = 2;
+        game.save_game(path).unwrap();
+        let mut bytes = std::fs::read(path).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xFF;
+        std::fs::write(path, bytes).unwrap();
+        let loaded = LurhookGame::load_game(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        std::fs::remove_file(save::backup_key(path)).unwrap();
+        assert_eq!(loaded.player.hp, 4);
+    }
+
+    #[test]
+    fn memory_storage_keeps_persistence_off_disk() {
+        let mut game = memory_game(0);
+        game.save_game("memory_only.ron").unwrap();
+        game.toggle_adaptive();
+        assert!(std::fs::metadata("memory_only.ron").is_err());
+        assert!(game.storage.read("memory_only.ron").unwrap().is_some());
+        let cfg = game.storage.read_string(CONFIG_PATH).unwrap().unwrap();
+        assert!(cfg.contains("adaptive = true"));
+    }
+
     #[test]
     fn camera_clamps_to_bounds() {
         let mut game = LurhookGame::default();
         game.player.pos = common::Point::new(0, 0);
-        assert_eq!(game.camera(), (0, 0));
+        assert_eq!(game.camera().origin(), common::Point::new(0, 0));
 
         game.player.pos = common::Point::new(game.map.width as i32, game.map.height as i32);
         let cam = game.camera();
-        assert!(cam.0 <= game.map.width as i32 - super::VIEW_WIDTH);
-        assert!(cam.1 <= game.map.height as i32 - super::VIEW_HEIGHT);
+        assert_eq!(cam.intersect(game.map.bounds()), Some(cam));
+        assert_eq!(cam.width, game.view_panel().width);
+    }
+
+    #[test]
+    fn larger_consoles_show_more_of_the_map() {
+        let mut game = memory_game(0);
+        game.ui.set_layout(UILayout::Standard);
+        let mut buf = ui_crate::TextBuffer::new(96, 40);
+        game.render(&mut buf);
+        let cam = game.camera();
+        assert_eq!((cam.width, cam.height), (76, 27));
+        let screen = cam.to_local(game.player.pos);
+        assert_eq!(buf.glyph(screen.x, screen.y), Some('@'));
+        assert!(buf.contains("Inventory"));
+
+        // A console wider than the map shows all of it
+        let mut buf = ui_crate::TextBuffer::new(300, 120);
+        game.render(&mut buf);
+        assert_eq!(game.camera(), game.map.bounds());
     }
 
     #[test]
@@ -1074,7 +2223,7 @@ mod tests {
         let mut game = LurhookGame::default();
         game.cast();
         game.confirm_cast();
-        if let GameMode::Fishing { ref mut wait } = game.mode {
+        if let GameMode::Fishing { ref mut wait, .. } = game.mode {
             *wait = 0;
         }
         game.meter = Some(TensionMeter {
@@ -1090,7 +2239,7 @@ mod tests {
         let mut game = LurhookGame::default();
         game.cast();
         game.confirm_cast();
-        if let GameMode::Fishing { ref mut wait } = game.mode {
+        if let GameMode::Fishing { ref mut wait, .. } = game.mode {
             *wait = 0;
         }
         game.meter = Some(TensionMeter {
@@ -1103,6 +2252,319 @@ mod tests {
         assert_eq!(game.ui.layout(), UILayout::Standard);
     }
 
+    #[test]
+    fn lost_fish_splash_spooks_fish_nearby() {
+        let mut game = memory_game(0);
+        let bait = game.fishes[0].position;
+        game.fishes[1].position = bait;
+        game.player.pos = bait + common::Point::new(20, 0);
+        game.bait = Some(bait);
+        game.mode = GameMode::Fishing {
+            wait: 0,
+            bite: None,
+        };
+        game.hooked = Some(0);
+        game.meter = Some(TensionMeter {
+            tension: 10,
+            ..Default::default()
+        });
+        game.reeling = true;
+        game.update_fishing();
+        assert_eq!(game.splashes, [bait]);
+        game.move_fish();
+        assert!(game.splashes.is_empty());
+        assert!(game.fishes[1].spooked > 0);
+        assert_eq!(game.fishes[1].flee_from, bait);
+    }
+
+    /// Strikes a turn after the bite, which sets the hook perfectly.
+    fn strike_on_time(game: &mut LurhookGame) {
+        game.update_fishing();
+        game.strike_bite();
+        game.update_fishing();
+    }
+
+    #[test]
+    fn export_codex_writes_csv_report() {
+        let mut game = memory_game(0);
+        let id = game.fish_types[0].id.clone();
+        game.codex.record_capture(&test_capture(&id));
+        let key = game.export_codex(ExportFormat::Csv).unwrap();
+        assert_eq!(key, "codex_report.csv");
+        let csv = game.storage.read_string(&key).unwrap().unwrap();
+        assert_eq!(csv.lines().count(), game.fish_types.len() + 2);
+        assert!(csv.contains(&id));
+    }
+
+    #[test]
+    fn codex_milestone_grants_lure_and_palette() {
+        let mut game = memory_game(0);
+        let coast: Vec<String> = game
+            .fish_types
+            .iter()
+            .filter(|f| game.area.holds(f))
+            .map(|f| f.id.clone())
+            .collect();
+        let half = coast.len().div_ceil(2);
+        for id in &coast[..half] {
+            game.codex.record_capture(&test_capture(id));
+        }
+        let items = game.player.items.len();
+        game.check_codex_rewards();
+        assert_eq!(game.player.items.len(), items + 1);
+        assert_eq!(game.profile.palettes(), vec!["Sunset"]);
+        let saved = Profile::load(game.storage.as_ref(), PROFILE_PATH).unwrap();
+        assert_eq!(saved, game.profile);
+        assert!(game.achievements.is_unlocked("codex_coast_50"));
+        let file = game
+            .storage
+            .read_string(ACHIEVEMENTS_PATH)
+            .unwrap()
+            .unwrap();
+        assert!(file.contains("codex_coast_25"));
+        game.check_codex_rewards();
+        assert_eq!(game.player.items.len(), items + 1);
+    }
+
+    #[test]
+    fn unlocked_rewards_apply_to_new_games() {
+        let storage = Box::new(MemoryStorage::default());
+        let mut profile = Profile::default();
+        profile.unlock_milestones("Coast", 50.0);
+        profile.save(storage.as_ref(), PROFILE_PATH).unwrap();
+        let mut game = LurhookGame::new_with_storage(0, Difficulty::Normal, None, storage).unwrap();
+        assert!(game
+            .player
+            .items
+            .iter()
+            .any(|i| i.name == "Tidepool Spinner"));
+        game.cycle_palette();
+        assert_eq!(game.input.palette, "Sunset");
+        assert_eq!(game.palette, ColorPalette::named("Sunset").unwrap());
+        game.cycle_palette();
+        assert_eq!(game.input.palette, DEFAULT_PALETTE);
+        assert_eq!(game.palette, ColorPalette::default());
+    }
+
+    #[test]
+    fn locked_palette_falls_back_to_default() {
+        let input = InputConfig {
+            palette: "Abyss".into(),
+            ..Default::default()
+        };
+        let palette = LurhookGame::resolve_palette(&input, &Profile::default());
+        assert_eq!(palette, ColorPalette::default());
+    }
+
+    fn test_capture(id: &str) -> Capture<'_> {
+        Capture {
+            id,
+            turn: 0,
+            timestamp: None,
+            weight: 0.0,
+            location: "Coast",
+        }
+    }
+
+    #[test]
+    fn lost_fish_recorded_as_escape() {
+        let mut game = memory_game(0);
+        let id = game.fishes[0].kind.id.clone();
+        game.mode = GameMode::Fishing {
+            wait: 0,
+            bite: None,
+        };
+        game.hooked = Some(0);
+        game.meter = Some(TensionMeter {
+            tension: 10,
+            ..Default::default()
+        });
+        game.reeling = true;
+        game.update_fishing();
+        game.dispatch_events();
+        assert_eq!(game.codex.record(&id).unwrap().escapes, 1);
+        assert_eq!(game.codex.count(&id), 0);
+    }
+
+    #[test]
+    fn catch_recorded_with_turn_and_area() {
+        let mut game = memory_game(0);
+        game.turn = 7;
+        let id = game.fishes.last().unwrap().kind.id.clone();
+        game.mode = GameMode::Fishing {
+            wait: 0,
+            bite: None,
+        };
+        game.hooked = Some(game.fishes.len() - 1);
+        game.meter = Some(TensionMeter {
+            duration: 1,
+            ..Default::default()
+        });
+        game.update_fishing();
+        game.dispatch_events();
+        let rec = game.codex.record(&id).expect("record");
+        assert_eq!(rec.count, 1);
+        assert_eq!(rec.first_turn, Some(7));
+        assert_eq!(rec.favorite_location(), Some("Coast"));
+        assert!(rec.first_timestamp.is_some());
+    }
+
+    #[test]
+    fn the_codex_file_waits_for_a_save_point() {
+        let mut game = memory_game(0);
+        let id = game.fish_types[0].id.clone();
+        game.codex.record_capture(&test_capture(&id));
+        assert_eq!(game.storage.read(CODEX_PATH).unwrap(), None);
+        game.quick_save();
+        let saved = Codex::load(game.storage.as_ref(), CODEX_PATH).unwrap();
+        assert_eq!(saved.count(&id), 1);
+
+        game.codex.record_capture(&test_capture(&id));
+        game.end_run();
+        let saved = Codex::load(game.storage.as_ref(), CODEX_PATH).unwrap();
+        assert_eq!(saved.count(&id), 2, "flushed when the run ends");
+    }
+
+    #[test]
+    fn cast_hooks_the_fish_at_the_target_and_catches_it() {
+        let mut game = memory_game(0);
+        game.player.bait_bonus = 1.0;
+        let target = game.fishes[2].position;
+        let id = game.fishes[2].kind.id.clone();
+        let left = game.fishes.len() - 1;
+        game.mode = GameMode::Aiming { target };
+        game.confirm_cast();
+        game.mode = GameMode::Fishing {
+            wait: 0,
+            bite: None,
+        };
+        game.update_fishing();
+        assert_eq!(game.hooked, Some(2));
+        assert_eq!(game.hooked_id(), Some(id.clone()));
+        strike_on_time(&mut game);
+        game.meter.as_mut().unwrap().duration = 1;
+        game.reeling = false;
+        game.update_fishing();
+        assert_eq!(game.fishes.len(), left);
+        assert!(!game.fishes.iter().any(|f| f.position == target));
+        assert_eq!(game.player.inventory.last().unwrap().kind.id, id);
+        assert!(game.hooked.is_none());
+    }
+
+    #[test]
+    fn no_bite_without_fish_near_the_target() {
+        let mut game = memory_game(0);
+        game.player.bait_bonus = 1.0;
+        let far = fishing::STRIKE_RADIUS + 1;
+        for fish in &mut game.fishes {
+            fish.position = game.player.pos + common::Point::new(far, 0);
+        }
+        game.mode = GameMode::Aiming {
+            target: game.player.pos,
+        };
+        game.confirm_cast();
+        game.mode = GameMode::Fishing {
+            wait: 0,
+            bite: None,
+        };
+        game.update_fishing();
+        assert!(game.meter.is_none());
+        assert!(matches!(game.mode, GameMode::Exploring));
+    }
+
+    #[test]
+    fn render_draws_player_headless() {
+        let mut game = memory_game(0);
+        game.ui.set_layout(UILayout::Standard);
+        let mut buf = ui_crate::TextBuffer::new(80, 25);
+        game.render(&mut buf);
+        let screen = game.camera().to_local(game.player.pos);
+        assert_eq!(buf.glyph(screen.x, screen.y), Some('@'));
+        assert!(buf.contains("Inventory"));
+    }
+
+    #[test]
+    fn minimap_key_toggles_the_overlay() {
+        let mut game = memory_game(0);
+        game.ui.set_layout(UILayout::Standard);
+        let players = |game: &mut LurhookGame| {
+            let mut buf = ui_crate::TextBuffer::new(80, 25);
+            game.render(&mut buf);
+            (0..80)
+                .flat_map(|x| (0..25).map(move |y| (x, y)))
+                .filter(|&(x, y)| buf.glyph(x, y) == Some('@'))
+                .count()
+        };
+        assert_eq!(players(&mut game), 1);
+        game.handle_input_key(Some(game.input.minimap));
+        assert!(game.show_minimap);
+        assert_eq!(players(&mut game), 2);
+        let view = game.minimap_view();
+        assert_eq!(view.tiles.len(), game.map.tiles.len());
+        assert!(view.tiles.contains(&MinimapTile::Unexplored));
+        game.handle_input_key(Some(game.input.minimap));
+        assert!(!game.show_minimap);
+    }
+
+    #[test]
+    fn render_shows_summary_when_run_ends() {
+        let mut game = memory_game(0);
+        game.mode = GameMode::End { score: 42 };
+        let mut buf = ui_crate::TextBuffer::new(80, 25);
+        game.render(&mut buf);
+        assert!(buf.contains("Run Complete!"));
+        assert!(buf.contains("Final score: 42"));
+        assert!(buf.contains("Seed 0  Traveled 0 tiles over 1 days"));
+    }
+
+    #[test]
+    fn the_status_panel_shows_the_seed_off_the_boat() {
+        let mut game = memory_game(42);
+        game.ui.set_layout(UILayout::Standard);
+        let mut buf = ui_crate::TextBuffer::new(80, 25);
+        game.render(&mut buf);
+        assert!(buf.contains("Seed 42"));
+        game.player.aboard = true;
+        game.render(&mut buf);
+        assert!(!buf.contains("Seed 42"), "the boat takes the row");
+    }
+
+    #[test]
+    fn catch_shows_card_until_key_press() {
+        let mut game = memory_game(0);
+        let description = game.fishes.last().unwrap().kind.description.clone();
+        game.mode = GameMode::Fishing {
+            wait: 0,
+            bite: None,
+        };
+        game.hooked = Some(game.fishes.len() - 1);
+        game.meter = Some(TensionMeter {
+            duration: 1,
+            ..Default::default()
+        });
+        game.update_fishing();
+        game.dispatch_events();
+        let card = game.ui.catch_card().expect("card").clone();
+        assert_eq!(card.name, game.player.inventory[0].kind.name);
+        assert_eq!(card.description, description);
+        assert!(!card.art.is_empty());
+        assert!(card.first, "the codex starts empty");
+        assert_eq!(card.score, catch_score(&game.player.inventory));
+        let mut buf = TextBuffer::new(SCREEN_WIDTH, SCREEN_HEIGHT);
+        game.render(&mut buf);
+        assert!(buf.contains(&card.name));
+        assert!(buf.contains("New codex entry!"));
+        let turn = game.turn;
+        let mut ctx = key_frame(VirtualKeyCode::Left);
+        game.update(&mut ctx);
+        assert!(game.ui.catch_card().is_none());
+        assert_eq!(game.ui.layout(), UILayout::Standard);
+        assert_eq!(game.turn, turn);
+        let mut buf = TextBuffer::new(SCREEN_WIDTH, SCREEN_HEIGHT);
+        game.render(&mut buf);
+        assert!(!buf.contains("New codex entry!"), "the card is gone");
+    }
+
     #[test]
     fn cannot_cast_without_line() {
         let mut game = LurhookGame::default();
@@ -1148,119 +2610,81 @@ mod tests {
         let mut game = LurhookGame::default();
         let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../../assets/fish.json");
         let fish = data::load_fish_types(path).expect("types")[0].clone();
-        game.player.inventory.push(fish.clone());
+        game.player.inventory.push(Catch::average(fish.clone()));
         let expected = ((1.0 / fish.rarity) * 10.0) as i32;
         assert_eq!(game.score(), expected);
     }
 
+    #[test]
+    fn heavier_catches_score_more() {
+        let game = LurhookGame::default();
+        let fish = game.fish_types[0].clone();
+        let average = Catch::average(fish.clone()).score();
+        assert!(Catch::new(fish.clone(), fish.max_weight).score() > average);
+        assert!(Catch::new(fish.clone(), fish.min_weight).score() < average);
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     #[test]
     fn end_run_sets_mode() {
         let mut game = LurhookGame::default();
         let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../../assets/fish.json");
         let fish = data::load_fish_types(path).expect("types")[0].clone();
-        game.player.inventory.push(fish);
+        game.player.inventory.push(Catch::average(fish));
         game.end_run();
         assert!(matches!(game.mode, GameMode::End { .. }));
     }
 
-    fn dummy_ctx(key: VirtualKeyCode) -> BTerm {
-        BTerm {
-            width_pixels: 0,
-            height_pixels: 0,
-            original_height_pixels: 0,
-            original_width_pixels: 0,
-            fps: 0.0,
-            frame_time_ms: 0.0,
-            active_console: 0,
-            key: Some(key),
-            mouse_pos: (0, 0),
-            left_click: false,
-            shift: false,
-            control: false,
-            alt: false,
-            web_button: None,
-            quitting: false,
-            post_scanlines: false,
-            post_screenburn: false,
-            screen_burn_color: RGB::from_f32(0.0, 0.0, 0.0),
-            mouse_visible: true,
-        }
-    }
-
-    fn dummy_ctx_opt(key: Option<VirtualKeyCode>) -> BTerm {
-        BTerm {
-            width_pixels: 0,
-            height_pixels: 0,
-            original_height_pixels: 0,
-            original_width_pixels: 0,
-            fps: 0.0,
-            frame_time_ms: 0.0,
-            active_console: 0,
-            key,
-            mouse_pos: (0, 0),
-            left_click: false,
-            shift: false,
-            control: false,
-            alt: false,
-            web_button: None,
-            quitting: false,
-            post_scanlines: false,
-            post_screenburn: false,
-            screen_burn_color: RGB::from_f32(0.0, 0.0, 0.0),
-            mouse_visible: true,
-        }
-    }
-
-    fn dummy_ctx_click(x: i32, y: i32) -> BTerm {
-        BTerm {
-            width_pixels: 0,
-            height_pixels: 0,
-            original_height_pixels: 0,
-            original_width_pixels: 0,
-            fps: 0.0,
-            frame_time_ms: 0.0,
-            active_console: 0,
-            key: None,
-            mouse_pos: (x, y),
-            left_click: true,
-            shift: false,
-            control: false,
-            alt: false,
-            web_button: None,
-            quitting: false,
-            post_scanlines: false,
-            post_screenburn: false,
-            screen_burn_color: RGB::from_f32(0.0, 0.0, 0.0),
-            mouse_visible: true,
-        }
+    #[test]
+    fn advance_time_records_metrics() {
+        let mut game = LurhookGame::default();
+        game.advance_time();
+        game.advance_time();
+        let metrics = game.metrics();
+        assert_eq!(metrics.hp.len(), 2);
+        assert_eq!(metrics.hunger.len(), 2);
+        assert_eq!(metrics.catches, vec![0, 0]);
+        assert_eq!(*metrics.hunger.last().unwrap(), game.player.hunger);
+    }
+
+    #[test]
+    fn summary_charts_include_all_series() {
+        let mut metrics = RunMetrics::default();
+        metrics.record_turn(MAX_HP, MAX_HUNGER);
+        metrics.record_catch();
+        metrics.record_turn(MAX_HP / 2, 0);
+        let lines = ui::summary_chart_lines(&metrics);
+        assert!(lines[0].starts_with("HP") && lines[0].ends_with("#~"));
+        assert!(lines[1].starts_with("Hunger") && lines[1].ends_with("#_"));
+        assert_eq!(lines[2], "Catches (1)");
+        assert!(lines.last().unwrap().ends_with(" #"));
     }
 
     #[test]
     fn pressing_s_saves_game() {
         let mut game = LurhookGame::default();
-        let mut ctx = dummy_ctx(VirtualKeyCode::S);
+        let mut ctx = key_frame(VirtualKeyCode::S);
         game.handle_input(&mut ctx);
         assert!(std::fs::metadata(super::SAVE_PATH).is_ok());
         std::fs::remove_file(super::SAVE_PATH).unwrap();
     }
 
     #[test]
-    fn pressing_q_quits() {
+    fn pressing_q_pauses_instead_of_quitting() {
         let mut game = LurhookGame::default();
-        let mut ctx = dummy_ctx(VirtualKeyCode::Q);
+        let mut ctx = key_frame(VirtualKeyCode::Q);
         game.handle_input(&mut ctx);
-        assert!(ctx.quitting);
+        assert!(!ctx.quitting);
+        assert_eq!(game.question, Some(prompts::Question::Pause));
     }
 
     #[test]
     fn time_advances_only_on_input() {
         let mut game = LurhookGame::default();
-        let mut ctx = dummy_ctx_opt(None);
-        game.handle_input_key(None, &mut ctx);
+        game.handle_input_key(None);
         assert_eq!(game.turn, 0);
 
-        game.handle_input_key(Some(VirtualKeyCode::Right), &mut ctx);
+        game.handle_input_key(Some(VirtualKeyCode::Right));
         game.advance_time();
         assert_eq!(game.turn, 1);
     }
@@ -1270,13 +2694,15 @@ mod tests {
         let mut game = LurhookGame::default();
         game.player.tension_bonus = 50;
         game.player.bait_bonus = 1.0; // guarantee bite
+        game.fishes[0].position = game.player.pos;
         game.cast();
         game.confirm_cast();
-        if let GameMode::Fishing { ref mut wait } = game.mode {
+        if let GameMode::Fishing { ref mut wait, .. } = game.mode {
             *wait = 0;
         }
         // Force meter creation
         game.update_fishing();
+        strike_on_time(&mut game);
         if let Some(m) = &game.meter {
             assert_eq!(m.max_tension, 150);
         } else {
@@ -1289,14 +2715,17 @@ mod tests {
         let mut game = LurhookGame::default();
         game.player.reel_factor = 2.0;
         game.player.bait_bonus = 1.0;
+        game.fishes[0].position = game.player.pos;
         game.cast();
         game.confirm_cast();
-        if let GameMode::Fishing { ref mut wait } = game.mode {
+        if let GameMode::Fishing { ref mut wait, .. } = game.mode {
             *wait = 0;
         }
         game.update_fishing();
+        strike_on_time(&mut game);
         if let Some(mut m) = game.meter.take() {
             m.tension = 30;
+            m.phase = fishing::FightPhase::Thrash;
             let before = m.tension;
             m.update(true);
             assert!(m.tension <= before - 20); // factor 2.0 reduces by >=20
@@ -1319,7 +2748,24 @@ mod tests {
         let mut game = LurhookGame::default();
         game.map.tiles.fill(TileKind::Land);
         game.player.pos = common::Point::new(0, 0);
-        assert!(game.is_visible(common::Point::new(100, 0)));
+        assert!(game.is_visible(common::Point::new(79, 0)));
+    }
+
+    #[test]
+    fn islands_block_sight_from_the_water() {
+        let mut game = LurhookGame::default();
+        game.map.tiles.fill(TileKind::ShallowWater);
+        game.player.pos = common::Point::new(10, 10);
+        for y in 0..20 {
+            let idx = game.map.idx(common::Point::new(12, y));
+            game.map.tiles[idx] = TileKind::Land;
+        }
+        assert!(game.is_visible(common::Point::new(12, 10)));
+        assert!(!game.is_visible(common::Point::new(14, 10)));
+
+        // Ashore the player looks over the land
+        game.player.pos = common::Point::new(12, 5);
+        assert!(game.is_visible(common::Point::new(14, 10)));
     }
 
     #[test]
@@ -1327,7 +2773,7 @@ mod tests {
         let mut game = LurhookGame::default();
         let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../../assets/fish.json");
         let fish = data::load_fish_types(path).expect("types")[0].clone();
-        game.player.inventory.push(fish);
+        game.player.inventory.push(Catch::average(fish));
         game.player.hunger = 50;
         game.eat_fish();
         assert!(game.player.hunger > 50);
@@ -1339,7 +2785,7 @@ mod tests {
         let mut game = LurhookGame::default();
         let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../../assets/fish.json");
         let fish = data::load_fish_types(path).expect("types")[0].clone();
-        game.player.inventory.push(fish);
+        game.player.inventory.push(Catch::average(fish));
         game.player.hunger = super::MAX_HUNGER - 5;
         game.eat_fish();
         assert_eq!(game.player.hunger, super::MAX_HUNGER);
@@ -1357,11 +2803,13 @@ mod tests {
         let mut game = LurhookGame::default();
         let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../../assets/fish.json");
         let fish = data::load_fish_types(path).expect("types")[0].clone();
-        game.player.inventory.push(fish);
+        game.player.inventory.push(Catch::average(fish));
         game.player.hunger = 50;
         game.player.hp = super::MAX_HP - 2;
-        // ensure on land
+        // ensure on land, by a campfire
         game.map.tiles.fill(TileKind::Land);
+        game.player.driftwood = 3;
+        game.build_campfire();
         game.cook_fish();
         assert!(game.player.hunger > 50);
         assert_eq!(game.player.hp, super::MAX_HP);
@@ -1377,6 +2825,81 @@ mod tests {
         assert!(c2.g < c1.g);
     }
 
+    #[test]
+    fn strong_currents_show_their_direction() {
+        let mut game = LurhookGame::default();
+        game.map.tiles.fill(TileKind::ShallowWater);
+        game.map.flow.fill(common::Point::new(0, 0));
+        let pt = game.player.pos + common::Point::new(1, 0);
+        let idx = game.map.idx(pt);
+        game.map.flow[idx] = common::Point::new(0, -2);
+        let mut buf = ui_crate::TextBuffer::new(80, 25);
+        game.draw_map(&mut buf);
+        let view = game.camera();
+        let glyph_at = |p| {
+            let screen = view.to_local(p);
+            buf.glyph(screen.x, screen.y)
+        };
+        assert_eq!(glyph_at(pt), Some('↑'));
+        assert_eq!(glyph_at(game.player.pos), Some('~'));
+        assert_eq!(ui::flow_glyph(common::Point::new(2, 2)), '\\');
+    }
+
+    #[test]
+    fn map_memory_keeps_seen_tiles_dimmed() {
+        let mut game = LurhookGame::default();
+        game.map.tiles.fill(TileKind::DeepWater);
+        game.map.flow.fill(common::Point::new(0, 0));
+        game.map.explored.fill(false);
+        let far = game.player.pos + common::Point::new(8, 0);
+        let view = game.camera();
+        let cell = |game: &LurhookGame| {
+            let mut buf = ui_crate::TextBuffer::new(80, 25);
+            game.draw_map(&mut buf);
+            let screen = view.to_local(far);
+            buf.glyph(screen.x, screen.y)
+        };
+        assert_eq!(cell(&game), Some(' '));
+
+        game.player.pos = far;
+        game.explore();
+        game.player.pos = far - common::Point::new(8, 0);
+        assert!(!game.is_visible(far));
+        assert!(game.map.is_explored(far));
+        assert_eq!(cell(&game), Some('≈'));
+    }
+
+    #[test]
+    fn bait_drifts_with_the_current() {
+        let mut game = LurhookGame::default();
+        game.map.tiles.fill(TileKind::ShallowWater);
+        game.map.flow.fill(common::Point::new(1, 0));
+        let target = game.player.pos + common::Point::new(2, 0);
+        game.mode = GameMode::Aiming { target };
+        game.confirm_cast();
+        game.update_fishing();
+        assert_eq!(game.bait, Some(target + common::Point::new(1, 0)));
+    }
+
+    #[test]
+    fn biome_tiles_have_their_own_glyphs() {
+        use TileKind::*;
+        let game = LurhookGame::default();
+        let tiles = [
+            Land,
+            ShallowWater,
+            DeepWater,
+            CoralReef,
+            KelpForest,
+            Sandbar,
+            Ice,
+        ];
+        let glyphs: std::collections::HashSet<char> =
+            tiles.iter().map(|&t| game.tile_style(t, true).0).collect();
+        assert_eq!(glyphs.len(), tiles.len());
+        assert_eq!(game.tile_style(Ice, true).1, game.palette.ice);
+    }
+
     #[test]
     fn canned_food_restores_hunger() {
         let mut game = LurhookGame::default();
@@ -1389,21 +2912,65 @@ mod tests {
 
     #[test]
     fn land_event_triggers() {
-        let mut game = LurhookGame::new(8).unwrap();
+        let mut game = LurhookGame::new(33).unwrap();
         game.map.tiles.fill(TileKind::Land);
+        game.player.hp -= 1;
         let hp = game.player.hp;
         let food = game.player.canned_food;
-        game.advance_time();
-        assert!(game.player.hp > hp || game.player.canned_food > food);
+        for _ in 0..50 {
+            game.advance_time();
+            if game.player.hp > hp || game.player.canned_food > food {
+                return;
+            }
+        }
+        panic!("no shore event in 50 turns");
     }
 
     #[test]
-    fn storm_event_sets_turns() {
-        let mut game = LurhookGame::new(8).unwrap();
-        game.map.tiles.fill(TileKind::DeepWater);
-        game.player.pos = common::Point::new(0, 0);
-        game.advance_time();
-        assert!(game.storm_turns > 0);
+    fn weather_changes_are_logged() {
+        let mut game = memory_game(0);
+        game.weather = WeatherState::new(Weather::Storm, 1);
+        let before = game.ui.logs().len();
+        for _ in 0..20 {
+            game.advance_clock();
+            if game.weather.weather != Weather::Storm {
+                break;
+            }
+        }
+        assert_ne!(game.weather.weather, Weather::Storm);
+        game.dispatch_events();
+        assert!(game.ui.logs().len() > before);
+    }
+
+    #[test]
+    fn subscribers_receive_game_events() {
+        struct Shared(std::rc::Rc<std::cell::RefCell<Vec<GameEvent>>>);
+        impl EventListener for Shared {
+            fn on_event(&mut self, event: &GameEvent) {
+                self.0.borrow_mut().push(event.clone());
+            }
+        }
+        let mut game = memory_game(0);
+        let seen = std::rc::Rc::default();
+        game.subscribe(Box::new(Shared(std::rc::Rc::clone(&seen))));
+        game.mode = GameMode::Fishing {
+            wait: 0,
+            bite: None,
+        };
+        game.hooked = Some(0);
+        game.meter = Some(TensionMeter {
+            tension: 10,
+            ..Default::default()
+        });
+        game.reeling = true;
+        game.update_fishing();
+        assert!(seen.borrow().is_empty());
+        game.dispatch_events();
+        assert!(matches!(
+            seen.borrow()[..],
+            [GameEvent::FishEscaped { id: Some(_) }]
+        ));
+        assert!(game.ui.logs().iter().any(|l| l == "The fish escaped!"));
     }
 
     #[test]
@@ -1411,17 +2978,92 @@ mod tests {
         let mut game = LurhookGame::default();
         game.map.tiles.fill(TileKind::DeepWater);
         game.player.pos = common::Point::new(0, 0);
-        game.storm_turns = 1;
+        game.weather = WeatherState::new(Weather::Storm, 1);
         assert!(!game.is_visible(common::Point::new(6, 0)));
         assert!(game.is_visible(common::Point::new(3, 0)));
     }
 
+    #[test]
+    fn fog_limits_sight_on_land() {
+        let mut game = LurhookGame::default();
+        game.map.tiles.fill(TileKind::Land);
+        game.player.pos = common::Point::new(0, 0);
+        assert!(game.is_visible(common::Point::new(20, 0)));
+        game.weather = WeatherState::new(Weather::Fog, 1);
+        assert!(!game.is_visible(common::Point::new(4, 0)));
+    }
+
+    #[test]
+    fn status_view_shows_storm_and_starving_icons() {
+        let mut game = LurhookGame::default();
+        assert!(game.status_view().icons.is_empty());
+        game.weather = WeatherState::new(Weather::Storm, 2);
+        game.player.hunger = 0;
+        let view = game.status_view();
+        assert_eq!(view.icons.len(), 2);
+        assert_eq!(view.max_line, super::MAX_LINE);
+        assert_eq!(view.hunger, 0);
+    }
+
+    #[test]
+    fn deep_fish_only_show_on_sonar() {
+        let mut game = memory_game(0);
+        game.ui.set_layout(UILayout::Standard);
+        game.map.tiles.fill(TileKind::DeepWater);
+        game.fishes.truncate(1);
+        let pos = game.player.pos + common::Point::new(1, 0);
+        game.fishes[0].position = pos;
+        game.fishes[0].weight = 2.2;
+        let screen = game.camera().to_local(pos);
+
+        let mut buf = ui_crate::TextBuffer::new(80, 25);
+        game.render(&mut buf);
+        assert_ne!(buf.glyph(screen.x, screen.y), Some('ƒ'));
+        assert_eq!(game.status_view().sonar, None);
+
+        game.player.sonar = Some(data::ItemType {
+            id: "SONAR".into(),
+            name: "Fishfinder".into(),
+            kind: data::ItemKind::Sonar,
+            tension_bonus: 0,
+            reel_factor: 1.0,
+            bite_bonus: 0.0,
+            max_cast_range: data::DEFAULT_CAST_RANGE,
+        });
+        let mut buf = ui_crate::TextBuffer::new(80, 25);
+        game.render(&mut buf);
+        assert_eq!(buf.glyph(screen.x, screen.y), Some('ƒ'));
+        assert_eq!(game.status_view().sonar, Some(2.0));
+    }
+
+    #[test]
+    fn fight_view_only_while_hooked() {
+        let mut game = LurhookGame::default();
+        assert!(game.fight_view().is_none());
+        game.meter = Some(TensionMeter::default());
+        let view = game.fight_view().expect("fight view");
+        assert!(view.name.is_none());
+        assert_eq!(view.max_line, super::MAX_LINE);
+    }
+
+    #[test]
+    fn fight_view_names_species_already_in_codex() {
+        let mut game = memory_game(0);
+        let id = game.fishes[0].kind.id.clone();
+        game.codex.record_capture(&test_capture(&id));
+        game.meter = Some(TensionMeter::default());
+        game.hooked = Some(0);
+        let view = game.fight_view().expect("fight view");
+        assert_eq!(view.name, Some(game.fishes[0].kind.name.as_str()));
+    }
+
     #[test]
     fn hazard_damages_player() {
         let mut game = LurhookGame::default();
         game.hazards.push(Hazard {
             pos: game.player.pos,
             turns: 1,
+            kind: HazardKind::Jellyfish,
         });
         let hp = game.player.hp;
         let line = game.player.line;
@@ -1431,6 +3073,97 @@ mod tests {
         assert!(game.hazards.is_empty());
     }
 
+    /// Lays `tiles` out eastward from the player, who starts ashore.
+    fn shoreline(game: &mut LurhookGame, tiles: &[TileKind]) -> common::Point {
+        let start = common::Point::new(10, 10);
+        for (i, tile) in tiles.iter().enumerate() {
+            let idx = game.map.idx(start + common::Point::new(i as i32, 0));
+            game.map.tiles[idx] = *tile;
+        }
+        game.player.pos = start;
+        game.player.boat = Boat::new(start, BOAT_DURABILITY, BOAT_SPEED);
+        game.player.aboard = false;
+        start
+    }
+
+    #[test]
+    fn deep_water_needs_the_boat() {
+        use TileKind::*;
+        let mut game = LurhookGame::default();
+        let start = shoreline(&mut game, &[ShallowWater, DeepWater, DeepWater, DeepWater]);
+        game.try_move(common::Point::new(1, 0));
+        assert_eq!(game.player.pos, start);
+
+        game.toggle_boat();
+        assert!(game.player.aboard);
+        game.try_move(common::Point::new(1, 0));
+        let at_sea = start + common::Point::new(BOAT_SPEED, 0);
+        assert_eq!(game.player.pos, at_sea);
+        assert_eq!(game.player.boat.pos, at_sea);
+
+        // No stepping off at sea, and no moving at anchor
+        game.toggle_boat();
+        assert!(game.player.aboard);
+        game.toggle_anchor();
+        game.try_move(common::Point::new(1, 0));
+        assert_eq!(game.player.pos, at_sea);
+    }
+
+    #[test]
+    fn boat_stops_at_the_shore() {
+        use TileKind::*;
+        let mut game = LurhookGame::default();
+        let start = shoreline(&mut game, &[Land, ShallowWater, Land]);
+        game.try_move(common::Point::new(1, 0));
+        game.toggle_boat();
+        assert!(game.player.aboard);
+        assert_eq!(game.player.pos, start);
+        game.try_move(common::Point::new(1, 0));
+        assert_eq!(game.player.pos, start + common::Point::new(1, 0));
+        game.try_move(common::Point::new(1, 0));
+        assert_eq!(game.player.pos, start + common::Point::new(1, 0));
+
+        game.toggle_boat();
+        game.try_move(common::Point::new(1, 0));
+        assert!(!game.player.aboard);
+        assert_eq!(game.player.pos, start + common::Point::new(2, 0));
+        assert_eq!(game.player.boat.pos, start + common::Point::new(1, 0));
+    }
+
+    #[test]
+    fn hazards_damage_the_boat_while_aboard() {
+        let mut game = LurhookGame::default();
+        shoreline(&mut game, &[TileKind::DeepWater]);
+        game.player.aboard = true;
+        game.player.boat.durability = HAZARD_DAMAGE;
+        let hp = game.player.hp;
+        game.hazards.push(Hazard {
+            pos: game.player.pos,
+            turns: 3,
+            kind: HazardKind::Jellyfish,
+        });
+        game.sting_player();
+        assert_eq!(game.player.hp, hp);
+        assert!(game.player.boat.is_wrecked());
+        assert_eq!(game.player.boat.moves(), 1);
+
+        // A wrecked hull no longer shields the player
+        game.sting_player();
+        assert!(game.player.hp < hp);
+    }
+
+    #[test]
+    fn anchoring_steadies_the_line() {
+        let mut game = LurhookGame::default();
+        shoreline(&mut game, &[TileKind::ShallowWater]);
+        let bonus = game.line_tension_bonus();
+        game.toggle_anchor();
+        assert!(!game.player.boat.anchored);
+        game.toggle_boat();
+        game.toggle_anchor();
+        assert_eq!(game.line_tension_bonus(), bonus + ANCHOR_TENSION_BONUS);
+    }
+
     #[test]
     fn line_path_returns_endpoints() {
         let start = common::Point::new(0, 0);
@@ -1461,9 +3194,10 @@ mod tests {
             tension_bonus: 0,
             reel_factor: 1.0,
             bite_bonus: 0.0,
+            max_cast_range: data::DEFAULT_CAST_RANGE,
         });
         game.inventory_focus = true;
-        let mut ctx = dummy_ctx(VirtualKeyCode::Down);
+        let mut ctx = key_frame(VirtualKeyCode::Down);
         game.handle_input(&mut ctx);
         assert_eq!(game.inventory_cursor, 1);
     }
@@ -1478,6 +3212,7 @@ mod tests {
             tension_bonus: 5,
             reel_factor: 1.0,
             bite_bonus: 0.0,
+            max_cast_range: data::DEFAULT_CAST_RANGE,
         };
         game.player.items.push(rod.clone());
         game.inventory_cursor = game.player.items.len() - 1;
@@ -1497,19 +3232,139 @@ mod tests {
     #[test]
     fn options_key_opens_menu() {
         let mut game = LurhookGame::default();
-        let mut ctx = dummy_ctx(game.input.options);
+        let mut ctx = key_frame(game.input.options);
         game.handle_input(&mut ctx);
         assert_eq!(game.ui.layout(), UILayout::Options);
     }
 
+    #[test]
+    fn achievements_screen_opens_from_options() {
+        let mut game = memory_game(0);
+        game.ui.set_layout(UILayout::Options);
+        game.handle_input(&mut key_frame(VirtualKeyCode::T));
+        assert_eq!(game.ui.layout(), UILayout::Achievements);
+        let mut buf = ui_crate::TextBuffer::new(80, 25);
+        game.render(&mut buf);
+        assert!(buf.contains("First Catch"));
+        // Other keys are ignored until the screen is closed
+        game.handle_input(&mut key_frame(game.input.help));
+        assert_eq!(game.ui.layout(), UILayout::Achievements);
+        game.handle_input(&mut key_frame(game.input.options));
+        assert_eq!(game.ui.layout(), UILayout::Options);
+    }
+
+    #[test]
+    fn codex_key_opens_the_codex() {
+        let mut game = memory_game(0);
+        game.ui.set_layout(UILayout::Standard);
+        let trout = game.fish_types[0].id.clone();
+        game.codex.record_capture(&test_capture(&trout));
+        game.handle_input(&mut key_frame(game.input.codex));
+        assert_eq!(game.ui.layout(), UILayout::Codex);
+        let mut buf = ui_crate::TextBuffer::new(80, 25);
+        game.render(&mut buf);
+        assert!(buf.contains(&game.fish_types[0].name));
+        assert!(!buf.contains(&game.fish_types[1].name));
+        assert!(buf.contains("???"));
+        // Other keys are ignored until the screen is closed
+        game.handle_input(&mut key_frame(game.input.options));
+        assert_eq!(game.ui.layout(), UILayout::Codex);
+        game.handle_input(&mut key_frame(VirtualKeyCode::Escape));
+        assert_eq!(game.ui.layout(), UILayout::Standard);
+    }
+
     #[test]
     fn toggle_colorblind_persists() {
         let mut game = LurhookGame::default();
         let _ = std::fs::remove_file(CONFIG_PATH);
         game.toggle_colorblind();
-        let loaded = InputConfig::load(CONFIG_PATH).unwrap();
+        let loaded = InputConfig::load(&FileStorage::default(), CONFIG_PATH).unwrap();
         std::fs::remove_file(CONFIG_PATH).unwrap();
         assert_eq!(loaded.colorblind, game.input.colorblind);
+        assert_eq!(
+            game.ui.theme(),
+            ui_crate::widgets::Theme::high_contrast(),
+            "the panels follow"
+        );
+    }
+
+    #[test]
+    fn toggle_mute_persists_and_silences() {
+        let mut game = LurhookGame::default();
+        let _ = std::fs::remove_file(CONFIG_PATH);
+        game.toggle_mute();
+        let loaded = InputConfig::load(&FileStorage::default(), CONFIG_PATH).unwrap();
+        std::fs::remove_file(CONFIG_PATH).unwrap();
+        assert!(loaded.muted);
+        assert!(game.audio.is_muted());
+        assert_eq!(loaded.sfx_volume, game.input.sfx_volume);
+    }
+
+    #[test]
+    fn toggle_adaptive_persists_and_enables_tracker() {
+        let mut game = LurhookGame::default();
+        let _ = std::fs::remove_file(CONFIG_PATH);
+        game.toggle_adaptive();
+        let loaded = InputConfig::load(&FileStorage::default(), CONFIG_PATH).unwrap();
+        std::fs::remove_file(CONFIG_PATH).unwrap();
+        assert!(game.input.adaptive);
+        assert_eq!(loaded.adaptive, game.input.adaptive);
+        game.adaptive.record(false);
+        assert!(game.adaptive.bite_bonus() > 0.0);
+    }
+
+    #[test]
+    fn failed_fight_recorded_by_adaptive_tracker() {
+        let mut game = LurhookGame::default();
+        game.adaptive.set_enabled(true);
+        game.cast();
+        game.confirm_cast();
+        if let GameMode::Fishing { ref mut wait, .. } = game.mode {
+            *wait = 0;
+        }
+        game.meter = Some(TensionMeter {
+            max_tension: 1,
+            ..Default::default()
+        });
+        game.update_fishing();
+        assert!(game.adaptive.bite_bonus() > 0.0);
+    }
+
+    #[test]
+    fn window_options_cycle_and_persist() {
+        let mut game = memory_game(0);
+        game.toggle_fullscreen();
+        game.toggle_vsync();
+        for _ in 0..2 {
+            game.cycle_window_size();
+        }
+        let loaded = InputConfig::load(game.storage.as_ref(), CONFIG_PATH).unwrap();
+        assert!(loaded.fullscreen);
+        assert!(!loaded.vsync);
+        assert_eq!(loaded.window_size, Some(WINDOW_SIZES[1]));
+        for _ in 1..WINDOW_SIZES.len() {
+            game.cycle_window_size();
+        }
+        assert_eq!(game.input.window_size, None);
+    }
+
+    #[test]
+    fn tile_dimensions_follow_window_size() {
+        let mut cfg = InputConfig {
+            font_scale: 2,
+            ..InputConfig::default()
+        };
+        assert_eq!(tile_dimensions(&cfg), (16, 16));
+        cfg.window_size = Some((1280, 400));
+        assert_eq!(tile_dimensions(&cfg), (16, 16));
+        cfg.window_size = Some((100, 100));
+        assert_eq!(tile_dimensions(&cfg), (8, 8));
+        cfg.window_size = Some((1920, 600));
+        cfg.console_size = Some((120, 30));
+        assert_eq!(console_size(&cfg), (120, 30));
+        assert_eq!(tile_dimensions(&cfg), (16, 20));
+        cfg.console_size = Some((40, 10));
+        assert_eq!(console_size(&cfg), (80, 25));
     }
 
     #[test]
@@ -1518,7 +3373,7 @@ mod tests {
         let _ = std::fs::remove_file(CONFIG_PATH);
         let orig = game.input.cast;
         game.cycle_cast_key();
-        let loaded = InputConfig::load(CONFIG_PATH).unwrap();
+        let loaded = InputConfig::load(&FileStorage::default(), CONFIG_PATH).unwrap();
         std::fs::remove_file(CONFIG_PATH).unwrap();
         assert_ne!(loaded.cast, orig);
         assert_eq!(loaded.cast, game.input.cast);
@@ -1529,8 +3384,8 @@ mod tests {
         let mut game = LurhookGame::default();
         let _ = std::fs::remove_file(CONFIG_PATH);
         game.input.font_scale = 2;
-        let _ = game.input.save(CONFIG_PATH);
-        let loaded = InputConfig::load(CONFIG_PATH).unwrap();
+        let _ = game.input.save(game.storage.as_ref(), CONFIG_PATH);
+        let loaded = InputConfig::load(&FileStorage::default(), CONFIG_PATH).unwrap();
         std::fs::remove_file(CONFIG_PATH).unwrap();
         assert_eq!(loaded.font_scale, 2);
     }
@@ -1538,27 +3393,69 @@ mod tests {
     #[test]
     fn left_click_moves_player() {
         let mut game = LurhookGame::default();
-        let (cam_x, cam_y) = game.camera();
-        let mut ctx = dummy_ctx_click(1, 1);
+        let cam = game.camera().origin();
+        let target = game.map.idx(cam + common::Point::new(1, 1));
+        game.map.tiles[target] = TileKind::ShallowWater;
+        game.player.aboard = false;
+        let mut ctx = click_frame(1, 1);
         game.handle_input(&mut ctx);
-        assert_eq!(game.player.pos, common::Point::new(cam_x + 1, cam_y + 1));
+        assert_eq!(game.player.pos, cam + common::Point::new(1, 1));
     }
 
     #[test]
     fn left_click_sets_aim_target() {
         let mut game = LurhookGame::default();
         game.cast();
-        let (cam_x, cam_y) = game.camera();
-        let mut ctx = dummy_ctx_click(2, 2);
+        let near = game.player.pos + common::Point::new(2, 2);
+        let screen = game.camera().to_local(near);
+        let mut ctx = click_frame(screen.x, screen.y);
         game.handle_input(&mut ctx);
         match game.mode {
             GameMode::Aiming { target } => {
-                assert_eq!(target, common::Point::new(cam_x + 2, cam_y + 2));
+                assert_eq!(target, near);
             }
             _ => panic!("not aiming"),
         }
     }
 
+    #[test]
+    fn aiming_stays_within_cast_range() {
+        let mut game = LurhookGame::default();
+        game.player.cast_range = 4;
+        game.cast();
+        let mut ctx = click_frame(0, 0);
+        game.handle_input(&mut ctx);
+        let GameMode::Aiming { target } = game.mode else {
+            panic!("not aiming");
+        };
+        assert_eq!(target, game.player.pos + common::Point::new(-4, -4));
+        for _ in 0..6 {
+            game.handle_input_key(Some(game.input.right));
+        }
+        let GameMode::Aiming { target } = game.mode else {
+            panic!("not aiming");
+        };
+        assert_eq!(target.x, game.player.pos.x + 2);
+    }
+
+    #[test]
+    fn longer_casts_start_with_more_tension() {
+        let mut game = memory_game(0);
+        game.player.bait_bonus = 1.0;
+        let fish = game.fishes[0].position;
+        game.player.pos = fish + common::Point::new(-5, 0);
+        game.mode = GameMode::Aiming { target: fish };
+        game.confirm_cast();
+        game.mode = GameMode::Fishing {
+            wait: 0,
+            bite: None,
+        };
+        game.update_fishing();
+        strike_on_time(&mut game);
+        let meter = game.meter.as_ref().expect("meter");
+        assert_eq!(meter.tension, fishing::cast_tension(5));
+    }
+
     #[test]
     fn difficulty_affects_hunger() {
         let mut easy = LurhookGame::new_with_difficulty(0, Difficulty::Easy).unwrap();
@@ -1576,31 +3473,75 @@ mod tests {
 
     #[test]
     fn hazard_chance_scales() {
-        assert!(
-            Difficulty::Hard.hazard_chance(Area::Coast)
-                > Difficulty::Normal.hazard_chance(Area::Coast)
-        );
-        assert!(
-            Difficulty::Easy.hazard_chance(Area::Coast)
-                < Difficulty::Normal.hazard_chance(Area::Coast)
+        let coast = data::AreaType::new("Coast", 80, 50);
+        let chance = |d: Difficulty| d.profile().hazard_chance_in(&coast);
+        assert!(chance(Difficulty::Hard) > chance(Difficulty::Normal));
+        assert!(chance(Difficulty::Easy) < chance(Difficulty::Normal));
+        let rough = data::AreaType {
+            hazard_multiplier: 3,
+            ..coast.clone()
+        };
+        assert_eq!(
+            Difficulty::Normal.profile().hazard_chance_in(&rough),
+            3 * chance(Difficulty::Normal)
         );
     }
 
     #[test]
-    fn new_with_area_sets_map_size() {
-        let game = LurhookGame::new_with_area(0, Difficulty::Normal, Area::DeepSea).unwrap();
-        assert!(game.map.width > 120 && game.map.height > 80);
+    fn fish_strength_scales_with_difficulty() {
+        let strength = |d: Difficulty, s| d.profile().scale_strength(s);
+        assert!(strength(Difficulty::Easy, 8) < strength(Difficulty::Normal, 8));
+        assert!(strength(Difficulty::Hard, 8) > strength(Difficulty::Normal, 8));
+        assert_eq!(strength(Difficulty::Easy, 1), 1);
     }
 
     #[test]
-    fn area_upgrades_after_catches() {
-        let mut game = LurhookGame::default();
-        let path = "/tmp/test_codex.json";
-        for _ in 0..3 {
-            game.codex.record_capture(path, "A").unwrap();
+    fn bite_bonus_and_escape_scale_with_difficulty() {
+        let (easy, hard) = (Difficulty::Easy.profile(), Difficulty::Hard.profile());
+        assert!(easy.bite_bonus > hard.bite_bonus);
+        assert!(easy.escape_threshold < hard.escape_threshold);
+    }
+
+    #[test]
+    fn hard_snap_damages_line_more() {
+        let mut game = LurhookGame::new_with_difficulty(0, Difficulty::Hard).unwrap();
+        game.cast();
+        game.confirm_cast();
+        if let GameMode::Fishing { ref mut wait, .. } = game.mode {
+            *wait = 0;
         }
-        game.check_area_upgrade();
-        std::fs::remove_file(path).unwrap();
-        assert_eq!(game.area, Area::Offshore);
+        game.meter = Some(TensionMeter {
+            max_tension: 1,
+            ..Default::default()
+        });
+        game.update_fishing();
+        assert_eq!(game.player.line, 100 - super::LINE_DAMAGE * 2);
+    }
+
+    #[test]
+    fn meter_uses_difficulty_escape_threshold() {
+        let mut game = LurhookGame::new_with_difficulty(0, Difficulty::Easy).unwrap();
+        game.player.bait_bonus = 1.0;
+        game.fishes[0].position = game.player.pos;
+        game.cast();
+        game.confirm_cast();
+        if let GameMode::Fishing { ref mut wait, .. } = game.mode {
+            *wait = 0;
+        }
+        game.update_fishing();
+        strike_on_time(&mut game);
+        let meter = game.meter.as_ref().expect("meter");
+        assert_eq!(
+            meter.escape_threshold,
+            Difficulty::Easy.profile().escape_threshold
+        );
+    }
+
+    #[test]
+    fn new_with_area_sets_map_size() {
+        let game = LurhookGame::new_with_area(0, Difficulty::Normal, Some("DeepSea")).unwrap();
+        assert_eq!((game.map.width, game.map.height), (160, 120));
+        assert!(game.fishes.iter().all(|f| game.area.holds(&f.kind)));
+        assert!(LurhookGame::new_with_area(0, Difficulty::Normal, Some("Moon")).is_err());
     }
 }
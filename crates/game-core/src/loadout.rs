@@ -0,0 +1,196 @@
+//! Starting loadouts: kits from `loadouts.json` that a run starts with in
+//! place of fixed gear. Achievements unlock more of them; the menu picks
+//! one and keeps the choice in the profile, which the next run reads.
+
+use super::*;
+use data::{ItemType, Loadout};
+
+/// Loads the loadouts from `assets/loadouts.json`.
+pub(crate) fn load_loadouts() -> GameResult<Vec<Loadout>> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        data::load_loadouts_embedded()
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../../assets/loadouts.json");
+        data::load_loadouts(path)
+    }
+}
+
+/// Whether `loadout` is available with the `achievements` unlocked.
+fn unlocked(loadout: &Loadout, achievements: &dyn AchievementSink) -> bool {
+    match &loadout.unlock {
+        None => true,
+        Some(id) => achievements.is_unlocked(id),
+    }
+}
+
+/// Loadout a run starts with: the one picked in `profile` while it is
+/// unlocked, the first otherwise.
+pub(crate) fn chosen<'a>(
+    loadouts: &'a [Loadout],
+    profile: &Profile,
+    achievements: &dyn AchievementSink,
+) -> &'a Loadout {
+    loadouts
+        .iter()
+        .find(|l| profile.loadout.as_ref() == Some(&l.id) && unlocked(l, achievements))
+        .unwrap_or(&loadouts[0])
+}
+
+/// Gear of a loadout, looked up in the item catalogue.
+pub(crate) struct Kit {
+    pub rod: Option<ItemType>,
+    pub reel: Option<ItemType>,
+    pub lure: Option<ItemType>,
+    pub items: Vec<ItemType>,
+}
+
+impl Kit {
+    pub(crate) fn new(loadout: &Loadout, catalogue: &[ItemType]) -> GameResult<Self> {
+        let item = |id: &String| {
+            catalogue
+                .iter()
+                .find(|i| &i.id == id)
+                .cloned()
+                .ok_or_else(|| {
+                    GameError::Parse(format!("loadout {}: unknown item {}", loadout.id, id))
+                })
+        };
+        Ok(Self {
+            rod: loadout.rod.as_ref().map(item).transpose()?,
+            reel: loadout.reel.as_ref().map(item).transpose()?,
+            lure: loadout.lure.as_ref().map(item).transpose()?,
+            items: loadout.items.iter().map(item).collect::<GameResult<_>>()?,
+        })
+    }
+}
+
+/// The unlocked loadouts as the menu offers them.
+#[derive(Default)]
+pub(crate) struct LoadoutMenu {
+    loadouts: Vec<Loadout>,
+    selected: usize,
+}
+
+impl LoadoutMenu {
+    /// Reads the unlocked loadouts and the one picked from `storage`;
+    /// offers none when they cannot be read.
+    pub(crate) fn load(storage: Box<dyn Storage>) -> Self {
+        let storage: std::rc::Rc<dyn Storage> = storage.into();
+        let (Ok(loadouts), Ok(profile), Ok(achievements)) = (
+            load_loadouts(),
+            Profile::load(storage.as_ref(), PROFILE_PATH),
+            LocalAchievements::load(Box::new(storage.clone()), ACHIEVEMENTS_PATH),
+        ) else {
+            return Self::default();
+        };
+        let loadouts: Vec<Loadout> = loadouts
+            .into_iter()
+            .filter(|l| unlocked(l, &achievements))
+            .collect();
+        let selected = loadouts
+            .iter()
+            .position(|l| profile.loadout.as_ref() == Some(&l.id))
+            .unwrap_or(0);
+        Self { loadouts, selected }
+    }
+
+    /// Picks the next unlocked loadout and keeps the choice in the profile
+    /// in `storage`.
+    pub(crate) fn next(&mut self, storage: &dyn Storage) {
+        if self.loadouts.len() < 2 {
+            return;
+        }
+        self.selected = (self.selected + 1) % self.loadouts.len();
+        let mut profile = Profile::load(storage, PROFILE_PATH).unwrap_or_default();
+        profile.loadout = Some(self.loadouts[self.selected].id.clone());
+        profile.save(storage, PROFILE_PATH).ok();
+    }
+
+    /// Menu row naming the picked loadout, once there is a choice.
+    pub(crate) fn line(&self) -> Option<String> {
+        if self.loadouts.len() < 2 {
+            return None;
+        }
+        let loadout = &self.loadouts[self.selected];
+        Some(format!(
+            "L: Loadout - {} ({})",
+            loadout.name, loadout.description
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    fn unlock(storage: &Rc<dyn Storage>, id: &str) {
+        LocalAchievements::load(Box::new(storage.clone()), ACHIEVEMENTS_PATH)
+            .unwrap()
+            .unlock(id)
+            .unwrap();
+    }
+
+    #[test]
+    fn unlocks_are_known_achievements() {
+        for loadout in load_loadouts().unwrap() {
+            if let Some(id) = &loadout.unlock {
+                assert!(achievements::find(id).is_some(), "{}", id);
+            }
+        }
+    }
+
+    #[test]
+    fn runs_start_with_the_standard_loadout_until_another_is_unlocked() {
+        let storage: Rc<dyn Storage> = Rc::new(MemoryStorage::default());
+        let mut profile = Profile::default();
+        profile.loadout = Some("DECKHAND".into());
+        profile.save(storage.as_ref(), PROFILE_PATH).unwrap();
+        let game =
+            LurhookGame::new_with_storage(0, Difficulty::Normal, None, Box::new(storage.clone()))
+                .unwrap();
+        assert_eq!(game.player.rod.as_ref().unwrap().id, "BASIC_ROD");
+        assert_eq!(game.player.lure.as_ref().unwrap().id, "LURE_PLUS");
+        let ids: Vec<&str> = game.player.items.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, ["CANNED_MEAL", "CHUM_BUCKET"]);
+
+        unlock(&storage, "first_catch");
+        let game =
+            LurhookGame::new_with_storage(0, Difficulty::Normal, None, Box::new(storage)).unwrap();
+        assert_eq!(game.player.rod.as_ref().unwrap().id, "OLD_ROD");
+        assert!(game.player.tension_bonus < 0);
+        assert!(game.player.lure.is_none());
+        let meals = game.player.items.iter().filter(|i| i.id == "CANNED_MEAL");
+        assert_eq!(meals.count(), 3);
+    }
+
+    #[test]
+    fn the_menu_cycles_through_unlocked_loadouts() {
+        let storage: Rc<dyn Storage> = Rc::new(MemoryStorage::default());
+        let mut menu = LoadoutMenu::load(Box::new(storage.clone()));
+        assert_eq!(menu.line(), None, "nothing to choose yet");
+        menu.next(storage.as_ref());
+        assert_eq!(
+            Profile::load(storage.as_ref(), PROFILE_PATH)
+                .unwrap()
+                .loadout,
+            None
+        );
+
+        unlock(&storage, "legendary");
+        let mut menu = LoadoutMenu::load(Box::new(storage.clone()));
+        assert!(menu.line().unwrap().contains("Standard"));
+        menu.next(storage.as_ref());
+        assert!(menu
+            .line()
+            .unwrap()
+            .contains("Pro Angler (good lure, no food)"));
+        let reloaded = LoadoutMenu::load(Box::new(storage.clone()));
+        assert_eq!(reloaded.line(), menu.line());
+        menu.next(storage.as_ref());
+        assert!(menu.line().unwrap().contains("Standard"));
+    }
+}
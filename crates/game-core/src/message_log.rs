@@ -0,0 +1,86 @@
+//! The full message log screen: every message with its turn, filtered by
+//! category and searched by typing.
+
+use super::*;
+use input::key_name;
+
+impl LurhookGame {
+    /// Handles a key on the full log. Letters, digits and spaces go into
+    /// the search. Time does not pass.
+    pub(crate) fn handle_message_log_key(&mut self, key: VirtualKeyCode) {
+        if key == VirtualKeyCode::Escape || key == self.input.message_log {
+            self.ui.close_message_log();
+            return;
+        }
+        let mut search = self.ui.log_search().to_string();
+        match key {
+            VirtualKeyCode::Tab => self.ui.cycle_log_filter(),
+            VirtualKeyCode::Back => {
+                search.pop();
+                self.ui.set_log_search(&search);
+            }
+            k if k == self.input.scroll_up || k == VirtualKeyCode::Up => self.ui.scroll_up(),
+            k if k == self.input.scroll_down || k == VirtualKeyCode::Down => self.ui.scroll_down(),
+            _ => {
+                if let Some(c) = search_char(key) {
+                    search.push(c);
+                    self.ui.set_log_search(&search);
+                }
+            }
+        }
+    }
+}
+
+/// Character `key` types into the search, if any.
+fn search_char(key: VirtualKeyCode) -> Option<char> {
+    if key == VirtualKeyCode::Space {
+        return Some(' ');
+    }
+    let name = key_name(key)?;
+    let mut chars = name.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii_alphanumeric() => Some(c.to_ascii_lowercase()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ui_crate::LogCategory;
+
+    fn press(game: &mut LurhookGame, key: VirtualKeyCode) {
+        let mut ctx = empty_frame(0.0);
+        ctx.key = Some(key);
+        game.update(&mut ctx);
+    }
+
+    #[test]
+    fn typing_searches_the_log_without_using_turns() {
+        let mut game = LurhookGame::default();
+        game.ui.set_layout(UILayout::Fishing);
+        game.ui
+            .add_entry(LogCategory::Fishing, "Hooked a fish!")
+            .unwrap();
+        game.ui
+            .add_entry(LogCategory::Event, "Rain sets in.")
+            .unwrap();
+        press(&mut game, VirtualKeyCode::Slash);
+        assert_eq!(game.ui.layout(), UILayout::MessageLog);
+        let turn = game.turn;
+        for key in [VirtualKeyCode::R, VirtualKeyCode::A, VirtualKeyCode::Q] {
+            press(&mut game, key);
+        }
+        press(&mut game, VirtualKeyCode::Back);
+        assert_eq!(game.ui.log_search(), "ra");
+        press(&mut game, VirtualKeyCode::Tab);
+        assert_eq!(game.ui.log_filter(), Some(LogCategory::Combat));
+        assert_eq!(game.turn, turn);
+        let mut buf = TextBuffer::new(SCREEN_WIDTH, SCREEN_HEIGHT);
+        game.render(&mut buf);
+        assert!(buf.contains("Message Log"));
+        press(&mut game, VirtualKeyCode::Escape);
+        assert_eq!(game.ui.layout(), UILayout::Fishing, "back where it opened");
+        assert_eq!(game.ui.log_search(), "");
+    }
+}
@@ -0,0 +1,339 @@
+//! Fishing without the rod: a cast net thrown over shallow water lands
+//! several small fish at once, and longlines set across the water hook
+//! fish on their own until the angler comes back to haul them in.
+
+use super::*;
+
+/// Farthest a cast net is thrown, in tiles.
+pub(crate) const NET_RANGE: i32 = 2;
+/// Tiles around where the net lands that it covers.
+const NET_RADIUS: i32 = 1;
+/// Heaviest fish the net holds, in kilograms; bigger ones slip out.
+const NET_MAX_WEIGHT: f32 = 2.0;
+/// Most fish one throw brings up.
+const NET_MAX_CATCH: usize = 3;
+/// Turns the net needs after a throw before it can be thrown again.
+const NET_COOLDOWN: u8 = 15;
+/// Farthest a longline reaches from where it is set, in tiles.
+pub(crate) const LONGLINE_LENGTH: i32 = 6;
+/// Hooks on a longline; a full line catches nothing more.
+const LONGLINE_HOOKS: usize = 4;
+/// Share of the usual bite chance with which a fish among the hooks takes
+/// one each turn.
+const LONGLINE_BITE: f32 = 0.2;
+
+impl LurhookGame {
+    /// How far the aiming cursor reaches for what is being thrown.
+    pub(crate) fn aim_range(&self) -> i32 {
+        match self.throwing {
+            Some(Throw::Net) => NET_RANGE,
+            Some(Throw::Longline) => LONGLINE_LENGTH,
+            Some(Throw::Trap) => traps::TRAP_RANGE,
+            Some(Throw::Chum) | None => self.player.cast_range + self.skills.cast_range,
+        }
+    }
+
+    /// Starts aiming the cast net unless it is still drying from the last
+    /// throw.
+    pub(crate) fn start_net(&mut self) {
+        if self.net_cooldown > 0 {
+            let msg = format!("Your net needs {} more turns to dry.", self.net_cooldown);
+            self.ui.add_entry(LogCategory::Fishing, &msg).ok();
+            return;
+        }
+        self.start_throw(Throw::Net, "Throw the net where?");
+    }
+
+    /// Starts aiming where a longline runs from the player.
+    pub(crate) fn start_longline(&mut self) {
+        self.start_throw(Throw::Longline, "Set the longline towards where?");
+    }
+
+    pub(crate) fn start_throw(&mut self, throw: Throw, prompt: &str) {
+        self.ui.add_entry(LogCategory::Fishing, prompt).ok();
+        self.inventory_focus = false;
+        self.throwing = Some(throw);
+        self.mode = GameMode::Aiming {
+            target: self.player.pos,
+        };
+    }
+
+    /// Throws the net at the aimed tile, which must be shallow water, and
+    /// lands the small fish under it.
+    pub(crate) fn throw_net(&mut self) {
+        let GameMode::Aiming { target } = self.mode else {
+            return;
+        };
+        self.throwing = None;
+        self.mode = GameMode::Exploring;
+        if self.map.tiles[self.map.idx(target)] != TileKind::ShallowWater {
+            self.ui
+                .add_entry(
+                    LogCategory::Fishing,
+                    "A cast net only works in shallow water.",
+                )
+                .ok();
+            return;
+        }
+        self.net_cooldown = NET_COOLDOWN;
+        let mut caught: Vec<usize> = ecology::fish_near(&self.fishes, target, NET_RADIUS)
+            .into_iter()
+            .map(|(i, _)| i)
+            .filter(|&i| self.hooked != Some(i) && self.fishes[i].weight <= NET_MAX_WEIGHT)
+            .take(NET_MAX_CATCH)
+            .collect();
+        // Remove from the back so the other indices stay put
+        caught.sort_unstable_by(|a, b| b.cmp(a));
+        for &i in &caught {
+            let fish = self.take_fish(i);
+            self.land(Catch::new(fish.kind, fish.weight));
+        }
+        // Whatever was too big to hold bolts from the splash
+        self.splashes.push(target);
+        let msg = match caught.len() {
+            0 => "Your net comes up empty.".to_string(),
+            1 => "Your net comes up with a fish!".to_string(),
+            n => format!("Your net comes up with {} fish!", n),
+        };
+        self.ui.add_entry(LogCategory::Fishing, &msg).ok();
+    }
+
+    /// Sets a longline over the water tiles between the player and the
+    /// aimed tile.
+    pub(crate) fn set_longline(&mut self) {
+        let GameMode::Aiming { target } = self.mode else {
+            return;
+        };
+        self.throwing = None;
+        self.mode = GameMode::Exploring;
+        let tiles: Vec<common::Point> = Self::line_path(self.player.pos, target)
+            .into_iter()
+            .filter(|&pt| self.map.tiles[self.map.idx(pt)].is_water())
+            .collect();
+        if tiles.is_empty() {
+            self.ui
+                .add_entry(LogCategory::Fishing, "A longline has to lie in the water.")
+                .ok();
+            return;
+        }
+        let Some(idx) = self
+            .player
+            .items
+            .iter()
+            .position(|i| i.kind == data::ItemKind::Longline)
+        else {
+            return;
+        };
+        let item = self.player.items.remove(idx);
+        self.longlines.push(Longline {
+            anchor: self.player.pos,
+            tiles,
+            catches: Vec::new(),
+            left: false,
+            item,
+        });
+        self.ui
+            .add_entry(
+                LogCategory::Fishing,
+                "You set a longline. Come back here to haul it in.",
+            )
+            .ok();
+    }
+
+    /// Dries the net, hauls in longlines the player has come back to and
+    /// lets fish take the hooks of the others.
+    pub(crate) fn update_methods(&mut self) {
+        self.net_cooldown = self.net_cooldown.saturating_sub(1);
+        self.haul_longlines();
+        self.hook_longline_fish();
+    }
+
+    fn haul_longlines(&mut self) {
+        let pos = self.player.pos;
+        for line in &mut self.longlines {
+            line.left |= line.anchor != pos;
+        }
+        let Some(idx) = self
+            .longlines
+            .iter()
+            .position(|l| l.left && l.anchor == pos)
+        else {
+            return;
+        };
+        let line = self.longlines.remove(idx);
+        let count = line.catches.len();
+        for catch in line.catches {
+            self.land(catch);
+        }
+        self.player.items.push(line.item);
+        let msg = match count {
+            0 => "You haul in your longline. Nothing took the hooks.".to_string(),
+            1 => "You haul in your longline with a fish!".to_string(),
+            n => format!("You haul in your longline with {} fish!", n),
+        };
+        self.ui.add_entry(LogCategory::Fishing, &msg).ok();
+    }
+
+    fn hook_longline_fish(&mut self) {
+        for l in 0..self.longlines.len() {
+            let mut i = 0;
+            while i < self.fishes.len() && self.longlines[l].catches.len() < LONGLINE_HOOKS {
+                let fish = &self.fishes[i];
+                if self.hooked == Some(i) || !self.longlines[l].covers(fish.position) {
+                    i += 1;
+                    continue;
+                }
+                let tile = self.map.tiles[self.map.idx(fish.position)];
+                let temperature = self.season().water_temperature();
+                let chance = fishing::bite_probability(
+                    tile,
+                    fishing::temperature_bonus(temperature, &fish.kind),
+                    fish.feeding_state(),
+                    false,
+                    fishing::Presentation::default(),
+                    &fish.kind,
+                ) * LONGLINE_BITE;
+                if self.rng.range(0.0, 1.0) < chance {
+                    let fish = self.take_fish(i);
+                    self.longlines[l]
+                        .catches
+                        .push(Catch::new(fish.kind, fish.weight));
+                } else {
+                    i += 1;
+                }
+            }
+        }
+    }
+
+    /// Takes the fish at `idx` off the map, keeping `hooked` on the fish
+    /// it was on.
+    pub(crate) fn take_fish(&mut self, idx: usize) -> Fish {
+        if let Some(hooked) = self.hooked.as_mut() {
+            if *hooked > idx {
+                *hooked -= 1;
+            }
+        }
+        self.fishes.remove(idx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(id: &str, kind: data::ItemKind) -> data::ItemType {
+        data::ItemType {
+            id: id.into(),
+            name: id.into(),
+            kind,
+            tension_bonus: 0,
+            reel_factor: 1.0,
+            bite_bonus: 0.0,
+            max_cast_range: data::DEFAULT_CAST_RANGE,
+        }
+    }
+
+    /// A game on open shallows holding `kind` and nothing else.
+    fn shallows_with(kind: data::ItemKind) -> LurhookGame {
+        let mut game = LurhookGame::default();
+        game.map.tiles.fill(TileKind::ShallowWater);
+        game.player.items = vec![item("TOOL", kind)];
+        game.inventory_cursor = 0;
+        game
+    }
+
+    fn place_fish(game: &mut LurhookGame, offsets: &[(i32, i32, f32)]) {
+        let kind = game.fishes[0].kind.clone();
+        game.fishes = offsets
+            .iter()
+            .map(|&(dx, dy, weight)| Fish {
+                weight,
+                ..Fish::new(kind.clone(), game.player.pos + common::Point::new(dx, dy))
+            })
+            .collect();
+    }
+
+    #[test]
+    fn the_net_lands_small_fish_and_then_dries() {
+        let mut game = shallows_with(data::ItemKind::Net);
+        place_fish(
+            &mut game,
+            &[(2, 0, 1.0), (2, 1, 1.5), (1, 0, 5.0), (5, 5, 1.0)],
+        );
+        game.activate_selected_item();
+        assert!(matches!(game.mode, GameMode::Aiming { .. }));
+        game.aim_at(game.player.pos + common::Point::new(4, 0));
+        let target = game.player.pos + common::Point::new(NET_RANGE, 0);
+        assert_eq!(game.mode, GameMode::Aiming { target });
+        game.throw_net();
+
+        assert_eq!(game.player.inventory.len(), 2);
+        assert_eq!(game.fishes.len(), 2, "the big fish and the far one stay");
+        assert_eq!(game.player.items.len(), 1, "the net is kept");
+        assert!(game
+            .events
+            .drain()
+            .any(|e| matches!(e, GameEvent::FishCaught { .. })));
+
+        game.activate_selected_item();
+        assert_eq!(game.mode, GameMode::Exploring);
+        for _ in 0..NET_COOLDOWN {
+            game.update_methods();
+        }
+        game.activate_selected_item();
+        assert!(matches!(game.mode, GameMode::Aiming { .. }));
+    }
+
+    #[test]
+    fn the_net_needs_shallow_water() {
+        let mut game = shallows_with(data::ItemKind::Net);
+        game.map.tiles.fill(TileKind::DeepWater);
+        game.start_net();
+        game.throw_net();
+        assert_eq!(game.net_cooldown, 0);
+        assert!(game.player.inventory.is_empty());
+    }
+
+    #[test]
+    fn longlines_hook_fish_until_hauled_in() {
+        let mut game = shallows_with(data::ItemKind::Longline);
+        let anchor = game.player.pos;
+        place_fish(&mut game, &[(1, 0, 1.0), (2, 0, 1.0), (0, 3, 1.0)]);
+        for fish in &mut game.fishes {
+            fish.hunger = 100;
+        }
+        game.activate_selected_item();
+        game.aim_at(anchor + common::Point::new(3, 0));
+        game.set_longline();
+        assert!(game.player.items.is_empty());
+        assert_eq!(game.longlines[0].tiles.len(), 3);
+
+        for _ in 0..200 {
+            game.update_methods();
+        }
+        assert_eq!(game.longlines[0].catches.len(), 2);
+        assert_eq!(game.fishes.len(), 1);
+        assert!(
+            game.player.inventory.is_empty(),
+            "not hauled while standing by"
+        );
+
+        game.player.pos = anchor + common::Point::new(0, -1);
+        game.update_methods();
+        game.player.pos = anchor;
+        game.update_methods();
+        assert!(game.longlines.is_empty());
+        assert_eq!(game.player.inventory.len(), 2);
+        assert_eq!(game.player.items[0].kind, data::ItemKind::Longline);
+    }
+
+    #[test]
+    fn taking_fish_keeps_the_hooked_index() {
+        let mut game = shallows_with(data::ItemKind::Net);
+        place_fish(&mut game, &[(1, 0, 1.0), (2, 0, 1.0), (3, 0, 9.0)]);
+        game.hooked = Some(2);
+        game.take_fish(0);
+        assert_eq!(game.hooked, Some(1));
+        assert_eq!(game.hooked_fish().unwrap().weight, 9.0);
+    }
+}
@@ -0,0 +1,58 @@
+//! Per-turn metrics recorded over a run for the summary charts.
+
+/// Time series of player stats sampled once per turn.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RunMetrics {
+    /// Hit points at the end of each turn.
+    pub hp: Vec<i32>,
+    /// Hunger at the end of each turn.
+    pub hunger: Vec<i32>,
+    /// Fish caught during each turn.
+    pub catches: Vec<i32>,
+    pending_catches: i32,
+}
+
+impl RunMetrics {
+    /// Counts a catch towards the current turn.
+    pub fn record_catch(&mut self) {
+        self.pending_catches += 1;
+    }
+
+    /// Closes the current turn with the given stats.
+    pub fn record_turn(&mut self, hp: i32, hunger: i32) {
+        self.hp.push(hp);
+        self.hunger.push(hunger);
+        self.catches.push(self.pending_catches);
+        self.pending_catches = 0;
+    }
+
+    /// Total fish caught over the recorded turns.
+    pub fn total_catches(&self) -> i32 {
+        self.catches.iter().sum::<i32>() + self.pending_catches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns_record_all_series() {
+        let mut m = RunMetrics::default();
+        m.record_turn(10, 100);
+        m.record_catch();
+        m.record_turn(9, 99);
+        assert_eq!(m.hp, vec![10, 9]);
+        assert_eq!(m.hunger, vec![100, 99]);
+        assert_eq!(m.catches, vec![0, 1]);
+    }
+
+    #[test]
+    fn pending_catches_count_in_total() {
+        let mut m = RunMetrics::default();
+        m.record_catch();
+        m.record_turn(10, 100);
+        m.record_catch();
+        assert_eq!(m.total_catches(), 2);
+    }
+}
@@ -0,0 +1,103 @@
+//! Migration runs: on the turns set in `assets/migrations.json` a school
+//! enters the area from a map edge, swims the water its species lives in
+//! across to the far edge and leaves by it.
+
+use super::*;
+use ecology::migration::{route, Edge, Run};
+
+impl LurhookGame {
+    /// Starts the runs due this turn in the current area and moves the
+    /// schools passing through. A fish on the line stays behind.
+    pub(crate) fn update_migrations(&mut self) {
+        self.start_migrations();
+        for r in 0..self.runs.len() {
+            for i in self.runs[r].advance(&mut self.fishes, self.hooked) {
+                self.take_fish(i);
+            }
+        }
+        let fishes = &self.fishes;
+        self.runs
+            .retain(|run| fishes.iter().any(|f| run.carries(f)));
+    }
+
+    /// Sends in the schools whose runs start this turn from a random edge,
+    /// or the next one round that water connects to the far side.
+    fn start_migrations(&mut self) {
+        let due: Vec<data::Migration> = self
+            .migrations
+            .iter()
+            .filter(|m| m.area == self.area.id && m.starts_on(self.turn))
+            .filter(|m| !self.runs.iter().any(|r| r.fish == m.fish))
+            .cloned()
+            .collect();
+        for migration in due {
+            let Some(kind) = self.fish_types.iter().find(|f| f.id == migration.fish) else {
+                continue;
+            };
+            let kind = kind.clone();
+            let first = self.rng.range(0, Edge::ALL.len() as i32) as usize;
+            let path = (0..Edge::ALL.len())
+                .find_map(|k| route(&self.map, &kind, Edge::ALL[(first + k) % Edge::ALL.len()]));
+            let Some(path) = path else {
+                continue;
+            };
+            for _ in 0..migration.school {
+                let weight = ecology::roll_weight(&kind, &mut self.rng);
+                self.fishes.push(Fish {
+                    weight,
+                    migrating: true,
+                    ..Fish::new(kind.clone(), path[0])
+                });
+            }
+            self.runs.push(Run::new(&kind.id, path));
+            self.ui
+                .add_entry(LogCategory::Event, &migration.announcement)
+                .ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schools_cross_the_area_and_leave() {
+        let mut game = LurhookGame::default();
+        let residents = game.fishes.len();
+        game.migrations = vec![data::Migration {
+            id: "RUN".into(),
+            area: game.area.id.clone(),
+            fish: "TWSA".into(),
+            school: 3,
+            every: 10_000,
+            first: game.turn,
+            announcement: "Twilight salmon are running along the coast!".into(),
+        }];
+        game.update_migrations();
+        assert_eq!(
+            game.ui.logs().last().unwrap(),
+            "Twilight salmon are running along the coast!"
+        );
+        assert_eq!(game.runs.len(), 1);
+        let migrants = |g: &LurhookGame| g.fishes.iter().filter(|f| f.migrating).count();
+        assert_eq!(migrants(&game), 3);
+        let path = game.runs[0].route.clone();
+        for _ in 0..path.len() + 10 {
+            game.turn += 1;
+            game.update_migrations();
+        }
+        assert_eq!(migrants(&game), 0);
+        assert!(game.runs.is_empty());
+        assert_eq!(game.fishes.len(), residents);
+    }
+
+    #[test]
+    fn the_table_gives_each_area_its_run() {
+        let game = LurhookGame::default();
+        let areas: Vec<&str> = game.migrations.iter().map(|m| m.area.as_str()).collect();
+        assert!(areas.contains(&"Coast"));
+        assert!(areas.contains(&"Offshore"));
+        assert!(areas.contains(&"DeepSea"));
+    }
+}
@@ -0,0 +1,194 @@
+//! Mouse play beyond clicking tiles: holding the left button reels, right
+//! click cancels aiming, and hovering shows what is on a tile.
+
+use super::*;
+use input::{REPEAT_DELAY_MS, REPEAT_MS};
+
+impl LurhookGame {
+    /// Sets the mouse buttons for the next update; backends call this each
+    /// frame alongside filling in `BTerm`.
+    pub fn set_mouse_buttons(&mut self, mouse: MouseButtons) {
+        self.mouse = mouse;
+    }
+
+    /// Turns this frame's mouse buttons into the keys they stand for, before
+    /// the frame is handled and recorded so replays play them back. Holding
+    /// the left button while fishing reels like holding the reel key, and
+    /// right click cancels aiming like Escape. Keys pressed in the same
+    /// frame win.
+    pub(crate) fn mouse_input(&mut self, ctx: &mut BTerm) {
+        let mouse = std::mem::take(&mut self.mouse);
+        self.hover = Some(common::Point::new(ctx.mouse_pos.0, ctx.mouse_pos.1));
+        if ctx.key.is_some() {
+            return;
+        }
+        match self.mode {
+            GameMode::Fishing { .. } if mouse.left_held => {
+                if ctx.left_click {
+                    self.reel_repeat_in = REPEAT_DELAY_MS;
+                } else {
+                    self.reel_repeat_in -= ctx.frame_time_ms;
+                    if self.reel_repeat_in > 0.0 {
+                        return;
+                    }
+                    self.reel_repeat_in += REPEAT_MS;
+                }
+                ctx.left_click = false;
+                ctx.key = Some(self.input.reel);
+            }
+            GameMode::Aiming { .. } if mouse.right_click => {
+                ctx.key = Some(VirtualKeyCode::Escape);
+            }
+            _ => self.reel_repeat_in = REPEAT_DELAY_MS,
+        }
+    }
+
+    /// What the tooltip says about the tile under the pointer, if it holds
+    /// a fish the player can make out, the merchant, the moored boat,
+    /// chum, a longline, a trap, a campfire or a hazard. Fish are named
+    /// once recorded in the codex.
+    pub(crate) fn tooltip_lines(&self) -> Option<Vec<String>> {
+        let view = self.camera();
+        let hover = self.hover?;
+        if !Rect::new(0, 0, view.width, view.height).contains(hover) {
+            return None;
+        }
+        let pt = view.origin() + hover;
+        if !self.is_visible(pt) {
+            return None;
+        }
+        let name = if let Some(fish) = self
+            .fishes
+            .iter()
+            .find(|f| f.position == pt && self.spotted(f))
+        {
+            if self.codex.count(&fish.kind.id) > 0 {
+                fish.kind.name.clone()
+            } else {
+                "???".to_string()
+            }
+        } else if self.merchant == Some(pt) {
+            "Merchant".to_string()
+        } else if !self.player.aboard && self.player.boat.pos == pt {
+            "Your boat".to_string()
+        } else if self.chum.iter().any(|c| c.pos == pt) {
+            "Chum".to_string()
+        } else if self.longlines.iter().any(|l| l.covers(pt)) {
+            "Longline".to_string()
+        } else if self.traps.iter().any(|t| t.pos == pt) {
+            "Trap".to_string()
+        } else if self.campfires.iter().any(|f| f.pos == pt) {
+            "Campfire".to_string()
+        } else if let Some(hazard) = self.hazards.iter().find(|h| h.pos == pt) {
+            hazard.kind.name().to_string()
+        } else {
+            return None;
+        };
+        let mut lines = vec![name];
+        if self.map.tiles[self.map.idx(pt)].is_water() {
+            lines.push(format!("Depth: {}m", self.map.depth(pt)));
+        }
+        lines.push(format!("Distance: {}", pt.chebyshev(self.player.pos)));
+        Some(lines)
+    }
+
+    /// Draws the tooltip next to the pointer when it is over something.
+    pub(super) fn draw_tooltip(&self, ctx: &mut dyn Renderer) {
+        if let (Some(hover), Some(lines)) = (self.hover, self.tooltip_lines()) {
+            self.ui.draw_tooltip(ctx, hover, &lines).ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_game;
+
+    fn open_water() -> LurhookGame {
+        let mut game = memory_game(0);
+        game.map.tiles.fill(TileKind::ShallowWater);
+        *game.sight.borrow_mut() = None;
+        game
+    }
+
+    fn frame(game: &mut LurhookGame, mouse: MouseButtons, click: bool, ms: f32) {
+        let mut ctx = empty_frame(ms);
+        ctx.left_click = click;
+        game.set_mouse_buttons(mouse);
+        game.update(&mut ctx);
+    }
+
+    #[test]
+    fn holding_the_left_button_reels_and_is_recorded() {
+        let mut game = open_water();
+        game.record_replay();
+        game.mode = GameMode::Fishing {
+            wait: 2,
+            bite: None,
+        };
+        let held = MouseButtons {
+            left_held: true,
+            ..MouseButtons::default()
+        };
+        let turn = game.turn;
+        frame(&mut game, held, true, 16.0);
+        assert_eq!(game.turn, turn + 1);
+        frame(&mut game, held, false, REPEAT_DELAY_MS - 1.0);
+        assert_eq!(game.turn, turn + 1);
+        frame(&mut game, held, false, 1.0);
+        frame(&mut game, held, false, REPEAT_MS);
+        assert_eq!(game.turn, turn + 3);
+        frame(&mut game, MouseButtons::default(), false, 1000.0);
+        assert_eq!(game.turn, turn + 3);
+        let replay = game.replay_recording().unwrap();
+        assert_eq!(replay.len(), 3);
+    }
+
+    #[test]
+    fn right_click_cancels_aiming() {
+        let mut game = open_water();
+        game.mode = GameMode::Aiming {
+            target: game.player.pos,
+        };
+        let right = MouseButtons {
+            right_click: true,
+            ..MouseButtons::default()
+        };
+        frame(&mut game, right, false, 16.0);
+        assert_eq!(game.mode, GameMode::Exploring);
+        assert!(game.ui.logs().last().unwrap().contains("Cast cancelled"));
+        assert!(game.cast_path.is_none());
+    }
+
+    #[test]
+    fn tooltip_names_fish_once_in_the_codex() {
+        let mut game = open_water();
+        let pos = game.player.pos + common::Point::new(2, 1);
+        game.fishes.truncate(1);
+        game.fishes[0].position = pos;
+        game.hover = Some(game.camera().to_local(pos));
+        let lines = game.tooltip_lines().unwrap();
+        assert_eq!(lines[0], "???");
+        assert_eq!(lines[1], format!("Depth: {}m", game.map.depth(pos)));
+        assert_eq!(lines[2], "Distance: 2");
+
+        let id = game.fishes[0].kind.id.clone();
+        let capture = codex::Capture {
+            id: &id,
+            turn: 0,
+            timestamp: None,
+            weight: 1.0,
+            location: "Coast",
+        };
+        game.codex.record_capture(&capture);
+        assert_eq!(game.tooltip_lines().unwrap()[0], game.fishes[0].kind.name);
+        game.ui.set_layout(UILayout::Standard);
+        let mut buf = ui_crate::TextBuffer::new(80, 25);
+        game.render(&mut buf);
+        assert!(buf.contains("Distance: 2"));
+
+        game.hover = Some(game.camera().to_local(pos + common::Point::new(1, 0)));
+        assert_eq!(game.tooltip_lines(), None);
+    }
+}
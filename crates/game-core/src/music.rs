@@ -0,0 +1,57 @@
+//! Soundtrack: the music follows the state of play, calm while exploring,
+//! darker in a storm, driving while a fish fights on the line and
+//! ominous when that fish is a legend.
+
+use super::*;
+use audio::MusicState;
+
+impl LurhookGame {
+    /// The soundtrack the game calls for now. A fight outranks the
+    /// weather.
+    pub(crate) fn music_state(&self) -> MusicState {
+        if self.meter.is_some() {
+            return match self.hooked_fish() {
+                Some(fish) if fish.kind.legendary => MusicState::Legendary,
+                _ => MusicState::Fight,
+            };
+        }
+        if self.weather.weather.is_stormy() {
+            MusicState::Storm
+        } else {
+            MusicState::Calm
+        }
+    }
+
+    /// Hands the soundtrack state to the audio manager when it changes and
+    /// moves its crossfade on by a frame of `ms`.
+    pub(crate) fn update_music(&mut self, ms: f32) {
+        let state = self.music_state();
+        self.audio.set_music(state);
+        self.audio.advance_music(ms);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn music_follows_the_weather_and_fights() {
+        let mut game = LurhookGame::default();
+        game.update_music(0.0);
+        assert_eq!(game.audio.music(), Some(MusicState::Calm));
+        game.weather.weather = Weather::Gale;
+        game.update_music(0.0);
+        assert_eq!(game.audio.music(), Some(MusicState::Storm));
+
+        let ray = game.fish_types.iter().find(|f| f.id == "NBRY").unwrap();
+        let pos = game.player.pos;
+        game.fishes = vec![Fish::new(ray.clone(), pos)];
+        game.hooked = Some(0);
+        game.meter = Some(TensionMeter::default());
+        assert_eq!(game.music_state(), MusicState::Legendary);
+        game.fishes[0].kind.legendary = false;
+        game.update_music(0.0);
+        assert_eq!(game.audio.music(), Some(MusicState::Fight));
+    }
+}
@@ -0,0 +1,941 @@
+//! Two-player co-op over TCP.
+//!
+//! The host runs the authoritative simulation. Each turn it merges its own
+//! key press with the guest's [`Action`], then sends the guest a [`Delta`]
+//! of what changed. The guest keeps a mirror game that only renders.
+//! Messages are newline-delimited JSON of at most [`MAX_MESSAGE_BYTES`].
+
+use super::*;
+use ecology::Fish;
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+
+/// TCP port used when none is given.
+pub const DEFAULT_PORT: u16 = 7878;
+
+/// Longest message accepted from a peer; a longer one drops the peer.
+const MAX_MESSAGE_BYTES: usize = 1 << 20;
+
+/// A hooked fish's fight as shown on the fight screen.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FightState {
+    pub tension: i32,
+    pub max_tension: i32,
+    pub strength: i32,
+    pub duration: i32,
+    pub style: data::FightStyle,
+    pub phase: fishing::FightPhase,
+    pub stamina: i32,
+    /// Boss fight stage out of `stages`.
+    pub stage: u32,
+    pub stages: u32,
+}
+
+/// One angler's condition as both peers see it.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct AnglerState {
+    pub pos: (i32, i32),
+    pub hp: i32,
+    pub hunger: i32,
+    pub thirst: i32,
+    pub line: i32,
+    pub effects: Vec<StatusEffect>,
+    /// Fish caught so far as `(id, weight)`.
+    pub catches: Vec<(String, f32)>,
+    /// Turn stamp and whether it is cooked for each of `catches`.
+    pub freshness: Vec<(u32, bool)>,
+    /// Line is in the water (casting or fighting).
+    pub fishing: bool,
+    /// A fish holds the bait and waits for the strike.
+    pub biting: bool,
+    pub fight: Option<FightState>,
+    pub aboard: bool,
+    /// Boat as `(x, y, durability)`.
+    pub boat: (i32, i32, i32),
+    pub anchored: bool,
+}
+
+/// Shared world and both anglers after a turn.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub turn: u32,
+    /// Map seed and area id; the map itself is regenerated from them.
+    pub world: (u64, String),
+    pub weather: Weather,
+    /// Fish as `(id, x, y)`.
+    pub fish: Vec<(String, i32, i32)>,
+    /// Hazards as `(x, y, turns left, kind)`.
+    pub hazards: Vec<(i32, i32, u8, HazardKind)>,
+    pub host: AnglerState,
+    pub guest: AnglerState,
+}
+
+/// Parts of a [`Snapshot`] that changed since the previous turn.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Delta {
+    pub turn: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub world: Option<(u64, String)>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weather: Option<Weather>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fish: Option<Vec<(String, i32, i32)>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hazards: Option<Vec<(i32, i32, u8, HazardKind)>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host: Option<AnglerState>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub guest: Option<AnglerState>,
+    /// New log lines for the guest.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub log: Vec<String>,
+}
+
+fn changed<T: Clone + PartialEq>(new: &T, old: &T) -> Option<T> {
+    (new != old).then(|| new.clone())
+}
+
+impl Snapshot {
+    /// Fields that differ from `prev`.
+    pub fn diff(&self, prev: &Snapshot) -> Delta {
+        Delta {
+            turn: self.turn,
+            world: changed(&self.world, &prev.world),
+            weather: changed(&self.weather, &prev.weather),
+            fish: changed(&self.fish, &prev.fish),
+            hazards: changed(&self.hazards, &prev.hazards),
+            host: changed(&self.host, &prev.host),
+            guest: changed(&self.guest, &prev.guest),
+            log: Vec::new(),
+        }
+    }
+
+    /// Updates this snapshot with the fields present in `delta`.
+    pub fn apply(&mut self, delta: &Delta) {
+        self.turn = delta.turn;
+        if let Some(world) = &delta.world {
+            self.world = world.clone();
+        }
+        if let Some(weather) = delta.weather {
+            self.weather = weather;
+        }
+        if let Some(fish) = &delta.fish {
+            self.fish = fish.clone();
+        }
+        if let Some(hazards) = &delta.hazards {
+            self.hazards = hazards.clone();
+        }
+        if let Some(host) = &delta.host {
+            self.host = host.clone();
+        }
+        if let Some(guest) = &delta.guest {
+            self.guest = guest.clone();
+        }
+    }
+}
+
+/// Everything sent over the wire.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum Message {
+    /// Host to guest on connect: parameters to build the same game.
+    Hello {
+        seed: u64,
+        area: String,
+        difficulty: Difficulty,
+        /// Rules of the host's run, custom ones included.
+        rules: DifficultyProfile,
+    },
+    Action(Action),
+    Delta(Box<Delta>),
+}
+
+/// Reads the next line of `reader` as a message; `None` once the peer
+/// closed the connection. A line over [`MAX_MESSAGE_BYTES`] is an error
+/// without reading the rest of it.
+fn read_message(reader: &mut impl BufRead) -> GameResult<Option<Message>> {
+    let mut line = Vec::new();
+    reader
+        .take(MAX_MESSAGE_BYTES as u64 + 1)
+        .read_until(b'\n', &mut line)?;
+    if line.is_empty() {
+        return Ok(None);
+    }
+    if line.last() == Some(&b'\n') {
+        line.pop();
+    } else if line.len() > MAX_MESSAGE_BYTES {
+        return Err(GameError::Parse(format!(
+            "net: message over {} bytes",
+            MAX_MESSAGE_BYTES
+        )));
+    }
+    serde_json::from_slice(&line)
+        .map(Some)
+        .map_err(|e| GameError::Parse(format!("net: {}", e)))
+}
+
+/// A JSON-lines connection read on a background thread.
+struct Link {
+    writer: TcpStream,
+    incoming: Receiver<GameResult<Message>>,
+}
+
+impl Link {
+    fn new(stream: TcpStream) -> GameResult<Self> {
+        stream.set_nodelay(true)?;
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let (tx, incoming) = mpsc::channel();
+        std::thread::spawn(move || loop {
+            let msg = match read_message(&mut reader) {
+                Ok(None) => return,
+                Ok(Some(msg)) => Ok(msg),
+                Err(e) => Err(e),
+            };
+            let failed = msg.is_err();
+            if tx.send(msg).is_err() || failed {
+                return;
+            }
+        });
+        Ok(Self {
+            writer: stream,
+            incoming,
+        })
+    }
+
+    fn send(&mut self, msg: &Message) -> GameResult<()> {
+        let mut line =
+            serde_json::to_string(msg).map_err(|e| GameError::Parse(format!("net: {}", e)))?;
+        line.push('\n');
+        self.writer.write_all(line.as_bytes())?;
+        Ok(())
+    }
+
+    /// Next received message, if any. Errors once the peer is gone.
+    fn try_recv(&self) -> GameResult<Option<Message>> {
+        match self.incoming.try_recv() {
+            Ok(msg) => msg.map(Some),
+            Err(TryRecvError::Empty) => Ok(None),
+            Err(TryRecvError::Disconnected) => Err(GameError::InvalidOperation),
+        }
+    }
+
+    fn recv(&self) -> GameResult<Message> {
+        self.incoming
+            .recv()
+            .map_err(|_| GameError::InvalidOperation)?
+    }
+}
+
+impl Drop for Link {
+    fn drop(&mut self) {
+        // The reader thread holds a clone of the socket, so close it
+        // explicitly to let the peer see the disconnect.
+        let _ = self.writer.shutdown(std::net::Shutdown::Both);
+    }
+}
+
+/// Per-angler game fields swapped in while the host simulates the guest.
+struct Partner {
+    player: Player,
+    ui: UIContext,
+    mode: GameMode,
+    meter: Option<TensionMeter>,
+    hooked: Option<usize>,
+    throwing: Option<Throw>,
+    reeling: bool,
+    cast_path: Option<Vec<common::Point>>,
+    cast_step: usize,
+    metrics: RunMetrics,
+    depth: i32,
+    /// Log lines already sent to the guest.
+    sent_logs: usize,
+}
+
+impl Partner {
+    fn new(game: &LurhookGame) -> Self {
+        Self {
+            player: game.player.clone(),
+            ui: UIContext::default(),
+            mode: GameMode::Exploring,
+            meter: None,
+            hooked: None,
+            throwing: None,
+            reeling: false,
+            cast_path: None,
+            cast_step: 0,
+            metrics: RunMetrics::default(),
+            depth: game.depth,
+            sent_logs: 0,
+        }
+    }
+
+    fn swap(&mut self, game: &mut LurhookGame) {
+        std::mem::swap(&mut game.player, &mut self.player);
+        std::mem::swap(&mut game.ui, &mut self.ui);
+        std::mem::swap(&mut game.mode, &mut self.mode);
+        std::mem::swap(&mut game.meter, &mut self.meter);
+        std::mem::swap(&mut game.hooked, &mut self.hooked);
+        std::mem::swap(&mut game.throwing, &mut self.throwing);
+        std::mem::swap(&mut game.reeling, &mut self.reeling);
+        std::mem::swap(&mut game.cast_path, &mut self.cast_path);
+        std::mem::swap(&mut game.cast_step, &mut self.cast_step);
+        std::mem::swap(&mut game.metrics, &mut self.metrics);
+        std::mem::swap(&mut game.depth, &mut self.depth);
+    }
+}
+
+fn angler(player: &Player, mode: GameMode, meter: Option<&TensionMeter>) -> AnglerState {
+    AnglerState {
+        pos: (player.pos.x, player.pos.y),
+        hp: player.hp,
+        hunger: player.hunger,
+        thirst: player.thirst,
+        line: player.line,
+        effects: player.effects.clone(),
+        catches: player
+            .inventory
+            .iter()
+            .map(|c| (c.kind.id.clone(), c.weight))
+            .collect(),
+        freshness: player
+            .inventory
+            .iter()
+            .map(|c| (c.stamp, c.cooked))
+            .collect(),
+        fishing: matches!(mode, GameMode::Fishing { .. }),
+        biting: matches!(mode, GameMode::Fishing { bite: Some(_), .. }),
+        fight: meter.map(|m| FightState {
+            tension: m.tension,
+            max_tension: m.max_tension,
+            strength: m.strength,
+            duration: m.duration,
+            style: m.style,
+            phase: m.phase,
+            stamina: m.stamina,
+            stage: m.stage,
+            stages: m.stages,
+        }),
+        aboard: player.aboard,
+        boat: (player.boat.pos.x, player.boat.pos.y, player.boat.durability),
+        anchored: player.boat.anchored,
+    }
+}
+
+/// Input the host pressed while waiting for the guest.
+#[derive(Clone, Copy, Debug)]
+struct QueuedInput {
+    key: Option<VirtualKeyCode>,
+    shift: bool,
+    left_click: bool,
+    mouse_pos: (i32, i32),
+}
+
+/// Host side: owns the simulation and the guest's angler.
+pub struct CoopHost {
+    game: Box<LurhookGame>,
+    partner: Partner,
+    link: Link,
+    queued: Option<QueuedInput>,
+    guest_action: Option<Action>,
+    last: Snapshot,
+}
+
+impl CoopHost {
+    fn start(stream: TcpStream, game: LurhookGame) -> GameResult<Self> {
+        let mut link = Link::new(stream)?;
+        link.send(&Message::Hello {
+            seed: game.seed,
+            area: game.area.id.clone(),
+            difficulty: game.difficulty,
+            rules: game.rules.clone(),
+        })?;
+        let partner = Partner::new(&game);
+        let mut host = Self {
+            game: Box::new(game),
+            partner,
+            link,
+            queued: None,
+            guest_action: None,
+            last: Snapshot::default(),
+        };
+        host.game.companion = Some(host.partner.player.pos);
+        // Turns wait for both anglers, fights included
+        host.game.turn_based_fishing = true;
+        host.send_delta()?;
+        Ok(host)
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        let game = &self.game;
+        Snapshot {
+            turn: game.turn,
+            world: (game.seed, game.area.id.clone()),
+            weather: game.weather.weather,
+            fish: game
+                .fishes
+                .iter()
+                .map(|f| (f.kind.id.clone(), f.position.x, f.position.y))
+                .collect(),
+            hazards: game
+                .hazards
+                .iter()
+                .map(|h| (h.pos.x, h.pos.y, h.turns, h.kind))
+                .collect(),
+            host: angler(&game.player, game.mode, game.meter.as_ref()),
+            guest: angler(
+                &self.partner.player,
+                self.partner.mode,
+                self.partner.meter.as_ref(),
+            ),
+        }
+    }
+
+    fn send_delta(&mut self) -> GameResult<()> {
+        let snapshot = self.snapshot();
+        let mut delta = snapshot.diff(&self.last);
+        let logs = self.partner.ui.entries();
+        delta.log = logs[self.partner.sent_logs.min(logs.len())..]
+            .iter()
+            .map(|e| e.text.clone())
+            .collect();
+        self.partner.sent_logs = logs.len();
+        self.link.send(&Message::Delta(Box::new(delta)))?;
+        self.last = snapshot;
+        Ok(())
+    }
+
+    /// Plays the guest's half of a turn with their angler swapped in.
+    fn guest_turn(&mut self, action: Action) {
+        self.partner.swap(&mut self.game);
+        let game = &mut self.game;
+        game.reeling = false;
+        match (action, game.mode) {
+            (Action::Move { dx, dy }, GameMode::Exploring) => {
+                game.try_move(common::Point::new(dx.signum(), dy.signum()));
+                if let GameMode::Trading { .. } = game.mode {
+                    game.mode = GameMode::Exploring;
+                    game.ui
+                        .add_log("The merchant only deals with one angler at a time.")
+                        .ok();
+                }
+            }
+            (Action::Cast { x, y, presentation }, GameMode::Exploring) => {
+                game.player.presentation = presentation;
+                game.cast();
+                if let GameMode::Aiming { .. } = game.mode {
+                    game.aim_at(common::Point::new(x, y));
+                    game.confirm_cast();
+                }
+            }
+            (Action::Reel, GameMode::Fishing { .. }) => game.reeling = true,
+            (Action::Strike, GameMode::Fishing { .. }) => game.strike_bite(),
+            (Action::Board, GameMode::Exploring) => game.toggle_boat(),
+            (Action::Anchor, GameMode::Exploring) => game.toggle_anchor(),
+            _ => {}
+        }
+        game.advance_player();
+        if matches!(game.mode, GameMode::Fishing { .. }) {
+            game.update_fishing();
+        }
+        game.sting_player();
+        // Dispatch while swapped so the guest's events reach their own log
+        game.dispatch_events();
+        self.partner.swap(&mut self.game);
+        self.game.companion = Some(self.partner.player.pos);
+    }
+
+    /// Queues local input and plays a turn once both anglers have acted.
+    fn update(&mut self, ctx: &mut BTerm) -> GameResult<()> {
+        while let Some(msg) = self.link.try_recv()? {
+            if let Message::Action(action) = msg {
+                self.guest_action = Some(action);
+            }
+        }
+        if self.queued.is_none() && (ctx.key.is_some() || ctx.left_click) {
+            self.queued = Some(QueuedInput {
+                key: ctx.key,
+                shift: ctx.shift,
+                left_click: ctx.left_click,
+                mouse_pos: ctx.mouse_pos,
+            });
+        }
+        ctx.key = None;
+        ctx.left_click = false;
+        self.game.update(ctx);
+        if let (Some(input), Some(action)) = (self.queued, self.guest_action) {
+            self.queued = None;
+            self.guest_action = None;
+            ctx.key = input.key;
+            ctx.shift = input.shift;
+            ctx.left_click = input.left_click;
+            ctx.mouse_pos = input.mouse_pos;
+            self.game.update(ctx);
+            self.guest_turn(action);
+            self.send_delta()?;
+        }
+        Ok(())
+    }
+
+    fn render(&self, ctx: &mut dyn Renderer) {
+        self.game.render(ctx);
+        if self.queued.is_some() {
+            ctx.print(0, 0, " Waiting for partner... ");
+        }
+    }
+}
+
+/// Guest side: a mirror game redrawn from the host's deltas.
+pub struct CoopGuest {
+    game: Box<LurhookGame>,
+    link: Link,
+    snapshot: Snapshot,
+    /// An action was sent and the host has not answered yet.
+    waiting: bool,
+}
+
+impl CoopGuest {
+    fn start(stream: TcpStream, storage: Box<dyn Storage>) -> GameResult<Self> {
+        let link = Link::new(stream)?;
+        let Message::Hello {
+            seed,
+            area,
+            difficulty,
+            rules,
+        } = link.recv()?
+        else {
+            return Err(GameError::Parse("net: expected hello".into()));
+        };
+        let mut game = LurhookGame::new_with_storage(seed, difficulty, Some(&area), storage)?;
+        game.rules = rules;
+        Ok(Self {
+            game: Box::new(game),
+            link,
+            snapshot: Snapshot {
+                world: (seed, area),
+                ..Snapshot::default()
+            },
+            waiting: true,
+        })
+    }
+
+    /// Copies the latest snapshot into the mirror game.
+    fn mirror(&mut self) -> GameResult<()> {
+        let snap = &self.snapshot;
+        let game = &mut self.game;
+        let (seed, ref area) = snap.world;
+        if game.seed != seed || game.area.id != *area {
+            let area = find_area(&game.areas, area)?;
+            game.map = generate(seed, &area)?;
+            game.ocean = mapgen::chunks::Ocean::new(seed, &area);
+            game.sight.take();
+            game.seed = seed;
+            game.area = area;
+            game.chart_features();
+        }
+        game.turn = snap.turn;
+        game.time_of_day = TIMES[((snap.turn / TIME_SEGMENT_TURNS) % TIMES.len() as u32) as usize];
+        game.update_tide();
+        game.weather.weather = snap.weather;
+        let kind = |id: &str| game.fish_types.iter().find(|f| f.id == id).cloned();
+        game.fishes = snap
+            .fish
+            .iter()
+            .filter_map(|(id, x, y)| {
+                kind(id).map(|kind| Fish::new(kind, common::Point::new(*x, *y)))
+            })
+            .collect();
+        game.player.inventory = snap
+            .guest
+            .catches
+            .iter()
+            .zip(&snap.guest.freshness)
+            .filter_map(|((id, weight), &(stamp, cooked))| {
+                kind(id).map(|kind| Catch {
+                    stamp,
+                    cooked,
+                    ..Catch::new(kind, *weight)
+                })
+            })
+            .collect();
+        game.hazards = snap
+            .hazards
+            .iter()
+            .map(|&(x, y, turns, kind)| Hazard {
+                pos: common::Point::new(x, y),
+                turns,
+                kind,
+            })
+            .collect();
+        let me = &snap.guest;
+        game.player.pos = common::Point::new(me.pos.0, me.pos.1);
+        game.player.hp = me.hp;
+        game.player.hunger = me.hunger;
+        game.player.thirst = me.thirst;
+        game.player.effects = me.effects.clone();
+        game.player.line = me.line;
+        game.player.aboard = me.aboard;
+        game.player.boat.pos = common::Point::new(me.boat.0, me.boat.1);
+        game.player.boat.durability = me.boat.2;
+        game.player.boat.anchored = me.anchored;
+        game.depth = game.map.depth(game.player.pos);
+        game.explore();
+        game.companion = Some(common::Point::new(snap.host.pos.0, snap.host.pos.1));
+        game.meter = me.fight.map(|f| TensionMeter {
+            tension: f.tension,
+            max_tension: f.max_tension,
+            duration: f.duration,
+            strength: f.strength,
+            phase: f.phase,
+            stamina: f.stamina,
+            stage: f.stage,
+            stages: f.stages,
+            ..TensionMeter::new(f.strength, f.style, 1.0, 1.0)
+        });
+        if me.fishing {
+            if !matches!(game.mode, GameMode::Fishing { .. }) {
+                game.mode = GameMode::Fishing {
+                    wait: 0,
+                    bite: None,
+                };
+                game.ui.set_layout(UILayout::Fishing);
+            }
+            if let GameMode::Fishing { bite, .. } = &mut game.mode {
+                *bite = me.biting.then_some(Bite {
+                    turns: 0,
+                    struck: false,
+                });
+            }
+        } else if matches!(game.mode, GameMode::Fishing { .. }) {
+            game.mode = GameMode::Exploring;
+            game.ui.set_layout(UILayout::Standard);
+        }
+        Ok(())
+    }
+
+    /// Turns a key press into an action; aiming happens locally.
+    fn action_for(&mut self, key: VirtualKeyCode, ctx: &mut BTerm) -> Option<Action> {
+        let game = &mut self.game;
+        if key == game.input.cast {
+            match game.mode {
+                GameMode::Exploring => game.cast(),
+                GameMode::Aiming { target } => {
+                    game.mode = GameMode::Exploring;
+                    return Some(Action::Cast {
+                        x: target.x,
+                        y: target.y,
+                        presentation: game.player.presentation,
+                    });
+                }
+                GameMode::Fishing { bite: Some(_), .. } => return Some(Action::Strike),
+                _ => {}
+            }
+            return None;
+        }
+        if key == game.input.reel && matches!(game.mode, GameMode::Fishing { .. }) {
+            return Some(Action::Reel);
+        }
+        if matches!(game.mode, GameMode::Aiming { .. }) {
+            if key == game.input.lure_depth {
+                game.cycle_lure_depth();
+                return None;
+            }
+            if key == game.input.reel {
+                game.cycle_retrieve();
+                return None;
+            }
+        }
+        if game.mode == GameMode::Exploring {
+            if key == game.input.board {
+                return Some(Action::Board);
+            }
+            if key == game.input.anchor {
+                return Some(Action::Anchor);
+            }
+        }
+        if let Some(direction) = game.key_direction(key) {
+            let delta = direction.delta();
+            return match &mut game.mode {
+                GameMode::Aiming { target } => {
+                    *target = game.map.bounds().clamp(*target + delta);
+                    None
+                }
+                _ => Some(Action::Move {
+                    dx: delta.x,
+                    dy: delta.y,
+                }),
+            };
+        }
+        let local = [
+            game.input.help,
+            game.input.options,
+            game.input.scroll_up,
+            game.input.scroll_down,
+        ];
+        if local.contains(&key) {
+            game.handle_input_key(Some(key));
+            return None;
+        }
+        if key == game.input.quit {
+            // The host keeps the run; the guest just leaves
+            game.flush_codex();
+            ctx.quit();
+            return None;
+        }
+        Some(Action::Wait)
+    }
+
+    fn update(&mut self, ctx: &mut BTerm) -> GameResult<()> {
+        self.game.ui.next_frame();
+        while let Some(msg) = self.link.try_recv()? {
+            if let Message::Delta(delta) = msg {
+                self.snapshot.apply(&delta);
+                self.mirror()?;
+                for line in &delta.log {
+                    self.game.ui.add_log(line).ok();
+                }
+                self.waiting = false;
+            }
+        }
+        if let Some(key) = ctx.key {
+            if self.game.ui.dismiss_popup() {
+                return Ok(());
+            }
+            if let Some(action) = self.action_for(key, ctx) {
+                if !self.waiting {
+                    self.link.send(&Message::Action(action))?;
+                    self.waiting = true;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn render(&self, ctx: &mut dyn Renderer) {
+        self.game.render(ctx);
+        if self.waiting {
+            ctx.print(0, 0, " Waiting for host... ");
+        }
+    }
+}
+
+/// A running co-op game from either side.
+pub enum CoopSession {
+    Host(Box<CoopHost>),
+    Guest(Box<CoopGuest>),
+    /// The connection dropped; holds the reason.
+    Closed(String),
+}
+
+impl CoopSession {
+    /// Waits on `addr` for a guest, then starts a new game as host.
+    pub fn host(addr: impl ToSocketAddrs) -> GameResult<Self> {
+        let listener = TcpListener::bind(addr)?;
+        println!("Waiting for a partner on {}...", listener.local_addr()?);
+        let (stream, _) = listener.accept()?;
+        let seed = unix_time().unwrap_or(0);
+        let game = LurhookGame::new_with_area(seed, Difficulty::Normal, None)?;
+        Ok(Self::Host(Box::new(CoopHost::start(stream, game)?)))
+    }
+
+    /// Connects to a host at `addr` and joins its game.
+    pub fn join(addr: impl ToSocketAddrs) -> GameResult<Self> {
+        let stream = TcpStream::connect(addr)?;
+        Ok(Self::Guest(Box::new(CoopGuest::start(
+            stream,
+            default_storage(),
+        )?)))
+    }
+
+    /// Returns `true` once the connection is gone.
+    pub fn is_closed(&self) -> bool {
+        matches!(self, Self::Closed(_))
+    }
+
+    /// Applies one frame of input and network traffic.
+    pub fn update(&mut self, ctx: &mut BTerm) {
+        let result = match self {
+            Self::Host(host) => host.update(ctx),
+            Self::Guest(guest) => guest.update(ctx),
+            Self::Closed(_) => Ok(()),
+        };
+        if result.is_err() {
+            *self = Self::Closed("Partner disconnected.".to_string());
+        }
+    }
+
+    /// Draws the shared map or the disconnect notice.
+    pub fn render(&self, ctx: &mut dyn Renderer) {
+        match self {
+            Self::Host(host) => host.render(ctx),
+            Self::Guest(guest) => guest.render(ctx),
+            Self::Closed(reason) => {
+                ctx.cls();
+                ctx.print_centered(10, reason);
+                ctx.print_centered(12, "Press Enter for Menu, Q to Quit");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{key_frame, memory_game};
+    use common::MemoryStorage;
+    use std::time::Duration;
+
+    /// Host and guest connected over loopback.
+    fn pair() -> (CoopHost, CoopGuest) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (server, _) = listener.accept().unwrap();
+        let host = CoopHost::start(server, memory_game(7)).unwrap();
+        let guest = CoopGuest::start(client, Box::new(MemoryStorage::default())).unwrap();
+        (host, guest)
+    }
+
+    fn poll_until(mut step: impl FnMut() -> bool) {
+        for _ in 0..200 {
+            if step() {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        panic!("timed out waiting for peer");
+    }
+
+    #[test]
+    fn delta_carries_only_changes() {
+        let prev = Snapshot {
+            turn: 3,
+            world: (7, "Coast".into()),
+            fish: vec![("salmon".into(), 1, 2)],
+            ..Snapshot::default()
+        };
+        let mut next = prev.clone();
+        next.turn = 4;
+        next.guest.pos = (5, 5);
+        let delta = next.diff(&prev);
+        assert!(delta.world.is_none());
+        assert!(delta.fish.is_none());
+        assert!(delta.host.is_none());
+        assert_eq!(delta.guest.as_ref().unwrap().pos, (5, 5));
+        let mut mirror = prev.clone();
+        mirror.apply(&delta);
+        assert_eq!(mirror, next);
+    }
+
+    #[test]
+    fn messages_round_trip_as_json() {
+        let messages = [
+            Message::Hello {
+                seed: 9,
+                area: "Offshore".into(),
+                difficulty: Difficulty::Hard,
+                rules: Difficulty::Hard.profile(),
+            },
+            Message::Action(Action::Cast {
+                x: 3,
+                y: 4,
+                presentation: fishing::Presentation {
+                    depth: fishing::LureDepth::Deep,
+                    retrieve: fishing::Retrieve::Jerky,
+                },
+            }),
+            Message::Action(Action::Strike),
+            Message::Delta(Box::new(Delta {
+                turn: 2,
+                weather: Some(Weather::Storm),
+                log: vec!["Caught!".into()],
+                ..Delta::default()
+            })),
+        ];
+        for msg in messages {
+            let json = serde_json::to_string(&msg).unwrap();
+            assert!(!json.contains('\n'));
+            assert_eq!(serde_json::from_str::<Message>(&json).unwrap(), msg);
+        }
+    }
+
+    #[test]
+    fn turn_merges_both_players_actions() {
+        let (mut host, mut guest) = pair();
+        assert_eq!(guest.game.seed, host.game.seed);
+        poll_until(|| {
+            guest.update(&mut empty_frame(0.0)).unwrap();
+            !guest.waiting
+        });
+        let start = guest.game.player.pos;
+        // Wade one tile rather than sail two
+        let next = host.game.map.idx(start + common::Point::new(1, 0));
+        host.game.map.tiles[next] = TileKind::ShallowWater;
+        host.partner.player.aboard = false;
+        guest.update(&mut key_frame(VirtualKeyCode::Right)).unwrap();
+        assert!(guest.waiting);
+
+        host.update(&mut key_frame(VirtualKeyCode::Left)).unwrap();
+        poll_until(|| {
+            host.update(&mut empty_frame(0.0)).unwrap();
+            host.queued.is_none()
+        });
+        assert_eq!(host.partner.player.pos, start + common::Point::new(1, 0));
+
+        poll_until(|| {
+            guest.update(&mut empty_frame(0.0)).unwrap();
+            !guest.waiting
+        });
+        assert_eq!(guest.game.player.pos, host.partner.player.pos);
+        assert_eq!(guest.game.companion, Some(host.game.player.pos));
+        assert_eq!(guest.game.turn, host.game.turn);
+    }
+
+    #[test]
+    fn dropped_peer_closes_session() {
+        let (host, guest) = pair();
+        drop(guest);
+        let mut session = CoopSession::Host(Box::new(host));
+        poll_until(|| {
+            session.update(&mut empty_frame(0.0));
+            session.is_closed()
+        });
+        let mut buf = ui_crate::TextBuffer::new(80, 25);
+        session.render(&mut buf);
+        assert!(buf.contains("Partner disconnected."));
+    }
+
+    #[test]
+    fn messages_are_read_up_to_the_size_cap() {
+        let action = serde_json::to_string(&Message::Action(Action::Wait)).unwrap();
+        let mut reader = std::io::Cursor::new(format!("{}\n", action).into_bytes());
+        assert_eq!(
+            read_message(&mut reader).unwrap(),
+            Some(Message::Action(Action::Wait))
+        );
+        assert_eq!(read_message(&mut reader).unwrap(), None);
+
+        let mut reader = std::io::Cursor::new(vec![b'x'; MAX_MESSAGE_BYTES * 2]);
+        assert!(matches!(
+            read_message(&mut reader),
+            Err(GameError::Parse(_))
+        ));
+        assert_eq!(reader.position(), MAX_MESSAGE_BYTES as u64 + 1);
+    }
+
+    #[test]
+    fn endless_line_drops_the_peer() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (server, _) = listener.accept().unwrap();
+        let link = Link::new(server).unwrap();
+        let flood = std::thread::spawn(move || {
+            let chunk = [b'x'; 4096];
+            while client.write_all(&chunk).is_ok() {}
+        });
+        assert!(matches!(link.recv(), Err(GameError::Parse(_))));
+        drop(link);
+        flood.join().unwrap();
+    }
+}
@@ -0,0 +1,166 @@
+//! Endless areas: sailing off the edge of the map of an endless area
+//! brings up the next chunk of sea (see [`mapgen::chunks::Ocean`]) with
+//! fish of its own. Positions on the map are local to the current chunk.
+
+use super::*;
+
+impl LurhookGame {
+    /// Where the player is, counted from the corner of the area's first
+    /// map.
+    pub(crate) fn world_pos(&self) -> common::Point {
+        self.ocean.world(self.chunk, self.player.pos)
+    }
+
+    /// Sails the boat over the map edge in direction `delta` onto the next
+    /// chunk. Only the boat goes, and gear in the water or a co-op partner
+    /// keeps it here.
+    pub(crate) fn sail_on(&mut self, delta: common::Point) {
+        let held = if !self.player.aboard {
+            Some("Only your boat can take you past the edge of the chart.")
+        } else if self.companion.is_some() {
+            Some("Your partner is still fishing these waters.")
+        } else if !self.longlines.is_empty() || !self.traps.is_empty() {
+            Some("Haul in your longlines and traps before sailing on.")
+        } else {
+            None
+        };
+        if let Some(reason) = held {
+            self.ui.add_entry(LogCategory::Event, reason).ok();
+            return;
+        }
+        let (chunk, local) = self.ocean.locate(self.world_pos() + delta);
+        let mut map = self.ocean.take(chunk).expect("map");
+        map.apply_tide(tide::tide_at(self.turn));
+        if !map.tiles[map.idx(local)].is_water() {
+            self.ocean.store(chunk, map);
+            self.ui
+                .add_entry(LogCategory::Event, "The boat cannot go ashore.")
+                .ok();
+            return;
+        }
+        let left = std::mem::replace(&mut self.map, map);
+        self.ocean.store(self.chunk, left);
+        self.chunk = chunk;
+        self.features = Default::default();
+        self.sight.take();
+        let area = self.area.clone();
+        self.populate(&area);
+        self.hazards.clear();
+        self.runs.clear();
+        self.chum.clear();
+        self.campfires.clear();
+        self.splashes.clear();
+        let home = common::Point::new(0, 0);
+        self.merchant = if chunk == home {
+            let start = common::Point::new(self.map.width as i32 / 2, self.map.height as i32 / 2);
+            shop::merchant_spot(&self.map, start)
+        } else {
+            None
+        };
+        self.place_player(local);
+        self.chart_features();
+        self.explore();
+        let msg = format!("You sail on to sea chart {},{}.", chunk.x, chunk.y);
+        self.ui.add_entry(LogCategory::Event, &msg).ok();
+        self.flush_codex();
+        self.autosave_now();
+    }
+
+    /// Fills the map with fish of `area` for the season, leaving out the
+    /// species fished out there.
+    pub(crate) fn populate(&mut self, area: &data::AreaType) {
+        let species = area_fish(area, &self.fish_types, self.season());
+        let map = &self.map;
+        let stock = self
+            .populations
+            .entry(area.id.clone())
+            .or_insert_with(|| ecology::population::Population::new(map));
+        let species = stock.available(&species);
+        self.fishes =
+            spawn_fish_population(&mut self.map, &species, 5, &mut self.rng).expect("fish");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A game in the endless deep sea, aboard at the middle of the east
+    /// edge of the map with open water beyond it.
+    fn at_the_edge() -> LurhookGame {
+        let storage = Box::new(common::MemoryStorage::default());
+        let mut game =
+            LurhookGame::new_with_storage(4, Difficulty::Normal, Some("DeepSea"), storage).unwrap();
+        let east = game.map.width as i32 - 1;
+        let y = (0..game.map.height as i32)
+            .find(|&y| {
+                let pt = common::Point::new(east, y);
+                game.map.tiles[game.map.idx(pt)].is_water()
+                    && game
+                        .ocean
+                        .tile(game.world_pos() - game.player.pos + pt + common::Point::new(1, 0))
+                        .unwrap()
+                        .is_water()
+            })
+            .expect("open water across the edge");
+        game.player.aboard = true;
+        game.place_player(common::Point::new(east, y));
+        game
+    }
+
+    #[test]
+    fn boats_sail_on_into_new_chunks_and_back() {
+        let mut game = at_the_edge();
+        let start = game.world_pos();
+        let home = game.map.tiles.clone();
+        game.try_move(common::Point::new(1, 0));
+        assert_eq!(game.chunk, common::Point::new(1, 0));
+        assert_eq!(game.world_pos(), start + common::Point::new(1, 0));
+        assert_eq!(game.player.pos.x, 0);
+        assert_eq!(game.player.boat.pos, game.player.pos);
+        assert_ne!(game.map.tiles, home);
+        assert!(game.merchant.is_none());
+        assert!(!game.fishes.is_empty());
+        assert!(game.map.is_explored(game.player.pos));
+        assert_eq!(
+            game.ui.logs().last().unwrap(),
+            "You sail on to sea chart 1,0."
+        );
+        let saved = savestate::SaveState::of(&game)
+            .restore(Box::new(common::MemoryStorage::default()))
+            .unwrap();
+        assert_eq!(saved.chunk, game.chunk);
+        assert_eq!(saved.map.tiles, game.map.tiles);
+        assert!(saved.merchant.is_none());
+
+        game.try_move(common::Point::new(-1, 0));
+        assert_eq!(game.chunk, common::Point::new(0, 0));
+        assert_eq!(game.world_pos(), start);
+        assert_eq!(
+            game.ui.logs().last().unwrap(),
+            "You sail on to sea chart 0,0."
+        );
+    }
+
+    #[test]
+    fn gear_in_the_water_and_bounded_areas_keep_you_here() {
+        let mut game = at_the_edge();
+        game.player.aboard = false;
+        game.try_move(common::Point::new(1, 0));
+        assert_eq!(game.chunk, common::Point::new(0, 0));
+        assert_eq!(
+            game.ui.logs().last().unwrap(),
+            "Only your boat can take you past the edge of the chart."
+        );
+
+        let mut game = LurhookGame::default();
+        assert!(!game.area.endless);
+        let east = game.map.width as i32 - 1;
+        let pos = game.player.pos;
+        game.place_player(common::Point::new(east, pos.y));
+        game.player.aboard = true;
+        game.try_move(common::Point::new(1, 0));
+        assert_eq!(game.chunk, common::Point::new(0, 0));
+        assert_eq!(game.player.pos.x, east);
+    }
+}
@@ -0,0 +1,179 @@
+//! Photo mode: a frozen free camera and shareable text cards.
+
+use super::*;
+
+/// Cells the camera moves per pan key.
+const PHOTO_PAN: i32 = 3;
+
+impl LurhookGame {
+    /// Freezes the run and frees the camera, starting over the player.
+    pub(crate) fn enter_photo(&mut self) {
+        self.mode = GameMode::Photo {
+            focus: self.player.pos,
+        };
+        self.ui
+            .add_log("Photo mode: pan to frame, Enter to save, Esc to leave.")
+            .ok();
+    }
+
+    /// Handles a key while in photo mode. Time does not pass.
+    pub(crate) fn handle_photo_key(&mut self, key: VirtualKeyCode) {
+        if key == self.input.photo || key == VirtualKeyCode::Escape {
+            self.mode = GameMode::Exploring;
+            return;
+        }
+        if key == self.input.cast || key == VirtualKeyCode::Return {
+            self.save_photo();
+            return;
+        }
+        if let Some(direction) = self.key_direction(key) {
+            let view = self.camera();
+            let centered = Point::new(view.width / 2, view.height / 2);
+            let focus = view.origin() + centered + direction.delta() * PHOTO_PAN;
+            self.mode = GameMode::Photo { focus };
+            // Snap to the clamped camera so panning back responds at once
+            self.mode = GameMode::Photo {
+                focus: self.camera().origin() + centered,
+            };
+        }
+    }
+
+    /// Species in frame, named once recorded in the codex.
+    pub(crate) fn visible_species(&self) -> Vec<String> {
+        let view = self.camera();
+        let mut names: Vec<String> = Vec::new();
+        for fish in &self.fishes {
+            if !view.contains(fish.position) || !self.is_visible(fish.position) {
+                continue;
+            }
+            let name = if self.codex.count(&fish.kind.id) > 0 {
+                fish.kind.name.clone()
+            } else {
+                "???".to_string()
+            };
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        names
+    }
+
+    /// The framed view in a border followed by seed, turn and species lines.
+    pub(crate) fn photo_card(&self) -> Vec<String> {
+        let view = self.camera();
+        let mut frame = TextBuffer::new(view.width, view.height);
+        self.draw_scene(&mut frame);
+        let border = format!("+{}+", "-".repeat(view.width as usize));
+        let mut card = vec![border.clone()];
+        card.extend((0..view.height).map(|y| format!("|{}|", frame.row(y))));
+        card.push(border);
+        let species = self.visible_species();
+        card.push(format!(
+            "Lurhook - {}, {}",
+            self.area.name, self.time_of_day
+        ));
+        card.push(format!("Seed {}  Turn {}", self.seed, self.turn));
+        card.push(format!(
+            "In view: {}",
+            if species.is_empty() {
+                "no fish".to_string()
+            } else {
+                species.join(", ")
+            }
+        ));
+        card
+    }
+
+    /// Writes the current card to storage and logs where it went.
+    fn save_photo(&mut self) {
+        let focus = match self.mode {
+            GameMode::Photo { focus } => focus,
+            _ => self.player.pos,
+        };
+        let key = format!(
+            "photo_{}_{}_{}x{}.txt",
+            self.seed, self.turn, focus.x, focus.y
+        );
+        let mut text = self.photo_card().join("\n");
+        text.push('\n');
+        match self.storage.write(&key, text.as_bytes()) {
+            Ok(()) => self.ui.add_log(&format!("Photo saved to {}", key)).ok(),
+            Err(e) => self.ui.add_log(&format!("Photo failed: {}", e)).ok(),
+        };
+    }
+
+    /// Hint bar drawn over the top of the view while framing a shot.
+    pub(super) fn draw_photo_banner(&self, ctx: &mut dyn Renderer) {
+        let text = " PHOTO  move: pan  Enter: save  Esc: leave ";
+        ctx.print_color(
+            (self.view_panel().width - text.len() as i32) / 2,
+            0,
+            RGB::named(BLACK),
+            RGB::named(WHITE),
+            text,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{key_frame, memory_game};
+
+    fn game() -> LurhookGame {
+        let mut game = memory_game(3);
+        game.ui.set_layout(UILayout::Standard);
+        game
+    }
+
+    #[test]
+    fn photo_mode_freezes_time_and_pans_camera() {
+        let mut game = game();
+        game.update(&mut key_frame(VirtualKeyCode::P));
+        assert!(matches!(game.mode, GameMode::Photo { .. }));
+        let turn = game.turn;
+        let player = game.player.pos;
+        let start = game.camera();
+        for _ in 0..3 {
+            game.update(&mut key_frame(VirtualKeyCode::L));
+        }
+        assert_eq!(game.turn, turn);
+        assert_eq!(game.player.pos, player);
+        assert!(game.camera().x > start.x);
+        game.update(&mut key_frame(VirtualKeyCode::Escape));
+        assert_eq!(game.mode, GameMode::Exploring);
+        assert_eq!(game.camera(), start);
+    }
+
+    #[test]
+    fn panning_stops_at_map_edge() {
+        let mut game = game();
+        game.enter_photo();
+        for _ in 0..50 {
+            game.handle_photo_key(VirtualKeyCode::H);
+        }
+        assert_eq!(game.camera().x, 0);
+        game.handle_photo_key(VirtualKeyCode::L);
+        assert_eq!(game.camera().x, PHOTO_PAN);
+    }
+
+    #[test]
+    fn saved_card_has_frame_and_metadata() {
+        let mut game = game();
+        let fish = game.fish_types[0].clone();
+        game.fishes = vec![Fish::new(fish, game.player.pos + Point::new(1, 0))];
+        game.enter_photo();
+        assert_eq!(game.visible_species(), vec!["???".to_string()]);
+        game.handle_photo_key(VirtualKeyCode::Return);
+        let pos = game.player.pos;
+        let key = format!("photo_3_{}_{}x{}.txt", game.turn, pos.x, pos.y);
+        let card = game.storage.read_string(&key).unwrap().unwrap();
+        let lines: Vec<&str> = card.lines().collect();
+        assert_eq!(lines.len(), game.camera().height as usize + 5);
+        assert!(lines[1].starts_with('|') && lines[1].ends_with('|'));
+        assert!(card.contains('@'));
+        assert!(card.contains("Lurhook - Coast"));
+        assert!(card.contains(&format!("Seed 3  Turn {}", game.turn)));
+        assert!(card.contains("In view: ???"));
+    }
+}
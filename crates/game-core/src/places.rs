@@ -0,0 +1,83 @@
+//! Named places: the bays, islands, reefs and trenches on the map (see
+//! [`mapgen::features`]), announced as the player enters them and named
+//! under the minimap.
+
+use super::*;
+use mapgen::features::Features;
+
+impl LurhookGame {
+    /// Finds and names the places on the current map. They are charted at
+    /// mean sea level, so the tide never moves or renames them.
+    pub(crate) fn chart_features(&mut self) {
+        let mut calm = self.map.clone();
+        calm.apply_tide(0);
+        let chunk = (self.chunk.x as u64).rotate_left(32) ^ self.chunk.y as u64;
+        self.features = Features::detect(&calm, self.seed ^ chunk);
+        self.place = self.features.index_at(self.player.pos);
+    }
+
+    /// Name of the place the player is in, if any.
+    pub(crate) fn place_name(&self) -> Option<&str> {
+        self.place.map(|i| self.features.list[i].name.as_str())
+    }
+
+    /// Notes the place at the player's position, logging its name when they
+    /// have just come into it.
+    pub(crate) fn enter_place(&mut self) {
+        let place = self.features.index_at(self.player.pos);
+        if place == self.place {
+            return;
+        }
+        self.place = place;
+        if let Some(name) = self.place_name().map(String::from) {
+            let msg = format!("You are entering {}.", name);
+            self.ui.add_entry(LogCategory::Event, &msg).ok();
+            self.journal_found(&name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entering_a_place_logs_its_name() {
+        let mut game = LurhookGame::default();
+        let (idx, place) = game
+            .features
+            .list
+            .iter()
+            .enumerate()
+            .find(|(i, f)| {
+                game.features.index_at(f.center) == Some(*i) && f.center != game.player.pos
+            })
+            .expect("a named place");
+        let (center, name) = (place.center, place.name.clone());
+        game.place = None;
+        game.place_player(center);
+        assert_eq!(game.place, Some(idx));
+        assert_eq!(
+            game.ui.logs().last().unwrap(),
+            &format!("You are entering {}.", name)
+        );
+        assert_eq!(game.minimap_view().place.as_deref(), Some(name.as_str()));
+        let logs = game.ui.logs().len();
+        game.place_player(center);
+        assert_eq!(game.ui.logs().len(), logs, "announced once");
+    }
+
+    #[test]
+    fn places_keep_their_names_through_the_tides() {
+        let mut game = LurhookGame::default();
+        let names = |g: &LurhookGame| -> Vec<String> {
+            g.features.list.iter().map(|f| f.name.clone()).collect()
+        };
+        let before = names(&game);
+        assert!(!before.is_empty());
+        game.turn = 5;
+        game.update_tide();
+        game.chart_features();
+        assert_eq!(names(&game), before);
+    }
+}
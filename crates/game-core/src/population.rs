@@ -0,0 +1,105 @@
+//! Fish stocks: schools breed up to what each area's water carries, and
+//! overfishing a species leaves it scarce in that area for a long while.
+//! Every area visited keeps its own stock, saved with the run.
+
+use super::*;
+use ecology::population::{breed, Population, SpeciesStats, BREEDING_TURNS};
+
+impl LurhookGame {
+    /// Fish stock of the current area.
+    pub(crate) fn population_mut(&mut self) -> &mut Population {
+        let map = &self.map;
+        self.populations
+            .entry(self.area.id.clone())
+            .or_insert_with(|| Population::new(map))
+    }
+
+    /// How every species is faring in the current area, for an ecology
+    /// report.
+    pub fn ecology_report(&self) -> Vec<SpeciesStats> {
+        self.populations
+            .get(&self.area.id)
+            .map_or_else(Vec::new, |stock| stock.report(&self.fishes))
+    }
+
+    /// Moves every area's stock on a turn and, every few turns, lets the
+    /// schools here breed.
+    pub(crate) fn update_population(&mut self) {
+        for stock in self.populations.values_mut() {
+            stock.advance(self.turn);
+        }
+        if !self.turn.is_multiple_of(BREEDING_TURNS) {
+            return;
+        }
+        self.population_mut();
+        let stock = &self.populations[&self.area.id];
+        breed(&self.map, &mut self.fishes, stock, &mut self.rng);
+    }
+
+    /// Counts a catch of `kind` against the stock here, warning when it
+    /// fishes the species out.
+    pub(crate) fn record_stock(&mut self, kind: &data::FishType) {
+        if self.population_mut().record_catch(&kind.id) {
+            let msg = format!(
+                "The {} are fished out here. Give them time to recover.",
+                kind.name
+            );
+            self.ui.add_entry(LogCategory::Event, &msg).ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ecology::population::{DEPLETION_TURNS, OVERFISHING_CATCHES};
+
+    #[test]
+    fn overfishing_keeps_a_species_away() {
+        let mut game = LurhookGame::default();
+        let koi = game
+            .fish_types
+            .iter()
+            .find(|f| f.id == "GDKO")
+            .unwrap()
+            .clone();
+        for _ in 0..OVERFISHING_CATCHES {
+            game.land(Catch::new(koi.clone(), 1.0));
+        }
+        assert_eq!(
+            game.ui.logs().last().unwrap(),
+            "The Golden Koi are fished out here. Give them time to recover."
+        );
+        let report = game.ecology_report();
+        let row = report.iter().find(|s| s.id == "GDKO").unwrap();
+        assert_eq!(row.depleted_turns, DEPLETION_TURNS);
+        let species = area_fish(&game.area, &game.fish_types, game.season());
+        assert!(game
+            .population_mut()
+            .available(&species)
+            .iter()
+            .all(|f| f.id != "GDKO"));
+    }
+
+    #[test]
+    fn schools_grow_over_time() {
+        let mut game = LurhookGame::default();
+        let capacity = game.population_mut().capacity;
+        // A species at home anywhere in the water
+        let kind = data::FishType {
+            min_depth: 0,
+            max_depth: i32::MAX,
+            biomes: Vec::new(),
+            legendary: false,
+            ..game.fishes[0].kind.clone()
+        };
+        let pos = game.fishes[0].position;
+        game.fishes = vec![Fish::new(kind.clone(), pos), Fish::new(kind, pos)];
+        game.turn = BREEDING_TURNS - 1;
+        for _ in 0..BREEDING_TURNS * 3 {
+            game.advance_clock();
+        }
+        assert!(game.fishes.len() > 2);
+        assert!(game.fishes.len() <= capacity);
+    }
+}
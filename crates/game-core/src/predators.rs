@@ -0,0 +1,249 @@
+//! Predators: while a fish is fought, sharks and big aggressive fish close
+//! in on it. One that reaches it tears it off a slack line, or takes the
+//! hook itself if the line is taut and starts a harder fight.
+
+use super::*;
+use data::FightStyle;
+
+/// How far from the hooked fish predators notice the fight, in tiles.
+const PREDATOR_RANGE: i32 = 6;
+/// How many times stronger than the hooked fish an aggressive fish has to
+/// be to hunt it.
+const PREDATOR_STRENGTH: i32 = 2;
+
+impl LurhookGame {
+    /// Free fish that hunt the one being fought: aggressive fish at least
+    /// [`PREDATOR_STRENGTH`] times as strong within [`PREDATOR_RANGE`].
+    fn predator_fish(&self) -> Vec<usize> {
+        let Some(prey) = self.meter.as_ref().and(self.hooked_fish()) else {
+            return Vec::new();
+        };
+        self.fishes
+            .iter()
+            .enumerate()
+            .filter(|&(i, f)| {
+                self.hooked != Some(i)
+                    && f.kind.fight_style == FightStyle::Aggressive
+                    && f.kind.strength >= prey.kind.strength * PREDATOR_STRENGTH
+                    && f.position.chebyshev(prey.position) <= PREDATOR_RANGE
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// How many tiles the nearest shark or predator is from the hooked
+    /// fish, if one is in range, for the fight screen.
+    pub(crate) fn predator_distance(&self) -> Option<i32> {
+        let prey = self.meter.as_ref().and(self.hooked_fish())?.position;
+        let sharks = self
+            .hazards
+            .iter()
+            .filter(|h| h.kind == HazardKind::Shark)
+            .map(|h| h.pos);
+        let fish = self
+            .predator_fish()
+            .into_iter()
+            .map(|i| self.fishes[i].position);
+        sharks
+            .chain(fish)
+            .map(|p| p.chebyshev(prey))
+            .filter(|&d| d <= PREDATOR_RANGE)
+            .min()
+    }
+
+    /// Predator fish swim a tile towards the fish being fought.
+    pub(crate) fn stalk_hooked_fish(&mut self) {
+        let Some(prey) = self.hooked_fish().map(|f| f.position) else {
+            return;
+        };
+        for i in self.predator_fish() {
+            let pos = self.fishes[i].position;
+            let next = pos + (prey - pos).signum();
+            if next != prey && self.is_open_water(next) {
+                self.fishes[i].position = next;
+            }
+        }
+    }
+
+    /// A shark or predator next to the fish being fought gets it. On a
+    /// taut line the predator takes the hook and the fight goes on against
+    /// it; on a slack one the catch is lost.
+    pub(crate) fn predator_strike(&mut self) {
+        let (Some(meter), Some(prey)) = (self.meter.as_ref(), self.hooked_fish()) else {
+            return;
+        };
+        let taut = meter.tension * 2 >= meter.max_tension;
+        let pos = prey.position;
+        let shark = self
+            .hazards
+            .iter()
+            .position(|h| h.kind == HazardKind::Shark && h.pos.chebyshev(pos) <= 1);
+        let fish = self
+            .predator_fish()
+            .into_iter()
+            .find(|&i| self.fishes[i].position.chebyshev(pos) <= 1);
+        // A shark taking the hook is fought as the strongest aggressive
+        // species there is
+        let hooker = match (shark, fish) {
+            (_, Some(i)) => Some(self.fishes[i].clone()),
+            (Some(h), None) => self
+                .shark_species()
+                .map(|kind| Fish::new(kind, self.hazards[h].pos)),
+            (None, None) => return,
+        };
+        let id = self.hooked_id();
+        if let Some(i) = self.hooked.take() {
+            self.take_fish(i);
+        }
+        if let Some(h) = shark {
+            self.hazards.remove(h);
+        }
+        match hooker.filter(|_| taut) {
+            Some(predator) => self.switch_hook(predator, fish.is_some()),
+            None => {
+                self.meter = None;
+                self.adaptive.record(false);
+                self.events.push(GameEvent::FishStolen { id });
+                self.splash();
+                self.mode = GameMode::Exploring;
+                self.ui.set_layout(UILayout::Standard);
+            }
+        }
+    }
+
+    /// Puts `predator` on the hook in place of the eaten fish. A fish
+    /// already in the water is taken out of it first.
+    fn switch_hook(&mut self, predator: Fish, in_water: bool) {
+        if in_water {
+            let idx = self
+                .fishes
+                .iter()
+                .position(|f| f.position == predator.position && f.kind.id == predator.kind.id);
+            if let Some(idx) = idx {
+                self.fishes.remove(idx);
+            }
+        }
+        let reel = self.reel_strength();
+        let Some(meter) = self.meter.as_mut() else {
+            return;
+        };
+        let mut fight = TensionMeter::new(
+            self.rules.scale_strength(predator.kind.strength),
+            predator.kind.fight_style,
+            reel,
+            predator.size_ratio(),
+        );
+        fight.tension = meter.tension;
+        fight.max_tension = meter.max_tension;
+        fight.escape_threshold = meter.escape_threshold;
+        fight.peak_tension = meter.peak_tension;
+        fight.alert = true;
+        *meter = fight;
+        self.fishes.push(predator);
+        self.hooked = Some(self.fishes.len() - 1);
+        self.ui
+            .add_entry(
+                LogCategory::Fishing,
+                "Something bigger takes the hook! Hold on!",
+            )
+            .ok();
+    }
+
+    /// The species a shark is fought as: the strongest aggressive one that
+    /// is not legendary.
+    fn shark_species(&self) -> Option<data::FishType> {
+        self.fish_types
+            .iter()
+            .filter(|f| f.fight_style == FightStyle::Aggressive && !f.legendary)
+            .max_by_key(|f| f.strength)
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A game at sea fighting a trout four tiles east of the player, with
+    /// the line at `tension` and a shark three tiles south of the fish.
+    fn fighting(tension: i32) -> LurhookGame {
+        let mut game = LurhookGame::default();
+        game.map.tiles.fill(TileKind::DeepWater);
+        let pos = game.player.pos + common::Point::new(4, 0);
+        let trout = game.fish_types.iter().find(|f| f.id == "TROUT").unwrap();
+        game.fishes = vec![Fish::new(trout.clone(), pos)];
+        game.hooked = Some(0);
+        game.meter = Some(TensionMeter {
+            tension,
+            ..TensionMeter::default()
+        });
+        game.mode = GameMode::Fishing {
+            wait: 0,
+            bite: None,
+        };
+        game.ui.set_layout(UILayout::Fishing);
+        game.hazards = vec![Hazard {
+            pos: pos + common::Point::new(0, 3),
+            turns: 20,
+            kind: HazardKind::Shark,
+        }];
+        game
+    }
+
+    #[test]
+    fn sharks_tear_fish_off_a_slack_line() {
+        let mut game = fighting(10);
+        assert_eq!(game.predator_distance(), Some(3));
+        game.update_hazards();
+        assert!(game.meter.is_some(), "still swimming over");
+        assert_eq!(game.fight_view().unwrap().threat, Some(2));
+        game.update_hazards();
+        assert!(game.meter.is_none());
+        assert!(game.hooked.is_none());
+        assert!(game.fishes.is_empty());
+        assert!(game.hazards.is_empty(), "the shark swims off with it");
+        assert_eq!(game.mode, GameMode::Exploring);
+        assert!(game
+            .events
+            .drain()
+            .any(|e| matches!(e, GameEvent::FishStolen { id: Some(_) })));
+    }
+
+    #[test]
+    fn sharks_take_the_hook_on_a_taut_line() {
+        let mut game = fighting(80);
+        let before = game.meter.as_ref().unwrap().strength;
+        game.update_hazards();
+        game.update_hazards();
+        let meter = game.meter.as_ref().expect("still fighting");
+        assert_eq!(meter.tension, 80);
+        assert!(meter.strength > before);
+        assert_eq!(game.hooked_fish().unwrap().kind.id, "AZSH");
+        assert_eq!(game.fishes.len(), 1);
+        assert_eq!(
+            game.ui.logs().last().unwrap(),
+            "Something bigger takes the hook! Hold on!"
+        );
+    }
+
+    #[test]
+    fn big_aggressive_fish_hunt_the_hooked_one() {
+        let mut game = fighting(80);
+        game.hazards.clear();
+        let prey = game.fishes[0].position;
+        let pike = game.fish_types.iter().find(|f| f.id == "LUR1").unwrap();
+        let guppy = game.fish_types.iter().find(|f| f.id == "RGUP").unwrap();
+        game.fishes
+            .push(Fish::new(pike.clone(), prey + common::Point::new(3, 0)));
+        game.fishes
+            .push(Fish::new(guppy.clone(), prey + common::Point::new(-2, 0)));
+        assert_eq!(game.predator_distance(), Some(3));
+
+        game.update_hazards();
+        assert_eq!(game.fishes[1].position, prey + common::Point::new(2, 0));
+        assert_eq!(game.fishes[2].position, prey + common::Point::new(-2, 0));
+        game.update_hazards();
+        assert_eq!(game.hooked_fish().unwrap().kind.id, "LUR1");
+        assert_eq!(game.fishes.len(), 2, "the trout is eaten");
+    }
+}
@@ -0,0 +1,202 @@
+//! Rich-status integrations showing the current run outside the game.
+//!
+//! A [`Presence`] receives a [`RunStatus`] whenever it changes. The game
+//! has no event bus yet, so [`PresenceHook`] compares the status every
+//! frame and only forwards changes.
+
+use super::*;
+use discord_rich_presence::activity::{Activity, Timestamps};
+use discord_rich_presence::{DiscordIpc, DiscordIpcClient};
+
+/// Environment variable holding the Discord application id.
+pub const DISCORD_CLIENT_ID_VAR: &str = "LURHOOK_DISCORD_CLIENT_ID";
+
+/// Run state shared with presence integrations.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RunStatus {
+    /// Display name of the area.
+    pub area: String,
+    pub score: i32,
+    /// Name of the heaviest fish caught this run.
+    pub biggest_catch: Option<String>,
+    pub time_of_day: &'static str,
+}
+
+impl RunStatus {
+    /// Status of `game` as it stands.
+    pub(crate) fn of(game: &LurhookGame) -> Self {
+        Self {
+            area: game.area.name.clone(),
+            score: game.score(),
+            biggest_catch: game
+                .player
+                .inventory
+                .iter()
+                .max_by(|a, b| a.weight.total_cmp(&b.weight))
+                .map(|c| c.kind.name.clone()),
+            time_of_day: game.time_of_day,
+        }
+    }
+
+    /// First status line, e.g. `Fishing the Coast at Dawn`.
+    pub fn details(&self) -> String {
+        format!("Fishing the {} at {}", self.area, self.time_of_day)
+    }
+
+    /// Second status line with score and best fish.
+    pub fn state(&self) -> String {
+        match &self.biggest_catch {
+            Some(name) => format!("Score {} | Biggest: {}", self.score, name),
+            None => format!("Score {}", self.score),
+        }
+    }
+}
+
+/// Something that can show the player's current run, such as a chat client.
+pub trait Presence {
+    /// Shows `status` in place of whatever was shown before.
+    fn show(&mut self, status: &RunStatus) -> GameResult<()>;
+
+    /// Removes the status, e.g. when back at the menu.
+    fn clear(&mut self) -> GameResult<()>;
+}
+
+/// Forwards status changes to a [`Presence`].
+pub struct PresenceHook {
+    presence: Box<dyn Presence>,
+    last: Option<RunStatus>,
+}
+
+impl PresenceHook {
+    pub fn new(presence: Box<dyn Presence>) -> Self {
+        Self {
+            presence,
+            last: None,
+        }
+    }
+
+    /// Sends the status of `game` (or a clear with no game) if it changed.
+    pub(crate) fn refresh(&mut self, game: Option<&LurhookGame>) {
+        let status = game.map(RunStatus::of);
+        if status == self.last {
+            return;
+        }
+        let sent = match &status {
+            Some(status) => self.presence.show(status),
+            None => self.presence.clear(),
+        };
+        // A failed update is retried on the next change
+        if sent.is_ok() {
+            self.last = status;
+        }
+    }
+}
+
+fn ipc_error(e: discord_rich_presence::error::Error) -> GameError {
+    GameError::Io(std::io::Error::other(e.to_string()))
+}
+
+/// [`Presence`] shown as Discord Rich Presence through the local client.
+pub struct DiscordPresence {
+    client: DiscordIpcClient,
+    /// Unix time in seconds the session started, shown as elapsed time.
+    started: i64,
+}
+
+impl DiscordPresence {
+    /// Connects to a running Discord client for application `client_id`.
+    pub fn connect(client_id: &str) -> GameResult<Self> {
+        let mut client = DiscordIpcClient::new(client_id);
+        client.connect().map_err(ipc_error)?;
+        Ok(Self {
+            client,
+            started: unix_time().unwrap_or(0) as i64,
+        })
+    }
+}
+
+impl Presence for DiscordPresence {
+    fn show(&mut self, status: &RunStatus) -> GameResult<()> {
+        let details = status.details();
+        let state = status.state();
+        let activity = Activity::new()
+            .details(details.as_str())
+            .state(state.as_str())
+            .timestamps(Timestamps::new().start(self.started));
+        self.client.set_activity(activity).map_err(ipc_error)
+    }
+
+    fn clear(&mut self) -> GameResult<()> {
+        self.client.clear_activity().map_err(ipc_error)
+    }
+}
+
+impl Drop for DiscordPresence {
+    fn drop(&mut self) {
+        let _ = self.client.close();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_game;
+    use std::sync::{Arc, Mutex};
+
+    /// Records every call for inspection.
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<Mutex<Vec<Option<RunStatus>>>>);
+
+    impl Presence for Recorder {
+        fn show(&mut self, status: &RunStatus) -> GameResult<()> {
+            self.0.lock().unwrap().push(Some(status.clone()));
+            Ok(())
+        }
+
+        fn clear(&mut self) -> GameResult<()> {
+            self.0.lock().unwrap().push(None);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn status_names_heaviest_catch() {
+        let mut game = memory_game(0);
+        let fish = &game.fish_types;
+        game.player.inventory = vec![
+            Catch::new(fish[0].clone(), 3.0),
+            Catch::new(fish[1].clone(), 7.5),
+            Catch::new(fish[2].clone(), 5.0),
+        ];
+        let status = RunStatus::of(&game);
+        assert_eq!(status.biggest_catch.as_ref(), Some(&fish[1].name));
+        assert_eq!(
+            status.details(),
+            format!("Fishing the Coast at {}", game.time_of_day)
+        );
+        assert!(status
+            .state()
+            .starts_with(&format!("Score {} | Biggest:", game.score())));
+        game.player.inventory.clear();
+        assert_eq!(RunStatus::of(&game).state(), "Score 0");
+    }
+
+    #[test]
+    fn hook_forwards_only_changes() {
+        let recorder = Recorder::default();
+        let mut hook = PresenceHook::new(Box::new(recorder.clone()));
+        let mut game = memory_game(0);
+        hook.refresh(Some(&game));
+        hook.refresh(Some(&game));
+        game.player
+            .inventory
+            .push(Catch::average(game.fish_types[0].clone()));
+        hook.refresh(Some(&game));
+        hook.refresh(None);
+        let calls = recorder.0.lock().unwrap();
+        assert_eq!(calls.len(), 3);
+        assert_eq!(calls[0].as_ref().unwrap().score, 0);
+        assert!(calls[1].as_ref().unwrap().biggest_catch.is_some());
+        assert!(calls[2].is_none());
+    }
+}
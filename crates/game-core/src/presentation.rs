@@ -0,0 +1,82 @@
+//! Lure presentation: while aiming, the angler picks how deep the lure
+//! runs and how it is retrieved, and each fish near the bait judges the
+//! cast by its depth band and fight style.
+
+use super::*;
+
+impl LurhookGame {
+    /// Runs the lure one band deeper, back to the surface after the
+    /// deepest.
+    pub(crate) fn cycle_lure_depth(&mut self) {
+        let presentation = &mut self.player.presentation;
+        presentation.depth = presentation.depth.next();
+        self.log_presentation();
+    }
+
+    /// Switches to the next retrieve style.
+    pub(crate) fn cycle_retrieve(&mut self) {
+        let presentation = &mut self.player.presentation;
+        presentation.retrieve = presentation.retrieve.next();
+        self.log_presentation();
+    }
+
+    /// The lure depth and retrieve while aiming a cast, for the status panel.
+    pub(crate) fn presentation_view(&self) -> Option<(&'static str, &'static str)> {
+        if self.throwing.is_some() || !matches!(self.mode, GameMode::Aiming { .. }) {
+            return None;
+        }
+        let presentation = self.player.presentation;
+        Some((presentation.depth.name(), presentation.retrieve.name()))
+    }
+
+    fn log_presentation(&mut self) {
+        let presentation = self.player.presentation;
+        let msg = format!(
+            "Lure runs {}, {} retrieve.",
+            presentation.depth.name().to_lowercase(),
+            presentation.retrieve.name().to_lowercase()
+        );
+        self.ui.add_entry(LogCategory::Fishing, &msg).ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fishing::{LureDepth, Retrieve};
+
+    fn aiming() -> LurhookGame {
+        let mut game = LurhookGame::default();
+        game.handle_input_key(Some(game.input.cast));
+        game
+    }
+
+    #[test]
+    fn aiming_keys_pick_the_depth_and_retrieve() {
+        let mut game = aiming();
+        assert_eq!(game.presentation_view(), Some(("Mid", "Steady")));
+        game.handle_input_key(Some(game.input.lure_depth));
+        game.handle_input_key(Some(game.input.reel));
+        game.handle_input_key(Some(game.input.reel));
+        assert_eq!(game.player.presentation.depth, LureDepth::Deep);
+        assert_eq!(game.player.presentation.retrieve, Retrieve::Slow);
+        assert_eq!(
+            game.ui.logs().last().unwrap(),
+            "Lure runs deep, slow retrieve."
+        );
+
+        // The choice stays with the angler for the next cast
+        game.handle_input_key(Some(game.input.cast));
+        assert!(game.presentation_view().is_none());
+        assert_eq!(game.player.presentation.depth, LureDepth::Deep);
+    }
+
+    #[test]
+    fn the_depth_key_does_nothing_outside_aiming() {
+        let mut game = LurhookGame::default();
+        let pos = game.player.pos;
+        game.handle_input_key(Some(game.input.lure_depth));
+        assert_eq!(game.player.presentation, fishing::Presentation::default());
+        assert_eq!(game.player.pos, pos);
+    }
+}
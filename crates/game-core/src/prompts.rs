@@ -0,0 +1,221 @@
+//! Blocking questions: a prompt over the screen holds the game until the
+//! player picks an option with the arrow keys and Enter, a number key or
+//! a click. The game keeps what was asked so the answer reaches it. The
+//! pause menu is one of them.
+
+use super::*;
+use ui_crate::Prompt;
+
+/// What the open prompt asks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Question {
+    /// The pause menu; Escape resumes.
+    Pause,
+    /// Whether to end the run; Escape keeps fishing.
+    EndRun,
+    /// Which choice of scripted event `i` to take.
+    Event(usize),
+}
+
+impl LurhookGame {
+    /// Opens `prompt` for `question`.
+    pub(crate) fn ask(&mut self, question: Question, prompt: Prompt) {
+        self.question = Some(question);
+        self.ui.show_prompt(prompt);
+    }
+
+    /// Opens the pause menu over the map.
+    pub(crate) fn pause(&mut self) {
+        if matches!(self.ui.layout(), UILayout::Help | UILayout::Options) {
+            self.ui.set_layout(UILayout::Standard);
+        }
+        let options = ["Resume", "Save", "Options", "Abandon Run"]
+            .map(String::from)
+            .to_vec();
+        let prompt = Prompt::new("Paused", "The water waits for you.", options);
+        self.ask(Question::Pause, prompt);
+    }
+
+    /// Asks before ending the run, keeping fishing highlighted. The
+    /// tutorial ends without asking.
+    pub(crate) fn confirm_end_run(&mut self) {
+        if self.in_tutorial() {
+            self.end_run();
+            return;
+        }
+        let options = vec!["Keep fishing".to_string(), "End the run".to_string()];
+        let prompt = Prompt::new(
+            "End run?",
+            &format!("End the run now with a score of {}?", self.score()),
+            options,
+        );
+        self.ask(Question::EndRun, prompt);
+    }
+
+    /// Steers the open prompt with this frame's key or click.
+    pub(crate) fn handle_prompt_input(&mut self, ctx: &BTerm) {
+        if ctx.left_click {
+            let at = common::Point::new(ctx.mouse_pos.0, ctx.mouse_pos.1);
+            if let Some(option) = self.ui.prompt_option_at(at) {
+                self.ui.select_prompt(option);
+                self.answer_prompt();
+            }
+            return;
+        }
+        let Some(key) = ctx.key else {
+            return;
+        };
+        let number = input::key_name(key)
+            .and_then(|name| name.parse::<usize>().ok())
+            .and_then(|n| n.checked_sub(1));
+        if let Some(option) = number {
+            if self.ui.select_prompt(option) {
+                self.answer_prompt();
+            }
+        } else if key == VirtualKeyCode::Return || key == self.input.cast {
+            self.answer_prompt();
+        } else if key == VirtualKeyCode::Escape
+            && matches!(self.question, Some(Question::Pause | Question::EndRun))
+        {
+            self.ui.close_prompt();
+            self.question = None;
+        } else {
+            match self.key_direction(key) {
+                Some(Direction::North) => self.ui.move_prompt(-1),
+                Some(Direction::South) => self.ui.move_prompt(1),
+                _ => {}
+            }
+        }
+    }
+
+    /// Closes the prompt and acts on the highlighted option.
+    pub(crate) fn answer_prompt(&mut self) {
+        let (Some(question), Some(option)) = (self.question.take(), self.ui.close_prompt()) else {
+            return;
+        };
+        match question {
+            Question::Pause => match option {
+                1 => self.quick_save(),
+                2 => self.ui.set_layout(UILayout::Options),
+                3 => self.confirm_end_run(),
+                _ => {}
+            },
+            Question::EndRun if option == 1 => self.end_run(),
+            Question::EndRun => {}
+            Question::Event(i) => self.choose_event_option(i, option),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(game: &mut LurhookGame, key: VirtualKeyCode) {
+        let mut ctx = empty_frame(0.0);
+        ctx.key = Some(key);
+        game.update_frame(&mut ctx, false);
+    }
+
+    fn explorer() -> LurhookGame {
+        let mut game = LurhookGame::default();
+        game.ui.set_layout(UILayout::Standard);
+        game
+    }
+
+    #[test]
+    fn return_asks_before_ending_the_run() {
+        let mut game = explorer();
+        let turn = game.turn;
+        press(&mut game, VirtualKeyCode::Return);
+        assert_eq!(game.question, Some(Question::EndRun));
+        assert_eq!(game.turn, turn, "asking takes no turn");
+        let mut buf = TextBuffer::new(SCREEN_WIDTH, SCREEN_HEIGHT);
+        game.render(&mut buf);
+        assert!(buf.contains(">1: Keep fishing"));
+
+        press(&mut game, VirtualKeyCode::Return);
+        assert_eq!(game.mode, GameMode::Exploring, "keep fishing by default");
+        assert!(game.ui.prompt().is_none());
+
+        press(&mut game, VirtualKeyCode::Return);
+        press(&mut game, VirtualKeyCode::Escape);
+        assert!(game.question.is_none());
+        assert_eq!(game.mode, GameMode::Exploring);
+
+        press(&mut game, VirtualKeyCode::Return);
+        press(&mut game, VirtualKeyCode::Down);
+        assert_eq!(game.ui.prompt().unwrap().selected, 1);
+        press(&mut game, VirtualKeyCode::Return);
+        assert_eq!(game.mode, GameMode::End { score: 0 });
+    }
+
+    #[test]
+    fn escape_opens_the_pause_menu() {
+        let mut game = explorer();
+        let turn = game.turn;
+        press(&mut game, VirtualKeyCode::Escape);
+        assert_eq!(game.question, Some(Question::Pause));
+        let mut buf = TextBuffer::new(SCREEN_WIDTH, SCREEN_HEIGHT);
+        game.render(&mut buf);
+        assert!(buf.contains(" Paused "));
+        assert!(buf.contains("4: Abandon Run"));
+        press(&mut game, VirtualKeyCode::Escape);
+        assert!(game.question.is_none(), "Escape resumes");
+        assert_eq!(game.turn, turn, "pausing takes no turn");
+
+        press(&mut game, VirtualKeyCode::Escape);
+        press(&mut game, VirtualKeyCode::Key3);
+        assert_eq!(game.ui.layout(), UILayout::Options);
+        press(&mut game, VirtualKeyCode::Escape);
+        assert_eq!(game.ui.layout(), UILayout::Standard);
+        assert_eq!(game.question, Some(Question::Pause), "back to the menu");
+
+        press(&mut game, VirtualKeyCode::Key4);
+        assert_eq!(
+            game.question,
+            Some(Question::EndRun),
+            "abandoning asks first"
+        );
+        press(&mut game, VirtualKeyCode::Key2);
+        assert_eq!(game.mode, GameMode::End { score: 0 });
+    }
+
+    #[test]
+    fn the_pause_menu_saves() {
+        let storage: std::rc::Rc<dyn Storage> = std::rc::Rc::new(MemoryStorage::default());
+        let mut game =
+            LurhookGame::new_with_storage(0, Difficulty::Normal, None, Box::new(storage.clone()))
+                .unwrap();
+        game.ui.set_layout(UILayout::Standard);
+        let quit = game.input.quit;
+        press(&mut game, quit);
+        press(&mut game, VirtualKeyCode::Down);
+        press(&mut game, VirtualKeyCode::Return);
+        assert!(game.ui.logs().iter().any(|l| l == "Game saved."));
+        assert!(LurhookGame::load_game_from(Box::new(storage), SAVE_PATH).is_ok());
+    }
+
+    #[test]
+    fn number_keys_and_clicks_pick_options() {
+        let mut game = explorer();
+        press(&mut game, VirtualKeyCode::Return);
+        press(&mut game, VirtualKeyCode::Key3);
+        assert!(game.ui.prompt().is_some(), "no third option");
+        press(&mut game, VirtualKeyCode::Key1);
+        assert!(game.ui.prompt().is_none());
+        assert_eq!(game.mode, GameMode::Exploring);
+
+        press(&mut game, VirtualKeyCode::Return);
+        let mut buf = TextBuffer::new(SCREEN_WIDTH, SCREEN_HEIGHT);
+        game.render(&mut buf);
+        let row = (0..SCREEN_HEIGHT)
+            .find(|&y| buf.row(y).contains("2: End the run"))
+            .unwrap();
+        let mut ctx = empty_frame(0.0);
+        ctx.left_click = true;
+        ctx.mouse_pos = (SCREEN_WIDTH / 2, row);
+        game.update_frame(&mut ctx, false);
+        assert_eq!(game.mode, GameMode::End { score: 0 });
+    }
+}
@@ -0,0 +1,200 @@
+//! Hooks the `quests` crate into the run: reports events and grants rewards.
+
+use super::*;
+use quests::{QuestEvent, Reward as QuestReward};
+
+impl LurhookGame {
+    /// Counts `event` towards the active quests and grants the rewards of
+    /// those it completes.
+    pub(crate) fn record_quest(&mut self, event: QuestEvent) {
+        for quest in self.quests.record(event) {
+            self.ui
+                .add_entry(
+                    LogCategory::Event,
+                    &format!("Quest complete: {}!", quest.title),
+                )
+                .ok();
+            for reward in quest.rewards {
+                self.grant_quest_reward(reward);
+            }
+        }
+    }
+
+    fn grant_quest_reward(&mut self, reward: QuestReward) {
+        match reward {
+            QuestReward::Item(item) => {
+                self.ui
+                    .add_entry(LogCategory::Event, &format!("Received the {}.", item.name))
+                    .ok();
+                self.player.items.push(item);
+            }
+            QuestReward::ScoreMultiplier(factor) => {
+                self.ui
+                    .add_entry(LogCategory::Event, &format!("Final score x{:.1}.", factor))
+                    .ok();
+            }
+            QuestReward::UnlockArea(id) => match find_area(&self.areas, &id) {
+                Ok(area) => self.unlock_area(area),
+                Err(_) => {
+                    self.ui
+                        .add_entry(LogCategory::Event, &format!("Unknown quest area {}.", id))
+                        .ok();
+                }
+            },
+        }
+    }
+
+    /// Moves the run on to `area` with a freshly generated map. Areas the
+    /// run has already passed, and those whose `requires` it has not
+    /// reached yet, are ignored.
+    pub(crate) fn unlock_area(&mut self, area: data::AreaType) {
+        let rank = |id: &str| self.areas.iter().position(|a| a.id == id);
+        let current = rank(&self.area.id);
+        if rank(&area.id) <= current {
+            return;
+        }
+        if let Some(req) = &area.requires {
+            if rank(req) > current {
+                self.ui
+                    .add_entry(
+                        LogCategory::Event,
+                        &format!("{} is out of reach for now.", area.name),
+                    )
+                    .ok();
+                return;
+            }
+        }
+        self.seed += 1;
+        self.map = generate(self.seed, &area).expect("map");
+        self.ocean = mapgen::chunks::Ocean::new(self.seed, &area);
+        self.chunk = common::Point::new(0, 0);
+        self.features = Default::default();
+        self.sight.take();
+        self.update_tide();
+        self.populate(&area);
+        self.hazards.clear();
+        self.runs.clear();
+        let center = common::Point::new(self.map.width as i32 / 2, self.map.height as i32 / 2);
+        let start = mapgen::basins::start_point(&self.map, center);
+        self.player.aboard = self.map.tiles[self.map.idx(start)] == TileKind::DeepWater;
+        self.player.boat.pos = start;
+        self.player.boat.anchored = false;
+        self.leave_trail();
+        self.journal_found(&area.name);
+        self.place_player(start);
+        self.chart_features();
+        self.explore();
+        self.merchant = shop::merchant_spot(&self.map, start);
+        self.ui
+            .add_entry(LogCategory::Event, &format!("Unlocked {}!", area.name))
+            .ok();
+        self.area = area;
+        self.flush_codex();
+        self.autosave_now();
+    }
+
+    /// Rows for the active-quest panel.
+    pub(super) fn quest_views(&self) -> Vec<QuestView<'_>> {
+        self.quests
+            .active()
+            .map(|(quest, progress)| QuestView {
+                title: &quest.title,
+                progress,
+                target: quest.objective.target(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_game;
+    use common::MemoryStorage;
+
+    #[test]
+    fn catch_quests_unlock_the_next_area() {
+        let mut game = memory_game(5);
+        let fish = game.fish_types[0].clone();
+        let catch = QuestEvent::Catch {
+            fish: &fish,
+            area: "Coast",
+        };
+        for _ in 0..3 {
+            game.record_quest(catch);
+        }
+        assert_eq!(game.area.id, "Offshore");
+        assert!(game.quests.is_completed("FIRST_HAUL"));
+        assert_eq!(game.player.boat.pos, game.player.pos);
+        assert!(game
+            .quest_views()
+            .iter()
+            .any(|q| q.title == "Catch 3 more fish"));
+    }
+
+    #[test]
+    fn unlocked_areas_fill_with_the_same_fish_for_a_seed() {
+        let unlocked = || {
+            let mut game = memory_game(5);
+            game.unlock_area(find_area(&game.areas, "Offshore").unwrap());
+            assert_eq!(game.area.id, "Offshore");
+            game.fishes
+                .iter()
+                .map(|f| (f.kind.id.clone(), f.position, f.weight))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(unlocked(), unlocked());
+    }
+
+    #[test]
+    fn areas_are_never_unlocked_backwards() {
+        let storage = Box::new(MemoryStorage::default());
+        let mut game =
+            LurhookGame::new_with_storage(5, Difficulty::Normal, Some("DeepSea"), storage).unwrap();
+        let tiles = game.map.tiles.clone();
+        let offshore = find_area(&game.areas, "Offshore").unwrap();
+        game.unlock_area(offshore);
+        assert_eq!(game.area.id, "DeepSea");
+        assert_eq!(game.map.tiles, tiles);
+    }
+
+    #[test]
+    fn areas_open_only_after_their_requirement() {
+        let mut game = memory_game(5);
+        let deep = find_area(&game.areas, "DeepSea").unwrap();
+        assert_eq!(deep.requires.as_deref(), Some("Offshore"));
+        game.unlock_area(deep.clone());
+        assert_eq!(game.area.id, "Coast");
+        game.unlock_area(find_area(&game.areas, "Offshore").unwrap());
+        game.unlock_area(deep);
+        assert_eq!(game.area.id, "DeepSea");
+    }
+
+    #[test]
+    fn quest_rewards_are_granted() {
+        let mut game = memory_game(5);
+        let items = game.player.items.len();
+        let evasive = game
+            .fish_types
+            .iter()
+            .find(|f| f.fight_style == data::FightStyle::Evasive)
+            .unwrap()
+            .clone();
+        for _ in 0..3 {
+            game.record_quest(QuestEvent::Catch {
+                fish: &evasive,
+                area: "Coast",
+            });
+        }
+        assert_eq!(game.player.items.len(), items + 1);
+        assert_eq!(game.player.items.last().unwrap().id, "DART_LURE");
+
+        game.player.inventory = vec![Catch::average(evasive)];
+        let score = game.score();
+        for _ in 0..100 {
+            game.record_quest(QuestEvent::Turn);
+        }
+        assert!(game.quests.is_completed("ENDURANCE"));
+        assert_eq!(game.score(), (score as f32 * 1.2).round() as i32);
+    }
+}
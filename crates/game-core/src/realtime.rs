@@ -0,0 +1,140 @@
+//! Real-time fishing: while a line is out, turns pass on a clock instead of
+//! on key presses, so the fight keeps moving between keys. Keys pressed in
+//! between still steer it; a reel key counts for the next turn.
+
+use super::*;
+
+/// Time one fishing turn takes in real time.
+const FIGHT_TICK_MS: f32 = 250.0;
+
+impl LurhookGame {
+    /// Whether fishing turns pass on the clock right now. The clock stops
+    /// while another screen such as help or options is open.
+    pub(crate) fn realtime_fishing(&self) -> bool {
+        self.input.realtime_fights
+            && !self.turn_based_fishing
+            && matches!(self.mode, GameMode::Fishing { .. })
+            && self.ui.layout() == UILayout::Fishing
+    }
+
+    /// Runs the fishing clock for a frame of `frame_time_ms`; whether a
+    /// turn is due. A long frame passes one turn, not several.
+    pub(crate) fn fight_tick(&mut self, frame_time_ms: f32) -> bool {
+        if !self.realtime_fishing() {
+            self.fight_clock = 0.0;
+            return false;
+        }
+        self.fight_clock += frame_time_ms;
+        if self.fight_clock < FIGHT_TICK_MS {
+            return false;
+        }
+        self.fight_clock = (self.fight_clock - FIGHT_TICK_MS).min(FIGHT_TICK_MS);
+        true
+    }
+
+    pub(crate) fn toggle_realtime_fights(&mut self) {
+        self.input.realtime_fights = !self.input.realtime_fights;
+        let _ = self.input.save(self.storage.as_ref(), CONFIG_PATH);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_game;
+
+    fn fishing_game() -> LurhookGame {
+        let mut game = memory_game(0);
+        game.mode = GameMode::Fishing {
+            wait: 5,
+            bite: None,
+        };
+        game.ui.set_layout(UILayout::Fishing);
+        game
+    }
+
+    fn frame(game: &mut LurhookGame, key: Option<VirtualKeyCode>, ms: f32) {
+        let mut ctx = empty_frame(ms);
+        ctx.key = key;
+        game.update(&mut ctx);
+    }
+
+    #[test]
+    fn fishing_turns_pass_on_the_clock() {
+        let mut game = fishing_game();
+        let turn = game.turn;
+        frame(&mut game, None, FIGHT_TICK_MS / 2.0);
+        frame(&mut game, Some(VirtualKeyCode::Period), 0.0);
+        assert_eq!(game.turn, turn);
+        frame(&mut game, None, FIGHT_TICK_MS / 2.0);
+        assert_eq!(game.turn, turn + 1);
+        assert_eq!(
+            game.mode,
+            GameMode::Fishing {
+                wait: 4,
+                bite: None
+            }
+        );
+        frame(&mut game, None, FIGHT_TICK_MS * 10.0);
+        assert_eq!(game.turn, turn + 2);
+
+        // The clock stops while the help screen is open
+        game.ui.set_layout(UILayout::Help);
+        frame(&mut game, None, FIGHT_TICK_MS * 2.0);
+        assert_eq!(game.turn, turn + 2);
+    }
+
+    #[test]
+    fn reel_key_counts_for_the_next_turn() {
+        let mut game = fishing_game();
+        let reel = game.input.reel;
+        frame(&mut game, Some(reel), 10.0);
+        frame(&mut game, None, FIGHT_TICK_MS);
+        assert!(game.reeling);
+        frame(&mut game, None, FIGHT_TICK_MS);
+        assert!(!game.reeling);
+    }
+
+    #[test]
+    fn turn_based_config_keeps_one_turn_per_key() {
+        let mut game = memory_game(0);
+        game.ui.set_layout(UILayout::Options);
+        frame(&mut game, Some(VirtualKeyCode::R), 0.0);
+        assert!(!game.input.realtime_fights);
+        let saved = InputConfig::load(game.storage.as_ref(), CONFIG_PATH).unwrap();
+        assert!(!saved.realtime_fights);
+        game.mode = GameMode::Fishing {
+            wait: 5,
+            bite: None,
+        };
+        game.ui.set_layout(UILayout::Fishing);
+
+        let turn = game.turn;
+        frame(&mut game, None, FIGHT_TICK_MS * 2.0);
+        assert_eq!(game.turn, turn);
+        frame(&mut game, Some(VirtualKeyCode::Period), 0.0);
+        assert_eq!(game.turn, turn + 1);
+    }
+
+    #[test]
+    fn replays_record_clock_turns() {
+        let mut game = memory_game(0);
+        game.record_replay();
+        let cast = game.input.cast;
+        frame(&mut game, Some(cast), 0.0);
+        frame(&mut game, Some(cast), 0.0);
+        assert!(game.realtime_fishing());
+        let mut ticks = 0;
+        while game.realtime_fishing() && ticks < 200 {
+            frame(&mut game, None, FIGHT_TICK_MS);
+            ticks += 1;
+        }
+        game.write_replay();
+        let replay = Replay::load(game.storage.as_ref(), REPLAY_PATH).unwrap();
+        assert_eq!(replay.len(), 2 + ticks);
+        let played = LurhookGame::replay_from(game.storage.as_ref(), REPLAY_PATH).unwrap();
+        assert_eq!(played.turn, game.turn);
+        assert_eq!(played.mode, game.mode);
+        assert_eq!(played.player.inventory.len(), game.player.inventory.len());
+    }
+}
@@ -0,0 +1,358 @@
+//! Recorded runs: the seed, difficulty and area, the files the run started
+//! from and every input, enough to play the run back exactly.
+
+use super::*;
+use input::{key_name, parse_key};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Version written to replay files. Version 1 predates real-time fishing
+/// and plays back turn by turn; versions before 3 predate hook setting and
+/// hook every bite at once.
+const REPLAY_VERSION: u32 = 3;
+/// Files that shape a run (bindings, starting gear, codex rewards, the
+/// loadouts achievements unlock); their contents at the start are stored
+/// in the replay.
+const REPLAY_FILES: [&str; 4] = [CONFIG_PATH, PROFILE_PATH, CODEX_PATH, ACHIEVEMENTS_PATH];
+/// Token recorded for keys without a name.
+const UNNAMED_TOKEN: &str = "?";
+/// Token recorded for a real-time fishing turn.
+const TICK_TOKEN: &str = ".";
+/// Key played back for [`UNNAMED_TOKEN`]. Neither unnamed keys nor this one
+/// can be bound (see [`input::is_reserved`]), so they only pass a turn and
+/// any of them replays the same.
+const UNNAMED_KEY: VirtualKeyCode = VirtualKeyCode::F12;
+
+/// One frame of recorded input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ReplayInput {
+    Key(VirtualKeyCode),
+    /// Left click at the console cell `(x, y)`.
+    Click(i32, i32),
+    /// Real-time fishing turn passing on the clock.
+    Tick,
+}
+
+impl ReplayInput {
+    /// The input of `ctx`, if it has any.
+    fn of(ctx: &BTerm) -> Option<Self> {
+        if ctx.left_click {
+            return Some(ReplayInput::Click(ctx.mouse_pos.0, ctx.mouse_pos.1));
+        }
+        ctx.key.map(ReplayInput::Key)
+    }
+
+    fn token(self) -> String {
+        match self {
+            ReplayInput::Key(key) => key_name(key).unwrap_or(UNNAMED_TOKEN).to_string(),
+            ReplayInput::Click(x, y) => format!("@{},{}", x, y),
+            ReplayInput::Tick => TICK_TOKEN.to_string(),
+        }
+    }
+
+    fn parse(token: &str) -> GameResult<Self> {
+        let bad = || GameError::Parse(format!("replay: bad input {}", token));
+        if token == UNNAMED_TOKEN {
+            return Ok(ReplayInput::Key(UNNAMED_KEY));
+        }
+        if token == TICK_TOKEN {
+            return Ok(ReplayInput::Tick);
+        }
+        if let Some(pos) = token.strip_prefix('@') {
+            let (x, y) = pos.split_once(',').ok_or_else(bad)?;
+            let x = x.parse().map_err(|_| bad())?;
+            let y = y.parse().map_err(|_| bad())?;
+            return Ok(ReplayInput::Click(x, y));
+        }
+        parse_key(token).map(ReplayInput::Key).ok_or_else(bad)
+    }
+
+    /// Input frame that plays this input back.
+    fn frame(self) -> BTerm {
+        let mut ctx = empty_frame(0.0);
+        match self {
+            ReplayInput::Key(key) => ctx.key = Some(key),
+            ReplayInput::Click(x, y) => {
+                ctx.mouse_pos = (x, y);
+                ctx.left_click = true;
+            }
+            ReplayInput::Tick => {}
+        }
+        ctx
+    }
+}
+
+/// A run being recorded or loaded for playback.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Replay {
+    pub seed: u64,
+    pub difficulty: Difficulty,
+    /// Id of the area the run started in.
+    pub area: String,
+    /// Contents of [`REPLAY_FILES`] when the run started.
+    files: BTreeMap<String, String>,
+    inputs: Vec<ReplayInput>,
+    /// Format the replay was recorded in.
+    version: u32,
+}
+
+/// On-disk form: inputs become space separated tokens, runs of the same
+/// input shortened to `token*count`.
+#[derive(Serialize, Deserialize)]
+struct ReplayFile {
+    version: u32,
+    seed: u64,
+    difficulty: Difficulty,
+    area: String,
+    files: BTreeMap<String, String>,
+    inputs: String,
+}
+
+impl Replay {
+    /// Starts an empty recording of `game`, keeping the files it started
+    /// from.
+    fn start(game: &LurhookGame) -> Self {
+        let files = REPLAY_FILES
+            .iter()
+            .filter_map(|&key| {
+                let data = game.storage.read_string(key).ok().flatten()?;
+                Some((key.to_string(), data))
+            })
+            .collect();
+        Self {
+            seed: game.seed,
+            difficulty: game.difficulty,
+            area: game.area.id.clone(),
+            files,
+            inputs: Vec::new(),
+            version: REPLAY_VERSION,
+        }
+    }
+
+    /// Number of recorded input frames.
+    pub fn len(&self) -> usize {
+        self.inputs.len()
+    }
+
+    /// Whether no input has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.inputs.is_empty()
+    }
+
+    fn encode_inputs(&self) -> String {
+        let mut tokens = Vec::new();
+        let mut i = 0;
+        while i < self.inputs.len() {
+            let input = self.inputs[i];
+            let run = self.inputs[i..]
+                .iter()
+                .take_while(|&&other| other == input)
+                .count();
+            let token = input.token();
+            tokens.push(if run > 1 {
+                format!("{}*{}", token, run)
+            } else {
+                token
+            });
+            i += run;
+        }
+        tokens.join(" ")
+    }
+
+    fn decode_inputs(data: &str) -> GameResult<Vec<ReplayInput>> {
+        let mut inputs = Vec::new();
+        for token in data.split_whitespace() {
+            let (token, run) = match token.rsplit_once('*') {
+                Some((token, run)) => (
+                    token,
+                    run.parse::<usize>()
+                        .map_err(|_| GameError::Parse(format!("replay: bad count {}", run)))?,
+                ),
+                None => (token, 1),
+            };
+            let input = ReplayInput::parse(token)?;
+            inputs.resize(inputs.len() + run, input);
+        }
+        Ok(inputs)
+    }
+
+    /// Writes the replay as compressed JSON under `key`.
+    pub fn save(&self, storage: &dyn Storage, key: &str) -> GameResult<()> {
+        let file = ReplayFile {
+            version: self.version,
+            seed: self.seed,
+            difficulty: self.difficulty,
+            area: self.area.clone(),
+            files: self.files.clone(),
+            inputs: self.encode_inputs(),
+        };
+        let json = serde_json::to_string(&file).map_err(|e| GameError::Parse(e.to_string()))?;
+        storage.write(key, &save::encode(&json)?)
+    }
+
+    /// Reads the replay stored under `key`.
+    pub fn load(storage: &dyn Storage, key: &str) -> GameResult<Self> {
+        let bytes = storage
+            .read(key)?
+            .ok_or_else(|| GameError::Parse(format!("replay: {} not found", key)))?;
+        let json = save::decode(&bytes)?;
+        let file: ReplayFile =
+            serde_json::from_str(&json).map_err(|e| GameError::Parse(format!("replay: {}", e)))?;
+        if !(1..=REPLAY_VERSION).contains(&file.version) {
+            return Err(GameError::Parse(format!(
+                "replay: unsupported version {}",
+                file.version
+            )));
+        }
+        Ok(Self {
+            seed: file.seed,
+            difficulty: file.difficulty,
+            area: file.area,
+            files: file.files,
+            inputs: Self::decode_inputs(&file.inputs)?,
+            version: file.version,
+        })
+    }
+}
+
+impl LurhookGame {
+    /// Starts recording this run's inputs. Call before the first update;
+    /// the replay is written to [`REPLAY_PATH`] when the run ends.
+    pub fn record_replay(&mut self) {
+        self.replay = Some(Replay::start(self));
+    }
+
+    /// The inputs recorded so far, if recording.
+    pub fn replay_recording(&self) -> Option<&Replay> {
+        self.replay.as_ref()
+    }
+
+    /// Adds the input of `ctx`, then the real-time fishing turn when `tick`
+    /// is set, to the recording, if recording.
+    pub(crate) fn record_input(&mut self, ctx: &BTerm, tick: bool) {
+        if let Some(replay) = &mut self.replay {
+            replay.inputs.extend(ReplayInput::of(ctx));
+            if tick {
+                replay.inputs.push(ReplayInput::Tick);
+            }
+        }
+    }
+
+    /// Writes the recording to [`REPLAY_PATH`], if recording.
+    pub(crate) fn write_replay(&mut self) {
+        if let Some(replay) = &self.replay {
+            if replay.save(self.storage.as_ref(), REPLAY_PATH).is_err() {
+                self.ui.add_log("Could not save the replay.").ok();
+            }
+        }
+    }
+
+    /// Plays back the replay stored under `key` in the default storage.
+    pub fn replay(key: &str) -> GameResult<Self> {
+        Self::replay_from(default_storage().as_ref(), key)
+    }
+
+    /// Plays back the replay stored under `key` and returns the game as it
+    /// was after the last input. The run is played against the files it
+    /// started from, kept in memory, so playback leaves `storage` untouched.
+    pub fn replay_from(storage: &dyn Storage, key: &str) -> GameResult<Self> {
+        let replay = Replay::load(storage, key)?;
+        let files = MemoryStorage::default();
+        for (key, data) in &replay.files {
+            files.write(key, data.as_bytes())?;
+        }
+        let mut game = Self::new_with_storage(
+            replay.seed,
+            replay.difficulty,
+            Some(&replay.area),
+            Box::new(files),
+        )?;
+        game.turn_based_fishing = replay.version < 2;
+        game.instant_hook = replay.version < 3;
+        for &input in &replay.inputs {
+            game.update_frame(&mut input.frame(), input == ReplayInput::Tick);
+        }
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recorded_game() -> LurhookGame {
+        let storage = Box::new(MemoryStorage::default());
+        let mut game = LurhookGame::new_with_storage(4, Difficulty::Hard, None, storage).unwrap();
+        game.record_replay();
+        game
+    }
+
+    #[test]
+    fn inputs_round_trip_compactly() {
+        let mut game = recorded_game();
+        let inputs = [
+            ReplayInput::Key(VirtualKeyCode::H),
+            ReplayInput::Key(VirtualKeyCode::H),
+            ReplayInput::Key(VirtualKeyCode::H),
+            ReplayInput::Click(3, 4),
+            ReplayInput::Key(VirtualKeyCode::F5),
+            ReplayInput::Key(VirtualKeyCode::Key1),
+        ];
+        game.replay.as_mut().unwrap().inputs = inputs.to_vec();
+        let replay = game.replay.as_ref().unwrap();
+        assert_eq!(replay.encode_inputs(), "H*3 @3,4 F5 1");
+        replay.save(game.storage.as_ref(), "run.replay").unwrap();
+        let loaded = Replay::load(game.storage.as_ref(), "run.replay").unwrap();
+        assert_eq!(loaded.seed, 4);
+        assert_eq!(loaded.difficulty, Difficulty::Hard);
+        assert_eq!(loaded.inputs, inputs);
+        // Keys without a name are recorded as `?`
+        assert_eq!(
+            ReplayInput::parse(UNNAMED_TOKEN).unwrap(),
+            ReplayInput::Key(UNNAMED_KEY)
+        );
+    }
+
+    #[test]
+    fn bad_replays_are_rejected() {
+        for inputs in ["H*x", "@1", "NoSuchKey"] {
+            assert!(matches!(
+                Replay::decode_inputs(inputs),
+                Err(GameError::Parse(_))
+            ));
+        }
+        let storage = MemoryStorage::default();
+        assert!(Replay::load(&storage, "missing.replay").is_err());
+    }
+
+    #[test]
+    fn playback_reaches_the_recorded_state() {
+        use VirtualKeyCode::*;
+        let mut game = recorded_game();
+        let keys = [F1, L, L, J, C, Right, Right, C, R, R, R, H, K, Q];
+        for key in keys.into_iter().chain([R; 30]) {
+            game.update(&mut ReplayInput::Key(key).frame());
+        }
+        game.update(&mut ReplayInput::Click(2, 2).frame());
+        // Frames without input are not recorded
+        game.update(&mut empty_frame(0.0));
+        assert_eq!(game.replay_recording().unwrap().len(), keys.len() + 31);
+        game.write_replay();
+
+        let played = LurhookGame::replay_from(game.storage.as_ref(), REPLAY_PATH).unwrap();
+        assert_eq!(played.turn, game.turn);
+        assert_eq!(played.player.pos, game.player.pos);
+        assert_eq!(played.player.hunger, game.player.hunger);
+        assert_eq!(played.player.line, game.player.line);
+        assert_eq!(played.mode, game.mode);
+        let fish = |g: &LurhookGame| -> Vec<(String, Point)> {
+            g.fishes
+                .iter()
+                .map(|f| (f.kind.id.clone(), f.position))
+                .collect()
+        };
+        assert_eq!(fish(&played), fish(&game));
+        assert_eq!(played.ui.logs(), game.ui.logs());
+        assert!(played.replay_recording().is_none());
+    }
+}
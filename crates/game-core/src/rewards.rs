@@ -0,0 +1,252 @@
+//! Codex completion milestones and the unlocks they grant.
+
+use common::{GameError, GameResult, Storage};
+use data::{ItemKind, ItemType};
+use progression::Progress;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Completion percentages (per area) that grant a reward.
+pub const MILESTONES: [u8; 3] = [25, 50, 100];
+
+/// Ids of the areas whose milestones grant rewards; milestones of other
+/// areas only count towards achievements.
+const REWARD_AREAS: [&str; 3] = ["Coast", "Offshore", "DeepSea"];
+
+/// Bite bonus of the lures granted at the first milestone.
+const REWARD_LURE_BONUS: f32 = 0.15;
+
+/// Something unlocked by reaching a codex milestone.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Reward {
+    /// A unique lure added to the starting gear.
+    Lure(ItemType),
+    /// A map palette selectable in Options.
+    Palette(&'static str),
+    /// A title shown on the main menu.
+    Title(&'static str),
+}
+
+impl Reward {
+    /// Short description for the message log.
+    pub fn describe(&self) -> String {
+        match self {
+            Reward::Lure(item) => format!("the {}", item.name),
+            Reward::Palette(name) => format!("the {} palette", name),
+            Reward::Title(title) => format!("the title \"{}\"", title),
+        }
+    }
+}
+
+/// Key of the area with id `area` in profiles and achievement ids, e.g.
+/// `deep_sea` for `DeepSea`.
+fn area_key(area: &str) -> String {
+    let mut key = String::new();
+    for (i, c) in area.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            key.push('_');
+        }
+        key.extend(c.to_lowercase());
+    }
+    key
+}
+
+/// Achievement id reported when `percent` completion is reached in `area`.
+pub fn milestone_achievement(area: &str, percent: u8) -> String {
+    format!("codex_{}_{}", area_key(area), percent)
+}
+
+/// Reward granted for reaching `percent` completion in `area`.
+pub fn milestone_reward(area: &str, percent: u8) -> Option<Reward> {
+    let reward = match (percent, area) {
+        (25, _) => {
+            let (id, name) = match area {
+                "Coast" => ("TIDEPOOL_SPINNER", "Tidepool Spinner"),
+                "Offshore" => ("REEF_DART", "Reef Dart"),
+                "DeepSea" => ("ABYSS_GLOWBAIT", "Abyss Glowbait"),
+                _ => return None,
+            };
+            Reward::Lure(ItemType {
+                id: id.to_string(),
+                name: name.to_string(),
+                kind: ItemKind::Lure,
+                tension_bonus: 0,
+                reel_factor: 1.0,
+                bite_bonus: REWARD_LURE_BONUS,
+                max_cast_range: data::DEFAULT_CAST_RANGE,
+            })
+        }
+        (50, "Coast") => Reward::Palette("Sunset"),
+        (50, "Offshore") => Reward::Palette("Reef"),
+        (50, "DeepSea") => Reward::Palette("Abyss"),
+        (100, "Coast") => Reward::Title("Shore Warden"),
+        (100, "Offshore") => Reward::Title("Reef Ranger"),
+        (100, "DeepSea") => Reward::Title("Abyss Sage"),
+        _ => return None,
+    };
+    Some(reward)
+}
+
+/// Persistent unlock state shared across runs.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    /// Reached milestones as `area:percent` keys.
+    milestones: BTreeSet<String>,
+    /// Angler experience and the skills it unlocked.
+    pub progress: Progress,
+    /// Id of the starting loadout picked on the menu.
+    pub loadout: Option<String>,
+}
+
+impl Profile {
+    /// Loads the profile stored under `key`, or an empty one.
+    pub fn load(storage: &dyn Storage, key: &str) -> GameResult<Self> {
+        match storage.read_string(key)? {
+            Some(data) => {
+                serde_json::from_str(&data).map_err(|e| GameError::Parse(format!("profile: {}", e)))
+            }
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Saves the profile under `key`.
+    pub fn save(&self, storage: &dyn Storage, key: &str) -> GameResult<()> {
+        let out = serde_json::to_string_pretty(self)
+            .map_err(|e| GameError::Parse(format!("profile: {}", e)))?;
+        storage.write(key, out.as_bytes())
+    }
+
+    /// Whether the `percent` milestone of `area` has been reached.
+    pub fn reached(&self, area: &str, percent: u8) -> bool {
+        self.milestones
+            .contains(&format!("{}:{}", area_key(area), percent))
+    }
+
+    /// Marks milestones covered by `completion` (0-100) in `area` and
+    /// returns the rewards that were newly unlocked.
+    pub fn unlock_milestones(&mut self, area: &str, completion: f32) -> Vec<Reward> {
+        let mut rewards = Vec::new();
+        for percent in MILESTONES {
+            if completion + f32::EPSILON >= percent as f32 && !self.reached(area, percent) {
+                self.milestones
+                    .insert(format!("{}:{}", area_key(area), percent));
+                rewards.extend(milestone_reward(area, percent));
+            }
+        }
+        rewards
+    }
+
+    /// Every reward unlocked so far, in area then milestone order.
+    pub fn rewards(&self) -> Vec<Reward> {
+        REWARD_AREAS
+            .iter()
+            .flat_map(|&area| MILESTONES.iter().map(move |&p| (area, p)))
+            .filter(|&(area, p)| self.reached(area, p))
+            .filter_map(|(area, p)| milestone_reward(area, p))
+            .collect()
+    }
+
+    /// Unlocked lures.
+    pub fn lures(&self) -> Vec<ItemType> {
+        self.rewards()
+            .into_iter()
+            .filter_map(|r| match r {
+                Reward::Lure(item) => Some(item),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Unlocked palette names.
+    pub fn palettes(&self) -> Vec<&'static str> {
+        self.rewards()
+            .into_iter()
+            .filter_map(|r| match r {
+                Reward::Palette(name) => Some(name),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Most prestigious unlocked title.
+    pub fn title(&self) -> Option<&'static str> {
+        self.rewards().into_iter().rev().find_map(|r| match r {
+            Reward::Title(title) => Some(title),
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::MemoryStorage;
+
+    #[test]
+    fn milestone_achievements_are_listed() {
+        for area in REWARD_AREAS {
+            for percent in MILESTONES {
+                let id = milestone_achievement(area, percent);
+                assert!(achievements::find(&id).is_some(), "{}", id);
+            }
+        }
+    }
+
+    #[test]
+    fn other_areas_only_count_for_achievements() {
+        let mut profile = Profile::default();
+        assert!(profile.unlock_milestones("KelpForest", 100.0).is_empty());
+        assert!(profile.reached("KelpForest", 50));
+        assert_eq!(
+            milestone_achievement("KelpForest", 25),
+            "codex_kelp_forest_25"
+        );
+    }
+
+    #[test]
+    fn milestones_unlock_once_in_order() {
+        let mut profile = Profile::default();
+        assert!(profile.unlock_milestones("Coast", 10.0).is_empty());
+        let first = profile.unlock_milestones("Coast", 60.0);
+        assert_eq!(first.len(), 2);
+        assert!(matches!(first[0], Reward::Lure(_)));
+        assert_eq!(first[1], Reward::Palette("Sunset"));
+        assert!(profile.unlock_milestones("Coast", 60.0).is_empty());
+        let last = profile.unlock_milestones("Coast", 100.0);
+        assert_eq!(last, vec![Reward::Title("Shore Warden")]);
+    }
+
+    #[test]
+    fn accessors_list_unlocks() {
+        let mut profile = Profile::default();
+        assert_eq!(profile.title(), None);
+        profile.unlock_milestones("Coast", 100.0);
+        profile.unlock_milestones("DeepSea", 100.0);
+        assert_eq!(profile.lures().len(), 2);
+        assert_eq!(profile.palettes(), vec!["Sunset", "Abyss"]);
+        assert_eq!(profile.title(), Some("Abyss Sage"));
+    }
+
+    #[test]
+    fn profile_round_trips_through_storage() {
+        let storage = MemoryStorage::default();
+        let mut profile = Profile::default();
+        profile.unlock_milestones("Offshore", 50.0);
+        profile.save(&storage, "profile.json").unwrap();
+        let loaded = Profile::load(&storage, "profile.json").unwrap();
+        assert_eq!(loaded, profile);
+        assert_eq!(
+            Profile::load(&storage, "missing.json").unwrap(),
+            Profile::default()
+        );
+    }
+
+    #[test]
+    fn describe_mentions_reward() {
+        assert!(Reward::Palette("Reef").describe().contains("Reef"));
+        assert!(Reward::Title("Abyss Sage")
+            .describe()
+            .contains("Abyss Sage"));
+    }
+}
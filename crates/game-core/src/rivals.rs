@@ -0,0 +1,298 @@
+//! Competition: rival anglers share the map with the player. Each turn
+//! they walk the shore and shallows towards fish, cast at any in reach and
+//! may land them, taking them out of the water and the area's stock just
+//! like the player's catches. Their scores race the player's on the
+//! status panel.
+
+use super::*;
+
+/// Names of the rivals in a competition.
+const RIVAL_NAMES: [&str; 3] = ["Finn", "Marlowe", "Old Pete"];
+/// Farthest a rival casts, in tiles.
+const RIVAL_CAST_RANGE: i32 = 4;
+/// Farthest a rival spots fish to walk towards, in tiles.
+const RIVAL_SIGHT: i32 = 10;
+/// Turns a rival's line is out before the fish bites or not.
+const RIVAL_CAST_TURNS: u32 = 3;
+/// Percent chance that a cast lands the nearest fish in reach.
+const RIVAL_CATCH_CHANCE: i32 = 40;
+/// Tiles from the player where the rivals set out.
+const RIVAL_SPREAD: i32 = 6;
+
+/// An NPC angler competing for the same fish.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Rival {
+    pub name: String,
+    pub pos: common::Point,
+    /// Turns left on the cast out, 0 while walking.
+    pub casting: u32,
+    /// Score of everything landed so far.
+    pub score: i32,
+}
+
+impl LurhookGame {
+    /// Starts a competition: a Normal run against rival anglers.
+    pub fn competition(seed: u64) -> GameResult<Self> {
+        let mut game = Self::new_with_difficulty(seed, Difficulty::Normal)?;
+        game.start_competition();
+        Ok(game)
+    }
+
+    /// Sets the rivals out on the shore around the player.
+    pub(crate) fn start_competition(&mut self) {
+        let offsets = [(RIVAL_SPREAD, 0), (-RIVAL_SPREAD, 0), (0, RIVAL_SPREAD)];
+        for (name, (dx, dy)) in RIVAL_NAMES.into_iter().zip(offsets) {
+            let near = self.player.pos + common::Point::new(dx, dy);
+            if let Some(pos) = self.rival_spot(near) {
+                self.rivals.push(Rival {
+                    name: name.to_string(),
+                    pos,
+                    casting: 0,
+                    score: 0,
+                });
+            }
+        }
+        self.ui
+            .add_entry(
+                LogCategory::Event,
+                "The competition is on! Outscore the other anglers.",
+            )
+            .ok();
+    }
+
+    /// Whether this run is a competition.
+    pub(crate) fn in_competition(&self) -> bool {
+        !self.rivals.is_empty()
+    }
+
+    /// Whether a rival can stand on `pos`: on the map, out of deep water
+    /// and not where another angler or the merchant is.
+    fn rival_can_stand(&self, pos: common::Point) -> bool {
+        self.map.bounds().contains(pos)
+            && self.map.tiles[self.map.idx(pos)] != TileKind::DeepWater
+            && pos != self.player.pos
+            && self.merchant != Some(pos)
+            && self.rivals.iter().all(|r| r.pos != pos)
+    }
+
+    /// Nearest tile to `near` a rival can stand on.
+    fn rival_spot(&self, near: common::Point) -> Option<common::Point> {
+        (0..=RIVAL_SIGHT).find_map(|radius| {
+            common::Rect::new(
+                near.x - radius,
+                near.y - radius,
+                radius * 2 + 1,
+                radius * 2 + 1,
+            )
+            .points()
+            .find(|&p| p.chebyshev(near) == radius && self.rival_can_stand(p))
+        })
+    }
+
+    /// Index of the nearest fish within `radius` of `pos`, leaving the one
+    /// on the player's line alone.
+    fn rival_target(&self, pos: common::Point, radius: i32) -> Option<usize> {
+        ecology::fish_near(&self.fishes, pos, radius)
+            .into_iter()
+            .map(|(i, _)| i)
+            .find(|&i| Some(i) != self.hooked)
+    }
+
+    /// Gives every rival their turn.
+    pub(crate) fn update_rivals(&mut self) {
+        for i in 0..self.rivals.len() {
+            self.rival_turn(i);
+        }
+    }
+
+    /// One rival reels in or casts at a fish in reach, or walks towards
+    /// the nearest fish in sight, or wanders.
+    fn rival_turn(&mut self, i: usize) {
+        let pos = self.rivals[i].pos;
+        if !self.map.bounds().contains(pos)
+            || self.map.tiles[self.map.idx(pos)] == TileKind::DeepWater
+        {
+            // The map changed under them: they catch up with the player
+            if let Some(spot) = self.rival_spot(self.player.pos) {
+                self.rivals[i].pos = spot;
+            }
+            return;
+        }
+        let in_reach = self.rival_target(pos, RIVAL_CAST_RANGE);
+        if self.rivals[i].casting > 0 {
+            self.rivals[i].casting -= 1;
+            if self.rivals[i].casting == 0 {
+                if let Some(idx) = in_reach {
+                    if self.rng.range(0, 100) < RIVAL_CATCH_CHANCE {
+                        self.rival_catch(i, idx);
+                    }
+                }
+            }
+            return;
+        }
+        if in_reach.is_some() {
+            self.rivals[i].casting = RIVAL_CAST_TURNS;
+            return;
+        }
+        let step = match self.rival_target(pos, RIVAL_SIGHT) {
+            Some(idx) => (self.fishes[idx].position - pos).signum(),
+            None => common::Point::new(self.rng.range(-1, 2), self.rng.range(-1, 2)),
+        };
+        if self.rival_can_stand(pos + step) {
+            self.rivals[i].pos = pos + step;
+        }
+    }
+
+    /// Rival `i` lands the fish at `idx`.
+    fn rival_catch(&mut self, i: usize, idx: usize) {
+        let fish = self.take_fish(idx);
+        self.record_stock(&fish.kind);
+        let catch = Catch::new(fish.kind, fish.weight);
+        let rival = &mut self.rivals[i];
+        rival.score += catch.score();
+        let msg = format!(
+            "{} lands a {} ({:.1}kg).",
+            rival.name, catch.kind.name, catch.weight
+        );
+        self.ui.add_entry(LogCategory::Event, &msg).ok();
+    }
+
+    /// Everyone's score, best first, for the status panel; the player
+    /// comes before rivals on the same score.
+    pub(crate) fn standings(&self) -> Option<StandingsView> {
+        if !self.in_competition() {
+            return None;
+        }
+        let score = self.score();
+        let mut rows: Vec<(String, i32)> = self
+            .rivals
+            .iter()
+            .map(|r| (r.name.clone(), r.score))
+            .collect();
+        rows.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+        let player = rows.iter().filter(|&&(_, s)| s > score).count();
+        rows.insert(player, ("You".to_string(), score));
+        Some(StandingsView { rows, player })
+    }
+
+    /// Logs where the player finished in the competition.
+    pub(crate) fn announce_standings(&mut self) {
+        let Some(standings) = self.standings() else {
+            return;
+        };
+        let msg = format!(
+            "You finished {} of {} anglers.",
+            ordinal(standings.player + 1),
+            standings.rows.len()
+        );
+        self.ui.add_entry(LogCategory::Event, &msg).ok();
+    }
+
+    pub(super) fn draw_rivals(&self, ctx: &mut dyn Renderer) {
+        let view = self.camera();
+        for rival in &self.rivals {
+            if view.contains(rival.pos) && self.is_visible(rival.pos) {
+                let screen = view.to_local(rival.pos);
+                ctx.set(
+                    screen.x,
+                    screen.y,
+                    RGB::named(MAGENTA),
+                    RGB::named(BLACK),
+                    '@',
+                );
+            }
+        }
+    }
+}
+
+/// `1st`, `2nd`, `3rd`, `4th`...
+fn ordinal(n: usize) -> String {
+    let suffix = match n {
+        1 => "st",
+        2 => "nd",
+        3 => "rd",
+        _ => "th",
+    };
+    format!("{}{}", n, suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn competition() -> LurhookGame {
+        let mut game = LurhookGame::default();
+        game.start_competition();
+        game
+    }
+
+    #[test]
+    fn rivals_set_out_on_the_shore() {
+        let game = competition();
+        assert_eq!(game.rivals.len(), RIVAL_NAMES.len());
+        for rival in &game.rivals {
+            assert_ne!(game.map.tiles[game.map.idx(rival.pos)], TileKind::DeepWater);
+            assert_ne!(rival.pos, game.player.pos);
+        }
+        assert!(!LurhookGame::default().in_competition());
+    }
+
+    #[test]
+    fn rivals_cast_at_fish_in_reach_and_take_them() {
+        let mut game = competition();
+        game.rivals.truncate(1);
+        let pos = game.rivals[0].pos;
+        let kind = game.fish_types[0].clone();
+        game.fishes = vec![Fish::new(kind.clone(), pos + common::Point::new(1, 0))];
+        game.update_rivals();
+        assert_eq!(game.rivals[0].casting, RIVAL_CAST_TURNS);
+        for _ in 0..100 {
+            if game.fishes.is_empty() {
+                break;
+            }
+            if game.rivals[0].casting == 0 {
+                game.rivals[0].casting = 1;
+            }
+            game.update_rivals();
+        }
+        assert!(game.fishes.is_empty(), "the rival landed the fish");
+        assert!(game.rivals[0].score > 0);
+        assert!(game.ui.logs().iter().any(|l| l.starts_with("Finn lands a")));
+    }
+
+    #[test]
+    fn rivals_leave_the_hooked_fish_alone() {
+        let mut game = competition();
+        let pos = game.rivals[0].pos;
+        let kind = game.fish_types[0].clone();
+        game.fishes = vec![Fish::new(kind, pos)];
+        game.hooked = Some(0);
+        assert_eq!(game.rival_target(pos, RIVAL_CAST_RANGE), None);
+    }
+
+    #[test]
+    fn standings_show_on_the_status_panel() {
+        let mut game = competition();
+        game.rivals[1].score = 10_000;
+        let kind = game.fish_types[0].clone();
+        game.player.inventory.push(Catch::average(kind));
+        let standings = game.standings().unwrap();
+        assert_eq!(standings.rows[0], ("Marlowe".to_string(), 10_000));
+        assert_eq!(standings.rows.len(), RIVAL_NAMES.len() + 1);
+        assert_eq!(standings.rows[standings.player].0, "You");
+        assert_eq!(standings.player, 1);
+        game.ui.set_layout(UILayout::Standard);
+        let mut buf = TextBuffer::new(SCREEN_WIDTH, SCREEN_HEIGHT);
+        game.render(&mut buf);
+        assert!(buf.contains("Rank 2/4"));
+        let mut tall = TextBuffer::new(SCREEN_WIDTH, SCREEN_HEIGHT * 2);
+        game.render(&mut tall);
+        assert!(tall.contains("Marlowe"));
+        game.end_run();
+        assert!(game
+            .ui
+            .logs()
+            .iter()
+            .any(|l| l == "You finished 2nd of 4 anglers."));
+    }
+}
@@ -0,0 +1,166 @@
+//! Compressed, checksummed save files with a single rolling backup.
+//!
+//! Layout: `LRHK` magic, format version byte, CRC32 of the uncompressed
+//! payload (little endian), then the gzip-compressed payload.
+
+use common::{GameError, GameResult, Storage};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use std::io::{Read, Write};
+
+const MAGIC: &[u8; 4] = b"LRHK";
+const VERSION: u8 = 1;
+const HEADER_LEN: usize = MAGIC.len() + 1 + 4;
+/// Prefix of saves written before compression was introduced.
+const LEGACY_PREFIX: &str = "(player:";
+
+/// Key of the backup kept alongside `key`.
+pub fn backup_key(key: &str) -> String {
+    format!("{}.bak", key)
+}
+
+/// Compresses `payload` and prepends the header with its checksum.
+pub fn encode(payload: &str) -> GameResult<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(payload.as_bytes())?;
+    let body = encoder.finish()?;
+    let mut out = Vec::with_capacity(HEADER_LEN + body.len());
+    out.extend_from_slice(MAGIC);
+    out.push(VERSION);
+    out.extend_from_slice(&crc32fast::hash(payload.as_bytes()).to_le_bytes());
+    out.extend_from_slice(&body);
+    Ok(out)
+}
+
+/// Validates and decompresses bytes produced by [`encode`].
+///
+/// Plain-text saves from older versions are accepted as-is.
+pub fn decode(bytes: &[u8]) -> GameResult<String> {
+    if bytes.starts_with(LEGACY_PREFIX.as_bytes()) {
+        return String::from_utf8(bytes.to_vec())
+            .map_err(|_| GameError::Corrupted("legacy save is not UTF-8".into()));
+    }
+    if bytes.len() < HEADER_LEN || &bytes[..MAGIC.len()] != MAGIC {
+        return Err(GameError::Corrupted("missing save header".into()));
+    }
+    let version = bytes[MAGIC.len()];
+    if version != VERSION {
+        return Err(GameError::Corrupted(format!(
+            "unsupported save version {}",
+            version
+        )));
+    }
+    let mut crc = [0u8; 4];
+    crc.copy_from_slice(&bytes[MAGIC.len() + 1..HEADER_LEN]);
+    let mut payload = String::new();
+    GzDecoder::new(&bytes[HEADER_LEN..])
+        .read_to_string(&mut payload)
+        .map_err(|e| GameError::Corrupted(format!("decompression failed: {}", e)))?;
+    if crc32fast::hash(payload.as_bytes()) != u32::from_le_bytes(crc) {
+        return Err(GameError::Corrupted("checksum mismatch".into()));
+    }
+    Ok(payload)
+}
+
+/// Writes `payload` under `key`, first moving a valid previous save to the backup.
+pub fn write_save(storage: &dyn Storage, key: &str, payload: &str) -> GameResult<()> {
+    let bytes = encode(payload)?;
+    if let Some(old) = storage.read(key)? {
+        if decode(&old).is_ok() {
+            storage.write(&backup_key(key), &old)?;
+        }
+    }
+    storage.write(key, &bytes)
+}
+
+/// Reads the save under `key`, falling back to its backup when it is corrupted.
+///
+/// Returns the original corruption error if the backup is unusable too.
+pub fn read_save(storage: &dyn Storage, key: &str) -> GameResult<String> {
+    match read_decoded(storage, key) {
+        Err(err @ GameError::Corrupted(_)) => {
+            read_decoded(storage, &backup_key(key)).map_err(|_| err)
+        }
+        other => other,
+    }
+}
+
+fn read_decoded(storage: &dyn Storage, key: &str) -> GameResult<String> {
+    let bytes = storage.read(key)?.ok_or_else(|| {
+        GameError::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("{} not found", key),
+        ))
+    })?;
+    decode(&bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::MemoryStorage;
+
+    #[test]
+    fn encode_decode_round_trip() {
+        let bytes = encode("(player:(hp:3))").unwrap();
+        assert!(bytes.starts_with(MAGIC));
+        assert_eq!(decode(&bytes).unwrap(), "(player:(hp:3))");
+    }
+
+    #[test]
+    fn checksum_mismatch_is_detected() {
+        let mut bytes = encode("(player:(hp:3))").unwrap();
+        bytes[MAGIC.len() + 1] ^= 0xFF;
+        assert!(matches!(decode(&bytes), Err(GameError::Corrupted(_))));
+    }
+
+    #[test]
+    fn garbage_is_rejected() {
+        assert!(matches!(decode(b"garbage"), Err(GameError::Corrupted(_))));
+        let mut bytes = encode("(player:(hp:3))").unwrap();
+        bytes.truncate(HEADER_LEN + 2);
+        assert!(matches!(decode(&bytes), Err(GameError::Corrupted(_))));
+    }
+
+    #[test]
+    fn legacy_plain_text_is_accepted() {
+        assert_eq!(decode(b"(player:(hp:1))").unwrap(), "(player:(hp:1))");
+    }
+
+    #[test]
+    fn second_write_keeps_backup() {
+        let storage = MemoryStorage::default();
+        write_save(&storage, "save.ron", "(player:first)").unwrap();
+        assert_eq!(storage.read(&backup_key("save.ron")).unwrap(), None);
+        write_save(&storage, "save.ron", "(player:second)").unwrap();
+        let backup = storage.read(&backup_key("save.ron")).unwrap().unwrap();
+        assert_eq!(decode(&backup).unwrap(), "(player:first)");
+    }
+
+    #[test]
+    fn corrupted_save_falls_back_to_backup() {
+        let storage = MemoryStorage::default();
+        write_save(&storage, "save.ron", "(player:first)").unwrap();
+        write_save(&storage, "save.ron", "(player:second)").unwrap();
+        storage.write("save.ron", b"LRHK\x01junk").unwrap();
+        assert_eq!(read_save(&storage, "save.ron").unwrap(), "(player:first)");
+    }
+
+    #[test]
+    fn corrupted_without_backup_reports_error() {
+        let storage = MemoryStorage::default();
+        storage.write("save.ron", b"not a save").unwrap();
+        assert!(matches!(
+            read_save(&storage, "save.ron"),
+            Err(GameError::Corrupted(_))
+        ));
+    }
+
+    #[test]
+    fn missing_save_is_io_error() {
+        let storage = MemoryStorage::default();
+        assert!(matches!(
+            read_save(&storage, "save.ron"),
+            Err(GameError::Io(_))
+        ));
+    }
+}
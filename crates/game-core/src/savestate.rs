@@ -0,0 +1,978 @@
+//! Full run state written to save files as JSON.
+//!
+//! The map is not stored: it is regenerated from the seed and area, then
+//! the fish, hazards, chum, player and clock are restored on top of it. Fish and
+//! gear are stored by id and looked up in the loaded assets. Only the map
+//! memory is kept, as run lengths of explored and unexplored tiles.
+
+use super::*;
+use serde::{Deserialize, Serialize};
+
+/// Version of the JSON layout below.
+const STATE_VERSION: u32 = 2;
+
+/// The player's stats, catches and gear.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct PlayerState {
+    pub pos: (i32, i32),
+    pub hp: i32,
+    pub hunger: i32,
+    pub line: i32,
+    pub canned_food: i32,
+    /// Fish caught this run as `(id, weight)`.
+    pub inventory: Vec<(String, f32)>,
+    /// Ids of the carried, unequipped items.
+    pub items: Vec<String>,
+    pub rod: Option<String>,
+    pub reel: Option<String>,
+    pub lure: Option<String>,
+    /// Missing from saves made before fishfinders existed.
+    #[serde(default)]
+    pub sonar: Option<String>,
+    /// Missing from saves made before boats existed.
+    #[serde(default)]
+    pub boat: Option<BoatState>,
+    #[serde(default)]
+    pub aboard: bool,
+    #[serde(default)]
+    pub coins: i32,
+    /// Rod and reel durability; missing from saves made before gear wore.
+    #[serde(default)]
+    pub gear: Option<(i32, i32)>,
+    /// Missing from saves made before thirst.
+    #[serde(default)]
+    pub thirst: Option<i32>,
+    /// Turn stamp and whether it is cooked for each fish in `inventory`;
+    /// missing from saves made before fish spoiled.
+    #[serde(default)]
+    pub freshness: Vec<(u32, bool)>,
+    /// Missing from saves made before campfires.
+    #[serde(default)]
+    pub driftwood: i32,
+    #[serde(default)]
+    pub buff: Option<data::Buff>,
+    /// Missing from saves made before status effects.
+    #[serde(default)]
+    pub effects: Vec<StatusEffect>,
+}
+
+/// A longline left in the water and the fish on its hooks.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct LonglineState {
+    pub anchor: (i32, i32),
+    pub tiles: Vec<(i32, i32)>,
+    /// Fish on the hooks as `(id, weight)`.
+    pub catches: Vec<(String, f32)>,
+    pub left: bool,
+    /// Id of the longline item.
+    pub item: String,
+}
+
+/// A trap left in the water and the fish inside.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct TrapState {
+    pub pos: (i32, i32),
+    pub baited: bool,
+    /// Fish inside as `(id, weight)`.
+    pub catches: Vec<(String, f32)>,
+    pub left: bool,
+    /// Id of the trap item.
+    pub item: String,
+}
+
+/// The fish stock of one area.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct PopulationState {
+    /// Id of the area, as in `areas.json`.
+    pub area: String,
+    pub capacity: usize,
+    /// Recent catches as `(fish id, count)`.
+    pub catches: Vec<(String, u32)>,
+    /// Overfished species as `(fish id, turns left)`.
+    pub depleted: Vec<(String, u32)>,
+}
+
+/// A migrating school passing through the area.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct RunState {
+    /// Id of the migrating species.
+    pub fish: String,
+    pub route: Vec<(i32, i32)>,
+    pub progress: usize,
+}
+
+/// Where the boat lies and how worn it is.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct BoatState {
+    pub pos: (i32, i32),
+    pub durability: i32,
+    pub anchored: bool,
+}
+
+/// Everything needed to resume a run.
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct SaveState {
+    pub version: u32,
+    pub seed: u64,
+    /// Id of the area, as in `areas.json`.
+    pub area: String,
+    pub difficulty: Difficulty,
+    /// Rules the run plays by; missing from saves made before custom
+    /// difficulties, whose runs play by their preset.
+    #[serde(default)]
+    pub rules: Option<DifficultyProfile>,
+    /// Whether the run's rescue from death was used.
+    #[serde(default)]
+    pub rescued: bool,
+    pub turn: u32,
+    /// Random number generator mid-run, so a loaded run draws what the
+    /// saved one would have; missing from saves made before it was kept.
+    #[serde(default)]
+    pub rng: Option<RandomNumberGenerator>,
+    pub weather: WeatherState,
+    /// Fish as `(id, x, y, hunger, weight)`.
+    pub fish: Vec<(String, i32, i32, i32, f32)>,
+    /// Hazards as `(x, y, turns left)`.
+    pub hazards: Vec<(i32, i32, u8)>,
+    /// Kind of each of `hazards`; missing from saves made when every
+    /// hazard was a jellyfish.
+    #[serde(default)]
+    pub hazard_kinds: Vec<HazardKind>,
+    /// Chum as `(x, y, turns left)`; missing from saves made before chum.
+    #[serde(default)]
+    pub chum: Vec<(i32, i32, u8)>,
+    /// Missing from saves made before longlines.
+    #[serde(default)]
+    pub longlines: Vec<LonglineState>,
+    #[serde(default)]
+    pub net_cooldown: u8,
+    /// Missing from saves made before traps.
+    #[serde(default)]
+    pub traps: Vec<TrapState>,
+    /// Campfires as `(x, y, turns left)`; missing from saves made before
+    /// campfires.
+    #[serde(default)]
+    pub campfires: Vec<(i32, i32, u8)>,
+    pub player: PlayerState,
+    /// Merchant stock left as `(item id, count)`; missing means untouched.
+    #[serde(default)]
+    pub shop: Option<Vec<(String, u32)>>,
+    /// Missing from saves made before quests existed.
+    #[serde(default)]
+    pub quests: quests::QuestProgress,
+    /// Explored tiles in row-major order as alternating run lengths,
+    /// unexplored first; missing from saves made before map memory.
+    #[serde(default)]
+    pub explored: Vec<u32>,
+    /// Fish stock of every area visited; missing from saves made before
+    /// fish bred.
+    #[serde(default)]
+    pub populations: Vec<PopulationState>,
+    /// Chunk of an endless area the map shows; missing from saves made
+    /// before endless areas.
+    #[serde(default)]
+    pub chunk: (i32, i32),
+    /// Missing from saves made before migrations.
+    #[serde(default)]
+    pub runs: Vec<RunState>,
+    /// Indices in `fish` of the migrating fish; missing from saves made
+    /// before migrations.
+    #[serde(default)]
+    pub migrants: Vec<usize>,
+    /// Rivals of a competition as `(name, x, y, casting, score)`; missing
+    /// from saves made before competitions.
+    #[serde(default)]
+    pub rivals: Vec<(String, i32, i32, u32, i32)>,
+    /// Trail and diary of the run; missing from saves made before the
+    /// travel journal.
+    #[serde(default)]
+    pub journal: journal::Journal,
+    pub codex: Codex,
+    pub metrics: RunMetrics,
+}
+
+fn item_id(item: &Option<data::ItemType>) -> Option<String> {
+    item.as_ref().map(|i| i.id.clone())
+}
+
+/// Run lengths of equal flags, starting with a run of `false`.
+fn encode_runs(flags: &[bool]) -> Vec<u32> {
+    let mut runs = Vec::new();
+    let (mut current, mut len) = (false, 0);
+    for &flag in flags {
+        if flag != current {
+            runs.push(len);
+            (current, len) = (flag, 0);
+        }
+        len += 1;
+    }
+    runs.push(len);
+    runs
+}
+
+/// Flags back from the run lengths written by [`encode_runs`].
+fn decode_runs(runs: &[u32]) -> Vec<bool> {
+    runs.iter()
+        .enumerate()
+        .flat_map(|(i, &len)| vec![i % 2 == 1; len as usize])
+        .collect()
+}
+
+impl SaveState {
+    /// Captures the state of `game`.
+    pub(crate) fn of(game: &LurhookGame) -> Self {
+        let player = &game.player;
+        Self {
+            version: STATE_VERSION,
+            seed: game.seed,
+            area: game.area.id.clone(),
+            difficulty: game.difficulty,
+            rules: Some(game.rules.clone()),
+            rescued: game.rescued,
+            turn: game.turn,
+            rng: Some(game.rng.clone()),
+            weather: game.weather,
+            fish: game
+                .fishes
+                .iter()
+                .map(|f| {
+                    (
+                        f.kind.id.clone(),
+                        f.position.x,
+                        f.position.y,
+                        f.hunger,
+                        f.weight,
+                    )
+                })
+                .collect(),
+            hazards: game
+                .hazards
+                .iter()
+                .map(|h| (h.pos.x, h.pos.y, h.turns))
+                .collect(),
+            hazard_kinds: game.hazards.iter().map(|h| h.kind).collect(),
+            chum: game
+                .chum
+                .iter()
+                .map(|c| (c.pos.x, c.pos.y, c.turns))
+                .collect(),
+            longlines: game
+                .longlines
+                .iter()
+                .map(|l| LonglineState {
+                    anchor: (l.anchor.x, l.anchor.y),
+                    tiles: l.tiles.iter().map(|p| (p.x, p.y)).collect(),
+                    catches: l
+                        .catches
+                        .iter()
+                        .map(|c| (c.kind.id.clone(), c.weight))
+                        .collect(),
+                    left: l.left,
+                    item: l.item.id.clone(),
+                })
+                .collect(),
+            net_cooldown: game.net_cooldown,
+            traps: game
+                .traps
+                .iter()
+                .map(|t| TrapState {
+                    pos: (t.pos.x, t.pos.y),
+                    baited: t.baited,
+                    catches: t
+                        .catches
+                        .iter()
+                        .map(|c| (c.kind.id.clone(), c.weight))
+                        .collect(),
+                    left: t.left,
+                    item: t.item.id.clone(),
+                })
+                .collect(),
+            campfires: game
+                .campfires
+                .iter()
+                .map(|f| (f.pos.x, f.pos.y, f.turns))
+                .collect(),
+            player: PlayerState {
+                pos: (player.pos.x, player.pos.y),
+                hp: player.hp,
+                hunger: player.hunger,
+                line: player.line,
+                canned_food: player.canned_food,
+                inventory: player
+                    .inventory
+                    .iter()
+                    .map(|c| (c.kind.id.clone(), c.weight))
+                    .collect(),
+                items: player.items.iter().map(|i| i.id.clone()).collect(),
+                rod: item_id(&player.rod),
+                reel: item_id(&player.reel),
+                lure: item_id(&player.lure),
+                sonar: item_id(&player.sonar),
+                boat: Some(BoatState {
+                    pos: (player.boat.pos.x, player.boat.pos.y),
+                    durability: player.boat.durability,
+                    anchored: player.boat.anchored,
+                }),
+                aboard: player.aboard,
+                coins: player.coins,
+                gear: Some((player.rod_durability, player.reel_durability)),
+                thirst: Some(player.thirst),
+                freshness: player
+                    .inventory
+                    .iter()
+                    .map(|c| (c.stamp, c.cooked))
+                    .collect(),
+                driftwood: player.driftwood,
+                buff: player.buff,
+                effects: player.effects.clone(),
+            },
+            shop: Some(
+                game.shop
+                    .iter()
+                    .map(|e| (e.item.id.clone(), e.stock))
+                    .collect(),
+            ),
+            quests: game.quests.progress().clone(),
+            explored: encode_runs(&game.map.explored),
+            populations: game
+                .populations
+                .iter()
+                .map(|(area, stock)| PopulationState {
+                    area: area.clone(),
+                    capacity: stock.capacity,
+                    catches: stock.catches.clone(),
+                    depleted: stock.depleted.clone(),
+                })
+                .collect(),
+            chunk: (game.chunk.x, game.chunk.y),
+            runs: game
+                .runs
+                .iter()
+                .map(|r| RunState {
+                    fish: r.fish.clone(),
+                    route: r.route.iter().map(|p| (p.x, p.y)).collect(),
+                    progress: r.progress,
+                })
+                .collect(),
+            migrants: (0..game.fishes.len())
+                .filter(|&i| game.fishes[i].migrating)
+                .collect(),
+            rivals: game
+                .rivals
+                .iter()
+                .map(|r| (r.name.clone(), r.pos.x, r.pos.y, r.casting, r.score))
+                .collect(),
+            codex: game.codex.clone(),
+            metrics: game.metrics.clone(),
+            journal: game.journal.clone(),
+        }
+    }
+
+    pub(crate) fn to_json(&self) -> GameResult<String> {
+        serde_json::to_string(self).map_err(|e| GameError::Parse(format!("save: {}", e)))
+    }
+
+    pub(crate) fn from_json(data: &str) -> GameResult<Self> {
+        let state: Self =
+            serde_json::from_str(data).map_err(|e| GameError::Parse(format!("save: {}", e)))?;
+        if state.version != STATE_VERSION {
+            return Err(GameError::Parse(format!(
+                "unsupported save state version {}",
+                state.version
+            )));
+        }
+        Ok(state)
+    }
+
+    /// Builds the saved run on a fresh game using `storage`.
+    pub(crate) fn restore(self, storage: Box<dyn Storage>) -> GameResult<LurhookGame> {
+        let mut game =
+            LurhookGame::new_with_storage(self.seed, self.difficulty, Some(&self.area), storage)?;
+        if let Some(rules) = self.rules {
+            game.rules = rules;
+        }
+        game.rescued = self.rescued;
+        let fish_kind = |id: &str| {
+            game.fish_types
+                .iter()
+                .find(|f| f.id == id)
+                .cloned()
+                .ok_or_else(|| GameError::Parse(format!("save: unknown fish {}", id)))
+        };
+        let mut fishes = self
+            .fish
+            .iter()
+            .map(|(id, x, y, hunger, weight)| {
+                // Spooked fish calm down over a save
+                Ok(Fish {
+                    hunger: *hunger,
+                    weight: *weight,
+                    ..Fish::new(fish_kind(id)?, Point::new(*x, *y))
+                })
+            })
+            .collect::<GameResult<Vec<_>>>()?;
+        for &i in &self.migrants {
+            let fish = fishes
+                .get_mut(i)
+                .ok_or_else(|| GameError::Parse(format!("save: no migrating fish {}", i)))?;
+            fish.migrating = true;
+        }
+        let inventory = self
+            .player
+            .inventory
+            .iter()
+            .enumerate()
+            .map(|(i, (id, weight))| {
+                // Older saves count their fish as freshly caught
+                let (stamp, cooked) = self
+                    .player
+                    .freshness
+                    .get(i)
+                    .copied()
+                    .unwrap_or((self.turn, false));
+                Ok(Catch {
+                    stamp,
+                    cooked,
+                    ..Catch::new(fish_kind(id)?, *weight)
+                })
+            })
+            .collect::<GameResult<Vec<_>>>()?;
+
+        // A fresh game knows every item it could have: the catalog, unlocked
+        // lures, the merchant's full stock and the quest rewards
+        let player = &mut game.player;
+        let mut catalog: Vec<data::ItemType> = player.items.drain(..).collect();
+        catalog.extend(game.catalogue.iter().cloned());
+        catalog.extend(game.shop.iter().map(|e| e.item.clone()));
+        catalog.extend(game.quests.reward_items().cloned());
+        catalog.extend(player.rod.take());
+        catalog.extend(player.reel.take());
+        catalog.extend(player.lure.take());
+        catalog.extend(player.sonar.take());
+        let item = |id: &String| {
+            catalog
+                .iter()
+                .find(|i| &i.id == id)
+                .cloned()
+                .ok_or_else(|| GameError::Parse(format!("save: unknown item {}", id)))
+        };
+        let saved = self.player;
+        player.items = saved.items.iter().map(item).collect::<GameResult<_>>()?;
+        player.rod = saved.rod.as_ref().map(item).transpose()?;
+        player.reel = saved.reel.as_ref().map(item).transpose()?;
+        player.lure = saved.lure.as_ref().map(item).transpose()?;
+        player.sonar = saved.sonar.as_ref().map(item).transpose()?;
+        player.bait_bonus = player.lure.as_ref().map_or(0.0, |l| l.bite_bonus);
+        player.tension_bonus = player.rod.as_ref().map_or(0, |r| r.tension_bonus);
+        player.reel_factor = player.reel.as_ref().map_or(1.0, |r| r.reel_factor);
+        player.cast_range = player
+            .reel
+            .as_ref()
+            .map_or(data::DEFAULT_CAST_RANGE, |r| r.max_cast_range);
+        player.pos = game
+            .map
+            .bounds()
+            .clamp(Point::new(saved.pos.0, saved.pos.1));
+        if let Some((rod, reel)) = saved.gear {
+            player.rod_durability = rod;
+            player.reel_durability = reel;
+        }
+        player.hp = saved.hp;
+        player.hunger = saved.hunger;
+        if let Some(thirst) = saved.thirst {
+            player.thirst = thirst;
+        }
+        player.line = saved.line;
+        player.canned_food = saved.canned_food;
+        player.coins = saved.coins;
+        player.driftwood = saved.driftwood;
+        player.buff = saved.buff;
+        player.effects = saved.effects;
+        player.inventory = inventory;
+        if let Some(boat) = &saved.boat {
+            player.boat.pos = game.map.bounds().clamp(Point::new(boat.pos.0, boat.pos.1));
+            player.boat.durability = boat.durability.clamp(0, player.boat.max_durability);
+            player.boat.anchored = boat.anchored;
+            player.aboard = saved.aboard;
+        } else if game.map.tiles[game.map.idx(player.pos)] == TileKind::DeepWater {
+            // Older saves may leave the player at sea: put them in the boat
+            player.boat.pos = player.pos;
+            player.aboard = true;
+        }
+        let longlines = self
+            .longlines
+            .iter()
+            .map(|l| {
+                Ok(Longline {
+                    anchor: Point::new(l.anchor.0, l.anchor.1),
+                    tiles: l.tiles.iter().map(|&(x, y)| Point::new(x, y)).collect(),
+                    catches: l
+                        .catches
+                        .iter()
+                        .map(|(id, weight)| Ok(Catch::new(fish_kind(id)?, *weight)))
+                        .collect::<GameResult<_>>()?,
+                    left: l.left,
+                    item: item(&l.item)?,
+                })
+            })
+            .collect::<GameResult<Vec<_>>>()?;
+        let traps = self
+            .traps
+            .iter()
+            .map(|t| {
+                Ok(Trap {
+                    pos: Point::new(t.pos.0, t.pos.1),
+                    baited: t.baited,
+                    catches: t
+                        .catches
+                        .iter()
+                        .map(|(id, weight)| Ok(Catch::new(fish_kind(id)?, *weight)))
+                        .collect::<GameResult<_>>()?,
+                    left: t.left,
+                    item: item(&t.item)?,
+                })
+            })
+            .collect::<GameResult<Vec<_>>>()?;
+
+        if let Some(stock) = &self.shop {
+            game.shop.retain_mut(
+                |entry| match stock.iter().find(|(id, _)| *id == entry.item.id) {
+                    Some(&(_, count)) => {
+                        entry.stock = count;
+                        count > 0
+                    }
+                    None => false,
+                },
+            );
+        }
+        game.quests.restore(self.quests);
+        if self.chunk != (0, 0) {
+            game.chunk = Point::new(self.chunk.0, self.chunk.1);
+            game.map = game.ocean.take(game.chunk)?;
+            game.merchant = None;
+        }
+        if self.explored.is_empty() {
+            game.explore();
+        } else {
+            let explored = decode_runs(&self.explored);
+            if explored.len() != game.map.explored.len() {
+                return Err(GameError::Parse(
+                    "save: explored tiles do not fit the map".into(),
+                ));
+            }
+            game.map.explored = explored;
+        }
+        game.fishes = fishes;
+        game.runs = self
+            .runs
+            .into_iter()
+            .map(|r| ecology::migration::Run {
+                route: r.route.iter().map(|&(x, y)| Point::new(x, y)).collect(),
+                progress: r.progress,
+                ..ecology::migration::Run::new(&r.fish, Vec::new())
+            })
+            .collect();
+        // Older saves keep the fresh stock of the current area
+        if !self.populations.is_empty() {
+            game.populations = self
+                .populations
+                .into_iter()
+                .map(|p| {
+                    let stock = ecology::population::Population {
+                        capacity: p.capacity,
+                        catches: p.catches,
+                        depleted: p.depleted,
+                    };
+                    (p.area, stock)
+                })
+                .collect();
+        }
+        let kinds = self
+            .hazard_kinds
+            .iter()
+            .copied()
+            .chain(std::iter::repeat(HazardKind::Jellyfish));
+        game.hazards = self
+            .hazards
+            .iter()
+            .zip(kinds)
+            .map(|(&(x, y, turns), kind)| Hazard {
+                pos: Point::new(x, y),
+                turns,
+                kind,
+            })
+            .collect();
+        game.chum = self
+            .chum
+            .iter()
+            .map(|&(x, y, turns)| Chum {
+                pos: Point::new(x, y),
+                turns,
+            })
+            .collect();
+        game.longlines = longlines;
+        game.net_cooldown = self.net_cooldown;
+        game.traps = traps;
+        game.campfires = self
+            .campfires
+            .iter()
+            .map(|&(x, y, turns)| Campfire {
+                pos: Point::new(x, y),
+                turns,
+            })
+            .collect();
+        game.rivals = self
+            .rivals
+            .into_iter()
+            .map(|(name, x, y, casting, score)| rivals::Rival {
+                name,
+                pos: Point::new(x, y),
+                casting,
+                score,
+            })
+            .collect();
+        game.turn = self.turn;
+        game.time_of_day = TIMES[((self.turn / TIME_SEGMENT_TURNS) % TIMES.len() as u32) as usize];
+        game.update_tide();
+        game.weather = self.weather;
+        game.depth = game.map.depth(game.player.pos);
+        game.chart_features();
+        game.rng = self
+            .rng
+            .unwrap_or_else(|| RandomNumberGenerator::seeded(self.seed ^ u64::from(self.turn)));
+        game.metrics = self.metrics;
+        game.journal = self.journal;
+        // The codex file is flushed with every save, so it only lags behind
+        // the save when it was lost or the save was copied from elsewhere
+        if self.codex.total_captures() > game.codex.total_captures() {
+            game.codex.replace_records(self.codex);
+            game.codex.flush(game.storage.as_ref())?;
+        }
+        game.ui.set_layout(UILayout::Standard);
+        Ok(game)
+    }
+}
+
+/// Restores position, stats and time of day from a save written before the
+/// full state was stored, e.g.
+/// `(player:(pos:(x:1, y:2), hp:3, hunger:4, food:0), time_of_day:"Dawn")`.
+pub(crate) fn restore_legacy(data: &str, storage: Box<dyn Storage>) -> GameResult<LurhookGame> {
+    fn parse_i32(s: &str, key: &str) -> GameResult<i32> {
+        let start = s
+            .find(key)
+            .ok_or_else(|| GameError::Parse(format!("missing {}", key)))?;
+        let s = &s[start + key.len()..];
+        let end = s
+            .find(|c: char| [',', ')'].contains(&c))
+            .ok_or_else(|| GameError::Parse(format!("malformed {}", key)))?;
+        s[..end]
+            .trim()
+            .parse()
+            .map_err(|_| GameError::Parse(format!("invalid {}", key)))
+    }
+
+    fn parse_str<'a>(s: &'a str, key: &str) -> GameResult<&'a str> {
+        let start = s
+            .find(key)
+            .ok_or_else(|| GameError::Parse(format!("missing {}", key)))?;
+        let s = &s[start + key.len()..];
+        let start_quote = s
+            .find('"')
+            .ok_or_else(|| GameError::Parse(format!("malformed {}", key)))?
+            + 1;
+        let end_quote = s[start_quote..]
+            .find('"')
+            .ok_or_else(|| GameError::Parse(format!("malformed {}", key)))?;
+        Ok(&s[start_quote..start_quote + end_quote])
+    }
+
+    let mut game = LurhookGame::new_with_storage(0, Difficulty::Normal, None, storage)?;
+    game.player.pos.x = parse_i32(data, "x:")?;
+    game.player.pos.y = parse_i32(data, "y:")?;
+    game.player.hp = parse_i32(data, "hp:")?;
+    game.player.hunger = parse_i32(data, "hunger:")?;
+    game.player.canned_food = parse_i32(data, "food:")?;
+    let tod = parse_str(data, "time_of_day:")?;
+    game.time_of_day = TIMES
+        .iter()
+        .find(|t| **t == tod)
+        .ok_or_else(|| GameError::Parse(format!("invalid time_of_day {}", tod)))?;
+    Ok(game)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex::Capture;
+    use common::MemoryStorage;
+    use std::rc::Rc;
+
+    fn game(storage: Rc<dyn Storage>) -> LurhookGame {
+        LurhookGame::new_with_storage(42, Difficulty::Hard, Some("Offshore"), Box::new(storage))
+            .unwrap()
+    }
+
+    #[test]
+    fn load_resumes_the_saved_run() {
+        let storage: Rc<dyn Storage> = Rc::new(MemoryStorage::default());
+        let mut game = game(storage.clone());
+        for _ in 0..25 {
+            game.advance_time();
+        }
+        game.weather = WeatherState::new(Weather::Fog, 3);
+        game.hazards = vec![Hazard {
+            pos: Point::new(4, 5),
+            turns: 2,
+            kind: HazardKind::Whirlpool,
+        }];
+        game.chum = vec![Chum {
+            pos: Point::new(6, 5),
+            turns: 9,
+        }];
+        game.fishes.truncate(2);
+        game.fishes[1].migrating = true;
+        game.runs = vec![ecology::migration::Run {
+            progress: 3,
+            ..ecology::migration::Run::new(
+                &game.fishes[1].kind.id,
+                vec![Point::new(0, 4), Point::new(1, 4)],
+            )
+        }];
+        game.player.pos = Point::new(7, 9);
+        game.player.line = 60;
+        game.player.inventory = vec![Catch {
+            stamp: 7,
+            cooked: true,
+            ..Catch::new(game.fish_types[1].clone(), 2.5)
+        }];
+        game.player.thirst = 33;
+        game.player.driftwood = 2;
+        game.player.buff = Some(data::Buff {
+            kind: data::BuffKind::Tension,
+            amount: 10.0,
+            turns: 25,
+        });
+        game.campfires = vec![Campfire {
+            pos: Point::new(7, 8),
+            turns: 30,
+        }];
+        game.player.effects = vec![StatusEffect {
+            kind: StatusKind::Soaked,
+            turns: 6,
+        }];
+        let rod = game.player.rod.take();
+        game.player.items.extend(rod);
+        game.player.tension_bonus = 0;
+        game.player.boat.pos = Point::new(8, 9);
+        game.player.boat.durability = 4;
+        game.player.boat.anchored = true;
+        game.player.aboard = false;
+        game.player.coins = 35;
+        game.player.reel_durability = 12;
+        game.longlines = vec![Longline {
+            anchor: Point::new(7, 9),
+            tiles: vec![Point::new(8, 9), Point::new(9, 9)],
+            catches: vec![Catch::new(game.fish_types[0].clone(), 1.5)],
+            left: true,
+            item: game
+                .shop
+                .iter()
+                .find(|e| e.item.id == "LONGLINE")
+                .unwrap()
+                .item
+                .clone(),
+        }];
+        game.net_cooldown = 4;
+        game.traps = vec![Trap {
+            pos: Point::new(8, 10),
+            baited: true,
+            catches: vec![Catch::new(game.fish_types[1].clone(), 0.8)],
+            left: false,
+            item: game
+                .shop
+                .iter()
+                .find(|e| e.item.id == "CRAB_POT")
+                .unwrap()
+                .item
+                .clone(),
+        }];
+        let trout = game.fish_types[0].clone();
+        for _ in 0..3 {
+            game.record_quest(quests::QuestEvent::Catch {
+                fish: &trout,
+                area: "Offshore",
+            });
+        }
+        game.record_quest(quests::QuestEvent::Turn);
+        let sold_out = game.shop.remove(0);
+        game.player.items.push(sold_out.item);
+        game.shop[1].stock -= 1;
+        game.population_mut().depleted.push(("GDKO".into(), 40));
+        game.rescued = true;
+        game.start_competition();
+        game.rivals[0].casting = 2;
+        game.record_trail();
+        game.journal_found("Gullwing Bay");
+        game.rivals[0].score = 80;
+        game.save_game("run.json").unwrap();
+
+        let loaded = LurhookGame::load_game_from(Box::new(storage), "run.json").unwrap();
+        assert_eq!((loaded.seed, loaded.area.id.as_str()), (42, "Offshore"));
+        assert_eq!(loaded.difficulty, Difficulty::Hard);
+        assert_eq!(loaded.rules, Difficulty::Hard.profile());
+        assert!(loaded.rescued);
+        assert_eq!(loaded.turn, game.turn);
+        assert_eq!(loaded.time_of_day, game.time_of_day);
+        assert_eq!(loaded.weather, WeatherState::new(Weather::Fog, 3));
+        assert_eq!(loaded.map.tiles, game.map.tiles);
+        assert_eq!(loaded.hazards.len(), 1);
+        assert_eq!(loaded.hazards[0].pos, Point::new(4, 5));
+        assert_eq!(loaded.hazards[0].kind, HazardKind::Whirlpool);
+        assert_eq!(
+            (loaded.chum[0].pos, loaded.chum[0].turns),
+            (Point::new(6, 5), 9)
+        );
+        let fish = |g: &LurhookGame| {
+            g.fishes
+                .iter()
+                .map(|f| (f.kind.id.clone(), f.position, f.hunger, f.weight))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(fish(&loaded), fish(&game));
+        assert_eq!(loaded.player.pos, Point::new(7, 9));
+        assert_eq!(loaded.player.line, 60);
+        assert_eq!(loaded.player.inventory[0].kind.id, game.fish_types[1].id);
+        assert_eq!(loaded.player.inventory[0].weight, 2.5);
+        assert_eq!(loaded.player.inventory[0].stamp, 7);
+        assert!(loaded.player.inventory[0].cooked);
+        assert_eq!(loaded.player.thirst, 33);
+        assert_eq!(loaded.player.driftwood, 2);
+        assert_eq!(loaded.player.buff, game.player.buff);
+        assert_eq!(loaded.player.effects, game.player.effects);
+        assert_eq!(
+            (loaded.campfires[0].pos, loaded.campfires[0].turns),
+            (Point::new(7, 8), 30)
+        );
+        assert!(loaded.player.rod.is_none());
+        assert_eq!(loaded.player.tension_bonus, 0);
+        assert_eq!(loaded.player.items, game.player.items);
+        assert_eq!(loaded.player.lure, game.player.lure);
+        assert_eq!(loaded.player.sonar, game.player.sonar);
+        assert_eq!(loaded.player.boat, game.player.boat);
+        assert!(!loaded.player.aboard);
+        assert_eq!(loaded.player.coins, 35);
+        assert_eq!(loaded.player.reel_durability, 12);
+        assert_eq!(loaded.net_cooldown, 4);
+        assert_eq!(loaded.rivals, game.rivals);
+        let line = &loaded.longlines[0];
+        assert_eq!((line.anchor, line.left), (Point::new(7, 9), true));
+        assert_eq!(line.tiles, game.longlines[0].tiles);
+        assert_eq!(line.catches[0].kind.id, game.fish_types[0].id);
+        assert_eq!(line.item.id, "LONGLINE");
+        let trap = &loaded.traps[0];
+        assert_eq!(
+            (trap.pos, trap.baited, trap.left),
+            (Point::new(8, 10), true, false)
+        );
+        assert_eq!(trap.catches[0].weight, 0.8);
+        assert_eq!(trap.item.id, "CRAB_POT");
+        assert_eq!(loaded.shop, game.shop);
+        assert_eq!(loaded.quests.progress(), game.quests.progress());
+        assert_eq!(loaded.metrics, game.metrics);
+        assert_eq!(loaded.journal, game.journal);
+        assert_eq!(loaded.map.explored, game.map.explored);
+        assert_eq!(loaded.populations, game.populations);
+        assert_eq!(loaded.runs, game.runs);
+        assert!(!loaded.fishes[0].migrating && loaded.fishes[1].migrating);
+    }
+
+    #[test]
+    fn explored_tiles_round_trip_as_runs() {
+        let flags = [false, false, true, true, true, false, true];
+        assert_eq!(encode_runs(&flags), [2, 3, 1, 1]);
+        assert_eq!(decode_runs(&encode_runs(&flags)), flags);
+        assert_eq!(encode_runs(&[true]), [0, 1]);
+
+        let storage: Rc<dyn Storage> = Rc::new(MemoryStorage::default());
+        let mut state = SaveState::of(&game(storage.clone()));
+        state.explored = vec![3, 4];
+        assert!(matches!(
+            state.restore(Box::new(storage)),
+            Err(GameError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn saved_codex_fills_a_missing_codex_file() {
+        let storage: Rc<dyn Storage> = Rc::new(MemoryStorage::default());
+        let mut game = game(storage.clone());
+        let id = game.fish_types[0].id.clone();
+        let capture = Capture {
+            id: &id,
+            turn: 1,
+            timestamp: None,
+            weight: 1.0,
+            location: "Offshore",
+        };
+        game.codex.record_capture(&capture);
+        let state = SaveState::of(&game).to_json().unwrap();
+
+        let fresh: Rc<dyn Storage> = Rc::new(MemoryStorage::default());
+        let loaded = SaveState::from_json(&state)
+            .unwrap()
+            .restore(Box::new(fresh.clone()))
+            .unwrap();
+        assert_eq!(loaded.codex.count(&id), 1);
+        assert!(fresh.read(CODEX_PATH).unwrap().is_some());
+    }
+
+    #[test]
+    fn unknown_fish_is_a_parse_error() {
+        let storage: Rc<dyn Storage> = Rc::new(MemoryStorage::default());
+        let mut state = SaveState::of(&game(storage.clone()));
+        state.fish.push(("NOT_A_FISH".into(), 0, 0, 0, 1.0));
+        assert!(matches!(
+            state.restore(Box::new(storage)),
+            Err(GameError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn legacy_save_still_loads() {
+        let storage = MemoryStorage::default();
+        let legacy = "(player:(pos:(x:3, y:4), hp:5, hunger:6, food:1), time_of_day:\"Dusk\")";
+        storage.write("old.ron", legacy.as_bytes()).unwrap();
+        let loaded = LurhookGame::load_game_from(Box::new(storage), "old.ron").unwrap();
+        assert_eq!(loaded.player.pos, Point::new(3, 4));
+        assert_eq!(loaded.player.hp, 5);
+        assert_eq!(loaded.time_of_day, "Dusk");
+    }
+
+    #[test]
+    fn old_save_slot_loads_when_the_slot_is_empty() {
+        let storage = MemoryStorage::default();
+        let legacy = "(player:(pos:(x:3, y:4), hp:5, hunger:6, food:1), time_of_day:\"Dusk\")";
+        storage.write(LEGACY_SAVE_PATH, legacy.as_bytes()).unwrap();
+        let loaded = LurhookGame::load_game_from(Box::new(storage), SAVE_PATH).unwrap();
+        assert_eq!(loaded.player.pos, Point::new(3, 4));
+        assert!(loaded
+            .ui
+            .logs()
+            .iter()
+            .any(|l| l.contains(LEGACY_SAVE_PATH)));
+    }
+
+    #[test]
+    fn loaded_run_draws_the_same_numbers() {
+        let storage: Rc<dyn Storage> = Rc::new(MemoryStorage::default());
+        let mut game = game(storage.clone());
+        game.rng.next_u64();
+        let state = SaveState::of(&game).to_json().unwrap();
+        let mut loaded = SaveState::from_json(&state)
+            .unwrap()
+            .restore(Box::new(storage))
+            .unwrap();
+        let draws = |rng: &mut RandomNumberGenerator| [rng.next_u64(), rng.next_u64()];
+        assert_eq!(draws(&mut loaded.rng), draws(&mut game.rng));
+    }
+}
@@ -0,0 +1,101 @@
+//! Seasons: the year turns every few in-game days. Each season sets the
+//! water temperature that bites depend on, how stormy the weather gets,
+//! which species are about and how the map is tinted.
+
+use super::*;
+use data::Season;
+
+/// In-game days a season lasts.
+pub(crate) const SEASON_DAYS: u32 = 3;
+/// Turns in a full day and night.
+pub(crate) const DAY_TURNS: u32 = TIME_SEGMENT_TURNS * TIMES.len() as u32;
+/// Fish that swim in when a season starts, on top of those replacing the
+/// species that left.
+const SEASON_ARRIVALS: usize = 2;
+
+/// The season `turn` falls in, starting with spring.
+pub(crate) fn season_at(turn: u32) -> Season {
+    let idx = turn / (DAY_TURNS * SEASON_DAYS) % Season::ALL.len() as u32;
+    Season::ALL[idx as usize]
+}
+
+impl LurhookGame {
+    pub(crate) fn season(&self) -> Season {
+        season_at(self.turn)
+    }
+
+    /// At the turn of a season, species out of season leave the area and
+    /// fish of the new season arrive. The fish on the line stays.
+    pub(crate) fn update_season(&mut self) {
+        if self.turn == 0 || !self.turn.is_multiple_of(DAY_TURNS * SEASON_DAYS) {
+            return;
+        }
+        let season = self.season();
+        let mut left = 0;
+        for i in (0..self.fishes.len()).rev() {
+            if self.hooked != Some(i) && !self.fishes[i].kind.in_season(season) {
+                self.take_fish(i);
+                left += 1;
+            }
+        }
+        let species = area_fish(&self.area, &self.fish_types, season);
+        let species = self.population_mut().available(&species);
+        if let Ok(arrivals) = spawn_fish_population(
+            &mut self.map,
+            &species,
+            left + SEASON_ARRIVALS,
+            &mut self.rng,
+        ) {
+            self.fishes.extend(arrivals);
+        }
+        self.ui
+            .add_entry(
+                LogCategory::Event,
+                &format!(
+                    "{} arrives. The water is {}°C.",
+                    season.name(),
+                    season.water_temperature()
+                ),
+            )
+            .ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seasons_follow_the_days() {
+        assert_eq!(season_at(0), Season::Spring);
+        assert_eq!(season_at(DAY_TURNS * SEASON_DAYS - 1), Season::Spring);
+        assert_eq!(season_at(DAY_TURNS * SEASON_DAYS), Season::Summer);
+        assert_eq!(season_at(DAY_TURNS * SEASON_DAYS * 3), Season::Winter);
+        assert_eq!(season_at(DAY_TURNS * SEASON_DAYS * 4), Season::Spring);
+    }
+
+    #[test]
+    fn out_of_season_species_leave() {
+        let mut game = LurhookGame::default();
+        let koi = game.fish_types.iter().find(|f| f.id == "GDKO").unwrap();
+        let pos = game.player.pos;
+        game.fishes = vec![Fish::new(koi.clone(), pos), Fish::new(koi.clone(), pos)];
+        game.hooked = Some(1);
+        game.turn = DAY_TURNS * SEASON_DAYS * 2 - 1;
+        game.advance_clock();
+        assert_eq!(game.season(), Season::Autumn);
+        assert_eq!(game.hooked, Some(0), "the hooked koi stays");
+        assert_eq!(
+            game.fishes.iter().filter(|f| f.kind.id == "GDKO").count(),
+            1
+        );
+        assert!(game.fishes.len() > 1, "autumn fish swim in");
+        assert!(game.fishes[1..]
+            .iter()
+            .all(|f| f.kind.in_season(Season::Autumn)));
+        assert_eq!(
+            game.ui.logs().last().unwrap(),
+            "Autumn arrives. The water is 16°C."
+        );
+    }
+}
@@ -0,0 +1,328 @@
+//! The merchant: buys catches for coins and sells gear from `shop.json`.
+
+use super::*;
+
+/// First row of the trade lists in the view panel.
+const LIST_TOP: i32 = 4;
+
+/// Shore tile closest to `start` where the merchant sets up: land or beach
+/// with water next to it.
+pub(crate) fn merchant_spot(map: &Map, start: Point) -> Option<Point> {
+    (0..map.height as i32)
+        .flat_map(|y| (0..map.width as i32).map(move |x| Point::new(x, y)))
+        .filter(|&pt| pt != start && is_shore(map, pt))
+        .min_by_key(|pt| pt.manhattan(start))
+}
+
+/// Whether `pt` is land or beach next to the water.
+pub(crate) fn is_shore(map: &Map, pt: Point) -> bool {
+    let bounds = map.bounds();
+    matches!(map.tiles[map.idx(pt)], TileKind::Land | TileKind::Sandbar)
+        && Direction::ALL.iter().any(|d| {
+            let next = pt + d.delta();
+            bounds.contains(next) && map.tiles[map.idx(next)].is_water()
+        })
+}
+
+impl LurhookGame {
+    /// Opens the trade screen. Time does not pass while trading.
+    pub(crate) fn enter_trading(&mut self) {
+        self.mode = GameMode::Trading {
+            cursor: 0,
+            buying: false,
+        };
+        self.ui
+            .add_log("The merchant waves you over. Enter to trade, Esc to leave.")
+            .ok();
+    }
+
+    /// Number of rows in the sell or buy list.
+    fn trade_list_len(&self, buying: bool) -> usize {
+        if buying {
+            self.shop.len()
+        } else {
+            self.player.inventory.len()
+        }
+    }
+
+    /// Handles a key at the merchant's counter.
+    pub(crate) fn handle_trading_key(&mut self, key: VirtualKeyCode) {
+        let GameMode::Trading { cursor, buying } = self.mode else {
+            return;
+        };
+        if key == VirtualKeyCode::Escape || key == self.input.quit {
+            self.mode = GameMode::Exploring;
+            return;
+        }
+        if key == self.input.cast || key == VirtualKeyCode::Return {
+            if buying {
+                self.buy(cursor);
+            } else {
+                self.sell(cursor);
+            }
+            let len = self.trade_list_len(buying);
+            self.mode = GameMode::Trading {
+                cursor: cursor.min(len.saturating_sub(1)),
+                buying,
+            };
+            return;
+        }
+        let (cursor, buying) = match self.key_direction(key) {
+            Some(Direction::North) => (cursor.saturating_sub(1), buying),
+            Some(Direction::South) => (
+                (cursor + 1).min(self.trade_list_len(buying).saturating_sub(1)),
+                buying,
+            ),
+            Some(Direction::West) => (0, false),
+            Some(Direction::East) => (0, true),
+            _ => return,
+        };
+        self.mode = GameMode::Trading { cursor, buying };
+    }
+
+    /// Sells the catch at `idx` for its price.
+    fn sell(&mut self, idx: usize) {
+        if idx >= self.player.inventory.len() {
+            self.ui.add_log("You have no fish to sell.").ok();
+            return;
+        }
+        let catch = self.player.inventory.remove(idx);
+        let price = self.skills.sell_price(catch.price());
+        self.player.coins += price;
+        self.ui
+            .add_log(&format!("Sold a {} for {} coins.", catch.kind.name, price))
+            .ok();
+    }
+
+    /// Buys one of the shop entry at `idx` if the player can afford it.
+    fn buy(&mut self, idx: usize) {
+        let Some(entry) = self.shop.get_mut(idx) else {
+            return;
+        };
+        let price = self.skills.buy_price(entry.price);
+        if self.player.coins < price {
+            self.ui
+                .add_log(&format!("The {} costs {} coins.", entry.item.name, price))
+                .ok();
+            return;
+        }
+        self.player.coins -= price;
+        entry.stock -= 1;
+        let item = entry.item.clone();
+        if entry.stock == 0 {
+            self.shop.remove(idx);
+        }
+        self.ui.add_log(&format!("Bought a {}.", item.name)).ok();
+        self.player.items.push(item);
+    }
+
+    /// Draws the trade screen in place of the map.
+    pub(super) fn draw_trading(&self, ctx: &mut dyn Renderer) {
+        let GameMode::Trading { cursor, buying } = self.mode else {
+            return;
+        };
+        let view = self.view_panel();
+        // Sell list on the left half of the view, buy list on the right
+        let list_rows = view.height - LIST_TOP - 2;
+        let buy_x = view.width / 2 + 1;
+        let row_width = (view.width / 2 - 3) as usize;
+        ctx.print(2, 1, "-- Merchant --");
+        ctx.print(2, 2, &format!("Coins: {}", self.player.coins));
+        let sell: Vec<String> = self
+            .player
+            .inventory
+            .iter()
+            .map(|c| format!("{} {:.1}kg", c.kind.name, c.weight))
+            .zip(
+                self.player
+                    .inventory
+                    .iter()
+                    .map(|c| self.skills.sell_price(c.price())),
+            )
+            .map(|(name, price)| trade_row(&name, price, row_width))
+            .collect();
+        let buy: Vec<String> = self
+            .shop
+            .iter()
+            .map(|e| {
+                let price = self.skills.buy_price(e.price);
+                trade_row(&format!("{} x{}", e.item.name, e.stock), price, row_width)
+            })
+            .collect();
+        for (x, title, rows, active) in [(2, "Sell", &sell, !buying), (buy_x, "Buy", &buy, buying)]
+        {
+            let (fg, bg) = if active {
+                (RGB::named(BLACK), RGB::named(WHITE))
+            } else {
+                (RGB::named(WHITE), RGB::named(BLACK))
+            };
+            ctx.print_color(x, LIST_TOP - 1, fg, bg, title);
+            if rows.is_empty() {
+                ctx.print(x, LIST_TOP, "(nothing)");
+                continue;
+            }
+            // Scroll so the cursor stays in view
+            let skip = if active {
+                (cursor as i32 - list_rows + 1).max(0) as usize
+            } else {
+                0
+            };
+            for (i, row) in rows.iter().enumerate().skip(skip).take(list_rows as usize) {
+                let y = LIST_TOP + (i - skip) as i32;
+                if active && i == cursor {
+                    ctx.print_color(x, y, RGB::named(BLACK), RGB::named(YELLOW), row);
+                } else {
+                    ctx.print(x, y, row);
+                }
+            }
+        }
+        ctx.print(
+            2,
+            view.height - 1,
+            "move: choose/switch  Enter: trade  Esc: leave",
+        );
+    }
+
+    /// Draws the merchant where the player can see them.
+    pub(super) fn draw_merchant(&self, ctx: &mut dyn Renderer) {
+        let view = self.camera();
+        if let Some(pos) = self.merchant {
+            if view.contains(pos) && self.is_visible(pos) {
+                let screen = view.to_local(pos);
+                ctx.set(
+                    screen.x,
+                    screen.y,
+                    RGB::named(YELLOW),
+                    RGB::named(BLACK),
+                    '$',
+                );
+            }
+        }
+    }
+}
+
+/// One list row of `width` characters: the name cut to fit, then the
+/// price right-aligned.
+fn trade_row(name: &str, price: i32, width: usize) -> String {
+    let price = format!("{}c", price);
+    let name: String = name.chars().take(width - price.len() - 1).collect();
+    format!("{:<w$}{}", name, price, w = width - price.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_game;
+
+    fn game() -> LurhookGame {
+        let mut game = memory_game(3);
+        game.ui.set_layout(UILayout::Standard);
+        game
+    }
+
+    #[test]
+    fn merchant_stands_on_the_shore() {
+        let game = game();
+        let pos = game.merchant.expect("merchant");
+        assert!(matches!(
+            game.map.tiles[game.map.idx(pos)],
+            TileKind::Land | TileKind::Sandbar
+        ));
+        assert!(Direction::ALL.iter().any(|d| {
+            let next = pos + d.delta();
+            game.map.bounds().contains(next) && game.map.tiles[game.map.idx(next)] != TileKind::Land
+        }));
+    }
+
+    #[test]
+    fn no_shore_means_no_merchant() {
+        let mut map = Map::new(4, 4);
+        map.tiles.fill(TileKind::DeepWater);
+        assert_eq!(merchant_spot(&map, Point::new(0, 0)), None);
+    }
+
+    #[test]
+    fn walking_into_the_merchant_opens_trading() {
+        let mut game = game();
+        let merchant = game.merchant.unwrap();
+        game.player.aboard = false;
+        game.player.pos = merchant + Point::new(-1, 0);
+        let idx = game.map.idx(game.player.pos);
+        game.map.tiles[idx] = TileKind::Land;
+        let turn = game.turn;
+        game.try_move(Point::new(1, 0));
+        assert!(matches!(game.mode, GameMode::Trading { .. }));
+        assert_eq!(game.player.pos, merchant + Point::new(-1, 0));
+        game.handle_trading_key(VirtualKeyCode::Escape);
+        assert_eq!(game.mode, GameMode::Exploring);
+        assert_eq!(game.turn, turn);
+    }
+
+    #[test]
+    fn selling_fish_pays_coins() {
+        let mut game = game();
+        let fish = game.fish_types[0].clone();
+        let catch = Catch::new(fish.clone(), fish.max_weight);
+        let price = catch.price();
+        assert!(price > Catch::average(fish).price());
+        game.player.inventory.push(catch);
+        game.enter_trading();
+        game.handle_trading_key(VirtualKeyCode::Return);
+        assert_eq!(game.player.coins, price);
+        assert!(game.player.inventory.is_empty());
+    }
+
+    #[test]
+    fn buying_spends_coins_and_stock() {
+        let mut game = game();
+        let spool = game
+            .shop
+            .iter()
+            .position(|e| e.item.kind == data::ItemKind::Line)
+            .unwrap();
+        let entry = game.shop[spool].clone();
+        game.enter_trading();
+        game.mode = GameMode::Trading {
+            cursor: spool,
+            buying: true,
+        };
+        game.handle_trading_key(VirtualKeyCode::Return);
+        assert!(game.player.items.iter().all(|i| i.id != entry.item.id));
+
+        game.player.coins = entry.price * entry.stock as i32;
+        for _ in 0..entry.stock {
+            game.handle_trading_key(VirtualKeyCode::Return);
+        }
+        assert_eq!(game.player.coins, 0);
+        assert!(game.shop.iter().all(|e| e.item.id != entry.item.id));
+        let bought = game.player.items.iter().filter(|i| i.id == entry.item.id);
+        assert_eq!(bought.count(), entry.stock as usize);
+    }
+
+    #[test]
+    fn line_spool_restores_the_line() {
+        let mut game = game();
+        let spool = game
+            .shop
+            .iter()
+            .find(|e| e.item.kind == data::ItemKind::Line)
+            .unwrap()
+            .item
+            .clone();
+        game.player.line = 10;
+        game.player.items = vec![spool];
+        game.inventory_cursor = 0;
+        game.activate_selected_item();
+        assert_eq!(game.player.line, MAX_LINE);
+        assert!(game.player.items.is_empty());
+    }
+
+    #[test]
+    fn trade_rows_fit_their_column() {
+        for width in [27, 47] {
+            let row = trade_row("An extremely long fish name 12.5kg", 120, width);
+            assert_eq!(row.len(), width);
+            assert!(row.ends_with(" 120c"));
+        }
+    }
+}
@@ -0,0 +1,275 @@
+//! Headless play: bots, balance scripts and integration tests drive a run
+//! one [`Action`] per turn with no window, key bindings or frame input,
+//! and read back a [`StepReport`] of what the turn left behind. Co-op
+//! guests send the same actions over the network.
+
+use super::*;
+use serde::{Deserialize, Serialize};
+
+/// One turn's command from a bot or a co-op guest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Action {
+    Move {
+        dx: i32,
+        dy: i32,
+    },
+    Cast {
+        x: i32,
+        y: i32,
+        /// Missing from guests that predate lure presentation.
+        #[serde(default)]
+        presentation: fishing::Presentation,
+    },
+    Reel,
+    /// Sets the hook on a fish holding the bait.
+    Strike,
+    /// Boards the boat or steps off it.
+    Board,
+    /// Drops or weighs the anchor.
+    Anchor,
+    /// Eats the catch that spoils first.
+    Eat,
+    /// Picks option `option` (from 0) of the open question.
+    Choose {
+        option: usize,
+    },
+    /// Ends the run without asking.
+    EndRun,
+    Wait,
+}
+
+/// Where a headless run stands.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    Exploring,
+    /// The line is out and nothing holds the bait yet.
+    Waiting,
+    /// A fish holds the bait; strike to hook it.
+    Biting,
+    /// A hooked fish is fighting the line.
+    Fighting,
+    /// A question waits for [`Action::Choose`].
+    Choosing,
+    /// The run ended or the angler died.
+    Over,
+}
+
+/// The state of a headless run after a turn.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StepReport {
+    pub turn: u32,
+    pub phase: Phase,
+    pub hp: i32,
+    pub hunger: i32,
+    pub score: i32,
+    pub pos: common::Point,
+    /// Fish the angler can see within casting range, nearest first.
+    pub fish_in_reach: Vec<common::Point>,
+    /// Events of the turn, in order.
+    pub events: Vec<GameEvent>,
+}
+
+impl LurhookGame {
+    /// A new run that keeps everything it would persist in memory, for
+    /// bots that play many runs.
+    pub fn headless(seed: u64, difficulty: Difficulty) -> GameResult<Self> {
+        let mut game =
+            Self::new_with_storage(seed, difficulty, None, Box::new(MemoryStorage::default()))?;
+        game.ui.set_layout(UILayout::Standard);
+        Ok(game)
+    }
+
+    /// Plays one turn of `action`. Actions that make no sense right now
+    /// are skipped, but the turn still passes; while a question is open
+    /// only [`Action::Choose`] counts and no turn passes.
+    pub fn act(&mut self, action: Action) -> StepReport {
+        self.ui.next_frame();
+        self.ui.dismiss_popup();
+        self.ui.dismiss_catch_card();
+        if self.final_score().is_none() {
+            if self.ui.prompt().is_some() {
+                if let Action::Choose { option } = action {
+                    if self.ui.select_prompt(option) {
+                        self.answer_prompt();
+                    }
+                }
+            } else {
+                self.apply_action(action);
+                if self.ui.prompt().is_none() {
+                    self.pass_turn();
+                }
+            }
+        }
+        let events = self.events.iter().cloned().collect();
+        self.dispatch_events();
+        StepReport {
+            events,
+            ..self.observe()
+        }
+    }
+
+    fn apply_action(&mut self, action: Action) {
+        self.reeling = false;
+        match (action, self.mode) {
+            (Action::Move { dx, dy }, GameMode::Exploring) => {
+                self.try_move(common::Point::new(dx.signum(), dy.signum()));
+                if let GameMode::Trading { .. } = self.mode {
+                    // Bots do not trade
+                    self.mode = GameMode::Exploring;
+                }
+            }
+            (Action::Cast { x, y, presentation }, GameMode::Exploring) => {
+                self.player.presentation = presentation;
+                self.cast();
+                if let GameMode::Aiming { .. } = self.mode {
+                    self.aim_at(common::Point::new(x, y));
+                    self.confirm_cast();
+                }
+            }
+            (Action::Reel, GameMode::Fishing { .. }) => self.reeling = true,
+            (Action::Strike, GameMode::Fishing { .. }) => self.strike_bite(),
+            (Action::Board, GameMode::Exploring) => self.toggle_boat(),
+            (Action::Anchor, GameMode::Exploring) => self.toggle_anchor(),
+            (Action::Eat, GameMode::Exploring) => self.eat_fish(),
+            (Action::EndRun, _) => self.end_run(),
+            _ => {}
+        }
+    }
+
+    /// The state of the run, without the events of any turn.
+    pub fn observe(&self) -> StepReport {
+        let phase = match self.mode {
+            _ if self.final_score().is_some() => Phase::Over,
+            _ if self.ui.prompt().is_some() => Phase::Choosing,
+            GameMode::Fishing { .. } if self.meter.is_some() => Phase::Fighting,
+            GameMode::Fishing { bite: Some(_), .. } => Phase::Biting,
+            GameMode::Fishing { .. } => Phase::Waiting,
+            _ => Phase::Exploring,
+        };
+        let fish_in_reach = ecology::fish_near(&self.fishes, self.player.pos, self.aim_range())
+            .into_iter()
+            .map(|(i, _)| self.fishes[i].position)
+            .filter(|&pos| self.is_visible(pos))
+            .collect();
+        StepReport {
+            turn: self.turn,
+            phase,
+            hp: self.player.hp,
+            hunger: self.player.hunger,
+            score: self.final_score().unwrap_or_else(|| self.score()),
+            pos: self.player.pos,
+            fish_in_reach,
+            events: Vec::new(),
+        }
+    }
+
+    /// Plays a headless run of `seed` with the actions `bot` picks from
+    /// each report, until it is over or `max_turns` have passed. Returns
+    /// the last report.
+    pub fn simulate(
+        seed: u64,
+        difficulty: Difficulty,
+        max_turns: u32,
+        mut bot: impl FnMut(&StepReport) -> Action,
+    ) -> GameResult<StepReport> {
+        let mut game = Self::headless(seed, difficulty)?;
+        let mut report = game.observe();
+        // Questions take no turns, so count the actions too
+        let mut actions = 0;
+        while report.phase != Phase::Over && report.turn < max_turns && actions < max_turns * 2 {
+            report = game.act(bot(&report));
+            actions += 1;
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Casts at the nearest fish, strikes, reels and eats when hungry.
+    fn angler(report: &StepReport) -> Action {
+        match report.phase {
+            Phase::Choosing => Action::Choose { option: 0 },
+            Phase::Biting => Action::Strike,
+            Phase::Fighting | Phase::Waiting => Action::Reel,
+            _ if report.hunger < 30 && report.score > 0 => Action::Eat,
+            _ => match report.fish_in_reach.first() {
+                Some(fish) => Action::Cast {
+                    x: fish.x,
+                    y: fish.y,
+                    presentation: fishing::Presentation::default(),
+                },
+                None => Action::Move {
+                    dx: [1, 0, -1, 0][(report.turn / 7 % 4) as usize],
+                    dy: [0, 1, 0, -1][(report.turn / 7 % 4) as usize],
+                },
+            },
+        }
+    }
+
+    #[test]
+    fn actions_play_turns_without_a_window() {
+        let mut game = LurhookGame::headless(1, Difficulty::Normal).unwrap();
+        let start = game.observe();
+        assert_eq!((start.turn, start.phase), (0, Phase::Exploring));
+        let report = game.act(Action::Wait);
+        assert_eq!(report.turn, 1);
+        let report = game.act(Action::Strike);
+        assert_eq!(report.turn, 2, "a pointless action still passes the turn");
+        let report = game.act(Action::EndRun);
+        assert_eq!(report.phase, Phase::Over);
+        assert_eq!(game.act(Action::Wait).turn, report.turn);
+    }
+
+    #[test]
+    fn questions_wait_for_a_choice() {
+        let mut game = LurhookGame::headless(1, Difficulty::Normal).unwrap();
+        let bottle = game
+            .scripted_events
+            .iter()
+            .position(|e| e.id == "MESSAGE_IN_A_BOTTLE")
+            .unwrap();
+        game.start_event(bottle);
+        assert_eq!(game.observe().phase, Phase::Choosing);
+        let coins = game.player.coins;
+        assert_eq!(game.act(Action::Wait).turn, 0);
+        let report = game.act(Action::Choose { option: 0 });
+        assert_eq!((report.turn, report.phase), (0, Phase::Exploring));
+        assert_eq!(game.player.coins, coins + 15);
+    }
+
+    #[test]
+    fn a_seed_replays_the_same_run() {
+        let play = |seed| {
+            let mut game = LurhookGame::headless(seed, Difficulty::Normal).unwrap();
+            let fish: Vec<_> = game.fishes.iter().map(|f| f.position).collect();
+            let mut reports = Vec::new();
+            for _ in 0..60 {
+                let report = game.observe();
+                reports.push(game.act(angler(&report)));
+            }
+            (game.map.tiles.clone(), fish, reports)
+        };
+        assert_eq!(play(5), play(5));
+        assert_ne!(play(5).0, play(6).0);
+    }
+
+    #[test]
+    fn bots_land_fish() {
+        let mut catches = 0;
+        let last = LurhookGame::simulate(0, Difficulty::Easy, 200, |report| {
+            catches += report
+                .events
+                .iter()
+                .filter(|e| matches!(e, GameEvent::FishCaught { .. }))
+                .count();
+            angler(report)
+        })
+        .unwrap();
+        assert!(catches > 0, "the bot caught nothing");
+        assert!(last.turn == 200 || last.phase == Phase::Over);
+    }
+}
@@ -0,0 +1,105 @@
+//! Angler skills: catches earn experience kept in the profile between
+//! runs, and each run starts with the modifiers of the skills unlocked
+//! so far. Skills unlocked during a run count from the next one.
+
+use super::*;
+use progression::Progress;
+
+impl LurhookGame {
+    /// Takes the profile's skills into the run.
+    pub(crate) fn apply_skills(&mut self) {
+        self.skills = self.profile.progress.modifiers();
+        self.rules.hunger_rate = self.rules.hunger_rate * self.skills.hunger / 100;
+    }
+
+    /// Angler experience kept in the profile.
+    pub(crate) fn progress(&self) -> &Progress {
+        &self.profile.progress
+    }
+
+    /// Earns the experience for landing fish `id` at `weight`, its catch
+    /// score, and announces any level and skill it brings. The tutorial
+    /// earns none.
+    pub(crate) fn gain_xp(&mut self, id: &str, weight: f32) {
+        if self.in_tutorial() {
+            return;
+        }
+        let Some(kind) = self.fish_types.iter().find(|f| f.id == id) else {
+            return;
+        };
+        let xp = Catch::new(kind.clone(), weight).score().max(1) as u32;
+        let level = self.profile.progress.level();
+        let unlocked = self.profile.progress.gain(xp);
+        let now = self.profile.progress.level();
+        if now > level {
+            self.ui
+                .add_entry(LogCategory::Event, &format!("Angler level {}!", now))
+                .ok();
+        }
+        for skill in unlocked {
+            self.ui
+                .add_entry(
+                    LogCategory::Event,
+                    &format!(
+                        "New skill from your next run: {} ({}).",
+                        skill.name(),
+                        skill.description()
+                    ),
+                )
+                .ok();
+        }
+        let _ = self.profile.save(self.storage.as_ref(), PROFILE_PATH);
+    }
+
+    /// Reeling strength of the angler's gear and skill.
+    pub(crate) fn reel_strength(&self) -> f32 {
+        self.player.reel_strength() * self.skills.reel
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    fn game_with(progress: Progress, storage: &Rc<dyn Storage>) -> LurhookGame {
+        let mut profile = Profile::default();
+        profile.progress = progress;
+        profile.save(storage.as_ref(), PROFILE_PATH).unwrap();
+        LurhookGame::new_with_storage(0, Difficulty::Normal, None, Box::new(storage.clone()))
+            .unwrap()
+    }
+
+    #[test]
+    fn runs_start_with_the_unlocked_skills() {
+        let storage: Rc<dyn Storage> = Rc::new(MemoryStorage::default());
+        let novice = game_with(Progress::default(), &storage);
+        let master = game_with(Progress { xp: 1000 }, &storage);
+        assert_eq!(
+            master.rules.hunger_rate,
+            novice.rules.hunger_rate * 80 / 100
+        );
+        assert!(master.reel_strength() > novice.reel_strength());
+        assert_eq!(master.aim_range(), novice.aim_range() + 2);
+        assert!(master.skills.buy_price(100) < novice.skills.buy_price(100));
+    }
+
+    #[test]
+    fn catches_earn_experience_for_later_runs() {
+        let storage: Rc<dyn Storage> = Rc::new(MemoryStorage::default());
+        let mut game = game_with(Progress { xp: 99 }, &storage);
+        let id = game.fish_types[0].id.clone();
+        game.gain_xp(&id, 1.0);
+        assert_eq!(game.progress().level(), 2);
+        assert!(game.ui.logs().iter().any(|l| l.contains("Quick Reel")));
+        assert!(
+            (game.skills.reel - 1.0).abs() < f32::EPSILON,
+            "from the next run"
+        );
+        let saved = Profile::load(storage.as_ref(), PROFILE_PATH).unwrap();
+        assert_eq!(&saved.progress, game.progress());
+        let next =
+            LurhookGame::new_with_storage(0, Difficulty::Normal, None, Box::new(storage)).unwrap();
+        assert!(next.skills.reel > 1.0);
+    }
+}
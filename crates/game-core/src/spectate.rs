@@ -0,0 +1,376 @@
+//! Spectator stream: mirrors the screen and event feed to local viewers.
+//!
+//! Viewers connect over plain TCP and receive newline-delimited JSON
+//! [`Frame`]s, or connect with a WebSocket (a browser or chat bot) and
+//! receive one frame per text message. The event feed of a frame is the
+//! message log lines added since the one before.
+
+use super::*;
+use crate::app::LurhookApp;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tungstenite::WebSocket;
+use ui_crate::TextBuffer;
+
+/// TCP port used when none is given.
+pub const DEFAULT_SPECTATE_PORT: u16 = 7879;
+
+/// How long a new connection may take to reveal a WebSocket handshake.
+const SNIFF_TIMEOUT: Duration = Duration::from_millis(200);
+/// How long a WebSocket client may take to finish its handshake.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(2);
+/// Viewers slower than this are dropped rather than stalling the game.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(1);
+/// Connections still handshaking at once; more are refused.
+const MAX_HANDSHAKES: usize = 8;
+/// Viewers watching at once; more are refused.
+const MAX_VIEWERS: usize = 32;
+
+/// Run numbers shown beside the viewport.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Status {
+    pub turn: u32,
+    /// Id of the area.
+    pub area: String,
+    pub time_of_day: String,
+    pub hp: i32,
+    pub hunger: i32,
+    pub line: i32,
+    pub depth: i32,
+    pub score: i32,
+    pub catches: usize,
+}
+
+/// One update sent to every viewer.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Frame {
+    /// Increases by one per frame sent.
+    pub seq: u64,
+    /// Which screen is showing: `menu`, `game`, `hotseat`, `coop` or `summary`.
+    pub screen: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<Status>,
+    /// Screen text, one string per row with trailing spaces removed.
+    pub viewport: Vec<String>,
+    /// Log lines added since the previous frame.
+    pub events: Vec<String>,
+}
+
+enum Viewer {
+    Plain(TcpStream),
+    Web(Box<WebSocket<TcpStream>>),
+}
+
+impl Viewer {
+    /// Sends one JSON frame. Returns `false` once the viewer is gone.
+    fn send(&mut self, json: &str) -> bool {
+        match self {
+            Self::Plain(stream) => stream
+                .write_all(json.as_bytes())
+                .and_then(|_| stream.write_all(b"\n"))
+                .is_ok(),
+            Self::Web(ws) => ws
+                .send(tungstenite::Message::Text(json.to_string()))
+                .is_ok(),
+        }
+    }
+}
+
+/// Upgrades `stream` to a WebSocket if the client opens with an HTTP request.
+fn handshake(stream: TcpStream) -> Option<Viewer> {
+    stream.set_write_timeout(Some(WRITE_TIMEOUT)).ok()?;
+    stream.set_read_timeout(Some(SNIFF_TIMEOUT)).ok()?;
+    let mut head = [0u8; 4];
+    let is_http = matches!(stream.peek(&mut head), Ok(4) if &head == b"GET ");
+    if !is_http {
+        return Some(Viewer::Plain(stream));
+    }
+    stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT)).ok()?;
+    tungstenite::accept(stream)
+        .ok()
+        .map(|ws| Viewer::Web(Box::new(ws)))
+}
+
+#[derive(Default)]
+struct Shared {
+    viewers: Vec<Viewer>,
+    /// Viewers taken out of `viewers` while a frame is sent to them.
+    sending: usize,
+    /// Connections whose handshake is under way.
+    handshakes: usize,
+    /// Last frame sent, replayed to viewers as they join.
+    latest: Option<String>,
+}
+
+impl Shared {
+    fn viewer_count(&self) -> usize {
+        self.viewers.len() + self.sending
+    }
+
+    /// Whether another connection may start its handshake.
+    fn admits(&self) -> bool {
+        self.handshakes < MAX_HANDSHAKES && self.viewer_count() < MAX_VIEWERS
+    }
+}
+
+/// Catches `viewer` up on the latest frame, then adds it to the viewers
+/// unless they are full. The frame is sent without holding the lock, so it
+/// is sent again if a newer one was published meanwhile.
+fn join(shared: &Mutex<Shared>, mut viewer: Viewer) {
+    let mut sent: Option<String> = None;
+    loop {
+        let Ok(mut guard) = shared.lock() else {
+            return;
+        };
+        if guard.viewer_count() >= MAX_VIEWERS {
+            return;
+        }
+        if guard.latest == sent {
+            guard.viewers.push(viewer);
+            return;
+        }
+        let latest = guard.latest.clone();
+        drop(guard);
+        if let Some(json) = &latest {
+            if !viewer.send(json) {
+                return;
+            }
+        }
+        sent = latest;
+    }
+}
+
+/// Local server broadcasting [`Frame`]s of the running app.
+pub struct Spectator {
+    addr: SocketAddr,
+    shared: Arc<Mutex<Shared>>,
+    last_viewport: Vec<String>,
+    /// Log lines already sent as events.
+    sent_logs: usize,
+    seq: u64,
+}
+
+impl Spectator {
+    /// Listens on `addr` and accepts viewers in the background, refusing
+    /// connections past [`MAX_HANDSHAKES`] handshakes or [`MAX_VIEWERS`]
+    /// viewers.
+    pub fn bind(addr: impl ToSocketAddrs) -> GameResult<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let addr = listener.local_addr()?;
+        let shared = Arc::new(Mutex::new(Shared::default()));
+        let accept = Arc::clone(&shared);
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let Ok(mut guard) = accept.lock() else {
+                    return;
+                };
+                if !guard.admits() {
+                    // dropping the stream closes it
+                    continue;
+                }
+                guard.handshakes += 1;
+                drop(guard);
+                // a slow client must not hold up the next one
+                let shared = Arc::clone(&accept);
+                std::thread::spawn(move || {
+                    if let Some(viewer) = handshake(stream) {
+                        join(&shared, viewer);
+                    }
+                    if let Ok(mut guard) = shared.lock() {
+                        guard.handshakes -= 1;
+                    }
+                });
+            }
+        });
+        Ok(Self {
+            addr,
+            shared,
+            last_viewport: Vec::new(),
+            sent_logs: 0,
+            seq: 0,
+        })
+    }
+
+    /// Address viewers connect to.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Number of connected viewers.
+    pub fn viewers(&self) -> usize {
+        self.shared.lock().map_or(0, |s| s.viewer_count())
+    }
+
+    /// Builds the next frame, or `None` if nothing changed since the last one.
+    fn frame(&mut self, app: &LurhookApp) -> Option<Frame> {
+        let mut screen = TextBuffer::new(SCREEN_WIDTH, SCREEN_HEIGHT);
+        app.render(&mut screen);
+        let viewport: Vec<String> = (0..SCREEN_HEIGHT)
+            .map(|y| screen.row(y).trim_end().to_string())
+            .collect();
+        let game = app.game();
+        let logs = game.map_or(&[][..], |g| g.ui.entries());
+        if logs.len() < self.sent_logs {
+            // A new game started
+            self.sent_logs = 0;
+        }
+        let events: Vec<String> = logs[self.sent_logs..]
+            .iter()
+            .map(|e| e.text.clone())
+            .collect();
+        self.sent_logs = logs.len();
+        if viewport == self.last_viewport && events.is_empty() {
+            return None;
+        }
+        self.last_viewport = viewport.clone();
+        self.seq += 1;
+        Some(Frame {
+            seq: self.seq,
+            screen: app.screen_name().to_string(),
+            status: game.map(|g| Status {
+                turn: g.turn,
+                area: g.area.id.clone(),
+                time_of_day: g.time_of_day.to_string(),
+                hp: g.player.hp,
+                hunger: g.player.hunger,
+                line: g.player.line,
+                depth: g.depth,
+                score: g.score(),
+                catches: g.player.inventory.len(),
+            }),
+            viewport,
+            events,
+        })
+    }
+
+    /// Sends the app's current screen to every viewer if it changed.
+    pub fn publish(&mut self, app: &LurhookApp) {
+        let Some(frame) = self.frame(app) else {
+            return;
+        };
+        let Ok(json) = serde_json::to_string(&frame) else {
+            return;
+        };
+        let Ok(mut viewers) = self.shared.lock().map(|mut shared| {
+            shared.latest = Some(json.clone());
+            shared.sending = shared.viewers.len();
+            std::mem::take(&mut shared.viewers)
+        }) else {
+            return;
+        };
+        // sent unlocked so viewers can join while a slow one is written to
+        viewers.retain_mut(|v| v.send(&json));
+        if let Ok(mut shared) = self.shared.lock() {
+            shared.sending = 0;
+            shared.viewers.append(&mut viewers);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::AppState;
+    use crate::test_support::memory_game;
+    use std::io::{BufRead, BufReader, Read};
+
+    fn wait_for_viewers(spectator: &Spectator, count: usize) {
+        for _ in 0..200 {
+            if spectator.viewers() == count {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        panic!("viewer never connected");
+    }
+
+    #[test]
+    fn plain_viewer_receives_json_lines() {
+        let mut spectator = Spectator::bind("127.0.0.1:0").unwrap();
+        let stream = TcpStream::connect(spectator.local_addr()).unwrap();
+        wait_for_viewers(&spectator, 1);
+        spectator.publish(&LurhookApp::new());
+        let mut line = String::new();
+        BufReader::new(stream).read_line(&mut line).unwrap();
+        let frame: Frame = serde_json::from_str(&line).unwrap();
+        assert_eq!(frame.seq, 1);
+        assert_eq!(frame.screen, "menu");
+        assert!(frame.status.is_none());
+        assert_eq!(frame.viewport.len(), SCREEN_HEIGHT as usize);
+        assert!(frame.viewport.iter().any(|row| row.contains("Lurhook")));
+    }
+
+    #[test]
+    fn websocket_viewer_gets_latest_frame_on_join() {
+        let mut spectator = Spectator::bind("127.0.0.1:0").unwrap();
+        spectator.publish(&LurhookApp::new());
+        let stream = TcpStream::connect(spectator.local_addr()).unwrap();
+        let (mut ws, _) = tungstenite::client("ws://localhost/", stream).unwrap();
+        let msg = ws.read().unwrap();
+        let frame: Frame = serde_json::from_str(msg.to_text().unwrap()).unwrap();
+        assert_eq!(frame.seq, 1);
+        assert_eq!(frame.screen, "menu");
+    }
+
+    #[test]
+    fn stalled_handshake_does_not_hold_up_other_viewers() {
+        let spectator = Spectator::bind("127.0.0.1:0").unwrap();
+        let mut stalled = TcpStream::connect(spectator.local_addr()).unwrap();
+        stalled.write_all(b"GET / HTTP/1.1\r\n").unwrap();
+        std::thread::sleep(SNIFF_TIMEOUT);
+        let _viewer = TcpStream::connect(spectator.local_addr()).unwrap();
+        wait_for_viewers(&spectator, 1);
+    }
+
+    /// Whether the server closed `stream` without sending anything.
+    fn refused(mut stream: TcpStream) -> bool {
+        stream
+            .set_read_timeout(Some(Duration::from_secs(1)))
+            .unwrap();
+        matches!(stream.read(&mut [0u8; 1]), Ok(0))
+    }
+
+    #[test]
+    fn connections_past_the_limits_are_refused() {
+        let spectator = Spectator::bind("127.0.0.1:0").unwrap();
+        let stalled: Vec<TcpStream> = (0..MAX_HANDSHAKES)
+            .map(|_| {
+                let mut s = TcpStream::connect(spectator.local_addr()).unwrap();
+                s.write_all(b"GET / HTTP/1.1\r\n").unwrap();
+                s
+            })
+            .collect();
+        let extra = TcpStream::connect(spectator.local_addr()).unwrap();
+        assert!(refused(extra), "every handshake slot is taken");
+        drop(stalled);
+
+        let spectator = Spectator::bind("127.0.0.1:0").unwrap();
+        let viewers: Vec<TcpStream> = (0..MAX_VIEWERS)
+            .map(|i| {
+                let mut s = TcpStream::connect(spectator.local_addr()).unwrap();
+                s.write_all(b"\n").unwrap();
+                wait_for_viewers(&spectator, i + 1);
+                s
+            })
+            .collect();
+        let extra = TcpStream::connect(spectator.local_addr()).unwrap();
+        assert!(refused(extra), "the viewers are full");
+        assert_eq!(spectator.viewers(), viewers.len());
+    }
+
+    #[test]
+    fn unchanged_screen_sends_nothing_and_logs_become_events() {
+        let mut spectator = Spectator::bind("127.0.0.1:0").unwrap();
+        let mut game = memory_game(0);
+        game.ui.add_log("A fish bites!").unwrap();
+        let app = LurhookApp::with_state(AppState::Running(Box::new(game)), None);
+        let first = spectator.frame(&app).unwrap();
+        assert_eq!(first.screen, "game");
+        assert_eq!(first.status.as_ref().unwrap().area, "Coast");
+        assert_eq!(first.events.last().unwrap(), "A fish bites!");
+        assert!(spectator.frame(&app).is_none());
+    }
+}
@@ -0,0 +1,243 @@
+//! Pure text terminal front end, usable over SSH without a window.
+
+use crate::app::LurhookApp;
+use crate::input::{InputConfig, MouseButtons};
+use crate::{empty_frame, CONFIG_PATH};
+use bracket_lib::prelude::{BTerm, VirtualKeyCode};
+use common::{default_storage, GameResult};
+use crossterm::event::{
+    self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind,
+};
+use crossterm::{cursor, execute, terminal};
+use std::io::{self, Stdout};
+use std::time::{Duration, Instant};
+use ui_crate::{Renderer, TerminalRenderer};
+
+/// Time to wait for input before advancing a frame.
+const FRAME_TIME: Duration = Duration::from_millis(33);
+
+/// Puts the terminal into raw alternate-screen mode and restores it on drop.
+struct TerminalGuard {
+    out: Stdout,
+}
+
+impl TerminalGuard {
+    fn enter() -> GameResult<Self> {
+        let mut out = io::stdout();
+        terminal::enable_raw_mode()?;
+        execute!(
+            out,
+            terminal::EnterAlternateScreen,
+            terminal::Clear(terminal::ClearType::All),
+            cursor::Hide,
+            event::EnableMouseCapture
+        )?;
+        Ok(Self { out })
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        // Best effort: there is nowhere left to report a failure
+        let _ = execute!(
+            self.out,
+            event::DisableMouseCapture,
+            cursor::Show,
+            terminal::LeaveAlternateScreen
+        );
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Converts a terminal key to the key code used by the game.
+fn map_key(code: KeyCode) -> Option<VirtualKeyCode> {
+    use VirtualKeyCode as V;
+    let key = match code {
+        KeyCode::Char(c) => match c.to_ascii_lowercase() {
+            'a' => V::A,
+            'b' => V::B,
+            'c' => V::C,
+            'd' => V::D,
+            'e' => V::E,
+            'f' => V::F,
+            'g' => V::G,
+            'h' => V::H,
+            'i' => V::I,
+            'j' => V::J,
+            'k' => V::K,
+            'l' => V::L,
+            'm' => V::M,
+            'n' => V::N,
+            'o' => V::O,
+            'p' => V::P,
+            'q' => V::Q,
+            'r' => V::R,
+            's' => V::S,
+            't' => V::T,
+            'u' => V::U,
+            'v' => V::V,
+            'w' => V::W,
+            'x' => V::X,
+            'y' => V::Y,
+            'z' => V::Z,
+            '0' => V::Key0,
+            '1' => V::Key1,
+            '2' => V::Key2,
+            '3' => V::Key3,
+            '4' => V::Key4,
+            '5' => V::Key5,
+            '6' => V::Key6,
+            '7' => V::Key7,
+            '8' => V::Key8,
+            '9' => V::Key9,
+            ' ' => V::Space,
+            '+' | '=' => V::Plus,
+            '-' => V::Minus,
+            '[' => V::LBracket,
+            ']' => V::RBracket,
+            ',' => V::Comma,
+            '.' => V::Period,
+            '/' => V::Slash,
+            ';' => V::Semicolon,
+            _ => return None,
+        },
+        KeyCode::Left => V::Left,
+        KeyCode::Right => V::Right,
+        KeyCode::Up => V::Up,
+        KeyCode::Down => V::Down,
+        KeyCode::Enter => V::Return,
+        KeyCode::Esc => V::Escape,
+        KeyCode::Tab => V::Tab,
+        KeyCode::Backspace => V::Back,
+        KeyCode::F(n) => match n {
+            1 => V::F1,
+            2 => V::F2,
+            3 => V::F3,
+            4 => V::F4,
+            5 => V::F5,
+            6 => V::F6,
+            7 => V::F7,
+            8 => V::F8,
+            9 => V::F9,
+            10 => V::F10,
+            11 => V::F11,
+            12 => V::F12,
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(key)
+}
+
+/// Copies a key press into the frame input. Returns `false` on Ctrl+C.
+fn apply_key(ctx: &mut BTerm, key: KeyEvent) -> bool {
+    let control = key.modifiers.contains(KeyModifiers::CONTROL);
+    if control && key.code == KeyCode::Char('c') {
+        return false;
+    }
+    ctx.key = map_key(key.code);
+    ctx.control = control;
+    ctx.alt = key.modifiers.contains(KeyModifiers::ALT);
+    ctx.shift = key.modifiers.contains(KeyModifiers::SHIFT)
+        || matches!(key.code, KeyCode::Char(c) if c.is_ascii_uppercase());
+    true
+}
+
+/// Runs the game in the current terminal until the player quits.
+pub fn run_terminal() -> GameResult<()> {
+    run_terminal_app(LurhookApp::new())
+}
+
+/// Runs `app` in the current terminal until the player quits.
+///
+/// The screen fills the terminal and follows it when resized, unless
+/// `lurhook.toml` fixes the size in its `[display]` section.
+pub fn run_terminal_app(mut app: LurhookApp) -> GameResult<()> {
+    crate::init_subsystems()?;
+    let fixed = InputConfig::load(default_storage().as_ref(), CONFIG_PATH)
+        .unwrap_or_default()
+        .console_size;
+    let (columns, rows) = match fixed {
+        Some(size) => size,
+        None => terminal::size().map(|(w, h)| (w as i32, h as i32))?,
+    };
+    let mut guard = TerminalGuard::enter()?;
+    let mut screen = TerminalRenderer::new(columns, rows);
+    let mut last = Instant::now();
+    // Mouse events only arrive when something changes
+    let mut mouse_pos = (0, 0);
+    let mut left_held = false;
+    loop {
+        let mut ctx = empty_frame(last.elapsed().as_secs_f32() * 1000.0);
+        last = Instant::now();
+        ctx.mouse_pos = mouse_pos;
+        let mut right_click = false;
+        if event::poll(FRAME_TIME)? {
+            match event::read()? {
+                Event::Key(key)
+                    if key.kind != KeyEventKind::Release && !apply_key(&mut ctx, key) =>
+                {
+                    break;
+                }
+                Event::Mouse(mouse) => {
+                    mouse_pos = (mouse.column as i32, mouse.row as i32);
+                    ctx.mouse_pos = mouse_pos;
+                    match mouse.kind {
+                        MouseEventKind::Down(MouseButton::Left) => {
+                            ctx.left_click = true;
+                            left_held = true;
+                        }
+                        MouseEventKind::Up(MouseButton::Left) => left_held = false,
+                        MouseEventKind::Down(MouseButton::Right) => right_click = true,
+                        _ => {}
+                    }
+                }
+                Event::Resize(columns, rows) => {
+                    execute!(guard.out, terminal::Clear(terminal::ClearType::All))?;
+                    if fixed.is_none() {
+                        screen = TerminalRenderer::new(columns as i32, rows as i32);
+                    }
+                    screen.invalidate();
+                }
+                _ => {}
+            }
+        }
+        app.set_mouse_buttons(MouseButtons {
+            left_held,
+            right_click,
+        });
+        app.update(&mut ctx);
+        if ctx.quitting {
+            break;
+        }
+        app.render(&mut screen);
+        screen.present()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_letters_digits_and_symbols() {
+        assert_eq!(map_key(KeyCode::Char('h')), Some(VirtualKeyCode::H));
+        assert_eq!(map_key(KeyCode::Char('Q')), Some(VirtualKeyCode::Q));
+        assert_eq!(map_key(KeyCode::Char('3')), Some(VirtualKeyCode::Key3));
+        assert_eq!(map_key(KeyCode::Char('[')), Some(VirtualKeyCode::LBracket));
+        assert_eq!(map_key(KeyCode::Enter), Some(VirtualKeyCode::Return));
+        assert_eq!(map_key(KeyCode::F(1)), Some(VirtualKeyCode::F1));
+        assert_eq!(map_key(KeyCode::Char('~')), None);
+    }
+
+    #[test]
+    fn uppercase_sets_shift_and_ctrl_c_quits() {
+        let mut ctx = empty_frame(0.0);
+        assert!(apply_key(&mut ctx, KeyEvent::from(KeyCode::Char('S'))));
+        assert_eq!(ctx.key, Some(VirtualKeyCode::S));
+        assert!(ctx.shift);
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert!(!apply_key(&mut empty_frame(0.0), ctrl_c));
+    }
+}
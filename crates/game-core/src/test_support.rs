@@ -0,0 +1,25 @@
+//! Fixtures shared by the game-core tests.
+
+use super::*;
+use common::MemoryStorage;
+
+/// A game on `seed` at normal difficulty that keeps its files in memory.
+pub(crate) fn memory_game(seed: u64) -> LurhookGame {
+    let storage = Box::new(MemoryStorage::default());
+    LurhookGame::new_with_storage(seed, Difficulty::Normal, None, storage).unwrap()
+}
+
+/// An input frame with `key` pressed.
+pub(crate) fn key_frame(key: VirtualKeyCode) -> BTerm {
+    let mut ctx = empty_frame(0.0);
+    ctx.key = Some(key);
+    ctx
+}
+
+/// An input frame with a left click at `(x, y)`.
+pub(crate) fn click_frame(x: i32, y: i32) -> BTerm {
+    let mut ctx = empty_frame(0.0);
+    ctx.mouse_pos = (x, y);
+    ctx.left_click = true;
+    ctx
+}
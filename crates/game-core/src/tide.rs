@@ -0,0 +1,186 @@
+//! Tides: the sea rises and falls twice a day, with high water in the
+//! middle of dawn and of dusk. The moon moves on a phase each day and sets
+//! how far the tide runs, furthest under a new or full moon. The tide
+//! floods and drains the tidal flats, draws fish in and out with it and
+//! brings on bites at the twilight highs.
+
+use super::*;
+use crate::seasons::DAY_TURNS;
+
+/// Turns from one high tide to the next.
+const TIDE_TURNS: u32 = DAY_TURNS / 2;
+/// Turn of the first high tide, in the middle of dawn.
+const HIGH_WATER: u32 = TIME_SEGMENT_TURNS / 2;
+/// Tidal range in meters of a spring tide, under a new or full moon.
+const SPRING_RANGE: i32 = 3;
+
+/// Phase of the moon; it moves on one phase a day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum MoonPhase {
+    New,
+    WaxingCrescent,
+    FirstQuarter,
+    WaxingGibbous,
+    Full,
+    WaningGibbous,
+    LastQuarter,
+    WaningCrescent,
+}
+
+impl MoonPhase {
+    const ALL: [MoonPhase; 8] = [
+        MoonPhase::New,
+        MoonPhase::WaxingCrescent,
+        MoonPhase::FirstQuarter,
+        MoonPhase::WaxingGibbous,
+        MoonPhase::Full,
+        MoonPhase::WaningGibbous,
+        MoonPhase::LastQuarter,
+        MoonPhase::WaningCrescent,
+    ];
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            MoonPhase::New => "New moon",
+            MoonPhase::WaxingCrescent => "Waxing crescent",
+            MoonPhase::FirstQuarter => "First quarter",
+            MoonPhase::WaxingGibbous => "Waxing gibbous",
+            MoonPhase::Full => "Full moon",
+            MoonPhase::WaningGibbous => "Waning gibbous",
+            MoonPhase::LastQuarter => "Last quarter",
+            MoonPhase::WaningCrescent => "Waning crescent",
+        }
+    }
+
+    /// How far in meters the tide rises above and falls below mean sea
+    /// level: spring tides at new and full moon, neap tides at the
+    /// quarters.
+    fn range(self) -> i32 {
+        match self {
+            MoonPhase::New | MoonPhase::Full => SPRING_RANGE,
+            MoonPhase::FirstQuarter | MoonPhase::LastQuarter => 1,
+            _ => 2,
+        }
+    }
+}
+
+/// The moon on `turn`, starting new.
+pub(crate) fn moon_at(turn: u32) -> MoonPhase {
+    MoonPhase::ALL[(turn / DAY_TURNS) as usize % MoonPhase::ALL.len()]
+}
+
+/// Tide level in meters above mean sea level on `turn`. The first day
+/// starts at mean level, so the map is as generated.
+pub(crate) fn tide_at(turn: u32) -> i32 {
+    let phase = (turn % TIDE_TURNS) as f32 - HIGH_WATER as f32;
+    let angle = phase / TIDE_TURNS as f32 * std::f32::consts::TAU;
+    (moon_at(turn).range() as f32 * angle.cos()).round() as i32
+}
+
+/// Whether the tide is flooding (1), ebbing (-1) or slack at high or low
+/// water (0) on `turn`.
+pub(crate) fn tide_flow(turn: u32) -> i32 {
+    let since_high = (turn + TIDE_TURNS - HIGH_WATER) % TIDE_TURNS;
+    match since_high {
+        0 => 0,
+        t if t == TIDE_TURNS / 2 => 0,
+        t if t < TIDE_TURNS / 2 => -1,
+        _ => 1,
+    }
+}
+
+impl LurhookGame {
+    /// Word for the tide in the status panel.
+    pub(crate) fn tide_name(&self) -> &'static str {
+        match tide_flow(self.turn) {
+            1 => "Flood",
+            -1 => "Ebb",
+            _ if tide_at(self.turn) > 0 => "High",
+            _ => "Low",
+        }
+    }
+
+    /// How far the water stands towards the top of a spring high tide,
+    /// from 0 at or below mean sea level to 1.
+    pub(crate) fn high_water(&self) -> f32 {
+        tide_at(self.turn).max(0) as f32 / SPRING_RANGE as f32
+    }
+
+    /// Floods or drains the tidal flats to this turn's tide level.
+    pub(crate) fn update_tide(&mut self) {
+        if self.map.apply_tide(tide_at(self.turn)) {
+            self.sight.take();
+            self.depth = self.map.depth(self.player.pos);
+        }
+    }
+
+    /// Moves the tide on a turn, announcing the spring tides as each day
+    /// begins under a new or full moon.
+    pub(crate) fn advance_tide(&mut self) {
+        self.update_tide();
+        let moon = moon_at(self.turn);
+        if self.turn.is_multiple_of(DAY_TURNS) && moon.range() == SPRING_RANGE {
+            let msg = format!("{} tonight: the tides run high.", moon.name());
+            self.ui.add_entry(LogCategory::Event, &msg).ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tides_peak_at_dawn_and_dusk() {
+        assert_eq!(tide_at(0), 0);
+        assert_eq!(tide_at(HIGH_WATER), SPRING_RANGE);
+        assert_eq!(tide_at(HIGH_WATER + TIDE_TURNS), SPRING_RANGE);
+        assert_eq!(tide_at(HIGH_WATER + TIDE_TURNS / 2), -SPRING_RANGE);
+        assert_eq!(TIMES[(HIGH_WATER / TIME_SEGMENT_TURNS) as usize], "Dawn");
+        let dusk = (HIGH_WATER + TIDE_TURNS) / TIME_SEGMENT_TURNS;
+        assert_eq!(TIMES[dusk as usize], "Dusk");
+        assert_eq!(tide_flow(1), 1);
+        assert_eq!(tide_flow(HIGH_WATER), 0);
+        assert_eq!(tide_flow(HIGH_WATER + 1), -1);
+    }
+
+    #[test]
+    fn the_moon_sets_the_range() {
+        assert_eq!(moon_at(0), MoonPhase::New);
+        assert_eq!(moon_at(DAY_TURNS * 4), MoonPhase::Full);
+        assert_eq!(moon_at(DAY_TURNS * 8), MoonPhase::New);
+        let neap = DAY_TURNS * 2 + HIGH_WATER;
+        assert_eq!(moon_at(neap), MoonPhase::FirstQuarter);
+        assert_eq!(tide_at(neap), 1);
+    }
+
+    #[test]
+    fn the_tide_floods_the_flats() {
+        let mut game = LurhookGame::default();
+        let water = |g: &LurhookGame| g.map.tiles.iter().filter(|t| t.is_water()).count();
+        let mean = water(&game);
+        game.turn = HIGH_WATER;
+        game.update_tide();
+        assert!(water(&game) > mean);
+        assert_eq!(game.tide_name(), "High");
+        assert_eq!(game.high_water(), 1.0);
+        game.turn = HIGH_WATER + TIDE_TURNS / 2;
+        game.update_tide();
+        assert!(water(&game) < mean);
+        assert_eq!(game.tide_name(), "Low");
+        assert_eq!(game.high_water(), 0.0);
+    }
+
+    #[test]
+    fn spring_tides_are_announced() {
+        let mut game = LurhookGame {
+            turn: DAY_TURNS * 4 - 1,
+            ..Default::default()
+        };
+        game.advance_clock();
+        assert_eq!(
+            game.ui.logs().last().unwrap(),
+            "Full moon tonight: the tides run high."
+        );
+    }
+}
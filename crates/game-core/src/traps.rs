@@ -0,0 +1,223 @@
+//! Traps: crab pots set on a water tile next to the angler fill up on
+//! their own as fish swim in, until the angler comes back to empty them
+//! or a storm smashes them.
+
+use super::*;
+use ecology::TrapSite;
+
+/// Farthest from the player a trap is set, in tiles.
+pub(crate) const TRAP_RANGE: i32 = 1;
+/// Fish a trap holds before nothing more fits.
+const TRAP_CAPACITY: usize = 3;
+/// Chance each stormy turn that a trap is smashed.
+const TRAP_STORM_LOSS: f32 = 0.05;
+
+impl LurhookGame {
+    /// Starts aiming where to set a trap.
+    pub(crate) fn start_trap(&mut self) {
+        self.start_throw(Throw::Trap, "Set the trap where?");
+    }
+
+    /// Sets a trap on the aimed water tile, baiting it with chum if the
+    /// player carries any.
+    pub(crate) fn set_trap(&mut self) {
+        let GameMode::Aiming { target } = self.mode else {
+            return;
+        };
+        self.throwing = None;
+        self.mode = GameMode::Exploring;
+        if !self.map.tiles[self.map.idx(target)].is_water() {
+            self.ui
+                .add_entry(LogCategory::Fishing, "A trap has to go in the water.")
+                .ok();
+            return;
+        }
+        if self.traps.iter().any(|t| t.pos == target) {
+            self.ui
+                .add_entry(LogCategory::Fishing, "There is a trap there already.")
+                .ok();
+            return;
+        }
+        let Some(idx) = self
+            .player
+            .items
+            .iter()
+            .position(|i| i.kind == data::ItemKind::Trap)
+        else {
+            return;
+        };
+        let item = self.player.items.remove(idx);
+        let bait = self
+            .player
+            .items
+            .iter()
+            .position(|i| i.kind == data::ItemKind::Bait);
+        if let Some(bait) = bait {
+            self.player.items.remove(bait);
+        }
+        self.traps.push(Trap {
+            pos: target,
+            baited: bait.is_some(),
+            catches: Vec::new(),
+            left: false,
+            item,
+        });
+        let msg = if bait.is_some() {
+            "You bait the trap with chum and set it."
+        } else {
+            "You set the trap."
+        };
+        self.ui.add_entry(LogCategory::Fishing, msg).ok();
+    }
+
+    /// Lets storms smash traps, empties those the player has come back to
+    /// and lets fish swim into the rest.
+    pub(crate) fn update_traps(&mut self) {
+        if self.weather.weather.is_stormy() {
+            let before = self.traps.len();
+            let rng = &mut self.rng;
+            self.traps
+                .retain(|_| rng.range(0.0, 1.0) >= TRAP_STORM_LOSS);
+            if self.traps.len() < before {
+                self.ui
+                    .add_entry(LogCategory::Fishing, "The storm smashes one of your traps!")
+                    .ok();
+            }
+        }
+        self.empty_traps();
+        for t in 0..self.traps.len() {
+            let site = TrapSite {
+                pos: self.traps[t].pos,
+                depth: self.map.depth(self.traps[t].pos),
+                baited: self.traps[t].baited,
+            };
+            let mut entries = ecology::trap_entries(&self.fishes, &site, &mut self.rng);
+            entries.retain(|&i| self.hooked != Some(i));
+            entries.truncate(TRAP_CAPACITY - self.traps[t].catches.len());
+            // Remove from the back so the other indices stay put
+            entries.sort_unstable_by(|a, b| b.cmp(a));
+            for i in entries {
+                let fish = self.take_fish(i);
+                self.traps[t]
+                    .catches
+                    .push(Catch::new(fish.kind, fish.weight));
+            }
+        }
+    }
+
+    fn empty_traps(&mut self) {
+        let pos = self.player.pos;
+        let near = |trap: &Trap| trap.pos.chebyshev(pos) <= TRAP_RANGE;
+        for trap in &mut self.traps {
+            trap.left |= !near(trap);
+        }
+        let (emptied, kept): (Vec<Trap>, Vec<Trap>) =
+            self.traps.drain(..).partition(|t| t.left && near(t));
+        self.traps = kept;
+        for trap in emptied {
+            let msg = match trap.catches.len() {
+                0 => "You pull up an empty trap.".to_string(),
+                1 => "You pull up your trap with a fish inside!".to_string(),
+                n => format!("You pull up your trap with {} fish inside!", n),
+            };
+            for catch in trap.catches {
+                self.land(catch);
+            }
+            self.player.items.push(trap.item);
+            self.ui.add_entry(LogCategory::Fishing, &msg).ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(id: &str, kind: data::ItemKind) -> data::ItemType {
+        data::ItemType {
+            id: id.into(),
+            name: id.into(),
+            kind,
+            tension_bonus: 0,
+            reel_factor: 1.0,
+            bite_bonus: 0.0,
+            max_cast_range: data::DEFAULT_CAST_RANGE,
+        }
+    }
+
+    /// A game on open shallows where the player carries a trap and chum and
+    /// has just set the trap east of them, with hungry fish around it.
+    fn trapping() -> LurhookGame {
+        let mut game = LurhookGame::default();
+        game.map.tiles.fill(TileKind::ShallowWater);
+        game.weather = WeatherState::new(Weather::Clear, 50);
+        game.player.items = vec![
+            item("CRAB_POT", data::ItemKind::Trap),
+            item("CHUM", data::ItemKind::Bait),
+        ];
+        game.inventory_cursor = 0;
+        game.activate_selected_item();
+        game.aim_at(game.player.pos + common::Point::new(3, 0));
+        game.set_trap();
+        let kind = game.fishes[0].kind.clone();
+        let trap = game.traps[0].pos;
+        game.fishes = [(0, 1), (1, 0), (1, 1), (0, -1), (5, 5)]
+            .iter()
+            .map(|&(dx, dy)| Fish {
+                hunger: ecology::MAX_FISH_HUNGER,
+                ..Fish::new(kind.clone(), trap + common::Point::new(dx, dy))
+            })
+            .collect();
+        game
+    }
+
+    #[test]
+    fn traps_fill_up_and_are_emptied_on_return() {
+        let mut game = trapping();
+        let start = game.player.pos;
+        assert_eq!(game.traps[0].pos, start + common::Point::new(1, 0));
+        assert!(game.traps[0].baited);
+        assert!(game.player.items.is_empty(), "the trap and chum go in");
+
+        for _ in 0..300 {
+            game.update_traps();
+        }
+        assert_eq!(game.traps[0].catches.len(), TRAP_CAPACITY);
+        assert_eq!(game.fishes.len(), 2);
+        assert!(game.player.inventory.is_empty());
+
+        game.player.pos = start + common::Point::new(-3, 0);
+        game.update_traps();
+        game.player.pos = start;
+        game.update_traps();
+        assert!(game.traps.is_empty());
+        assert_eq!(game.player.inventory.len(), TRAP_CAPACITY);
+        assert_eq!(game.player.items[0].kind, data::ItemKind::Trap);
+    }
+
+    #[test]
+    fn storms_smash_traps() {
+        let mut game = trapping();
+        game.weather = WeatherState::new(Weather::Storm, 200);
+        for _ in 0..200 {
+            game.update_traps();
+        }
+        assert!(game.traps.is_empty());
+        assert!(game
+            .ui
+            .logs()
+            .iter()
+            .any(|l| l == "The storm smashes one of your traps!"));
+    }
+
+    #[test]
+    fn traps_need_water() {
+        let mut game = LurhookGame::default();
+        game.map.tiles.fill(TileKind::Land);
+        game.player.items = vec![item("CRAB_POT", data::ItemKind::Trap)];
+        game.start_trap();
+        game.set_trap();
+        assert!(game.traps.is_empty());
+        assert_eq!(game.player.items.len(), 1);
+    }
+}
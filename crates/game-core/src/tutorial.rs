@@ -0,0 +1,246 @@
+//! Tutorial: a run on a small fixed stretch of coast where prompts walk a
+//! new player through moving, casting, the fight, eating and cooking.
+//! Each step watches the game events, or where the player has got to,
+//! and gives way to the next once its lesson is done. Escape leaves the
+//! tutorial at any time.
+
+use super::*;
+use crate::cooking::CAMPFIRE_WOOD;
+use crate::input::key_name;
+
+/// Species swimming in the tutorial's shallows.
+const TUTORIAL_FISH: &str = "TROUT";
+/// Where the tutorial's fish start, within a cast of the shore.
+const FISH_SPOTS: [(i32, i32); 4] = [(17, 6), (18, 8), (17, 10), (19, 7)];
+
+/// Lesson the tutorial is on, in the order they are taught.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum TutorialStep {
+    Move,
+    Cast,
+    Strike,
+    Fight,
+    Eat,
+    Cook,
+    Done,
+}
+
+/// Progress through the tutorial.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Tutorial {
+    pub step: TutorialStep,
+    /// Where the player started; walking away from it ends the first step.
+    start: common::Point,
+}
+
+impl Tutorial {
+    pub(crate) fn new(start: common::Point) -> Self {
+        Self {
+            step: TutorialStep::Move,
+            start,
+        }
+    }
+
+    /// Moves on once the player has left the start, and back to casting
+    /// when a fish on the line or at the bait got away.
+    fn observe(&mut self, pos: common::Point, mode: GameMode) {
+        use TutorialStep::*;
+        self.step = match (self.step, mode) {
+            (Move, _) if pos != self.start => Cast,
+            (Strike | Fight, GameMode::Exploring) => Cast,
+            (step, _) => step,
+        };
+    }
+
+    /// What the player is asked to do, naming their keys from `input`.
+    fn prompt(&self, input: &InputConfig) -> String {
+        let key = |k| key_name(k).unwrap_or("?");
+        match self.step {
+            TutorialStep::Move => format!(
+                "Welcome! Walk to the water's edge with the arrow keys or {} {} {} {}.",
+                key(input.left),
+                key(input.down),
+                key(input.up),
+                key(input.right)
+            ),
+            TutorialStep::Cast => format!(
+                "Press {} to cast, move the target onto the fish and press {} again to throw.",
+                key(input.cast),
+                key(input.cast)
+            ),
+            TutorialStep::Strike => format!(
+                "Something is biting! Press {} now to set the hook.",
+                key(input.cast)
+            ),
+            TutorialStep::Fight => format!(
+                "Reel with {} while the fish thrashes or tires; let it run or dive. Keep the tension out of the red.",
+                key(input.reel)
+            ),
+            TutorialStep::Eat => format!(
+                "Landed! Open your bag with {} and press {} to eat the fish.",
+                key(input.inventory),
+                key(input.eat)
+            ),
+            TutorialStep::Cook => format!(
+                "Cooked fish feed you better. Catch another, build a campfire ashore with {} and cook it: {}, then {}.",
+                key(input.campfire),
+                key(input.inventory),
+                key(input.cook)
+            ),
+            TutorialStep::Done => format!(
+                "That's the basics! Press {} to finish the tutorial.",
+                key(input.end_run)
+            ),
+        }
+    }
+}
+
+impl EventListener for Tutorial {
+    fn on_event(&mut self, event: &GameEvent) {
+        use TutorialStep::*;
+        let step = self.step;
+        self.step = match event {
+            GameEvent::FishBite if step <= Cast => Strike,
+            GameEvent::FishHooked if step <= Strike => Fight,
+            GameEvent::FishCaught { .. } if step <= Fight => Eat,
+            GameEvent::FishEaten if step == Eat => Cook,
+            GameEvent::DishCooked if step <= Cook => Done,
+            _ => step,
+        };
+    }
+}
+
+impl LurhookGame {
+    /// Starts the tutorial: an easy run on the tutorial map with a few
+    /// trout in the shallows, driftwood for a campfire and an appetite.
+    pub fn tutorial() -> GameResult<Self> {
+        let mut game = Self::new_with_difficulty(0, Difficulty::Easy)?;
+        let trout = game
+            .fish_types
+            .iter()
+            .find(|f| f.id == TUTORIAL_FISH)
+            .cloned()
+            .ok_or_else(|| GameError::Parse(format!("unknown fish {}", TUTORIAL_FISH)))?;
+        game.map = mapgen::tutorial::map();
+        game.features = Default::default();
+        game.place = None;
+        game.sight.take();
+        game.merchant = None;
+        game.populations.insert(
+            game.area.id.clone(),
+            ecology::population::Population::new(&game.map),
+        );
+        game.fishes = FISH_SPOTS
+            .iter()
+            .map(|&(x, y)| Fish::new(trout.clone(), common::Point::new(x, y)))
+            .collect();
+        let start = mapgen::tutorial::start();
+        game.player.aboard = false;
+        game.player.boat.pos = start;
+        game.place_player(start);
+        game.player.driftwood = CAMPFIRE_WOOD;
+        game.player.hunger = MAX_HUNGER / 2;
+        game.explore();
+        game.tutorial = Some(Tutorial::new(start));
+        game.ui.set_layout(UILayout::Standard);
+        Ok(game)
+    }
+
+    /// Whether this run is the tutorial.
+    pub(crate) fn in_tutorial(&self) -> bool {
+        self.tutorial.is_some()
+    }
+
+    /// Moves the tutorial on from where the player has got to.
+    pub(crate) fn update_tutorial(&mut self) {
+        let (pos, mode) = (self.player.pos, self.mode);
+        if let Some(tutorial) = &mut self.tutorial {
+            tutorial.observe(pos, mode);
+        }
+    }
+
+    /// Leaves the tutorial, ending its run.
+    pub(crate) fn skip_tutorial(&mut self) {
+        self.ui.add_log("Tutorial skipped.").ok();
+        self.end_run();
+    }
+
+    /// Draws the current tutorial prompt over the bottom of the map.
+    pub(super) fn draw_tutorial(&self, ctx: &mut dyn Renderer) {
+        if let Some(tutorial) = &self.tutorial {
+            self.ui
+                .draw_tutorial(ctx, &tutorial.prompt(&self.input))
+                .ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::key_frame;
+
+    #[test]
+    fn steps_follow_the_player_through_a_catch_and_a_meal() {
+        let mut game = LurhookGame::tutorial().unwrap();
+        let start = game.player.pos;
+        assert_eq!(game.map.tiles[game.map.idx(start)], TileKind::Land);
+        assert_eq!(game.fishes.len(), FISH_SPOTS.len());
+        let step = |game: &LurhookGame| game.tutorial.as_ref().unwrap().step;
+        assert_eq!(step(&game), TutorialStep::Move);
+        game.update_frame(&mut key_frame(VirtualKeyCode::Right), false);
+        assert_eq!(step(&game), TutorialStep::Cast);
+
+        game.events.push(GameEvent::FishBite);
+        game.dispatch_events();
+        assert_eq!(step(&game), TutorialStep::Strike);
+        game.update_tutorial();
+        assert_eq!(step(&game), TutorialStep::Cast, "the fish got away");
+
+        game.events.push(GameEvent::FishBite);
+        game.events.push(GameEvent::FishHooked);
+        game.dispatch_events();
+        assert_eq!(step(&game), TutorialStep::Fight);
+        let trout = game.fishes[0].kind.clone();
+        game.player.inventory.push(Catch::average(trout.clone()));
+        game.events.push(GameEvent::FishCaught {
+            id: trout.id.clone(),
+            name: trout.name.clone(),
+            weight: 1.0,
+            description: String::new(),
+            habitat_note: String::new(),
+            location: String::new(),
+            turn: 0,
+            timestamp: None,
+        });
+        game.dispatch_events();
+        assert_eq!(step(&game), TutorialStep::Eat);
+
+        game.eat_fish();
+        game.dispatch_events();
+        assert_eq!(step(&game), TutorialStep::Cook);
+        game.map.tiles.fill(TileKind::Land);
+        game.player.inventory.push(Catch::average(trout));
+        game.build_campfire();
+        game.cook_fish();
+        game.dispatch_events();
+        assert_eq!(step(&game), TutorialStep::Done);
+        assert!(game
+            .tutorial
+            .as_ref()
+            .unwrap()
+            .prompt(&game.input)
+            .contains("Return"));
+    }
+
+    #[test]
+    fn escape_skips_the_tutorial() {
+        let mut game = LurhookGame::tutorial().unwrap();
+        let mut buf = TextBuffer::new(SCREEN_WIDTH, SCREEN_HEIGHT);
+        game.render(&mut buf);
+        assert!(buf.contains("Tutorial"));
+        assert!(buf.contains("Walk to the water's edge"));
+        game.update_frame(&mut key_frame(VirtualKeyCode::Escape), false);
+        assert!(matches!(game.mode, GameMode::End { .. }));
+    }
+}
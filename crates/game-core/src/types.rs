@@ -9,6 +9,8 @@ pub struct Player {
     pub hp: i32,
     /// Current hunger level (0-100). 0 means starving.
     pub hunger: i32,
+    /// Current thirst level (0-100). 0 means parched.
+    pub thirst: i32,
     /// Strength of the fishing line.
     pub line: i32,
     /// Bonus applied to bite probability from equipped bait/lure.
@@ -17,18 +19,177 @@ pub struct Player {
     pub tension_bonus: i32,
     /// Multiplier applied when reeling in line tension.
     pub reel_factor: f32,
+    /// Farthest a cast reaches in tiles, set by the equipped reel.
+    pub cast_range: i32,
     /// Number of canned food items carried.
     pub canned_food: i32,
-    /// Collected fish kinds.
-    pub inventory: Vec<FishType>,
+    /// Pieces of driftwood gathered for campfires.
+    pub driftwood: i32,
+    /// Boost from the last dish eaten, counting down its turns.
+    pub buff: Option<data::Buff>,
+    /// Temporary conditions such as poison, counting down their turns.
+    pub effects: Vec<crate::StatusEffect>,
+    /// Coins earned selling fish to the merchant.
+    pub coins: i32,
+    /// Fish landed this run.
+    pub inventory: Vec<Catch>,
     /// Gear and consumable items held.
     pub items: Vec<data::ItemType>,
     /// Equipped fishing rod.
     pub rod: Option<data::ItemType>,
     /// Equipped reel.
     pub reel: Option<data::ItemType>,
+    /// Condition of the equipped rod, down to 0 when it is worn out.
+    pub rod_durability: i32,
+    /// Condition of the equipped reel, down to 0 when it is worn out.
+    pub reel_durability: i32,
     /// Equipped lure/bait.
     pub lure: Option<data::ItemType>,
+    /// Lure depth and retrieve chosen for casts.
+    pub presentation: fishing::Presentation,
+    /// Equipped fishfinder; reveals fish in deep water.
+    pub sonar: Option<data::ItemType>,
+    /// The player's own boat, needed to cross deep water.
+    pub boat: Boat,
+    /// Whether the player is in the boat; it then moves with them.
+    pub aboard: bool,
+}
+
+impl Player {
+    /// Tension bonus the rod lends the line; a worn-out rod lends none.
+    pub fn rod_tension_bonus(&self) -> i32 {
+        if self.rod_durability > 0 {
+            self.tension_bonus
+        } else {
+            0
+        }
+    }
+
+    /// Reeling strength of the reel; a worn-out reel reels at half. A
+    /// meal boost and adrenaline add on top.
+    pub fn reel_strength(&self) -> f32 {
+        let reel = if self.reel_durability > 0 {
+            self.reel_factor
+        } else {
+            self.reel_factor / 2.0
+        };
+        let adrenaline = if self.has_effect(crate::StatusKind::Adrenaline) {
+            crate::effects::ADRENALINE_REEL
+        } else {
+            0.0
+        };
+        reel + self.meal_bonus(data::BuffKind::Reel) + adrenaline
+    }
+
+    /// Whether the status effect `kind` is on the player.
+    pub fn has_effect(&self, kind: crate::StatusKind) -> bool {
+        self.effects.iter().any(|e| e.kind == kind)
+    }
+
+    /// What the current meal boost adds of `kind`.
+    pub fn meal_bonus(&self, kind: data::BuffKind) -> f32 {
+        self.buff
+            .filter(|b| b.kind == kind)
+            .map_or(0.0, |b| b.amount)
+    }
+}
+
+/// Small boat that carries the player over deep water.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Boat {
+    pub pos: Point,
+    /// Remaining hull points; hazards wear these down while aboard.
+    pub durability: i32,
+    pub max_durability: i32,
+    /// Tiles covered per move while aboard.
+    pub speed: i32,
+    /// An anchored boat holds still and steadies the line.
+    pub anchored: bool,
+}
+
+impl Boat {
+    pub fn new(pos: Point, durability: i32, speed: i32) -> Self {
+        Self {
+            pos,
+            durability,
+            max_durability: durability,
+            speed,
+            anchored: false,
+        }
+    }
+
+    /// A wrecked hull no longer shields the player.
+    pub fn is_wrecked(&self) -> bool {
+        self.durability <= 0
+    }
+
+    /// Tiles the boat moves per step; a wreck only limps along.
+    pub fn moves(&self) -> i32 {
+        if self.is_wrecked() {
+            1
+        } else {
+            self.speed
+        }
+    }
+}
+
+/// Turns a raw fish keeps before it spoils.
+pub const RAW_SHELF_LIFE: u32 = 120;
+/// Turns a cooked fish keeps before it spoils.
+pub const COOKED_SHELF_LIFE: u32 = 400;
+
+/// A landed fish and how heavy it was.
+#[derive(Debug, Clone)]
+pub struct Catch {
+    pub kind: FishType,
+    /// Weight in kilograms.
+    pub weight: f32,
+    /// Turn the fish was landed, or cooked once it has been.
+    pub stamp: u32,
+    pub cooked: bool,
+}
+
+impl Catch {
+    pub fn new(kind: FishType, weight: f32) -> Self {
+        Self {
+            kind,
+            weight,
+            stamp: 0,
+            cooked: false,
+        }
+    }
+
+    /// Turns left on `turn` before the fish spoils; zero once it has.
+    pub fn keeps_for(&self, turn: u32) -> u32 {
+        let life = if self.cooked {
+            COOKED_SHELF_LIFE
+        } else {
+            RAW_SHELF_LIFE
+        };
+        (self.stamp + life).saturating_sub(turn)
+    }
+
+    pub fn is_spoiled(&self, turn: u32) -> bool {
+        self.keeps_for(turn) == 0
+    }
+
+    /// A catch of typical size for its species.
+    pub fn average(kind: FishType) -> Self {
+        let weight = kind.average_weight();
+        Self::new(kind, weight)
+    }
+
+    /// Points for this catch: rarer species score more, scaled by how
+    /// heavy the fish was for its species. Bosses add their bonus on top.
+    pub fn score(&self) -> i32 {
+        let bonus = self.kind.boss.as_ref().map_or(0, |b| b.bonus);
+        ((1.0 / self.kind.rarity) * 10.0 * self.kind.size_ratio(self.weight)) as i32 + bonus
+    }
+
+    /// Coins the merchant pays for this fish: half its score.
+    pub fn price(&self) -> i32 {
+        (self.score() / 2).max(1)
+    }
 }
 
 /// Temporary hazard entity that damages the player on contact.
@@ -36,30 +197,65 @@ pub struct Player {
 pub struct Hazard {
     pub pos: Point,
     pub turns: u8,
+    pub kind: crate::HazardKind,
 }
 
-/// Progression area stage.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum Area {
-    Coast,
-    Offshore,
-    DeepSea,
+/// A campfire on land; fish are cooked next to it until it burns down.
+#[derive(Debug, Clone)]
+pub struct Campfire {
+    pub pos: Point,
+    pub turns: u8,
 }
 
-impl Area {
-    pub fn size(self) -> (u32, u32) {
-        match self {
-            Area::Coast => (80, 50),
-            Area::Offshore => (120, 80),
-            Area::DeepSea => (160, 120),
-        }
+/// Chum in the water, drawing fish to its tile until it thins out.
+#[derive(Debug, Clone)]
+pub struct Chum {
+    pub pos: Point,
+    pub turns: u8,
+}
+
+/// A longline set across the water; fish taking its hooks stay on them
+/// until the angler comes back to haul it in.
+#[derive(Debug, Clone)]
+pub struct Longline {
+    /// Where it was set from; stepping back onto it hauls the line in.
+    pub anchor: Point,
+    /// Water tiles the hooks hang over.
+    pub tiles: Vec<Point>,
+    /// Fish on the hooks.
+    pub catches: Vec<Catch>,
+    /// Whether the angler has stepped away since setting it.
+    pub left: bool,
+    /// The longline item, given back when it is hauled in.
+    pub item: data::ItemType,
+}
+
+/// A crab pot or fish trap left in the water, holding what swims in
+/// until the angler comes back for it.
+#[derive(Debug, Clone)]
+pub struct Trap {
+    pub pos: Point,
+    /// Whether it was baited with chum when set.
+    pub baited: bool,
+    /// Fish inside.
+    pub catches: Vec<Catch>,
+    /// Whether the angler has stepped away since setting it.
+    pub left: bool,
+    /// The trap item, given back when it is emptied.
+    pub item: data::ItemType,
+}
+
+impl Longline {
+    /// Whether a fish on `pt` swims among the hooks.
+    pub fn covers(&self, pt: Point) -> bool {
+        self.tiles.contains(&pt)
     }
+}
 
-    pub fn hazard_multiplier(self) -> i32 {
-        match self {
-            Area::Coast => 1,
-            Area::Offshore => 2,
-            Area::DeepSea => 3,
-        }
+impl Chum {
+    /// Whether a cast landing on `pt` lands in the chum, which clouds its
+    /// tile and the neighbouring ones.
+    pub fn covers(&self, pt: Point) -> bool {
+        self.pos.chebyshev(pt) <= 1
     }
 }
@@ -1,42 +1,256 @@
 use super::*;
 
 impl LurhookGame {
+    /// Collects the stats and condition icons shown in the status panel.
+    pub(super) fn status_view(&self) -> StatusView<'static> {
+        let mut icons = Vec::new();
+        if self.weather.weather.is_stormy() {
+            icons.push(StatusIcon {
+                glyph: '≈',
+                color: RGB::named(CYAN),
+            });
+        }
+        if self.player.hunger == 0 {
+            icons.push(StatusIcon {
+                glyph: '!',
+                color: RGB::named(RED),
+            });
+        }
+        if self.player.buff.is_some() {
+            // A meal boost is running
+            icons.push(StatusIcon {
+                glyph: '+',
+                color: RGB::named(GREEN),
+            });
+        }
+        if self.player.thirst == 0 {
+            icons.push(StatusIcon {
+                glyph: '!',
+                color: RGB::named(BLUE),
+            });
+        }
+        for effect in &self.player.effects {
+            let (glyph, color) = match effect.kind {
+                StatusKind::Poisoned => ('!', MAGENTA),
+                StatusKind::WellFed => ('♥', GREEN),
+                StatusKind::Soaked => ('~', CYAN),
+                StatusKind::Adrenaline => ('↑', YELLOW),
+            };
+            icons.push(StatusIcon {
+                glyph,
+                color: RGB::named(color),
+            });
+        }
+        let (rod, reel) = self.gear_condition();
+        if rod.into_iter().chain(reel).any(|d| d == 0) {
+            // Worn-out gear until it is repaired
+            icons.push(StatusIcon {
+                glyph: '/',
+                color: RGB::named(ORANGE),
+            });
+        }
+        StatusView {
+            hp: self.player.hp,
+            max_hp: MAX_HP,
+            line: self.player.line,
+            max_line: MAX_LINE,
+            hunger: self.player.hunger,
+            max_hunger: MAX_HUNGER,
+            thirst: self.player.thirst,
+            max_thirst: MAX_THIRST,
+            depth: self.depth,
+            time: self.time_of_day,
+            season: self.season().name(),
+            tide: self.tide_name(),
+            weather: self.weather.weather.name(),
+            coins: self.player.coins,
+            boat: self.player.aboard.then_some(BoatView {
+                durability: self.player.boat.durability,
+                max_durability: self.player.boat.max_durability,
+                anchored: self.player.boat.anchored,
+            }),
+            icons,
+            effects: self
+                .player
+                .effects
+                .iter()
+                .map(|e| (e.kind.name(), e.turns))
+                .collect(),
+            sonar: self.sonar_estimate(),
+            lure: self.presentation_view(),
+            standings: self.standings(),
+            seed: self.seed,
+        }
+    }
+
+    /// Describes the ongoing fight; species names are revealed once in the codex.
+    pub(super) fn fight_view(&self) -> Option<FightView<'_>> {
+        let meter = self.meter.as_ref()?;
+        let (rod, reel) = self.gear_condition();
+        let fish = self.hooked_fish();
+        let name = fish
+            .filter(|f| self.codex.count(&f.kind.id) > 0)
+            .map(|f| f.kind.name.as_str());
+        Some(FightView {
+            name,
+            strength: meter.strength,
+            tension: meter.tension,
+            max_tension: meter.max_tension,
+            trend: meter.trend(),
+            slack: meter.is_slack(),
+            near_breaking: meter.near_breaking(),
+            line: self.player.line,
+            max_line: MAX_LINE,
+            turns_left: meter.duration,
+            stage: meter.stage,
+            stages: meter.stages,
+            phase: meter.phase_text(),
+            hint: meter.phase.hint(),
+            stamina: meter.stamina,
+            max_stamina: fishing::MAX_STAMINA,
+            rod,
+            reel,
+            threat: self.predator_distance(),
+        })
+    }
+
+    /// Rows for the codex screen, one per species in data file order.
+    pub(super) fn codex_views(&self) -> Vec<CodexView<'_>> {
+        self.fish_types
+            .iter()
+            .map(|fish| CodexView {
+                fish,
+                count: self.codex.count(&fish.id),
+            })
+            .collect()
+    }
+
     pub(super) fn tile_style(&self, tile: TileKind, visible: bool) -> (char, RGB) {
         let (glyph, color) = match tile {
             TileKind::Land => ('.', self.palette.land),
             TileKind::ShallowWater => ('~', self.palette.shallow),
             TileKind::DeepWater => ('≈', self.palette.deep),
+            TileKind::CoralReef => ('♣', self.palette.coral),
+            TileKind::KelpForest => ('"', self.palette.kelp),
+            TileKind::Sandbar => ('░', self.palette.sand),
+            TileKind::Ice => ('▒', self.palette.ice),
+        };
+        let color = if self.input.colorblind {
+            color
+        } else {
+            ui_crate::season_tint(color, self.season())
         };
         let color = if visible { color } else { color * 0.4 };
         (glyph, color)
     }
 
-    pub(super) fn draw_map(&self, ctx: &mut BTerm) {
-        let (cam_x, cam_y) = self.camera();
-        for y in 0..VIEW_HEIGHT {
-            for x in 0..VIEW_WIDTH {
-                let mx = cam_x + x;
-                let my = cam_y + y;
-                let pt = common::Point::new(mx, my);
-                let idx = self.map.idx(pt);
-                let tile = self.map.tiles[idx];
-                let visible = self.is_visible(pt);
-                let (glyph, color) = self.tile_style(tile, visible);
-                ctx.set(x, y, color, RGB::named(BLACK), to_cp437(glyph));
-            }
+    /// How the map looks at `pt`: blank where the player has never looked,
+    /// dimmed where they remember it but cannot see it now, and arrows over
+    /// strong currents.
+    pub(super) fn map_cell(&self, pt: common::Point) -> (char, RGB) {
+        let visible = self.is_visible(pt);
+        if !visible && !self.map.is_explored(pt) {
+            return (' ', RGB::named(BLACK));
+        }
+        let (glyph, color) = self.tile_style(self.map.tiles[self.map.idx(pt)], visible);
+        if self.map.strong_flow(pt) {
+            (flow_glyph(self.map.flow(pt)), color)
+        } else {
+            (glyph, color)
+        }
+    }
+
+    fn in_deep_water(&self, fish: &Fish) -> bool {
+        self.map.tiles[self.map.idx(fish.position)] == TileKind::DeepWater
+    }
+
+    /// Whether the player can make out `fish`: it is in sight, and in the
+    /// shallows unless a fishfinder shows the deep.
+    pub(super) fn spotted(&self, fish: &Fish) -> bool {
+        self.is_visible(fish.position) && (self.player.sonar.is_some() || !self.in_deep_water(fish))
+    }
+
+    /// The whole map as the minimap shows it: explored tiles, the player and
+    /// the fish they can make out.
+    pub(super) fn minimap_view(&self) -> MinimapView {
+        let tiles = self
+            .map
+            .tiles
+            .iter()
+            .zip(&self.map.explored)
+            .map(|(&tile, &explored)| match tile {
+                _ if !explored => MinimapTile::Unexplored,
+                TileKind::DeepWater => MinimapTile::DeepWater,
+                t if t.is_water() => MinimapTile::Water,
+                _ => MinimapTile::Land,
+            })
+            .collect();
+        MinimapView {
+            width: self.map.width as i32,
+            height: self.map.height as i32,
+            tiles,
+            player: self.player.pos,
+            schools: self
+                .fishes
+                .iter()
+                .filter(|f| self.spotted(f))
+                .map(|f| f.position)
+                .collect(),
+            place: self.place_name().map(String::from),
+            palette: self.palette,
+        }
+    }
+
+    /// Map, trail, chum, longlines, traps, campfires, fish, hazards and anglers in the view panel.
+    pub(super) fn draw_scene(&self, ctx: &mut dyn Renderer) {
+        self.draw_map(ctx);
+        self.draw_trail(ctx);
+        self.draw_chum(ctx);
+        self.draw_longlines(ctx);
+        self.draw_traps(ctx);
+        self.draw_campfires(ctx);
+        self.draw_fish(ctx);
+        self.draw_bobber(ctx);
+        self.draw_hazards(ctx);
+        self.draw_boat(ctx);
+        self.draw_merchant(ctx);
+        self.draw_companion(ctx);
+        self.draw_rivals(ctx);
+        let screen = self.camera().to_local(self.player.pos);
+        ctx.set(
+            screen.x,
+            screen.y,
+            self.palette.player,
+            RGB::named(BLACK),
+            '@',
+        );
+    }
+
+    pub(super) fn draw_map(&self, ctx: &mut dyn Renderer) {
+        let view = self.camera();
+        for pt in view.points() {
+            let screen = view.to_local(pt);
+            let (glyph, color) = self.map_cell(pt);
+            ctx.set(screen.x, screen.y, color, RGB::named(BLACK), glyph);
         }
         if let GameMode::Aiming { target } = self.mode {
-            if target.x >= cam_x
-                && target.x < cam_x + VIEW_WIDTH
-                && target.y >= cam_y
-                && target.y < cam_y + VIEW_HEIGHT
-            {
+            // Shade the edge of the cast range behind the tiles on it
+            let range = self.aim_range();
+            for pt in view.points() {
+                if pt.chebyshev(self.player.pos) == range {
+                    let screen = view.to_local(pt);
+                    let (glyph, color) = self.map_cell(pt);
+                    ctx.set(screen.x, screen.y, color, RGB::named(GRAY) * 0.4, glyph);
+                }
+            }
+            if view.contains(target) {
+                let screen = view.to_local(target);
                 ctx.set(
-                    target.x - cam_x,
-                    target.y - cam_y,
+                    screen.x,
+                    screen.y,
                     RGB::named(WHITE),
                     RGB::named(BLACK),
-                    to_cp437('*'),
+                    '*',
                 );
             }
         }
@@ -45,57 +259,269 @@ impl LurhookGame {
                 if i >= self.cast_step {
                     break;
                 }
-                if pt.x >= cam_x && pt.x < cam_x + VIEW_WIDTH && pt.y >= cam_y && pt.y < cam_y + VIEW_HEIGHT {
+                if view.contains(*pt) {
                     let glyph = if i == path.len() - 1 { 'o' } else { '*' };
+                    let screen = view.to_local(*pt);
                     ctx.set(
-                        pt.x - cam_x,
-                        pt.y - cam_y,
+                        screen.x,
+                        screen.y,
                         RGB::named(WHITE),
                         RGB::named(BLACK),
-                        to_cp437(glyph),
+                        glyph,
                     );
                 }
             }
         }
     }
 
-    pub(super) fn draw_fish(&self, ctx: &mut BTerm) {
-        let (cam_x, cam_y) = self.camera();
-        for fish in &self.fishes {
-            if fish.position.x >= cam_x
-                && fish.position.x < cam_x + VIEW_WIDTH
-                && fish.position.y >= cam_y
-                && fish.position.y < cam_y + VIEW_HEIGHT
-                && self.is_visible(fish.position)
-            {
+    /// Draws the bobber once the cast has landed, with a `!` above it
+    /// while a fish holds the bait.
+    pub(super) fn draw_bobber(&self, ctx: &mut dyn Renderer) {
+        let GameMode::Fishing { bite, .. } = self.mode else {
+            return;
+        };
+        let (Some(bait), None) = (self.bait, &self.cast_path) else {
+            return;
+        };
+        let view = self.camera();
+        if view.contains(bait) {
+            let screen = view.to_local(bait);
+            ctx.set(
+                screen.x,
+                screen.y,
+                RGB::named(WHITE),
+                RGB::named(BLACK),
+                'o',
+            );
+        }
+        let above = bait + common::Point::new(0, -1);
+        if bite.is_some() && view.contains(above) {
+            let screen = view.to_local(above);
+            ctx.set(
+                screen.x,
+                screen.y,
+                RGB::named(YELLOW),
+                RGB::named(BLACK),
+                '!',
+            );
+        }
+    }
+
+    /// Marks chummed tiles; fish drawn later stay on top.
+    pub(super) fn draw_chum(&self, ctx: &mut dyn Renderer) {
+        let view = self.camera();
+        for c in &self.chum {
+            if view.contains(c.pos) && self.is_visible(c.pos) {
+                let screen = view.to_local(c.pos);
                 ctx.set(
-                    fish.position.x - cam_x,
-                    fish.position.y - cam_y,
-                    self.palette.fish,
+                    screen.x,
+                    screen.y,
+                    RGB::named(YELLOW),
                     RGB::named(BLACK),
-                    to_cp437('f'),
+                    '%',
                 );
             }
         }
     }
 
-    pub(super) fn draw_hazards(&self, ctx: &mut BTerm) {
-        let (cam_x, cam_y) = self.camera();
-        for h in &self.hazards {
-            if h.pos.x >= cam_x
-                && h.pos.x < cam_x + VIEW_WIDTH
-                && h.pos.y >= cam_y
-                && h.pos.y < cam_y + VIEW_HEIGHT
-                && self.is_visible(h.pos)
-            {
+    /// Marks the water under set longlines, brighter once a fish is on.
+    pub(super) fn draw_longlines(&self, ctx: &mut dyn Renderer) {
+        let view = self.camera();
+        for line in &self.longlines {
+            let color = if line.catches.is_empty() {
+                RGB::named(GRAY)
+            } else {
+                RGB::named(WHITE)
+            };
+            for &pt in line.tiles.iter().filter(|&&pt| view.contains(pt)) {
+                if self.is_visible(pt) {
+                    let screen = view.to_local(pt);
+                    ctx.set(screen.x, screen.y, color, RGB::named(BLACK), '-');
+                }
+            }
+        }
+    }
+
+    /// Marks set traps, brighter once a fish is inside.
+    pub(super) fn draw_traps(&self, ctx: &mut dyn Renderer) {
+        let view = self.camera();
+        for trap in &self.traps {
+            if view.contains(trap.pos) && self.is_visible(trap.pos) {
+                let color = if trap.catches.is_empty() {
+                    RGB::named(ORANGE) * 0.7
+                } else {
+                    RGB::named(ORANGE)
+                };
+                let screen = view.to_local(trap.pos);
+                ctx.set(screen.x, screen.y, color, RGB::named(BLACK), '⌂');
+            }
+        }
+    }
+
+    /// Marks burning campfires.
+    pub(super) fn draw_campfires(&self, ctx: &mut dyn Renderer) {
+        let view = self.camera();
+        for fire in &self.campfires {
+            if view.contains(fire.pos) && self.is_visible(fire.pos) {
+                let screen = view.to_local(fire.pos);
                 ctx.set(
-                    h.pos.x - cam_x,
-                    h.pos.y - cam_y,
-                    self.palette.hazard,
+                    screen.x,
+                    screen.y,
+                    RGB::named(ORANGE),
                     RGB::named(BLACK),
-                    to_cp437('!'),
+                    '*',
                 );
             }
         }
     }
+
+    /// Draws the visible fish. Fish in deep water only show up on sonar,
+    /// which also sets them apart from those in the shallows.
+    pub(super) fn draw_fish(&self, ctx: &mut dyn Renderer) {
+        let view = self.camera();
+        for fish in &self.fishes {
+            if !view.contains(fish.position) || !self.spotted(fish) {
+                continue;
+            }
+            let (glyph, color) = self.fish_style(fish);
+            let screen = view.to_local(fish.position);
+            ctx.set(screen.x, screen.y, color, RGB::named(BLACK), glyph);
+        }
+    }
+
+    pub(super) fn fish_style(&self, fish: &Fish) -> (char, RGB) {
+        if self.in_deep_water(fish) {
+            ('ƒ', self.palette.deep_fish)
+        } else {
+            ('f', self.palette.fish)
+        }
+    }
+
+    /// Sonar's reading of the heaviest fish next to the player, to the
+    /// nearest half kilogram.
+    pub(super) fn sonar_estimate(&self) -> Option<f32> {
+        self.player.sonar.as_ref()?;
+        self.fishes
+            .iter()
+            .filter(|f| f.position.chebyshev(self.player.pos) <= 1)
+            .map(|f| (f.weight * 2.0).round() / 2.0)
+            .reduce(f32::max)
+    }
+
+    /// Draws the boat where it is moored; aboard, the player covers it.
+    pub(super) fn draw_boat(&self, ctx: &mut dyn Renderer) {
+        let view = self.camera();
+        let pos = self.player.boat.pos;
+        if self.player.aboard || !view.contains(pos) || !self.is_visible(pos) {
+            return;
+        }
+        let screen = view.to_local(pos);
+        ctx.set(
+            screen.x,
+            screen.y,
+            RGB::named(ORANGE),
+            RGB::named(BLACK),
+            'B',
+        );
+    }
+
+    pub(super) fn draw_companion(&self, ctx: &mut dyn Renderer) {
+        let view = self.camera();
+        if let Some(pos) = self.companion.filter(|p| view.contains(*p)) {
+            let screen = view.to_local(pos);
+            ctx.set(screen.x, screen.y, RGB::named(CYAN), RGB::named(BLACK), '@');
+        }
+    }
+
+    pub(super) fn draw_hazards(&self, ctx: &mut dyn Renderer) {
+        let view = self.camera();
+        for h in &self.hazards {
+            if view.contains(h.pos) && self.is_visible(h.pos) {
+                let screen = view.to_local(h.pos);
+                let color = self.hazard_color(h.kind);
+                ctx.set(screen.x, screen.y, color, RGB::named(BLACK), h.kind.glyph());
+            }
+        }
+    }
+
+    pub(super) fn hazard_color(&self, kind: HazardKind) -> RGB {
+        match kind {
+            HazardKind::Jellyfish => self.palette.hazard,
+            HazardKind::Shark => self.palette.shark,
+            HazardKind::Debris => self.palette.debris,
+            HazardKind::Whirlpool => self.palette.whirlpool,
+        }
+    }
+
+    /// Title, score and distance lines of the end-of-run screen: a
+    /// completed run, or the cause of death and the score at death.
+    pub(super) fn summary_heading(&self, score: i32) -> [String; 4] {
+        let traveled = format!(
+            "Seed {}  Traveled {} tiles over {} days",
+            self.seed,
+            self.distance(),
+            self.journal.day_count()
+        );
+        match self.mode {
+            GameMode::GameOver { cause, .. } => [
+                "Game Over".to_string(),
+                format!("Cause of death: {} (turn {})", cause.name(), self.turn),
+                format!("Score at death: {}", score),
+                traveled,
+            ],
+            _ => [
+                "Run Complete!".to_string(),
+                String::new(),
+                format!("Final score: {}", score),
+                traveled,
+            ],
+        }
+    }
+}
+
+/// Arrow drawn over a tile with a strong current flowing along `flow`.
+pub(super) fn flow_glyph(flow: common::Point) -> char {
+    match (flow.x.signum(), flow.y.signum()) {
+        (1, 0) => '→',
+        (-1, 0) => '←',
+        (0, -1) => '↑',
+        (0, 1) => '↓',
+        (1, -1) | (-1, 1) => '/',
+        (1, 1) | (-1, -1) => '\\',
+        _ => '·',
+    }
+}
+
+/// Width of the summary screen charts in cells.
+const CHART_WIDTH: usize = 50;
+/// Height of the catches bar chart in rows.
+const CATCH_CHART_HEIGHT: usize = 3;
+
+/// Builds the chart rows shown on the end-of-run summary.
+pub(super) fn summary_chart_lines(metrics: &RunMetrics) -> Vec<String> {
+    let mut lines = vec![
+        format!("HP     {}", sparkline(&metrics.hp, MAX_HP, CHART_WIDTH)),
+        format!(
+            "Hunger {}",
+            sparkline(&metrics.hunger, MAX_HUNGER, CHART_WIDTH)
+        ),
+        format!("Catches ({})", metrics.total_catches()),
+    ];
+    lines.extend(
+        bar_chart(&metrics.catches, CHART_WIDTH, CATCH_CHART_HEIGHT)
+            .into_iter()
+            .map(|row| format!("       {}", row)),
+    );
+    lines
+}
+
+/// Draws the end-of-run screen with its `heading` lines and run charts.
+pub(super) fn draw_summary(ctx: &mut dyn Renderer, heading: &[String], metrics: &RunMetrics) {
+    ctx.cls();
+    for (i, line) in heading.iter().enumerate() {
+        ctx.print_centered(8 + i as i32, line);
+    }
+    for (i, line) in summary_chart_lines(metrics).iter().enumerate() {
+        ctx.print(12, 12 + i as i32, line);
+    }
 }
@@ -0,0 +1,243 @@
+//! Weather that changes every few turns and affects sight, bites and fish.
+
+use bracket_lib::prelude::RandomNumberGenerator;
+use data::Season;
+use serde::{Deserialize, Serialize};
+
+/// Current sky and sea conditions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Weather {
+    #[default]
+    Clear,
+    Rain,
+    Fog,
+    Storm,
+    Gale,
+}
+
+impl Weather {
+    pub fn name(self) -> &'static str {
+        match self {
+            Weather::Clear => "Clear",
+            Weather::Rain => "Rain",
+            Weather::Fog => "Fog",
+            Weather::Storm => "Storm",
+            Weather::Gale => "Gale",
+        }
+    }
+
+    /// Furthest the player can see, if this weather limits it.
+    pub fn sight_limit(self) -> Option<i32> {
+        match self {
+            Weather::Clear => None,
+            Weather::Rain => Some(6),
+            Weather::Fog | Weather::Storm => Some(3),
+            Weather::Gale => Some(2),
+        }
+    }
+
+    /// Flat modifier added to the bite probability.
+    pub fn bite_bonus(self) -> f32 {
+        match self {
+            Weather::Clear | Weather::Fog => 0.0,
+            // Rain stirs up food and brings fish up
+            Weather::Rain => 0.05,
+            Weather::Storm => -0.1,
+            Weather::Gale => -0.2,
+        }
+    }
+
+    /// Extra cells fish may move per turn in rough water.
+    pub fn fish_speed(self) -> i32 {
+        match self {
+            Weather::Storm | Weather::Gale => 1,
+            _ => 0,
+        }
+    }
+
+    /// Rain, storms and gales soak an angler out in them.
+    pub fn is_wet(self) -> bool {
+        matches!(self, Weather::Rain | Weather::Storm | Weather::Gale)
+    }
+
+    /// Storms and gales call for the storm sound and warning.
+    pub fn is_stormy(self) -> bool {
+        matches!(self, Weather::Storm | Weather::Gale)
+    }
+
+    /// Log line announcing a change to this weather.
+    pub fn announcement(self) -> &'static str {
+        match self {
+            Weather::Clear => "The sky clears.",
+            Weather::Rain => "Rain starts to fall.",
+            Weather::Fog => "Fog rolls in.",
+            Weather::Storm => "A storm reduces visibility!",
+            Weather::Gale => "A gale whips up the sea!",
+        }
+    }
+
+    /// Weighted next states, rolled once the current weather runs out.
+    fn transitions(self) -> &'static [(Weather, i32)] {
+        match self {
+            Weather::Clear => &[
+                (Weather::Clear, 70),
+                (Weather::Rain, 15),
+                (Weather::Fog, 15),
+            ],
+            Weather::Rain => &[
+                (Weather::Clear, 40),
+                (Weather::Rain, 20),
+                (Weather::Storm, 30),
+                (Weather::Fog, 10),
+            ],
+            Weather::Fog => &[
+                (Weather::Clear, 60),
+                (Weather::Rain, 30),
+                (Weather::Fog, 10),
+            ],
+            Weather::Storm => &[
+                (Weather::Rain, 50),
+                (Weather::Gale, 30),
+                (Weather::Storm, 20),
+            ],
+            Weather::Gale => &[(Weather::Storm, 60), (Weather::Rain, 40)],
+        }
+    }
+
+    /// How likely storms and gales are in `season`, in percent of the
+    /// usual odds.
+    fn storminess(season: Season) -> i32 {
+        match season {
+            Season::Spring => 100,
+            Season::Summer => 50,
+            Season::Autumn => 180,
+            Season::Winter => 140,
+        }
+    }
+
+    /// Turns (min, max inclusive) a spell of this weather lasts.
+    fn duration(self) -> (i32, i32) {
+        match self {
+            Weather::Clear => (8, 16),
+            Weather::Rain => (5, 10),
+            Weather::Fog => (4, 8),
+            Weather::Storm => (3, 6),
+            Weather::Gale => (2, 4),
+        }
+    }
+}
+
+/// The weather and how long it has left.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WeatherState {
+    pub weather: Weather,
+    pub turns_left: u8,
+}
+
+impl Default for WeatherState {
+    fn default() -> Self {
+        Self::new(Weather::Clear, Weather::Clear.duration().1 as u8)
+    }
+}
+
+impl WeatherState {
+    pub fn new(weather: Weather, turns_left: u8) -> Self {
+        Self {
+            weather,
+            turns_left,
+        }
+    }
+
+    /// Advances one turn. Returns the new weather when it changed. The
+    /// season makes storms and gales more or less likely.
+    pub fn advance(&mut self, rng: &mut RandomNumberGenerator, season: Season) -> Option<Weather> {
+        if self.turns_left > 1 {
+            self.turns_left -= 1;
+            return None;
+        }
+        let storminess = Weather::storminess(season);
+        let options: Vec<(Weather, i32)> = self
+            .weather
+            .transitions()
+            .iter()
+            .map(|&(weather, weight)| {
+                if weather.is_stormy() {
+                    (weather, weight * storminess / 100)
+                } else {
+                    (weather, weight)
+                }
+            })
+            .collect();
+        let mut roll = rng.range(0, options.iter().map(|(_, w)| w).sum::<i32>());
+        let mut next = self.weather;
+        for (weather, weight) in options {
+            if roll < weight {
+                next = weather;
+                break;
+            }
+            roll -= weight;
+        }
+        let (min, max) = next.duration();
+        self.turns_left = rng.range(min, max + 1) as u8;
+        let changed = next != self.weather;
+        self.weather = next;
+        changed.then_some(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weather_holds_until_its_spell_ends() {
+        let mut rng = RandomNumberGenerator::seeded(1);
+        let mut state = WeatherState::new(Weather::Fog, 3);
+        assert_eq!(state.advance(&mut rng, Season::Spring), None);
+        assert_eq!(state.advance(&mut rng, Season::Spring), None);
+        assert_eq!(state.weather, Weather::Fog);
+        state.advance(&mut rng, Season::Spring);
+        let (min, max) = state.weather.duration();
+        assert!((min..=max).contains(&(state.turns_left as i32)));
+    }
+
+    #[test]
+    fn every_state_is_reachable_from_clear() {
+        let mut rng = RandomNumberGenerator::seeded(7);
+        let mut state = WeatherState::default();
+        let mut seen = vec![state.weather];
+        for _ in 0..5000 {
+            if let Some(weather) = state.advance(&mut rng, Season::Spring) {
+                if !seen.contains(&weather) {
+                    seen.push(weather);
+                }
+            }
+        }
+        assert_eq!(seen.len(), 5);
+    }
+
+    #[test]
+    fn autumn_brings_more_storms_than_summer() {
+        let storms = |season| {
+            let mut rng = RandomNumberGenerator::seeded(3);
+            let mut state = WeatherState::default();
+            (0..5000)
+                .filter(|_| {
+                    state.advance(&mut rng, season);
+                    state.weather.is_stormy()
+                })
+                .count()
+        };
+        assert!(storms(Season::Autumn) > storms(Season::Summer) * 2);
+    }
+
+    #[test]
+    fn gales_only_follow_storms() {
+        for weather in [Weather::Clear, Weather::Rain, Weather::Fog, Weather::Gale] {
+            assert!(weather
+                .transitions()
+                .iter()
+                .all(|(next, _)| *next != Weather::Gale));
+        }
+    }
+}
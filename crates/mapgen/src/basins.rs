@@ -0,0 +1,212 @@
+//! Water basins: the 8-connected bodies of water on a map. Generation
+//! carves channels so every sizeable body joins the largest, the main
+//! basin, and the player and fish start out in it rather than stranded in
+//! a pond.
+use crate::{Map, TileKind};
+use common::Point;
+use std::collections::VecDeque;
+
+/// Bodies of water of at least this many tiles are joined to the main
+/// basin; smaller ponds are left alone.
+const MIN_BASIN_TILES: usize = 30;
+/// Depth in meters of a carved channel.
+const CHANNEL_DEPTH: i32 = 2;
+
+fn point(map: &Map, idx: usize) -> Point {
+    Point::new(
+        (idx % map.width as usize) as i32,
+        (idx / map.width as usize) as i32,
+    )
+}
+
+fn neighbours(map: &Map, idx: usize) -> impl Iterator<Item = usize> + '_ {
+    let pt = point(map, idx);
+    (-1..=1)
+        .flat_map(move |dy| (-1..=1).map(move |dx| pt + Point::new(dx, dy)))
+        .filter(move |&n| n != pt && map.bounds().contains(n))
+        .map(|n| map.idx(n))
+}
+
+/// The bodies of water on `map`, largest first, each as its tile indices.
+fn bodies(map: &Map) -> Vec<Vec<usize>> {
+    let mut seen = vec![false; map.tiles.len()];
+    let mut found = Vec::new();
+    for start in 0..map.tiles.len() {
+        if seen[start] || !map.tiles[start].is_water() {
+            continue;
+        }
+        seen[start] = true;
+        let mut body = vec![start];
+        let mut open = vec![start];
+        while let Some(idx) = open.pop() {
+            for n in neighbours(map, idx) {
+                if !seen[n] && map.tiles[n].is_water() {
+                    seen[n] = true;
+                    body.push(n);
+                    open.push(n);
+                }
+            }
+        }
+        found.push(body);
+    }
+    // Stable, so equal bodies keep the order they were found in
+    found.sort_by_key(|b| std::cmp::Reverse(b.len()));
+    found
+}
+
+/// Carves a channel of shallow water from `body` to the nearest tile in
+/// `main`, and adds both to `main`. The channel takes the shortest path
+/// in straight steps so that it is no mere diagonal crack in the land.
+fn carve(map: &mut Map, body: &[usize], main: &mut [bool]) {
+    let mut prev: Vec<Option<usize>> = vec![None; map.tiles.len()];
+    let mut reached = vec![false; map.tiles.len()];
+    let mut open: VecDeque<usize> = body.iter().copied().collect();
+    for &idx in body {
+        reached[idx] = true;
+    }
+    let mut end = None;
+    while let Some(idx) = open.pop_front() {
+        if main[idx] {
+            end = Some(idx);
+            break;
+        }
+        let pt = point(map, idx);
+        let steps = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+        for step in steps {
+            let next = pt + Point::new(step.0, step.1);
+            if !map.bounds().contains(next) {
+                continue;
+            }
+            let n = map.idx(next);
+            if !reached[n] {
+                reached[n] = true;
+                prev[n] = Some(idx);
+                open.push_back(n);
+            }
+        }
+    }
+    let Some(mut at) = end else {
+        return;
+    };
+    while let Some(p) = prev[at] {
+        if !map.tiles[p].is_water() {
+            map.tiles[p] = TileKind::ShallowWater;
+            map.depths[p] = CHANNEL_DEPTH;
+            map.tidal[p] = None;
+        }
+        main[p] = true;
+        at = p;
+    }
+    for &idx in body {
+        main[idx] = true;
+    }
+}
+
+/// Joins every body of water of at least [`MIN_BASIN_TILES`] to the
+/// largest with carved channels, then records each water tile's basin in
+/// [`Map::basins`]: 0 for the main basin, then the ponds by size.
+pub fn connect(map: &mut Map) {
+    let found = bodies(map);
+    let mut main = vec![false; map.tiles.len()];
+    if let Some(first) = found.first() {
+        for &idx in first {
+            main[idx] = true;
+        }
+    }
+    for body in found.iter().skip(1) {
+        if body.len() >= MIN_BASIN_TILES {
+            carve(map, body, &mut main);
+        }
+    }
+    label(map);
+}
+
+/// Records the basin of every water tile in [`Map::basins`].
+pub fn label(map: &mut Map) {
+    map.basins = vec![None; map.tiles.len()];
+    for (id, body) in bodies(map).into_iter().enumerate() {
+        for idx in body {
+            map.basins[idx] = Some(id);
+        }
+    }
+}
+
+/// Where to start a player looking to begin at `near`: there, unless it
+/// is water cut off from the main basin, in which case the nearest tile
+/// of the main basin.
+pub fn start_point(map: &Map, near: Point) -> Point {
+    let idx = map.idx(near);
+    if !map.tiles[idx].is_water() || !matches!(map.basins[idx], Some(b) if b != 0) {
+        return near;
+    }
+    (0..map.tiles.len())
+        .filter(|&i| map.basins[i] == Some(0))
+        .map(|i| point(map, i))
+        .min_by_key(|pt| pt.chebyshev(near))
+        .unwrap_or(near)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Land with a 10x6 lake on the left, a 6x6 one on the right and a
+    /// 2x2 pond between them.
+    fn lakes() -> Map {
+        let mut map = Map::new(30, 10);
+        let mut dig = |xs: std::ops::Range<i32>, ys: std::ops::Range<i32>| {
+            for y in ys {
+                for x in xs.clone() {
+                    let idx = map.idx(Point::new(x, y));
+                    map.tiles[idx] = TileKind::ShallowWater;
+                    map.depths[idx] = 5;
+                }
+            }
+        };
+        dig(1..11, 2..8);
+        dig(22..28, 2..8);
+        dig(15..17, 4..6);
+        map
+    }
+
+    #[test]
+    fn sizeable_lakes_are_joined_to_the_main_basin() {
+        let mut map = lakes();
+        connect(&mut map);
+        let basin = |x, y| map.basins[map.idx(Point::new(x, y))];
+        assert_eq!(basin(2, 3), Some(0));
+        assert_eq!(basin(25, 5), Some(0), "joined by a channel");
+        assert_eq!(basin(15, 4), Some(1), "the pond is left alone");
+        assert_eq!(basin(0, 0), None);
+        assert!(map.in_main_basin(Point::new(25, 5)));
+        assert!(!map.in_main_basin(Point::new(15, 4)));
+        let channel = map
+            .tiles
+            .iter()
+            .zip(&lakes().tiles)
+            .filter(|(now, before)| now != before)
+            .count();
+        assert_eq!(channel, 11, "the shortest way across");
+        let start = start_point(&map, Point::new(15, 4));
+        assert!(map.in_main_basin(start));
+        assert_eq!(start.chebyshev(Point::new(15, 4)), 2, "by the channel");
+        assert_eq!(start_point(&map, Point::new(5, 5)), Point::new(5, 5));
+        assert_eq!(start_point(&map, Point::new(0, 0)), Point::new(0, 0));
+    }
+
+    #[test]
+    fn small_lakes_stay_apart() {
+        let mut map = lakes();
+        let small: Vec<usize> = (22..28)
+            .flat_map(|x| (2..6).map(move |y| (x, y)))
+            .map(|(x, y)| map.idx(Point::new(x, y)))
+            .collect();
+        for idx in small {
+            map.tiles[idx] = TileKind::Land;
+        }
+        let before = map.tiles.clone();
+        connect(&mut map);
+        assert_eq!(map.tiles, before);
+        assert_eq!(map.basins[map.idx(Point::new(25, 7))], Some(1));
+    }
+}
@@ -0,0 +1,166 @@
+//! Endless areas: the sea goes on past the edges of the map in chunks of
+//! the area's size, each generated the first time it is needed. World
+//! coordinates count from the corner of chunk (0, 0), the area's usual map.
+use crate::{generate_chunk, Map, TileKind};
+use common::{GameResult, Point};
+use data::AreaType;
+use std::collections::HashMap;
+
+/// Chunks kept in memory besides the one being played; the farthest are
+/// dropped and generated again if the player returns.
+const MAX_CACHED_CHUNKS: usize = 8;
+
+/// The chunks of one endless area.
+#[derive(Clone, Debug)]
+pub struct Ocean {
+    seed: u64,
+    area: AreaType,
+    chunks: HashMap<Point, Map>,
+}
+
+impl Ocean {
+    pub fn new(seed: u64, area: &AreaType) -> Self {
+        Self {
+            seed,
+            area: area.clone(),
+            chunks: HashMap::new(),
+        }
+    }
+
+    /// Size of one chunk.
+    fn size(&self) -> Point {
+        Point::new(self.area.width as i32, self.area.height as i32)
+    }
+
+    /// The chunk `world` lies in and its position on that chunk's map.
+    pub fn locate(&self, world: Point) -> (Point, Point) {
+        let size = self.size();
+        (
+            Point::new(world.x.div_euclid(size.x), world.y.div_euclid(size.y)),
+            Point::new(world.x.rem_euclid(size.x), world.y.rem_euclid(size.y)),
+        )
+    }
+
+    /// World coordinates of `local` on the map of `chunk`.
+    pub fn world(&self, chunk: Point, local: Point) -> Point {
+        let size = self.size();
+        Point::new(chunk.x * size.x + local.x, chunk.y * size.y + local.y)
+    }
+
+    /// Whether `chunk` has been generated and is kept in memory.
+    pub fn is_cached(&self, chunk: Point) -> bool {
+        self.chunks.contains_key(&chunk)
+    }
+
+    /// The map of `chunk`, generating it first if need be.
+    pub fn chunk(&mut self, chunk: Point) -> GameResult<&Map> {
+        if !self.chunks.contains_key(&chunk) {
+            let map = generate_chunk(self.seed, &self.area, chunk)?;
+            self.chunks.insert(chunk, map);
+        }
+        Ok(&self.chunks[&chunk])
+    }
+
+    /// Tile at `world`, generating its chunk if need be.
+    pub fn tile(&mut self, world: Point) -> GameResult<TileKind> {
+        let (chunk, local) = self.locate(world);
+        let map = self.chunk(chunk)?;
+        Ok(map.tiles[map.idx(local)])
+    }
+
+    /// Depth in meters at `world`, generating its chunk if need be.
+    pub fn depth(&mut self, world: Point) -> GameResult<i32> {
+        let (chunk, local) = self.locate(world);
+        Ok(self.chunk(chunk)?.depth(local))
+    }
+
+    /// Takes the map of `chunk` out to be played on, generating it if need
+    /// be. Hand it back with [`Ocean::store`] when the player leaves it.
+    pub fn take(&mut self, chunk: Point) -> GameResult<Map> {
+        match self.chunks.remove(&chunk) {
+            Some(map) => Ok(map),
+            None => generate_chunk(self.seed, &self.area, chunk),
+        }
+    }
+
+    /// Keeps the map of `chunk`, with its explored tiles, for when the
+    /// player comes back, dropping the chunks farthest from it past
+    /// [`MAX_CACHED_CHUNKS`].
+    pub fn store(&mut self, chunk: Point, map: Map) {
+        self.chunks.insert(chunk, map);
+        while self.chunks.len() > MAX_CACHED_CHUNKS {
+            let far = *self
+                .chunks
+                .keys()
+                .max_by_key(|c| (c.chebyshev(chunk), c.x, c.y))
+                .expect("chunk");
+            self.chunks.remove(&far);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate;
+
+    fn area() -> AreaType {
+        AreaType::new("Test", 40, 30)
+    }
+
+    #[test]
+    fn world_coordinates_span_the_chunks() {
+        let ocean = Ocean::new(0, &area());
+        assert_eq!(
+            ocean.locate(Point::new(-1, 65)),
+            (Point::new(-1, 2), Point::new(39, 5))
+        );
+        assert_eq!(
+            ocean.world(Point::new(-1, 2), Point::new(39, 5)),
+            Point::new(-1, 65)
+        );
+    }
+
+    #[test]
+    fn chunks_are_generated_on_demand_and_fixed_by_the_seed() {
+        let mut ocean = Ocean::new(7, &area());
+        let home = generate(7, &area()).unwrap();
+        assert_eq!(ocean.chunk(Point::new(0, 0)).unwrap().tiles, home.tiles);
+        assert!(!ocean.is_cached(Point::new(3, -2)));
+        let far = ocean.depth(Point::new(130, -45)).unwrap();
+        assert!(ocean.is_cached(Point::new(3, -2)));
+        let again = generate_chunk(7, &area(), Point::new(3, -2)).unwrap();
+        assert_eq!(far, again.depth(Point::new(10, 15)));
+        assert_ne!(again.tiles, home.tiles);
+    }
+
+    #[test]
+    fn noise_runs_on_across_chunk_edges() {
+        let area = AreaType {
+            noise_frequency: 0.01,
+            ..area()
+        };
+        let mut ocean = Ocean::new(2, &area);
+        // Neighbouring tiles either side of an edge differ no more than
+        // neighbours inside a chunk
+        let mut jumps = 0;
+        for y in 0..30 {
+            let west = ocean.depth(Point::new(39, y)).unwrap();
+            let east = ocean.depth(Point::new(40, y)).unwrap();
+            jumps += (west - east).abs();
+        }
+        assert!(jumps <= 30, "{}", jumps);
+    }
+
+    #[test]
+    fn far_chunks_are_dropped() {
+        let mut ocean = Ocean::new(0, &area());
+        for x in 0..12 {
+            let map = ocean.take(Point::new(x, 0)).unwrap();
+            ocean.store(Point::new(x, 0), map);
+        }
+        assert!(ocean.is_cached(Point::new(11, 0)));
+        assert!(ocean.is_cached(Point::new(4, 0)));
+        assert!(!ocean.is_cached(Point::new(0, 0)));
+    }
+}
@@ -0,0 +1,278 @@
+//! Named map features: bays, islands, reefs and trenches found by
+//! connected-component analysis of the tiles and depths, each with a
+//! procedurally generated name.
+use crate::{Map, TileKind};
+use bracket_lib::prelude::RandomNumberGenerator;
+use common::Point;
+
+/// Fewest tiles a feature covers.
+const MIN_FEATURE_TILES: usize = 8;
+/// Depth in meters from which water counts as a trench.
+const TRENCH_DEPTH: i32 = 40;
+/// How far, in tiles, a bay looks for the land around it.
+const BAY_REACH: i32 = 8;
+/// Directions, of eight, that have to meet land within [`BAY_REACH`] for
+/// water to count as a bay.
+const BAY_SHORES: usize = 6;
+
+const NAME_STARTS: [&str; 16] = [
+    "Gull", "Raven", "Salt", "Storm", "Silver", "Drift", "Whale", "Amber", "Mist", "Heron",
+    "Kestrel", "Lantern", "Briar", "Hollow", "Grey", "Copper",
+];
+const NAME_ENDS: [&str; 12] = [
+    "wing", "crest", "reach", "water", "mere", "haven", "tide", "stone", "fall", "shade", "wick",
+    "holm",
+];
+
+/// What sort of place a feature is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FeatureKind {
+    /// Shallow water mostly closed in by land.
+    Bay,
+    /// Land with water all round it.
+    Island,
+    /// A stretch of coral reef.
+    Reef,
+    /// Water deeper than [`TRENCH_DEPTH`].
+    Trench,
+}
+
+impl FeatureKind {
+    /// Word that ends the feature's name.
+    fn word(self) -> &'static str {
+        match self {
+            FeatureKind::Bay => "Bay",
+            FeatureKind::Island => "Isle",
+            FeatureKind::Reef => "Reef",
+            FeatureKind::Trench => "Trench",
+        }
+    }
+}
+
+/// A named place on the map.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Feature {
+    pub kind: FeatureKind,
+    /// e.g. `Gullwing Bay`.
+    pub name: String,
+    /// Tiles it covers.
+    pub size: usize,
+    /// Its tile nearest the middle, where a label goes.
+    pub center: Point,
+}
+
+/// The features of one map and which one each tile belongs to.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Features {
+    pub list: Vec<Feature>,
+    width: i32,
+    height: i32,
+    /// Index into `list` for each tile, in row-major order.
+    owner: Vec<Option<usize>>,
+}
+
+impl Features {
+    /// Finds the reefs, trenches, bays and islands on `map`, in that order
+    /// of precedence where they overlap, and names them from `seed`.
+    pub fn detect(map: &Map, seed: u64) -> Self {
+        let mut features = Self {
+            list: Vec::new(),
+            width: map.width as i32,
+            height: map.height as i32,
+            owner: vec![None; map.tiles.len()],
+        };
+        let mut rng = RandomNumberGenerator::seeded(seed);
+        let reef = |idx: usize| map.tiles[idx] == TileKind::CoralReef;
+        let trench = |idx: usize| map.tiles[idx].is_water() && map.depths[idx] >= TRENCH_DEPTH;
+        let bay = |idx: usize| map.tiles[idx].is_shallow() && enclosed(map, idx);
+        let land = |idx: usize| !map.tiles[idx].is_water();
+        features.add(map, FeatureKind::Reef, reef, &mut rng);
+        features.add(map, FeatureKind::Trench, trench, &mut rng);
+        features.add(map, FeatureKind::Bay, bay, &mut rng);
+        features.add(map, FeatureKind::Island, land, &mut rng);
+        features
+    }
+
+    /// Adds every unclaimed 4-connected region of tiles matching `part` as
+    /// a feature of `kind`. Islands must not touch the map edge.
+    fn add(
+        &mut self,
+        map: &Map,
+        kind: FeatureKind,
+        part: impl Fn(usize) -> bool,
+        rng: &mut RandomNumberGenerator,
+    ) {
+        let mut seen = vec![false; map.tiles.len()];
+        for start in 0..map.tiles.len() {
+            if seen[start] || self.owner[start].is_some() || !part(start) {
+                continue;
+            }
+            seen[start] = true;
+            let mut region = vec![start];
+            let mut open = vec![start];
+            let mut at_edge = false;
+            while let Some(idx) = open.pop() {
+                let pt = point(map, idx);
+                for step in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+                    let n = pt + Point::new(step.0, step.1);
+                    if !map.bounds().contains(n) {
+                        at_edge = true;
+                        continue;
+                    }
+                    let ni = map.idx(n);
+                    if !seen[ni] && self.owner[ni].is_none() && part(ni) {
+                        seen[ni] = true;
+                        region.push(ni);
+                        open.push(ni);
+                    }
+                }
+            }
+            if region.len() < MIN_FEATURE_TILES || (kind == FeatureKind::Island && at_edge) {
+                continue;
+            }
+            let n = region.len() as i32;
+            let (sx, sy) = region.iter().fold((0, 0), |(x, y), &idx| {
+                let pt = point(map, idx);
+                (x + pt.x, y + pt.y)
+            });
+            let middle = Point::new(sx / n, sy / n);
+            let center = region
+                .iter()
+                .map(|&idx| point(map, idx))
+                .min_by_key(|pt| pt.manhattan(middle))
+                .expect("region");
+            let id = self.list.len();
+            for &idx in &region {
+                self.owner[idx] = Some(id);
+            }
+            let name = self.name(kind, rng);
+            self.list.push(Feature {
+                kind,
+                name,
+                size: region.len(),
+                center,
+            });
+        }
+    }
+
+    /// A name for a new feature of `kind`, unlike those taken so far when
+    /// there is one left.
+    fn name(&self, kind: FeatureKind, rng: &mut RandomNumberGenerator) -> String {
+        let mut name = String::new();
+        for _ in 0..NAME_STARTS.len() {
+            let start = NAME_STARTS[rng.range(0, NAME_STARTS.len() as i32) as usize];
+            let end = NAME_ENDS[rng.range(0, NAME_ENDS.len() as i32) as usize];
+            name = format!("{}{} {}", start, end, kind.word());
+            if !self.list.iter().any(|f| f.name == name) {
+                break;
+            }
+        }
+        name
+    }
+
+    /// Index in [`Features::list`] of the feature at `pt`, if any.
+    pub fn index_at(&self, pt: Point) -> Option<usize> {
+        if pt.x < 0 || pt.y < 0 || pt.x >= self.width || pt.y >= self.height {
+            return None;
+        }
+        self.owner[(pt.y * self.width + pt.x) as usize]
+    }
+
+    /// The feature at `pt`, if any.
+    pub fn at(&self, pt: Point) -> Option<&Feature> {
+        self.index_at(pt).map(|i| &self.list[i])
+    }
+}
+
+fn point(map: &Map, idx: usize) -> Point {
+    Point::new(
+        (idx % map.width as usize) as i32,
+        (idx / map.width as usize) as i32,
+    )
+}
+
+/// Whether the water at `idx` has land within [`BAY_REACH`] in at least
+/// [`BAY_SHORES`] of the eight directions.
+fn enclosed(map: &Map, idx: usize) -> bool {
+    let pt = point(map, idx);
+    let shores = (-1..=1)
+        .flat_map(|dy| (-1..=1).map(move |dx| Point::new(dx, dy)))
+        .filter(|&d| d != Point::new(0, 0))
+        .filter(|&d| {
+            (1..=BAY_REACH)
+                .map(|r| pt + Point::new(d.x * r, d.y * r))
+                .any(|p| map.bounds().contains(p) && !map.tiles[map.idx(p)].is_water())
+        })
+        .count();
+    shores >= BAY_SHORES
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Open sea 10m deep with a 5x5 island, a 3x4 patch of reef, a 60m
+    /// trench along the bottom rows and a bay cut into land on the left.
+    fn chart() -> Map {
+        let mut map = Map::new(40, 30);
+        map.tiles.fill(TileKind::ShallowWater);
+        map.depths.fill(10);
+        let mut paint = |xs: std::ops::Range<i32>, ys: std::ops::Range<i32>, tile, depth| {
+            for y in ys {
+                for x in xs.clone() {
+                    let idx = map.idx(Point::new(x, y));
+                    map.tiles[idx] = tile;
+                    map.depths[idx] = depth;
+                }
+            }
+        };
+        paint(20..25, 5..10, TileKind::Land, 0);
+        paint(30..33, 12..16, TileKind::CoralReef, 3);
+        paint(0..40, 26..30, TileKind::DeepWater, 60);
+        // Land on three sides of a 3x4 inlet
+        paint(0..8, 0..20, TileKind::Land, 0);
+        paint(2..5, 6..10, TileKind::ShallowWater, 4);
+        map
+    }
+
+    #[test]
+    fn features_are_found_and_named() {
+        let map = chart();
+        let features = Features::detect(&map, 1);
+        let kind = |pt: Point| features.at(pt).map(|f| f.kind);
+        assert_eq!(kind(Point::new(22, 7)), Some(FeatureKind::Island));
+        assert_eq!(kind(Point::new(31, 13)), Some(FeatureKind::Reef));
+        assert_eq!(kind(Point::new(10, 28)), Some(FeatureKind::Trench));
+        assert_eq!(kind(Point::new(3, 7)), Some(FeatureKind::Bay));
+        assert_eq!(kind(Point::new(15, 20)), None, "open water");
+        assert_eq!(kind(Point::new(2, 2)), None, "land at the edge");
+        let island = features.at(Point::new(22, 7)).unwrap();
+        assert_eq!(island.size, 25);
+        assert_eq!(island.center, Point::new(22, 7));
+        assert!(island.name.ends_with(" Isle"), "{}", island.name);
+        assert!(features
+            .at(Point::new(3, 7))
+            .unwrap()
+            .name
+            .ends_with(" Bay"));
+        assert_eq!(features.index_at(Point::new(-1, 0)), None);
+    }
+
+    #[test]
+    fn names_follow_the_seed() {
+        let map = chart();
+        let names = |seed| -> Vec<String> {
+            Features::detect(&map, seed)
+                .list
+                .into_iter()
+                .map(|f| f.name)
+                .collect()
+        };
+        assert_eq!(names(4), names(4));
+        assert_ne!(names(4), names(5));
+        let mut unique = names(4);
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), 4);
+    }
+}
@@ -0,0 +1,230 @@
+//! Field of view by symmetric shadowcasting.
+//!
+//! Each quadrant is scanned row by row outwards from the origin, narrowing
+//! the visible arc behind tiles that block sight. A floor tile counts as
+//! seen only when its centre lies inside the arc, which makes sight
+//! symmetric: if A sees B, B sees A.
+use crate::Map;
+use common::{Point, Rect};
+
+/// Tiles seen from one spot.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldOfView {
+    origin: Point,
+    radius: i32,
+    bounds: Rect,
+    visible: Vec<bool>,
+}
+
+impl FieldOfView {
+    /// Where the viewer stands.
+    pub fn origin(&self) -> Point {
+        self.origin
+    }
+
+    /// How far the viewer sees, in Chebyshev distance.
+    pub fn radius(&self) -> i32 {
+        self.radius
+    }
+
+    /// Every tile in sight.
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        self.bounds.points().filter(|&pt| self.contains(pt))
+    }
+
+    /// Whether `pt` is in sight; tiles off the map never are.
+    pub fn contains(&self, pt: Point) -> bool {
+        self.bounds.contains(pt) && self.visible[self.index(pt)]
+    }
+
+    fn index(&self, pt: Point) -> usize {
+        (pt.y as usize) * self.bounds.width as usize + pt.x as usize
+    }
+
+    fn reveal(&mut self, pt: Point) {
+        let idx = self.index(pt);
+        self.visible[idx] = true;
+    }
+}
+
+/// A slope `num / den` from the origin, with `den > 0`.
+#[derive(Clone, Copy, Debug)]
+struct Slope {
+    num: i32,
+    den: i32,
+}
+
+impl Slope {
+    const fn new(num: i32, den: i32) -> Self {
+        Self { num, den }
+    }
+
+    /// Slope to the near edge of the tile at `col` in the row at `depth`.
+    fn edge(depth: i32, col: i32) -> Self {
+        Self::new(2 * col - 1, 2 * depth)
+    }
+}
+
+/// One row of a quadrant scan: the columns between two slopes at `depth`.
+#[derive(Clone, Copy, Debug)]
+struct Row {
+    depth: i32,
+    start: Slope,
+    end: Slope,
+}
+
+impl Row {
+    /// First column of the row, rounding ties towards the end slope.
+    fn min_col(&self) -> i32 {
+        (2 * self.depth * self.start.num + self.start.den).div_euclid(2 * self.start.den)
+    }
+
+    /// Last column of the row, rounding ties towards the start slope.
+    fn max_col(&self) -> i32 {
+        -(self.end.den - 2 * self.depth * self.end.num).div_euclid(2 * self.end.den)
+    }
+
+    /// Whether the centre of the tile at `col` lies inside the arc.
+    fn symmetric(&self, col: i32) -> bool {
+        col * self.start.den >= self.depth * self.start.num
+            && col * self.end.den <= self.depth * self.end.num
+    }
+
+    fn next(self) -> Self {
+        Self {
+            depth: self.depth + 1,
+            ..self
+        }
+    }
+}
+
+/// Computes what a viewer at `origin` sees within `radius` tiles.
+///
+/// `blocks_sight` decides which tiles stop sight; they are seen themselves
+/// but hide what lies behind them. The map edge blocks sight too.
+pub fn field_of_view(
+    map: &Map,
+    origin: Point,
+    radius: i32,
+    blocks_sight: impl Fn(Point) -> bool,
+) -> FieldOfView {
+    let bounds = map.bounds();
+    let mut fov = FieldOfView {
+        origin,
+        radius,
+        bounds,
+        visible: vec![false; (map.width * map.height) as usize],
+    };
+    if !bounds.contains(origin) {
+        return fov;
+    }
+    fov.reveal(origin);
+    let quadrants: [fn(i32, i32) -> Point; 4] = [
+        |depth, col| Point::new(col, -depth),
+        |depth, col| Point::new(depth, col),
+        |depth, col| Point::new(col, depth),
+        |depth, col| Point::new(-depth, col),
+    ];
+    for transform in quadrants {
+        let tile = |depth, col| origin + transform(depth, col);
+        let blocked = |pt: Point| !bounds.contains(pt) || blocks_sight(pt);
+        let mut rows = vec![Row {
+            depth: 1,
+            start: Slope::new(-1, 1),
+            end: Slope::new(1, 1),
+        }];
+        while let Some(mut row) = rows.pop() {
+            if row.depth > radius {
+                continue;
+            }
+            let mut prev_blocked = None;
+            for col in row.min_col()..=row.max_col() {
+                let pt = tile(row.depth, col);
+                let is_blocked = blocked(pt);
+                if bounds.contains(pt) && (is_blocked || row.symmetric(col)) {
+                    fov.reveal(pt);
+                }
+                if prev_blocked == Some(true) && !is_blocked {
+                    row.start = Slope::edge(row.depth, col);
+                }
+                if prev_blocked == Some(false) && is_blocked {
+                    rows.push(Row {
+                        end: Slope::edge(row.depth, col),
+                        ..row.next()
+                    });
+                }
+                prev_blocked = Some(is_blocked);
+            }
+            if prev_blocked == Some(false) {
+                rows.push(row.next());
+            }
+        }
+    }
+    fov
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TileKind;
+
+    fn open_sea(width: u32, height: u32) -> Map {
+        let mut map = Map::new(width, height);
+        map.tiles.fill(TileKind::DeepWater);
+        map
+    }
+
+    fn land(map: &Map) -> impl Fn(Point) -> bool + '_ {
+        |pt| map.tiles[map.idx(pt)] == TileKind::Land
+    }
+
+    #[test]
+    fn open_water_is_seen_within_the_radius() {
+        let map = open_sea(20, 20);
+        let origin = Point::new(10, 10);
+        let fov = field_of_view(&map, origin, 4, land(&map));
+        assert_eq!(fov.origin(), origin);
+        for pt in map.bounds().points() {
+            assert_eq!(fov.contains(pt), pt.chebyshev(origin) <= 4, "{:?}", pt);
+        }
+        assert!(!fov.contains(Point::new(-1, 0)));
+        assert_eq!(fov.points().count(), 81);
+    }
+
+    #[test]
+    fn land_hides_what_lies_behind_it() {
+        let mut map = open_sea(20, 20);
+        for y in 0..20 {
+            let idx = map.idx(Point::new(12, y));
+            map.tiles[idx] = TileKind::Land;
+        }
+        let fov = field_of_view(&map, Point::new(10, 10), 20, land(&map));
+        assert!(fov.contains(Point::new(11, 3)));
+        assert!(fov.contains(Point::new(12, 10)));
+        assert!(!fov.contains(Point::new(13, 10)));
+        assert!(!fov.contains(Point::new(19, 0)));
+    }
+
+    #[test]
+    fn sight_is_symmetric() {
+        let mut map = open_sea(16, 16);
+        for (x, y) in [(5, 5), (6, 9), (9, 4), (10, 10), (3, 12), (12, 7)] {
+            let idx = map.idx(Point::new(x, y));
+            map.tiles[idx] = TileKind::Land;
+        }
+        let water: Vec<Point> = map
+            .bounds()
+            .points()
+            .filter(|&pt| map.tiles[map.idx(pt)] != TileKind::Land)
+            .collect();
+        let views: Vec<FieldOfView> = water
+            .iter()
+            .map(|&pt| field_of_view(&map, pt, 16, land(&map)))
+            .collect();
+        for (a, view_a) in water.iter().zip(&views) {
+            for (b, view_b) in water.iter().zip(&views) {
+                assert_eq!(view_a.contains(*b), view_b.contains(*a), "{:?} {:?}", a, b);
+            }
+        }
+    }
+}
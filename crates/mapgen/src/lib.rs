@@ -1,6 +1,14 @@
 //! Map generation utilities.
+pub mod basins;
+pub mod chunks;
+pub mod features;
+pub mod fov;
+pub mod passes;
+pub mod tutorial;
+
 use bracket_lib::prelude::{FastNoise, NoiseType};
-use common::{GameResult, Point};
+use common::{GameResult, Point, Rect};
+use data::{AreaType, Biome};
 
 /// Kind of a tile on the game map.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -11,6 +19,92 @@ pub enum TileKind {
     ShallowWater,
     /// Deep water tile.
     DeepWater,
+    /// Shallow water over coral.
+    CoralReef,
+    /// Shallow water thick with kelp.
+    KelpForest,
+    /// Sand bank just above the waterline; walkable like land.
+    Sandbar,
+    /// Ice floe over deep water; walkable like land.
+    Ice,
+}
+
+impl TileKind {
+    /// Whether fish can swim here.
+    pub fn is_water(self) -> bool {
+        matches!(
+            self,
+            TileKind::ShallowWater
+                | TileKind::DeepWater
+                | TileKind::CoralReef
+                | TileKind::KelpForest
+        )
+    }
+
+    /// Whether this is water shallow enough to wade through.
+    pub fn is_shallow(self) -> bool {
+        self.is_water() && self != TileKind::DeepWater
+    }
+
+    /// Biome this tile belongs to, if any.
+    pub fn biome(self) -> Option<Biome> {
+        match self {
+            TileKind::CoralReef => Some(Biome::CoralReef),
+            TileKind::KelpForest => Some(Biome::KelpForest),
+            TileKind::Sandbar => Some(Biome::Sandbar),
+            TileKind::Ice => Some(Biome::Ice),
+            _ => None,
+        }
+    }
+}
+
+/// Biome noise above which reefs, sandbars and ice form, and below whose
+/// negation kelp grows.
+const BIOME_THRESHOLD: f32 = 0.3;
+/// Terrain noise below which low land is sand rather than solid ground.
+const SANDBAR_HEIGHT: f32 = 0.08;
+/// Terrain noise above which deep water can freeze over.
+const ICE_DEPTH: f32 = -0.35;
+/// Terrain noise either side of the waterline over which the tide floods
+/// and drains the shallows.
+const TIDAL_BAND: f32 = 0.03;
+/// Depth difference in meters across a tile that sets the water moving.
+const FLOW_GRADIENT: i32 = 12;
+/// Depth difference across a tile that makes a strong, two-tile current.
+const STRONG_FLOW_GRADIENT: i32 = 20;
+
+/// Biome that replaces a `kind` tile with terrain noise `v` and biome noise
+/// `b`, among those `area` allows.
+fn biome_at(area: &AreaType, kind: TileKind, v: f32, b: f32) -> Option<Biome> {
+    let biome = match kind {
+        TileKind::Land if v < SANDBAR_HEIGHT && b > BIOME_THRESHOLD => Biome::Sandbar,
+        TileKind::ShallowWater if b > BIOME_THRESHOLD => Biome::CoralReef,
+        TileKind::ShallowWater if b < -BIOME_THRESHOLD => Biome::KelpForest,
+        TileKind::DeepWater if v > ICE_DEPTH && b > BIOME_THRESHOLD => Biome::Ice,
+        _ => return None,
+    };
+    area.biomes.contains(&biome).then_some(biome)
+}
+
+impl From<Biome> for TileKind {
+    fn from(biome: Biome) -> Self {
+        match biome {
+            Biome::CoralReef => TileKind::CoralReef,
+            Biome::KelpForest => TileKind::KelpForest,
+            Biome::Sandbar => TileKind::Sandbar,
+            Biome::Ice => TileKind::Ice,
+        }
+    }
+}
+
+/// A tile on the tidal flats, flooded or left dry by the tide.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TidalTile {
+    /// Height above mean sea level; the tile is under water while the tide
+    /// stands above it.
+    pub height: i32,
+    /// What the tile turns into when the water leaves it.
+    pub dry: TileKind,
 }
 
 /// Simple map representation.
@@ -20,6 +114,16 @@ pub struct Map {
     pub height: u32,
     pub tiles: Vec<TileKind>,
     pub depths: Vec<i32>,
+    /// Current on each tile: the step it carries drifting things each turn.
+    pub flow: Vec<Point>,
+    /// Tiles the player has seen this run.
+    pub explored: Vec<bool>,
+    /// Tidal flats: tiles near the waterline that the tide covers and
+    /// uncovers (see [`Map::apply_tide`]).
+    pub tidal: Vec<Option<TidalTile>>,
+    /// Body of water each tile belonged to when the map was generated, 0
+    /// being the main basin (see [`basins::connect`]); `None` on land.
+    pub basins: Vec<Option<usize>>,
 }
 
 impl Map {
@@ -30,6 +134,10 @@ impl Map {
             height,
             tiles: vec![TileKind::Land; (width * height) as usize],
             depths: vec![0; (width * height) as usize],
+            flow: vec![Point::new(0, 0); (width * height) as usize],
+            explored: vec![false; (width * height) as usize],
+            tidal: vec![None; (width * height) as usize],
+            basins: vec![None; (width * height) as usize],
         }
     }
 
@@ -38,22 +146,149 @@ impl Map {
         (pt.y as usize) * self.width as usize + pt.x as usize
     }
 
+    /// Area covered by the map.
+    pub fn bounds(&self) -> Rect {
+        Rect::new(0, 0, self.width as i32, self.height as i32)
+    }
+
     /// Returns the depth in meters at the given point.
     pub fn depth(&self, pt: Point) -> i32 {
         self.depths[self.idx(pt)]
     }
+
+    /// Returns the current at the given point.
+    pub fn flow(&self, pt: Point) -> Point {
+        self.flow[self.idx(pt)]
+    }
+
+    /// Whether `pt` was water of the main basin when the map was generated.
+    pub fn in_main_basin(&self, pt: Point) -> bool {
+        self.bounds().contains(pt) && self.basins[self.idx(pt)] == Some(0)
+    }
+
+    /// Whether the player has seen the tile at `pt` this run.
+    pub fn is_explored(&self, pt: Point) -> bool {
+        self.explored[self.idx(pt)]
+    }
+
+    /// Whether the current at `pt` carries things two tiles a turn.
+    pub fn strong_flow(&self, pt: Point) -> bool {
+        let flow = self.flow(pt);
+        flow.x.abs().max(flow.y.abs()) > 1
+    }
+
+    /// Where something drifting on `pt` ends up after one turn: carried
+    /// along the current as far as the water goes.
+    pub fn drift(&self, pt: Point) -> Point {
+        let flow = self.flow(pt);
+        let step = flow.signum();
+        let mut pos = pt;
+        for _ in 0..flow.x.abs().max(flow.y.abs()) {
+            let next = pos + step;
+            if !self.bounds().contains(next) || !self.tiles[self.idx(next)].is_water() {
+                break;
+            }
+            pos = next;
+        }
+        pos
+    }
+
+    /// Floods the tidal flats below the tide `level` and drains those above
+    /// it, then recomputes the currents. Level 0 is mean sea level, where
+    /// the map was generated. Returns whether any tile changed.
+    pub fn apply_tide(&mut self, level: i32) -> bool {
+        let mut changed = false;
+        for idx in 0..self.tiles.len() {
+            let Some(flat) = self.tidal[idx] else {
+                continue;
+            };
+            let (kind, depth) = if flat.height < level {
+                (TileKind::ShallowWater, level - flat.height)
+            } else {
+                (flat.dry, 0)
+            };
+            if self.tiles[idx] != kind || self.depths[idx] != depth {
+                self.tiles[idx] = kind;
+                self.depths[idx] = depth;
+                changed = true;
+            }
+        }
+        if changed {
+            self.compute_flow();
+        }
+        changed
+    }
+
+    /// Recomputes the currents from the depths: water runs down the depth
+    /// gradient, from the shallows towards the deeps, and faster where the
+    /// bottom drops steeply. Land has no current.
+    pub fn compute_flow(&mut self) {
+        let bounds = self.bounds();
+        for y in 0..self.height as i32 {
+            for x in 0..self.width as i32 {
+                let pt = Point::new(x, y);
+                let idx = self.idx(pt);
+                if !self.tiles[idx].is_water() {
+                    self.flow[idx] = Point::new(0, 0);
+                    continue;
+                }
+                let depth = |p: Point| {
+                    if bounds.contains(p) {
+                        self.depth(p)
+                    } else {
+                        self.depth(pt)
+                    }
+                };
+                let axis = |ahead: Point, behind: Point| {
+                    let gradient = depth(pt + ahead) - depth(pt + behind);
+                    let speed = match gradient.abs() {
+                        g if g >= STRONG_FLOW_GRADIENT => 2,
+                        g if g >= FLOW_GRADIENT => 1,
+                        _ => 0,
+                    };
+                    gradient.signum() * speed
+                };
+                self.flow[idx] = Point::new(
+                    axis(Point::new(1, 0), Point::new(-1, 0)),
+                    axis(Point::new(0, 1), Point::new(0, -1)),
+                );
+            }
+        }
+    }
 }
 
-/// Generates a map using Perlin noise.
-pub fn generate(seed: u64, width: u32, height: u32) -> GameResult<Map> {
+/// Generates the map of `area` using Perlin noise.
+///
+/// A second noise channel lays the area's biomes over the plain land and
+/// water: reefs and kelp in the shallows, sandbars on low ground and ice
+/// on the shallower deep water. Plain shallows and open ground close to
+/// the waterline become tidal flats. The area's clean-up passes then run
+/// (see [`passes::post_process`]), channels join the sizeable bodies of
+/// water into one basin (see [`basins::connect`]) and currents follow
+/// from the finished depths (see [`Map::compute_flow`]).
+pub fn generate(seed: u64, area: &AreaType) -> GameResult<Map> {
+    generate_chunk(seed, area, Point::new(0, 0))
+}
+
+/// Generates chunk `chunk` of an endless `area`: the map of its size that
+/// lies that many maps across and down from the first one, which is the
+/// map [`generate`] makes. The noise is sampled at world coordinates, so
+/// the land and water run on across the edges between chunks.
+pub fn generate_chunk(seed: u64, area: &AreaType, chunk: Point) -> GameResult<Map> {
+    let (width, height) = (area.width, area.height);
+    let origin = Point::new(chunk.x * width as i32, chunk.y * height as i32);
     let mut map = Map::new(width, height);
     let mut noise = FastNoise::seeded(seed);
     noise.set_noise_type(NoiseType::Perlin);
-    noise.set_frequency(0.08);
+    noise.set_frequency(area.noise_frequency);
+    let mut biomes = FastNoise::seeded(seed.wrapping_add(1));
+    biomes.set_noise_type(NoiseType::Perlin);
+    biomes.set_frequency(area.noise_frequency * 2.0);
 
     for y in 0..height {
         for x in 0..width {
-            let v = noise.get_noise(x as f32, y as f32);
+            let (wx, wy) = ((origin.x + x as i32) as f32, (origin.y + y as i32) as f32);
+            let v = noise.get_noise(wx, wy);
             let kind = if v < -0.2 {
                 TileKind::DeepWater
             } else if v < 0.0 {
@@ -62,15 +297,38 @@ pub fn generate(seed: u64, width: u32, height: u32) -> GameResult<Map> {
                 TileKind::Land
             };
             let idx = map.idx(Point::new(x as i32, y as i32));
+            let b = biomes.get_noise(wx, wy);
+            let kind = biome_at(area, kind, v, b).map_or(kind, TileKind::from);
             map.tiles[idx] = kind;
-            let depth = if kind == TileKind::Land {
+            let depth = if !kind.is_water() {
                 0
             } else {
-                ((-v) * 100.0).round() as i32
+                ((-v) * 100.0 * area.depth_scale).round() as i32
             };
             map.depths[idx] = depth.max(0);
+            let flat = matches!(
+                kind,
+                TileKind::ShallowWater | TileKind::Land | TileKind::Sandbar
+            );
+            if flat && (-TIDAL_BAND..TIDAL_BAND).contains(&v) {
+                let height = (v * 100.0).floor() as i32;
+                let dry = if kind.is_water() {
+                    map.depths[idx] = -height;
+                    TileKind::Sandbar
+                } else {
+                    kind
+                };
+                map.tidal[idx] = Some(TidalTile { height, dry });
+            }
         }
     }
+    passes::post_process(&mut map, &area.generation);
+    basins::connect(&mut map);
+    if area.generation.beaches {
+        // Channels cut through the beaches, so they are laid again
+        passes::add_beaches(&mut map);
+    }
+    map.compute_flow();
 
     println!("Initialized crate: mapgen");
     Ok(map)
@@ -80,9 +338,16 @@ pub fn generate(seed: u64, width: u32, height: u32) -> GameResult<Map> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn bounds_cover_map() {
+        let map = Map::new(4, 3);
+        assert_eq!(map.bounds(), Rect::new(0, 0, 4, 3));
+        assert!(map.bounds().contains(Point::new(3, 2)));
+    }
+
     #[test]
     fn generate_map() {
-        let map = generate(0, 120, 80).expect("map");
+        let map = generate(0, &AreaType::new("Test", 120, 80)).expect("map");
         assert_eq!(map.width, 120);
         assert_eq!(map.height, 80);
         assert_eq!(map.tiles.len(), 120 * 80);
@@ -91,11 +356,107 @@ mod tests {
 
     #[test]
     fn snapshot_seed_0() {
-        let map = generate(0, 120, 80).expect("map");
+        let map = generate(0, &AreaType::new("Test", 120, 80)).expect("map");
         let expected = include_str!("snapshot_seed0.txt").replace('\r', "");
         assert_eq!(format!("{:?}\n", map), expected);
     }
 
+    #[test]
+    fn depth_scale_deepens_water() {
+        let shallow = generate(0, &AreaType::new("Test", 40, 30)).expect("map");
+        let area = AreaType {
+            depth_scale: 2.0,
+            ..AreaType::new("Test", 40, 30)
+        };
+        let deep = generate(0, &area).expect("map");
+        assert_eq!(shallow.tiles, deep.tiles);
+        let max = |m: &Map| m.depths.iter().copied().max().unwrap();
+        assert!(max(&deep) > max(&shallow));
+    }
+
+    #[test]
+    fn biomes_only_where_the_area_allows() {
+        let plain = generate(3, &AreaType::new("Test", 120, 80)).expect("map");
+        assert!(plain.tiles.iter().all(|t| t.biome().is_none()));
+        let area = AreaType {
+            biomes: vec![Biome::CoralReef, Biome::KelpForest, Biome::Sandbar],
+            ..AreaType::new("Test", 120, 80)
+        };
+        let map = generate(3, &area).expect("map");
+        for biome in &area.biomes {
+            assert!(map.tiles.contains(&TileKind::from(*biome)), "{:?}", biome);
+        }
+        assert!(!map.tiles.contains(&TileKind::Ice));
+        for (i, tile) in map.tiles.iter().enumerate() {
+            match plain.tiles[i] {
+                TileKind::ShallowWater => assert!(tile.is_shallow()),
+                kind => assert!(*tile == kind || *tile == TileKind::Sandbar),
+            }
+            if !tile.is_water() {
+                assert_eq!(map.depths[i], 0);
+            }
+        }
+        let frozen = AreaType {
+            biomes: vec![Biome::Ice],
+            ..AreaType::new("Test", 120, 80)
+        };
+        let map = generate(3, &frozen).expect("map");
+        assert!(map.tiles.contains(&TileKind::Ice));
+    }
+
+    #[test]
+    fn tide_floods_and_drains_the_flats() {
+        let mut map = generate(0, &AreaType::new("Test", 120, 80)).expect("map");
+        let start = map.tiles.clone();
+        assert!(map.tidal.iter().any(Option::is_some));
+        assert!(!map.apply_tide(0), "mean sea level is where the map began");
+        assert!(map.apply_tide(3));
+        let water = |m: &Map| m.tiles.iter().filter(|t| t.is_water()).count();
+        let high = water(&map);
+        assert!(map.apply_tide(-3));
+        assert!(water(&map) < high);
+        for (i, flat) in map.tidal.iter().enumerate() {
+            match flat {
+                Some(f) => assert_eq!(map.tiles[i], f.dry),
+                None => assert_eq!(map.tiles[i], start[i]),
+            }
+        }
+        let depths = generate(0, &AreaType::new("Test", 120, 80)).unwrap().depths;
+        map.apply_tide(0);
+        assert_eq!(map.tiles, start);
+        assert_eq!(map.depths, depths);
+    }
+
+    #[test]
+    fn passes_clean_up_generated_maps() {
+        let area = AreaType {
+            generation: data::GenParams {
+                smoothing: 2,
+                min_region: 6,
+                beaches: true,
+            },
+            ..AreaType::new("Test", 120, 80)
+        };
+        let map = generate(0, &area).expect("map");
+        let raw = generate(0, &AreaType::new("Test", 120, 80)).expect("map");
+        assert_ne!(map.tiles, raw.tiles);
+        let wet = |pt: Point| map.bounds().contains(pt) && map.tiles[map.idx(pt)].is_water();
+        for y in 1..79 {
+            for x in 1..119 {
+                let pt = Point::new(x, y);
+                let steps = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+                let alike = steps
+                    .iter()
+                    .filter(|s| wet(pt + Point::new(s.0, s.1)) == wet(pt))
+                    .count();
+                assert!(alike > 0, "speck at {:?}", pt);
+                if map.tiles[map.idx(pt)] == TileKind::Land {
+                    assert!(!wet(pt + Point::new(1, 0)) && !wet(pt - Point::new(1, 0)));
+                }
+            }
+        }
+    }
+
     #[test]
     fn index_calculation() {
         let map = Map::new(10, 10);
@@ -108,11 +469,44 @@ mod tests {
         let map = Map::new(4, 3);
         assert!(map.tiles.iter().all(|&t| t == TileKind::Land));
         assert!(map.depths.iter().all(|&d| d == 0));
+        assert!(map.explored.iter().all(|&e| !e));
     }
 
     #[test]
     fn generated_map_has_water() {
-        let map = generate(1, 120, 80).expect("map");
+        let map = generate(1, &AreaType::new("Test", 120, 80)).expect("map");
         assert!(map.tiles.iter().any(|&t| t != TileKind::Land));
     }
+
+    #[test]
+    fn currents_run_down_the_slope() {
+        let mut map = Map::new(8, 3);
+        for x in 0..8 {
+            for y in 0..3 {
+                let idx = map.idx(Point::new(x, y));
+                map.tiles[idx] = TileKind::ShallowWater;
+                map.depths[idx] = x * x;
+            }
+        }
+        let idx = map.idx(Point::new(0, 1));
+        map.tiles[idx] = TileKind::Land;
+        map.compute_flow();
+        assert_eq!(map.flow(Point::new(0, 1)), Point::new(0, 0));
+        assert_eq!(map.flow(Point::new(1, 1)), Point::new(0, 0));
+        assert_eq!(map.flow(Point::new(3, 1)), Point::new(1, 0));
+        assert!(!map.strong_flow(Point::new(3, 1)));
+        assert!(map.strong_flow(Point::new(5, 1)));
+        assert_eq!(map.drift(Point::new(5, 1)), Point::new(7, 1));
+        // The map edge stops the drift
+        assert_eq!(map.drift(Point::new(6, 1)), Point::new(7, 1));
+    }
+
+    #[test]
+    fn generated_maps_have_currents() {
+        let map = generate(0, &AreaType::new("Test", 120, 80)).expect("map");
+        assert!(map.flow.iter().any(|f| *f != Point::new(0, 0)));
+        for (tile, flow) in map.tiles.iter().zip(&map.flow) {
+            assert!(tile.is_water() || *flow == Point::new(0, 0));
+        }
+    }
 }
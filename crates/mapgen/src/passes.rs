@@ -0,0 +1,231 @@
+//! Post-processing passes run over a freshly generated map (see
+//! [`data::GenParams`]): smoothing evens out jagged coasts, culling fills
+//! in specks of land and water, and beaches ring the land with sand.
+use crate::{Map, TileKind};
+use common::Point;
+use data::GenParams;
+
+/// Wet neighbours, of eight, that turn a land tile into water.
+const FLOOD_NEIGHBOURS: usize = 5;
+/// Wet neighbours, of eight, at or below which a water tile silts up.
+const SILT_NEIGHBOURS: usize = 3;
+
+/// Runs the passes `params` turns on, in order: smoothing, culling, then
+/// beaches.
+pub fn post_process(map: &mut Map, params: &GenParams) {
+    for _ in 0..params.smoothing {
+        smooth(map);
+    }
+    if params.min_region > 0 {
+        cull_regions(map, params.min_region);
+    }
+    if params.beaches {
+        add_beaches(map);
+    }
+}
+
+fn neighbours(pt: Point) -> impl Iterator<Item = Point> {
+    (-1..=1)
+        .flat_map(move |dy| (-1..=1).map(move |dx| pt + Point::new(dx, dy)))
+        .filter(move |&n| n != pt)
+}
+
+/// Average depth of the water tiles next to `tiles`, at least 1m.
+fn shore_depth(map: &Map, tiles: &[usize]) -> i32 {
+    let depths: Vec<i32> = tiles
+        .iter()
+        .flat_map(|&idx| neighbours(point(map, idx)))
+        .filter(|&n| map.bounds().contains(n) && map.tiles[map.idx(n)].is_water())
+        .map(|n| map.depth(n))
+        .collect();
+    if depths.is_empty() {
+        return 1;
+    }
+    (depths.iter().sum::<i32>() / depths.len() as i32).max(1)
+}
+
+fn point(map: &Map, idx: usize) -> Point {
+    Point::new(
+        (idx % map.width as usize) as i32,
+        (idx / map.width as usize) as i32,
+    )
+}
+
+/// Turns the tile at `idx` into plain land or shallow water `depth` deep.
+/// It is no longer a tidal flat.
+fn set(map: &mut Map, idx: usize, water: bool, depth: i32) {
+    map.tiles[idx] = if water {
+        TileKind::ShallowWater
+    } else {
+        TileKind::Land
+    };
+    map.depths[idx] = if water { depth } else { 0 };
+    map.tidal[idx] = None;
+}
+
+/// One round of cellular-automata smoothing: land with most of its
+/// neighbours wet floods and water with few wet neighbours silts up.
+/// Beyond the map edge counts as more of the tile itself.
+fn smooth(map: &mut Map) {
+    let wet: Vec<bool> = map.tiles.iter().map(|t| t.is_water()).collect();
+    let mut changes = Vec::new();
+    for (idx, &here) in wet.iter().enumerate() {
+        let count = neighbours(point(map, idx))
+            .filter(|&n| {
+                if map.bounds().contains(n) {
+                    wet[map.idx(n)]
+                } else {
+                    here
+                }
+            })
+            .count();
+        if !here && count >= FLOOD_NEIGHBOURS {
+            changes.push((idx, true, shore_depth(map, &[idx])));
+        } else if here && count <= SILT_NEIGHBOURS {
+            changes.push((idx, false, 0));
+        }
+    }
+    for (idx, water, depth) in changes {
+        set(map, idx, water, depth);
+    }
+}
+
+/// Fills in islands and ponds of fewer than `min` tiles with what
+/// surrounds them. Regions touching the map edge may go on past it and are
+/// kept.
+fn cull_regions(map: &mut Map, min: usize) {
+    let mut seen = vec![false; map.tiles.len()];
+    for start in 0..map.tiles.len() {
+        if seen[start] {
+            continue;
+        }
+        let wet = map.tiles[start].is_water();
+        let mut region = vec![start];
+        let mut open = vec![start];
+        let mut at_edge = false;
+        seen[start] = true;
+        while let Some(idx) = open.pop() {
+            let pt = point(map, idx);
+            for step in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+                let n = pt + Point::new(step.0, step.1);
+                if !map.bounds().contains(n) {
+                    at_edge = true;
+                    continue;
+                }
+                let ni = map.idx(n);
+                if !seen[ni] && map.tiles[ni].is_water() == wet {
+                    seen[ni] = true;
+                    region.push(ni);
+                    open.push(ni);
+                }
+            }
+        }
+        if at_edge || region.len() >= min {
+            continue;
+        }
+        let depth = shore_depth(map, &region);
+        for idx in region {
+            set(map, idx, !wet, depth);
+        }
+    }
+}
+
+/// Rings the land with beach: plain land next to water becomes sandbar,
+/// and so do tidal flats that dry out to it.
+pub(crate) fn add_beaches(map: &mut Map) {
+    for idx in 0..map.tiles.len() {
+        let shore = neighbours(point(map, idx))
+            .any(|n| map.bounds().contains(n) && map.tiles[map.idx(n)].is_water());
+        if !shore {
+            continue;
+        }
+        if map.tiles[idx] == TileKind::Land {
+            map.tiles[idx] = TileKind::Sandbar;
+        }
+        if let Some(flat) = map.tidal[idx].as_mut() {
+            if flat.dry == TileKind::Land {
+                flat.dry = TileKind::Sandbar;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Land on the west half, water 10m deep on the east half.
+    fn coast() -> Map {
+        let mut map = Map::new(12, 8);
+        for y in 0..8 {
+            for x in 6..12 {
+                let idx = map.idx(Point::new(x, y));
+                map.tiles[idx] = TileKind::ShallowWater;
+                map.depths[idx] = 10;
+            }
+        }
+        map
+    }
+
+    fn tile(map: &Map, x: i32, y: i32) -> TileKind {
+        map.tiles[map.idx(Point::new(x, y))]
+    }
+
+    #[test]
+    fn smoothing_evens_out_the_coast() {
+        let mut map = coast();
+        let speck = map.idx(Point::new(2, 4));
+        map.tiles[speck] = TileKind::ShallowWater;
+        let notch = map.idx(Point::new(6, 2));
+        map.tiles[notch] = TileKind::Land;
+        map.depths[notch] = 0;
+        post_process(
+            &mut map,
+            &GenParams {
+                smoothing: 1,
+                ..GenParams::default()
+            },
+        );
+        assert_eq!(tile(&map, 2, 4), TileKind::Land);
+        assert_eq!(tile(&map, 6, 2), TileKind::ShallowWater);
+        assert_eq!(map.depth(Point::new(6, 2)), 10);
+        assert_eq!(map.tiles, coast().tiles);
+    }
+
+    #[test]
+    fn small_regions_are_culled_unless_at_the_edge() {
+        let mut map = coast();
+        for pt in [Point::new(8, 3), Point::new(9, 3), Point::new(11, 0)] {
+            let idx = map.idx(pt);
+            map.tiles[idx] = TileKind::Land;
+            map.depths[idx] = 0;
+        }
+        post_process(
+            &mut map,
+            &GenParams {
+                min_region: 3,
+                ..GenParams::default()
+            },
+        );
+        assert_eq!(tile(&map, 8, 3), TileKind::ShallowWater);
+        assert_eq!(map.depth(Point::new(9, 3)), 10);
+        assert_eq!(tile(&map, 11, 0), TileKind::Land, "may go on past the edge");
+    }
+
+    #[test]
+    fn beaches_ring_the_land() {
+        let mut map = coast();
+        post_process(
+            &mut map,
+            &GenParams {
+                beaches: true,
+                ..GenParams::default()
+            },
+        );
+        for y in 0..8 {
+            assert_eq!(tile(&map, 5, y), TileKind::Sandbar);
+            assert_eq!(tile(&map, 4, y), TileKind::Land);
+            assert_eq!(tile(&map, 6, y), TileKind::ShallowWater);
+        }
+    }
+}
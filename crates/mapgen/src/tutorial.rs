@@ -0,0 +1,91 @@
+//! The tutorial's map: a small fixed stretch of coast, the same every
+//! time, with a beach to walk, shallows to cast into and deep water
+//! beyond.
+use crate::{Map, TileKind};
+use common::Point;
+
+/// The map row by row: `.` is land, `~` shallow water, `≈` deep water and
+/// `@` the land the player starts on.
+const LAYOUT: [&str; 16] = [
+    "............~~~~~~~~~≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈",
+    ".............~~~~~~~~~≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈",
+    "..............~~~~~~~~≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈",
+    "..............~~~~~~~~~≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈",
+    "...............~~~~~~~~≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈",
+    "...............~~~~~~~~~≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈",
+    "..............~~~~~~~~~~≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈",
+    "......@.......~~~~~~~~~~≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈",
+    "..............~~~~~~~~~~≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈",
+    "...............~~~~~~~~~≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈",
+    "...............~~~~~~~~≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈",
+    "..............~~~~~~~~~≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈",
+    "..............~~~~~~~~≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈",
+    ".............~~~~~~~~~≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈",
+    "............~~~~~~~~~≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈",
+    "...........~~~~~~~~~≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈≈",
+];
+/// Meters the shallows deepen per tile out from the shore.
+const SHALLOW_SLOPE: i32 = 2;
+/// Depth in meters where the deep water begins.
+const DEEP_DEPTH: i32 = 22;
+/// Meters the deep water deepens per tile out from its edge.
+const DEEP_SLOPE: i32 = 3;
+
+/// The tutorial map, with its currents.
+pub fn map() -> Map {
+    let mut map = Map::new(LAYOUT[0].chars().count() as u32, LAYOUT.len() as u32);
+    for (y, row) in LAYOUT.iter().enumerate() {
+        let (mut shallow, mut deep) = (0, 0);
+        for (x, c) in row.chars().enumerate() {
+            let idx = map.idx(Point::new(x as i32, y as i32));
+            let (tile, depth) = match c {
+                '~' => {
+                    shallow += 1;
+                    (TileKind::ShallowWater, shallow * SHALLOW_SLOPE)
+                }
+                '≈' => {
+                    deep += 1;
+                    (TileKind::DeepWater, DEEP_DEPTH + deep * DEEP_SLOPE)
+                }
+                _ => (TileKind::Land, 0),
+            };
+            map.tiles[idx] = tile;
+            map.depths[idx] = depth;
+            map.basins[idx] = tile.is_water().then_some(0);
+        }
+    }
+    map.compute_flow();
+    map
+}
+
+/// Where the player starts on the tutorial map.
+pub fn start() -> Point {
+    LAYOUT
+        .iter()
+        .enumerate()
+        .find_map(|(y, row)| {
+            row.chars()
+                .position(|c| c == '@')
+                .map(|x| Point::new(x as i32, y as i32))
+        })
+        .expect("start")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_start_is_ashore_within_a_walk_of_the_shallows() {
+        let map = map();
+        let start = start();
+        assert_eq!(map.tiles[map.idx(start)], TileKind::Land);
+        let shallows = (0..map.width as i32)
+            .map(|x| Point::new(x, start.y))
+            .find(|&p| map.tiles[map.idx(p)] == TileKind::ShallowWater)
+            .expect("shallows");
+        assert!(shallows.x - start.x <= 10);
+        assert!(map.depth(shallows) < map.depth(Point::new(39, start.y)));
+        assert_eq!(map.tiles, super::map().tiles, "the same every time");
+    }
+}
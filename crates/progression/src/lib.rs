@@ -0,0 +1,208 @@
+//! Angler progression kept between runs.
+//!
+//! Catches earn experience; levels unlock [`Skill`]s whose [`Modifiers`]
+//! every new run starts with. The progress is stored in the player's
+//! profile next to the codex unlocks.
+
+use serde::{Deserialize, Serialize};
+
+/// Experience needed to go from level 1 to level 2; each level after
+/// needs that much more than the one before.
+pub const LEVEL_XP: u32 = 100;
+
+/// A perk unlocked by reaching its level.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Skill {
+    /// Reels harder.
+    QuickReel,
+    /// Gets hungry more slowly.
+    IronStomach,
+    /// Casts farther.
+    LongCast,
+    /// Buys for less and sells for more.
+    Haggler,
+}
+
+impl Skill {
+    pub const ALL: [Skill; 4] = [
+        Skill::QuickReel,
+        Skill::IronStomach,
+        Skill::LongCast,
+        Skill::Haggler,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Skill::QuickReel => "Quick Reel",
+            Skill::IronStomach => "Iron Stomach",
+            Skill::LongCast => "Long Cast",
+            Skill::Haggler => "Haggler",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Skill::QuickReel => "reel 15% harder",
+            Skill::IronStomach => "get hungry 20% more slowly",
+            Skill::LongCast => "cast 2 tiles farther",
+            Skill::Haggler => "10% better prices at the merchant",
+        }
+    }
+
+    /// Angler level that unlocks the skill.
+    pub fn level(self) -> u32 {
+        match self {
+            Skill::QuickReel => 2,
+            Skill::IronStomach => 3,
+            Skill::LongCast => 4,
+            Skill::Haggler => 5,
+        }
+    }
+}
+
+/// How the unlocked skills change a run.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Modifiers {
+    /// Multiplier on reeling strength.
+    pub reel: f32,
+    /// Hunger lost, in percent of the usual.
+    pub hunger: u32,
+    /// Tiles added to the cast range.
+    pub cast_range: i32,
+    /// Percent off the merchant's prices, and on top of what they pay.
+    pub haggle: i32,
+}
+
+impl Default for Modifiers {
+    fn default() -> Self {
+        Self {
+            reel: 1.0,
+            hunger: 100,
+            cast_range: 0,
+            haggle: 0,
+        }
+    }
+}
+
+impl Modifiers {
+    /// What the merchant asks for something priced `price`.
+    pub fn buy_price(&self, price: i32) -> i32 {
+        (price * (100 - self.haggle) / 100).max(1)
+    }
+
+    /// What the merchant pays for something priced `price`.
+    pub fn sell_price(&self, price: i32) -> i32 {
+        price * (100 + self.haggle) / 100
+    }
+}
+
+/// Experience earned over every run.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Progress {
+    pub xp: u32,
+}
+
+impl Progress {
+    /// Total experience needed to reach `level`.
+    pub fn xp_for_level(level: u32) -> u32 {
+        let steps = level.saturating_sub(1);
+        LEVEL_XP * steps * (steps + 1) / 2
+    }
+
+    /// Angler level, from 1.
+    pub fn level(&self) -> u32 {
+        let mut level = 1;
+        while self.xp >= Self::xp_for_level(level + 1) {
+            level += 1;
+        }
+        level
+    }
+
+    /// Skills unlocked so far.
+    pub fn skills(&self) -> Vec<Skill> {
+        let level = self.level();
+        Skill::ALL
+            .into_iter()
+            .filter(|s| s.level() <= level)
+            .collect()
+    }
+
+    /// Adds `xp` and returns the skills it unlocked.
+    pub fn gain(&mut self, xp: u32) -> Vec<Skill> {
+        let before = self.level();
+        self.xp += xp;
+        let after = self.level();
+        Skill::ALL
+            .into_iter()
+            .filter(|s| (before + 1..=after).contains(&s.level()))
+            .collect()
+    }
+
+    /// Modifiers of the unlocked skills.
+    pub fn modifiers(&self) -> Modifiers {
+        let mut mods = Modifiers::default();
+        for skill in self.skills() {
+            match skill {
+                Skill::QuickReel => mods.reel = 1.15,
+                Skill::IronStomach => mods.hunger = 80,
+                Skill::LongCast => mods.cast_range = 2,
+                Skill::Haggler => mods.haggle = 10,
+            }
+        }
+        mods
+    }
+
+    /// Level and experience as shown on the menu.
+    pub fn summary(&self) -> String {
+        let level = self.level();
+        format!(
+            "Angler level {} ({}/{} XP)",
+            level,
+            self.xp,
+            Self::xp_for_level(level + 1)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels_need_more_experience_each_time() {
+        assert_eq!(Progress::xp_for_level(1), 0);
+        assert_eq!(Progress::xp_for_level(2), 100);
+        assert_eq!(Progress::xp_for_level(3), 300);
+        assert_eq!(Progress { xp: 99 }.level(), 1);
+        assert_eq!(Progress { xp: 300 }.level(), 3);
+        assert_eq!(
+            Progress { xp: 120 }.summary(),
+            "Angler level 2 (120/300 XP)"
+        );
+    }
+
+    #[test]
+    fn gaining_levels_unlocks_skills() {
+        let mut progress = Progress::default();
+        assert!(progress.gain(50).is_empty());
+        assert_eq!(
+            progress.gain(300),
+            vec![Skill::QuickReel, Skill::IronStomach]
+        );
+        assert_eq!(progress.modifiers().hunger, 80);
+        assert_eq!(progress.modifiers().cast_range, 0);
+        assert_eq!(Progress::default().modifiers(), Modifiers::default());
+    }
+
+    #[test]
+    fn haggling_bends_prices() {
+        let mods = Modifiers {
+            haggle: 10,
+            ..Modifiers::default()
+        };
+        assert_eq!(mods.buy_price(50), 45);
+        assert_eq!(mods.sell_price(50), 55);
+        assert_eq!(mods.buy_price(1), 1);
+    }
+}
@@ -0,0 +1,349 @@
+//! Quests loaded from `assets/quests.json` and progressed by game events.
+//!
+//! The game reports [`QuestEvent`]s to a [`QuestLog`], which returns the
+//! quests they completed so the game can hand out the [`Reward`]s. A quest
+//! naming another in `requires` stays hidden until that one is done, so
+//! quests can be chained.
+
+use common::{GameError, GameResult};
+use data::{FightStyle, FishType, ItemType};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// What a quest asks the player to do.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub enum Objective {
+    /// Land `count` fish, optionally only of one fight style, only
+    /// legendary ones or only in one area (by id such as `DeepSea`).
+    Catch {
+        count: u32,
+        #[serde(default)]
+        style: Option<FightStyle>,
+        #[serde(default)]
+        legendary: bool,
+        #[serde(default)]
+        area: Option<String>,
+    },
+    /// Stay alive for `turns` turns after the quest becomes active.
+    Survive { turns: u32 },
+}
+
+impl Objective {
+    /// Progress needed to complete the objective.
+    pub fn target(&self) -> u32 {
+        match self {
+            Objective::Catch { count, .. } => *count,
+            Objective::Survive { turns } => *turns,
+        }
+    }
+
+    /// Whether `event` counts towards the objective.
+    fn counts(&self, event: &QuestEvent) -> bool {
+        match (self, event) {
+            (
+                Objective::Catch {
+                    style,
+                    legendary,
+                    area,
+                    ..
+                },
+                QuestEvent::Catch { fish, area: at },
+            ) => {
+                !matches!(style, Some(s) if *s != fish.fight_style)
+                    && (!legendary || fish.legendary)
+                    && !matches!(area.as_deref(), Some(a) if a != *at)
+            }
+            (Objective::Survive { .. }, QuestEvent::Turn) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Something granted when a quest is completed.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub enum Reward {
+    /// A piece of gear added to the player's items.
+    Item(ItemType),
+    /// Factor applied to the final score of the run.
+    ScoreMultiplier(f32),
+    /// Moves the run on to the area with this id.
+    UnlockArea(String),
+}
+
+/// One quest definition.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct Quest {
+    pub id: String,
+    /// Short text shown in the quest panel.
+    pub title: String,
+    pub objective: Objective,
+    #[serde(default)]
+    pub rewards: Vec<Reward>,
+    /// Id of the quest that must be completed first.
+    #[serde(default)]
+    pub requires: Option<String>,
+}
+
+/// Something that happened in the game that quests may count.
+#[derive(Clone, Copy, Debug)]
+pub enum QuestEvent<'a> {
+    /// A fish was landed in the area with id `area`.
+    Catch { fish: &'a FishType, area: &'a str },
+    /// A turn passed.
+    Turn,
+}
+
+/// Progress of every quest, as stored in save files.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuestProgress {
+    /// Progress towards the objective of each started quest.
+    pub counts: BTreeMap<String, u32>,
+    /// Ids of completed quests.
+    pub completed: BTreeSet<String>,
+}
+
+/// The quests of a run and how far along each one is.
+#[derive(Clone, Debug, Default)]
+pub struct QuestLog {
+    quests: Vec<Quest>,
+    progress: QuestProgress,
+}
+
+impl QuestLog {
+    /// Starts a log with no progress on `quests`.
+    pub fn new(quests: Vec<Quest>) -> Self {
+        Self {
+            quests,
+            progress: QuestProgress::default(),
+        }
+    }
+
+    /// Every quest definition in file order.
+    pub fn quests(&self) -> &[Quest] {
+        &self.quests
+    }
+
+    /// Whether the quest with `id` has been completed.
+    pub fn is_completed(&self, id: &str) -> bool {
+        self.progress.completed.contains(id)
+    }
+
+    fn is_active(&self, quest: &Quest) -> bool {
+        !self.is_completed(&quest.id)
+            && match quest.requires.as_deref() {
+                None => true,
+                Some(id) => self.is_completed(id),
+            }
+    }
+
+    /// Quests that can currently be progressed, with their progress.
+    pub fn active(&self) -> impl Iterator<Item = (&Quest, u32)> {
+        self.quests
+            .iter()
+            .filter(|q| self.is_active(q))
+            .map(|q| (q, self.progress.counts.get(&q.id).copied().unwrap_or(0)))
+    }
+
+    /// Counts `event` towards the active quests and returns those it
+    /// completed. Quests unlocked by a completion only count later events.
+    pub fn record(&mut self, event: QuestEvent) -> Vec<Quest> {
+        let counted: Vec<usize> = (0..self.quests.len())
+            .filter(|&i| {
+                let quest = &self.quests[i];
+                self.is_active(quest) && quest.objective.counts(&event)
+            })
+            .collect();
+        let mut done = Vec::new();
+        for i in counted {
+            let quest = &self.quests[i];
+            let count = self.progress.counts.entry(quest.id.clone()).or_insert(0);
+            *count += 1;
+            if *count >= quest.objective.target() {
+                self.progress.counts.remove(&quest.id);
+                self.progress.completed.insert(quest.id.clone());
+                done.push(quest.clone());
+            }
+        }
+        done
+    }
+
+    /// Product of the score multipliers of completed quests.
+    pub fn score_multiplier(&self) -> f32 {
+        self.quests
+            .iter()
+            .filter(|q| self.is_completed(&q.id))
+            .flat_map(|q| &q.rewards)
+            .map(|r| match r {
+                Reward::ScoreMultiplier(factor) => *factor,
+                _ => 1.0,
+            })
+            .product()
+    }
+
+    /// Items any quest can grant, for resolving saved gear.
+    pub fn reward_items(&self) -> impl Iterator<Item = &ItemType> {
+        self.quests
+            .iter()
+            .flat_map(|q| &q.rewards)
+            .filter_map(|r| match r {
+                Reward::Item(item) => Some(item),
+                _ => None,
+            })
+    }
+
+    /// Current progress, for saving.
+    pub fn progress(&self) -> &QuestProgress {
+        &self.progress
+    }
+
+    /// Replaces the progress with a saved one.
+    pub fn restore(&mut self, progress: QuestProgress) {
+        self.progress = progress;
+    }
+}
+
+/// Loads quests from the given JSON file path.
+pub fn load_quests(path: &str) -> GameResult<Vec<Quest>> {
+    let data = std::fs::read_to_string(path)?;
+    parse_quests(&data)
+}
+
+/// Loads the quests embedded at compile time (used on WASM).
+pub fn load_quests_embedded() -> GameResult<Vec<Quest>> {
+    parse_quests(include_str!("../../../assets/quests.json"))
+}
+
+/// Parses quest definitions, rejecting duplicate ids, unknown
+/// requirements and objectives that are already met.
+pub fn parse_quests(data: &str) -> GameResult<Vec<Quest>> {
+    let quests: Vec<Quest> =
+        serde_json::from_str(data).map_err(|e| GameError::Parse(format!("quests: {}", e)))?;
+    let mut ids = BTreeSet::new();
+    for quest in &quests {
+        if !ids.insert(quest.id.as_str()) {
+            return Err(GameError::Parse(format!(
+                "quests: duplicate id {}",
+                quest.id
+            )));
+        }
+        if quest.objective.target() == 0 {
+            return Err(GameError::Parse(format!(
+                "quests: {} has an empty objective",
+                quest.id
+            )));
+        }
+    }
+    if let Some(quest) = quests
+        .iter()
+        .find(|q| matches!(q.requires.as_deref(), Some(id) if !ids.contains(id)))
+    {
+        return Err(GameError::Parse(format!(
+            "quests: {} requires an unknown quest",
+            quest.id
+        )));
+    }
+    Ok(quests)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fish(style: FightStyle, legendary: bool) -> FishType {
+        FishType {
+            id: "F".into(),
+            name: "F".into(),
+            rarity: 0.5,
+            strength: 1,
+            min_depth: 0,
+            max_depth: 10,
+            fight_style: style,
+            legendary,
+            description: String::new(),
+            habitat_note: String::new(),
+            diet: Vec::new(),
+            min_weight: 1.0,
+            max_weight: 1.0,
+            biomes: Vec::new(),
+            boss: None,
+            seasons: Vec::new(),
+        }
+    }
+
+    const CHAIN: &str = r#"[
+        {"id": "A", "title": "Catch 2 Evasive fish",
+         "objective": {"Catch": {"count": 2, "style": "Evasive"}},
+         "rewards": [{"ScoreMultiplier": 1.5}]},
+        {"id": "B", "title": "Catch a legendary in DeepSea", "requires": "A",
+         "objective": {"Catch": {"count": 1, "legendary": true, "area": "DeepSea"}},
+         "rewards": [{"UnlockArea": "DeepSea"}, {"ScoreMultiplier": 2.0}]},
+        {"id": "C", "title": "Survive 2 turns",
+         "objective": {"Survive": {"turns": 2}},
+         "rewards": [{"Item": {"id": "L", "name": "Lure", "kind": "Lure", "bite_bonus": 0.2}}]}
+    ]"#;
+
+    #[test]
+    fn catches_progress_matching_quests() {
+        let mut log = QuestLog::new(parse_quests(CHAIN).unwrap());
+        let evasive = fish(FightStyle::Evasive, true);
+        let other = fish(FightStyle::Endurance, false);
+        assert!(log
+            .record(QuestEvent::Catch {
+                fish: &other,
+                area: "Coast"
+            })
+            .is_empty());
+        let catch = QuestEvent::Catch {
+            fish: &evasive,
+            area: "DeepSea",
+        };
+        assert!(log.record(catch).is_empty());
+        assert_eq!(log.active().find(|(q, _)| q.id == "A").unwrap().1, 1);
+
+        // The catch completing A does not also count towards B
+        let done = log.record(catch);
+        assert_eq!(done.len(), 1);
+        assert_eq!(done[0].id, "A");
+        assert_eq!(log.active().find(|(q, _)| q.id == "B").unwrap().1, 0);
+        assert_eq!(log.record(catch)[0].id, "B");
+        assert_eq!(log.score_multiplier(), 3.0);
+    }
+
+    #[test]
+    fn survive_counts_turns() {
+        let mut log = QuestLog::new(parse_quests(CHAIN).unwrap());
+        assert!(log.record(QuestEvent::Turn).is_empty());
+        let done = log.record(QuestEvent::Turn);
+        assert_eq!(done[0].id, "C");
+        assert!(matches!(&done[0].rewards[0], Reward::Item(item) if item.reel_factor == 1.0));
+        assert!(log.active().all(|(q, _)| q.id != "C"));
+    }
+
+    #[test]
+    fn progress_round_trips() {
+        let mut log = QuestLog::new(parse_quests(CHAIN).unwrap());
+        log.record(QuestEvent::Turn);
+        let json = serde_json::to_string(log.progress()).unwrap();
+        let mut restored = QuestLog::new(parse_quests(CHAIN).unwrap());
+        restored.restore(serde_json::from_str(&json).unwrap());
+        assert_eq!(restored.progress(), log.progress());
+    }
+
+    #[test]
+    fn bad_quests_are_rejected() {
+        let dup = r#"[{"id": "A", "title": "", "objective": {"Survive": {"turns": 1}}},
+                      {"id": "A", "title": "", "objective": {"Survive": {"turns": 1}}}]"#;
+        let unknown = r#"[{"id": "A", "title": "", "requires": "Z",
+                           "objective": {"Survive": {"turns": 1}}}]"#;
+        let empty = r#"[{"id": "A", "title": "", "objective": {"Catch": {"count": 0}}}]"#;
+        for json in [dup, unknown, empty, "not json"] {
+            assert!(matches!(parse_quests(json), Err(GameError::Parse(_))));
+        }
+    }
+
+    #[test]
+    fn embedded_quests_load() {
+        assert!(!load_quests_embedded().unwrap().is_empty());
+    }
+}
@@ -1,8 +1,20 @@
 //! UI context stubs.
 use bracket_lib::prelude::{
-    BTerm, VirtualKeyCode, CYAN, GRAY, GREEN, NAVY, RED, RGB, WHITE, YELLOW,
+    VirtualKeyCode, BLACK, CYAN, GOLD, GRAY, GREEN, MAGENTA, NAVY, ORANGE, RED, RGB, WHITE, YELLOW,
 };
 
+mod messages;
+mod render;
+#[cfg(feature = "tui")]
+mod terminal;
+pub mod widgets;
+
+pub use messages::{LogCategory, LogEntry};
+pub use render::{Renderer, TextBuffer};
+#[cfg(feature = "tui")]
+pub use terminal::TerminalRenderer;
+use widgets::{Bar, ListView, Panel, Theme};
+
 /// UI layout type.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum UILayout {
@@ -16,6 +28,18 @@ pub enum UILayout {
     Help,
     /// Layout showing game options.
     Options,
+    /// Layout listing achievements, opened from the options.
+    Achievements,
+    /// Layout listing every species in the codex.
+    Codex,
+    /// Layout editing the key bindings, opened from the options.
+    Keybindings,
+    /// Layout showing the whole message log, filtered and searched.
+    MessageLog,
+    /// Layout showing the card of a fish just landed over the map.
+    CatchCard,
+    /// Layout showing the travel journal of the run.
+    Journal,
 }
 
 /// Color palette for map and entity rendering.
@@ -24,9 +48,23 @@ pub struct ColorPalette {
     pub land: RGB,
     pub shallow: RGB,
     pub deep: RGB,
+    /// Coral reef biome tiles.
+    pub coral: RGB,
+    /// Kelp forest biome tiles.
+    pub kelp: RGB,
+    /// Sandbar biome tiles.
+    pub sand: RGB,
+    /// Ice biome tiles.
+    pub ice: RGB,
     pub player: RGB,
     pub fish: RGB,
+    /// Fish in deep water, shown by sonar.
+    pub deep_fish: RGB,
+    /// Jellyfish.
     pub hazard: RGB,
+    pub shark: RGB,
+    pub debris: RGB,
+    pub whirlpool: RGB,
 }
 
 impl Default for ColorPalette {
@@ -35,9 +73,17 @@ impl Default for ColorPalette {
             land: RGB::named(GRAY),
             shallow: RGB::named(CYAN),
             deep: RGB::named(NAVY),
+            coral: RGB::named(MAGENTA),
+            kelp: RGB::named(GREEN),
+            sand: RGB::named(YELLOW),
+            ice: RGB::named(WHITE),
             player: RGB::named(YELLOW),
             fish: RGB::named(GREEN),
+            deep_fish: RGB::named(GOLD),
             hazard: RGB::named(RED),
+            shark: RGB::named(WHITE),
+            debris: RGB::named(ORANGE),
+            whirlpool: RGB::named(CYAN),
         }
     }
 }
@@ -49,23 +95,569 @@ impl ColorPalette {
             land: RGB::named(WHITE),
             shallow: RGB::named(YELLOW),
             deep: RGB::named(GRAY),
+            coral: RGB::named(ORANGE),
+            kelp: RGB::named(MAGENTA),
+            sand: RGB::named(GOLD),
+            ice: RGB::named(CYAN),
             player: RGB::named(WHITE),
             fish: RGB::named(RED),
+            deep_fish: RGB::named(CYAN),
             hazard: RGB::named(RED),
+            shark: RGB::named(MAGENTA),
+            debris: RGB::named(ORANGE),
+            whirlpool: RGB::named(CYAN),
+        }
+    }
+
+    /// Returns an unlockable palette by name (`Sunset`, `Reef` or `Abyss`).
+    pub fn named(name: &str) -> Option<Self> {
+        let palette = match name {
+            "Sunset" => Self {
+                land: RGB::named(ORANGE),
+                shallow: RGB::named(GOLD),
+                deep: RGB::named(MAGENTA),
+                coral: RGB::named(RED),
+                kelp: RGB::named(GREEN),
+                sand: RGB::named(YELLOW),
+                ice: RGB::named(WHITE),
+                player: RGB::named(WHITE),
+                fish: RGB::named(CYAN),
+                deep_fish: RGB::named(GREEN),
+                hazard: RGB::named(RED),
+                shark: RGB::named(WHITE),
+                debris: RGB::named(YELLOW),
+                whirlpool: RGB::named(CYAN),
+            },
+            "Reef" => Self {
+                land: RGB::named(YELLOW),
+                shallow: RGB::named(CYAN),
+                deep: RGB::named(GREEN),
+                coral: RGB::named(RED),
+                kelp: RGB::named(NAVY),
+                sand: RGB::named(GOLD),
+                ice: RGB::named(WHITE),
+                player: RGB::named(WHITE),
+                fish: RGB::named(MAGENTA),
+                deep_fish: RGB::named(ORANGE),
+                hazard: RGB::named(RED),
+                shark: RGB::named(GRAY),
+                debris: RGB::named(GOLD),
+                whirlpool: RGB::named(CYAN),
+            },
+            "Abyss" => Self {
+                land: RGB::named(GRAY),
+                shallow: RGB::named(NAVY),
+                deep: RGB::named(BLACK),
+                coral: RGB::named(MAGENTA),
+                kelp: RGB::named(GREEN),
+                sand: RGB::named(YELLOW),
+                ice: RGB::named(WHITE),
+                player: RGB::named(CYAN),
+                fish: RGB::named(GOLD),
+                deep_fish: RGB::named(WHITE),
+                hazard: RGB::named(MAGENTA),
+                shark: RGB::named(RED),
+                debris: RGB::named(ORANGE),
+                whirlpool: RGB::named(YELLOW),
+            },
+            _ => return None,
+        };
+        Some(palette)
+    }
+}
+
+/// Shades a terrain color for `season`: summer warms it, autumn turns it
+/// amber and winter frosts it over.
+pub fn season_tint(color: RGB, season: data::Season) -> RGB {
+    match season {
+        data::Season::Spring => color,
+        data::Season::Summer => color.lerp(RGB::named(GOLD), 0.15),
+        data::Season::Autumn => color.lerp(RGB::named(ORANGE), 0.25),
+        data::Season::Winter => color.lerp(RGB::named(WHITE), 0.3),
+    }
+}
+use common::{EventListener, GameEvent, GameResult, Point, Rect};
+
+/// Smallest console, in cells, the panels are laid out for.
+pub const MIN_SCREEN_WIDTH: i32 = 80;
+pub const MIN_SCREEN_HEIGHT: i32 = 25;
+/// Width of the inventory, quest and status column.
+const SIDEBAR_WIDTH: i32 = 20;
+/// Height of the quest panel at the foot of the sidebar.
+const QUEST_ROWS: i32 = 6;
+const FIGHT_X: i32 = 2;
+const FIGHT_Y: i32 = 1;
+const TENSION_Y: i32 = FIGHT_Y + 5;
+/// Width legendary lore is wrapped to on the codex screen.
+const CODEX_LORE_WIDTH: usize = MIN_SCREEN_WIDTH as usize - 4;
+const BLINK_FRAMES: u32 = 15;
+const POPUP_X: i32 = 6;
+const POPUP_Y: i32 = 3;
+const POPUP_WIDTH: usize = 46;
+/// Largest minimap inside its frame, in the top-right corner of the map.
+const MINIMAP_COLS: i32 = 24;
+/// Rows per column of the key binding editor.
+const BINDING_ROWS: usize = 15;
+const MINIMAP_ROWS: i32 = 15;
+
+/// Where each panel sits on a console of a given size.
+///
+/// The sidebar keeps its width and the map takes the rest of the columns;
+/// the message log gets a third of the rows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScreenLayout {
+    /// Map window in the top-left corner.
+    pub map: Rect,
+    /// Message log below the map.
+    pub log: Rect,
+    /// Stat bars beside the message log.
+    pub status: Rect,
+    /// Inventory list to the right of the map.
+    pub inventory: Rect,
+    /// Active quests below the inventory.
+    pub quest: Rect,
+}
+
+impl ScreenLayout {
+    /// Lays the panels out on a `width`×`height` console. Consoles smaller
+    /// than the minimum get the minimum layout, clipped at the edges.
+    pub fn new(width: i32, height: i32) -> Self {
+        let width = width.max(MIN_SCREEN_WIDTH);
+        let height = height.max(MIN_SCREEN_HEIGHT);
+        let map_width = width - SIDEBAR_WIDTH;
+        let log_rows = height / 3;
+        let map_height = height - log_rows;
+        Self {
+            map: Rect::new(0, 0, map_width, map_height),
+            log: Rect::new(0, map_height, map_width, log_rows),
+            status: Rect::new(map_width, map_height, SIDEBAR_WIDTH, log_rows),
+            inventory: Rect::new(map_width, 0, SIDEBAR_WIDTH, map_height - QUEST_ROWS),
+            quest: Rect::new(
+                map_width,
+                map_height - QUEST_ROWS,
+                SIDEBAR_WIDTH,
+                QUEST_ROWS,
+            ),
+        }
+    }
+
+    /// The whole console.
+    pub fn screen(&self) -> Rect {
+        Rect::new(
+            0,
+            0,
+            self.map.width + SIDEBAR_WIDTH,
+            self.map.height + self.log.height,
+        )
+    }
+}
+
+impl Default for ScreenLayout {
+    fn default() -> Self {
+        Self::new(MIN_SCREEN_WIDTH, MIN_SCREEN_HEIGHT)
+    }
+}
+
+/// Severity of a stat shown in the status panel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatLevel {
+    /// Above 60% of the maximum.
+    Normal,
+    /// Between 20% and 60% of the maximum.
+    Warning,
+    /// At or below 20% of the maximum; drawn blinking.
+    Critical,
+}
+
+/// Small colored glyph marking an active condition such as a storm.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StatusIcon {
+    pub glyph: char,
+    pub color: RGB,
+}
+
+/// Player stats rendered by [`UIContext::draw_status`].
+#[derive(Clone, Debug)]
+pub struct StatusView<'a> {
+    pub hp: i32,
+    pub max_hp: i32,
+    pub line: i32,
+    pub max_line: i32,
+    pub hunger: i32,
+    pub max_hunger: i32,
+    pub thirst: i32,
+    pub max_thirst: i32,
+    pub depth: i32,
+    pub time: &'a str,
+    pub season: &'a str,
+    /// `Flood`, `Ebb`, `High` or `Low`.
+    pub tide: &'a str,
+    pub weather: &'a str,
+    pub coins: i32,
+    /// Hull condition while the player is in the boat.
+    pub boat: Option<BoatView>,
+    pub icons: Vec<StatusIcon>,
+    /// Status effects on the player as `(name, turns left)`.
+    pub effects: Vec<(&'a str, u32)>,
+    /// Sonar's estimate in kilograms of the fish next to the player.
+    pub sonar: Option<f32>,
+    /// Lure depth and retrieve while aiming a cast.
+    pub lure: Option<(&'a str, &'a str)>,
+    /// Scores of the competition, in a competition run.
+    pub standings: Option<StandingsView>,
+    /// Seed of the run, so it can be played again.
+    pub seed: u64,
+}
+
+/// Scores of a competition shown in the status panel.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StandingsView {
+    /// Anglers and their scores, best first.
+    pub rows: Vec<(String, i32)>,
+    /// Index in `rows` of the player.
+    pub player: usize,
+}
+
+/// Boat condition shown in the status panel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BoatView {
+    pub durability: i32,
+    pub max_durability: i32,
+    pub anchored: bool,
+}
+
+/// One row of [`UIContext::draw_achievements`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AchievementView {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub unlocked: bool,
+}
+
+/// One species row of [`UIContext::draw_codex`].
+#[derive(Clone, Copy, Debug)]
+pub struct CodexView<'a> {
+    pub fish: &'a data::FishType,
+    /// Times the species has been landed; zero keeps it undiscovered.
+    pub count: u32,
+}
+
+/// An active quest rendered by [`UIContext::draw_quests`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QuestView<'a> {
+    pub title: &'a str,
+    pub progress: u32,
+    pub target: u32,
+}
+
+/// Details of an ongoing fight rendered by [`UIContext::draw_fight`].
+#[derive(Clone, Debug)]
+pub struct FightView<'a> {
+    /// Species name, or `None` while the fish is still unidentified.
+    pub name: Option<&'a str>,
+    pub strength: i32,
+    pub tension: i32,
+    pub max_tension: i32,
+    /// How the fish's pull has moved the tension lately: positive while
+    /// it rises, negative while it falls.
+    pub trend: i32,
+    /// The line is close to slack and the fish may slip away.
+    pub slack: bool,
+    /// One more spike from the fish would snap the line.
+    pub near_breaking: bool,
+    pub line: i32,
+    pub max_line: i32,
+    pub turns_left: i32,
+    /// Stage of a boss fight out of `stages`; plain fights have one.
+    pub stage: u32,
+    pub stages: u32,
+    /// What the fish is doing, and what the player should do about it.
+    pub phase: &'a str,
+    pub hint: &'a str,
+    pub stamina: i32,
+    pub max_stamina: i32,
+    /// Durability of the equipped rod and reel, out of 100.
+    pub rod: Option<i32>,
+    pub reel: Option<i32>,
+    /// Tiles between the hooked fish and the nearest shark or predator
+    /// closing in on it.
+    pub threat: Option<i32>,
+}
+
+/// A question that holds the game until one of its options is picked.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Prompt {
+    pub title: String,
+    pub text: String,
+    pub options: Vec<String>,
+    /// Option highlighted for confirming.
+    pub selected: usize,
+}
+
+impl Prompt {
+    /// A prompt with the first option highlighted.
+    pub fn new(title: &str, text: &str, options: Vec<String>) -> Self {
+        Self {
+            title: title.to_string(),
+            text: text.to_string(),
+            options,
+            selected: 0,
+        }
+    }
+}
+
+/// A fish just landed, shown on the catch card.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CatchCard {
+    pub name: String,
+    /// ASCII art of the species.
+    pub art: Vec<String>,
+    /// Weight of the landed fish in kilograms.
+    pub weight: f32,
+    /// Weight against the species' average; 1.0 is average.
+    pub size: f32,
+    /// Rarity from one to five stars.
+    pub stars: u8,
+    /// Whether it is the first of its species in the codex.
+    pub first: bool,
+    /// Score the catch adds to the run.
+    pub score: i32,
+    pub description: String,
+    pub habitat_note: String,
+}
+
+/// Current settings rendered by [`UIContext::draw_options`].
+#[derive(Clone, Debug)]
+pub struct OptionsView<'a> {
+    pub colorblind: bool,
+    pub sfx_volume: u8,
+    pub music_volume: u8,
+    pub muted: bool,
+    pub cast_key: VirtualKeyCode,
+    pub font_scale: u8,
+    pub adaptive: bool,
+    /// Fishing runs on a timer instead of turn by turn.
+    pub realtime_fights: bool,
+    /// Name of the selected map palette.
+    pub palette: &'a str,
+    pub fullscreen: bool,
+    pub vsync: bool,
+    /// Window size label such as `1280x400` or `Auto`.
+    pub window_size: String,
+}
+
+/// One action in the key binding editor.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BindingView<'a> {
+    /// Action name as written in `lurhook.toml`.
+    pub action: &'a str,
+    /// Name of the bound key.
+    pub key: &'a str,
+    /// Another action is bound to the same key.
+    pub conflict: bool,
+}
+
+/// The key binding editor rendered by [`UIContext::draw_keybindings`].
+#[derive(Clone, Debug)]
+pub struct KeybindingsView<'a> {
+    pub bindings: Vec<BindingView<'a>>,
+    /// Selected row.
+    pub cursor: usize,
+    /// The selected action is waiting for its new key.
+    pub capturing: bool,
+}
+
+/// What the minimap shows for one map tile.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MinimapTile {
+    /// Not seen yet this run.
+    Unexplored,
+    Land,
+    Water,
+    DeepWater,
+}
+
+/// The whole map, rendered scaled down by [`UIContext::draw_minimap`].
+#[derive(Clone, Debug)]
+pub struct MinimapView {
+    pub width: i32,
+    pub height: i32,
+    /// Every map tile in row-major order.
+    pub tiles: Vec<MinimapTile>,
+    pub player: Point,
+    /// Fish the player can currently make out.
+    pub schools: Vec<Point>,
+    /// Name of the place the player is in, shown under the map.
+    pub place: Option<String>,
+    pub palette: ColorPalette,
+}
+
+impl MinimapView {
+    /// Map tiles per minimap cell along each axis, so the whole map fits
+    /// in [`MINIMAP_COLS`]×[`MINIMAP_ROWS`] cells.
+    fn scale(&self) -> i32 {
+        ((self.width + MINIMAP_COLS - 1) / MINIMAP_COLS)
+            .max((self.height + MINIMAP_ROWS - 1) / MINIMAP_ROWS)
+            .max(1)
+    }
+
+    /// Glyph and colour of the minimap cell covering `block`: the player,
+    /// else a school, else the commonest explored tile.
+    fn cell(&self, block: Rect) -> (char, RGB) {
+        let palette = &self.palette;
+        if block.contains(self.player) {
+            return ('@', palette.player);
+        }
+        if self.schools.iter().any(|&f| block.contains(f)) {
+            return ('f', palette.fish);
+        }
+        let mut counts = [0; 3];
+        for pt in block.points() {
+            if pt.x >= self.width || pt.y >= self.height {
+                continue;
+            }
+            match self.tiles[(pt.y * self.width + pt.x) as usize] {
+                MinimapTile::Unexplored => {}
+                MinimapTile::Land => counts[0] += 1,
+                MinimapTile::Water => counts[1] += 1,
+                MinimapTile::DeepWater => counts[2] += 1,
+            }
+        }
+        let most = counts.iter().copied().max().unwrap_or(0);
+        if most == 0 {
+            (' ', RGB::named(BLACK))
+        } else if counts[1] == most {
+            ('~', palette.shallow)
+        } else if counts[2] == most {
+            ('≈', palette.deep)
+        } else {
+            ('.', palette.land)
         }
     }
 }
-use common::GameResult;
 
-const LOG_Y: i32 = 17;
-const LOG_WINDOW: i32 = 8;
-const TENSION_Y: i32 = LOG_Y - 1;
+/// Line written to the message log for `event`, if any, with its
+/// category.
+fn event_message(event: &GameEvent) -> Option<(LogCategory, String)> {
+    use LogCategory::*;
+    let entry = match event {
+        GameEvent::FishBite => (Fishing, "Something takes the bait! Strike!".to_string()),
+        GameEvent::FishHooked => (Fishing, "Hooked a fish!".to_string()),
+        GameEvent::NoBite => (Fishing, "The fish got away...".to_string()),
+        GameEvent::FishCaught { name, weight, .. } => {
+            (Fishing, format!("Caught a {} ({:.1} kg)!", name, weight))
+        }
+        GameEvent::LineSnapped {
+            lure_lost: true, ..
+        } => (Fishing, "Line snapped and took your lure!".to_string()),
+        GameEvent::LineSnapped { .. } => (Fishing, "Line snapped!".to_string()),
+        GameEvent::FishEscaped { .. } => (Fishing, "The fish escaped!".to_string()),
+        GameEvent::FishStolen { .. } => (Combat, "A shark steals your fish!".to_string()),
+        GameEvent::LineRuined => (Fishing, "Your line is ruined.".to_string()),
+        GameEvent::WeatherChanged { announcement, .. } => (Event, announcement.clone()),
+        GameEvent::HungerCritical => (Combat, "You are starving!".to_string()),
+        GameEvent::ThirstCritical => (Combat, "You are parched!".to_string()),
+        // Already announced by the weather change
+        GameEvent::StormPassed => return None,
+        // Logged with what was eaten by the game itself
+        GameEvent::FishEaten | GameEvent::DishCooked => return None,
+    };
+    Some(entry)
+}
+
+/// Classifies `value` against `max` into a [`StatLevel`].
+pub fn stat_level(value: i32, max: i32) -> StatLevel {
+    let pct = if max > 0 { value * 100 / max } else { 0 };
+    if pct > 60 {
+        StatLevel::Normal
+    } else if pct > 20 {
+        StatLevel::Warning
+    } else {
+        StatLevel::Critical
+    }
+}
+
+/// Band of the tension bar a tension falls in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TensionZone {
+    /// Below half the maximum.
+    Safe,
+    /// From half to 80% of the maximum.
+    Warning,
+    /// From 80% of the maximum; the line is at risk.
+    Danger,
+}
+
+impl TensionZone {
+    fn color(self) -> RGB {
+        match self {
+            TensionZone::Safe => RGB::named(GREEN),
+            TensionZone::Warning => RGB::named(YELLOW),
+            TensionZone::Danger => RGB::named(RED),
+        }
+    }
+}
+
+/// Classifies `tension` against the line's `max` tension.
+pub fn tension_zone(tension: i32, max: i32) -> TensionZone {
+    let pct = if max > 0 { tension * 100 / max } else { 100 };
+    if pct < 50 {
+        TensionZone::Safe
+    } else if pct < 80 {
+        TensionZone::Warning
+    } else {
+        TensionZone::Danger
+    }
+}
+
+/// Arrow showing which way the tension is heading.
+fn trend_arrow(trend: i32) -> char {
+    match trend.signum() {
+        1 => '↑',
+        -1 => '↓',
+        _ => '→',
+    }
+}
+
+fn stat_colors(level: StatLevel, blink_on: bool) -> (RGB, RGB) {
+    match level {
+        StatLevel::Normal => (RGB::named(GREEN), RGB::named(BLACK)),
+        StatLevel::Warning => (RGB::named(YELLOW), RGB::named(BLACK)),
+        StatLevel::Critical if blink_on => (RGB::named(WHITE), RGB::named(RED)),
+        StatLevel::Critical => (RGB::named(RED), RGB::named(BLACK)),
+    }
+}
 
 /// Basic UI context for logging and redraw requests.
 pub struct UIContext {
-    logs: Vec<String>,
+    logs: Vec<LogEntry>,
     scroll: usize,
+    /// Turn new log entries are stamped with.
+    turn: u32,
+    /// Category the log shows alone, or `None` for every message.
+    log_filter: Option<LogCategory>,
+    /// Text the full log screen looks for.
+    log_search: String,
+    /// Layout to go back to when the full log or the catch card closes.
+    return_layout: UILayout,
+    catch_card: Option<CatchCard>,
     layout: UILayout,
+    frame: u32,
+    popup: Option<Vec<String>>,
+    prompt: Option<Prompt>,
+    /// Panel positions for the console last drawn to.
+    screen: std::cell::Cell<ScreenLayout>,
+    /// Colors of the panels, bars and lists.
+    theme: Theme,
+}
+
+impl EventListener for UIContext {
+    /// Logs every event.
+    fn on_event(&mut self, event: &GameEvent) {
+        if let Some((category, msg)) = event_message(event) {
+            self.add_entry(category, &msg).ok();
+        }
+    }
 }
 
 impl Default for UIContext {
@@ -73,7 +665,17 @@ impl Default for UIContext {
         Self {
             logs: Vec::new(),
             scroll: 0,
+            turn: 0,
+            log_filter: None,
+            log_search: String::new(),
+            return_layout: UILayout::Standard,
+            catch_card: None,
             layout: UILayout::Standard,
+            frame: 0,
+            popup: None,
+            prompt: None,
+            screen: Default::default(),
+            theme: Theme::default(),
         }
     }
 }
@@ -88,16 +690,117 @@ impl UIContext {
     pub fn layout(&self) -> UILayout {
         self.layout
     }
-    /// Adds a message to the log queue.
-    pub fn add_log(&mut self, msg: &str) -> GameResult<()> {
-        self.logs.push(msg.to_string());
-        println!("LOG: {}", msg);
+
+    /// Lays the panels out for `ctx`'s console. Call before drawing a frame.
+    pub fn fit(&self, ctx: &dyn Renderer) {
+        let (width, height) = ctx.dimensions();
+        self.screen.set(ScreenLayout::new(width, height));
+    }
+
+    /// Where the panels sit on the console last fitted to.
+    pub fn screen(&self) -> ScreenLayout {
+        self.screen.get()
+    }
+
+    /// Sets the colors the widgets draw in.
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+
+    pub fn theme(&self) -> Theme {
+        self.theme
+    }
+    /// Sets the turn new log entries are stamped with.
+    pub fn set_turn(&mut self, turn: u32) {
+        self.turn = turn;
+    }
+
+    /// Adds a message about `category` to the log queue.
+    pub fn add_entry(&mut self, category: LogCategory, msg: &str) -> GameResult<()> {
+        self.logs.push(LogEntry {
+            category,
+            turn: self.turn,
+            text: msg.to_string(),
+        });
         Ok(())
     }
 
+    /// Adds a system message to the log queue.
+    pub fn add_log(&mut self, msg: &str) -> GameResult<()> {
+        self.add_entry(LogCategory::System, msg)
+    }
+
+    /// All log messages, oldest first.
+    pub fn logs(&self) -> Vec<String> {
+        self.logs.iter().map(|e| e.text.clone()).collect()
+    }
+
+    /// All log entries, oldest first.
+    pub fn entries(&self) -> &[LogEntry] {
+        &self.logs
+    }
+
+    /// Category the log shows alone, if any.
+    pub fn log_filter(&self) -> Option<LogCategory> {
+        self.log_filter
+    }
+
+    /// Shows the next category alone, or every message after the last.
+    pub fn cycle_log_filter(&mut self) {
+        self.log_filter = LogCategory::next_filter(self.log_filter);
+        self.scroll = 0;
+    }
+
+    /// Opens the full log screen on its newest messages.
+    pub fn open_message_log(&mut self) {
+        if self.layout != UILayout::MessageLog {
+            self.return_layout = self.layout;
+        }
+        self.layout = UILayout::MessageLog;
+        self.set_log_search("");
+    }
+
+    /// Closes the full log screen, back to the layout it was opened from.
+    pub fn close_message_log(&mut self) {
+        self.layout = self.return_layout;
+        self.set_log_search("");
+    }
+
+    /// Text the full log screen looks for.
+    pub fn log_search(&self) -> &str {
+        &self.log_search
+    }
+
+    pub fn set_log_search(&mut self, search: &str) {
+        self.log_search = search.to_string();
+        self.scroll = 0;
+    }
+
+    /// Entries the log shows: those passing the filter and, on the full
+    /// log screen, containing the search text.
+    fn shown_logs(&self) -> Vec<&LogEntry> {
+        let search = match self.layout {
+            UILayout::MessageLog => self.log_search.as_str(),
+            _ => "",
+        };
+        self.logs
+            .iter()
+            .filter(|e| e.matches(self.log_filter, search))
+            .collect()
+    }
+
+    /// Rows of log the current layout shows.
+    fn log_rows(&self) -> usize {
+        match self.layout {
+            // Inside the frame, below the filter line and above the help
+            UILayout::MessageLog => (self.screen().screen().height - 4).max(0) as usize,
+            _ => self.screen().log.height as usize,
+        }
+    }
+
     /// Scrolls log view one line up.
     pub fn scroll_up(&mut self) {
-        if self.scroll + (LOG_WINDOW as usize) < self.logs.len() {
+        if self.scroll + self.log_rows() < self.shown_logs().len() {
             self.scroll += 1;
         }
     }
@@ -116,81 +819,531 @@ impl UIContext {
     }
 
     /// Draws log window to the screen.
-    pub fn draw_logs(&self, ctx: &mut BTerm) -> GameResult<()> {
+    pub fn draw_logs(&self, ctx: &mut dyn Renderer) -> GameResult<()> {
         if self.layout == UILayout::Help {
             return Ok(());
         }
-        let log_y = if self.layout == UILayout::Fishing {
-            LOG_Y + 1
-        } else {
-            LOG_Y
-        };
-        let start = self
-            .logs
+        let mut panel = self.screen().log;
+        if self.layout == UILayout::Fishing {
+            panel.y += 1;
+        }
+        let shown = self.shown_logs();
+        let lines: Vec<String> = shown.iter().map(|e| e.text.clone()).collect();
+        let colors: Vec<RGB> = shown.iter().map(|e| e.category.color()).collect();
+        let start = lines
             .len()
-            .saturating_sub(LOG_WINDOW as usize + self.scroll);
-        let end = std::cmp::min(start + LOG_WINDOW as usize, self.logs.len());
-        for (i, line) in self.logs[start..end].iter().enumerate() {
-            ctx.print(0, log_y + i as i32, line);
+            .saturating_sub(panel.height as usize + self.scroll);
+        ListView::new(&lines)
+            .colors(&colors)
+            .scrolled_to(start)
+            .draw(ctx, panel, &self.theme);
+        Ok(())
+    }
+
+    /// Draws every message with its turn and category when in
+    /// `MessageLog` layout, newest at the bottom.
+    pub fn draw_message_log(&self, ctx: &mut dyn Renderer) -> GameResult<()> {
+        if self.layout != UILayout::MessageLog {
+            return Ok(());
         }
+        let theme = &self.theme;
+        let inner =
+            Panel::titled("Message Log")
+                .bordered()
+                .draw(ctx, self.screen().screen(), theme);
+        let (header, body) = inner.split_top(1);
+        let (list, footer) = body.split_top(body.height - 1);
+        let filter = self.log_filter.map_or("All", LogCategory::name);
+        widgets::print_in(
+            ctx,
+            header,
+            theme.title,
+            theme.background,
+            &format!("Filter: {:<8} Search: {}_", filter, self.log_search),
+        );
+        let shown = self.shown_logs();
+        let lines: Vec<String> = shown.iter().map(|e| e.line()).collect();
+        let colors: Vec<RGB> = shown.iter().map(|e| e.category.color()).collect();
+        let start = lines
+            .len()
+            .saturating_sub(list.height.max(0) as usize + self.scroll);
+        ListView::new(&lines)
+            .colors(&colors)
+            .scrolled_to(start)
+            .draw(ctx, list, theme);
+        widgets::print_in(
+            ctx,
+            footer,
+            theme.text,
+            theme.background,
+            "Type to search  Tab: Filter  PgUp/PgDn: Scroll  Esc: Close",
+        );
         Ok(())
     }
 
-    /// Draws a status panel on the far right side.
-    pub fn draw_status(
+    /// Advances the frame counter used for blinking warnings.
+    pub fn next_frame(&mut self) {
+        self.frame = self.frame.wrapping_add(1);
+    }
+
+    /// Opens a popup with `lines` over the map until dismissed.
+    pub fn show_popup(&mut self, lines: Vec<String>) {
+        self.popup = Some(lines);
+    }
+
+    /// Closes the popup, returning whether one was open.
+    pub fn dismiss_popup(&mut self) -> bool {
+        self.popup.take().is_some()
+    }
+
+    /// Opens `prompt` over the screen until an option is picked.
+    pub fn show_prompt(&mut self, prompt: Prompt) {
+        self.prompt = Some(prompt);
+    }
+
+    /// The open prompt.
+    pub fn prompt(&self) -> Option<&Prompt> {
+        self.prompt.as_ref()
+    }
+
+    /// Moves the prompt's highlight `delta` options down, wrapping around.
+    pub fn move_prompt(&mut self, delta: i32) {
+        if let Some(prompt) = &mut self.prompt {
+            let len = prompt.options.len() as i32;
+            if len > 0 {
+                prompt.selected = (prompt.selected as i32 + delta).rem_euclid(len) as usize;
+            }
+        }
+    }
+
+    /// Highlights option `index` of the prompt, returning whether it has
+    /// one.
+    pub fn select_prompt(&mut self, index: usize) -> bool {
+        match &mut self.prompt {
+            Some(prompt) if index < prompt.options.len() => {
+                prompt.selected = index;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Closes the prompt, returning the option highlighted.
+    pub fn close_prompt(&mut self) -> Option<usize> {
+        self.prompt.take().map(|p| p.selected)
+    }
+
+    /// The prompt's option drawn on the console cell `at`.
+    pub fn prompt_option_at(&self, at: Point) -> Option<usize> {
+        let prompt = self.prompt.as_ref()?;
+        let (_, options) = self.prompt_layout(prompt);
+        if !options.contains(at) {
+            return None;
+        }
+        Some((at.y - options.y) as usize).filter(|&i| i < prompt.options.len())
+    }
+
+    /// Lines of the prompt's text, and where its options go.
+    fn prompt_layout(&self, prompt: &Prompt) -> (Vec<String>, Rect) {
+        let mut lines = wrap_text(&prompt.text, POPUP_WIDTH);
+        lines.push(String::new());
+        let screen = self.screen().screen();
+        let (width, height) = (
+            POPUP_WIDTH as i32 + 4,
+            (lines.len() + prompt.options.len()) as i32 + 2,
+        );
+        let area = Rect::new(
+            (screen.width - width) / 2,
+            ((screen.height - height) / 2).max(0),
+            width,
+            height,
+        );
+        let options = Rect::new(
+            area.x + 1,
+            area.y + 1 + lines.len() as i32,
+            area.width - 2,
+            prompt.options.len() as i32,
+        );
+        (lines, options)
+    }
+
+    /// Draws the open prompt in a framed box in the middle of the screen:
+    /// its text, then its numbered options with the highlighted one marked.
+    pub fn draw_prompt(&self, ctx: &mut dyn Renderer) -> GameResult<()> {
+        let Some(prompt) = &self.prompt else {
+            return Ok(());
+        };
+        let (lines, options) = self.prompt_layout(prompt);
+        let area = Rect::new(
+            options.x - 1,
+            options.y - 1 - lines.len() as i32,
+            options.width + 2,
+            (lines.len() + prompt.options.len()) as i32 + 2,
+        );
+        let title = format!(" {} ", prompt.title);
+        self.draw_box(ctx, area, &title, &lines);
+        let numbered: Vec<String> = prompt
+            .options
+            .iter()
+            .enumerate()
+            .map(|(i, option)| format!("{}: {}", i + 1, option))
+            .collect();
+        ListView::new(&numbered)
+            .selected(Some(prompt.selected))
+            .draw(ctx, options, &self.theme);
+        Ok(())
+    }
+
+    /// Shows `card` over the map until a key is pressed.
+    pub fn show_catch_card(&mut self, card: CatchCard) {
+        if self.layout != UILayout::CatchCard {
+            self.return_layout = self.layout;
+        }
+        self.layout = UILayout::CatchCard;
+        self.catch_card = Some(card);
+    }
+
+    /// Closes the catch card, returning whether one was open.
+    pub fn dismiss_catch_card(&mut self) -> bool {
+        if self.layout != UILayout::CatchCard {
+            return false;
+        }
+        self.layout = self.return_layout;
+        self.catch_card.take().is_some()
+    }
+
+    /// The card of the fish just landed, while it is shown.
+    pub fn catch_card(&self) -> Option<&CatchCard> {
+        self.catch_card
+            .as_ref()
+            .filter(|_| self.layout == UILayout::CatchCard)
+    }
+
+    /// Draws the catch card, titled with the species, in the middle of
+    /// the screen.
+    pub fn draw_catch_card(&self, ctx: &mut dyn Renderer) -> GameResult<()> {
+        let Some(card) = self.catch_card() else {
+            return Ok(());
+        };
+        let lines = catch_card_lines(card);
+        let screen = self.screen().screen();
+        let (width, height) = (POPUP_WIDTH as i32 + 4, lines.len() as i32 + 2);
+        let area = Rect::new(
+            (screen.width - width) / 2,
+            ((screen.height - height) / 2).max(0),
+            width,
+            height,
+        );
+        let title = format!(" {} ", card.name);
+        self.draw_box(ctx, area, &title, &lines);
+        Ok(())
+    }
+
+    /// Draws a tutorial prompt in a framed box along the bottom of the
+    /// map, titled with how to skip the tutorial.
+    pub fn draw_tutorial(&self, ctx: &mut dyn Renderer, prompt: &str) -> GameResult<()> {
+        let map = self.screen().map;
+        let lines = wrap_text(prompt, (map.width - 4).max(1) as usize);
+        let height = lines.len() as i32 + 2;
+        let area = Rect::new(map.x, map.bottom() - height, map.width, height);
+        self.draw_box(ctx, area, " Tutorial - Esc: skip ", &lines);
+        Ok(())
+    }
+
+    /// Draws `lines` in a framed box titled `title` that covers whatever
+    /// is drawn under `area`.
+    fn draw_box(&self, ctx: &mut dyn Renderer, area: Rect, title: &str, lines: &[String]) {
+        let inner = Panel::titled(title).bordered().draw(ctx, area, &self.theme);
+        let blank = " ".repeat(inner.width.max(0) as usize);
+        for i in 0..inner.height {
+            let (fg, bg) = (self.theme.text, self.theme.background);
+            widgets::print_in(ctx, inner.row(i), fg, bg, &blank);
+        }
+        let (_, text) = inner.split_left(1);
+        ListView::new(lines).draw(ctx, text, &self.theme);
+    }
+
+    /// Returns the lines of the open popup.
+    pub fn popup(&self) -> Option<&[String]> {
+        self.popup.as_deref()
+    }
+
+    /// Draws the open popup as a framed box over the map.
+    pub fn draw_popup(&self, ctx: &mut dyn Renderer) -> GameResult<()> {
+        if let Some(lines) = &self.popup {
+            ctx.draw_box(
+                POPUP_X,
+                POPUP_Y,
+                POPUP_WIDTH as i32 + 3,
+                lines.len() as i32 + 1,
+                RGB::named(WHITE),
+                RGB::named(BLACK),
+            );
+            for (i, line) in lines.iter().enumerate() {
+                ctx.print(POPUP_X + 2, POPUP_Y + 1 + i as i32, line);
+            }
+        }
+        Ok(())
+    }
+
+    /// Draws `lines` in a framed box beside the console cell `at`, on the
+    /// other side of it where the box would leave the map panel.
+    pub fn draw_tooltip(
         &self,
-        ctx: &mut BTerm,
-        hp: i32,
-        line: i32,
-        hunger: i32,
-        depth: i32,
-        time: &str,
+        ctx: &mut dyn Renderer,
+        at: Point,
+        lines: &[String],
     ) -> GameResult<()> {
+        let panel = self.screen().map;
+        let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0) as i32 + 3;
+        let height = lines.len() as i32 + 1;
+        let mut x = at.x + 2;
+        if x + width >= panel.right() {
+            x = at.x - width - 2;
+        }
+        let mut y = at.y + 1;
+        if y + height >= panel.bottom() {
+            y = at.y - height - 1;
+        }
+        let (x, y) = (x.max(panel.x), y.max(panel.y));
+        ctx.draw_box(x, y, width, height, RGB::named(WHITE), RGB::named(BLACK));
+        for (i, line) in lines.iter().enumerate() {
+            ctx.print(x + 2, y + 1 + i as i32, line);
+        }
+        Ok(())
+    }
+
+    fn blink_on(&self) -> bool {
+        (self.frame / BLINK_FRAMES).is_multiple_of(2)
+    }
+
+    /// Draws the status panel with stat bars, active condition icons and,
+    /// where there is room, the status effects by name.
+    pub fn draw_status(&self, ctx: &mut dyn Renderer, status: &StatusView) -> GameResult<()> {
         if self.layout == UILayout::Help {
             return Ok(());
         }
-        let base_y = if self.layout == UILayout::Fishing {
-            LOG_Y + 1
+        let mut panel = self.screen().status;
+        if self.layout == UILayout::Fishing {
+            panel.y += 1;
+        }
+        let theme = &self.theme;
+        let bars = [
+            ("HP  ", status.hp, status.max_hp),
+            ("Line", status.line, status.max_line),
+            ("Food", status.hunger, status.max_hunger),
+            ("Wtr ", status.thirst, status.max_thirst),
+        ];
+        for (i, &(label, value, max)) in bars.iter().enumerate() {
+            let (fg, bg) = stat_colors(stat_level(value, max), self.blink_on());
+            Bar::new(label, value, max)
+                .colors(fg, bg)
+                .draw(ctx, panel.row(i as i32), theme);
+        }
+        let text = |ctx: &mut dyn Renderer, row: Rect, line: &str| {
+            widgets::print_in(ctx, row, theme.text, theme.background, line);
+        };
+        text(
+            ctx,
+            panel.row(4),
+            &format!(
+                "{}m {} {} {}c",
+                status.depth, status.time, status.tide, status.coins
+            ),
+        );
+        let (icons, sonar) = panel.row(5).split_left(status.icons.len() as i32 * 2);
+        for (i, icon) in status.icons.iter().enumerate() {
+            ctx.set(
+                icons.x + i as i32 * 2,
+                icons.y,
+                icon.color,
+                theme.background,
+                icon.glyph,
+            );
+        }
+        if let Some(weight) = status.sonar {
+            text(ctx, sonar, &format!("Sonar ~{:.1}kg", weight));
+        } else if let Some(standings) = &status.standings {
+            let rank = format!("Rank {}/{}", standings.player + 1, standings.rows.len());
+            text(ctx, sonar, &rank);
+        }
+        // The lure only matters while aiming, so it borrows the weather row
+        let weather = match status.lure {
+            Some((depth, retrieve)) => format!("Lure {} {}", depth, retrieve),
+            None => format!("{}: {}", status.season, status.weather),
+        };
+        text(ctx, panel.row(6), &weather);
+        // Hull condition matters more than the seed while aboard
+        let boat_or_seed = match status.boat {
+            Some(boat) => format!(
+                "Boat {}/{}{}",
+                boat.durability,
+                boat.max_durability,
+                if boat.anchored { " anchored" } else { "" }
+            ),
+            None => format!("Seed {}", status.seed),
+        };
+        text(ctx, panel.row(7), &boat_or_seed);
+        // The full standings, then effects, are listed below the stats on
+        // panels tall enough for them
+        let (_, mut below) = panel.split_top(8);
+        if let Some(standings) = &status.standings {
+            let rows: Vec<String> = standings
+                .rows
+                .iter()
+                .map(|(name, score)| format!("{:<12}{:>5}", name, score))
+                .collect();
+            let (table, rest) = below.split_top(rows.len() as i32);
+            ListView::new(&rows)
+                .selected(Some(standings.player))
+                .draw(ctx, table, theme);
+            below = rest;
+        }
+        let effects: Vec<String> = status
+            .effects
+            .iter()
+            .map(|(name, turns)| format!("{} ({})", name, turns))
+            .collect();
+        ListView::new(&effects).draw(ctx, below, theme);
+        Ok(())
+    }
+
+    /// Draws the tension bar with each cell colored by its zone, an arrow
+    /// for the way the fish's pull is moving it and the fight phase. The
+    /// bar flashes while one more spike would snap the line.
+    pub fn draw_tension(&self, ctx: &mut dyn Renderer, fight: &FightView) -> GameResult<()> {
+        if self.layout != UILayout::Fishing {
+            return Ok(());
+        }
+        let map = self.screen().map;
+        let row = Rect::new(FIGHT_X, TENSION_Y, map.right() - FIGHT_X, 1);
+        let flash = fight.near_breaking && self.blink_on();
+        let bg = if flash {
+            RGB::named(RED)
         } else {
-            LOG_Y
+            self.theme.background
         };
-        ctx.print(70, base_y, format!("HP: {}", hp));
-        ctx.print(70, base_y + 1, format!("Line: {}", line));
-        ctx.print(70, base_y + 2, format!("Depth: {}m", depth));
-        let bar = hunger_bar_string(hunger, 100);
-        use bracket_lib::prelude::*;
-        let color = if hunger > 60 {
-            GREEN
-        } else if hunger > 30 {
-            YELLOW
+        let (max, cells) = (fight.max_tension, widgets::BAR_WIDTH as i32);
+        let bar = widgets::bar_string(fight.tension, max);
+        let mut x = row.x;
+        let mut print = |ctx: &mut dyn Renderer, fg: RGB, text: &str| {
+            let area = Rect::new(x, row.y, row.right() - x, 1);
+            widgets::print_in(ctx, area, fg, bg, text);
+            x += text.chars().count() as i32;
+        };
+        let text = if flash {
+            RGB::named(WHITE)
         } else {
-            RED
+            self.theme.text
         };
-        ctx.print_color(
-            70,
-            base_y + 3,
-            color,
-            RGB::named(BLACK),
-            format!("Food: {}", bar),
+        print(ctx, text, "Tension [");
+        for (i, glyph) in bar.chars().skip(1).take(widgets::BAR_WIDTH).enumerate() {
+            let zone = tension_zone(i as i32 * max / cells, max);
+            let fg = if flash { text } else { zone.color() };
+            print(ctx, fg, &glyph.to_string());
+        }
+        let detail = format!(
+            "] {}/{} {} {}",
+            fight.tension,
+            max,
+            trend_arrow(fight.trend),
+            fight.phase
         );
-        ctx.print(70, base_y + 4, format!("Time: {}", time));
+        print(ctx, text, &detail);
         Ok(())
     }
 
-    /// Draws a simple tension bar using ASCII.
-    pub fn draw_tension(&self, ctx: &mut BTerm, tension: i32, max: i32) -> GameResult<()> {
+    /// Draws the fight screen in place of the map while a fish is hooked.
+    pub fn draw_fight(&self, ctx: &mut dyn Renderer, fight: &FightView) -> GameResult<()> {
         if self.layout != UILayout::Fishing {
             return Ok(());
         }
-        let bar = tension_bar_string(tension, max);
-        ctx.print(0, TENSION_Y, bar);
+        ctx.print(FIGHT_X, FIGHT_Y, "-- Fight --");
+        ctx.print_color(
+            FIGHT_X,
+            FIGHT_Y + 2,
+            RGB::named(CYAN),
+            RGB::named(BLACK),
+            fish_silhouette(fight.strength),
+        );
+        ctx.print(FIGHT_X, FIGHT_Y + 3, fight.name.unwrap_or("???"));
+        self.draw_tension(ctx, fight)?;
+        if fight.near_breaking {
+            ctx.print_color(
+                FIGHT_X,
+                TENSION_Y + 1,
+                RGB::named(RED),
+                RGB::named(BLACK),
+                "DANGER - one more pull could snap the line!",
+            );
+        } else if fight.slack {
+            ctx.print_color(
+                FIGHT_X,
+                TENSION_Y + 1,
+                RGB::named(YELLOW),
+                RGB::named(BLACK),
+                "SLACK - the fish may slip free!",
+            );
+        }
+        ctx.print(
+            FIGHT_X,
+            TENSION_Y + 2,
+            &format!(
+                "Fish  {}",
+                tension_bar_string(fight.stamina, fight.max_stamina)
+            ),
+        );
+        ctx.print(
+            FIGHT_X,
+            TENSION_Y + 3,
+            &format!(
+                "Line  {}",
+                line_remaining_string(fight.line, fight.max_line)
+            ),
+        );
+        let stage = if fight.stages > 1 {
+            format!("  Stage {}/{}", fight.stage, fight.stages)
+        } else {
+            String::new()
+        };
+        ctx.print(
+            FIGHT_X,
+            TENSION_Y + 4,
+            &format!("Turns left: {}{}", fight.turns_left.max(0), stage),
+        );
+        ctx.print(
+            FIGHT_X,
+            TENSION_Y + 5,
+            &format!("Phase: {} - {}", fight.phase, fight.hint),
+        );
+        let condition = |name: &str, durability: Option<i32>| {
+            durability.map_or(String::new(), |d| format!("{} {}% ", name, d))
+        };
+        ctx.print(
+            FIGHT_X,
+            TENSION_Y + 6,
+            &format!(
+                "{}{}",
+                condition("Rod", fight.rod),
+                condition("Reel", fight.reel)
+            ),
+        );
+        if let Some(distance) = fight.threat {
+            ctx.print_color(
+                FIGHT_X,
+                TENSION_Y + 7,
+                RGB::named(RED),
+                RGB::named(BLACK),
+                &format!("A predator closes in! {} tiles away", distance),
+            );
+        }
         Ok(())
     }
 
     /// Draws the player's inventory panel.
     pub fn draw_inventory(
         &self,
-        ctx: &mut BTerm,
+        ctx: &mut dyn Renderer,
         lines: &[String],
         cursor: usize,
         focused: bool,
@@ -198,16 +1351,34 @@ impl UIContext {
         if matches!(self.layout, UILayout::Help | UILayout::Options) {
             return Ok(());
         }
-        ctx.print(60, 0, "Inventory");
-        for (i, line) in lines.iter().enumerate() {
-            let prefix = if focused && i == cursor { ">" } else { " " };
-            ctx.print(60, 1 + i as i32, format!("{}{}", prefix, line));
+        let panel = self.screen().inventory;
+        let list = Panel::titled("Inventory").draw(ctx, panel, &self.theme);
+        ListView::new(lines)
+            .selected(focused.then_some(cursor))
+            .draw(ctx, list, &self.theme);
+        Ok(())
+    }
+
+    /// Draws the active quests with their progress.
+    pub fn draw_quests(&self, ctx: &mut dyn Renderer, quests: &[QuestView]) -> GameResult<()> {
+        if matches!(self.layout, UILayout::Help | UILayout::Options) {
+            return Ok(());
+        }
+        let panel = self.screen().quest;
+        ctx.print(panel.x, panel.y, "Quests");
+        let rows = (panel.height - 1) as usize;
+        for (i, quest) in quests.iter().enumerate().take(rows) {
+            ctx.print(
+                panel.x,
+                panel.y + 1 + i as i32,
+                &quest_line(quest, panel.width as usize),
+            );
         }
         Ok(())
     }
 
     /// Draws help text when in `Help` layout.
-    pub fn draw_help(&self, ctx: &mut BTerm) -> GameResult<()> {
+    pub fn draw_help(&self, ctx: &mut dyn Renderer) -> GameResult<()> {
         if self.layout != UILayout::Help {
             return Ok(());
         }
@@ -217,22 +1388,180 @@ impl UIContext {
         Ok(())
     }
 
-    /// Draws options text when in `Options` layout.
-    pub fn draw_options(
+    /// Draws the achievement list when in `Achievements` layout.
+    pub fn draw_achievements(
         &self,
-        ctx: &mut BTerm,
-        colorblind: bool,
-        volume: u8,
-        cast_key: VirtualKeyCode,
-        font_scale: u8,
+        ctx: &mut dyn Renderer,
+        achievements: &[AchievementView],
     ) -> GameResult<()> {
-        if self.layout != UILayout::Options {
+        if self.layout != UILayout::Achievements {
+            return Ok(());
+        }
+        let unlocked = achievements.iter().filter(|a| a.unlocked).count();
+        ctx.print_centered(
+            2,
+            &format!("Achievements {}/{}", unlocked, achievements.len()),
+        );
+        for (i, a) in achievements.iter().enumerate() {
+            let (mark, fg) = if a.unlocked {
+                ('*', RGB::named(GOLD))
+            } else {
+                (' ', RGB::named(GRAY))
+            };
+            ctx.print_color(
+                4,
+                4 + i as i32,
+                fg,
+                RGB::named(BLACK),
+                &format!("[{}] {:<18} {}", mark, a.name, a.description),
+            );
+        }
+        ctx.print_centered(5 + achievements.len() as i32, "T/O: Back");
+        Ok(())
+    }
+
+    /// Draws the species list when in `Codex` layout. Species never caught
+    /// show `???` in place of their details.
+    pub fn draw_codex(&self, ctx: &mut dyn Renderer, entries: &[CodexView]) -> GameResult<()> {
+        if self.layout != UILayout::Codex {
             return Ok(());
         }
-        for (i, line) in options_strings(colorblind, volume, cast_key, font_scale)
+        let found = entries.iter().filter(|e| e.count > 0).count();
+        ctx.print_centered(1, &format!("Codex {}/{}", found, entries.len()));
+        ctx.print(
+            2,
+            3,
+            &format!(
+                "{:<20} {:>6} {:>6} {:>9}  {}",
+                "Species", "Caught", "Rarity", "Depth", "Fight"
+            ),
+        );
+        for (i, entry) in entries.iter().enumerate() {
+            let fg = if entry.count == 0 {
+                RGB::named(GRAY)
+            } else if entry.fish.legendary {
+                RGB::named(GOLD)
+            } else {
+                RGB::named(WHITE)
+            };
+            ctx.print_color(2, 4 + i as i32, fg, RGB::named(BLACK), &codex_line(entry));
+        }
+        // Lore of the legendary fish landed so far
+        let mut y = 5 + entries.len() as i32;
+        for entry in entries.iter().filter(|e| e.count > 0) {
+            let Some(boss) = entry.fish.boss.as_ref().filter(|b| !b.lore.is_empty()) else {
+                continue;
+            };
+            let text = format!("{}: {}", entry.fish.name, boss.lore);
+            for line in wrap_text(&text, CODEX_LORE_WIDTH) {
+                ctx.print_color(2, y, RGB::named(GOLD), RGB::named(BLACK), &line);
+                y += 1;
+            }
+        }
+        ctx.print_centered(y, "Esc/D: Back");
+        Ok(())
+    }
+
+    /// Draws the travel journal when in `Journal` layout: the totals in
+    /// `lines[0]`, then a line per day, the latest days when they do not
+    /// all fit.
+    pub fn draw_journal(&self, ctx: &mut dyn Renderer, lines: &[String]) -> GameResult<()> {
+        if self.layout != UILayout::Journal {
+            return Ok(());
+        }
+        let (_, height) = ctx.dimensions();
+        ctx.print_centered(1, "Travel Journal");
+        let Some((total, days)) = lines.split_first() else {
+            return Ok(());
+        };
+        ctx.print_color(2, 3, RGB::named(GOLD), RGB::named(BLACK), total);
+        let rows: Vec<String> = days
             .iter()
-            .enumerate()
-        {
+            .flat_map(|day| wrap_text(day, CODEX_LORE_WIDTH))
+            .collect();
+        let fit = (height - 8).max(0) as usize;
+        for (i, row) in rows[rows.len().saturating_sub(fit)..].iter().enumerate() {
+            ctx.print(2, 5 + i as i32, row);
+        }
+        ctx.print_centered(height - 2, "Esc/E: Back");
+        Ok(())
+    }
+
+    /// Draws the minimap over the top-right corner of the map window, scaled
+    /// so the whole map fits.
+    pub fn draw_minimap(&self, ctx: &mut dyn Renderer, map: &MinimapView) -> GameResult<()> {
+        let scale = map.scale();
+        let cols = (map.width + scale - 1) / scale;
+        let rows = (map.height + scale - 1) / scale;
+        let panel = self.screen().map;
+        let x = panel.right() - cols - 2;
+        let y = panel.y;
+        ctx.draw_box(
+            x,
+            y,
+            cols + 1,
+            rows + 1,
+            RGB::named(WHITE),
+            RGB::named(BLACK),
+        );
+        for row in 0..rows {
+            for col in 0..cols {
+                let block = Rect::new(col * scale, row * scale, scale, scale);
+                let (glyph, color) = map.cell(block);
+                ctx.set(x + 1 + col, y + 1 + row, color, RGB::named(BLACK), glyph);
+            }
+        }
+        if let Some(place) = &map.place {
+            let label: String = place.chars().take(cols as usize + 2).collect();
+            ctx.print(x, y + rows + 2, &label);
+        }
+        Ok(())
+    }
+
+    /// Draws the key binding editor in two columns when in `Keybindings`
+    /// layout. Keys shared by several actions are marked.
+    pub fn draw_keybindings(
+        &self,
+        ctx: &mut dyn Renderer,
+        view: &KeybindingsView,
+    ) -> GameResult<()> {
+        if self.layout != UILayout::Keybindings {
+            return Ok(());
+        }
+        ctx.print_centered(1, "Key Bindings");
+        for (i, binding) in view.bindings.iter().enumerate() {
+            let x = 4 + (i / BINDING_ROWS) as i32 * 38;
+            let y = 3 + (i % BINDING_ROWS) as i32;
+            let (fg, bg) = if i != view.cursor {
+                let fg = if binding.conflict { RED } else { WHITE };
+                (RGB::named(fg), RGB::named(BLACK))
+            } else if view.capturing {
+                (RGB::named(BLACK), RGB::named(YELLOW))
+            } else {
+                (RGB::named(BLACK), RGB::named(WHITE))
+            };
+            ctx.print_color(x, y, fg, bg, &binding_line(binding));
+        }
+        let footer = match view.bindings.get(view.cursor) {
+            Some(selected) if view.capturing => {
+                format!("Press a key for {} (Esc: cancel)", selected.action)
+            }
+            _ => "move: choose  Enter: rebind  Esc/O: back".to_string(),
+        };
+        let y = 4 + BINDING_ROWS as i32;
+        ctx.print_centered(y, &footer);
+        if view.bindings.iter().any(|b| b.conflict) {
+            ctx.print_centered(y + 1, "!: key shared with another action");
+        }
+        Ok(())
+    }
+
+    /// Draws options text when in `Options` layout.
+    pub fn draw_options(&self, ctx: &mut dyn Renderer, options: &OptionsView) -> GameResult<()> {
+        if self.layout != UILayout::Options {
+            return Ok(());
+        }
+        for (i, line) in options_strings(options).iter().enumerate() {
             ctx.print_centered(5 + i as i32, line);
         }
         Ok(())
@@ -240,19 +1569,154 @@ impl UIContext {
 }
 
 fn tension_bar_string(tension: i32, max: i32) -> String {
-    let width = 10;
-    let filled = ((tension as f32 / max as f32) * width as f32).round() as usize;
-    format!("[{}{}]", "#".repeat(filled), "-".repeat(width - filled))
+    widgets::bar_string(tension, max)
+}
+
+fn line_remaining_string(line: i32, max: i32) -> String {
+    format!("{} {}/{}", tension_bar_string(line, max), line, max)
+}
+
+/// Quest title cut to fit `width` with the progress at the end.
+fn quest_line(quest: &QuestView, width: usize) -> String {
+    let progress = format!(" {}/{}", quest.progress, quest.target);
+    let title: String = quest
+        .title
+        .chars()
+        .take(width.saturating_sub(progress.len()))
+        .collect();
+    format!("{}{}", title, progress)
+}
+
+/// ASCII silhouette sized by fish strength.
+fn fish_silhouette(strength: i32) -> &'static str {
+    if strength >= 12 {
+        "><((((((((*>"
+    } else if strength >= 6 {
+        "><((((*>"
+    } else {
+        "><(*>"
+    }
+}
+
+/// Glyphs used by [`sparkline`], from lowest to highest.
+const SPARK_LEVELS: [char; 7] = ['_', '.', '-', '~', '=', '*', '#'];
+
+/// Splits `values` into at most `width` consecutive buckets.
+fn chart_buckets(values: &[i32], width: usize) -> std::slice::Chunks<'_, i32> {
+    let size = values.len().div_ceil(width.max(1)).max(1);
+    values.chunks(size)
+}
+
+/// Renders a one-line sparkline of `values` scaled to `max`, averaging
+/// samples so the result is at most `width` characters wide.
+pub fn sparkline(values: &[i32], max: i32, width: usize) -> String {
+    let top = SPARK_LEVELS.len() - 1;
+    chart_buckets(values, width)
+        .map(|chunk| {
+            let avg = chunk.iter().sum::<i32>() as f32 / chunk.len() as f32;
+            let ratio = if max > 0 { avg / max as f32 } else { 0.0 };
+            SPARK_LEVELS[(ratio.clamp(0.0, 1.0) * top as f32).round() as usize]
+        })
+        .collect()
 }
 
-fn hunger_bar_string(hunger: i32, max: i32) -> String {
-    tension_bar_string(hunger, max)
+/// Renders a vertical bar chart `height` rows tall, summing samples into at
+/// most `width` columns. Rows are returned top to bottom.
+pub fn bar_chart(values: &[i32], width: usize, height: usize) -> Vec<String> {
+    let sums: Vec<i32> = chart_buckets(values, width)
+        .map(|chunk| chunk.iter().sum())
+        .collect();
+    let max = sums.iter().copied().max().unwrap_or(0);
+    (1..=height)
+        .rev()
+        .map(|row| {
+            sums.iter()
+                .map(|&sum| {
+                    let filled = if max > 0 {
+                        (sum as f32 / max as f32 * height as f32).ceil() as usize
+                    } else {
+                        0
+                    };
+                    if filled >= row {
+                        '#'
+                    } else {
+                        ' '
+                    }
+                })
+                .collect()
+        })
+        .collect()
 }
 
 pub fn init() {
     println!("Initialized crate: ui");
 }
 
+/// Splits `text` into lines of at most `width` characters at word breaks.
+pub fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        if !current.is_empty() && current.len() + 1 + word.len() > width {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// Builds the catch card: the species' art, the fish's weight and size,
+/// rarity stars, score and flavor text.
+pub fn catch_card_lines(fish: &CatchCard) -> Vec<String> {
+    let art_width = fish
+        .art
+        .iter()
+        .map(|l| l.chars().count())
+        .max()
+        .unwrap_or(0);
+    let pad = " ".repeat(POPUP_WIDTH.saturating_sub(art_width) / 2);
+    let mut lines: Vec<String> = fish.art.iter().map(|l| format!("{}{}", pad, l)).collect();
+    if !lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines.push(format!(
+        "Weight {:.1} kg ({:.1}x average)",
+        fish.weight, fish.size
+    ));
+    let stars = usize::from(fish.stars.min(5));
+    lines.push(format!(
+        "Rarity {}{}",
+        "*".repeat(stars),
+        ".".repeat(5 - stars)
+    ));
+    lines.push(format!("Score  +{}", fish.score));
+    if fish.first {
+        lines.push("New codex entry!".to_string());
+    }
+    if !fish.description.is_empty() {
+        lines.push(String::new());
+        lines.extend(wrap_text(&fish.description, POPUP_WIDTH));
+    }
+    if !fish.habitat_note.is_empty() {
+        lines.push(String::new());
+        lines.extend(wrap_text(
+            &format!("Habitat: {}", fish.habitat_note),
+            POPUP_WIDTH,
+        ));
+    }
+    lines.push(String::new());
+    lines.push("(press any key)".to_string());
+    lines
+}
+
+/// Returns display names for caught fish, or `(empty)`.
+#[cfg(test)]
 fn inventory_strings(items: &[data::FishType]) -> Vec<String> {
     if items.is_empty() {
         vec!["(empty)".to_string()]
@@ -261,33 +1725,91 @@ fn inventory_strings(items: &[data::FishType]) -> Vec<String> {
     }
 }
 
+fn codex_line(entry: &CodexView) -> String {
+    let fish = entry.fish;
+    if entry.count == 0 {
+        return format!(
+            "{:<20} {:>6} {:>6} {:>9}  {}",
+            "???", 0, "???", "???", "???"
+        );
+    }
+    format!(
+        "{:<20} {:>6} {:>6.2} {:>9}  {:?}",
+        fish.name,
+        entry.count,
+        fish.rarity,
+        format!("{}-{}m", fish.min_depth, fish.max_depth),
+        fish.fight_style
+    )
+}
+
 fn help_strings() -> Vec<String> {
     vec![
         "Controls:".to_string(),
         "Arrow keys / hjkl: Move".to_string(),
-        "c: Cast line".to_string(),
-        "r: Reel".to_string(),
+        "c: Cast line / strike on a bite (!)".to_string(),
+        "r: Reel / change retrieve while aiming".to_string(),
+        "z: Change lure depth while aiming".to_string(),
+        "v: Throw chum".to_string(),
+        "w: Build a campfire (3 driftwood)".to_string(),
         "i: Toggle Inventory".to_string(),
+        "p: Photo mode".to_string(),
+        "t: Board/leave boat".to_string(),
+        "Walk into $: Trade with the merchant".to_string(),
+        "m: Drop/weigh anchor".to_string(),
+        "d: Codex".to_string(),
+        "e: Travel journal".to_string(),
+        "a: Toggle trail".to_string(),
+        "F2: Export the map".to_string(),
+        "/: Message log".to_string(),
+        "Tab: Toggle minimap".to_string(),
         "F1: Toggle this help".to_string(),
-        "Esc/Q: Quit".to_string(),
+        "Esc/Q: Pause menu".to_string(),
     ]
 }
 
-fn options_strings(
-    colorblind: bool,
-    volume: u8,
-    cast_key: VirtualKeyCode,
-    font_scale: u8,
-) -> Vec<String> {
+/// One editor row: action, key name and a conflict mark.
+fn binding_line(binding: &BindingView) -> String {
+    format!(
+        "{:<12}{:<15}{}",
+        binding.action,
+        binding.key,
+        if binding.conflict { "!" } else { " " }
+    )
+}
+
+fn options_strings(options: &OptionsView) -> Vec<String> {
     vec![
         "Options:".to_string(),
         format!(
             "C: Colorblind Mode [{}]",
-            if colorblind { "On" } else { "Off" }
+            if options.colorblind { "On" } else { "Off" }
+        ),
+        format!("+/-: Effects Volume {}", options.sfx_volume),
+        format!(",/.: Music Volume {}", options.music_volume),
+        format!("M: Mute [{}]", if options.muted { "On" } else { "Off" }),
+        format!("[/]: Font Scale {}x", options.font_scale),
+        format!("1: Cast Key [{:?}]", options.cast_key),
+        format!(
+            "A: Adaptive Difficulty [{}]",
+            if options.adaptive { "On" } else { "Off" }
+        ),
+        format!(
+            "R: Real-time Fishing [{}]",
+            if options.realtime_fights { "On" } else { "Off" }
+        ),
+        format!("P: Palette [{}]", options.palette),
+        format!(
+            "F: Fullscreen [{}] (restart)",
+            if options.fullscreen { "On" } else { "Off" }
+        ),
+        format!(
+            "V: VSync [{}] (restart)",
+            if options.vsync { "On" } else { "Off" }
         ),
-        format!("+/-: Volume {}", volume),
-        format!("[/]: Font Scale {}x", font_scale),
-        format!("1: Cast Key [{:?}]", cast_key),
+        format!("W: Window Size [{}] (restart)", options.window_size),
+        "K: Key Bindings".to_string(),
+        "T: Achievements".to_string(),
         "O: Back".to_string(),
     ]
 }
@@ -324,8 +1846,116 @@ mod tests {
     }
 
     #[test]
-    fn hunger_bar_alias() {
-        assert_eq!(super::hunger_bar_string(5, 10), "[#####-----]");
+    fn tension_bar_clamps_out_of_range() {
+        assert_eq!(super::tension_bar_string(15, 10), "[##########]");
+        assert_eq!(super::tension_bar_string(-3, 10), "[----------]");
+        assert_eq!(super::tension_bar_string(3, 0), "[----------]");
+    }
+
+    #[test]
+    fn stat_levels_follow_thresholds() {
+        assert_eq!(stat_level(100, 100), StatLevel::Normal);
+        assert_eq!(stat_level(50, 100), StatLevel::Warning);
+        assert_eq!(stat_level(2, 10), StatLevel::Critical);
+        assert_eq!(stat_level(0, 0), StatLevel::Critical);
+    }
+
+    #[test]
+    fn critical_stats_blink() {
+        let on = stat_colors(StatLevel::Critical, true);
+        let off = stat_colors(StatLevel::Critical, false);
+        assert_ne!(on, off);
+        assert_eq!(
+            stat_colors(StatLevel::Normal, true),
+            stat_colors(StatLevel::Normal, false)
+        );
+    }
+
+    #[test]
+    fn blink_toggles_with_frames() {
+        let mut ui = UIContext::default();
+        let first = ui.blink_on();
+        for _ in 0..BLINK_FRAMES {
+            ui.next_frame();
+        }
+        assert_ne!(ui.blink_on(), first);
+    }
+
+    #[test]
+    fn tension_bar_grades_zones_and_shows_the_trend() {
+        assert_eq!(tension_zone(49, 100), TensionZone::Safe);
+        assert_eq!(tension_zone(50, 100), TensionZone::Warning);
+        assert_eq!(tension_zone(80, 100), TensionZone::Danger);
+        assert_eq!(tension_zone(3, 0), TensionZone::Danger);
+        let mut ui = UIContext::default();
+        ui.set_layout(UILayout::Fishing);
+        let mut fight = FightView {
+            name: None,
+            strength: 5,
+            tension: 85,
+            max_tension: 100,
+            trend: 12,
+            slack: false,
+            near_breaking: true,
+            line: 50,
+            max_line: 100,
+            turns_left: 3,
+            stage: 1,
+            stages: 1,
+            phase: "Running",
+            hint: "give line",
+            stamina: 5,
+            max_stamina: 10,
+            rod: None,
+            reel: None,
+            threat: None,
+        };
+        let mut buf = TextBuffer::new(MIN_SCREEN_WIDTH, MIN_SCREEN_HEIGHT);
+        ui.draw_fight(&mut buf, &fight).unwrap();
+        assert!(buf.contains("Tension [#########-] 85/100 ↑ Running"));
+        assert!(buf.contains("DANGER"));
+        fight.trend = -4;
+        fight.near_breaking = false;
+        buf.cls();
+        ui.draw_fight(&mut buf, &fight).unwrap();
+        assert!(buf.contains("85/100 ↓ Running"));
+        assert!(!buf.contains("DANGER"));
+    }
+
+    #[test]
+    fn silhouette_grows_with_strength() {
+        assert!(fish_silhouette(2).len() < fish_silhouette(8).len());
+        assert!(fish_silhouette(8).len() < fish_silhouette(15).len());
+    }
+
+    #[test]
+    fn line_remaining_shows_numbers() {
+        assert_eq!(line_remaining_string(50, 100), "[#####-----] 50/100");
+    }
+
+    #[test]
+    fn sparkline_scales_to_max() {
+        assert_eq!(sparkline(&[0, 5, 10], 10, 10), "_~#");
+        assert_eq!(sparkline(&[], 10, 10), "");
+        assert_eq!(sparkline(&[3], 0, 10), "_");
+    }
+
+    #[test]
+    fn sparkline_averages_into_width() {
+        let values: Vec<i32> = (0..100).map(|i| if i < 50 { 0 } else { 10 }).collect();
+        assert_eq!(sparkline(&values, 10, 4), "__##");
+    }
+
+    #[test]
+    fn bar_chart_sums_buckets() {
+        let rows = bar_chart(&[1, 0, 0, 0, 1, 1], 3, 2);
+        assert_eq!(rows, vec!["  #".to_string(), "# #".to_string()]);
+        assert_eq!(bar_chart(&[0, 0], 2, 1), vec!["  ".to_string()]);
+    }
+
+    #[test]
+    fn hunger_bar_matches_the_others() {
+        assert_eq!(Bar::new("Food", 5, 10).text(), "Food [#####-----]");
     }
 
     #[test]
@@ -342,6 +1972,38 @@ mod tests {
         assert_eq!(ui.layout(), UILayout::Options);
     }
 
+    #[test]
+    fn message_log_filters_searches_and_stamps_turns() {
+        let mut ui = UIContext::default();
+        ui.set_turn(3);
+        ui.add_entry(LogCategory::Fishing, "Hooked a fish!")
+            .unwrap();
+        ui.set_turn(4);
+        ui.add_entry(LogCategory::Event, "Rain sets in.").unwrap();
+        ui.add_log("Game saved.").unwrap();
+        assert_eq!(ui.entries()[2].category, LogCategory::System);
+        let mut buf = TextBuffer::new(MIN_SCREEN_WIDTH, MIN_SCREEN_HEIGHT);
+        ui.set_layout(UILayout::Standard);
+        ui.cycle_log_filter();
+        ui.cycle_log_filter();
+        assert_eq!(ui.log_filter(), Some(LogCategory::Fishing));
+        ui.draw_logs(&mut buf).unwrap();
+        assert!(buf.contains("Hooked a fish!"));
+        assert!(!buf.contains("Rain"), "filtered out of the panel");
+
+        ui.open_message_log();
+        ui.cycle_log_filter();
+        ui.set_log_search("RAIN");
+        buf.cls();
+        ui.draw_message_log(&mut buf).unwrap();
+        assert!(buf.contains("Filter: Event    Search: RAIN_"));
+        assert!(buf.contains("    4 Event   Rain sets in."));
+        assert!(!buf.contains("Hooked"));
+        ui.close_message_log();
+        assert_eq!(ui.layout(), UILayout::Standard);
+        assert_eq!(ui.log_search(), "");
+    }
+
     #[test]
     fn scrolling_bounds() {
         let mut ui = UIContext::default();
@@ -356,6 +2018,211 @@ mod tests {
         assert_eq!(ui.scroll, 0);
     }
 
+    #[test]
+    fn keybindings_list_actions_and_mark_conflicts() {
+        let mut ui = UIContext::default();
+        ui.set_layout(UILayout::Keybindings);
+        let bindings: Vec<BindingView> = (0..20)
+            .map(|i| BindingView {
+                action: if i == 16 { "minimap" } else { "cast" },
+                key: if i == 16 { "Tab" } else { "C" },
+                conflict: i != 16,
+            })
+            .collect();
+        let mut view = KeybindingsView {
+            bindings,
+            cursor: 16,
+            capturing: false,
+        };
+        let mut buf = TextBuffer::new(80, 25);
+        ui.draw_keybindings(&mut buf, &view).unwrap();
+        assert!(buf.contains("Key Bindings"));
+        assert!(buf.row(4).contains("minimap     Tab"));
+        assert!(buf.row(3).trim_end().ends_with('!'));
+        assert!(buf.contains("!: key shared with another action"));
+        view.capturing = true;
+        ui.draw_keybindings(&mut buf, &view).unwrap();
+        assert!(buf.contains("Press a key for minimap (Esc: cancel)"));
+        ui.set_layout(UILayout::Options);
+        buf.cls();
+        ui.draw_keybindings(&mut buf, &view).unwrap();
+        assert!(!buf.contains("Key Bindings"));
+    }
+
+    #[test]
+    fn draw_options_renders_headless() {
+        let mut ui = UIContext::default();
+        ui.set_layout(UILayout::Options);
+        let mut buf = TextBuffer::new(80, 25);
+        ui.draw_options(&mut buf, &options(true, 1, false)).unwrap();
+        assert!(buf.contains("C: Colorblind Mode [On]"));
+        ui.set_layout(UILayout::Standard);
+        buf.cls();
+        ui.draw_options(&mut buf, &options(true, 1, false)).unwrap();
+        assert!(!buf.contains("Options:"));
+    }
+
+    #[test]
+    fn panels_do_not_overlap() {
+        for (width, height) in [(80, 25), (132, 43), (200, 60)] {
+            let layout = ScreenLayout::new(width, height);
+            let screen = Rect::new(0, 0, width, height);
+            assert_eq!(layout.screen(), screen);
+            let panels = [
+                layout.map,
+                layout.log,
+                layout.status,
+                layout.inventory,
+                layout.quest,
+            ];
+            let cells: i32 = panels.iter().map(|p| p.width * p.height).sum();
+            assert_eq!(cells, width * height);
+            for (i, a) in panels.iter().enumerate() {
+                assert_eq!(a.intersect(screen), Some(*a));
+                for b in &panels[i + 1..] {
+                    assert_eq!(a.intersect(*b), None);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn default_layout_matches_the_classic_screen() {
+        let layout = ScreenLayout::default();
+        assert_eq!(layout.map, Rect::new(0, 0, 60, 17));
+        assert_eq!(layout.status.x, 60);
+        assert_eq!(layout.log.height, 8);
+        assert_eq!(ScreenLayout::new(40, 10), layout);
+    }
+
+    #[test]
+    fn panels_follow_the_console_size() {
+        let ui = UIContext::default();
+        let mut buf = TextBuffer::new(120, 40);
+        ui.fit(&buf);
+        assert_eq!(ui.screen().status.x, 100);
+        assert_eq!(ui.screen().log, Rect::new(0, 27, 100, 13));
+        ui.draw_inventory(&mut buf, &["Reel".to_string()], 0, false)
+            .unwrap();
+        assert_eq!(buf.glyph(100, 0), Some('I'));
+    }
+
+    #[test]
+    fn wrap_text_breaks_on_words() {
+        assert_eq!(
+            wrap_text("the quick brown fox jumps", 10),
+            vec!["the quick", "brown fox", "jumps"]
+        );
+        assert!(wrap_text("", 10).is_empty());
+        assert_eq!(wrap_text("overlongword", 4), vec!["overlongword"]);
+    }
+
+    fn trout_card() -> CatchCard {
+        CatchCard {
+            name: "Trout".into(),
+            art: vec!["><>".into()],
+            weight: 2.45,
+            size: 1.3,
+            stars: 2,
+            first: true,
+            score: 12,
+            description: "Speckled.".into(),
+            habitat_note: "Inlets.".into(),
+        }
+    }
+
+    #[test]
+    fn catch_card_shows_art_stats_and_flavor_text() {
+        let lines = catch_card_lines(&trout_card());
+        assert_eq!(lines[0].trim(), "><>");
+        assert!(lines.contains(&"Weight 2.5 kg (1.3x average)".to_string()));
+        assert!(lines.contains(&"Rarity **...".to_string()));
+        assert!(lines.contains(&"Score  +12".to_string()));
+        assert!(lines.contains(&"New codex entry!".to_string()));
+        assert!(lines.contains(&"Speckled.".to_string()));
+        assert!(lines.contains(&"Habitat: Inlets.".to_string()));
+        let bare = catch_card_lines(&CatchCard {
+            art: Vec::new(),
+            first: false,
+            description: String::new(),
+            habitat_note: String::new(),
+            ..trout_card()
+        });
+        assert_eq!(bare.len(), 5);
+    }
+
+    #[test]
+    fn catch_card_is_modal_until_dismissed() {
+        let mut ui = UIContext::default();
+        ui.set_layout(UILayout::Standard);
+        ui.show_catch_card(trout_card());
+        assert_eq!(ui.layout(), UILayout::CatchCard);
+        let mut buf = TextBuffer::new(MIN_SCREEN_WIDTH, MIN_SCREEN_HEIGHT);
+        ui.draw_catch_card(&mut buf).unwrap();
+        assert!(buf.contains(" Trout "));
+        assert!(buf.contains("New codex entry!"));
+        assert!(ui.dismiss_catch_card());
+        assert_eq!(ui.layout(), UILayout::Standard);
+        assert!(ui.catch_card().is_none());
+        assert!(!ui.dismiss_catch_card());
+    }
+
+    #[test]
+    fn prompt_options_are_numbered_and_picked() {
+        let mut ui = UIContext::default();
+        let options = vec!["Haul it in".to_string(), "Let it drift".to_string()];
+        ui.show_prompt(Prompt::new("Crate", "A crate drifts by.", options));
+        ui.move_prompt(-1);
+        assert_eq!(ui.prompt().unwrap().selected, 1);
+        assert!(!ui.select_prompt(2));
+        let mut buf = TextBuffer::new(MIN_SCREEN_WIDTH, MIN_SCREEN_HEIGHT);
+        ui.draw_prompt(&mut buf).unwrap();
+        assert!(buf.contains(" Crate "));
+        assert!(buf.contains("A crate drifts by."));
+        assert!(buf.contains(" 1: Haul it in"));
+        assert!(buf.contains(">2: Let it drift"));
+
+        let row = (0..MIN_SCREEN_HEIGHT)
+            .find(|&y| buf.row(y).contains("1: Haul it in"))
+            .unwrap();
+        let line = buf.row(row);
+        let x = line[..line.find("1: Haul").unwrap()].chars().count() as i32;
+        assert_eq!(ui.prompt_option_at(Point::new(x, row)), Some(0));
+        assert_eq!(ui.prompt_option_at(Point::new(x, row + 1)), Some(1));
+        assert_eq!(ui.prompt_option_at(Point::new(x, row - 1)), None);
+        assert_eq!(ui.close_prompt(), Some(1));
+        assert!(ui.prompt().is_none());
+    }
+
+    #[test]
+    fn tooltip_stays_inside_the_map_panel() {
+        let ui = UIContext::default();
+        let lines = vec!["Sardine".to_string(), "Depth: 3m".to_string()];
+        let mut buf = TextBuffer::new(80, 25);
+        ui.draw_tooltip(&mut buf, Point::new(10, 5), &lines)
+            .unwrap();
+        assert_eq!(buf.glyph(12, 6), Some('┌'));
+        assert!(buf.row(7).contains("Sardine"));
+
+        // Near the bottom-right corner the box opens up and to the left
+        let panel = ui.screen().map;
+        let at = Point::new(panel.right() - 2, panel.bottom() - 2);
+        let mut buf = TextBuffer::new(80, 25);
+        ui.draw_tooltip(&mut buf, at, &lines).unwrap();
+        assert_eq!(buf.glyph(at.x - 2, at.y - 1), Some('┘'));
+        assert!(buf.row(at.y - 2).contains("Depth: 3m"));
+    }
+
+    #[test]
+    fn popup_show_and_dismiss() {
+        let mut ui = UIContext::default();
+        assert!(!ui.dismiss_popup());
+        ui.show_popup(vec!["hi".into()]);
+        assert_eq!(ui.popup(), Some(&["hi".to_string()][..]));
+        assert!(ui.dismiss_popup());
+        assert!(ui.popup().is_none());
+    }
+
     #[test]
     fn inventory_string_generation() {
         let fish = data::FishType {
@@ -367,14 +2234,163 @@ mod tests {
             max_depth: 1,
             fight_style: data::FightStyle::Aggressive,
             legendary: false,
+            description: String::new(),
+            habitat_note: String::new(),
+            diet: Vec::new(),
+            min_weight: 1.0,
+            max_weight: 1.0,
+            biomes: Vec::new(),
+            boss: None,
+            seasons: Vec::new(),
         };
         assert_eq!(
-            inventory_strings(&[fish.clone()]),
+            inventory_strings(std::slice::from_ref(&fish)),
             vec!["FishA".to_string()]
         );
         assert_eq!(inventory_strings(&[]), vec!["(empty)".to_string()]);
     }
 
+    #[test]
+    fn draw_achievements_marks_unlocks() {
+        let rows = [
+            AchievementView {
+                name: "First Catch",
+                description: "Land your first fish.",
+                unlocked: true,
+            },
+            AchievementView {
+                name: "Stuff of Legend",
+                description: "Land a legendary fish.",
+                unlocked: false,
+            },
+        ];
+        let mut ui = UIContext::default();
+        let mut buf = TextBuffer::new(80, 25);
+        ui.draw_achievements(&mut buf, &rows).unwrap();
+        assert!(!buf.contains("Achievements"));
+        ui.set_layout(UILayout::Achievements);
+        ui.draw_achievements(&mut buf, &rows).unwrap();
+        assert!(buf.contains("Achievements 1/2"));
+        assert!(buf.contains("[*] First Catch"));
+        assert!(buf.contains("[ ] Stuff of Legend"));
+    }
+
+    #[test]
+    fn draw_codex_hides_undiscovered_species() {
+        let fish = |id: &str, name: &str| data::FishType {
+            id: id.into(),
+            name: name.into(),
+            rarity: 0.25,
+            strength: 1,
+            min_depth: 2,
+            max_depth: 8,
+            fight_style: data::FightStyle::Evasive,
+            legendary: false,
+            description: String::new(),
+            habitat_note: String::new(),
+            diet: Vec::new(),
+            min_weight: 1.0,
+            max_weight: 1.0,
+            biomes: Vec::new(),
+            boss: None,
+            seasons: Vec::new(),
+        };
+        let (trout, mut eel) = (fish("T", "Trout"), fish("E", "Eel"));
+        eel.boss = Some(data::BossFight {
+            stages: 2,
+            pattern: data::BossPattern::Sound,
+            min_rod: 0,
+            bonus: 0,
+            lore: "Sleeps in the trench.".into(),
+        });
+        let mut rows = [
+            CodexView {
+                fish: &trout,
+                count: 3,
+            },
+            CodexView {
+                fish: &eel,
+                count: 0,
+            },
+        ];
+        let mut ui = UIContext::default();
+        let mut buf = TextBuffer::new(80, 25);
+        ui.draw_codex(&mut buf, &rows).unwrap();
+        assert!(!buf.contains("Codex"));
+        ui.set_layout(UILayout::Codex);
+        ui.draw_codex(&mut buf, &rows).unwrap();
+        assert!(buf.contains("Codex 1/2"));
+        assert!(buf.contains("0.25      2-8m  Evasive"));
+        assert!(!buf.contains("Eel"));
+        assert!(codex_line(&rows[1]).starts_with("???"));
+
+        // Landing a legendary unlocks its lore
+        rows[1].count = 1;
+        let mut buf = TextBuffer::new(80, 25);
+        ui.draw_codex(&mut buf, &rows).unwrap();
+        assert!(buf.contains("Eel: Sleeps in the trench."));
+    }
+
+    #[test]
+    fn journal_shows_the_totals_and_the_latest_days() {
+        let mut lines =
+            vec!["3 fish, 0 storms weathered, 9 tiles traveled over 30 days.".to_string()];
+        lines.extend((1..=30).map(|day| format!("Day {}: 0 fish.", day)));
+        let mut ui = UIContext::default();
+        let mut buf = TextBuffer::new(80, 25);
+        ui.draw_journal(&mut buf, &lines).unwrap();
+        assert!(!buf.contains("Travel Journal"));
+        ui.set_layout(UILayout::Journal);
+        ui.draw_journal(&mut buf, &lines).unwrap();
+        assert!(buf.contains("Travel Journal"));
+        assert!(buf.contains("over 30 days."));
+        assert!(buf.contains("Day 30: 0 fish."));
+        assert!(!buf.contains("Day 1: 0 fish."), "the first days scroll off");
+        assert!(buf.contains("Esc/E: Back"));
+    }
+
+    #[test]
+    fn events_are_logged() {
+        let mut ui = UIContext::default();
+        ui.on_event(&GameEvent::LineSnapped {
+            id: None,
+            lure_lost: false,
+        });
+        ui.on_event(&GameEvent::FishCaught {
+            id: "T".into(),
+            name: "Trout".into(),
+            weight: 2.45,
+            description: "Speckled.".into(),
+            habitat_note: "Inlets.".into(),
+            location: "Coast".into(),
+            turn: 1,
+            timestamp: None,
+        });
+        assert_eq!(ui.logs(), ["Line snapped!", "Caught a Trout (2.5 kg)!"]);
+        assert_eq!(ui.entries()[1].category, LogCategory::Fishing);
+        assert!(ui.popup().is_none(), "the game shows the catch card");
+    }
+
+    #[test]
+    fn quest_lines_fit_the_panel() {
+        let quest = QuestView {
+            title: "Catch a legendary in DeepSea",
+            progress: 0,
+            target: 1,
+        };
+        let width = ScreenLayout::default().quest.width as usize;
+        let line = quest_line(&quest, width);
+        assert_eq!(line.len(), width);
+        assert!(line.ends_with(" 0/1"));
+
+        let mut ui = UIContext::default();
+        ui.set_layout(UILayout::Standard);
+        let mut buf = TextBuffer::new(80, 25);
+        ui.draw_quests(&mut buf, &[quest]).unwrap();
+        assert!(buf.contains("Quests"));
+        assert!(buf.contains("Catch a legendar"));
+    }
+
     #[test]
     fn colorblind_palette_differs() {
         let normal = ColorPalette::default();
@@ -389,11 +2405,80 @@ mod tests {
         assert!(lines.iter().any(|l| l.contains("F1")));
     }
 
+    #[test]
+    fn minimap_fits_the_whole_map() {
+        let (width, height) = (160, 120);
+        let mut tiles = vec![MinimapTile::Unexplored; (width * height) as usize];
+        for y in 40..48 {
+            for x in 80..88 {
+                tiles[(y * width + x) as usize] = MinimapTile::Water;
+            }
+        }
+        tiles[(40 * width + 80) as usize] = MinimapTile::Land;
+        let map = MinimapView {
+            width,
+            height,
+            tiles,
+            player: Point::new(0, 0),
+            schools: vec![Point::new(159, 119)],
+            place: Some("Gullwing Bay".into()),
+            palette: ColorPalette::default(),
+        };
+        let ui = UIContext::default();
+        let mut buf = TextBuffer::new(80, 25);
+        ui.draw_minimap(&mut buf, &map).unwrap();
+        // 8 tiles per cell: 20x15 cells framed at the right edge of the map
+        assert_eq!(buf.glyph(38, 0), Some('┌'));
+        assert_eq!(buf.glyph(59, 16), Some('┘'));
+        assert_eq!(buf.glyph(39, 1), Some('@'));
+        assert_eq!(buf.glyph(58, 15), Some('f'));
+        assert_eq!(buf.glyph(49, 6), Some('~'));
+        assert_eq!(buf.glyph(50, 6), Some(' '));
+        assert!(buf.row(17).contains("Gullwing Bay"));
+    }
+
+    #[test]
+    fn named_palettes_resolve() {
+        for name in ["Sunset", "Reef", "Abyss"] {
+            let palette = ColorPalette::named(name).expect("palette");
+            assert_ne!(palette, ColorPalette::default());
+        }
+        assert!(ColorPalette::named("Default").is_none());
+    }
+
+    fn options(colorblind: bool, font_scale: u8, adaptive: bool) -> OptionsView<'static> {
+        OptionsView {
+            colorblind,
+            sfx_volume: 5,
+            music_volume: 5,
+            muted: adaptive,
+            cast_key: VirtualKeyCode::C,
+            font_scale,
+            adaptive,
+            realtime_fights: !adaptive,
+            palette: "Reef",
+            fullscreen: colorblind,
+            vsync: true,
+            window_size: "Auto".to_string(),
+        }
+    }
+
     #[test]
     fn options_strings_show_status() {
-        let lines_on = options_strings(true, 5, VirtualKeyCode::C, 2);
+        let lines_on = options_strings(&options(true, 2, true));
         assert!(lines_on.iter().any(|l| l.contains("On")));
-        let lines_off = options_strings(false, 5, VirtualKeyCode::C, 1);
+        assert!(lines_on
+            .iter()
+            .any(|l| l.contains("Adaptive Difficulty [On]")));
+        assert!(lines_on.iter().any(|l| l == "P: Palette [Reef]"));
+        assert!(lines_on.iter().any(|l| l == "M: Mute [On]"));
+        assert!(lines_on.iter().any(|l| l == "+/-: Effects Volume 5"));
+        assert!(lines_on.iter().any(|l| l == "R: Real-time Fishing [Off]"));
+        assert!(lines_on.iter().any(|l| l.starts_with("F: Fullscreen [On]")));
+        assert!(lines_on
+            .iter()
+            .any(|l| l.starts_with("W: Window Size [Auto]")));
+        let lines_off = options_strings(&options(false, 1, false));
         assert!(lines_off.iter().any(|l| l.contains("Off")));
         assert!(lines_off.iter().any(|l| l.contains("Font Scale")));
     }
@@ -0,0 +1,107 @@
+//! Message log entries: each message keeps the turn it was written on and
+//! what it is about, so the log can color, filter and search them.
+
+use bracket_lib::prelude::{CYAN, GRAY, RED, RGB, YELLOW};
+
+/// What a log message is about.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogCategory {
+    /// Harm to the angler: hazards, hunger, thirst and the like.
+    Combat,
+    /// Casting, bites, fights and catches.
+    Fishing,
+    /// Happenings in the world: weather, seasons, tides, quests.
+    Event,
+    /// Saving, settings and other messages about the game itself.
+    System,
+}
+
+impl LogCategory {
+    pub const ALL: [LogCategory; 4] = [
+        LogCategory::Combat,
+        LogCategory::Fishing,
+        LogCategory::Event,
+        LogCategory::System,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            LogCategory::Combat => "Combat",
+            LogCategory::Fishing => "Fishing",
+            LogCategory::Event => "Event",
+            LogCategory::System => "System",
+        }
+    }
+
+    /// Color the category's messages are drawn in.
+    pub fn color(self) -> RGB {
+        match self {
+            LogCategory::Combat => RGB::named(RED),
+            LogCategory::Fishing => RGB::named(CYAN),
+            LogCategory::Event => RGB::named(YELLOW),
+            LogCategory::System => RGB::named(GRAY),
+        }
+    }
+
+    /// The filter after `filter`: all messages, then each category in
+    /// turn.
+    pub fn next_filter(filter: Option<LogCategory>) -> Option<LogCategory> {
+        match filter {
+            None => Some(Self::ALL[0]),
+            Some(category) => {
+                let idx = Self::ALL.iter().position(|&c| c == category).unwrap_or(0);
+                Self::ALL.get(idx + 1).copied()
+            }
+        }
+    }
+}
+
+/// One message in the log.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogEntry {
+    pub category: LogCategory,
+    /// Turn the message was written on.
+    pub turn: u32,
+    pub text: String,
+}
+
+impl LogEntry {
+    /// Whether the entry passes `filter` and contains `search`, ignoring
+    /// case.
+    pub fn matches(&self, filter: Option<LogCategory>, search: &str) -> bool {
+        (filter.is_none() || filter == Some(self.category))
+            && self.text.to_lowercase().contains(&search.to_lowercase())
+    }
+
+    /// The entry as a row of the full log: turn, category and text.
+    pub fn line(&self) -> String {
+        format!("{:>5} {:<8}{}", self.turn, self.category.name(), self.text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_filter_and_search() {
+        let entry = LogEntry {
+            category: LogCategory::Fishing,
+            turn: 12,
+            text: "Caught a Trout!".into(),
+        };
+        assert!(entry.matches(None, ""));
+        assert!(entry.matches(Some(LogCategory::Fishing), "trout"));
+        assert!(!entry.matches(Some(LogCategory::Event), ""));
+        assert!(!entry.matches(None, "salmon"));
+        assert_eq!(entry.line(), "   12 Fishing Caught a Trout!");
+        let mut filter = None;
+        let mut seen = Vec::new();
+        for _ in 0..5 {
+            filter = LogCategory::next_filter(filter);
+            seen.push(filter);
+        }
+        assert_eq!(seen[0], Some(LogCategory::Combat));
+        assert_eq!(seen[4], None, "back to all messages");
+    }
+}
@@ -0,0 +1,174 @@
+//! Drawing backend abstraction so screens can render without a window.
+
+use bracket_lib::prelude::{to_cp437, BTerm, BLACK, RGB, WHITE};
+use common::GameResult;
+
+/// Minimal set of drawing operations used by the game's screens.
+pub trait Renderer {
+    /// Draws `glyph` at a cell.
+    fn set(&mut self, x: i32, y: i32, fg: RGB, bg: RGB, glyph: char);
+    /// Prints `text` in the default colors.
+    fn print(&mut self, x: i32, y: i32, text: &str);
+    /// Clears the whole screen.
+    fn cls(&mut self);
+    /// Screen size in cells as `(width, height)`.
+    fn dimensions(&self) -> (i32, i32);
+
+    /// Shows the frame drawn since the last call. Backends whose drawing is
+    /// already on screen, like bracket-lib after each tick, do nothing.
+    fn present(&mut self) -> GameResult<()> {
+        Ok(())
+    }
+
+    /// Prints `text` with explicit colors.
+    fn print_color(&mut self, x: i32, y: i32, fg: RGB, bg: RGB, text: &str) {
+        for (i, glyph) in text.chars().enumerate() {
+            self.set(x + i as i32, y, fg, bg, glyph);
+        }
+    }
+
+    /// Prints `text` horizontally centered on row `y`.
+    fn print_centered(&mut self, y: i32, text: &str) {
+        let (width, _) = self.dimensions();
+        let x = (width - text.chars().count() as i32) / 2;
+        self.print(x.max(0), y, text);
+    }
+
+    /// Draws a single-line frame spanning `x..=x + width` and clears its inside.
+    fn draw_box(&mut self, x: i32, y: i32, width: i32, height: i32, fg: RGB, bg: RGB) {
+        for by in y..=y + height {
+            for bx in x..=x + width {
+                let glyph = match (bx == x || bx == x + width, by == y || by == y + height) {
+                    (true, true) => match (bx == x, by == y) {
+                        (true, true) => '┌',
+                        (false, true) => '┐',
+                        (true, false) => '└',
+                        (false, false) => '┘',
+                    },
+                    (true, false) => '│',
+                    (false, true) => '─',
+                    (false, false) => ' ',
+                };
+                self.set(bx, by, fg, bg, glyph);
+            }
+        }
+    }
+}
+
+impl Renderer for BTerm {
+    fn set(&mut self, x: i32, y: i32, fg: RGB, bg: RGB, glyph: char) {
+        BTerm::set(self, x, y, fg, bg, to_cp437(glyph));
+    }
+
+    fn print(&mut self, x: i32, y: i32, text: &str) {
+        BTerm::print(self, x, y, text);
+    }
+
+    fn cls(&mut self) {
+        BTerm::cls(self);
+    }
+
+    fn dimensions(&self) -> (i32, i32) {
+        let (w, h) = self.get_char_size();
+        (w as i32, h as i32)
+    }
+
+    fn print_color(&mut self, x: i32, y: i32, fg: RGB, bg: RGB, text: &str) {
+        BTerm::print_color(self, x, y, fg, bg, text);
+    }
+
+    fn print_centered(&mut self, y: i32, text: &str) {
+        BTerm::print_centered(self, y, text);
+    }
+
+    fn draw_box(&mut self, x: i32, y: i32, width: i32, height: i32, fg: RGB, bg: RGB) {
+        BTerm::draw_box(self, x, y, width, height, fg, bg);
+    }
+}
+
+/// Headless renderer that records glyphs in memory, for tests and tools.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextBuffer {
+    width: i32,
+    height: i32,
+    cells: Vec<char>,
+}
+
+impl TextBuffer {
+    /// Creates a blank buffer of `width` x `height` cells.
+    pub fn new(width: i32, height: i32) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![' '; (width * height).max(0) as usize],
+        }
+    }
+
+    /// Glyph at a cell, or `None` outside the buffer.
+    pub fn glyph(&self, x: i32, y: i32) -> Option<char> {
+        (x >= 0 && x < self.width && y >= 0 && y < self.height)
+            .then(|| self.cells[(y * self.width + x) as usize])
+    }
+
+    /// Contents of row `y` with trailing spaces removed.
+    pub fn row(&self, y: i32) -> String {
+        (0..self.width)
+            .filter_map(|x| self.glyph(x, y))
+            .collect::<String>()
+            .trim_end()
+            .to_string()
+    }
+
+    /// Whether any row contains `text`.
+    pub fn contains(&self, text: &str) -> bool {
+        (0..self.height).any(|y| self.row(y).contains(text))
+    }
+}
+
+impl Renderer for TextBuffer {
+    fn set(&mut self, x: i32, y: i32, _fg: RGB, _bg: RGB, glyph: char) {
+        if self.glyph(x, y).is_some() {
+            self.cells[(y * self.width + x) as usize] = glyph;
+        }
+    }
+
+    fn print(&mut self, x: i32, y: i32, text: &str) {
+        self.print_color(x, y, RGB::named(WHITE), RGB::named(BLACK), text);
+    }
+
+    fn cls(&mut self) {
+        self.cells.fill(' ');
+    }
+
+    fn dimensions(&self) -> (i32, i32) {
+        (self.width, self.height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_buffer_prints_and_clips() {
+        let mut buf = TextBuffer::new(10, 2);
+        buf.print(7, 0, "hello");
+        assert_eq!(buf.row(0), "       hel");
+        buf.print_centered(1, "ab");
+        assert_eq!(buf.row(1), "    ab");
+        assert!(buf.contains("hel"));
+        buf.cls();
+        assert_eq!(buf.row(0), "");
+        assert_eq!(buf.glyph(10, 0), None);
+        assert!(buf.present().is_ok());
+    }
+
+    #[test]
+    fn default_box_draws_corners() {
+        let mut buf = TextBuffer::new(5, 4);
+        buf.draw_box(0, 0, 3, 2, RGB::named(WHITE), RGB::named(BLACK));
+        assert_eq!(buf.row(0), "┌──┐");
+        assert_eq!(buf.row(1), "│  │");
+        assert_eq!(buf.row(2), "└──┘");
+    }
+}
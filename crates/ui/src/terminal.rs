@@ -0,0 +1,168 @@
+//! Text terminal backend drawing through crossterm escape sequences.
+
+use crate::render::Renderer;
+use bracket_lib::prelude::{BLACK, RGB, WHITE};
+use common::GameResult;
+use crossterm::cursor::MoveTo;
+use crossterm::queue;
+use crossterm::style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor};
+use std::io::{self, Write};
+
+/// One character cell with its colors.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Cell {
+    glyph: char,
+    fg: RGB,
+    bg: RGB,
+}
+
+impl Cell {
+    fn blank() -> Self {
+        Self {
+            glyph: ' ',
+            fg: RGB::named(WHITE),
+            bg: RGB::named(BLACK),
+        }
+    }
+}
+
+fn color(rgb: RGB) -> Color {
+    let channel = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+    Color::Rgb {
+        r: channel(rgb.r),
+        g: channel(rgb.g),
+        b: channel(rgb.b),
+    }
+}
+
+/// Renderer that buffers a frame and writes it to a terminal on [`flush`],
+/// or to standard output on [`Renderer::present`].
+///
+/// Only cells changed since the previous flush are sent, which keeps
+/// redraws cheap over slow connections such as SSH.
+///
+/// [`flush`]: TerminalRenderer::flush
+#[derive(Clone, Debug)]
+pub struct TerminalRenderer {
+    width: i32,
+    height: i32,
+    cells: Vec<Cell>,
+    shown: Vec<Option<Cell>>,
+}
+
+impl TerminalRenderer {
+    /// Creates a renderer for a `width` x `height` cell screen.
+    pub fn new(width: i32, height: i32) -> Self {
+        let len = (width * height).max(0) as usize;
+        Self {
+            width,
+            height,
+            cells: vec![Cell::blank(); len],
+            shown: vec![None; len],
+        }
+    }
+
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        (x >= 0 && x < self.width && y >= 0 && y < self.height)
+            .then(|| (y * self.width + x) as usize)
+    }
+
+    /// Glyph at a cell, or `None` outside the screen.
+    pub fn glyph(&self, x: i32, y: i32) -> Option<char> {
+        self.index(x, y).map(|i| self.cells[i].glyph)
+    }
+
+    /// Forces the next [`flush`](Self::flush) to redraw every cell.
+    pub fn invalidate(&mut self) {
+        self.shown.fill(None);
+    }
+
+    /// Writes the cells changed since the last flush to `out`.
+    pub fn flush(&mut self, out: &mut impl Write) -> io::Result<()> {
+        let mut colors: Option<(RGB, RGB)> = None;
+        let mut cursor: Option<(u16, u16)> = None;
+        for i in 0..self.cells.len() {
+            let cell = self.cells[i];
+            if self.shown[i] == Some(cell) {
+                continue;
+            }
+            let x = (i as i32 % self.width) as u16;
+            let y = (i as i32 / self.width) as u16;
+            if cursor != Some((x, y)) {
+                queue!(out, MoveTo(x, y))?;
+            }
+            if colors != Some((cell.fg, cell.bg)) {
+                queue!(
+                    out,
+                    SetForegroundColor(color(cell.fg)),
+                    SetBackgroundColor(color(cell.bg))
+                )?;
+                colors = Some((cell.fg, cell.bg));
+            }
+            queue!(out, Print(cell.glyph))?;
+            cursor = Some((x + 1, y));
+            self.shown[i] = Some(cell);
+        }
+        queue!(out, ResetColor)?;
+        out.flush()
+    }
+}
+
+impl Renderer for TerminalRenderer {
+    fn set(&mut self, x: i32, y: i32, fg: RGB, bg: RGB, glyph: char) {
+        if let Some(i) = self.index(x, y) {
+            self.cells[i] = Cell { glyph, fg, bg };
+        }
+    }
+
+    fn print(&mut self, x: i32, y: i32, text: &str) {
+        self.print_color(x, y, RGB::named(WHITE), RGB::named(BLACK), text);
+    }
+
+    fn cls(&mut self) {
+        self.cells.fill(Cell::blank());
+    }
+
+    fn dimensions(&self) -> (i32, i32) {
+        (self.width, self.height)
+    }
+
+    /// Writes the changed cells to standard output.
+    fn present(&mut self) -> GameResult<()> {
+        self.flush(&mut io::stdout().lock())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flush_only_sends_changed_cells() {
+        let mut term = TerminalRenderer::new(4, 2);
+        term.print(0, 0, "ab");
+        assert_eq!(term.glyph(1, 0), Some('b'));
+        let mut first = Vec::new();
+        term.flush(&mut first).unwrap();
+        assert!(String::from_utf8_lossy(&first).contains("ab"));
+
+        let mut unchanged = Vec::new();
+        term.flush(&mut unchanged).unwrap();
+        assert!(unchanged.len() < first.len());
+        assert!(!String::from_utf8_lossy(&unchanged).contains('a'));
+
+        term.set(3, 1, RGB::named(WHITE), RGB::named(BLACK), 'z');
+        let mut changed = Vec::new();
+        term.flush(&mut changed).unwrap();
+        assert!(String::from_utf8_lossy(&changed).contains('z'));
+    }
+
+    #[test]
+    fn writes_outside_screen_are_ignored() {
+        let mut term = TerminalRenderer::new(2, 1);
+        term.print(1, 0, "xyz");
+        assert_eq!(term.glyph(1, 0), Some('x'));
+        assert_eq!(term.glyph(2, 0), None);
+    }
+}
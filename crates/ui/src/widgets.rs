@@ -0,0 +1,312 @@
+//! Widgets the screens are built from: panels with an optional frame and
+//! title, labelled bars and scrolling lists. Each draws inside a layout
+//! [`Rect`] and clips its text to it, so screens place widgets by
+//! splitting rectangles rather than by counting cells.
+
+use crate::render::Renderer;
+use bracket_lib::prelude::{BLACK, CYAN, GRAY, RGB, WHITE, YELLOW};
+use common::Rect;
+
+/// Colors the widgets draw in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Theme {
+    pub text: RGB,
+    pub background: RGB,
+    pub border: RGB,
+    pub title: RGB,
+    /// The selected row of a list.
+    pub selected: RGB,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            text: RGB::named(WHITE),
+            background: RGB::named(BLACK),
+            border: RGB::named(GRAY),
+            title: RGB::named(CYAN),
+            selected: RGB::named(YELLOW),
+        }
+    }
+}
+
+impl Theme {
+    /// White on black throughout, for colorblind players.
+    pub fn high_contrast() -> Self {
+        Self {
+            border: RGB::named(WHITE),
+            title: RGB::named(WHITE),
+            selected: RGB::named(YELLOW),
+            ..Self::default()
+        }
+    }
+}
+
+/// `text` cut to the first `width` characters.
+fn clip(text: &str, width: i32) -> String {
+    text.chars().take(width.max(0) as usize).collect()
+}
+
+/// Prints `text` on the first row of `area`, clipped to its width.
+pub fn print_in(ctx: &mut dyn Renderer, area: Rect, fg: RGB, bg: RGB, text: &str) {
+    if area.is_empty() {
+        return;
+    }
+    ctx.print_color(area.x, area.y, fg, bg, &clip(text, area.width));
+}
+
+/// A region of the screen with an optional title and frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Panel<'a> {
+    pub title: Option<&'a str>,
+    pub border: bool,
+}
+
+impl<'a> Panel<'a> {
+    /// A panel titled `title` on its first row.
+    pub fn titled(title: &'a str) -> Self {
+        Self {
+            title: Some(title),
+            border: false,
+        }
+    }
+
+    /// The panel framed, with any title set into the top edge.
+    pub fn bordered(self) -> Self {
+        Self {
+            border: true,
+            ..self
+        }
+    }
+
+    /// Draws the panel over `area` and returns the area left inside it
+    /// for its contents.
+    pub fn draw(&self, ctx: &mut dyn Renderer, area: Rect, theme: &Theme) -> Rect {
+        if self.border {
+            if area.width < 2 || area.height < 2 {
+                return Rect::new(area.x, area.y, 0, 0);
+            }
+            ctx.draw_box(
+                area.x,
+                area.y,
+                area.width - 1,
+                area.height - 1,
+                theme.border,
+                theme.background,
+            );
+            if let Some(title) = self.title {
+                let edge = Rect::new(area.x + 2, area.y, area.width - 4, 1);
+                print_in(ctx, edge, theme.title, theme.background, title);
+            }
+            return area.inset(1);
+        }
+        match self.title {
+            Some(title) => {
+                let (head, body) = area.split_top(1);
+                print_in(ctx, head, theme.text, theme.background, title);
+                body
+            }
+            None => area,
+        }
+    }
+}
+
+/// A labelled gauge such as `HP   [#####-----]`, with optional text
+/// after it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bar<'a> {
+    pub label: &'a str,
+    pub value: i32,
+    pub max: i32,
+    /// Text after the gauge, such as the numbers it shows.
+    pub detail: Option<String>,
+    /// Colors in place of the theme's text colors.
+    pub colors: Option<(RGB, RGB)>,
+}
+
+/// Cells inside the brackets of a [`Bar`].
+pub const BAR_WIDTH: usize = 10;
+
+/// A gauge `[####------]` of [`BAR_WIDTH`] cells filled to `value / max`.
+pub fn bar_string(value: i32, max: i32) -> String {
+    let ratio = if max > 0 {
+        value as f32 / max as f32
+    } else {
+        0.0
+    };
+    let filled = (ratio.clamp(0.0, 1.0) * BAR_WIDTH as f32).round() as usize;
+    format!("[{}{}]", "#".repeat(filled), "-".repeat(BAR_WIDTH - filled))
+}
+
+impl<'a> Bar<'a> {
+    pub fn new(label: &'a str, value: i32, max: i32) -> Self {
+        Self {
+            label,
+            value,
+            max,
+            detail: None,
+            colors: None,
+        }
+    }
+
+    pub fn detail(self, detail: String) -> Self {
+        Self {
+            detail: Some(detail),
+            ..self
+        }
+    }
+
+    pub fn colors(self, fg: RGB, bg: RGB) -> Self {
+        Self {
+            colors: Some((fg, bg)),
+            ..self
+        }
+    }
+
+    /// The bar as one line of text.
+    pub fn text(&self) -> String {
+        let bar = bar_string(self.value, self.max);
+        match &self.detail {
+            Some(detail) => format!("{} {} {}", self.label, bar, detail),
+            None => format!("{} {}", self.label, bar),
+        }
+    }
+
+    /// Draws the bar on the first row of `area`.
+    pub fn draw(&self, ctx: &mut dyn Renderer, area: Rect, theme: &Theme) {
+        let (fg, bg) = self.colors.unwrap_or((theme.text, theme.background));
+        print_in(ctx, area, fg, bg, &self.text());
+    }
+}
+
+/// Rows of text, one per line of the area, scrolled to keep the selected
+/// row in view.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ListView<'a> {
+    pub items: &'a [String],
+    /// Color of each row in place of the theme's text color.
+    pub colors: Option<&'a [RGB]>,
+    pub selected: Option<usize>,
+    /// Leaves a column before each row for the `>` selection marker.
+    pub marker: bool,
+    /// First row shown; `None` scrolls to the selection.
+    pub top: Option<usize>,
+}
+
+impl<'a> ListView<'a> {
+    pub fn new(items: &'a [String]) -> Self {
+        Self {
+            items,
+            colors: None,
+            selected: None,
+            marker: false,
+            top: None,
+        }
+    }
+
+    /// The list with `selected` highlighted behind a `>` marker column.
+    pub fn selected(self, selected: Option<usize>) -> Self {
+        Self {
+            selected,
+            marker: true,
+            ..self
+        }
+    }
+
+    /// The list with each row drawn in its color from `colors`.
+    pub fn colors(self, colors: &'a [RGB]) -> Self {
+        Self {
+            colors: Some(colors),
+            ..self
+        }
+    }
+
+    /// The list scrolled so that `top` is the first row shown.
+    pub fn scrolled_to(self, top: usize) -> Self {
+        Self {
+            top: Some(top),
+            ..self
+        }
+    }
+
+    /// First row shown when `rows` fit.
+    pub fn first_row(&self, rows: usize) -> usize {
+        let top = self.top.unwrap_or_else(|| match self.selected {
+            Some(selected) if selected >= rows => selected + 1 - rows,
+            _ => 0,
+        });
+        top.min(self.items.len())
+    }
+
+    /// Draws as many rows as fit in `area`.
+    pub fn draw(&self, ctx: &mut dyn Renderer, area: Rect, theme: &Theme) {
+        let rows = area.height.max(0) as usize;
+        let top = self.first_row(rows);
+        for (i, item) in self.items[top..].iter().take(rows).enumerate() {
+            let chosen = self.selected == Some(top + i);
+            let fg = match self.colors.and_then(|c| c.get(top + i)) {
+                _ if chosen => theme.selected,
+                Some(&color) => color,
+                None => theme.text,
+            };
+            let text = match (self.marker, chosen) {
+                (false, _) => item.clone(),
+                (true, true) => format!(">{}", item),
+                (true, false) => format!(" {}", item),
+            };
+            print_in(ctx, area.row(i as i32), fg, theme.background, &text);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::TextBuffer;
+
+    #[test]
+    fn panels_frame_and_title_their_area() {
+        let mut buf = TextBuffer::new(12, 5);
+        let theme = Theme::default();
+        let inner = Panel::titled("Bag")
+            .bordered()
+            .draw(&mut buf, Rect::new(0, 0, 8, 4), &theme);
+        assert_eq!(inner, Rect::new(1, 1, 6, 2));
+        assert_eq!(buf.row(0), "┌─Bag──┐");
+        assert_eq!(buf.row(3), "└──────┘");
+        let body = Panel::titled("Quests").draw(&mut buf, Rect::new(0, 4, 4, 1), &theme);
+        assert_eq!(buf.row(4), "Ques");
+        assert!(body.is_empty());
+    }
+
+    #[test]
+    fn bars_fill_and_clip() {
+        let mut buf = TextBuffer::new(30, 2);
+        let theme = Theme::default();
+        let bar = Bar::new("HP", 5, 10).detail("5/10".into());
+        assert_eq!(bar.text(), "HP [#####-----] 5/10");
+        bar.draw(&mut buf, Rect::new(0, 0, 30, 1), &theme);
+        Bar::new("Line", 20, 10).draw(&mut buf, Rect::new(0, 1, 8, 1), &theme);
+        assert_eq!(buf.row(0), "HP [#####-----] 5/10");
+        assert_eq!(buf.row(1), "Line [##");
+        assert_eq!(bar_string(0, 0), "[----------]");
+    }
+
+    #[test]
+    fn lists_scroll_to_the_selection() {
+        let items: Vec<String> = (0..6).map(|i| format!("item {}", i)).collect();
+        let mut buf = TextBuffer::new(10, 3);
+        let theme = Theme::default();
+        let list = ListView::new(&items).selected(Some(4));
+        assert_eq!(list.first_row(3), 2);
+        list.draw(&mut buf, Rect::new(0, 0, 10, 3), &theme);
+        assert_eq!(buf.row(0), " item 2");
+        assert_eq!(buf.row(2), ">item 4");
+        buf.cls();
+        ListView::new(&items)
+            .scrolled_to(5)
+            .draw(&mut buf, Rect::new(0, 0, 10, 3), &theme);
+        assert_eq!(buf.row(0), "item 5");
+        assert_eq!(buf.row(1), "");
+    }
+}
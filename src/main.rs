@@ -1,6 +1,153 @@
+/// Asset directory checked by `--check-assets` when none is given.
+const DEFAULT_ASSET_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/assets");
+
+/// Builds the app for `--host [port] [--lan]` or `--join <addr>`, if given.
+/// The host only takes partners on this machine unless `--lan` is given.
+#[cfg(feature = "net")]
+fn coop_app(args: &[String]) -> Option<Result<game_core::LurhookApp, String>> {
+    let session = match args.first().map(String::as_str) {
+        Some("--host") => {
+            let port = match args.get(1).filter(|a| !a.starts_with("--")) {
+                Some(port) => match port.parse() {
+                    Ok(port) => port,
+                    Err(_) => return Some(Err(format!("invalid port: {}", port))),
+                },
+                None => game_core::DEFAULT_PORT,
+            };
+            let ip = if args.iter().any(|a| a == "--lan") {
+                "0.0.0.0"
+            } else {
+                "127.0.0.1"
+            };
+            game_core::CoopSession::host((ip, port))
+        }
+        Some("--join") => match args.get(1) {
+            Some(addr) => game_core::CoopSession::join(addr.as_str()),
+            None => return Some(Err("--join needs an address".to_string())),
+        },
+        _ => return None,
+    };
+    Some(
+        session
+            .map(game_core::LurhookApp::coop)
+            .map_err(|e| e.to_string()),
+    )
+}
+
+/// Starts the spectator server if `--spectate [port]` was given.
+#[cfg(feature = "spectate")]
+fn spectate(args: &[String], app: game_core::LurhookApp) -> game_core::LurhookApp {
+    let Some(i) = args.iter().position(|a| a == "--spectate") else {
+        return app;
+    };
+    let port = args
+        .get(i + 1)
+        .and_then(|p| p.parse().ok())
+        .unwrap_or(game_core::DEFAULT_SPECTATE_PORT);
+    match game_core::Spectator::bind(("127.0.0.1", port)) {
+        Ok(spectator) => {
+            println!("Spectators can connect to {}", spectator.local_addr());
+            app.with_spectator(spectator)
+        }
+        Err(e) => {
+            eprintln!("Spectator stream disabled: {}", e);
+            app
+        }
+    }
+}
+
+/// Connects Discord Rich Presence when an application id is configured.
+#[cfg(feature = "presence")]
+fn presence(app: game_core::LurhookApp) -> game_core::LurhookApp {
+    let Ok(client_id) = std::env::var(game_core::DISCORD_CLIENT_ID_VAR) else {
+        return app;
+    };
+    match game_core::DiscordPresence::connect(&client_id) {
+        Ok(discord) => app.with_presence(Box::new(discord)),
+        Err(e) => {
+            eprintln!("Discord Rich Presence disabled: {}", e);
+            app
+        }
+    }
+}
+
+/// Reads game controllers alongside the keyboard when a backend opens.
+#[cfg(feature = "gamepad")]
+fn gamepad(app: game_core::LurhookApp) -> game_core::LurhookApp {
+    match game_core::GilrsGamepad::connect() {
+        Ok(pad) => app.with_gamepad(Box::new(pad)),
+        Err(e) => {
+            eprintln!("Gamepad input disabled: {}", e);
+            app
+        }
+    }
+}
+
+/// Plays back the run recorded in `--replay [file]`, if given.
+fn replay_app(args: &[String]) -> Option<Result<game_core::LurhookApp, String>> {
+    let i = args.iter().position(|a| a == "--replay")?;
+    let key = args
+        .get(i + 1)
+        .filter(|a| !a.starts_with("--"))
+        .map_or(game_core::REPLAY_PATH, String::as_str);
+    Some(game_core::LurhookApp::replay(key).map_err(|e| e.to_string()))
+}
+
+/// Loads `quests.json` from `dir`; parsing already rejects bad quests.
+fn check_quests(dir: &str) -> data::validate::AssetCheck {
+    let path = std::path::Path::new(dir).join("quests.json");
+    let (entries, errors) = match quests::load_quests(&path.to_string_lossy()) {
+        Ok(list) => (list.len(), Vec::new()),
+        Err(e) => (0, vec![format!("failed to load: {}", e)]),
+    };
+    data::validate::AssetCheck {
+        file: "quests.json".to_string(),
+        entries,
+        errors,
+    }
+}
+
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("--check-assets") {
+        // Validate data files without launching the game
+        let dir = args.get(1).map_or(DEFAULT_ASSET_DIR, String::as_str);
+        let mut report = data::validate::check_assets(dir);
+        report.checks.push(check_quests(dir));
+        print!("{}", report);
+        std::process::exit(if report.is_ok() { 0 } else { 1 });
+    }
+    #[cfg(feature = "net")]
+    let app = match coop_app(&args) {
+        Some(Ok(app)) => app,
+        Some(Err(e)) => {
+            eprintln!("Game error: {}", e);
+            return;
+        }
+        None => game_core::LurhookApp::new(),
+    };
+    #[cfg(not(feature = "net"))]
+    let app = game_core::LurhookApp::new();
+    let app = match replay_app(&args) {
+        Some(Ok(app)) => app,
+        Some(Err(e)) => {
+            eprintln!("Game error: {}", e);
+            return;
+        }
+        None => app,
+    };
+    #[cfg(feature = "spectate")]
+    let app = spectate(&args, app);
+    #[cfg(feature = "presence")]
+    let app = presence(app);
+    #[cfg(feature = "gamepad")]
+    let app = gamepad(app);
     // Entry point - delegate to game core
-    if let Err(e) = game_core::run() {
+    #[cfg(feature = "tui")]
+    let result = game_core::run_terminal_app(app).map_err(|e| e.to_string());
+    #[cfg(not(feature = "tui"))]
+    let result = game_core::run_app(app).map_err(|e| e.to_string());
+    if let Err(e) = result {
         eprintln!("Game error: {}", e);
     }
 }